@@ -46,6 +46,14 @@ pub fn build_cli() -> Command {
                 .conflicts_with("raw")
                 .global(true),
         )
+        .arg(
+            Arg::new("ndjson")
+                .long("ndjson")
+                .help("Newline-delimited JSON output mode (one JSON value per line)")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["json", "raw"])
+                .global(true),
+        )
         .arg(
             Arg::new("raw")
                 .long("raw")
@@ -80,9 +88,14 @@ pub fn build_cli() -> Command {
         .subcommand(build_txn())
         .subcommand(build_ping())
         .subcommand(build_info())
+        .subcommand(build_stats())
+        .subcommand(build_keys())
         .subcommand(build_flush())
         .subcommand(build_compact())
+        .subcommand(build_reindex())
         .subcommand(build_search())
+        .subcommand(build_export())
+        .subcommand(build_import())
         .subcommand(build_setup())
         .subcommand(build_configure_model())
 }
@@ -105,9 +118,14 @@ pub fn build_repl_cmd() -> Command {
         .subcommand(build_txn())
         .subcommand(build_ping())
         .subcommand(build_info())
+        .subcommand(build_stats())
+        .subcommand(build_keys())
         .subcommand(build_flush())
         .subcommand(build_compact())
+        .subcommand(build_reindex())
         .subcommand(build_search())
+        .subcommand(build_export())
+        .subcommand(build_import())
         .subcommand(build_configure_model())
 }
 
@@ -155,13 +173,21 @@ fn build_kv() -> Command {
                 ),
         )
         .subcommand(
-            Command::new("del").about("Delete one or more keys").arg(
-                Arg::new("keys")
-                    .required(true)
-                    .num_args(1..)
-                    .value_name("KEY")
-                    .help("Key(s) to delete"),
-            ),
+            Command::new("del")
+                .about("Delete one or more keys")
+                .arg(
+                    Arg::new("keys")
+                        .required(true)
+                        .num_args(1..)
+                        .value_name("KEY")
+                        .help("Key(s) to delete"),
+                )
+                .arg(
+                    Arg::new("if")
+                        .long("if")
+                        .value_name("VALUE")
+                        .help("Only delete if the key currently holds this value (single key)"),
+                ),
         )
         .subcommand(
             Command::new("list")
@@ -328,6 +354,7 @@ fn build_event() -> Command {
                 ),
         )
         .subcommand(Command::new("len").about("Get total event count"))
+        .subcommand(Command::new("verify").about("Verify the event hash chain"))
 }
 
 // =========================================================================
@@ -439,6 +466,11 @@ fn build_vector() -> Command {
                     Arg::new("metadata")
                         .long("metadata")
                         .help("Metadata as JSON"),
+                )
+                .arg(
+                    Arg::new("mode")
+                        .long("mode")
+                        .help("If the key exists: replace (default) or error"),
                 ),
         )
         .subcommand(
@@ -471,14 +503,25 @@ fn build_vector() -> Command {
                 )
                 .arg(
                     Arg::new("query")
-                        .required(true)
-                        .help("Query vector as JSON array"),
+                        .required_unless_present("text")
+                        .help("Query vector as JSON array (with --text, the number of results)"),
                 )
                 .arg(Arg::new("k").default_value("10").help("Number of results"))
+                .arg(
+                    Arg::new("text")
+                        .long("text")
+                        .help("Embed this text as the query (requires the embed feature)"),
+                )
+                .arg(
+                    Arg::new("no-metadata")
+                        .long("no-metadata")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Omit each match's metadata"),
+                )
                 .arg(
                     Arg::new("metric")
                         .long("metric")
-                        .help("Distance metric: cosine, euclidean, dotproduct"),
+                        .help("Distance metric: cosine, euclidean, dotproduct, manhattan"),
                 )
                 .arg(
                     Arg::new("filter")
@@ -496,7 +539,10 @@ fn build_vector() -> Command {
                         .long("metric")
                         .default_value("cosine")
                         .help("Distance metric"),
-                ),
+                )
+                .arg(Arg::new("schema").long("schema").help(
+                    "Filterable metadata fields as JSON, e.g. {\"category\":\"string\",\"year\":\"number\"}",
+                )),
         )
         .subcommand(
             Command::new("drop")
@@ -590,11 +636,18 @@ fn build_branch() -> Command {
                         .help("Merge strategy: lww or strict"),
                 ),
         )
+        .subcommand(Command::new("tree").about("Show branch fork lineage as a tree"))
         .subcommand(
             Command::new("export")
                 .about("Export a branch to a bundle file")
                 .arg(Arg::new("branch").required(true).help("Branch name"))
-                .arg(Arg::new("path").required(true).help("Output file path")),
+                .arg(Arg::new("path").required(true).help("Output file path"))
+                .arg(
+                    Arg::new("digest")
+                        .long("digest")
+                        .help("Record SHA-256 section hashes and a digest, checked on import")
+                        .action(clap::ArgAction::SetTrue),
+                ),
         )
         .subcommand(
             Command::new("import")
@@ -681,6 +734,21 @@ fn build_info() -> Command {
     Command::new("info").about("Get database information")
 }
 
+fn build_stats() -> Command {
+    Command::new("stats").about("Summarize entry counts, WAL, and checkpoint state")
+}
+
+fn build_keys() -> Command {
+    Command::new("keys")
+        .about("List keys in the current branch and space")
+        .arg(
+            Arg::new("all")
+                .long("all")
+                .action(clap::ArgAction::SetTrue)
+                .help("List keys of every primitive, not just KV"),
+        )
+}
+
 fn build_flush() -> Command {
     Command::new("flush").about("Flush pending writes to disk")
 }
@@ -689,6 +757,10 @@ fn build_compact() -> Command {
     Command::new("compact").about("Trigger compaction")
 }
 
+fn build_reindex() -> Command {
+    Command::new("reindex").about("Rebuild the keyword search index from stored data")
+}
+
 // =========================================================================
 // Search
 // =========================================================================
@@ -718,6 +790,11 @@ fn build_search() -> Command {
                 .long("mode")
                 .help("Search mode: keyword, hybrid (default: hybrid)"),
         )
+        .arg(
+            Arg::new("typo-tolerance")
+                .long("typo-tolerance")
+                .help("Max edits per keyword term, 0-2 (default: 0)"),
+        )
         .arg(
             Arg::new("expand")
                 .long("expand")
@@ -730,6 +807,51 @@ fn build_search() -> Command {
         )
 }
 
+// =========================================================================
+// Import / Export
+// =========================================================================
+
+fn build_export() -> Command {
+    Command::new("export")
+        .about("Export data as JSON Lines")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("kv")
+                .about("Export key-value pairs in the current branch and space")
+                .arg(
+                    Arg::new("out")
+                        .long("out")
+                        .short('o')
+                        .value_name("FILE")
+                        .help("Output file (default: stdout)"),
+                )
+                .arg(
+                    Arg::new("prefix")
+                        .long("prefix")
+                        .short('p')
+                        .help("Key prefix filter"),
+                ),
+        )
+}
+
+fn build_import() -> Command {
+    Command::new("import")
+        .about("Import data from JSON Lines")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("kv")
+                .about("Import key-value pairs into the current branch and space")
+                .arg(
+                    Arg::new("in")
+                        .long("in")
+                        .short('i')
+                        .value_name("FILE")
+                        .required(true)
+                        .help("Input file produced by `export kv`"),
+                ),
+        )
+}
+
 // =========================================================================
 // Setup
 // =========================================================================
@@ -1,12 +1,18 @@
-//! Output → human/json/raw string formatting.
+//! Output → human/json/ndjson/raw string formatting.
 //!
-//! Three modes:
-//! - **Human** (default on TTY): Redis-style, e.g. `"value"`, `(integer) 42`, `(nil)`
+//! Four modes:
+//! - **Human** (default on TTY): Redis-style, e.g. `"value"`, `(integer) 42`, `(nil)`;
+//!   nested arrays and objects print as plain JSON (`Value::to_plain_json`)
 //! - **JSON** (`--json`): `serde_json::to_string_pretty`
+//! - **NDJSON** (`--ndjson`): One compact JSON value per line; list results
+//!   emit one line per item
 //! - **Raw** (`--raw`): Bare values, no quotes, no type prefixes
 
+use std::collections::{HashMap, HashSet};
+
 use strata_executor::{
-    BranchDiffResult, Error, ForkInfo, MergeInfo, Output, Value, VersionedValue,
+    BranchDiffResult, BranchInfo, Error, ForkInfo, MergeInfo, Output, SearchResultHit, Value,
+    VersionedValue,
 };
 
 /// Output formatting mode.
@@ -14,6 +20,7 @@ use strata_executor::{
 pub enum OutputMode {
     Human,
     Json,
+    Ndjson,
     Raw,
 }
 
@@ -21,6 +28,7 @@ pub enum OutputMode {
 pub fn format_output(output: &Output, mode: OutputMode) -> String {
     match mode {
         OutputMode::Json => format_json(output),
+        OutputMode::Ndjson => format_ndjson(output),
         OutputMode::Raw => format_raw(output),
         OutputMode::Human => format_human(output),
     }
@@ -29,10 +37,12 @@ pub fn format_output(output: &Output, mode: OutputMode) -> String {
 /// Format an error.
 pub fn format_error(err: &Error, mode: OutputMode) -> String {
     match mode {
-        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
-            "error": format!("{}", err)
-        }))
-        .unwrap_or_else(|_| format!("{{\"error\": \"{}\"}}", err)),
+        OutputMode::Json | OutputMode::Ndjson => encode_json(
+            &serde_json::json!({
+                "error": format!("{}", err)
+            }),
+            mode,
+        ),
         OutputMode::Raw => format!("{}", err),
         OutputMode::Human => format!("(error) {}", err),
     }
@@ -62,12 +72,14 @@ fn format_versioned_value(vv: &VersionedValue, mode: OutputMode) -> String {
                 vv.timestamp
             )
         }
-        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
-            "value": vv.value,
-            "version": vv.version,
-            "timestamp": vv.timestamp
-        }))
-        .unwrap(),
+        OutputMode::Json | OutputMode::Ndjson => encode_json(
+            &serde_json::json!({
+                "value": vv.value,
+                "version": vv.version,
+                "timestamp": vv.timestamp
+            }),
+            mode,
+        ),
         OutputMode::Raw => format_value_raw(&vv.value),
     }
 }
@@ -81,6 +93,21 @@ pub fn format_multi_output(outputs: &[Output], mode: OutputMode) -> String {
         .join("\n")
 }
 
+/// Expand a bulk KV output into one output per key.
+///
+/// Lets `kv put/get/del` with several keys print exactly like the
+/// equivalent sequence of single-key commands.
+pub fn split_bulk_output(output: Output) -> Vec<Output> {
+    match output {
+        Output::Versions(vs) => vs.into_iter().map(Output::Version).collect(),
+        Output::MaybeVersionedValues(vals) => {
+            vals.into_iter().map(Output::MaybeVersioned).collect()
+        }
+        Output::Bools(bs) => bs.into_iter().map(Output::Bool).collect(),
+        other => vec![other],
+    }
+}
+
 /// Format multiple versioned outputs.
 pub fn format_multi_versioned_output(
     outputs: &[Output],
@@ -97,13 +124,15 @@ pub fn format_multi_versioned_output(
 /// Format branch fork info.
 pub fn format_fork_info(info: &ForkInfo, mode: OutputMode) -> String {
     match mode {
-        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
-            "source": info.source,
-            "destination": info.destination,
-            "keys_copied": info.keys_copied,
-            "spaces_copied": info.spaces_copied,
-        }))
-        .unwrap(),
+        OutputMode::Json | OutputMode::Ndjson => encode_json(
+            &serde_json::json!({
+                "source": info.source,
+                "destination": info.destination,
+                "keys_copied": info.keys_copied,
+                "spaces_copied": info.spaces_copied,
+            }),
+            mode,
+        ),
         OutputMode::Raw => format!("{}", info.keys_copied),
         OutputMode::Human => format!(
             "Forked \"{}\" -> \"{}\" ({} keys, {} spaces)",
@@ -115,22 +144,24 @@ pub fn format_fork_info(info: &ForkInfo, mode: OutputMode) -> String {
 /// Format branch diff result.
 pub fn format_diff(diff: &BranchDiffResult, mode: OutputMode) -> String {
     match mode {
-        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
-            "branch_a": diff.branch_a,
-            "branch_b": diff.branch_b,
-            "summary": {
-                "total_added": diff.summary.total_added,
-                "total_removed": diff.summary.total_removed,
-                "total_modified": diff.summary.total_modified,
-            },
-            "spaces": diff.spaces.iter().map(|sd| serde_json::json!({
-                "space": sd.space,
-                "added": sd.added.len(),
-                "removed": sd.removed.len(),
-                "modified": sd.modified.len(),
-            })).collect::<Vec<_>>(),
-        }))
-        .unwrap(),
+        OutputMode::Json | OutputMode::Ndjson => encode_json(
+            &serde_json::json!({
+                "branch_a": diff.branch_a,
+                "branch_b": diff.branch_b,
+                "summary": {
+                    "total_added": diff.summary.total_added,
+                    "total_removed": diff.summary.total_removed,
+                    "total_modified": diff.summary.total_modified,
+                },
+                "spaces": diff.spaces.iter().map(|sd| serde_json::json!({
+                    "space": sd.space,
+                    "added": sd.added.len(),
+                    "removed": sd.removed.len(),
+                    "modified": sd.modified.len(),
+                })).collect::<Vec<_>>(),
+            }),
+            mode,
+        ),
         OutputMode::Raw => format!(
             "{}\t{}\t{}",
             diff.summary.total_added, diff.summary.total_removed, diff.summary.total_modified
@@ -167,14 +198,16 @@ pub fn format_diff(diff: &BranchDiffResult, mode: OutputMode) -> String {
 /// Format merge info.
 pub fn format_merge_info(info: &MergeInfo, mode: OutputMode) -> String {
     match mode {
-        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
-            "source": info.source,
-            "target": info.target,
-            "keys_applied": info.keys_applied,
-            "conflicts": info.conflicts.len(),
-            "spaces_merged": info.spaces_merged,
-        }))
-        .unwrap(),
+        OutputMode::Json | OutputMode::Ndjson => encode_json(
+            &serde_json::json!({
+                "source": info.source,
+                "target": info.target,
+                "keys_applied": info.keys_applied,
+                "conflicts": info.conflicts.len(),
+                "spaces_merged": info.spaces_merged,
+            }),
+            mode,
+        ),
         OutputMode::Raw => format!("{}", info.keys_applied),
         OutputMode::Human => {
             let conflict_note = if info.conflicts.is_empty() {
@@ -190,6 +223,189 @@ pub fn format_merge_info(info: &MergeInfo, mode: OutputMode) -> String {
     }
 }
 
+/// Root line under which orphaned branches are listed.
+const ORPHANED_ROOT: &str = "(orphaned)";
+
+/// Format branches as a fork-lineage tree.
+///
+/// Branches without a parent are roots. Branches whose parent has been
+/// deleted, or whose parent chain loops back on itself, are listed under a
+/// final `(orphaned)` root; a loop is cut at its oldest branch. Siblings are
+/// ordered by creation time. JSON modes emit nested
+/// `{name, created_at, children}` objects; NDJSON puts one root tree per line.
+pub fn format_branch_tree(branches: &[BranchInfo], mode: OutputMode) -> String {
+    let names: HashSet<&str> = branches.iter().map(|b| b.id.as_str()).collect();
+    let mut roots = Vec::new();
+    let mut orphans = Vec::new();
+    let mut children: HashMap<&str, Vec<&BranchInfo>> = HashMap::new();
+    for branch in branches {
+        match branch.parent_id.as_ref().map(|p| p.as_str()) {
+            None => roots.push(branch),
+            Some(parent) if names.contains(parent) => {
+                children.entry(parent).or_default().push(branch)
+            }
+            Some(_) => orphans.push(branch),
+        }
+    }
+    let by_creation = |a: &&BranchInfo, b: &&BranchInfo| {
+        (a.created_at, a.id.as_str()).cmp(&(b.created_at, b.id.as_str()))
+    };
+    for siblings in children.values_mut() {
+        siblings.sort_by(by_creation);
+    }
+
+    // Branches on a parent loop are unreachable from every root. Detach the
+    // oldest unreached branch from its parent and list it as an orphan; its
+    // subtree, the rest of the loop included, then hangs below it.
+    let mut reached = HashSet::new();
+    for top in roots.iter().chain(&orphans) {
+        mark_reached(top, &children, &mut reached);
+    }
+    let mut unreached: Vec<&BranchInfo> = branches
+        .iter()
+        .filter(|b| !reached.contains(b.id.as_str()))
+        .collect();
+    unreached.sort_by(by_creation);
+    for branch in unreached {
+        if reached.contains(branch.id.as_str()) {
+            continue;
+        }
+        if let Some(parent) = &branch.parent_id {
+            if let Some(siblings) = children.get_mut(parent.as_str()) {
+                siblings.retain(|s| s.id != branch.id);
+            }
+        }
+        mark_reached(branch, &children, &mut reached);
+        orphans.push(branch);
+    }
+    roots.sort_by(by_creation);
+    orphans.sort_by(by_creation);
+
+    match mode {
+        OutputMode::Json | OutputMode::Ndjson => {
+            let mut trees: Vec<_> = roots
+                .iter()
+                .map(|b| branch_tree_json(b, &children))
+                .collect();
+            if !orphans.is_empty() {
+                trees.push(serde_json::json!({
+                    "name": ORPHANED_ROOT,
+                    "created_at": null,
+                    "children": orphans
+                        .iter()
+                        .map(|b| branch_tree_json(b, &children))
+                        .collect::<Vec<_>>(),
+                }));
+            }
+            if mode == OutputMode::Json {
+                encode_json(&trees, mode)
+            } else {
+                ndjson_lines(&trees)
+            }
+        }
+        OutputMode::Raw => {
+            let mut lines = Vec::new();
+            for root in &roots {
+                branch_tree_raw(root, &children, 0, &mut lines);
+            }
+            if !orphans.is_empty() {
+                lines.push(ORPHANED_ROOT.to_string());
+                for orphan in &orphans {
+                    branch_tree_raw(orphan, &children, 1, &mut lines);
+                }
+            }
+            lines.join("\n")
+        }
+        OutputMode::Human => {
+            if roots.is_empty() && orphans.is_empty() {
+                return "(empty list)".to_string();
+            }
+            let mut lines = Vec::new();
+            for root in &roots {
+                lines.push(format!("{} (created_at: {})", root.id, root.created_at));
+                let kids = children.get(root.id.as_str()).map(Vec::as_slice);
+                branch_tree_human(kids.unwrap_or(&[]), &children, "", &mut lines);
+            }
+            if !orphans.is_empty() {
+                lines.push(ORPHANED_ROOT.to_string());
+                branch_tree_human(&orphans, &children, "", &mut lines);
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+fn mark_reached<'a>(
+    branch: &'a BranchInfo,
+    children: &HashMap<&str, Vec<&'a BranchInfo>>,
+    reached: &mut HashSet<&'a str>,
+) {
+    if !reached.insert(branch.id.as_str()) {
+        return;
+    }
+    for kid in children.get(branch.id.as_str()).into_iter().flatten() {
+        mark_reached(kid, children, reached);
+    }
+}
+
+fn branch_tree_json(
+    branch: &BranchInfo,
+    children: &HashMap<&str, Vec<&BranchInfo>>,
+) -> serde_json::Value {
+    let kids = children
+        .get(branch.id.as_str())
+        .map(|kids| {
+            kids.iter()
+                .map(|kid| branch_tree_json(kid, children))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    serde_json::json!({
+        "name": branch.id,
+        "created_at": branch.created_at,
+        "children": kids,
+    })
+}
+
+fn branch_tree_raw(
+    branch: &BranchInfo,
+    children: &HashMap<&str, Vec<&BranchInfo>>,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    lines.push(format!("{}{}", "  ".repeat(depth), branch.id));
+    for kid in children.get(branch.id.as_str()).into_iter().flatten() {
+        branch_tree_raw(kid, children, depth + 1, lines);
+    }
+}
+
+fn branch_tree_human(
+    kids: &[&BranchInfo],
+    children: &HashMap<&str, Vec<&BranchInfo>>,
+    prefix: &str,
+    lines: &mut Vec<String>,
+) {
+    for (i, kid) in kids.iter().enumerate() {
+        let last = i + 1 == kids.len();
+        let (branch_glyph, indent) = if last {
+            ("`-- ", "    ")
+        } else {
+            ("|-- ", "|   ")
+        };
+        lines.push(format!(
+            "{}{}{} (created_at: {})",
+            prefix, branch_glyph, kid.id, kid.created_at
+        ));
+        let grandkids = children.get(kid.id.as_str()).map(Vec::as_slice);
+        branch_tree_human(
+            grandkids.unwrap_or(&[]),
+            children,
+            &format!("{}{}", prefix, indent),
+            lines,
+        );
+    }
+}
+
 // =========================================================================
 // JSON mode
 // =========================================================================
@@ -198,6 +414,49 @@ fn format_json(output: &Output) -> String {
     serde_json::to_string_pretty(output).unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
 }
 
+/// Encode a JSON value pretty-printed for `--json` or on one line for `--ndjson`.
+fn encode_json<T: serde::Serialize + ?Sized>(value: &T, mode: OutputMode) -> String {
+    let encoded = if mode == OutputMode::Ndjson {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    };
+    encoded.unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+}
+
+// =========================================================================
+// NDJSON mode
+// =========================================================================
+
+/// List results emit one compact JSON value per item so each line parses on
+/// its own; every other output is a single compact line.
+fn format_ndjson(output: &Output) -> String {
+    match output {
+        Output::VersionedValues(items) => ndjson_lines(items),
+        Output::VersionHistory(Some(items)) => ndjson_lines(items),
+        Output::Keys(items) => ndjson_lines(items),
+        Output::MaybeVersionedValues(items) => ndjson_lines(items),
+        Output::Bools(items) => ndjson_lines(items),
+        Output::VectorMatches(items) => ndjson_lines(items),
+        Output::VectorCollectionList(items) => ndjson_lines(items),
+        Output::Versions(items) => ndjson_lines(items),
+        Output::BatchResults(items) => ndjson_lines(items),
+        Output::BranchInfoList(items) => ndjson_lines(items),
+        Output::SearchResults(items) => ndjson_lines(items),
+        Output::SpaceList(items) => ndjson_lines(items),
+        Output::Truncated(inner) => format_ndjson(inner),
+        other => encode_json(other, OutputMode::Ndjson),
+    }
+}
+
+fn ndjson_lines<T: serde::Serialize>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| encode_json(item, OutputMode::Ndjson))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 // =========================================================================
 // Raw mode
 // =========================================================================
@@ -212,6 +471,7 @@ fn format_raw(output: &Output) -> String {
         Output::MaybeVersion(None) => String::new(),
         Output::MaybeVersion(Some(v)) => v.to_string(),
         Output::Version(v) => v.to_string(),
+        Output::EventAppended { sequence, .. } => sequence.to_string(),
         Output::Bool(b) => {
             if *b {
                 "1".to_string()
@@ -232,10 +492,32 @@ fn format_raw(output: &Output) -> String {
             .collect::<Vec<_>>()
             .join("\n"),
         Output::Keys(keys) => keys.join("\n"),
+        Output::MaybeVersionedValues(vals) => vals
+            .iter()
+            .map(|v| {
+                v.as_ref()
+                    .map(|vv| format_value_raw(&vv.value))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Output::Bools(bs) => bs
+            .iter()
+            .map(|b| if *b { "1" } else { "0" })
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::JsonListResult { keys, .. } => keys.join("\n"),
+        Output::JsonListDetailed { docs, .. } => docs
+            .iter()
+            .map(|d| format!("{}\t{}\t{}", d.key, d.version, d.size_bytes))
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::VectorMatches(matches) => matches
             .iter()
-            .map(|m| format!("{}\t{}", m.key, m.score))
+            .map(|m| match &m.metadata {
+                Some(meta) => format!("{}\t{}\t{}", m.key, m.score, metadata_json(meta)),
+                None => format!("{}\t{}", m.key, m.score),
+            })
             .collect::<Vec<_>>()
             .join("\n"),
         Output::VectorData(None) => String::new(),
@@ -250,6 +532,14 @@ fn format_raw(output: &Output) -> String {
             .map(|v| v.to_string())
             .collect::<Vec<_>>()
             .join("\n"),
+        Output::VectorUpserted(r) => {
+            let action = if r.created { "created" } else { "updated" };
+            format!("{}\t{}", action, r.version)
+        }
+        Output::NovelUpsert(r) => match r.distance {
+            Some(d) if !r.inserted => format!("duplicate\t{}\t{}", r.key, d),
+            _ => format!("inserted\t{}", r.key),
+        },
         Output::MaybeBranchInfo(None) => String::new(),
         Output::MaybeBranchInfo(Some(bi)) => bi.info.id.0.clone(),
         Output::BranchInfoList(branches) => branches
@@ -260,6 +550,9 @@ fn format_raw(output: &Output) -> String {
         Output::BranchWithVersion { info, version } => {
             format!("{}\t{}", info.id, version)
         }
+        Output::BranchDeleted(info) => {
+            format!("{}\t{}", info.keys_reclaimed, info.bytes_reclaimed)
+        }
         Output::TxnInfo(None) => String::new(),
         Output::TxnInfo(Some(info)) => info.id.clone(),
         Output::TxnBegun => "OK".to_string(),
@@ -277,11 +570,20 @@ fn format_raw(output: &Output) -> String {
             .map(|h| format!("{}\t{}\t{}", h.entity, h.primitive, h.score))
             .collect::<Vec<_>>()
             .join("\n"),
+        Output::SearchBatchResults(results) => results
+            .iter()
+            .enumerate()
+            .flat_map(|(i, hits)| {
+                hits.iter()
+                    .map(move |h| format!("{}\t{}\t{}\t{}", i, h.entity, h.primitive, h.score))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::SpaceList(spaces) => spaces.join("\n"),
         Output::BranchExported(r) => format!("{}\t{}", r.path, r.entry_count),
         Output::BranchImported(r) => format!("{}\t{}", r.branch_id, r.keys_written),
         Output::BundleValidated(r) => {
-            if r.checksums_valid {
+            if r.checksums_valid && r.digest_valid != Some(false) {
                 "1".to_string()
             } else {
                 "0".to_string()
@@ -307,6 +609,15 @@ fn format_raw(output: &Output) -> String {
                 info.scheduler_queue_depth
             )
         }
+        Output::KeywordIndexRebuilt(stats) => {
+            format!("{}\t{}", stats.documents_indexed, stats.tokens_indexed)
+        }
+        Output::Compacted(info) => format!("{}\t{}", info.segments_removed, info.bytes_reclaimed),
+        Output::Truncated(inner) => format_raw(inner),
+        Output::ChainVerification(cv) => match cv.first_invalid {
+            Some(seq) => format!("0\t{}\t{}", cv.length, seq),
+            None => format!("1\t{}", cv.length),
+        },
         Output::BatchResults(results) => results
             .iter()
             .map(|r| match (&r.version, &r.error) {
@@ -319,6 +630,10 @@ fn format_raw(output: &Output) -> String {
     }
 }
 
+fn hash_hex(hash: &[u8; 32]) -> String {
+    hash.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 fn format_value_raw(v: &Value) -> String {
     match v {
         Value::Null => String::new(),
@@ -356,6 +671,7 @@ fn format_human(output: &Output) -> String {
         Output::MaybeVersion(None) => "(nil)".to_string(),
         Output::MaybeVersion(Some(v)) => format!("(version) {}", v),
         Output::Version(v) => format!("(version) {}", v),
+        Output::EventAppended { sequence, .. } => format!("(version) {}", sequence),
         Output::Bool(b) => format!("(boolean) {}", b),
         Output::Uint(n) => format!("(integer) {}", n),
         Output::VersionedValues(vals) => {
@@ -400,6 +716,31 @@ fn format_human(output: &Output) -> String {
             }
         }
         Output::Keys(keys) => format_string_list(keys),
+        Output::MaybeVersionedValues(vals) => {
+            if vals.is_empty() {
+                "(empty list)".to_string()
+            } else {
+                vals.iter()
+                    .enumerate()
+                    .map(|(i, v)| match v {
+                        Some(vv) => format!("{}) {}", i + 1, format_value_human(&vv.value)),
+                        None => format!("{}) (nil)", i + 1),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        Output::Bools(bs) => {
+            if bs.is_empty() {
+                "(empty list)".to_string()
+            } else {
+                bs.iter()
+                    .enumerate()
+                    .map(|(i, b)| format!("{}) (boolean) {}", i + 1, b))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
         Output::JsonListResult { keys, cursor } => {
             let mut out = format_string_list(keys);
             if let Some(c) = cursor {
@@ -410,6 +751,29 @@ fn format_human(output: &Output) -> String {
             }
             out
         }
+        Output::JsonListDetailed { docs, cursor } => {
+            let mut out = if docs.is_empty() {
+                "(empty list)".to_string()
+            } else {
+                docs.iter()
+                    .enumerate()
+                    .map(|(i, d)| {
+                        format!(
+                            "{}) \"{}\" (version {}, {} bytes)",
+                            i + 1,
+                            d.key,
+                            d.version,
+                            d.size_bytes
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            if let Some(c) = cursor {
+                out.push_str(&format!("\n(cursor) {}", c));
+            }
+            out
+        }
         Output::VectorMatches(matches) => {
             if matches.is_empty() {
                 "(empty list)".to_string()
@@ -417,7 +781,13 @@ fn format_human(output: &Output) -> String {
                 matches
                     .iter()
                     .enumerate()
-                    .map(|(i, m)| format!("{}) \"{}\" (score: {:.3})", i + 1, m.key, m.score))
+                    .map(|(i, m)| {
+                        let line = format!("{}) \"{}\" (score: {:.3})", i + 1, m.key, m.score);
+                        match &m.metadata {
+                            Some(meta) => format!("{} {}", line, metadata_json(meta)),
+                            None => line,
+                        }
+                    })
                     .collect::<Vec<_>>()
                     .join("\n")
             }
@@ -430,7 +800,7 @@ fn format_human(output: &Output) -> String {
                 format!("version: {}", vd.version),
             ];
             if let Some(meta) = &vd.data.metadata {
-                lines.push(format!("metadata: {}", format_value_human(meta)));
+                lines.push(format!("metadata: {}", metadata_json(meta)));
             }
             lines.join("\n")
         }
@@ -455,6 +825,21 @@ fn format_human(output: &Output) -> String {
                     .join("\n")
             }
         }
+        Output::VectorUpserted(r) => {
+            let action = if r.created { "created" } else { "updated" };
+            format!("({}) (version) {}", action, r.version)
+        }
+        Output::NovelUpsert(r) => {
+            if r.inserted {
+                format!("(inserted) \"{}\"", r.key)
+            } else {
+                format!(
+                    "(skipped) near-duplicate of \"{}\" (distance: {:.4})",
+                    r.key,
+                    r.distance.unwrap_or_default()
+                )
+            }
+        }
         Output::Versions(vs) => {
             if vs.is_empty() {
                 "(empty list)".to_string()
@@ -495,6 +880,18 @@ fn format_human(output: &Output) -> String {
         Output::BranchWithVersion { info, version } => {
             format!("Branch \"{}\" created (v{})", info.id, version)
         }
+        Output::BranchDeleted(info) if info.reclaim_pending => {
+            format!(
+                "Branch \"{}\" deleted ({} bytes reclaimed, rest freed once open snapshots are released)",
+                info.branch, info.bytes_reclaimed
+            )
+        }
+        Output::BranchDeleted(info) => {
+            format!(
+                "Branch \"{}\" deleted ({} keys, {} bytes reclaimed)",
+                info.branch, info.keys_reclaimed, info.bytes_reclaimed
+            )
+        }
         Output::TxnInfo(None) => "(nil)".to_string(),
         Output::TxnInfo(Some(info)) => {
             format!(
@@ -512,31 +909,13 @@ fn format_human(output: &Output) -> String {
             )
         }
         Output::Pong { version } => format!("PONG {}", version),
-        Output::SearchResults(hits) => {
-            if hits.is_empty() {
-                "(empty list)".to_string()
-            } else {
-                hits.iter()
-                    .enumerate()
-                    .map(|(i, h)| {
-                        let snippet = h
-                            .snippet
-                            .as_deref()
-                            .map(|s| format!(" - {}", s))
-                            .unwrap_or_default();
-                        format!(
-                            "{}) \"{}\" [{}] (score: {:.3}){}",
-                            i + 1,
-                            h.entity,
-                            h.primitive,
-                            h.score,
-                            snippet
-                        )
-                    })
-                    .collect::<Vec<_>>()
-                    .join("\n")
-            }
-        }
+        Output::SearchResults(hits) => format_search_hits(hits),
+        Output::SearchBatchResults(results) => results
+            .iter()
+            .enumerate()
+            .map(|(i, hits)| format!("query {}:\n{}", i + 1, format_search_hits(hits)))
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::SpaceList(spaces) => format_string_list(spaces),
         Output::BranchExported(r) => {
             format!(
@@ -552,11 +931,16 @@ fn format_human(output: &Output) -> String {
         }
         Output::BundleValidated(r) => {
             format!(
-                "Bundle valid: branch=\"{}\", format_version={}, entries={}, checksums={}",
+                "Bundle valid: branch=\"{}\", format_version={}, entries={}, checksums={}, digest={}",
                 r.branch_id,
                 r.format_version,
                 r.entry_count,
-                if r.checksums_valid { "OK" } else { "FAILED" }
+                if r.checksums_valid { "OK" } else { "FAILED" },
+                match r.digest_valid {
+                    Some(true) => "OK",
+                    Some(false) => "FAILED",
+                    None => "none",
+                }
             )
         }
         Output::TimeRange {
@@ -570,16 +954,47 @@ fn format_human(output: &Output) -> String {
         },
         Output::EmbedStatus(info) => {
             format!(
-                "auto_embed: {}\npending: {}\ntotal_queued: {}\ntotal_embedded: {}\ntotal_failed: {}\nscheduler_queue_depth: {}\nscheduler_active_tasks: {}",
+                "auto_embed: {}\npending: {}\ntotal_queued: {}\ntotal_embedded: {}\ntotal_failed: {}\nscheduler_queue_depth: {}\nscheduler_active_tasks: {}\nscheduler_tasks_rejected: {}",
                 info.auto_embed,
                 info.pending,
                 info.total_queued,
                 info.total_embedded,
                 info.total_failed,
                 info.scheduler_queue_depth,
-                info.scheduler_active_tasks
+                info.scheduler_active_tasks,
+                info.scheduler_tasks_rejected
             )
         }
+        Output::KeywordIndexRebuilt(stats) => format!(
+            "documents_indexed: {}\ntokens_indexed: {}",
+            stats.documents_indexed, stats.tokens_indexed
+        ),
+        Output::Compacted(info) => format!(
+            "segments_removed: {}\nbytes_reclaimed: {}",
+            info.segments_removed, info.bytes_reclaimed
+        ),
+        Output::Truncated(inner) => format!(
+            "{}\n(truncated: more results than the result limit allows)",
+            format_human(inner)
+        ),
+        Output::ChainVerification(cv) => {
+            if cv.is_valid {
+                return format!("(valid) {} events", cv.length);
+            }
+            let mut out = format!(
+                "(invalid) at sequence {}: {}",
+                cv.first_invalid.unwrap_or_default(),
+                cv.error.as_deref().unwrap_or("unknown error")
+            );
+            if let (Some(expected), Some(actual)) = (&cv.expected_hash, &cv.actual_hash) {
+                out.push_str(&format!(
+                    "\nexpected: {}\nactual:   {}",
+                    hash_hex(expected),
+                    hash_hex(actual)
+                ));
+            }
+            out
+        }
         Output::BatchResults(results) => {
             if results.is_empty() {
                 "(empty list)".to_string()
@@ -613,7 +1028,7 @@ fn format_value_human(v: &Value) -> String {
             } else {
                 arr.iter()
                     .enumerate()
-                    .map(|(i, v)| format!("{}) {}", i + 1, format_value_human(v)))
+                    .map(|(i, v)| format!("{}) {}", i + 1, format_nested_human(v)))
                     .collect::<Vec<_>>()
                     .join("\n")
             }
@@ -626,7 +1041,7 @@ fn format_value_human(v: &Value) -> String {
                 entries.sort_by_key(|(k, _): &(&String, &Value)| (*k).clone());
                 entries
                     .iter()
-                    .map(|(k, v)| format!("{}: {}", k, format_value_human(v)))
+                    .map(|(k, v)| format!("{}: {}", k, format_nested_human(v)))
                     .collect::<Vec<_>>()
                     .join("\n")
             }
@@ -634,6 +1049,46 @@ fn format_value_human(v: &Value) -> String {
     }
 }
 
+/// An array element or object field; nested containers print as compact
+/// plain JSON so each element stays on its own line.
+fn format_nested_human(v: &Value) -> String {
+    match v {
+        Value::Array(_) | Value::Object(_) => v.to_plain_json().to_string(),
+        _ => format_value_human(v),
+    }
+}
+
+/// Vector metadata as compact plain JSON, so it stays on the match's line.
+fn metadata_json(meta: &Value) -> String {
+    meta.to_plain_json().to_string()
+}
+
+fn format_search_hits(hits: &[SearchResultHit]) -> String {
+    if hits.is_empty() {
+        "(empty list)".to_string()
+    } else {
+        hits.iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let snippet = h
+                    .snippet
+                    .as_deref()
+                    .map(|s| format!(" - {}", s))
+                    .unwrap_or_default();
+                format!(
+                    "{}) \"{}\" [{}] (score: {:.3}){}",
+                    i + 1,
+                    h.entity,
+                    h.primitive,
+                    h.score,
+                    snippet
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 fn format_string_list(items: &[String]) -> String {
     if items.is_empty() {
         "(empty list)".to_string()
@@ -675,7 +1130,9 @@ fn base64_encode(data: &[u8]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use strata_executor::VersionedValue;
+    use strata_executor::{
+        BranchId, BranchStatus, ChainVerification, NovelUpsertResult, VectorMatch, VersionedValue,
+    };
 
     #[test]
     fn test_format_unit() {
@@ -701,6 +1158,26 @@ mod tests {
         assert_eq!(format_output(&Output::Bool(true), OutputMode::Raw), "1");
     }
 
+    #[test]
+    fn test_split_bulk_output_matches_single_key_format() {
+        let bulk = Output::Bools(vec![true, false]);
+        assert_eq!(
+            format_multi_output(&split_bulk_output(bulk), OutputMode::Raw),
+            "1\n0"
+        );
+
+        let vv = VersionedValue {
+            value: Value::Int(7),
+            version: 3,
+            timestamp: 0,
+        };
+        let bulk = Output::MaybeVersionedValues(vec![Some(vv), None]);
+        assert_eq!(
+            format_multi_output(&split_bulk_output(bulk), OutputMode::Human),
+            "(integer) 7\n(nil)"
+        );
+    }
+
     #[test]
     fn test_format_nil() {
         assert_eq!(
@@ -713,6 +1190,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_chain_verification() {
+        let valid = Output::ChainVerification(ChainVerification::valid(3));
+        assert_eq!(format_output(&valid, OutputMode::Human), "(valid) 3 events");
+        assert_eq!(format_output(&valid, OutputMode::Raw), "1\t3");
+
+        let broken = Output::ChainVerification(ChainVerification::hash_mismatch(
+            3,
+            1,
+            "event 1 does not match its hash",
+            [0xab; 32],
+            [0x01; 32],
+        ));
+        let human = format_output(&broken, OutputMode::Human);
+        assert!(human.starts_with("(invalid) at sequence 1: event 1 does not match its hash"));
+        assert!(human.contains(&"ab".repeat(32)));
+        assert_eq!(format_output(&broken, OutputMode::Raw), "0\t3\t1");
+    }
+
     #[test]
     fn test_format_versioned_value() {
         let vv = VersionedValue {
@@ -726,6 +1222,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_nested_value_as_plain_json() {
+        let doc = Value::Object(HashMap::from([
+            ("name".to_string(), Value::String("Alice".into())),
+            (
+                "tags".to_string(),
+                Value::Array(vec![Value::Int(1), Value::Bytes(b"foo".to_vec())]),
+            ),
+        ]));
+        assert_eq!(
+            format_output(&Output::Maybe(Some(doc)), OutputMode::Human),
+            "name: \"Alice\"\ntags: [1,\"Zm9v\"]"
+        );
+    }
+
     #[test]
     fn test_format_keys() {
         let keys = vec!["key1".to_string(), "key2".to_string()];
@@ -746,4 +1257,183 @@ mod tests {
         };
         assert_eq!(format_output(&pong, OutputMode::Human), "PONG 0.5.2");
     }
+
+    #[test]
+    fn test_format_vector_matches_with_metadata() {
+        let matches = Output::VectorMatches(vec![
+            VectorMatch {
+                key: "a".to_string(),
+                score: 0.5,
+                metadata: Some(Value::Object(
+                    [("title".to_string(), Value::String("doc".into()))]
+                        .into_iter()
+                        .collect(),
+                )),
+                embedding: None,
+            },
+            VectorMatch {
+                key: "b".to_string(),
+                score: 0.25,
+                metadata: None,
+                embedding: None,
+            },
+        ]);
+        assert_eq!(
+            format_output(&matches, OutputMode::Human),
+            "1) \"a\" (score: 0.500) {\"title\":\"doc\"}\n2) \"b\" (score: 0.250)"
+        );
+        assert_eq!(
+            format_output(&matches, OutputMode::Raw),
+            "a\t0.5\t{\"title\":\"doc\"}\nb\t0.25"
+        );
+    }
+
+    #[test]
+    fn test_format_novel_upsert() {
+        let inserted = Output::NovelUpsert(NovelUpsertResult {
+            inserted: true,
+            key: "a".to_string(),
+            version: Some(3),
+            distance: None,
+        });
+        let skipped = Output::NovelUpsert(NovelUpsertResult {
+            inserted: false,
+            key: "a".to_string(),
+            version: None,
+            distance: Some(0.0125),
+        });
+        assert_eq!(
+            format_output(&inserted, OutputMode::Human),
+            "(inserted) \"a\""
+        );
+        assert_eq!(
+            format_output(&skipped, OutputMode::Human),
+            "(skipped) near-duplicate of \"a\" (distance: 0.0125)"
+        );
+        assert_eq!(format_output(&inserted, OutputMode::Raw), "inserted\ta");
+        assert_eq!(
+            format_output(&skipped, OutputMode::Raw),
+            "duplicate\ta\t0.0125"
+        );
+    }
+
+    #[test]
+    fn test_format_ndjson_lists_one_item_per_line() {
+        let keys = vec!["key1".to_string(), "key2".to_string()];
+        assert_eq!(
+            format_output(&Output::Keys(keys), OutputMode::Ndjson),
+            "\"key1\"\n\"key2\""
+        );
+        assert_eq!(format_output(&Output::Keys(vec![]), OutputMode::Ndjson), "");
+
+        let matches = Output::VectorMatches(vec![
+            VectorMatch {
+                key: "a".to_string(),
+                score: 0.5,
+                metadata: Some(Value::String("multi\nline".into())),
+                embedding: None,
+            },
+            VectorMatch {
+                key: "b".to_string(),
+                score: 0.25,
+                metadata: None,
+                embedding: None,
+            },
+        ]);
+        let formatted = format_output(&matches, OutputMode::Ndjson);
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, key) in lines.iter().zip(["a", "b"]) {
+            let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(parsed["key"], key);
+        }
+    }
+
+    #[test]
+    fn test_format_ndjson_single_line() {
+        let formatted = format_output(&Output::Version(3), OutputMode::Ndjson);
+        assert!(!formatted.contains('\n'));
+        serde_json::from_str::<serde_json::Value>(&formatted).unwrap();
+
+        let err = Error::InvalidInput {
+            reason: "bad".into(),
+        };
+        let formatted = format_error(&err, OutputMode::Ndjson);
+        assert!(!formatted.contains('\n'));
+        let parsed: serde_json::Value = serde_json::from_str(&formatted).unwrap();
+        assert!(parsed["error"].as_str().unwrap().contains("bad"));
+    }
+
+    fn branch(name: &str, created_at: u64, parent: Option<&str>) -> BranchInfo {
+        BranchInfo {
+            id: BranchId::from(name),
+            status: BranchStatus::Active,
+            created_at,
+            updated_at: created_at,
+            parent_id: parent.map(BranchId::from),
+        }
+    }
+
+    #[test]
+    fn test_format_branch_tree() {
+        let branches = vec![
+            branch("exp-b", 4, Some("feature")),
+            branch("default", 1, None),
+            branch("feature", 2, Some("default")),
+            branch("exp-a", 3, Some("feature")),
+            branch("hotfix", 5, Some("default")),
+            branch("orphan", 6, Some("deleted")),
+        ];
+
+        assert_eq!(
+            format_branch_tree(&branches, OutputMode::Human),
+            "default (created_at: 1)\n\
+             |-- feature (created_at: 2)\n\
+             |   |-- exp-a (created_at: 3)\n\
+             |   `-- exp-b (created_at: 4)\n\
+             `-- hotfix (created_at: 5)\n\
+             (orphaned)\n\
+             `-- orphan (created_at: 6)"
+        );
+        assert_eq!(
+            format_branch_tree(&branches, OutputMode::Raw),
+            "default\n  feature\n    exp-a\n    exp-b\n  hotfix\n(orphaned)\n  orphan"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_branch_tree(&branches, OutputMode::Json)).unwrap();
+        assert_eq!(json[0]["name"], "default");
+        assert_eq!(json[0]["children"][0]["name"], "feature");
+        assert_eq!(json[0]["children"][0]["children"][1]["name"], "exp-b");
+        assert_eq!(json[0]["children"][1]["created_at"], 5);
+        assert_eq!(json[1]["name"], "(orphaned)");
+        assert_eq!(json[1]["children"][0]["name"], "orphan");
+
+        let ndjson = format_branch_tree(&branches, OutputMode::Ndjson);
+        assert_eq!(ndjson.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_format_branch_tree_parent_cycle() {
+        let branches = vec![
+            branch("default", 1, None),
+            branch("loop-b", 3, Some("loop-a")),
+            branch("loop-a", 2, Some("loop-b")),
+            branch("loop-c", 4, Some("loop-b")),
+            branch("self", 5, Some("self")),
+        ];
+
+        assert_eq!(
+            format_branch_tree(&branches, OutputMode::Human),
+            "default (created_at: 1)\n\
+             (orphaned)\n\
+             |-- loop-a (created_at: 2)\n\
+             |   `-- loop-b (created_at: 3)\n\
+             |       `-- loop-c (created_at: 4)\n\
+             `-- self (created_at: 5)"
+        );
+
+        let raw = format_branch_tree(&branches, OutputMode::Raw);
+        assert_eq!(raw.lines().count(), branches.len() + 1);
+    }
 }
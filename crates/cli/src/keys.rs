@@ -0,0 +1,65 @@
+//! `keys --all`: every key of the current branch and space, across primitives.
+//!
+//! Backed by `Strata::scan_all`, so the listing is one consistent snapshot,
+//! grouped by primitive and sorted by key within each group.
+
+use serde::Serialize;
+
+use crate::format::{format_error, OutputMode};
+use crate::state::SessionState;
+
+/// One listed key.
+#[derive(Serialize)]
+struct KeyEntry {
+    primitive: &'static str,
+    key: String,
+    version: u64,
+}
+
+/// List every key and print it. Returns true on success.
+pub fn run_keys_all(state: &mut SessionState, mode: OutputMode) -> bool {
+    match state.scan_all_keys() {
+        Ok(keys) => {
+            let entries: Vec<KeyEntry> = keys
+                .into_iter()
+                .map(|(primitive, key, version)| KeyEntry {
+                    primitive: primitive.id(),
+                    key,
+                    version,
+                })
+                .collect();
+            let formatted = format_keys(&entries, mode);
+            if !formatted.is_empty() {
+                println!("{}", formatted);
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", format_error(&e, mode));
+            false
+        }
+    }
+}
+
+fn format_keys(entries: &[KeyEntry], mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(entries)
+            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e)),
+        OutputMode::Ndjson => entries
+            .iter()
+            .filter_map(|entry| serde_json::to_string(entry).ok())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Raw => entries
+            .iter()
+            .map(|entry| format!("{}\t{}\t{}", entry.primitive, entry.key, entry.version))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Human if entries.is_empty() => "(empty list)".to_string(),
+        OutputMode::Human => entries
+            .iter()
+            .map(|entry| format!("{} {} (v{})", entry.primitive, entry.key, entry.version))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
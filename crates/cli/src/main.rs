@@ -7,20 +7,24 @@
 
 mod commands;
 mod format;
+mod keys;
 mod parse;
 mod repl;
 mod state;
+mod stats;
+mod transfer;
 mod value;
 
 use std::io::IsTerminal;
 use std::process;
 
-use strata_executor::{AccessMode, Command, OpenOptions, Output, Strata};
+use strata_executor::{AccessMode, BatchKvEntry, Command, OpenOptions, Output, Strata};
 
 use commands::build_cli;
 use format::{
-    format_diff, format_error, format_fork_info, format_merge_info, format_multi_output,
-    format_multi_versioned_output, format_output, format_versioned_output, OutputMode,
+    format_branch_tree, format_diff, format_error, format_fork_info, format_merge_info,
+    format_multi_output, format_multi_versioned_output, format_output, format_versioned_output,
+    split_bulk_output, OutputMode,
 };
 use parse::{matches_to_action, BranchOp, CliAction, Primitive};
 use state::SessionState;
@@ -38,6 +42,8 @@ fn main() {
     // Determine output mode
     let output_mode = if matches.get_flag("json") {
         OutputMode::Json
+    } else if matches.get_flag("ndjson") {
+        OutputMode::Ndjson
     } else if matches.get_flag("raw") {
         OutputMode::Raw
     } else {
@@ -167,6 +173,16 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     1
                 }
             },
+            BranchOp::Tree => match state.list_branch_infos() {
+                Ok(branches) => {
+                    println!("{}", format_branch_tree(&branches, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
         },
         Ok(CliAction::Meta(_)) => {
             eprintln!("(error) Meta-commands are only available in REPL mode");
@@ -177,21 +193,21 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             space,
             pairs,
         }) => {
-            let mut outputs = Vec::new();
-            for (key, value) in pairs {
-                match state.execute(Command::KvPut {
-                    branch: branch.clone(),
-                    space: space.clone(),
-                    key,
-                    value,
-                }) {
-                    Ok(output) => outputs.push(output),
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        return 1;
-                    }
+            let entries = pairs
+                .into_iter()
+                .map(|(key, value)| BatchKvEntry { key, value })
+                .collect();
+            let outputs = match state.execute(Command::KvPutMany {
+                branch,
+                space,
+                entries,
+            }) {
+                Ok(output) => split_bulk_output(output),
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    return 1;
                 }
-            }
+            };
             let formatted = format_multi_output(&outputs, mode);
             if !formatted.is_empty() {
                 println!("{}", formatted);
@@ -204,21 +220,17 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             keys,
             with_version,
         }) => {
-            let mut outputs = Vec::new();
-            for key in keys {
-                match state.execute(Command::KvGet {
-                    branch: branch.clone(),
-                    space: space.clone(),
-                    key,
-                    as_of: None,
-                }) {
-                    Ok(output) => outputs.push(output),
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        return 1;
-                    }
+            let outputs = match state.execute(Command::KvGetMany {
+                branch,
+                space,
+                keys,
+            }) {
+                Ok(output) => split_bulk_output(output),
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    return 1;
                 }
-            }
+            };
             let formatted = format_multi_versioned_output(&outputs, mode, with_version);
             if !formatted.is_empty() {
                 println!("{}", formatted);
@@ -230,20 +242,17 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             space,
             keys,
         }) => {
-            let mut outputs = Vec::new();
-            for key in keys {
-                match state.execute(Command::KvDelete {
-                    branch: branch.clone(),
-                    space: space.clone(),
-                    key,
-                }) {
-                    Ok(output) => outputs.push(output),
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        return 1;
-                    }
+            let outputs = match state.execute(Command::KvDeleteMany {
+                branch,
+                space,
+                keys,
+            }) {
+                Ok(output) => split_bulk_output(output),
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    return 1;
                 }
-            }
+            };
             let formatted = format_multi_output(&outputs, mode);
             if !formatted.is_empty() {
                 println!("{}", formatted);
@@ -261,14 +270,18 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
 
             loop {
                 let output = match primitive {
-                    Primitive::Kv => state.execute(Command::KvList {
-                        branch: branch.clone(),
-                        space: space.clone(),
-                        prefix: prefix.clone(),
-                        cursor: cursor.clone(),
-                        limit: Some(1000),
-                        as_of: None,
-                    }),
+                    Primitive::Kv => {
+                        match state.list_kv_keys(branch.clone(), space.clone(), prefix.clone()) {
+                            Ok(keys) => {
+                                all_keys = keys;
+                                break;
+                            }
+                            Err(e) => {
+                                eprintln!("{}", format_error(&e, mode));
+                                return 1;
+                            }
+                        }
+                    }
                     Primitive::Json => state.execute(Command::JsonList {
                         branch: branch.clone(),
                         space: space.clone(),
@@ -276,6 +289,7 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                         cursor: cursor.clone(),
                         limit: 1000,
                         as_of: None,
+                        include_metadata: false,
                     }),
                     Primitive::State => {
                         // State list doesn't have pagination, just execute once
@@ -301,10 +315,6 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                 };
 
                 match output {
-                    Ok(Output::Keys(keys)) => {
-                        all_keys.extend(keys);
-                        break;
-                    }
                     Ok(Output::JsonListResult { keys, cursor: next }) => {
                         all_keys.extend(keys);
                         if next.is_none() {
@@ -342,6 +352,55 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                 1
             }
         },
+        Ok(CliAction::VectorSearchText { text, command }) => {
+            match state.vector_search_text(&text, command) {
+                Ok(output) => {
+                    println!("{}", format_output(&output, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            }
+        }
+        Ok(CliAction::Stats) => {
+            if stats::run_stats(state, mode) {
+                0
+            } else {
+                1
+            }
+        }
+        Ok(CliAction::KeysAll) => {
+            if keys::run_keys_all(state, mode) {
+                0
+            } else {
+                1
+            }
+        }
+        Ok(CliAction::KvExport {
+            branch,
+            space,
+            prefix,
+            out,
+        }) => {
+            if transfer::run_kv_export(state, branch, space, prefix, out.as_deref(), mode) {
+                0
+            } else {
+                1
+            }
+        }
+        Ok(CliAction::KvImport {
+            branch,
+            space,
+            input,
+        }) => {
+            if transfer::run_kv_import(state, branch, space, &input, mode) {
+                0
+            } else {
+                1
+            }
+        }
         Err(e) => {
             eprintln!("(error) {}", e);
             1
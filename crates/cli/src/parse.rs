@@ -6,13 +6,14 @@
 //! - REPL meta-commands → `CliAction::Meta`
 //! - Multi-key operations → `CliAction::MultiPut/MultiGet/MultiDel`
 //! - Pagination → `CliAction::ListAll`
+//! - Keys across primitives → `CliAction::KeysAll`
 
 use std::io::Read;
 
 use clap::ArgMatches;
 use strata_executor::{
     BatchVectorEntry, BranchId, Command, DistanceMetric, MergeStrategy, MetadataFilter,
-    SearchQuery, TimeRangeInput, TxnOptions, Value,
+    SearchQuery, TimeRangeInput, TxnOptions, UpsertMode, Value,
 };
 
 use crate::state::SessionState;
@@ -58,6 +59,25 @@ pub enum CliAction {
         command: Command,
         with_version: bool,
     },
+    /// Export KV pairs as JSON Lines to a file or stdout.
+    KvExport {
+        branch: Option<BranchId>,
+        space: Option<String>,
+        prefix: Option<String>,
+        out: Option<String>,
+    },
+    /// Import KV pairs from a JSON Lines file.
+    KvImport {
+        branch: Option<BranchId>,
+        space: Option<String>,
+        input: String,
+    },
+    /// Vector search whose query vector is embedded from `text` at run time.
+    VectorSearchText { text: String, command: Command },
+    /// Summarize the database.
+    Stats,
+    /// List every key of the current branch and space, across primitives.
+    KeysAll,
 }
 
 /// Primitive type for ListAll pagination.
@@ -81,6 +101,7 @@ pub enum BranchOp {
         source: String,
         strategy: MergeStrategy,
     },
+    Tree,
 }
 
 /// REPL meta-commands.
@@ -94,6 +115,9 @@ pub enum MetaCommand {
     },
     Quit,
     Clear,
+    Begin,
+    Commit,
+    Abort,
 }
 
 /// Check for REPL meta-commands before delegating to clap.
@@ -116,6 +140,9 @@ pub fn check_meta_command(line: &str) -> Option<MetaCommand> {
             let space = parts.next().map(|s| s.trim().to_string());
             Some(MetaCommand::Use { branch, space })
         }
+        ".begin" => Some(MetaCommand::Begin),
+        ".commit" => Some(MetaCommand::Commit),
+        ".abort" | ".rollback" => Some(MetaCommand::Abort),
         _ => None,
     }
 }
@@ -140,9 +167,14 @@ pub fn matches_to_action(matches: &ArgMatches, state: &SessionState) -> Result<C
         "txn" => parse_txn(sub_matches),
         "ping" => Ok(CliAction::Execute(Command::Ping)),
         "info" => Ok(CliAction::Execute(Command::Info)),
+        "stats" => Ok(CliAction::Stats),
+        "keys" => parse_keys(sub_matches, state),
         "flush" => Ok(CliAction::Execute(Command::Flush)),
         "compact" => Ok(CliAction::Execute(Command::Compact)),
+        "reindex" => Ok(CliAction::Execute(Command::RebuildKeywordIndex)),
         "search" => parse_search(sub_matches, state),
+        "export" => parse_export(sub_matches, state),
+        "import" => parse_import(sub_matches, state),
         "configure-model" => parse_configure_model(sub_matches),
         other => Err(format!("Unknown command: {}", other)),
     }
@@ -307,7 +339,17 @@ fn parse_kv(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, Str
         "del" => {
             let keys: Vec<String> = m.get_many::<String>("keys").unwrap().cloned().collect();
 
-            if keys.len() == 1 {
+            if let Some(raw) = m.get_one::<String>("if") {
+                if keys.len() != 1 {
+                    return Err("--if requires exactly one key".to_string());
+                }
+                Ok(CliAction::Execute(Command::KvDeleteIfEquals {
+                    branch: branch(state),
+                    space: space(state),
+                    key: keys[0].clone(),
+                    expected: parse_value(raw),
+                }))
+            } else if keys.len() == 1 {
                 Ok(CliAction::Execute(Command::KvDelete {
                     branch: branch(state),
                     space: space(state),
@@ -446,6 +488,7 @@ fn parse_json(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, S
                     cursor,
                     limit,
                     as_of: None,
+                    include_metadata: false,
                 }))
             }
         }
@@ -524,6 +567,10 @@ fn parse_event(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
             branch: branch(state),
             space: space(state),
         })),
+        "verify" => Ok(CliAction::Execute(Command::EventVerifyChain {
+            branch: branch(state),
+            space: space(state),
+        })),
         other => Err(format!("Unknown event subcommand: {}", other)),
     }
 }
@@ -650,13 +697,25 @@ fn parse_state(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
 // Vector
 // =========================================================================
 
+fn parse_upsert_mode(s: &str) -> Result<UpsertMode, String> {
+    match s.to_lowercase().as_str() {
+        "replace" => Ok(UpsertMode::Replace),
+        "error" => Ok(UpsertMode::Error),
+        other => Err(format!(
+            "Unknown upsert mode: {}. Use replace or error",
+            other
+        )),
+    }
+}
+
 fn parse_metric(s: &str) -> Result<DistanceMetric, String> {
     match s.to_lowercase().as_str() {
         "cosine" => Ok(DistanceMetric::Cosine),
         "euclidean" => Ok(DistanceMetric::Euclidean),
         "dotproduct" | "dot_product" | "dot" => Ok(DistanceMetric::DotProduct),
+        "manhattan" | "l1" => Ok(DistanceMetric::Manhattan),
         other => Err(format!(
-            "Unknown metric: {}. Use cosine, euclidean, or dotproduct",
+            "Unknown metric: {}. Use cosine, euclidean, dotproduct, or manhattan",
             other
         )),
     }
@@ -673,6 +732,10 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 .get_one::<String>("metadata")
                 .map(|s| parse_json_value(s))
                 .transpose()?;
+            let mode = m
+                .get_one::<String>("mode")
+                .map(|s| parse_upsert_mode(s))
+                .transpose()?;
             Ok(CliAction::Execute(Command::VectorUpsert {
                 branch: branch(state),
                 space: space(state),
@@ -680,6 +743,7 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 key,
                 vector,
                 metadata,
+                mode,
             }))
         }
         "get" => {
@@ -705,12 +769,29 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
         }
         "search" => {
             let collection = m.get_one::<String>("collection").unwrap().clone();
-            let query = parse_vector(m.get_one::<String>("query").unwrap())?;
-            let k = m
-                .get_one::<String>("k")
-                .unwrap()
-                .parse::<u64>()
-                .map_err(|e| format!("Invalid k: {}", e))?;
+            let text = m.get_one::<String>("text").cloned();
+            // With --text there is no query vector, so the first positional
+            // after the collection is k.
+            let (query, k) = match &text {
+                Some(_) => {
+                    if m.contains_id("query")
+                        && m.value_source("k") == Some(clap::parser::ValueSource::CommandLine)
+                    {
+                        return Err("A query vector cannot be combined with --text".to_string());
+                    }
+                    (
+                        Vec::new(),
+                        m.get_one::<String>("query")
+                            .or(m.get_one::<String>("k"))
+                            .unwrap(),
+                    )
+                }
+                None => (
+                    parse_vector(m.get_one::<String>("query").unwrap())?,
+                    m.get_one::<String>("k").unwrap(),
+                ),
+            };
+            let k = k.parse::<u64>().map_err(|e| format!("Invalid k: {}", e))?;
             let metric = m
                 .get_one::<String>("metric")
                 .map(|s| parse_metric(s))
@@ -721,7 +802,7 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                     serde_json::from_str(s).map_err(|e| format!("Invalid filter JSON: {}", e))
                 })
                 .transpose()?;
-            Ok(CliAction::Execute(Command::VectorSearch {
+            let command = Command::VectorSearch {
                 branch: branch(state),
                 space: space(state),
                 collection,
@@ -730,7 +811,14 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 filter,
                 metric,
                 as_of: None,
-            }))
+                overfetch_factor: None,
+                include_metadata: m.get_flag("no-metadata").then_some(false),
+                include_vector: false,
+            };
+            Ok(match text {
+                Some(text) => CliAction::VectorSearchText { text, command },
+                None => CliAction::Execute(command),
+            })
         }
         "create" => {
             let collection = m.get_one::<String>("name").unwrap().clone();
@@ -740,12 +828,17 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 .parse::<u64>()
                 .map_err(|e| format!("Invalid dimension: {}", e))?;
             let metric = parse_metric(m.get_one::<String>("metric").unwrap())?;
+            let metadata_schema = m
+                .get_one::<String>("schema")
+                .map(|s| serde_json::from_str(s).map_err(|e| format!("Invalid schema JSON: {}", e)))
+                .transpose()?;
             Ok(CliAction::Execute(Command::VectorCreateCollection {
                 branch: branch(state),
                 space: space(state),
                 collection,
                 dimension,
                 metric,
+                metadata_schema,
             }))
         }
         "drop" => {
@@ -845,12 +938,14 @@ fn parse_branch(matches: &ArgMatches, _state: &SessionState) -> Result<CliAction
             };
             Ok(CliAction::BranchOp(BranchOp::Merge { source, strategy }))
         }
+        "tree" => Ok(CliAction::BranchOp(BranchOp::Tree)),
         "export" => {
             let branch_id = m.get_one::<String>("branch").unwrap().clone();
             let path = m.get_one::<String>("path").unwrap().clone();
             Ok(CliAction::Execute(Command::BranchExport {
                 branch_id,
                 path,
+                digest: m.get_flag("digest"),
             }))
         }
         "import" => {
@@ -923,6 +1018,18 @@ fn parse_txn(matches: &ArgMatches) -> Result<CliAction, String> {
     }
 }
 
+fn parse_keys(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
+    if matches.get_flag("all") {
+        return Ok(CliAction::KeysAll);
+    }
+    Ok(CliAction::ListAll {
+        branch: branch(state),
+        space: space(state),
+        prefix: None,
+        primitive: Primitive::Kv,
+    })
+}
+
 // =========================================================================
 // Search
 // =========================================================================
@@ -969,6 +1076,11 @@ fn parse_search(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
         (None, None) => None,
     };
 
+    let typo_tolerance = matches
+        .get_one::<String>("typo-tolerance")
+        .map(|s| s.parse::<u8>())
+        .transpose()
+        .map_err(|e| format!("Invalid typo-tolerance: {}", e))?;
     let mode = matches.get_one::<String>("mode").cloned();
     let expand = matches
         .get_one::<String>("expand")
@@ -985,9 +1097,40 @@ fn parse_search(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
             k,
             primitives,
             time_range,
+            as_of: None,
+            typo_tolerance,
             mode,
             expand,
             rerank,
         },
     }))
 }
+
+// =========================================================================
+// Import / Export
+// =========================================================================
+
+fn parse_export(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
+    let (sub, m) = matches.subcommand().ok_or("No export subcommand")?;
+    match sub {
+        "kv" => Ok(CliAction::KvExport {
+            branch: branch(state),
+            space: space(state),
+            prefix: m.get_one::<String>("prefix").cloned(),
+            out: m.get_one::<String>("out").cloned(),
+        }),
+        other => Err(format!("Unknown export subcommand: {}", other)),
+    }
+}
+
+fn parse_import(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
+    let (sub, m) = matches.subcommand().ok_or("No import subcommand")?;
+    match sub {
+        "kv" => Ok(CliAction::KvImport {
+            branch: branch(state),
+            space: space(state),
+            input: m.get_one::<String>("in").unwrap().clone(),
+        }),
+        other => Err(format!("Unknown import subcommand: {}", other)),
+    }
+}
@@ -12,17 +12,21 @@ use rustyline::hint::Hinter;
 use rustyline::validate::Validator;
 use rustyline::{CompletionType, Config, Context, Editor, Helper};
 
-use strata_executor::{Command, Output};
+use strata_executor::{BatchKvEntry, Command, Output, Result};
 
 use crate::commands::build_repl_cmd;
 use crate::format::{
-    format_diff, format_error, format_fork_info, format_merge_info, format_multi_output,
-    format_multi_versioned_output, format_output, format_versioned_output, OutputMode,
+    format_branch_tree, format_diff, format_error, format_fork_info, format_merge_info,
+    format_multi_output, format_multi_versioned_output, format_output, format_versioned_output,
+    split_bulk_output, OutputMode,
 };
+use crate::keys::run_keys_all;
 use crate::parse::{
     check_meta_command, matches_to_action, BranchOp, CliAction, MetaCommand, Primitive,
 };
 use crate::state::SessionState;
+use crate::stats::run_stats;
+use crate::transfer::{run_kv_export, run_kv_import};
 
 /// Run the interactive REPL.
 pub fn run_repl(state: &mut SessionState, mode: OutputMode) {
@@ -75,6 +79,15 @@ pub fn run_repl(state: &mut SessionState, mode: OutputMode) {
                                 eprintln!("{}", format_error(&e, mode));
                             }
                         },
+                        MetaCommand::Begin => {
+                            print_txn_result(state.begin_transaction(), mode);
+                        }
+                        MetaCommand::Commit => {
+                            print_txn_result(state.commit_transaction(), mode);
+                        }
+                        MetaCommand::Abort => {
+                            print_txn_result(state.abort_transaction(), mode);
+                        }
                     }
                     continue;
                 }
@@ -120,12 +133,28 @@ pub fn run_repl(state: &mut SessionState, mode: OutputMode) {
         }
     }
 
+    // Never leave a transaction dangling on exit
+    if state.in_transaction() {
+        match state.abort_transaction() {
+            Ok(_) => eprintln!("(transaction aborted)"),
+            Err(e) => eprintln!("{}", format_error(&e, mode)),
+        }
+    }
+
     // Save history
     if let Some(ref path) = history_path {
         let _ = rl.save_history(path);
     }
 }
 
+/// Print the outcome of a `.begin`/`.commit`/`.abort` meta-command.
+fn print_txn_result(result: Result<Output>, mode: OutputMode) {
+    match result {
+        Ok(output) => println!("{}", format_output(&output, mode)),
+        Err(e) => eprintln!("{}", format_error(&e, mode)),
+    }
+}
+
 /// Run in pipe mode: read lines from stdin, execute each.
 pub fn run_pipe(state: &mut SessionState, mode: OutputMode) -> i32 {
     let stdin = io::stdin();
@@ -221,6 +250,16 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     false
                 }
             },
+            BranchOp::Tree => match state.list_branch_infos() {
+                Ok(branches) => {
+                    println!("{}", format_branch_tree(&branches, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
         },
         Ok(CliAction::Meta(_)) => {
             // Meta-commands should have been handled before reaching here
@@ -231,21 +270,21 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             space,
             pairs,
         }) => {
-            let mut outputs = Vec::new();
-            for (key, value) in pairs {
-                match state.execute(Command::KvPut {
-                    branch: branch.clone(),
-                    space: space.clone(),
-                    key,
-                    value,
-                }) {
-                    Ok(output) => outputs.push(output),
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        return false;
-                    }
+            let entries = pairs
+                .into_iter()
+                .map(|(key, value)| BatchKvEntry { key, value })
+                .collect();
+            let outputs = match state.execute(Command::KvPutMany {
+                branch,
+                space,
+                entries,
+            }) {
+                Ok(output) => split_bulk_output(output),
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    return false;
                 }
-            }
+            };
             let formatted = format_multi_output(&outputs, mode);
             if !formatted.is_empty() {
                 println!("{}", formatted);
@@ -258,21 +297,17 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             keys,
             with_version,
         }) => {
-            let mut outputs = Vec::new();
-            for key in keys {
-                match state.execute(Command::KvGet {
-                    branch: branch.clone(),
-                    space: space.clone(),
-                    key,
-                    as_of: None,
-                }) {
-                    Ok(output) => outputs.push(output),
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        return false;
-                    }
+            let outputs = match state.execute(Command::KvGetMany {
+                branch,
+                space,
+                keys,
+            }) {
+                Ok(output) => split_bulk_output(output),
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    return false;
                 }
-            }
+            };
             let formatted = format_multi_versioned_output(&outputs, mode, with_version);
             if !formatted.is_empty() {
                 println!("{}", formatted);
@@ -284,20 +319,17 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             space,
             keys,
         }) => {
-            let mut outputs = Vec::new();
-            for key in keys {
-                match state.execute(Command::KvDelete {
-                    branch: branch.clone(),
-                    space: space.clone(),
-                    key,
-                }) {
-                    Ok(output) => outputs.push(output),
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        return false;
-                    }
+            let outputs = match state.execute(Command::KvDeleteMany {
+                branch,
+                space,
+                keys,
+            }) {
+                Ok(output) => split_bulk_output(output),
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    return false;
                 }
-            }
+            };
             let formatted = format_multi_output(&outputs, mode);
             if !formatted.is_empty() {
                 println!("{}", formatted);
@@ -315,14 +347,18 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
 
             loop {
                 let output = match primitive {
-                    Primitive::Kv => state.execute(Command::KvList {
-                        branch: branch.clone(),
-                        space: space.clone(),
-                        prefix: prefix.clone(),
-                        cursor: cursor.clone(),
-                        limit: Some(1000),
-                        as_of: None,
-                    }),
+                    Primitive::Kv => {
+                        match state.list_kv_keys(branch.clone(), space.clone(), prefix.clone()) {
+                            Ok(keys) => {
+                                all_keys = keys;
+                                break;
+                            }
+                            Err(e) => {
+                                eprintln!("{}", format_error(&e, mode));
+                                return false;
+                            }
+                        }
+                    }
                     Primitive::Json => state.execute(Command::JsonList {
                         branch: branch.clone(),
                         space: space.clone(),
@@ -330,6 +366,7 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                         cursor: cursor.clone(),
                         limit: 1000,
                         as_of: None,
+                        include_metadata: false,
                     }),
                     Primitive::State => {
                         // State list doesn't have pagination, just execute once
@@ -355,10 +392,6 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                 };
 
                 match output {
-                    Ok(Output::Keys(keys)) => {
-                        all_keys.extend(keys);
-                        break;
-                    }
                     Ok(Output::JsonListResult { keys, cursor: next }) => {
                         all_keys.extend(keys);
                         if next.is_none() {
@@ -396,6 +429,31 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                 false
             }
         },
+        Ok(CliAction::VectorSearchText { text, command }) => {
+            match state.vector_search_text(&text, command) {
+                Ok(output) => {
+                    println!("{}", format_output(&output, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            }
+        }
+        Ok(CliAction::Stats) => run_stats(state, mode),
+        Ok(CliAction::KeysAll) => run_keys_all(state, mode),
+        Ok(CliAction::KvExport {
+            branch,
+            space,
+            prefix,
+            out,
+        }) => run_kv_export(state, branch, space, prefix, out.as_deref(), mode),
+        Ok(CliAction::KvImport {
+            branch,
+            space,
+            input,
+        }) => run_kv_import(state, branch, space, &input, mode),
         Err(e) => {
             eprintln!("(error) {}", e);
             false
@@ -432,15 +490,21 @@ fn print_help(command: Option<&str>) {
         println!("  txn         Transaction info (info, active)");
         println!("  ping        Ping the database");
         println!("  info        Database information");
+        println!("  stats       Entry counts, WAL, and checkpoint summary");
         println!("  flush       Flush writes to disk");
         println!("  compact     Trigger compaction");
         println!("  search      Search across primitives");
+        println!("  export      Export data as JSON Lines (kv)");
+        println!("  import      Import data from JSON Lines (kv)");
         println!();
         println!("Meta-commands:");
         println!("  use <branch> [space]   Switch branch/space context");
         println!("  help [command]         Show help");
         println!("  quit / exit            Exit REPL");
         println!("  clear                  Clear screen");
+        println!("  .begin                 Open a transaction spanning the following commands");
+        println!("  .commit                Commit the open transaction");
+        println!("  .abort                 Abort the open transaction");
     }
 }
 
@@ -451,7 +515,8 @@ fn print_help(command: Option<&str>) {
 /// Known top-level commands for TAB completion.
 const TOP_LEVEL_COMMANDS: &[&str] = &[
     "kv", "json", "event", "state", "vector", "branch", "space", "begin", "commit", "rollback",
-    "txn", "ping", "info", "flush", "compact", "search", "use", "help", "quit", "exit", "clear",
+    "txn", "ping", "info", "stats", "keys", "flush", "compact", "search", "export", "import",
+    "use", "help", "quit", "exit", "clear", ".begin", ".commit", ".abort",
 ];
 
 /// Known subcommands for each top-level command.
@@ -474,11 +539,12 @@ fn subcommands_for(cmd: &str) -> &'static [&'static str] {
             "batch-upsert",
         ],
         "branch" => &[
-            "create", "info", "get", "list", "exists", "del", "fork", "diff", "merge", "export",
-            "import", "validate",
+            "create", "info", "get", "list", "exists", "del", "fork", "diff", "merge", "tree",
+            "export", "import", "validate",
         ],
         "space" => &["list", "create", "del", "exists"],
         "txn" => &["info", "active"],
+        "export" | "import" => &["kv"],
         _ => &[],
     }
 }
@@ -5,10 +5,13 @@
 //! `Arc<Database>`.
 
 use strata_executor::{
-    BranchDiffResult, Branches, Command, Error, ForkInfo, MergeInfo, MergeStrategy, Output, Result,
-    Session, Strata,
+    BranchDiffResult, BranchId, BranchInfo, Branches, Command, Error, ForkInfo, MergeInfo,
+    MergeStrategy, MetricsSnapshot, Output, PrimitiveType, Result, Session, Strata,
 };
 
+/// Keys requested per `KvList` page by [`SessionState::list_kv_keys`].
+const KV_PAGE: u64 = 1000;
+
 /// Wraps the database handles and tracks current context.
 pub struct SessionState {
     db: Strata,
@@ -64,6 +67,96 @@ impl SessionState {
         self.db.branches().merge(source, &self.branch, strategy)
     }
 
+    /// List all branches with their metadata.
+    pub fn list_branch_infos(&mut self) -> Result<Vec<BranchInfo>> {
+        match self.execute(Command::BranchList {
+            state: None,
+            limit: None,
+            offset: None,
+        })? {
+            Output::BranchInfoList(branches) => Ok(branches.into_iter().map(|b| b.info).collect()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchList".into(),
+            }),
+        }
+    }
+
+    /// Embed `text` and run it as the query vector of a `VectorSearch`.
+    pub fn vector_search_text(&mut self, text: &str, mut command: Command) -> Result<Output> {
+        let embedding = self.db.embed_text(text)?;
+        if let Command::VectorSearch { query, .. } = &mut command {
+            *query = embedding;
+        }
+        self.execute(command)
+    }
+
+    /// Database-wide transaction, WAL, and checkpoint metrics.
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.db.metrics_snapshot()
+    }
+
+    /// Every key of the current branch and space, from one snapshot.
+    pub fn scan_all_keys(&self) -> Result<Vec<(PrimitiveType, String, u64)>> {
+        let mut db = self.db.new_handle()?;
+        db.set_branch(&self.branch)?;
+        db.set_space(&self.space)?;
+        db.scan_all().collect()
+    }
+
+    /// Every KV key under `prefix`, listed a page at a time.
+    ///
+    /// A page cut short by the database's result limit resumes after its
+    /// last key, so the listing is never silently truncated.
+    pub fn list_kv_keys(
+        &mut self,
+        branch: Option<BranchId>,
+        space: Option<String>,
+        prefix: Option<String>,
+    ) -> Result<Vec<String>> {
+        let mut keys: Vec<String> = Vec::new();
+        loop {
+            let (output, truncated) = match self.execute(Command::KvList {
+                branch: branch.clone(),
+                space: space.clone(),
+                prefix: prefix.clone(),
+                cursor: keys.last().cloned(),
+                limit: Some(KV_PAGE),
+                as_of: None,
+            })? {
+                Output::Truncated(output) => (*output, true),
+                output => (output, false),
+            };
+            let Output::Keys(page) = output else {
+                return Err(Error::Internal {
+                    reason: "Unexpected output for KvList".into(),
+                });
+            };
+            let done = page.is_empty() || (!truncated && page.len() < KV_PAGE as usize);
+            keys.extend(page);
+            if done {
+                return Ok(keys);
+            }
+        }
+    }
+
+    /// Begin a transaction on the current branch.
+    pub fn begin_transaction(&mut self) -> Result<Output> {
+        self.execute(Command::TxnBegin {
+            branch: Some(BranchId::from(self.branch.as_str())),
+            options: None,
+        })
+    }
+
+    /// Commit the open transaction.
+    pub fn commit_transaction(&mut self) -> Result<Output> {
+        self.execute(Command::TxnCommit)
+    }
+
+    /// Abort the open transaction, discarding its writes.
+    pub fn abort_transaction(&mut self) -> Result<Output> {
+        self.execute(Command::TxnRollback)
+    }
+
     /// Current branch name.
     pub fn branch(&self) -> &str {
         &self.branch
@@ -76,6 +169,14 @@ impl SessionState {
 
     /// Switch branch context.
     pub fn set_branch(&mut self, name: &str) -> Result<()> {
+        // An open transaction is bound to the branch it began on
+        if self.in_transaction {
+            return Err(Error::InvalidInput {
+                reason:
+                    "Cannot switch branch while a transaction is open; commit or abort it first"
+                        .into(),
+            });
+        }
         // Verify branch exists
         let exists = match self.session.execute(Command::BranchExists {
             branch: name.into(),
@@ -102,7 +203,6 @@ impl SessionState {
     }
 
     /// Whether a transaction is currently active.
-    #[allow(dead_code)]
     pub fn in_transaction(&self) -> bool {
         self.in_transaction
     }
@@ -0,0 +1,294 @@
+//! `stats`: a one-glance summary of the database.
+//!
+//! Counts are for the current branch and space; the metrics (transactions,
+//! WAL, checkpoint watermark) are database-wide and come from
+//! `Strata::metrics_snapshot`. `--json` emits the full structure.
+
+use serde::Serialize;
+use strata_executor::{BranchId, Command, MetricsSnapshot, Output, Result};
+
+use crate::format::{format_error, OutputMode};
+use crate::state::SessionState;
+
+/// Page size used when counting JSON documents.
+const JSON_PAGE: u64 = 1000;
+
+/// Entry counts per primitive in one branch and space.
+#[derive(Serialize)]
+struct PrimitiveCounts {
+    kv: u64,
+    json: u64,
+    events: u64,
+    state: u64,
+    vectors: u64,
+}
+
+/// Size of one vector collection.
+#[derive(Serialize)]
+struct CollectionStats {
+    name: String,
+    dimension: usize,
+    count: u64,
+}
+
+/// Everything `stats` reports.
+#[derive(Serialize)]
+struct DbStats {
+    branch: String,
+    space: String,
+    counts: PrimitiveCounts,
+    branch_count: u64,
+    vector_collections: Vec<CollectionStats>,
+    metrics: MetricsSnapshot,
+}
+
+/// Gather and print database statistics. Returns true on success.
+pub fn run_stats(state: &mut SessionState, mode: OutputMode) -> bool {
+    match collect(state) {
+        Ok(stats) => {
+            println!("{}", format_stats(&stats, mode));
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", format_error(&e, mode));
+            false
+        }
+    }
+}
+
+fn collect(state: &mut SessionState) -> Result<DbStats> {
+    let branch = Some(BranchId::from(state.branch()));
+    let space = Some(state.space().to_string());
+
+    let kv = state
+        .list_kv_keys(branch.clone(), space.clone(), None)?
+        .len() as u64;
+
+    let mut json = 0u64;
+    let mut cursor = None;
+    while let Output::JsonListResult { keys, cursor: next } = state.execute(Command::JsonList {
+        branch: branch.clone(),
+        space: space.clone(),
+        prefix: None,
+        cursor,
+        limit: JSON_PAGE,
+        as_of: None,
+        include_metadata: false,
+    })? {
+        json += keys.len() as u64;
+        match next {
+            Some(next) if !keys.is_empty() => cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    let events = match state.execute(Command::EventLen {
+        branch: branch.clone(),
+        space: space.clone(),
+    })? {
+        Output::Uint(n) => n,
+        _ => 0,
+    };
+
+    let state_cells = match state.execute(Command::StateList {
+        branch: branch.clone(),
+        space: space.clone(),
+        prefix: None,
+        as_of: None,
+    })? {
+        Output::Keys(keys) => keys.len() as u64,
+        _ => 0,
+    };
+
+    let vector_collections = match state.execute(Command::VectorListCollections {
+        branch: branch.clone(),
+        space: space.clone(),
+    })? {
+        Output::VectorCollectionList(collections) => collections
+            .into_iter()
+            .map(|c| CollectionStats {
+                name: c.name,
+                dimension: c.dimension,
+                count: c.count,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(DbStats {
+        branch: state.branch().to_string(),
+        space: state.space().to_string(),
+        counts: PrimitiveCounts {
+            kv,
+            json,
+            events,
+            state: state_cells,
+            vectors: vector_collections.iter().map(|c| c.count).sum(),
+        },
+        branch_count: state.list_branch_infos()?.len() as u64,
+        vector_collections,
+        metrics: state.metrics_snapshot(),
+    })
+}
+
+fn format_stats(stats: &DbStats, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(stats),
+        OutputMode::Ndjson => serde_json::to_string(stats),
+        OutputMode::Raw => Ok(format_stats_raw(stats)),
+        OutputMode::Human => Ok(format_stats_human(stats)),
+    }
+    .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+}
+
+fn format_stats_human(stats: &DbStats) -> String {
+    let counts = &stats.counts;
+    let metrics = &stats.metrics;
+    let txns = &metrics.transactions;
+    let mut lines = vec![
+        format!("branch: {} (space: {})", stats.branch, stats.space),
+        format!("kv: {}", counts.kv),
+        format!("json: {}", counts.json),
+        format!("events: {}", counts.events),
+        format!("state: {}", counts.state),
+        format!(
+            "vectors: {} in {} collections",
+            counts.vectors,
+            stats.vector_collections.len()
+        ),
+    ];
+    for c in &stats.vector_collections {
+        lines.push(format!("  {}: {} (dim: {})", c.name, c.count, c.dimension));
+    }
+    lines.push(format!("branches: {}", stats.branch_count));
+    lines.push(format!("version: {}", metrics.current_version));
+    lines.push(format!(
+        "transactions: {} committed, {} aborted ({} conflicts), {} active",
+        txns.total_committed, txns.total_aborted, txns.total_conflicts, txns.active_count
+    ));
+    match (&metrics.durability, metrics.wal_size_bytes) {
+        (Some(wal), Some(size)) => {
+            lines.push(format!(
+                "wal: {} bytes on disk, {} appends, {} syncs",
+                size, wal.wal_appends, wal.sync_calls
+            ));
+            lines.push(format!(
+                "sync lag: {} writes ({} bytes)",
+                wal.unsynced_writes, wal.unsynced_bytes
+            ));
+        }
+        _ => lines.push("wal: (none)".to_string()),
+    }
+    lines.push(match metrics.snapshot_watermark {
+        Some(watermark) => format!("snapshot watermark: {}", watermark),
+        None => "snapshot watermark: (none)".to_string(),
+    });
+    lines.join("\n")
+}
+
+/// Tab-separated `name\tvalue` pairs.
+fn format_stats_raw(stats: &DbStats) -> String {
+    let counts = &stats.counts;
+    let metrics = &stats.metrics;
+    let mut pairs = vec![
+        ("kv", counts.kv.to_string()),
+        ("json", counts.json.to_string()),
+        ("events", counts.events.to_string()),
+        ("state", counts.state.to_string()),
+        ("vectors", counts.vectors.to_string()),
+        ("branches", stats.branch_count.to_string()),
+        ("version", metrics.current_version.to_string()),
+    ];
+    if let (Some(wal), Some(size)) = (&metrics.durability, metrics.wal_size_bytes) {
+        pairs.push(("wal_bytes", size.to_string()));
+        pairs.push(("unsynced_writes", wal.unsynced_writes.to_string()));
+        pairs.push(("unsynced_bytes", wal.unsynced_bytes.to_string()));
+    }
+    if let Some(watermark) = metrics.snapshot_watermark {
+        pairs.push(("snapshot_watermark", watermark.to_string()));
+    }
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}\t{}", name, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_executor::{OpenOptions, Strata, Value};
+
+    fn populated_state() -> SessionState {
+        let db = Strata::cache().unwrap();
+        db.kv_put("a", 1i64).unwrap();
+        db.kv_put("b", 2i64).unwrap();
+        db.json_set("doc", "$", Value::Int(1)).unwrap();
+        for _ in 0..3 {
+            db.event_append("tick", Value::Object(Default::default()))
+                .unwrap();
+        }
+        db.state_set("cell", 1i64).unwrap();
+        SessionState::new(db, "default".into(), "default".into())
+    }
+
+    #[test]
+    fn test_collect_counts_current_branch() {
+        let mut state = populated_state();
+        let stats = collect(&mut state).unwrap();
+
+        assert_eq!(stats.branch, "default");
+        assert_eq!(stats.space, "default");
+        assert_eq!(stats.counts.kv, 2);
+        assert_eq!(stats.counts.json, 1);
+        assert_eq!(stats.counts.events, 3);
+        assert_eq!(stats.counts.state, 1);
+        assert_eq!(stats.counts.vectors, 0);
+        assert!(stats.vector_collections.is_empty());
+        assert!(stats.branch_count >= 1);
+    }
+
+    #[test]
+    fn test_collect_counts_past_result_limit() {
+        let db = Strata::cache_with(OpenOptions::new().max_result_limit(2)).unwrap();
+        for key in ["a", "b", "c", "d", "e"] {
+            db.kv_put(key, 1i64).unwrap();
+        }
+        let mut state = SessionState::new(db, "default".into(), "default".into());
+
+        let stats = collect(&mut state).unwrap();
+        assert_eq!(stats.counts.kv, 5);
+    }
+
+    #[test]
+    fn test_format_stats() {
+        let mut state = populated_state();
+        let stats = collect(&mut state).unwrap();
+
+        let human = format_stats(&stats, OutputMode::Human);
+        let lines: Vec<&str> = human.lines().collect();
+        assert_eq!(lines[0], "branch: default (space: default)");
+        assert_eq!(
+            &lines[1..6],
+            [
+                "kv: 2",
+                "json: 1",
+                "events: 3",
+                "state: 1",
+                "vectors: 0 in 0 collections"
+            ]
+        );
+        assert!(lines.contains(&"wal: (none)"));
+
+        let raw = format_stats(&stats, OutputMode::Raw);
+        assert!(raw.lines().any(|line| line == "kv\t2"));
+        assert!(raw.lines().any(|line| line == "events\t3"));
+        assert!(!raw.contains("wal_bytes"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_stats(&stats, OutputMode::Json)).unwrap();
+        assert_eq!(json["counts"]["kv"], 2);
+        assert_eq!(json["counts"]["state"], 1);
+        assert!(!format_stats(&stats, OutputMode::Ndjson).contains('\n'));
+    }
+}
@@ -0,0 +1,156 @@
+//! JSON Lines import/export for `export kv` and `import kv`.
+//!
+//! Each line is one object: `{"key": "...", "value": <Value>}`, where the
+//! value uses the same tagged encoding as `--json` output (e.g.
+//! `{"Int": 42}`), so a dump round-trips every value type exactly.
+//!
+//! Data goes to stdout or the output file; progress and errors go to stderr.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+use serde::{Deserialize, Serialize};
+use strata_executor::{BranchId, Command, Output, Value};
+
+use crate::format::{format_error, OutputMode};
+use crate::state::SessionState;
+
+/// One exported key-value pair.
+#[derive(Serialize, Deserialize)]
+struct KvRecord {
+    key: String,
+    value: Value,
+}
+
+/// Export every KV pair under `prefix` as JSON Lines.
+///
+/// Writes to `out`, or streams to stdout when `out` is `None`. Returns true
+/// on success.
+pub fn run_kv_export(
+    state: &mut SessionState,
+    branch: Option<BranchId>,
+    space: Option<String>,
+    prefix: Option<String>,
+    out: Option<&str>,
+    mode: OutputMode,
+) -> bool {
+    let keys = match state.list_kv_keys(branch.clone(), space.clone(), prefix) {
+        Ok(keys) => keys,
+        Err(e) => {
+            eprintln!("{}", format_error(&e, mode));
+            return false;
+        }
+    };
+
+    let mut writer: Box<dyn Write> = match out {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("(error) Failed to create {}: {}", path, e);
+                return false;
+            }
+        },
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    let mut exported = 0usize;
+    for key in keys {
+        let value = match state.execute(Command::KvGet {
+            branch: branch.clone(),
+            space: space.clone(),
+            key: key.clone(),
+            as_of: None,
+        }) {
+            Ok(Output::MaybeVersioned(Some(vv))) => vv.value,
+            // Deleted between listing and reading
+            Ok(_) => continue,
+            Err(e) => {
+                eprintln!("{}", format_error(&e, mode));
+                return false;
+            }
+        };
+
+        let record = KvRecord { key, value };
+        let written = serde_json::to_string(&record)
+            .map_err(|e| e.to_string())
+            .and_then(|line| writeln!(writer, "{}", line).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            eprintln!("(error) Failed to write {}: {}", record.key, e);
+            return false;
+        }
+        exported += 1;
+    }
+
+    if let Err(e) = writer.flush() {
+        eprintln!("(error) Failed to flush export: {}", e);
+        return false;
+    }
+    eprintln!("Exported {} keys", exported);
+    true
+}
+
+/// Import KV pairs from a JSON Lines file written by [`run_kv_export`].
+///
+/// Blank lines are skipped. A line that fails to parse or write is reported
+/// to stderr and the import continues. Returns true if every row imported.
+pub fn run_kv_import(
+    state: &mut SessionState,
+    branch: Option<BranchId>,
+    space: Option<String>,
+    input: &str,
+    mode: OutputMode,
+) -> bool {
+    let file = match File::open(input) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("(error) Failed to open {}: {}", input, e);
+            return false;
+        }
+    };
+
+    let mut processed = 0usize;
+    let mut imported = 0usize;
+    let mut errors = 0usize;
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line_no = index + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("(error) line {}: {}", line_no, e);
+                errors += 1;
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        processed += 1;
+
+        let record: KvRecord = match serde_json::from_str(&line) {
+            Ok(record) => record,
+            Err(e) => {
+                eprintln!("(error) line {}: {}", line_no, e);
+                errors += 1;
+                continue;
+            }
+        };
+        match state.execute(Command::KvPut {
+            branch: branch.clone(),
+            space: space.clone(),
+            key: record.key,
+            value: record.value,
+        }) {
+            Ok(_) => imported += 1,
+            Err(e) => {
+                eprintln!("line {}: {}", line_no, format_error(&e, mode));
+                errors += 1;
+            }
+        }
+    }
+
+    eprintln!(
+        "Processed {} rows: {} imported, {} errors",
+        processed, imported, errors
+    );
+    errors == 0
+}
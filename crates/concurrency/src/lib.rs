@@ -23,7 +23,7 @@ pub mod validation;
 
 pub use manager::TransactionManager;
 pub use payload::TransactionPayload;
-pub use recovery::{RecoveryCoordinator, RecoveryResult, RecoveryStats};
+pub use recovery::{BranchReplay, RecoveryCoordinator, RecoveryResult, RecoveryStats};
 pub use snapshot::ClonedSnapshotView;
 pub use transaction::{CommitError, JsonStoreExt, TransactionContext, TransactionStatus};
 
@@ -35,7 +35,6 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use strata_core::traits::Storage;
 use strata_core::types::BranchId;
 use strata_durability::format::WalRecord;
-use strata_durability::now_micros;
 use strata_durability::wal::WalWriter;
 
 /// Manages transaction lifecycle and atomic commits
@@ -80,7 +79,36 @@ pub struct TransactionManager {
     ///
     /// Using per-branch locks allows parallel commits for different branches while
     /// still preventing TOCTOU within each branch.
-    commit_locks: DashMap<BranchId, Mutex<()>>,
+    commit_locks: CommitLocks,
+}
+
+/// Commit lock layout used by [`TransactionManager`].
+///
+/// The default keeps one lock per branch, created lazily on first commit.
+/// The sharded layout hashes each branch onto a fixed set of locks, which
+/// bounds memory and avoids a map insert on the commit path when many
+/// short-lived branches are committing concurrently. Two branches that hash
+/// to the same shard serialize their commits, so fewer shards trade
+/// parallelism for footprint.
+enum CommitLocks {
+    /// One lock per branch (default)
+    PerBranch(DashMap<BranchId, Mutex<()>>),
+    /// Fixed number of lock shards indexed by branch hash
+    Sharded(Box<[Mutex<()>]>),
+}
+
+impl CommitLocks {
+    fn sharded(shards: usize) -> Self {
+        CommitLocks::Sharded((0..shards.max(1)).map(|_| Mutex::new(())).collect())
+    }
+
+    fn shard_index(branch_id: &BranchId, shards: usize) -> usize {
+        // BranchIds are UUIDs, so their low bytes are already well distributed
+        let bytes = branch_id.as_bytes();
+        let mut low = [0u8; 8];
+        low.copy_from_slice(&bytes[8..16]);
+        (u64::from_le_bytes(low) % shards as u64) as usize
+    }
 }
 
 impl TransactionManager {
@@ -105,7 +133,24 @@ impl TransactionManager {
             version: AtomicU64::new(initial_version),
             // Start next_txn_id at max_txn_id + 1 to avoid conflicts
             next_txn_id: AtomicU64::new(max_txn_id + 1),
-            commit_locks: DashMap::new(),
+            commit_locks: CommitLocks::PerBranch(DashMap::new()),
+        }
+    }
+
+    /// Use a fixed number of commit lock shards instead of one lock per branch
+    ///
+    /// Branches are hashed onto `shards` locks; commits on branches in
+    /// different shards proceed in parallel. A value of 0 is treated as 1.
+    pub fn with_commit_lock_shards(mut self, shards: usize) -> Self {
+        self.commit_locks = CommitLocks::sharded(shards);
+        self
+    }
+
+    /// Number of commit lock shards, or `None` when using per-branch locks
+    pub fn commit_lock_shards(&self) -> Option<usize> {
+        match &self.commit_locks {
+            CommitLocks::PerBranch(_) => None,
+            CommitLocks::Sharded(locks) => Some(locks.len()),
         }
     }
 
@@ -114,6 +159,17 @@ impl TransactionManager {
         self.version.load(Ordering::SeqCst)
     }
 
+    /// Raise the version and transaction ID counters past recovered ones
+    ///
+    /// For recovery that reads the WAL after the manager was created: the
+    /// next commit gets a version above `version` and the next transaction
+    /// an ID above `max_txn_id`. Counters already past them are kept.
+    pub fn advance_to(&self, version: u64, max_txn_id: u64) {
+        self.version.fetch_max(version, Ordering::SeqCst);
+        self.next_txn_id
+            .fetch_max(max_txn_id.saturating_add(1), Ordering::SeqCst);
+    }
+
     /// Allocate next transaction ID
     ///
     /// # Panics
@@ -166,19 +222,21 @@ impl TransactionManager {
     ///
     /// # Returns
     /// - Ok(commit_version) on success
-    /// - Err(CommitError) if validation fails or WAL write fails
+    /// - Err(CommitError) if validation fails, the WAL record would exceed
+    ///   `WalConfig::max_record_bytes`, or the WAL write fails
     ///
     /// # Commit Sequence
     ///
     /// 1. Acquire per-branch commit lock (prevents TOCTOU race within same branch)
     /// 2. Validate and mark committed (in-memory state transition)
-    /// 3. Allocate commit version
-    /// 4. Write to WAL if provided (BeginTxn, operations, CommitTxn)
-    /// 5. Apply writes to storage
-    /// 6. Release commit lock
-    /// 7. Return commit version
+    /// 3. Merge counter adds and register merges into the latest committed values
+    /// 4. Allocate commit version
+    /// 5. Write to WAL if provided (BeginTxn, operations, CommitTxn)
+    /// 6. Apply writes to storage
+    /// 7. Release commit lock
+    /// 8. Return commit version
     ///
-    /// When `wal` is `None`, steps 4 is skipped entirely. The transaction is
+    /// When `wal` is `None`, step 5 is skipped entirely. The transaction is
     /// still validated and applied to storage, but without durability guarantees.
     ///
     /// # Thread Safety
@@ -210,11 +268,16 @@ impl TransactionManager {
         // This ensures no other transaction on the same branch can modify storage between
         // our validation check and our apply_writes call.
         // Transactions on different branches can proceed in parallel.
-        let branch_lock = self
-            .commit_locks
-            .entry(txn.branch_id)
-            .or_insert_with(|| Mutex::new(()));
-        let _commit_guard = branch_lock.lock();
+        let branch_lock;
+        let _commit_guard = match &self.commit_locks {
+            CommitLocks::PerBranch(locks) => {
+                branch_lock = locks.entry(txn.branch_id).or_insert_with(|| Mutex::new(()));
+                branch_lock.lock()
+            }
+            CommitLocks::Sharded(locks) => {
+                locks[CommitLocks::shard_index(&txn.branch_id, locks.len())].lock()
+            }
+        };
 
         // Step 1: Validate and mark committed (in-memory)
         // This performs: Active → Validating → Committed
@@ -238,6 +301,11 @@ impl TransactionManager {
             tracing::debug!(target: "strata::txn", txn_id = txn.txn_id, "Validation passed");
         }
 
+        // Merge counter adds and register merges into the latest committed
+        // values. Still under the commit lock, so no other commit on this
+        // branch can interleave.
+        txn.resolve_merges(store)?;
+
         // At this point, transaction is in Committed state
         // but NOT yet durable (not in WAL)
 
@@ -249,12 +317,22 @@ impl TransactionManager {
         let has_mutations = !txn.is_read_only() || !txn.json_writes().is_empty();
         if has_mutations {
             if let Some(wal) = wal.as_mut() {
-                let payload = TransactionPayload::from_transaction(txn, commit_version);
+                let payload = TransactionPayload::from_transaction(txn, commit_version).to_bytes();
+                let limit = wal.max_record_bytes();
+                if payload.len() as u64 > limit {
+                    txn.status = TransactionStatus::Aborted {
+                        reason: format!("WAL record of {} bytes exceeds limit", payload.len()),
+                    };
+                    return Err(CommitError::RecordTooLarge {
+                        size: payload.len() as u64,
+                        limit,
+                    });
+                }
                 let record = WalRecord::new(
                     txn.txn_id,
                     *txn.branch_id.as_bytes(),
-                    now_micros(),
-                    payload.to_bytes(),
+                    txn.now().as_micros(),
+                    payload,
                 );
 
                 if let Err(e) = wal.append(&record) {
@@ -310,8 +388,13 @@ impl TransactionManager {
     /// and no further transactions will target it. If a concurrent transaction
     /// is in-flight for this branch, the lock will be lazily re-created on
     /// next commit (via `or_insert_with` in `commit()`).
+    ///
+    /// No-op when commit locks are sharded, since shards are shared by
+    /// many branches and never grow.
     pub fn remove_branch_lock(&self, branch_id: &BranchId) {
-        self.commit_locks.remove(branch_id);
+        if let CommitLocks::PerBranch(locks) = &self.commit_locks {
+            locks.remove(branch_id);
+        }
     }
 }
 
@@ -543,6 +626,79 @@ mod tests {
         assert_eq!(sorted[num_threads - 1], num_threads as u64);
     }
 
+    #[test]
+    fn test_commit_lock_shards_default_is_per_branch() {
+        let manager = TransactionManager::new(0);
+        assert_eq!(manager.commit_lock_shards(), None);
+
+        let manager = TransactionManager::new(0).with_commit_lock_shards(16);
+        assert_eq!(manager.commit_lock_shards(), Some(16));
+
+        let manager = TransactionManager::new(0).with_commit_lock_shards(0);
+        assert_eq!(manager.commit_lock_shards(), Some(1));
+    }
+
+    #[test]
+    fn test_sharded_commit_locks_parallel_commits() {
+        let store = Arc::new(ShardedStore::new());
+        let manager = Arc::new(TransactionManager::new(0).with_commit_lock_shards(4));
+
+        let num_threads = 16;
+        let handles: Vec<_> = (0..num_threads)
+            .map(|i| {
+                let manager = Arc::clone(&manager);
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    let branch_id = BranchId::new();
+                    let ns = create_test_namespace(branch_id);
+                    let key = create_test_key(&ns, "key");
+
+                    let snapshot = store.snapshot();
+                    let mut txn = TransactionContext::with_snapshot(
+                        i as u64 + 1,
+                        branch_id,
+                        Box::new(snapshot),
+                    );
+                    txn.put(key.clone(), Value::Int(i as i64)).unwrap();
+                    manager.commit(&mut txn, store.as_ref(), None).unwrap();
+                    // Removing a lock is a no-op for shared shards
+                    manager.remove_branch_lock(&branch_id);
+                    store.get(&key).unwrap().unwrap().value
+                })
+            })
+            .collect();
+
+        for (i, h) in handles.into_iter().enumerate() {
+            assert_eq!(h.join().unwrap(), Value::Int(i as i64));
+        }
+        assert_eq!(manager.current_version(), num_threads as u64);
+    }
+
+    #[test]
+    fn test_sharded_commit_locks_still_detect_conflicts() {
+        let store = Arc::new(ShardedStore::new());
+        let manager = TransactionManager::new(0).with_commit_lock_shards(1);
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = create_test_key(&ns, "counter");
+
+        let mut seed = TransactionContext::with_snapshot(1, branch_id, Box::new(store.snapshot()));
+        seed.put(key.clone(), Value::Int(0)).unwrap();
+        manager.commit(&mut seed, store.as_ref(), None).unwrap();
+
+        // Both transactions read the same version, then write
+        let mut t1 = TransactionContext::with_snapshot(2, branch_id, Box::new(store.snapshot()));
+        let mut t2 = TransactionContext::with_snapshot(3, branch_id, Box::new(store.snapshot()));
+        t1.get(&key).unwrap();
+        t2.get(&key).unwrap();
+        t1.put(key.clone(), Value::Int(1)).unwrap();
+        t2.put(key.clone(), Value::Int(2)).unwrap();
+
+        assert!(manager.commit(&mut t1, store.as_ref(), None).is_ok());
+        assert!(manager.commit(&mut t2, store.as_ref(), None).is_err());
+    }
+
     /// Test that scan_prefix tracks deleted keys in read_set for conflict detection.
     #[test]
     fn test_scan_prefix_deleted_key_conflict_detection() {
@@ -663,6 +819,109 @@ mod tests {
         );
     }
 
+    /// Concurrent counter adds on one key both commit and neither is lost.
+    #[test]
+    fn test_concurrent_counter_adds_merge() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut wal = create_test_wal(&temp_dir.path().join("wal"));
+        let store = Arc::new(ShardedStore::new());
+        let manager = TransactionManager::new(0);
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let hits = create_test_key(&ns, "hits");
+
+        // Both transactions start from the same snapshot
+        let mut txn1 = TransactionContext::with_snapshot(1, branch_id, Box::new(store.snapshot()));
+        let mut txn2 = TransactionContext::with_snapshot(2, branch_id, Box::new(store.snapshot()));
+        txn1.add(hits.clone(), 5).unwrap();
+        txn1.add(hits.clone(), 1).unwrap();
+        txn2.add(hits.clone(), -2).unwrap();
+        assert!(!txn1.is_read_only());
+        assert_eq!(txn1.get(&hits).unwrap(), None);
+
+        manager
+            .commit(&mut txn1, store.as_ref(), Some(&mut wal))
+            .unwrap();
+        manager
+            .commit(&mut txn2, store.as_ref(), Some(&mut wal))
+            .unwrap();
+        assert_eq!(store.get(&hits).unwrap().unwrap().value, Value::Int(4));
+    }
+
+    /// Adds fold into buffered writes; non-integer counters abort the commit.
+    #[test]
+    fn test_counter_add_folding_and_type_check() {
+        let store = Arc::new(ShardedStore::new());
+        let manager = TransactionManager::new(0);
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let a = create_test_key(&ns, "a");
+        let b = create_test_key(&ns, "b");
+        let name = create_test_key(&ns, "name");
+
+        let mut txn = TransactionContext::with_snapshot(1, branch_id, Box::new(store.snapshot()));
+        txn.put(a.clone(), Value::Int(10)).unwrap();
+        txn.add(a.clone(), 5).unwrap();
+        txn.add(b.clone(), 7).unwrap();
+        txn.delete(b.clone()).unwrap();
+        txn.add(b.clone(), 3).unwrap();
+        txn.put(name.clone(), Value::String("x".into())).unwrap();
+        assert!(txn.add(name.clone(), 1).is_err());
+        assert!(txn.counter_adds().map_or(true, |adds| adds.is_empty()));
+        manager.commit(&mut txn, store.as_ref(), None).unwrap();
+        assert_eq!(store.get(&a).unwrap().unwrap().value, Value::Int(15));
+        assert_eq!(store.get(&b).unwrap().unwrap().value, Value::Int(3));
+
+        let version = manager.current_version();
+        let mut txn = TransactionContext::with_snapshot(2, branch_id, Box::new(store.snapshot()));
+        txn.add(a.clone(), 1).unwrap();
+        txn.add(name.clone(), 1).unwrap();
+        let result = manager.commit(&mut txn, store.as_ref(), None);
+        assert!(matches!(result, Err(CommitError::InvalidMerge(_))));
+        assert!(txn.is_aborted());
+        assert_eq!(manager.current_version(), version);
+        assert_eq!(store.get(&a).unwrap().unwrap().value, Value::Int(15));
+
+        let mut txn = TransactionContext::with_snapshot(3, branch_id, Box::new(store.snapshot()));
+        txn.add(a.clone(), i64::MAX).unwrap();
+        let result = manager.commit(&mut txn, store.as_ref(), None);
+        assert!(matches!(result, Err(CommitError::InvalidMerge(_))));
+    }
+
+    /// Concurrent register merges take the element-wise max and both commit.
+    #[test]
+    fn test_concurrent_max_merges() {
+        let store = Arc::new(ShardedStore::new());
+        let manager = TransactionManager::new(0);
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let sketch = create_test_key(&ns, "sketch");
+        let name = create_test_key(&ns, "name");
+
+        let mut txn1 = TransactionContext::with_snapshot(1, branch_id, Box::new(store.snapshot()));
+        let mut txn2 = TransactionContext::with_snapshot(2, branch_id, Box::new(store.snapshot()));
+        txn1.merge_max(sketch.clone(), vec![3, 0, 1]).unwrap();
+        txn1.merge_max(sketch.clone(), vec![0, 2, 0]).unwrap();
+        assert!(txn1.merge_max(sketch.clone(), vec![1]).is_err());
+        txn2.merge_max(sketch.clone(), vec![1, 1, 4]).unwrap();
+        assert!(!txn2.is_read_only());
+
+        manager.commit(&mut txn1, store.as_ref(), None).unwrap();
+        manager.commit(&mut txn2, store.as_ref(), None).unwrap();
+        assert_eq!(
+            store.get(&sketch).unwrap().unwrap().value,
+            Value::Bytes(vec![3, 2, 4])
+        );
+
+        let mut txn = TransactionContext::with_snapshot(3, branch_id, Box::new(store.snapshot()));
+        txn.put(name.clone(), Value::Int(1)).unwrap();
+        txn.merge_max(sketch.clone(), vec![0, 0, 0, 0]).unwrap();
+        let result = manager.commit(&mut txn, store.as_ref(), None);
+        assert!(matches!(result, Err(CommitError::InvalidMerge(_))));
+        assert_eq!(store.get(&name).unwrap(), None);
+    }
+
     // ========================================================================
     // Read-Only Fast Path Tests
     // ========================================================================
@@ -787,6 +1046,39 @@ mod tests {
         assert_eq!(stored.value, Value::Int(42));
     }
 
+    #[test]
+    fn test_oversized_record_rejected_before_wal_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut wal = WalWriter::new(
+            temp_dir.path().join("wal"),
+            [0u8; 16],
+            DurabilityMode::Always,
+            WalConfig::for_testing().with_max_record_bytes(1024),
+            Box::new(IdentityCodec),
+        )
+        .unwrap();
+        let store = Arc::new(ShardedStore::new());
+        let manager = TransactionManager::new(0);
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = create_test_key(&ns, "big");
+
+        let mut txn = TransactionContext::with_snapshot(1, branch_id, Box::new(store.snapshot()));
+        txn.put(key.clone(), Value::Bytes(vec![0u8; 2048])).unwrap();
+
+        let result = manager.commit(&mut txn, store.as_ref(), Some(&mut wal));
+        match result {
+            Err(CommitError::RecordTooLarge { size, limit }) => {
+                assert!(size > 2048);
+                assert_eq!(limit, 1024);
+            }
+            other => panic!("Expected RecordTooLarge, got {:?}", other),
+        }
+        assert!(txn.is_aborted());
+        assert_eq!(wal.counters().wal_appends, 0);
+        assert!(store.get(&key).unwrap().is_none());
+    }
+
     #[test]
     fn test_write_with_read_still_validates() {
         let temp_dir = TempDir::new().unwrap();
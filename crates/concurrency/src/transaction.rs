@@ -15,7 +15,7 @@ use strata_core::types::{BranchId, Key};
 use strata_core::value::Value;
 use strata_core::StrataError;
 use strata_core::StrataResult;
-use strata_core::{Version, Versioned, VersionedValue};
+use strata_core::{Timestamp, Version, Versioned, VersionedValue};
 
 /// Error type for commit failures
 ///
@@ -45,6 +45,24 @@ pub enum CommitError {
     /// A storage I/O error occurred while reading current versions for
     /// conflict detection. The transaction is aborted to prevent incorrect commits.
     StorageError(String),
+
+    /// Serialized transaction exceeds the WAL's maximum record size
+    ///
+    /// Checked before anything is written, so the transaction has no effect.
+    RecordTooLarge {
+        /// Serialized payload size in bytes
+        size: u64,
+        /// Configured maximum in bytes
+        limit: u64,
+    },
+
+    /// A counter add or register merge could not be applied to the key's
+    /// committed value
+    ///
+    /// The key holds a value of the wrong type or size, or a counter sum
+    /// overflows `i64`. Checked under the commit lock before anything is
+    /// written.
+    InvalidMerge(String),
 }
 
 impl std::fmt::Display for CommitError {
@@ -56,6 +74,12 @@ impl std::fmt::Display for CommitError {
             CommitError::InvalidState(msg) => write!(f, "Invalid state: {}", msg),
             CommitError::WALError(msg) => write!(f, "WAL error: {}", msg),
             CommitError::StorageError(msg) => write!(f, "Storage error during validation: {}", msg),
+            CommitError::RecordTooLarge { size, limit } => write!(
+                f,
+                "Transaction of {} bytes exceeds the WAL record limit of {} bytes",
+                size, limit
+            ),
+            CommitError::InvalidMerge(msg) => write!(f, "Invalid merge: {}", msg),
         }
     }
 }
@@ -78,6 +102,10 @@ impl From<CommitError> for StrataError {
                 message: format!("Storage error during validation: {}", msg),
                 source: None,
             },
+            CommitError::RecordTooLarge { size, limit } => {
+                StrataError::value_too_large(size, limit)
+            }
+            CommitError::InvalidMerge(msg) => StrataError::invalid_input(msg),
         }
     }
 }
@@ -422,6 +450,21 @@ pub struct TransactionContext {
     /// Only allocated when JSON operations are performed.
     json_snapshot_versions: Option<HashMap<Key, u64>>,
 
+    // Counter Operations (lazy allocation, like JSON fields)
+    /// Deltas to merge into integer counters at commit
+    ///
+    /// Not tracked in the read set: each delta is added to the latest
+    /// committed value under the commit lock, so concurrent adds to the
+    /// same key never conflict. Only allocated when counters are used.
+    counter_adds: Option<HashMap<Key, i64>>,
+
+    /// Byte registers to merge into their keys at commit, element-wise max
+    ///
+    /// Like counter adds, not tracked in the read set and merged with the
+    /// latest committed value under the commit lock. Only allocated when
+    /// used.
+    max_merges: Option<HashMap<Key, Vec<u8>>>,
+
     // State
     /// Current transaction status
     pub status: TransactionStatus,
@@ -469,6 +512,8 @@ impl TransactionContext {
             json_reads: None,
             json_writes: None,
             json_snapshot_versions: None,
+            counter_adds: None,
+            max_merges: None,
             status: TransactionStatus::Active,
             start_time: Instant::now(),
         }
@@ -517,6 +562,8 @@ impl TransactionContext {
             json_reads: None,
             json_writes: None,
             json_snapshot_versions: None,
+            counter_adds: None,
+            max_merges: None,
             status: TransactionStatus::Active,
             start_time: Instant::now(),
         }
@@ -765,6 +812,7 @@ impl TransactionContext {
 
         // Remove from delete_set if previously deleted in this txn
         self.delete_set.remove(&key);
+        self.discard_pending_merge(&key);
 
         // Add to write_set (overwrites any previous write to same key)
         self.write_set.insert(key, value);
@@ -803,6 +851,7 @@ impl TransactionContext {
 
         // Remove from write_set if previously written in this txn
         self.write_set.remove(&key);
+        self.discard_pending_merge(&key);
 
         // Add to delete_set
         self.delete_set.insert(key);
@@ -853,6 +902,152 @@ impl TransactionContext {
         Ok(())
     }
 
+    // === Counter Operations ===
+
+    /// Buffer an addition to the integer counter at `key`
+    ///
+    /// Unlike a read followed by a put, the key is not added to the read set.
+    /// At commit the delta is added to the latest committed value (a missing
+    /// key counts as 0) under the commit lock, so concurrent adds to the same
+    /// counter all commit and none is lost. Adds are not visible to reads in
+    /// this transaction. If the key was put or deleted earlier in this
+    /// transaction, the delta is folded into that write instead.
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active, or
+    /// if the delta cannot be folded into a buffered write because that write
+    /// is not an integer or the sum overflows.
+    pub fn add(&mut self, key: Key, delta: i64) -> StrataResult<()> {
+        self.ensure_active()?;
+
+        if self.delete_set.remove(&key) {
+            self.write_set.insert(key, Value::Int(delta));
+            return Ok(());
+        }
+        if let Some(value) = self.write_set.get_mut(&key) {
+            *value =
+                Value::Int(add_to_counter(Some(value), delta).map_err(StrataError::invalid_input)?);
+            return Ok(());
+        }
+        let pending = self
+            .counter_adds
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_insert(0);
+        *pending = pending
+            .checked_add(delta)
+            .ok_or_else(|| StrataError::invalid_input("counter delta overflows i64"))?;
+        Ok(())
+    }
+
+    /// Pending counter adds, by key
+    ///
+    /// Returns None if no adds have been buffered.
+    pub fn counter_adds(&self) -> Option<&HashMap<Key, i64>> {
+        self.counter_adds.as_ref()
+    }
+
+    /// Buffer an element-wise max merge of `registers` into the bytes at `key`
+    ///
+    /// At commit each byte of the stored value becomes the larger of itself
+    /// and the matching byte of `registers` (a missing key is taken as all
+    /// zeros). The merge is commutative and idempotent, so like [`add`]
+    /// it skips the read set and concurrent merges to the same key all
+    /// commit. This is the merge rule for register sketches such as
+    /// HyperLogLog. Merges are not visible to reads in this transaction;
+    /// a put or delete of the key earlier in this transaction absorbs the
+    /// merge instead.
+    ///
+    /// [`add`]: Self::add
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active, or
+    /// if `registers` differs in length from a buffered write or an earlier
+    /// merge of the same key.
+    pub fn merge_max(&mut self, key: Key, registers: Vec<u8>) -> StrataResult<()> {
+        self.ensure_active()?;
+
+        if self.delete_set.remove(&key) {
+            self.write_set.insert(key, Value::Bytes(registers));
+            return Ok(());
+        }
+        if let Some(value) = self.write_set.get_mut(&key) {
+            *value = Value::Bytes(
+                max_registers(Some(value), &registers).map_err(StrataError::invalid_input)?,
+            );
+            return Ok(());
+        }
+        let merges = self.max_merges.get_or_insert_with(HashMap::new);
+        let registers = match merges.get(&key) {
+            Some(pending) => max_bytes(pending, &registers).map_err(StrataError::invalid_input)?,
+            None => registers,
+        };
+        merges.insert(key, registers);
+        Ok(())
+    }
+
+    /// Pending register merges, by key
+    ///
+    /// Returns None if no merges have been buffered.
+    pub fn max_merges(&self) -> Option<&HashMap<Key, Vec<u8>>> {
+        self.max_merges.as_ref()
+    }
+
+    /// Turn pending counter adds and register merges into writes of the
+    /// merged values
+    ///
+    /// Called by the transaction manager under the commit lock, after
+    /// validation, so no other commit can change a merged key between reading
+    /// its value here and applying the write. The WAL then records the
+    /// merged values, which replay like any other put. On failure the
+    /// transaction is aborted.
+    ///
+    /// # Errors
+    /// - `CommitError::InvalidMerge` if a key holds a value of the wrong type
+    ///   or size, or a counter sum overflows
+    /// - `CommitError::StorageError` if reading the current value fails
+    pub fn resolve_merges<S: Storage>(
+        &mut self,
+        store: &S,
+    ) -> std::result::Result<(), CommitError> {
+        let adds = self.counter_adds.take().unwrap_or_default();
+        let merges = self.max_merges.take().unwrap_or_default();
+        let mut merged = Vec::with_capacity(adds.len() + merges.len());
+        for (key, delta) in adds {
+            let current = read_committed(store, &key)?;
+            let total = add_to_counter(current.as_ref(), delta).map(Value::Int);
+            merged.push((key, total));
+        }
+        for (key, registers) in merges {
+            let current = read_committed(store, &key)?;
+            let registers = max_registers(current.as_ref(), &registers).map(Value::Bytes);
+            merged.push((key, registers));
+        }
+        for (key, value) in merged {
+            match value {
+                Ok(value) => {
+                    self.write_set.insert(key, value);
+                }
+                Err(reason) => {
+                    self.status = TransactionStatus::Aborted {
+                        reason: format!("Invalid merge: {}", reason),
+                    };
+                    return Err(CommitError::InvalidMerge(reason));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn discard_pending_merge(&mut self, key: &Key) {
+        if let Some(adds) = self.counter_adds.as_mut() {
+            adds.remove(key);
+        }
+        if let Some(merges) = self.max_merges.as_mut() {
+            merges.remove(key);
+        }
+    }
+
     // === Event State Tracking ===
 
     /// Get the current event sequence count tracked across Transaction instances.
@@ -963,7 +1158,8 @@ impl TransactionContext {
     /// This is useful for retry scenarios where you want to restart
     /// a transaction's operations without creating a new transaction.
     ///
-    /// Clears: read_set, write_set, delete_set, cas_set, and all JSON operation sets
+    /// Clears: read_set, write_set, delete_set, cas_set, counter adds, register
+    /// merges, and all JSON operation sets
     ///
     /// Note: Does not change transaction state or snapshot.
     ///
@@ -980,6 +1176,8 @@ impl TransactionContext {
         self.json_reads = None;
         self.json_writes = None;
         self.json_snapshot_versions = None;
+        self.counter_adds = None;
+        self.max_merges = None;
         Ok(())
     }
 
@@ -1318,19 +1516,41 @@ impl TransactionContext {
 
     /// Check if transaction has any pending operations
     ///
-    /// Returns true if there are buffered writes, deletes, or CAS operations
-    /// that would need to be applied at commit.
+    /// Returns true if there are buffered writes, deletes, CAS operations,
+    /// counter adds or register merges that would need to be applied at
+    /// commit.
     pub fn has_pending_operations(&self) -> bool {
-        !self.write_set.is_empty() || !self.delete_set.is_empty() || !self.cas_set.is_empty()
+        !self.is_read_only()
     }
 
     /// Check if transaction is read-only
     ///
-    /// A read-only transaction has reads but no writes, deletes, or CAS ops.
-    /// Read-only transactions always commit successfully (no conflicts possible
-    /// since they don't modify anything).
+    /// A read-only transaction has reads but no writes, deletes, CAS ops,
+    /// counter adds or register merges. Read-only transactions always commit successfully (no
+    /// conflicts possible since they don't modify anything).
     pub fn is_read_only(&self) -> bool {
-        self.write_set.is_empty() && self.delete_set.is_empty() && self.cas_set.is_empty()
+        self.write_set.is_empty()
+            && self.delete_set.is_empty()
+            && self.cas_set.is_empty()
+            && self
+                .counter_adds
+                .as_ref()
+                .map_or(true, |adds| adds.is_empty())
+            && self
+                .max_merges
+                .as_ref()
+                .map_or(true, |merges| merges.is_empty())
+    }
+
+    /// Current time according to the snapshot's storage clock
+    ///
+    /// Use this rather than the system time to timestamp records written in
+    /// the transaction, so an injected clock applies to them. Falls back to
+    /// system time when the transaction has no snapshot.
+    pub fn now(&self) -> Timestamp {
+        self.snapshot
+            .as_ref()
+            .map_or_else(Timestamp::now, |snapshot| snapshot.now())
     }
 
     /// Get the abort reason if transaction is aborted
@@ -1408,12 +1628,23 @@ impl TransactionContext {
         self.json_reads = None;
         self.json_writes = None;
         self.json_snapshot_versions = None;
+        self.counter_adds = None;
+        self.max_merges = None;
 
         // Reset state
         self.status = TransactionStatus::Active;
         self.start_time = Instant::now();
     }
 
+    /// Drop the snapshot view without resetting anything else
+    ///
+    /// Called when a context is parked for reuse, so an idle context does
+    /// not keep its snapshot (and any versions the store pins for it) alive.
+    /// Reads after this fail until the next [`reset`](Self::reset).
+    pub fn release_snapshot(&mut self) {
+        self.snapshot = None;
+    }
+
     /// Get current capacity of internal collections (for debugging/testing)
     ///
     /// Returns (read_set_capacity, write_set_capacity, delete_set_capacity, cas_set_capacity).
@@ -1438,6 +1669,64 @@ impl TransactionContext {
     }
 }
 
+/// Add `delta` to a counter's current value, treating a missing key as 0
+fn add_to_counter(current: Option<&Value>, delta: i64) -> std::result::Result<i64, String> {
+    let base = match current {
+        None => 0,
+        Some(Value::Int(n)) => *n,
+        Some(other) => {
+            return Err(format!(
+                "counter holds a {}, expected an Int",
+                other.type_name()
+            ))
+        }
+    };
+    base.checked_add(delta)
+        .ok_or_else(|| format!("{} + {} overflows i64", base, delta))
+}
+
+/// Element-wise max of a register array and its current value, treating a
+/// missing key as all zeros
+fn max_registers(
+    current: Option<&Value>,
+    registers: &[u8],
+) -> std::result::Result<Vec<u8>, String> {
+    match current {
+        None => Ok(registers.to_vec()),
+        Some(Value::Bytes(existing)) => max_bytes(existing, registers),
+        Some(other) => Err(format!(
+            "registers hold a {}, expected Bytes",
+            other.type_name()
+        )),
+    }
+}
+
+fn max_bytes(existing: &[u8], registers: &[u8]) -> std::result::Result<Vec<u8>, String> {
+    if existing.len() != registers.len() {
+        return Err(format!(
+            "register array of {} bytes cannot merge into {} bytes",
+            registers.len(),
+            existing.len()
+        ));
+    }
+    Ok(existing
+        .iter()
+        .zip(registers)
+        .map(|(a, b)| *a.max(b))
+        .collect())
+}
+
+/// Latest committed value of `key`, for merging at commit
+fn read_committed<S: Storage>(
+    store: &S,
+    key: &Key,
+) -> std::result::Result<Option<Value>, CommitError> {
+    store
+        .get(key)
+        .map(|vv| vv.map(|vv| vv.value))
+        .map_err(|e| CommitError::StorageError(e.to_string()))
+}
+
 // ============================================================================
 // JsonStoreExt Implementation (M5 Epic 30)
 // ============================================================================
@@ -17,6 +17,7 @@
 //! - `versioned`: Generic versioned wrapper (Invariant 2)
 //! - `version`: Version identifier types (Invariant 2)
 //! - `timestamp`: Microsecond timestamps (Invariant 2)
+//! - `clock`: Injectable time source for timestamps
 //! - `primitive_type`: Primitive enumeration (Invariant 6)
 //! - `branch_name`: Semantic branch identifier (Invariant 5)
 //!
@@ -29,6 +30,7 @@
 //! ```
 
 pub mod branch_name;
+pub mod clock;
 pub mod entity_ref;
 pub mod primitive_type;
 pub mod timestamp;
@@ -38,6 +40,7 @@ pub mod versioned_history;
 
 // Re-exports
 pub use branch_name::{BranchName, BranchNameError, MAX_BRANCH_NAME_LENGTH};
+pub use clock::{Clock, ManualClock, SystemClock};
 pub use entity_ref::EntityRef;
 pub use primitive_type::PrimitiveType;
 pub use timestamp::Timestamp;
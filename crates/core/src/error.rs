@@ -417,7 +417,8 @@ impl std::fmt::Display for ConstraintReason {
 ///   - These are **retryable** with fresh data
 /// - **Structural Failures (ConstraintViolation)**: Invalid input, dimension mismatch, capacity exceeded
 ///   - These require input changes to resolve
-/// - **Storage**: Low-level storage failures (`Storage`, `Serialization`, `Corruption`)
+/// - **Storage**: Low-level storage failures (`Storage`, `Serialization`, `Corruption`,
+///   `UnsupportedFormatVersion`)
 /// - **Internal**: Unexpected internal errors (`Internal`)
 ///
 /// ## Usage
@@ -614,6 +615,19 @@ pub enum StrataError {
         state: String,
     },
 
+    /// Database closed
+    ///
+    /// The database has been shut down and no longer accepts operations.
+    /// Reopen it to continue.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::database_closed();
+    /// ```
+    #[error("database is closed")]
+    DatabaseClosed,
+
     // =========================================================================
     // Validation Errors
     // =========================================================================
@@ -674,6 +688,24 @@ pub enum StrataError {
         got: usize,
     },
 
+    /// Unknown filter field (Vector-specific)
+    ///
+    /// A search filter names a metadata field the collection's schema does
+    /// not declare, usually because of a typo.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::unknown_filter_field("docs", "categroy");
+    /// ```
+    #[error("unknown filter field '{field}' in collection {collection}")]
+    UnknownFilterField {
+        /// Collection searched
+        collection: String,
+        /// The undeclared field
+        field: String,
+    },
+
     /// Path not found (JSON-specific)
     ///
     /// The specified path doesn't exist in the JSON document.
@@ -784,6 +816,26 @@ pub enum StrataError {
         message: String,
     },
 
+    /// Unsupported on-disk format version
+    ///
+    /// A file was written by a newer format version than this build can
+    /// read, typically after downgrading. Nothing was read from it.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::unsupported_format_version("WAL segment 'wal/wal-000001.seg'", 3, 2);
+    /// ```
+    #[error("unsupported format version: {format} has version {found}, this build supports up to {supported}")]
+    UnsupportedFormatVersion {
+        /// The file whose format is too new
+        format: String,
+        /// Version found in the file
+        found: u32,
+        /// Newest version this build can read
+        supported: u32,
+    },
+
     // =========================================================================
     // Resource Errors
     // =========================================================================
@@ -810,6 +862,27 @@ pub enum StrataError {
         requested: usize,
     },
 
+    /// Value too large
+    ///
+    /// A write is larger than the configured maximum WAL record size. It was
+    /// rejected before anything was written.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::ValueTooLarge {
+    ///     size: 80 * 1024 * 1024,
+    ///     limit: 64 * 1024 * 1024,
+    /// };
+    /// ```
+    #[error("value too large: {size} bytes (limit: {limit})")]
+    ValueTooLarge {
+        /// Size of the rejected write in bytes
+        size: u64,
+        /// The configured limit in bytes
+        limit: u64,
+    },
+
     /// Budget exceeded
     ///
     /// The operation exceeded its computational budget.
@@ -827,6 +900,24 @@ pub enum StrataError {
         operation: String,
     },
 
+    /// Too many active transactions
+    ///
+    /// Beginning a transaction would exceed the configured
+    /// `max_active_transactions` limit. Retry once other transactions finish.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::too_many_transactions(64, 64);
+    /// ```
+    #[error("too many active transactions: {active} open (limit: {limit})")]
+    TooManyTransactions {
+        /// Transactions open when the request was rejected
+        active: u64,
+        /// The configured limit
+        limit: u64,
+    },
+
     // =========================================================================
     // Internal Errors
     // =========================================================================
@@ -945,6 +1036,17 @@ impl StrataError {
         }
     }
 
+    /// Create a DatabaseClosed error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::database_closed();
+    /// ```
+    pub fn database_closed() -> Self {
+        StrataError::DatabaseClosed
+    }
+
     /// Create an InvalidOperation error
     ///
     /// ## Example
@@ -988,6 +1090,20 @@ impl StrataError {
         StrataError::DimensionMismatch { expected, got }
     }
 
+    /// Create an UnknownFilterField error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::unknown_filter_field("docs", "categroy");
+    /// ```
+    pub fn unknown_filter_field(collection: impl Into<String>, field: impl Into<String>) -> Self {
+        StrataError::UnknownFilterField {
+            collection: collection.into(),
+            field: field.into(),
+        }
+    }
+
     /// Create a PathNotFound error
     ///
     /// ## Example
@@ -1089,6 +1205,25 @@ impl StrataError {
         }
     }
 
+    /// Create an UnsupportedFormatVersion error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::unsupported_format_version("MANIFEST", 2, 1);
+    /// ```
+    pub fn unsupported_format_version(
+        format: impl Into<String>,
+        found: u32,
+        supported: u32,
+    ) -> Self {
+        StrataError::UnsupportedFormatVersion {
+            format: format.into(),
+            found,
+            supported,
+        }
+    }
+
     /// Create a CapacityExceeded error
     ///
     /// ## Example
@@ -1104,6 +1239,17 @@ impl StrataError {
         }
     }
 
+    /// Create a ValueTooLarge error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::value_too_large(80 * 1024 * 1024, 64 * 1024 * 1024);
+    /// ```
+    pub fn value_too_large(size: u64, limit: u64) -> Self {
+        StrataError::ValueTooLarge { size, limit }
+    }
+
     /// Create a BudgetExceeded error
     ///
     /// ## Example
@@ -1117,6 +1263,17 @@ impl StrataError {
         }
     }
 
+    /// Create a TooManyTransactions error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::too_many_transactions(64, 64);
+    /// ```
+    pub fn too_many_transactions(active: u64, limit: u64) -> Self {
+        StrataError::TooManyTransactions { active, limit }
+    }
+
     /// Create an Internal error
     ///
     /// ## Example
@@ -1197,12 +1354,18 @@ impl StrataError {
             StrataError::TransactionTimeout { .. } => ErrorCode::Conflict,
             StrataError::TransactionNotActive { .. } => ErrorCode::Conflict,
 
+            // Lifecycle errors
+            StrataError::DatabaseClosed => ErrorCode::ConstraintViolation,
+
             // ConstraintViolation errors (structural failures)
             StrataError::InvalidOperation { .. } => ErrorCode::ConstraintViolation,
             StrataError::InvalidInput { .. } => ErrorCode::ConstraintViolation,
             StrataError::DimensionMismatch { .. } => ErrorCode::ConstraintViolation,
+            StrataError::UnknownFilterField { .. } => ErrorCode::ConstraintViolation,
             StrataError::CapacityExceeded { .. } => ErrorCode::ConstraintViolation,
+            StrataError::ValueTooLarge { .. } => ErrorCode::ConstraintViolation,
             StrataError::BudgetExceeded { .. } => ErrorCode::ConstraintViolation,
+            StrataError::TooManyTransactions { .. } => ErrorCode::ConstraintViolation,
 
             // Path errors
             StrataError::PathNotFound { .. } => ErrorCode::InvalidPath,
@@ -1214,6 +1377,7 @@ impl StrataError {
             StrataError::Storage { .. } => ErrorCode::StorageError,
             StrataError::Serialization { .. } => ErrorCode::SerializationError,
             StrataError::Corruption { .. } => ErrorCode::StorageError,
+            StrataError::UnsupportedFormatVersion { .. } => ErrorCode::StorageError,
 
             // Internal errors
             StrataError::Internal { .. } => ErrorCode::InternalError,
@@ -1269,6 +1433,7 @@ impl StrataError {
             StrataError::TransactionNotActive { state } => {
                 ErrorDetails::new().with_string("state", state)
             }
+            StrataError::DatabaseClosed => ErrorDetails::new(),
             StrataError::InvalidOperation { entity_ref, reason } => ErrorDetails::new()
                 .with_string("entity", entity_ref.to_string())
                 .with_string("reason", reason),
@@ -1278,6 +1443,9 @@ impl StrataError {
             StrataError::DimensionMismatch { expected, got } => ErrorDetails::new()
                 .with_int("expected", *expected as i64)
                 .with_int("got", *got as i64),
+            StrataError::UnknownFilterField { collection, field } => ErrorDetails::new()
+                .with_string("collection", collection)
+                .with_string("field", field),
             StrataError::PathNotFound { entity_ref, path } => ErrorDetails::new()
                 .with_string("entity", entity_ref.to_string())
                 .with_string("path", path),
@@ -1298,6 +1466,14 @@ impl StrataError {
             StrataError::Corruption { message } => {
                 ErrorDetails::new().with_string("message", message)
             }
+            StrataError::UnsupportedFormatVersion {
+                format,
+                found,
+                supported,
+            } => ErrorDetails::new()
+                .with_string("format", format)
+                .with_int("found", *found as i64)
+                .with_int("supported", *supported as i64),
             StrataError::CapacityExceeded {
                 resource,
                 limit,
@@ -1306,9 +1482,15 @@ impl StrataError {
                 .with_string("resource", resource)
                 .with_int("limit", *limit as i64)
                 .with_int("requested", *requested as i64),
+            StrataError::ValueTooLarge { size, limit } => ErrorDetails::new()
+                .with_int("size", *size as i64)
+                .with_int("limit", *limit as i64),
             StrataError::BudgetExceeded { operation } => {
                 ErrorDetails::new().with_string("operation", operation)
             }
+            StrataError::TooManyTransactions { active, limit } => ErrorDetails::new()
+                .with_int("active", *active as i64)
+                .with_int("limit", *limit as i64),
             StrataError::Internal { message } => {
                 ErrorDetails::new().with_string("message", message)
             }
@@ -1391,7 +1573,8 @@ impl StrataError {
 
     /// Check if this is a validation error
     ///
-    /// Returns true for: `InvalidOperation`, `InvalidInput`, `DimensionMismatch`
+    /// Returns true for: `InvalidOperation`, `InvalidInput`, `DimensionMismatch`,
+    /// `UnknownFilterField`
     ///
     /// Validation errors indicate bad input - don't retry, fix the input.
     ///
@@ -1409,12 +1592,14 @@ impl StrataError {
             StrataError::InvalidOperation { .. }
                 | StrataError::InvalidInput { .. }
                 | StrataError::DimensionMismatch { .. }
+                | StrataError::UnknownFilterField { .. }
         )
     }
 
     /// Check if this is a storage error
     ///
-    /// Returns true for: `Storage`, `Serialization`, `Corruption`
+    /// Returns true for: `Storage`, `Serialization`, `Corruption`,
+    /// `UnsupportedFormatVersion`
     ///
     /// ## Example
     /// ```no_run
@@ -1430,6 +1615,7 @@ impl StrataError {
             StrataError::Storage { .. }
                 | StrataError::Serialization { .. }
                 | StrataError::Corruption { .. }
+                | StrataError::UnsupportedFormatVersion { .. }
         )
     }
 
@@ -1491,7 +1677,8 @@ impl StrataError {
 
     /// Check if this is a resource error
     ///
-    /// Returns true for: `CapacityExceeded`, `BudgetExceeded`
+    /// Returns true for: `CapacityExceeded`, `ValueTooLarge`, `BudgetExceeded`,
+    /// `TooManyTransactions`
     ///
     /// ## Example
     /// ```no_run
@@ -1504,7 +1691,10 @@ impl StrataError {
     pub fn is_resource_error(&self) -> bool {
         matches!(
             self,
-            StrataError::CapacityExceeded { .. } | StrataError::BudgetExceeded { .. }
+            StrataError::CapacityExceeded { .. }
+                | StrataError::ValueTooLarge { .. }
+                | StrataError::BudgetExceeded { .. }
+                | StrataError::TooManyTransactions { .. }
         )
     }
 
@@ -1857,6 +2047,16 @@ mod strata_error_tests {
         assert!(e.is_serious());
     }
 
+    #[test]
+    fn test_unsupported_format_version_constructor() {
+        let e = StrataError::unsupported_format_version("MANIFEST", 2, 1);
+
+        assert!(e.is_storage_error());
+        assert!(!e.is_serious());
+        assert_eq!(e.code(), ErrorCode::StorageError);
+        assert!(e.to_string().contains("MANIFEST has version 2"));
+    }
+
     #[test]
     fn test_capacity_exceeded_constructor() {
         let e = StrataError::capacity_exceeded("event log", 1_000_000, 1_000_001);
@@ -1876,6 +2076,31 @@ mod strata_error_tests {
         }
     }
 
+    #[test]
+    fn test_too_many_transactions_constructor() {
+        let e = StrataError::too_many_transactions(8, 8);
+
+        assert!(e.is_resource_error());
+        assert!(!e.is_retryable());
+        assert_eq!(e.code(), ErrorCode::ConstraintViolation);
+        assert!(e.to_string().contains("8 open (limit: 8)"));
+    }
+
+    #[test]
+    fn test_value_too_large_constructor() {
+        let e = StrataError::value_too_large(2048, 1024);
+
+        assert!(e.is_resource_error());
+        assert_eq!(e.code(), ErrorCode::ConstraintViolation);
+        assert!(matches!(
+            e,
+            StrataError::ValueTooLarge {
+                size: 2048,
+                limit: 1024
+            }
+        ));
+    }
+
     #[test]
     fn test_budget_exceeded_constructor() {
         let e = StrataError::budget_exceeded("vector search");
@@ -2051,6 +2276,14 @@ mod strata_error_tests {
         assert_eq!(e.code(), ErrorCode::ConstraintViolation);
     }
 
+    #[test]
+    fn test_error_code_mapping_database_closed() {
+        let e = StrataError::database_closed();
+        assert_eq!(e.code(), ErrorCode::ConstraintViolation);
+        assert_eq!(e.to_string(), "database is closed");
+        assert!(!e.is_retryable());
+    }
+
     #[test]
     fn test_error_code_mapping_storage() {
         let e = StrataError::storage("disk full");
@@ -33,13 +33,16 @@ pub use error::{
 };
 pub use limits::{LimitError, Limits};
 pub use traits::{SnapshotView, Storage};
-pub use types::{validate_space_name, BranchId, Key, Namespace, TypeTag};
+pub use types::{
+    validate_space_name, BranchId, Key, KeyNormalization, Namespace, TypeTag, BRANCH_INDEX_PREFIX,
+    EVENT_META_KEY, EVENT_TYPE_INDEX_PREFIX,
+};
 pub use value::Value;
 
 // Re-export contract types at crate root for convenience
 pub use contract::{
-    BranchName, BranchNameError, EntityRef, PrimitiveType, Timestamp, Version, Versioned,
-    VersionedHistory, VersionedValue, MAX_BRANCH_NAME_LENGTH,
+    BranchName, BranchNameError, Clock, EntityRef, ManualClock, PrimitiveType, SystemClock,
+    Timestamp, Version, Versioned, VersionedHistory, VersionedValue, MAX_BRANCH_NAME_LENGTH,
 };
 
 // Re-export primitive extension trait and helpers
@@ -65,9 +68,11 @@ pub use primitives::{
     DistanceMetric,
     Event,
     JsonLimitError,
+    JsonLimits,
     JsonPatch,
     JsonPath,
     JsonPathError,
+    JsonPathValue,
     JsonScalar,
     JsonValue,
     MetadataFilter,
@@ -80,6 +85,11 @@ pub use primitives::{
     VectorEntry,
     VectorId,
     VectorMatch,
+    VectorStorage,
+    HARD_MAX_ARRAY_SIZE,
+    HARD_MAX_DOCUMENT_SIZE,
+    HARD_MAX_NESTING_DEPTH,
+    HARD_MAX_PATH_LENGTH,
     MAX_ARRAY_SIZE,
     MAX_DOCUMENT_SIZE,
     MAX_NESTING_DEPTH,
@@ -31,7 +31,7 @@ pub struct Event {
 /// Chain verification result
 ///
 /// Returned by `verify_chain()` to report the integrity status of an event chain.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ChainVerification {
     /// Whether the chain is valid
     pub is_valid: bool,
@@ -41,6 +41,12 @@ pub struct ChainVerification {
     pub first_invalid: Option<u64>,
     /// Error description (if any)
     pub error: Option<String>,
+    /// Hash the chain expected at the first break (if it was a hash mismatch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_hash: Option<[u8; 32]>,
+    /// Hash actually found at the first break (if it was a hash mismatch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_hash: Option<[u8; 32]>,
 }
 
 impl ChainVerification {
@@ -51,6 +57,8 @@ impl ChainVerification {
             length,
             first_invalid: None,
             error: None,
+            expected_hash: None,
+            actual_hash: None,
         }
     }
 
@@ -61,6 +69,24 @@ impl ChainVerification {
             length,
             first_invalid: Some(first_invalid),
             error: Some(error.into()),
+            expected_hash: None,
+            actual_hash: None,
+        }
+    }
+
+    /// Create an invalid verification result for a hash that does not match
+    /// the one recomputed from the chain
+    pub fn hash_mismatch(
+        length: u64,
+        first_invalid: u64,
+        error: impl Into<String>,
+        expected: [u8; 32],
+        actual: [u8; 32],
+    ) -> Self {
+        Self {
+            expected_hash: Some(expected),
+            actual_hash: Some(actual),
+            ..Self::invalid(length, first_invalid, error)
         }
     }
 }
@@ -173,6 +199,24 @@ mod tests {
         assert_eq!(cv.error.as_deref(), Some("broken chain"));
     }
 
+    #[test]
+    fn test_chain_verification_hash_mismatch() {
+        let cv = ChainVerification::hash_mismatch(10, 3, "hash mismatch", [1u8; 32], [2u8; 32]);
+        assert!(!cv.is_valid);
+        assert_eq!(cv.first_invalid, Some(3));
+        assert_eq!(cv.expected_hash, Some([1u8; 32]));
+        assert_eq!(cv.actual_hash, Some([2u8; 32]));
+
+        let json = serde_json::to_string(&cv).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ChainVerification>(&json).unwrap(),
+            cv
+        );
+        assert!(!serde_json::to_string(&ChainVerification::valid(1))
+            .unwrap()
+            .contains("expected_hash"));
+    }
+
     #[test]
     fn test_event_empty_event_type() {
         let event = Event {
@@ -10,12 +10,15 @@
 //!
 //! This module enforces the following limits to prevent memory issues:
 //!
-//! | Limit | Value | Constant |
-//! |-------|-------|----------|
-//! | Max document size | 16 MB | [`MAX_DOCUMENT_SIZE`] |
-//! | Max nesting depth | 100 levels | [`MAX_NESTING_DEPTH`] |
-//! | Max path length | 256 segments | [`MAX_PATH_LENGTH`] |
-//! | Max array size | 1M elements | [`MAX_ARRAY_SIZE`] |
+//! | Limit | Default | Constant | Hard ceiling |
+//! |-------|---------|----------|--------------|
+//! | Max document size | 16 MB | [`MAX_DOCUMENT_SIZE`] | 256 MB |
+//! | Max nesting depth | 100 levels | [`MAX_NESTING_DEPTH`] | 512 levels |
+//! | Max path length | 256 segments | [`MAX_PATH_LENGTH`] | 1024 segments |
+//! | Max array size | 1M elements | [`MAX_ARRAY_SIZE`] | 100M elements |
+//!
+//! A database can override the defaults through [`JsonLimits`]; overrides
+//! above the hard ceiling are lowered to it.
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -50,6 +53,62 @@ pub const MAX_PATH_LENGTH: usize = 256;
 /// Prevents creation of extremely large arrays that could cause memory issues.
 pub const MAX_ARRAY_SIZE: usize = 1_000_000;
 
+/// Highest configurable document size in bytes (256 MB)
+pub const HARD_MAX_DOCUMENT_SIZE: usize = 256 * 1024 * 1024;
+
+/// Highest configurable nesting depth (512 levels)
+///
+/// Serialization, conversion and path traversal recurse once per level.
+/// This ceiling keeps them well inside a thread's stack whatever a
+/// database is configured to accept.
+pub const HARD_MAX_NESTING_DEPTH: usize = 512;
+
+/// Highest configurable path length in segments (1024 segments)
+pub const HARD_MAX_PATH_LENGTH: usize = 1024;
+
+/// Highest configurable array size in elements (100 million elements)
+pub const HARD_MAX_ARRAY_SIZE: usize = 100_000_000;
+
+/// JSON document limits enforced by a database
+///
+/// Defaults to [`MAX_DOCUMENT_SIZE`], [`MAX_NESTING_DEPTH`],
+/// [`MAX_PATH_LENGTH`] and [`MAX_ARRAY_SIZE`]. Use [`clamped`](Self::clamped)
+/// to keep configured values under the `HARD_MAX_*` ceilings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonLimits {
+    /// Maximum document size in bytes
+    pub max_document_size: usize,
+    /// Maximum nesting depth
+    pub max_nesting_depth: usize,
+    /// Maximum path length in segments
+    pub max_path_length: usize,
+    /// Maximum array size in elements
+    pub max_array_size: usize,
+}
+
+impl Default for JsonLimits {
+    fn default() -> Self {
+        JsonLimits {
+            max_document_size: MAX_DOCUMENT_SIZE,
+            max_nesting_depth: MAX_NESTING_DEPTH,
+            max_path_length: MAX_PATH_LENGTH,
+            max_array_size: MAX_ARRAY_SIZE,
+        }
+    }
+}
+
+impl JsonLimits {
+    /// Lower each limit that exceeds its hard ceiling to the ceiling
+    pub fn clamped(self) -> Self {
+        JsonLimits {
+            max_document_size: self.max_document_size.min(HARD_MAX_DOCUMENT_SIZE),
+            max_nesting_depth: self.max_nesting_depth.min(HARD_MAX_NESTING_DEPTH),
+            max_path_length: self.max_path_length.min(HARD_MAX_PATH_LENGTH),
+            max_array_size: self.max_array_size.min(HARD_MAX_ARRAY_SIZE),
+        }
+    }
+}
+
 /// Error type for JSON document limit violations
 ///
 /// This error type is specific to JSON document constraints (size, nesting, paths).
@@ -270,6 +329,34 @@ impl JsonValue {
         self.validate_array_size()?;
         Ok(())
     }
+
+    /// Validate all document limits against configured `limits`
+    ///
+    /// Same checks as [`validate`](Self::validate), in the same order.
+    pub fn validate_with(&self, limits: &JsonLimits) -> Result<(), JsonLimitError> {
+        let size = self.size_bytes();
+        if size > limits.max_document_size {
+            return Err(JsonLimitError::DocumentTooLarge {
+                size,
+                max: limits.max_document_size,
+            });
+        }
+        let depth = self.nesting_depth();
+        if depth > limits.max_nesting_depth {
+            return Err(JsonLimitError::NestingTooDeep {
+                depth,
+                max: limits.max_nesting_depth,
+            });
+        }
+        let size = self.max_array_size();
+        if size > limits.max_array_size {
+            return Err(JsonLimitError::ArrayTooLarge {
+                size,
+                max: limits.max_array_size,
+            });
+        }
+        Ok(())
+    }
 }
 
 // Implement FromStr for parsing from strings
@@ -647,6 +734,19 @@ impl JsonPath {
         }
     }
 
+    /// Validate path length against configured `limits`
+    pub fn validate_with(&self, limits: &JsonLimits) -> Result<(), JsonLimitError> {
+        let length = self.segments.len();
+        if length > limits.max_path_length {
+            Err(JsonLimitError::PathTooLong {
+                length,
+                max: limits.max_path_length,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
     /// Convert to a string representation
     pub fn to_path_string(&self) -> String {
         if self.segments.is_empty() {
@@ -1028,6 +1128,68 @@ pub fn get_at_path<'a>(value: &'a JsonValue, path: &JsonPath) -> Option<&'a Json
     Some(unsafe { &*(current as *const serde_json::Value as *const JsonValue) })
 }
 
+/// Result of reading a path within a JSON document
+///
+/// Unlike `Option<JsonValue>`, this keeps "the path holds JSON `null`"
+/// visibly distinct from "the path does not exist", which patch logic and
+/// round-tripping depend on.
+///
+/// # Example
+///
+/// ```
+/// use strata_core::primitives::json::{get_at_path, JsonPath, JsonPathValue, JsonValue};
+///
+/// let json: JsonValue = serde_json::json!({"a": null}).into();
+/// let read = |p: &str| JsonPathValue::from(get_at_path(&json, &p.parse::<JsonPath>().unwrap()));
+///
+/// assert!(read("a").is_null());
+/// assert!(read("b").is_missing());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonPathValue {
+    /// The document or the path does not exist
+    Missing,
+    /// The path exists; the value may be JSON `null`
+    Present(JsonValue),
+}
+
+impl JsonPathValue {
+    /// Whether the path does not exist
+    pub fn is_missing(&self) -> bool {
+        matches!(self, JsonPathValue::Missing)
+    }
+
+    /// Whether the path exists and holds JSON `null`
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonPathValue::Present(v) if v.is_null())
+    }
+
+    /// Borrow the value if the path exists
+    pub fn as_present(&self) -> Option<&JsonValue> {
+        match self {
+            JsonPathValue::Missing => None,
+            JsonPathValue::Present(v) => Some(v),
+        }
+    }
+
+    /// Convert into the value if the path exists
+    pub fn into_present(self) -> Option<JsonValue> {
+        match self {
+            JsonPathValue::Missing => None,
+            JsonPathValue::Present(v) => Some(v),
+        }
+    }
+}
+
+impl From<Option<&JsonValue>> for JsonPathValue {
+    fn from(value: Option<&JsonValue>) -> Self {
+        match value {
+            Some(v) => JsonPathValue::Present(v.clone()),
+            None => JsonPathValue::Missing,
+        }
+    }
+}
+
 /// Get mutable reference to value at path within a JSON document
 ///
 /// Traverses the document following the path segments, returning a mutable
@@ -2506,6 +2668,63 @@ mod tests {
         assert!(matches!(result, Err(JsonLimitError::PathTooLong { .. })));
     }
 
+    #[test]
+    fn test_validate_with_configured_limits() {
+        let depth = MAX_NESTING_DEPTH + 5;
+        let mut json_str = String::new();
+        for _ in 0..depth {
+            json_str.push_str(r#"{"a":"#);
+        }
+        json_str.push('1');
+        for _ in 0..depth {
+            json_str.push('}');
+        }
+        let v: JsonValue = json_str.parse().expect("Should parse valid JSON");
+
+        assert_eq!(v.validate_with(&JsonLimits::default()), v.validate());
+        let deep = JsonLimits {
+            max_nesting_depth: depth,
+            ..JsonLimits::default()
+        };
+        assert!(v.validate_with(&deep).is_ok());
+
+        let tight = JsonLimits {
+            max_array_size: 2,
+            ..JsonLimits::default()
+        };
+        let arr = JsonValue::from(serde_json::json!([1, 2, 3]));
+        assert_eq!(
+            arr.validate_with(&tight),
+            Err(JsonLimitError::ArrayTooLarge { size: 3, max: 2 })
+        );
+
+        let path = JsonPath::root().key("a").key("b");
+        let short = JsonLimits {
+            max_path_length: 1,
+            ..JsonLimits::default()
+        };
+        assert_eq!(
+            path.validate_with(&short),
+            Err(JsonLimitError::PathTooLong { length: 2, max: 1 })
+        );
+    }
+
+    #[test]
+    fn test_json_limits_clamped_to_hard_ceiling() {
+        let limits = JsonLimits {
+            max_document_size: usize::MAX,
+            max_nesting_depth: usize::MAX,
+            max_path_length: 10,
+            max_array_size: usize::MAX,
+        }
+        .clamped();
+        assert_eq!(limits.max_document_size, HARD_MAX_DOCUMENT_SIZE);
+        assert_eq!(limits.max_nesting_depth, HARD_MAX_NESTING_DEPTH);
+        assert_eq!(limits.max_path_length, 10);
+        assert_eq!(limits.max_array_size, HARD_MAX_ARRAY_SIZE);
+        assert_eq!(JsonLimits::default().clamped(), JsonLimits::default());
+    }
+
     #[test]
     fn test_limit_error_display() {
         let err = JsonLimitError::DocumentTooLarge {
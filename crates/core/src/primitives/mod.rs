@@ -20,11 +20,13 @@ pub mod vector;
 pub use event::{ChainVerification, Event};
 pub use json::{
     apply_patches, delete_at_path, get_at_path, get_at_path_mut, merge_patch, set_at_path,
-    JsonLimitError, JsonPatch, JsonPath, JsonPathError, JsonValue, PathParseError, PathSegment,
-    MAX_ARRAY_SIZE, MAX_DOCUMENT_SIZE, MAX_NESTING_DEPTH, MAX_PATH_LENGTH,
+    JsonLimitError, JsonLimits, JsonPatch, JsonPath, JsonPathError, JsonPathValue, JsonValue,
+    PathParseError, PathSegment, HARD_MAX_ARRAY_SIZE, HARD_MAX_DOCUMENT_SIZE,
+    HARD_MAX_NESTING_DEPTH, HARD_MAX_PATH_LENGTH, MAX_ARRAY_SIZE, MAX_DOCUMENT_SIZE,
+    MAX_NESTING_DEPTH, MAX_PATH_LENGTH,
 };
 pub use state::State;
 pub use vector::{
     CollectionId, CollectionInfo, DistanceMetric, FilterCondition, FilterOp, JsonScalar,
-    MetadataFilter, StorageDtype, VectorConfig, VectorEntry, VectorId, VectorMatch,
+    MetadataFilter, StorageDtype, VectorConfig, VectorEntry, VectorId, VectorMatch, VectorStorage,
 };
@@ -34,6 +34,11 @@ pub enum DistanceMetric {
     /// WARNING: Assumes vectors are normalized. Non-normalized vectors
     /// will produce unbounded scores.
     DotProduct,
+
+    /// Manhattan similarity: 1 / (1 + l1_distance)
+    /// Range: (0, 1], higher = more similar
+    /// Best for: quantized or sparse embeddings, robustness to outliers
+    Manhattan,
 }
 
 impl DistanceMetric {
@@ -43,6 +48,7 @@ impl DistanceMetric {
             DistanceMetric::Cosine => "cosine",
             DistanceMetric::Euclidean => "euclidean",
             DistanceMetric::DotProduct => "dot_product",
+            DistanceMetric::Manhattan => "manhattan",
         }
     }
 
@@ -52,6 +58,7 @@ impl DistanceMetric {
             "cosine" => Some(DistanceMetric::Cosine),
             "euclidean" | "l2" => Some(DistanceMetric::Euclidean),
             "dot_product" | "dot" | "inner_product" => Some(DistanceMetric::DotProduct),
+            "manhattan" | "l1" => Some(DistanceMetric::Manhattan),
             _ => None,
         }
     }
@@ -62,6 +69,7 @@ impl DistanceMetric {
             DistanceMetric::Cosine => 0,
             DistanceMetric::Euclidean => 1,
             DistanceMetric::DotProduct => 2,
+            DistanceMetric::Manhattan => 3,
         }
     }
 
@@ -71,6 +79,7 @@ impl DistanceMetric {
             0 => Some(DistanceMetric::Cosine),
             1 => Some(DistanceMetric::Euclidean),
             2 => Some(DistanceMetric::DotProduct),
+            3 => Some(DistanceMetric::Manhattan),
             _ => None,
         }
     }
@@ -105,6 +114,24 @@ impl StorageDtype {
     }
 }
 
+/// Where vector embeddings live while a disk-backed database is open
+///
+/// Both modes keep a `.vec` file per collection on disk; they differ in how
+/// the running process holds it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorStorage {
+    /// Memory-map the `.vec` files and let the OS page embeddings in and
+    /// out (default). Resident memory stays bounded, but a search touching
+    /// cold pages waits on disk reads.
+    #[default]
+    Mmap,
+    /// Read every embedding into process memory on open and keep it there.
+    /// Searches never wait on disk, at the cost of memory proportional to
+    /// the number of vectors times their dimension.
+    InMemory,
+}
+
 /// Collection configuration - immutable after creation
 ///
 /// IMPORTANT: This struct must NOT contain backend-specific fields.
@@ -304,6 +331,10 @@ pub struct VectorMatch {
 
     /// Optional metadata (if requested and present)
     pub metadata: Option<serde_json::Value>,
+
+    /// Embedding of the matched vector (None if unavailable)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
 }
 
 impl VectorMatch {
@@ -313,6 +344,7 @@ impl VectorMatch {
             key,
             score,
             metadata,
+            embedding: None,
         }
     }
 }
@@ -455,10 +487,12 @@ pub enum FilterOp {
     Lt,
     /// Less than or equal (numeric only)
     Lte,
-    /// Value is one of a list (value must be an array of scalars)
+    /// Value is one of a list (one condition per listed scalar)
     In,
     /// String contains substring (string only)
     Contains,
+    /// Value is none of a list (one condition per listed scalar)
+    NotIn,
 }
 
 /// A single filter condition on a metadata field
@@ -566,6 +600,19 @@ impl MetadataFilter {
         self
     }
 
+    /// Add a "not in" condition (value must match none of the provided scalars)
+    pub fn not_in_values(mut self, field: impl Into<String>, values: Vec<JsonScalar>) -> Self {
+        let field_name: String = field.into();
+        for val in values {
+            self.conditions.push(FilterCondition {
+                field: field_name.clone(),
+                op: FilterOp::NotIn,
+                value: val,
+            });
+        }
+        self
+    }
+
     /// Add a string-contains condition (string only)
     pub fn contains(mut self, field: impl Into<String>, substring: impl Into<String>) -> Self {
         self.conditions.push(FilterCondition {
@@ -669,6 +716,8 @@ fn eval_condition(op: &FilterOp, expected: &JsonScalar, actual: &serde_json::Val
             // Single value check (grouped evaluation is done in matches())
             expected.matches_json(actual)
         }
+        // Every listed value must differ, so each condition stands alone
+        FilterOp::NotIn => !expected.matches_json(actual),
         FilterOp::Contains => {
             // String contains substring
             match (expected, actual) {
@@ -746,6 +795,7 @@ mod tests {
         assert_eq!(DistanceMetric::Cosine.name(), "cosine");
         assert_eq!(DistanceMetric::Euclidean.name(), "euclidean");
         assert_eq!(DistanceMetric::DotProduct.name(), "dot_product");
+        assert_eq!(DistanceMetric::Manhattan.name(), "manhattan");
     }
 
     #[test]
@@ -776,6 +826,11 @@ mod tests {
             DistanceMetric::parse("inner_product"),
             Some(DistanceMetric::DotProduct)
         );
+        assert_eq!(
+            DistanceMetric::parse("manhattan"),
+            Some(DistanceMetric::Manhattan)
+        );
+        assert_eq!(DistanceMetric::parse("l1"), Some(DistanceMetric::Manhattan));
         assert_eq!(
             DistanceMetric::parse("COSINE"),
             Some(DistanceMetric::Cosine)
@@ -789,6 +844,7 @@ mod tests {
             DistanceMetric::Cosine,
             DistanceMetric::Euclidean,
             DistanceMetric::DotProduct,
+            DistanceMetric::Manhattan,
         ] {
             let byte = metric.to_byte();
             let restored = DistanceMetric::from_byte(byte).unwrap();
@@ -1022,6 +1078,57 @@ mod tests {
         assert!(filter.matches(&meta));
     }
 
+    #[test]
+    fn test_metadata_filter_range_and_set_ops_on_mixed_metadata() {
+        let docs = [
+            serde_json::json!({"score": 0.9, "tag": "a"}),
+            serde_json::json!({"score": 0.8, "tag": "b"}),
+            serde_json::json!({"score": "high", "tag": "c"}),
+            serde_json::json!({"score": 0.5, "tag": 3}),
+            serde_json::json!({"tag": "a"}),
+        ];
+        let matching = |filter: MetadataFilter| -> Vec<usize> {
+            (0..docs.len())
+                .filter(|&i| filter.matches(&Some(docs[i].clone())))
+                .collect()
+        };
+
+        // Range ops compare numbers only; strings and missing fields never match
+        assert_eq!(matching(MetadataFilter::new().gt("score", 0.8)), vec![0]);
+        assert_eq!(
+            matching(MetadataFilter::new().gte("score", 0.8)),
+            vec![0, 1]
+        );
+        assert_eq!(matching(MetadataFilter::new().lt("score", 0.8)), vec![3]);
+        assert_eq!(
+            matching(MetadataFilter::new().lte("score", 0.8)),
+            vec![1, 3]
+        );
+        assert_eq!(
+            matching(MetadataFilter::new().gte("score", 0.5).lt("score", 0.9)),
+            vec![1, 3]
+        );
+
+        // Set membership compares by type: the string "3" is not the number 3
+        assert_eq!(
+            matching(MetadataFilter::new().in_values("tag", vec!["a".into(), 3.into()])),
+            vec![0, 3, 4]
+        );
+        assert_eq!(
+            matching(MetadataFilter::new().in_values("tag", vec!["3".into()])),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            matching(MetadataFilter::new().not_in_values("tag", vec!["a".into(), "b".into()])),
+            vec![2, 3]
+        );
+        // Like `ne`, `not in` requires the field to be present
+        assert_eq!(
+            matching(MetadataFilter::new().not_in_values("score", vec![0.9.into()])),
+            vec![1, 2, 3]
+        );
+    }
+
     #[test]
     fn test_metadata_filter_array_metadata_no_match() {
         let filter = MetadataFilter::new().eq("key", "val");
@@ -1195,6 +1302,7 @@ mod tests {
             DistanceMetric::Cosine,
             DistanceMetric::Euclidean,
             DistanceMetric::DotProduct,
+            DistanceMetric::Manhattan,
         ] {
             let json = serde_json::to_string(&metric).unwrap();
             let restored: DistanceMetric = serde_json::from_str(&json).unwrap();
@@ -1209,8 +1317,8 @@ mod tests {
 
     #[test]
     fn test_distance_metric_from_byte_reserved_values() {
-        // Bytes 3+ are not assigned - should return None
-        for b in 3..=255u8 {
+        // Bytes 4+ are not assigned - should return None
+        for b in 4..=255u8 {
             assert!(
                 DistanceMetric::from_byte(b).is_none(),
                 "Byte {} should not map to a metric",
@@ -5,7 +5,7 @@
 
 use std::time::Duration;
 
-use crate::contract::VersionedValue;
+use crate::contract::{Timestamp, VersionedValue};
 use crate::error::StrataResult;
 use crate::types::{BranchId, Key};
 use crate::value::Value;
@@ -203,6 +203,14 @@ pub trait SnapshotView: Send + Sync {
     ///
     /// Returns the version this snapshot was created at.
     fn version(&self) -> u64;
+
+    /// Current time according to the storage's clock
+    ///
+    /// Transactions use this to timestamp the records they write, so an
+    /// injected [`Clock`](crate::Clock) reaches them. Defaults to system time.
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
 }
 
 #[cfg(test)]
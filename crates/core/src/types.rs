@@ -5,8 +5,10 @@
 //! - Namespace: Hierarchical namespace (tenant/app/agent/branch/space)
 //! - TypeTag: Type discriminator for unified storage
 //! - Key: Composite key (namespace + type_tag + user_key)
+//! - KeyNormalization: Canonicalization applied to user keys
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 use uuid::Uuid;
 
@@ -223,6 +225,17 @@ impl TypeTag {
     }
 }
 
+/// User key of the per-space event log metadata entry
+pub const EVENT_META_KEY: &[u8] = b"__meta__";
+
+/// User key prefix of event type index entries
+pub const EVENT_TYPE_INDEX_PREFIX: &[u8] = b"__tidx__";
+
+/// User key prefix of branch index secondary entries. Branch names are
+/// stored as user keys next to these entries, so names containing this
+/// marker are rejected.
+pub const BRANCH_INDEX_PREFIX: &str = "__idx_";
+
 /// Unified key for all storage types
 ///
 /// A Key combines namespace, type tag, and user-defined key bytes to create
@@ -297,7 +310,7 @@ impl Key {
     ///
     /// The metadata key stores: { next_sequence: u64, head_hash: [u8; 32] }
     pub fn new_event_meta(namespace: Namespace) -> Self {
-        Self::new(namespace, TypeTag::Event, b"__meta__".to_vec())
+        Self::new(namespace, TypeTag::Event, EVENT_META_KEY.to_vec())
     }
 
     /// Create an event type index key
@@ -310,7 +323,7 @@ impl Key {
     /// sequence bytes ensure results are returned in sequence order.
     pub fn new_event_type_idx(namespace: Namespace, event_type: &str, sequence: u64) -> Self {
         let mut user_key = Vec::with_capacity(8 + event_type.len() + 1 + 8);
-        user_key.extend_from_slice(b"__tidx__");
+        user_key.extend_from_slice(EVENT_TYPE_INDEX_PREFIX);
         user_key.extend_from_slice(event_type.as_bytes());
         user_key.push(0); // null separator
         user_key.extend_from_slice(&sequence.to_be_bytes());
@@ -322,7 +335,7 @@ impl Key {
     /// Used by `get_by_type` to find all sequence numbers for a specific event type.
     pub fn new_event_type_idx_prefix(namespace: Namespace, event_type: &str) -> Self {
         let mut user_key = Vec::with_capacity(8 + event_type.len() + 1);
-        user_key.extend_from_slice(b"__tidx__");
+        user_key.extend_from_slice(EVENT_TYPE_INDEX_PREFIX);
         user_key.extend_from_slice(event_type.as_bytes());
         user_key.push(0); // null separator
         Self::new(namespace, TypeTag::Event, user_key)
@@ -365,7 +378,10 @@ impl Key {
         index_value: &str,
         branch_id: &str,
     ) -> Self {
-        let key_data = format!("__idx_{}__{}__{}", index_type, index_value, branch_id);
+        let key_data = format!(
+            "{}{}__{}__{}",
+            BRANCH_INDEX_PREFIX, index_type, index_value, branch_id
+        );
         Self::new(namespace, TypeTag::Branch, key_data.into_bytes())
     }
 
@@ -449,6 +465,22 @@ impl Key {
         String::from_utf8(self.user_key.clone()).ok()
     }
 
+    /// Whether this key holds primitive bookkeeping rather than user data
+    ///
+    /// True for event log metadata, event type index entries and branch
+    /// index secondary entries. Snapshots, diffs and index rebuilds skip
+    /// these keys.
+    pub fn is_internal(&self) -> bool {
+        match self.type_tag {
+            TypeTag::Event => {
+                self.user_key == EVENT_META_KEY
+                    || self.user_key.starts_with(EVENT_TYPE_INDEX_PREFIX)
+            }
+            TypeTag::Branch => self.user_key.starts_with(BRANCH_INDEX_PREFIX.as_bytes()),
+            _ => false,
+        }
+    }
+
     /// Check if this key starts with the given prefix
     ///
     /// For a key to match a prefix:
@@ -491,6 +523,34 @@ impl PartialOrd for Key {
     }
 }
 
+/// How user keys are canonicalized before they reach storage
+///
+/// Applied to KV keys, JSON document keys, state cell names, vector keys
+/// and blob keys on both write and read, so keys that normalize alike
+/// address the same entry. Keys already stored are never rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyNormalization {
+    /// Use keys exactly as given (default)
+    #[default]
+    Identity,
+    /// Lowercase keys (Unicode-aware), so `"User:123"` and `"user:123"`
+    /// are the same key
+    Lowercase,
+}
+
+impl KeyNormalization {
+    /// Canonical form of `key`, borrowed when it is already canonical
+    pub fn apply<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        match self {
+            KeyNormalization::Lowercase if key.chars().any(char::is_uppercase) => {
+                Cow::Owned(key.to_lowercase())
+            }
+            _ => Cow::Borrowed(key),
+        }
+    }
+}
+
 /// Validate a space name according to naming rules.
 ///
 /// Rules:
@@ -1266,6 +1326,22 @@ mod tests {
             .contains("__idx_tag__experiment__branch-456"));
     }
 
+    #[test]
+    fn test_is_internal() {
+        let ns = Namespace::for_branch(BranchId::new());
+
+        assert!(Key::new_event_meta(ns.clone()).is_internal());
+        assert!(Key::new_event_type_idx(ns.clone(), "click", 1).is_internal());
+        assert!(Key::new_branch_index(ns.clone(), "status", "Active", "b").is_internal());
+        assert!(!Key::new_event(ns.clone(), 1).is_internal());
+        assert!(!Key::new_branch_with_id(ns.clone(), "b").is_internal());
+
+        // The markers only mean something under their own type tag
+        assert!(!Key::new_kv(ns.clone(), "__meta__").is_internal());
+        assert!(!Key::new_state(ns.clone(), "__idx_x").is_internal());
+        assert!(!Key::new_json(ns, "__tidx__x").is_internal());
+    }
+
     #[test]
     fn test_user_key_string() {
         let branch_id = BranchId::new();
@@ -1759,4 +1835,16 @@ mod tests {
             "JSON key should not match KV prefix"
         );
     }
+
+    #[test]
+    fn test_key_normalization() {
+        let identity = KeyNormalization::default();
+        assert_eq!(identity, KeyNormalization::Identity);
+        assert_eq!(identity.apply("User:123"), "User:123");
+
+        let lower = KeyNormalization::Lowercase;
+        assert_eq!(lower.apply("User:123"), "user:123");
+        assert_eq!(lower.apply("STRASSE-Ä"), "strasse-ä");
+        assert!(matches!(lower.apply("user:123"), Cow::Borrowed(_)));
+    }
 }
@@ -198,6 +198,35 @@ impl Value {
             _ => None,
         }
     }
+
+    /// Convert to natural JSON for display and logging
+    ///
+    /// Ints and floats become numbers, strings become strings and bytes become
+    /// base64 strings, without the type wrappers of the serde wire form
+    /// (`{"Int": 42}`). The result is **lossy**: `Bytes` and `String` are
+    /// indistinguishable, `Int(1)` and `Float(1.0)` may both print as `1`,
+    /// and non-finite floats become `null`. Do not parse it back into a
+    /// `Value`; use the serde encoding when the value must round-trip.
+    pub fn to_plain_json(&self) -> serde_json::Value {
+        match self {
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Int(i) => serde_json::Value::Number((*i).into()),
+            Value::Float(f) => serde_json::Number::from_f64(*f)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Bytes(b) => serde_json::Value::String(base64_encode(b)),
+            Value::Array(arr) => {
+                serde_json::Value::Array(arr.iter().map(Value::to_plain_json).collect())
+            }
+            Value::Object(obj) => serde_json::Value::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), v.to_plain_json()))
+                    .collect(),
+            ),
+        }
+    }
 }
 
 // ============================================================================
@@ -308,27 +337,7 @@ impl From<serde_json::Value> for Value {
 
 impl From<Value> for serde_json::Value {
     fn from(v: Value) -> Self {
-        match v {
-            Value::Null => serde_json::Value::Null,
-            Value::Bool(b) => serde_json::Value::Bool(b),
-            Value::Int(i) => serde_json::Value::Number(i.into()),
-            Value::Float(f) => serde_json::Number::from_f64(f)
-                .map(serde_json::Value::Number)
-                .unwrap_or(serde_json::Value::Null),
-            Value::String(s) => serde_json::Value::String(s),
-            Value::Bytes(b) => {
-                // Encode bytes as base64 string for JSON compatibility
-                serde_json::Value::String(base64_encode(&b))
-            }
-            Value::Array(arr) => {
-                serde_json::Value::Array(arr.into_iter().map(serde_json::Value::from).collect())
-            }
-            Value::Object(obj) => serde_json::Value::Object(
-                obj.into_iter()
-                    .map(|(k, v)| (k, serde_json::Value::from(v)))
-                    .collect(),
-            ),
-        }
+        v.to_plain_json()
     }
 }
 
@@ -657,6 +666,28 @@ mod tests {
     // as_* returns None for wrong types
     // ====================================================================
 
+    #[test]
+    fn test_to_plain_json() {
+        let mut obj = HashMap::new();
+        obj.insert("n".to_string(), Value::Int(42));
+        obj.insert("blob".to_string(), Value::Bytes(b"foo".to_vec()));
+        obj.insert(
+            "list".to_string(),
+            Value::Array(vec![Value::Float(1.5), Value::String("x".into())]),
+        );
+        let json = Value::Object(obj).to_plain_json();
+        assert_eq!(
+            json,
+            serde_json::json!({"n": 42, "blob": "Zm9v", "list": [1.5, "x"]})
+        );
+        // No type wrappers, unlike the serde encoding
+        assert_eq!(Value::Int(42).to_plain_json().to_string(), "42");
+        assert_eq!(
+            serde_json::to_string(&Value::Int(42)).unwrap(),
+            r#"{"Int":42}"#
+        );
+    }
+
     #[test]
     fn test_as_wrong_type_returns_none() {
         let v = Value::Int(42);
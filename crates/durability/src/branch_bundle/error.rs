@@ -33,6 +33,10 @@ pub enum BranchBundleError {
         actual: String,
     },
 
+    /// Bundle signature is missing, malformed, or does not verify
+    #[error("Signature verification failed: {0}")]
+    Signature(String),
+
     /// Branch already exists (import conflict)
     #[error("Branch already exists: {0}")]
     BranchAlreadyExists(String),
@@ -91,6 +95,11 @@ impl BranchBundleError {
         Self::Compression(msg.into())
     }
 
+    /// Create a signature verification error
+    pub fn signature(msg: impl Into<String>) -> Self {
+        Self::Signature(msg.into())
+    }
+
     /// Create a serialization error
     pub fn serialization(msg: impl Into<String>) -> Self {
         Self::Serialization(msg.into())
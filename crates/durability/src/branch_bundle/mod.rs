@@ -8,7 +8,7 @@
 //! ```text
 //! archive.branchbundle.tar.zst
 //! └── branchbundle/
-//!     ├── MANIFEST.json   — format version, checksums
+//!     ├── MANIFEST.json   — format version, checksums, optional digest and signature
 //!     ├── BRANCH.json     — branch metadata (name, state, timestamps)
 //!     └── WAL.branchlog   — binary log of BranchlogPayload entries (msgpack v2)
 //! ```
@@ -36,6 +36,15 @@
 //! let info = db.import_branch(Path::new("./my-branch.branchbundle.tar.zst"))?;
 //! ```
 //!
+//! ## Tamper Evidence
+//!
+//! With [`ExportOptions::include_digest`], the manifest also records the
+//! SHA-256 of each section and a digest over those hashes. Readers check the
+//! digest whenever it is present, so an edited section fails import. With the
+//! `signing` feature, `ExportOptions::signing_key` adds an Ed25519
+//! signature over the digest, which `BranchBundleReader::verify_signature`
+//! checks against a trusted public key. Generating, storing and distributing keys is up to the caller.
+//!
 //! ## Design Principles
 //!
 //! - **Explicit**: All operations are explicit, no background behavior
@@ -54,9 +63,13 @@ pub mod writer;
 pub use error::{BranchBundleError, BranchBundleResult};
 pub use reader::{BranchBundleReader, BundleContents as ReadBundleContents};
 pub use types::{
-    paths, xxh3_hex, BranchExportInfo, BundleBranchInfo, BundleContents, BundleManifest,
-    BundleVerifyInfo, ExportOptions, ImportedBranchInfo, BRANCHBUNDLE_EXTENSION,
-    BRANCHBUNDLE_FORMAT_VERSION, WAL_BRANCHLOG_MAGIC, WAL_BRANCHLOG_VERSION,
+    paths, sha256_hex, xxh3_hex, BranchExportInfo, BundleBranchInfo, BundleContents,
+    BundleManifest, BundleSignature, BundleVerifyInfo, ExportOptions, ImportedBranchInfo,
+    BRANCHBUNDLE_EXTENSION, BRANCHBUNDLE_FORMAT_VERSION, WAL_BRANCHLOG_MAGIC,
+    WAL_BRANCHLOG_VERSION,
 };
 pub use wal_log::{BranchlogPayload, WalLogInfo, WalLogIterator, WalLogReader, WalLogWriter};
 pub use writer::BranchBundleWriter;
+
+#[cfg(feature = "signing")]
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
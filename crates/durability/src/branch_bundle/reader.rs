@@ -4,7 +4,7 @@
 
 use crate::branch_bundle::error::{BranchBundleError, BranchBundleResult};
 use crate::branch_bundle::types::{
-    paths, xxh3_hex, BundleBranchInfo, BundleManifest, BundleVerifyInfo,
+    paths, sha256_hex, xxh3_hex, BundleBranchInfo, BundleManifest, BundleVerifyInfo,
     BRANCHBUNDLE_FORMAT_VERSION,
 };
 use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogReader};
@@ -26,6 +26,7 @@ impl BranchBundleReader {
     /// - Archive can be decompressed
     /// - Required files exist (MANIFEST.json, BRANCH.json, WAL.branchlog)
     /// - Checksums match manifest
+    /// - SHA-256 section hashes match the digest, if the bundle has one
     /// - WAL.branchlog header is valid
     pub fn validate(path: &Path) -> BranchBundleResult<BundleVerifyInfo> {
         let files = Self::extract_all_files(path)?;
@@ -74,11 +75,17 @@ impl BranchBundleReader {
         // Parse branch info for branch_id
         let branch_info: BundleBranchInfo = serde_json::from_slice(branch_data)?;
 
+        let digest_valid = match Self::verify_digest(&manifest, &files) {
+            Ok(has_digest) => has_digest.then_some(true),
+            Err(_) => Some(false),
+        };
+
         Ok(BundleVerifyInfo {
             branch_id: branch_info.branch_id,
             format_version: manifest.format_version,
             wal_entry_count: manifest.contents.wal_entry_count,
             checksums_valid,
+            digest_valid,
         })
     }
 
@@ -138,9 +145,27 @@ impl BranchBundleReader {
     }
 
     /// Read all components from the bundle
+    ///
+    /// Fails if the bundle has a digest that does not match its sections.
     pub fn read_all(path: &Path) -> BranchBundleResult<BundleContents> {
+        Self::parse_all(&Self::extract_all_files(path)?)
+    }
+
+    /// Read all components from a bundle whose digest was signed by `key`
+    ///
+    /// The signature is checked against the same bytes that are returned.
+    #[cfg(feature = "signing")]
+    pub fn read_all_signed(
+        path: &Path,
+        key: &ed25519_dalek::VerifyingKey,
+    ) -> BranchBundleResult<BundleContents> {
         let files = Self::extract_all_files(path)?;
+        Self::check_signature(&files, key)?;
+        Self::parse_all(&files)
+    }
 
+    /// Parse extracted bundle files, checking the digest if there is one
+    fn parse_all(files: &HashMap<String, Vec<u8>>) -> BranchBundleResult<BundleContents> {
         let manifest_data = files
             .get("MANIFEST.json")
             .ok_or_else(|| BranchBundleError::missing_file("MANIFEST.json"))?;
@@ -152,6 +177,7 @@ impl BranchBundleReader {
             .ok_or_else(|| BranchBundleError::missing_file("WAL.branchlog"))?;
 
         let manifest: BundleManifest = serde_json::from_slice(manifest_data)?;
+        Self::verify_digest(&manifest, files)?;
         let branch_info: BundleBranchInfo = serde_json::from_slice(branch_data)?;
         let payloads = WalLogReader::read_from_slice(wal_data)?;
 
@@ -162,6 +188,82 @@ impl BranchBundleReader {
         })
     }
 
+    /// Check the manifest digest against its section hashes, and each
+    /// section hash against the section.
+    ///
+    /// Returns `Ok(false)` if the bundle was exported without a digest.
+    fn verify_digest(
+        manifest: &BundleManifest,
+        files: &HashMap<String, Vec<u8>>,
+    ) -> BranchBundleResult<bool> {
+        let Some(digest) = &manifest.digest else {
+            if manifest.content_hashes.is_empty() {
+                return Ok(false);
+            }
+            return Err(BranchBundleError::invalid_bundle(
+                "manifest has section hashes but no digest",
+            ));
+        };
+
+        let actual = manifest.compute_digest();
+        if digest != &actual {
+            return Err(BranchBundleError::ChecksumMismatch {
+                file: "MANIFEST.json digest".to_string(),
+                expected: digest.clone(),
+                actual,
+            });
+        }
+
+        for section in ["BRANCH.json", "WAL.branchlog"] {
+            let expected = manifest.content_hashes.get(section).ok_or_else(|| {
+                BranchBundleError::invalid_bundle(format!("digest does not cover {}", section))
+            })?;
+            let data = files
+                .get(section)
+                .ok_or_else(|| BranchBundleError::missing_file(section))?;
+            let actual = sha256_hex(data);
+            if expected != &actual {
+                return Err(BranchBundleError::ChecksumMismatch {
+                    file: format!("{} (sha256)", section),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check that the bundle is intact and its digest was signed by `key`
+    ///
+    /// Fails if the bundle has no digest, is unsigned, was signed by another
+    /// key, or any section no longer matches the signed digest.
+    #[cfg(feature = "signing")]
+    pub fn verify_signature(
+        path: &Path,
+        key: &ed25519_dalek::VerifyingKey,
+    ) -> BranchBundleResult<()> {
+        Self::check_signature(&Self::extract_all_files(path)?, key)
+    }
+
+    #[cfg(feature = "signing")]
+    fn check_signature(
+        files: &HashMap<String, Vec<u8>>,
+        key: &ed25519_dalek::VerifyingKey,
+    ) -> BranchBundleResult<()> {
+        let manifest_data = files
+            .get("MANIFEST.json")
+            .ok_or_else(|| BranchBundleError::missing_file("MANIFEST.json"))?;
+        let manifest: BundleManifest = serde_json::from_slice(manifest_data)?;
+
+        if !Self::verify_digest(&manifest, files)? {
+            return Err(BranchBundleError::signature("bundle has no digest"));
+        }
+        let (Some(digest), Some(signature)) = (&manifest.digest, &manifest.signature) else {
+            return Err(BranchBundleError::signature("bundle is not signed"));
+        };
+        signature.verify(digest, key)
+    }
+
     /// Extract a single file from the archive
     fn extract_file(path: &Path, file_name: &str) -> BranchBundleResult<Vec<u8>> {
         let file = File::open(path)?;
@@ -515,6 +617,178 @@ mod tests {
         assert_eq!(contents.manifest.contents.wal_entry_count, 2);
     }
 
+    /// Rebuild the bundle at `path` after passing each file through `edit`
+    fn rewrite_bundle(path: &Path, edit: impl Fn(&str, Vec<u8>) -> Vec<u8>) {
+        let files = BranchBundleReader::extract_all_files(path).unwrap();
+        let file = File::create(path).unwrap();
+        let encoder = zstd::Encoder::new(file, 3).unwrap().auto_finish();
+        let mut builder = tar::Builder::new(encoder);
+        for (name, data) in files {
+            let data = edit(&name, data);
+            let mut header = tar::Header::new_gnu();
+            header
+                .set_path(format!("{}/{}", paths::ROOT, name))
+                .unwrap();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, data.as_slice()).unwrap();
+        }
+        builder.into_inner().unwrap();
+    }
+
+    fn write_digest_bundle(path: &Path) {
+        let options = ExportOptions {
+            include_digest: true,
+            ..ExportOptions::default()
+        };
+        BranchBundleWriter::new(&options)
+            .write(&make_test_branch_info(), &make_test_payloads(), path)
+            .unwrap();
+    }
+
+    fn rename_branch(name: &str, data: Vec<u8>) -> Vec<u8> {
+        if name != "BRANCH.json" {
+            return data;
+        }
+        String::from_utf8(data)
+            .unwrap()
+            .replace("test-branch", "evil-branch")
+            .into_bytes()
+    }
+
+    #[test]
+    fn test_digest_round_trip() {
+        let dir = tempdir().unwrap();
+        let plain = dir.path().join("plain.branchbundle.tar.zst");
+        let digested = dir.path().join("digest.branchbundle.tar.zst");
+        BranchBundleWriter::with_defaults()
+            .write(&make_test_branch_info(), &make_test_payloads(), &plain)
+            .unwrap();
+        write_digest_bundle(&digested);
+
+        assert_eq!(
+            BranchBundleReader::validate(&plain).unwrap().digest_valid,
+            None
+        );
+        assert_eq!(
+            BranchBundleReader::validate(&digested)
+                .unwrap()
+                .digest_valid,
+            Some(true)
+        );
+
+        let manifest = BranchBundleReader::read_all(&digested).unwrap().manifest;
+        assert_eq!(manifest.content_hashes.len(), 2);
+        assert_eq!(manifest.digest, Some(manifest.compute_digest()));
+        assert!(manifest.signature.is_none());
+    }
+
+    #[test]
+    fn test_digest_detects_tampered_section() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("tampered.branchbundle.tar.zst");
+        write_digest_bundle(&path);
+        rewrite_bundle(&path, rename_branch);
+
+        assert_eq!(
+            BranchBundleReader::validate(&path).unwrap().digest_valid,
+            Some(false)
+        );
+        assert!(matches!(
+            BranchBundleReader::read_all(&path),
+            Err(BranchBundleError::ChecksumMismatch { file, .. }) if file == "BRANCH.json (sha256)"
+        ));
+    }
+
+    #[test]
+    fn test_digest_detects_tampered_hashes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("tampered.branchbundle.tar.zst");
+        write_digest_bundle(&path);
+
+        // Rewrite the section and its hash, but not the digest
+        rewrite_bundle(&path, |name, data| {
+            if name != "MANIFEST.json" {
+                return rename_branch(name, data);
+            }
+            let mut manifest: BundleManifest = serde_json::from_slice(&data).unwrap();
+            let branch = rename_branch(
+                "BRANCH.json",
+                serde_json::to_vec_pretty(&make_test_branch_info()).unwrap(),
+            );
+            manifest
+                .content_hashes
+                .insert("BRANCH.json".to_string(), sha256_hex(&branch));
+            serde_json::to_vec_pretty(&manifest).unwrap()
+        });
+
+        assert!(matches!(
+            BranchBundleReader::read_all(&path),
+            Err(BranchBundleError::ChecksumMismatch { file, .. }) if file == "MANIFEST.json digest"
+        ));
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_signed_bundle() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("signed.branchbundle.tar.zst");
+        let key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+        let options = ExportOptions {
+            signing_key: Some(key.clone()),
+            ..ExportOptions::default()
+        };
+        BranchBundleWriter::new(&options)
+            .write(&make_test_branch_info(), &make_test_payloads(), &path)
+            .unwrap();
+
+        BranchBundleReader::verify_signature(&path, &key.verifying_key()).unwrap();
+        let contents = BranchBundleReader::read_all_signed(&path, &key.verifying_key()).unwrap();
+        assert_eq!(contents.payloads.len(), 2);
+        let other = ed25519_dalek::SigningKey::from_bytes(&[4u8; 32]);
+        assert!(matches!(
+            BranchBundleReader::verify_signature(&path, &other.verifying_key()),
+            Err(BranchBundleError::Signature(_))
+        ));
+
+        // Re-hashing a tampered bundle passes the digest check but not the
+        // signature
+        rewrite_bundle(&path, |name, data| {
+            if name != "MANIFEST.json" {
+                return rename_branch(name, data);
+            }
+            let mut manifest: BundleManifest = serde_json::from_slice(&data).unwrap();
+            let branch = rename_branch(
+                "BRANCH.json",
+                serde_json::to_vec_pretty(&make_test_branch_info()).unwrap(),
+            );
+            manifest.add_content_hash("BRANCH.json", &branch);
+            serde_json::to_vec_pretty(&manifest).unwrap()
+        });
+        assert_eq!(
+            BranchBundleReader::validate(&path).unwrap().digest_valid,
+            Some(true)
+        );
+        assert!(matches!(
+            BranchBundleReader::verify_signature(&path, &key.verifying_key()),
+            Err(BranchBundleError::Signature(_))
+        ));
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_verify_signature_rejects_unsigned() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("digest.branchbundle.tar.zst");
+        write_digest_bundle(&path);
+        let key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+        assert!(matches!(
+            BranchBundleReader::verify_signature(&path, &key.verifying_key()),
+            Err(BranchBundleError::Signature(_))
+        ));
+    }
+
     #[test]
     fn test_missing_file_error() {
         let dir = tempdir().unwrap();
@@ -3,7 +3,8 @@
 //! Types for the BranchBundle archive format (.branchbundle.tar.zst)
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// Current BranchBundle format version
@@ -68,6 +69,19 @@ pub struct BundleManifest {
 
     /// Summary of bundle contents
     pub contents: BundleContents,
+
+    /// SHA-256 of each section, hex-encoded. Empty unless the export asked
+    /// for a digest.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub content_hashes: BTreeMap<String, String>,
+
+    /// SHA-256 over `content_hashes`, hex-encoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+
+    /// Signature over `digest`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<BundleSignature>,
 }
 
 impl BundleManifest {
@@ -80,6 +94,9 @@ impl BundleManifest {
             checksum_algorithm: "xxh3".to_string(),
             checksums: HashMap::new(),
             contents,
+            content_hashes: BTreeMap::new(),
+            digest: None,
+            signature: None,
         }
     }
 
@@ -87,6 +104,83 @@ impl BundleManifest {
     pub fn add_checksum(&mut self, path: impl Into<String>, checksum: impl Into<String>) {
         self.checksums.insert(path.into(), checksum.into());
     }
+
+    /// Record the SHA-256 of a section and refresh the digest
+    pub fn add_content_hash(&mut self, path: impl Into<String>, data: &[u8]) {
+        self.content_hashes.insert(path.into(), sha256_hex(data));
+        self.digest = Some(self.compute_digest());
+    }
+
+    /// SHA-256 over the section hashes, in path order
+    ///
+    /// Each section contributes `path NUL hash LF`, so the digest changes if
+    /// any section is altered, added or removed.
+    pub fn compute_digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        for (path, hash) in &self.content_hashes {
+            hasher.update(path.as_bytes());
+            hasher.update([0]);
+            hasher.update(hash.as_bytes());
+            hasher.update(b"\n");
+        }
+        hex(&hasher.finalize())
+    }
+}
+
+/// Signature over a bundle digest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleSignature {
+    /// Signature algorithm (currently "ed25519")
+    pub algorithm: String,
+
+    /// Hex-encoded public key of the signer
+    pub public_key: String,
+
+    /// Hex-encoded signature over the UTF-8 bytes of the digest
+    pub signature: String,
+}
+
+#[cfg(feature = "signing")]
+impl BundleSignature {
+    /// Sign `digest` with `key`
+    pub fn sign(digest: &str, key: &ed25519_dalek::SigningKey) -> Self {
+        use ed25519_dalek::Signer;
+        Self {
+            algorithm: "ed25519".to_string(),
+            public_key: hex(key.verifying_key().as_bytes()),
+            signature: hex(&key.sign(digest.as_bytes()).to_bytes()),
+        }
+    }
+
+    /// Check that this is a signature over `digest` made by `key`
+    pub fn verify(
+        &self,
+        digest: &str,
+        key: &ed25519_dalek::VerifyingKey,
+    ) -> crate::branch_bundle::error::BranchBundleResult<()> {
+        use crate::branch_bundle::error::BranchBundleError;
+
+        if self.algorithm != "ed25519" {
+            return Err(BranchBundleError::signature(format!(
+                "unsupported algorithm '{}'",
+                self.algorithm
+            )));
+        }
+        if self.public_key != hex(key.as_bytes()) {
+            return Err(BranchBundleError::signature(format!(
+                "bundle was signed by {}, not the trusted key",
+                self.public_key
+            )));
+        }
+        let bytes: [u8; 64] = unhex(&self.signature)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| BranchBundleError::signature("malformed signature"))?;
+        key.verify_strict(
+            digest.as_bytes(),
+            &ed25519_dalek::Signature::from_bytes(&bytes),
+        )
+        .map_err(|_| BranchBundleError::signature("signature does not match the digest"))
+    }
 }
 
 /// Summary of bundle contents
@@ -174,12 +268,34 @@ pub struct BranchExportInfo {
 pub struct ExportOptions {
     /// Zstd compression level (1-22, default: 3)
     pub compression_level: i32,
+
+    /// Record a SHA-256 of each section and an overall digest in the
+    /// manifest, checked on import (default: false)
+    pub include_digest: bool,
+
+    /// Sign the digest with this key. Implies `include_digest`.
+    #[cfg(feature = "signing")]
+    pub signing_key: Option<ed25519_dalek::SigningKey>,
+}
+
+impl ExportOptions {
+    /// Whether the manifest gets section hashes and a digest
+    pub fn wants_digest(&self) -> bool {
+        #[cfg(feature = "signing")]
+        if self.signing_key.is_some() {
+            return true;
+        }
+        self.include_digest
+    }
 }
 
 impl Default for ExportOptions {
     fn default() -> Self {
         Self {
             compression_level: 3,
+            include_digest: false,
+            #[cfg(feature = "signing")]
+            signing_key: None,
         }
     }
 }
@@ -202,6 +318,10 @@ pub struct BundleVerifyInfo {
 
     /// Whether all checksums are valid
     pub checksums_valid: bool,
+
+    /// Whether the SHA-256 section hashes match the digest; `None` if the
+    /// bundle has no digest
+    pub digest_valid: Option<bool>,
 }
 
 // =============================================================================
@@ -262,6 +382,28 @@ pub fn xxh3_hex(data: &[u8]) -> String {
     format!("{:016x}", xxh3_64(data))
 }
 
+/// Compute SHA-256 of data and return as hex string
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+/// Lowercase hex encoding
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hex string; `None` if it is malformed
+#[cfg(feature = "signing")]
+fn unhex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +536,41 @@ mod tests {
     fn test_export_options_default() {
         let opts = ExportOptions::default();
         assert_eq!(opts.compression_level, 3);
+        assert!(!opts.include_digest);
+    }
+
+    #[test]
+    fn test_manifest_digest() {
+        let mut manifest = BundleManifest::new("0.12.0", BundleContents::default());
+        assert!(manifest.digest.is_none());
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(!json.contains("digest"));
+
+        manifest.add_content_hash("BRANCH.json", b"branch");
+        manifest.add_content_hash("WAL.branchlog", b"wal");
+        assert_eq!(manifest.content_hashes["WAL.branchlog"], sha256_hex(b"wal"));
+        let digest = manifest.digest.clone().unwrap();
+        assert_eq!(digest.len(), 64);
+        assert_eq!(digest, manifest.compute_digest());
+
+        manifest.add_content_hash("WAL.branchlog", b"tampered");
+        assert_ne!(manifest.digest.as_ref(), Some(&digest));
+
+        let restored: BundleManifest =
+            serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(restored, manifest);
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_signature_round_trip() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let other = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+        let sig = BundleSignature::sign("abc", &key);
+
+        sig.verify("abc", &key.verifying_key()).unwrap();
+        assert!(sig.verify("abd", &key.verifying_key()).is_err());
+        assert!(sig.verify("abc", &other.verifying_key()).is_err());
     }
 
     #[test]
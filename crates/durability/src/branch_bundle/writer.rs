@@ -10,7 +10,7 @@ use crate::branch_bundle::types::{
     paths, xxh3_hex, BranchExportInfo, BundleBranchInfo, BundleContents, BundleManifest,
     ExportOptions,
 };
-use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogWriter};
+use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogInfo, WalLogWriter};
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
 use std::path::Path;
@@ -20,14 +20,14 @@ use tar::{Builder, Header};
 ///
 /// Creates .branchbundle.tar.zst files with atomic write semantics.
 pub struct BranchBundleWriter {
-    compression_level: i32,
+    options: ExportOptions,
 }
 
 impl BranchBundleWriter {
     /// Create a new writer with the given options
     pub fn new(options: &ExportOptions) -> Self {
         Self {
-            compression_level: options.compression_level,
+            options: options.clone(),
         }
     }
 
@@ -85,27 +85,12 @@ impl BranchBundleWriter {
         let branch_json = serde_json::to_vec_pretty(branch_info)?;
         let (wal_data, wal_info) = WalLogWriter::write_to_vec(payloads)?;
 
-        // Build manifest with checksums
-        let mut manifest = BundleManifest::new(
-            env!("CARGO_PKG_VERSION"),
-            BundleContents {
-                wal_entry_count: wal_info.entry_count,
-                wal_size_bytes: wal_info.bytes_written,
-            },
-        );
-        manifest.add_checksum("BRANCH.json", xxh3_hex(&branch_json));
-        manifest.add_checksum("WAL.branchlog", &wal_info.checksum);
-
-        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
-        manifest.add_checksum("MANIFEST.json", xxh3_hex(&manifest_json));
-
-        // Re-serialize manifest with its own checksum (for verification)
-        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        let manifest_json = self.manifest_json(&branch_json, &wal_data, &wal_info)?;
 
         // Create compressed tar archive
         let file = File::create(path)?;
         let buf_writer = BufWriter::new(file);
-        let zstd_writer = zstd::Encoder::new(buf_writer, self.compression_level)
+        let zstd_writer = zstd::Encoder::new(buf_writer, self.options.compression_level)
             .map_err(|e| BranchBundleError::compression(format!("zstd encoder: {}", e)))?;
         let zstd_writer = zstd_writer.auto_finish();
 
@@ -140,6 +125,42 @@ impl BranchBundleWriter {
         })
     }
 
+    /// Build the manifest with checksums and, if requested, the digest and
+    /// signature
+    fn manifest_json(
+        &self,
+        branch_json: &[u8],
+        wal_data: &[u8],
+        wal_info: &WalLogInfo,
+    ) -> BranchBundleResult<Vec<u8>> {
+        let mut manifest = BundleManifest::new(
+            env!("CARGO_PKG_VERSION"),
+            BundleContents {
+                wal_entry_count: wal_info.entry_count,
+                wal_size_bytes: wal_info.bytes_written,
+            },
+        );
+        manifest.add_checksum("BRANCH.json", xxh3_hex(branch_json));
+        manifest.add_checksum("WAL.branchlog", &wal_info.checksum);
+
+        if self.options.wants_digest() {
+            manifest.add_content_hash("BRANCH.json", branch_json);
+            manifest.add_content_hash("WAL.branchlog", wal_data);
+        }
+        #[cfg(feature = "signing")]
+        if let (Some(key), Some(digest)) = (&self.options.signing_key, &manifest.digest) {
+            manifest.signature = Some(crate::branch_bundle::types::BundleSignature::sign(
+                digest, key,
+            ));
+        }
+
+        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        manifest.add_checksum("MANIFEST.json", xxh3_hex(&manifest_json));
+
+        // Re-serialize manifest with its own checksum (for verification)
+        Ok(serde_json::to_vec_pretty(&manifest)?)
+    }
+
     /// Add a file to the tar archive
     fn add_file<W: Write>(
         &self,
@@ -173,26 +194,12 @@ impl BranchBundleWriter {
         let branch_json = serde_json::to_vec_pretty(branch_info)?;
         let (wal_data, wal_info) = WalLogWriter::write_to_vec(payloads)?;
 
-        // Build manifest with checksums
-        let mut manifest = BundleManifest::new(
-            env!("CARGO_PKG_VERSION"),
-            BundleContents {
-                wal_entry_count: wal_info.entry_count,
-                wal_size_bytes: wal_info.bytes_written,
-            },
-        );
-        manifest.add_checksum("BRANCH.json", xxh3_hex(&branch_json));
-        manifest.add_checksum("WAL.branchlog", &wal_info.checksum);
-
-        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
-        manifest.add_checksum("MANIFEST.json", xxh3_hex(&manifest_json));
-
-        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        let manifest_json = self.manifest_json(&branch_json, &wal_data, &wal_info)?;
 
         // Create compressed tar in memory
         let mut buffer = Vec::new();
         {
-            let zstd_writer = zstd::Encoder::new(&mut buffer, self.compression_level)
+            let zstd_writer = zstd::Encoder::new(&mut buffer, self.options.compression_level)
                 .map_err(|e| BranchBundleError::compression(format!("zstd encoder: {}", e)))?;
             let zstd_writer = zstd_writer.auto_finish();
 
@@ -23,6 +23,7 @@
 use crate::format::segment_meta::SegmentMeta;
 use crate::format::{
     ManifestManager, SegmentHeader, WalRecord, WalRecordError, SEGMENT_HEADER_SIZE,
+    WAL_RECORD_FORMAT_VERSION,
 };
 use parking_lot::Mutex;
 use std::path::{Path, PathBuf};
@@ -156,6 +157,31 @@ impl WalOnlyCompactor {
         Ok(info)
     }
 
+    /// Check whether [`compact`](Self::compact) would remove anything.
+    ///
+    /// Returns true if at least one closed segment is fully covered by the
+    /// snapshot watermark. Returns false when no snapshot exists yet. Nothing
+    /// is removed.
+    pub fn compaction_needed(&self) -> Result<bool, CompactionError> {
+        let (watermark, active_segment) = {
+            let manifest = self.manifest.lock();
+            match manifest.manifest().snapshot_watermark {
+                Some(watermark) => (watermark, manifest.manifest().active_wal_segment),
+                None => return Ok(false),
+            }
+        };
+
+        for segment_number in self.list_segments()? {
+            if segment_number >= active_segment {
+                break;
+            }
+            if let Ok(true) = self.segment_covered_by_watermark(segment_number, watermark) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     /// List all WAL segment numbers in sorted order
     fn list_segments(&self) -> Result<Vec<u64>, CompactionError> {
         let mut segments = Vec::new();
@@ -270,6 +296,11 @@ impl WalOnlyCompactor {
                 Err(WalRecordError::ChecksumMismatch { .. }) => {
                     break;
                 }
+                // A record from a newer format was skipped by recovery, so its
+                // txn_id is unknown; keep the segment rather than lose it.
+                Err(WalRecordError::UnsupportedVersion(v)) if v > WAL_RECORD_FORMAT_VERSION => {
+                    return Ok(false);
+                }
                 Err(_) => {
                     break;
                 }
@@ -430,6 +461,29 @@ mod tests {
         assert!(segment_path(&wal_dir, 1).exists());
     }
 
+    #[test]
+    fn test_compaction_needed() {
+        let (_dir, wal_dir, manifest) = setup_test_env();
+        create_segment_with_records(&wal_dir, 1, &[1, 2, 3]).unwrap();
+        create_segment_with_records(&wal_dir, 2, &[4, 5, 6]).unwrap();
+        manifest.lock().manifest_mut().active_wal_segment = 2;
+
+        let compactor = WalOnlyCompactor::new(wal_dir.clone(), Arc::clone(&manifest));
+        // No snapshot yet
+        assert!(!compactor.compaction_needed().unwrap());
+
+        // Watermark below segment 1's max txn
+        manifest.lock().set_snapshot_watermark(1, 2).unwrap();
+        assert!(!compactor.compaction_needed().unwrap());
+
+        manifest.lock().set_snapshot_watermark(2, 3).unwrap();
+        assert!(compactor.compaction_needed().unwrap());
+        assert!(segment_path(&wal_dir, 1).exists());
+
+        compactor.compact().unwrap();
+        assert!(!compactor.compaction_needed().unwrap());
+    }
+
     #[test]
     fn test_compact_empty_wal() {
         let (_dir, wal_dir, manifest) = setup_test_env();
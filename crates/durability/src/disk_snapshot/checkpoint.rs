@@ -4,11 +4,14 @@
 //! This is the main API for creating database checkpoints.
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use strata_core::Clock;
 
 use crate::codec::StorageCodec;
 use crate::disk_snapshot::{SnapshotSection, SnapshotWriter};
 use crate::format::primitives::SnapshotSerializer;
-use crate::format::snapshot::primitive_tags;
+use crate::format::snapshot::{list_snapshots, primitive_tags};
 use crate::format::watermark::{CheckpointInfo, SnapshotWatermark};
 
 /// Checkpoint coordinator
@@ -60,6 +63,12 @@ impl CheckpointCoordinator {
         })
     }
 
+    /// Use `clock` for snapshot creation timestamps instead of system time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.snapshot_writer = self.snapshot_writer.with_clock(clock);
+        self
+    }
+
     /// Get the current watermark state
     pub fn watermark(&self) -> &SnapshotWatermark {
         &self.watermark
@@ -148,6 +157,32 @@ impl CheckpointCoordinator {
         ))
     }
 
+    /// Delete old snapshots, keeping the newest `keep`
+    ///
+    /// The snapshot at the current watermark always counts as kept, and
+    /// snapshots newer than it (written but never recorded as the
+    /// watermark) are left alone. A `keep` of 0 behaves like 1. Without a
+    /// watermark nothing is deleted.
+    ///
+    /// Returns the IDs of the deleted snapshots, oldest first.
+    pub fn prune_snapshots(&self, keep: usize) -> std::io::Result<Vec<u64>> {
+        let Some(current) = self.watermark.snapshot_id() else {
+            return Ok(Vec::new());
+        };
+        let older: Vec<(u64, PathBuf)> = list_snapshots(self.snapshots_dir())?
+            .into_iter()
+            .filter(|(id, _)| *id < current)
+            .collect();
+        let excess = older.len().saturating_sub(keep.saturating_sub(1));
+
+        let mut pruned = Vec::with_capacity(excess);
+        for (id, path) in older.into_iter().take(excess) {
+            std::fs::remove_file(path)?;
+            pruned.push(id);
+        }
+        Ok(pruned)
+    }
+
     /// Clean up temporary files from failed checkpoints
     pub fn cleanup(&self) -> std::io::Result<usize> {
         self.snapshot_writer.cleanup_temp_files()
@@ -287,13 +322,13 @@ mod tests {
 
         let kv_entries = vec![
             KvSnapshotEntry {
-                key: "key1".to_string(),
+                key: b"key1".to_vec(),
                 value: b"value1".to_vec(),
                 version: 1,
                 timestamp: 1000,
             },
             KvSnapshotEntry {
-                key: "key2".to_string(),
+                key: b"key2".to_vec(),
                 value: b"value2".to_vec(),
                 version: 2,
                 timestamp: 2000,
@@ -348,7 +383,7 @@ mod tests {
 
         let data = CheckpointData::new()
             .with_kv(vec![KvSnapshotEntry {
-                key: "k".to_string(),
+                key: b"k".to_vec(),
                 value: vec![],
                 version: 1,
                 timestamp: 0,
@@ -411,6 +446,63 @@ mod tests {
         assert_eq!(info.snapshot_id, 6);
     }
 
+    #[test]
+    fn test_prune_snapshots() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut coordinator = CheckpointCoordinator::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+
+        // Nothing to prune before the first checkpoint
+        assert!(coordinator.prune_snapshots(1).unwrap().is_empty());
+
+        for txn in [100, 200, 300, 400] {
+            coordinator.checkpoint(txn, CheckpointData::new()).unwrap();
+        }
+        let ids = |dir: &Path| -> Vec<u64> {
+            list_snapshots(dir)
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+
+        assert_eq!(coordinator.prune_snapshots(2).unwrap(), vec![1, 2]);
+        assert_eq!(ids(temp_dir.path()), vec![3, 4]);
+        assert!(coordinator.prune_snapshots(2).unwrap().is_empty());
+
+        // The watermark snapshot survives even with keep = 0
+        assert_eq!(coordinator.prune_snapshots(0).unwrap(), vec![3]);
+        assert_eq!(ids(temp_dir.path()), vec![4]);
+    }
+
+    #[test]
+    fn test_prune_keeps_snapshots_newer_than_watermark() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut coordinator = CheckpointCoordinator::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+        for txn in [100, 200, 300] {
+            coordinator.checkpoint(txn, CheckpointData::new()).unwrap();
+        }
+
+        // Snapshot 3 was written but the watermark still points at 2
+        coordinator.set_watermark(SnapshotWatermark::with_values(2, 200, 0));
+        assert_eq!(coordinator.prune_snapshots(1).unwrap(), vec![1]);
+        let remaining: Vec<u64> = list_snapshots(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(remaining, vec![2, 3]);
+    }
+
     #[test]
     fn test_cleanup() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -16,6 +16,9 @@
 use std::fs::{File, OpenOptions};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use strata_core::{Clock, SystemClock};
 
 use crate::codec::StorageCodec;
 use crate::format::snapshot::{snapshot_path, SectionHeader, SnapshotHeader};
@@ -28,6 +31,7 @@ pub struct SnapshotWriter {
     snapshots_dir: PathBuf,
     codec: Box<dyn StorageCodec>,
     database_uuid: [u8; 16],
+    clock: Arc<dyn Clock>,
 }
 
 impl SnapshotWriter {
@@ -44,9 +48,16 @@ impl SnapshotWriter {
             snapshots_dir,
             codec,
             database_uuid,
+            clock: Arc::new(SystemClock),
         })
     }
 
+    /// Use `clock` for snapshot creation timestamps instead of system time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Get the snapshots directory
     pub fn snapshots_dir(&self) -> &Path {
         &self.snapshots_dir
@@ -78,10 +89,7 @@ impl SnapshotWriter {
             .write(true)
             .open(&temp_path)?;
 
-        let created_at = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_micros() as u64;
+        let created_at = self.clock.now().as_micros();
 
         let codec_id = self.codec.codec_id();
         let header = SnapshotHeader::new(
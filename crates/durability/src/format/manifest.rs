@@ -112,6 +112,16 @@ impl Manifest {
             return Err(ManifestError::InvalidMagic);
         }
 
+        // Check version before anything else: a newer layout may put the
+        // checksum or fields elsewhere
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version > MANIFEST_FORMAT_VERSION {
+            return Err(ManifestError::UnsupportedVersion {
+                version,
+                max_supported: MANIFEST_FORMAT_VERSION,
+            });
+        }
+
         // Verify CRC (last 4 bytes)
         let data = &bytes[..bytes.len() - 4];
         let stored_crc = u32::from_le_bytes(bytes[bytes.len() - 4..].try_into().unwrap());
@@ -287,6 +297,15 @@ pub enum ManifestError {
     #[error("Invalid magic bytes")]
     InvalidMagic,
 
+    /// Written by a newer MANIFEST format
+    #[error("Unsupported MANIFEST version {version}, max supported is {max_supported}")]
+    UnsupportedVersion {
+        /// Version found in the file
+        version: u32,
+        /// Maximum supported version
+        max_supported: u32,
+    },
+
     /// Invalid codec ID (not valid UTF-8)
     #[error("Invalid codec ID")]
     InvalidCodecId,
@@ -400,6 +419,18 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_manifest_newer_version() {
+        let manifest = Manifest::new(test_uuid(), "identity".to_string());
+        let mut bytes = manifest.to_bytes();
+        bytes[4..8].copy_from_slice(&(MANIFEST_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Manifest::from_bytes(&bytes),
+            Err(ManifestError::UnsupportedVersion { version, max_supported })
+                if version == MANIFEST_FORMAT_VERSION + 1 && max_supported == MANIFEST_FORMAT_VERSION
+        ));
+    }
+
     #[test]
     fn test_manifest_too_short() {
         let result = Manifest::from_bytes(&[0u8; 10]);
@@ -10,18 +10,20 @@
 //! - `writeset`: Transaction writeset serialization
 //! - `manifest`: MANIFEST file format (added in Epic 72)
 //! - `snapshot`: Snapshot file format (added in Epic 71)
+//! - `version`: Format version checks run before opening a database
 
 pub mod manifest;
 pub mod primitives;
 pub mod segment_meta;
 pub mod snapshot;
+pub mod version;
 pub mod wal_record;
 pub mod watermark;
 pub mod writeset;
 
 pub use snapshot::{
-    find_latest_snapshot, list_snapshots, parse_snapshot_id, primitive_tags, snapshot_path,
-    SectionHeader, SnapshotHeader, SnapshotHeaderError, SNAPSHOT_FORMAT_VERSION,
+    find_latest_snapshot, list_snapshots, parse_snapshot_id, primitive_tags, read_snapshot_header,
+    snapshot_path, SectionHeader, SnapshotHeader, SnapshotHeaderError, SNAPSHOT_FORMAT_VERSION,
     SNAPSHOT_HEADER_SIZE, SNAPSHOT_MAGIC,
 };
 pub use wal_record::{
@@ -41,4 +43,5 @@ pub use primitives::{
 pub use segment_meta::{
     SegmentMeta, SegmentMetaError, SEGMENT_META_MAGIC, SEGMENT_META_SIZE, SEGMENT_META_VERSION,
 };
+pub use version::{check_format_versions, FormatVersionError};
 pub use watermark::{CheckpointInfo, SnapshotWatermark, WatermarkError};
@@ -6,7 +6,9 @@
 //! # Binary Format
 //!
 //! All sections start with a 4-byte count of entries, followed by the entries.
-//! Strings are length-prefixed (4-byte length + bytes).
+//! Strings are length-prefixed (4-byte length + bytes). KV keys, State cell
+//! names and JSON document ids are stored the same way but as raw bytes, so
+//! keys that are not valid UTF-8 round-trip unchanged.
 //! All integers are little-endian.
 
 use crate::codec::StorageCodec;
@@ -16,8 +18,8 @@ use crate::codec::StorageCodec;
 /// Format: key_len(4) + key + value_len(4) + value + version(8) + timestamp(8)
 #[derive(Debug, Clone, PartialEq)]
 pub struct KvSnapshotEntry {
-    /// Key string
-    pub key: String,
+    /// Key bytes
+    pub key: Vec<u8>,
     /// Value bytes (pre-codec)
     pub value: Vec<u8>,
     /// Version counter
@@ -44,8 +46,8 @@ pub struct EventSnapshotEntry {
 /// Format: name_len(4) + name + value_len(4) + value + counter(8) + timestamp(8)
 #[derive(Debug, Clone, PartialEq)]
 pub struct StateSnapshotEntry {
-    /// State cell name
-    pub name: String,
+    /// State cell name bytes
+    pub name: Vec<u8>,
     /// Value bytes (pre-codec)
     pub value: Vec<u8>,
     /// CAS counter
@@ -74,8 +76,8 @@ pub struct BranchSnapshotEntry {
 /// Format: doc_id_len(4) + doc_id + content_len(4) + content + version(8) + timestamp(8)
 #[derive(Debug, Clone, PartialEq)]
 pub struct JsonSnapshotEntry {
-    /// Document identifier
-    pub doc_id: String,
+    /// Document identifier bytes
+    pub doc_id: Vec<u8>,
     /// JSON content bytes (pre-codec)
     pub content: Vec<u8>,
     /// Version counter
@@ -132,9 +134,8 @@ impl SnapshotSerializer {
 
         for entry in entries {
             // Key
-            let key_bytes = entry.key.as_bytes();
-            data.extend_from_slice(&(key_bytes.len() as u32).to_le_bytes());
-            data.extend_from_slice(key_bytes);
+            data.extend_from_slice(&(entry.key.len() as u32).to_le_bytes());
+            data.extend_from_slice(&entry.key);
 
             // Value (through codec)
             let value_bytes = self.codec.encode(&entry.value);
@@ -176,8 +177,7 @@ impl SnapshotSerializer {
             if cursor + key_len > data.len() {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
-            let key = String::from_utf8(data[cursor..cursor + key_len].to_vec())
-                .map_err(|_| PrimitiveSerializeError::InvalidUtf8)?;
+            let key = data[cursor..cursor + key_len].to_vec();
             cursor += key_len;
 
             // Value
@@ -294,9 +294,8 @@ impl SnapshotSerializer {
         data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
 
         for entry in entries {
-            let name_bytes = entry.name.as_bytes();
-            data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-            data.extend_from_slice(name_bytes);
+            data.extend_from_slice(&(entry.name.len() as u32).to_le_bytes());
+            data.extend_from_slice(&entry.name);
 
             let value_bytes = self.codec.encode(&entry.value);
             data.extend_from_slice(&(value_bytes.len() as u32).to_le_bytes());
@@ -336,8 +335,7 @@ impl SnapshotSerializer {
             if cursor + name_len > data.len() {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
-            let name = String::from_utf8(data[cursor..cursor + name_len].to_vec())
-                .map_err(|_| PrimitiveSerializeError::InvalidUtf8)?;
+            let name = data[cursor..cursor + name_len].to_vec();
             cursor += name_len;
 
             if cursor + 4 > data.len() {
@@ -473,9 +471,8 @@ impl SnapshotSerializer {
         data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
 
         for entry in entries {
-            let doc_id_bytes = entry.doc_id.as_bytes();
-            data.extend_from_slice(&(doc_id_bytes.len() as u32).to_le_bytes());
-            data.extend_from_slice(doc_id_bytes);
+            data.extend_from_slice(&(entry.doc_id.len() as u32).to_le_bytes());
+            data.extend_from_slice(&entry.doc_id);
 
             let content_bytes = self.codec.encode(&entry.content);
             data.extend_from_slice(&(content_bytes.len() as u32).to_le_bytes());
@@ -516,8 +513,7 @@ impl SnapshotSerializer {
             if cursor + doc_id_len > data.len() {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
-            let doc_id = String::from_utf8(data[cursor..cursor + doc_id_len].to_vec())
-                .map_err(|_| PrimitiveSerializeError::InvalidUtf8)?;
+            let doc_id = data[cursor..cursor + doc_id_len].to_vec();
             cursor += doc_id_len;
 
             // Content
@@ -756,13 +752,13 @@ mod tests {
 
         let entries = vec![
             KvSnapshotEntry {
-                key: "key1".to_string(),
+                key: b"key1".to_vec(),
                 value: b"value1".to_vec(),
                 version: 1,
                 timestamp: 1000,
             },
             KvSnapshotEntry {
-                key: "key2".to_string(),
+                key: b"key2".to_vec(),
                 value: b"value2".to_vec(),
                 version: 2,
                 timestamp: 2000,
@@ -791,7 +787,7 @@ mod tests {
         let serializer = test_serializer();
 
         let entries = vec![KvSnapshotEntry {
-            key: "key_\u{1F600}_emoji".to_string(),
+            key: "key_\u{1F600}_emoji".as_bytes().to_vec(),
             value: "value_\u{4E2D}\u{6587}_chinese".as_bytes().to_vec(),
             version: 42,
             timestamp: 9999,
@@ -803,6 +799,46 @@ mod tests {
         assert_eq!(entries, parsed);
     }
 
+    #[test]
+    fn test_kv_binary_key() {
+        let serializer = test_serializer();
+
+        let entries = vec![KvSnapshotEntry {
+            key: vec![0x00, 0xFF, 0xC3, 0x28, 0x80],
+            value: b"hashed".to_vec(),
+            version: 7,
+            timestamp: 1234,
+        }];
+
+        let data = serializer.serialize_kv(&entries);
+        let parsed = serializer.deserialize_kv(&data).unwrap();
+
+        assert_eq!(entries, parsed);
+    }
+
+    #[test]
+    fn test_states_and_json_binary_keys() {
+        let serializer = test_serializer();
+
+        let states = vec![StateSnapshotEntry {
+            name: vec![0xFE, 0x00, 0x41],
+            value: b"v".to_vec(),
+            counter: 3,
+            timestamp: 1,
+        }];
+        let data = serializer.serialize_states(&states);
+        assert_eq!(serializer.deserialize_states(&data).unwrap(), states);
+
+        let docs = vec![JsonSnapshotEntry {
+            doc_id: vec![0xC0, 0xAF],
+            content: b"{}".to_vec(),
+            version: 4,
+            timestamp: 2,
+        }];
+        let data = serializer.serialize_json(&docs);
+        assert_eq!(serializer.deserialize_json(&data).unwrap(), docs);
+    }
+
     #[test]
     fn test_events_roundtrip() {
         let serializer = test_serializer();
@@ -832,13 +868,13 @@ mod tests {
 
         let entries = vec![
             StateSnapshotEntry {
-                name: "state1".to_string(),
+                name: b"state1".to_vec(),
                 value: b"value1".to_vec(),
                 counter: 10,
                 timestamp: 1000,
             },
             StateSnapshotEntry {
-                name: "state2".to_string(),
+                name: b"state2".to_vec(),
                 value: b"value2".to_vec(),
                 counter: 20,
                 timestamp: 2000,
@@ -874,13 +910,13 @@ mod tests {
 
         let entries = vec![
             JsonSnapshotEntry {
-                doc_id: "doc1".to_string(),
+                doc_id: b"doc1".to_vec(),
                 content: b"{\"name\":\"test\"}".to_vec(),
                 version: 1,
                 timestamp: 1000,
             },
             JsonSnapshotEntry {
-                doc_id: "doc2".to_string(),
+                doc_id: b"doc2".to_vec(),
                 content: b"{\"value\":42}".to_vec(),
                 version: 2,
                 timestamp: 2000,
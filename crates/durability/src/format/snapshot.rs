@@ -233,6 +233,24 @@ pub fn parse_snapshot_id(file_name: &str) -> Option<u64> {
     id_str.parse().ok()
 }
 
+/// Read and validate the header of a snapshot file
+///
+/// Only the first [`SNAPSHOT_HEADER_SIZE`] bytes are read; sections and the
+/// footer CRC are not checked.
+pub fn read_snapshot_header(path: &Path) -> std::io::Result<SnapshotHeader> {
+    use std::io::Read;
+
+    let mut bytes = [0u8; SNAPSHOT_HEADER_SIZE];
+    std::fs::File::open(path)?.read_exact(&mut bytes)?;
+    let header = SnapshotHeader::from_bytes(&bytes).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid snapshot header")
+    })?;
+    header
+        .validate()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(header)
+}
+
 /// List all snapshot files in a directory, sorted by ID
 pub fn list_snapshots(dir: &Path) -> std::io::Result<Vec<(u64, PathBuf)>> {
     let mut snapshots = Vec::new();
@@ -275,6 +293,21 @@ mod tests {
         assert_eq!(header, parsed);
     }
 
+    #[test]
+    fn test_read_snapshot_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_path(dir.path(), 3);
+        let header = SnapshotHeader::new(3, 42, 1234567890, [2u8; 16], 8);
+        let mut contents = header.to_bytes().to_vec();
+        contents.extend_from_slice(b"identity");
+        std::fs::write(&path, &contents).unwrap();
+        assert_eq!(read_snapshot_header(&path).unwrap(), header);
+
+        std::fs::write(&path, [0u8; SNAPSHOT_HEADER_SIZE]).unwrap();
+        let err = read_snapshot_header(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
     #[test]
     fn test_snapshot_header_magic() {
         let uuid = [1u8; 16];
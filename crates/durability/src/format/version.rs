@@ -0,0 +1,184 @@
+//! Format version checks for an existing database directory
+//!
+//! WAL segments, snapshots and the MANIFEST all start with 4 magic bytes
+//! followed by a little-endian u32 format version. A build that reads a file
+//! written by a newer format would misinterpret its bytes, so
+//! [`check_format_versions`] is run before recovery and refuses to go on if
+//! any file is newer than this build supports.
+//!
+//! Only the 8-byte prefix is inspected. Files that are too short or have
+//! the wrong magic are left to the regular readers, which already handle
+//! torn writes and corruption.
+
+use super::manifest::{MANIFEST_FORMAT_VERSION, MANIFEST_MAGIC};
+use super::snapshot::{list_snapshots, SNAPSHOT_FORMAT_VERSION, SNAPSHOT_MAGIC};
+use super::wal_record::{SEGMENT_FORMAT_VERSION, SEGMENT_MAGIC};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Errors from [`check_format_versions`]
+#[derive(Debug, thiserror::Error)]
+pub enum FormatVersionError {
+    /// A file was written by a newer format version
+    #[error(
+        "{format} '{}' has format version {found}, but this build supports up to {supported}",
+        path.display()
+    )]
+    Unsupported {
+        /// Kind of file: "WAL segment", "snapshot" or "MANIFEST"
+        format: &'static str,
+        /// Path of the file
+        path: PathBuf,
+        /// Version found in the file
+        found: u32,
+        /// Newest version this build can read
+        supported: u32,
+    },
+
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Check that no file in `data_dir` uses a newer format than this build.
+///
+/// Looks at the MANIFEST, every WAL segment in `wal/` and every snapshot in
+/// `snapshots/`. Missing files and directories are fine.
+pub fn check_format_versions(data_dir: &Path) -> Result<(), FormatVersionError> {
+    let manifest = data_dir.join("MANIFEST");
+    if manifest.exists() {
+        check_file(
+            &manifest,
+            "MANIFEST",
+            MANIFEST_MAGIC,
+            MANIFEST_FORMAT_VERSION,
+        )?;
+    }
+
+    let wal_dir = data_dir.join("wal");
+    if wal_dir.is_dir() {
+        for entry in std::fs::read_dir(&wal_dir)? {
+            let path = entry?.path();
+            let is_segment = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("wal-") && name.ends_with(".seg"));
+            if is_segment {
+                check_file(&path, "WAL segment", SEGMENT_MAGIC, SEGMENT_FORMAT_VERSION)?;
+            }
+        }
+    }
+
+    for (_, path) in list_snapshots(&data_dir.join("snapshots"))? {
+        check_file(&path, "snapshot", SNAPSHOT_MAGIC, SNAPSHOT_FORMAT_VERSION)?;
+    }
+    Ok(())
+}
+
+fn check_file(
+    path: &Path,
+    format: &'static str,
+    magic: [u8; 4],
+    supported: u32,
+) -> Result<(), FormatVersionError> {
+    let mut prefix = [0u8; 8];
+    let mut file = std::fs::File::open(path)?;
+    match file.read_exact(&mut prefix) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
+    if prefix[0..4] != magic {
+        return Ok(());
+    }
+    let found = u32::from_le_bytes(prefix[4..8].try_into().unwrap());
+    if found > supported {
+        return Err(FormatVersionError::Unsupported {
+            format,
+            path: path.to_path_buf(),
+            found,
+            supported,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{Manifest, SegmentHeader, SnapshotHeader};
+    use tempfile::tempdir;
+
+    fn write(path: &Path, bytes: &[u8]) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_current_versions_pass() {
+        let dir = tempdir().unwrap();
+        assert!(check_format_versions(dir.path()).is_ok());
+
+        let manifest = Manifest::new([1u8; 16], "identity".to_string());
+        write(&dir.path().join("MANIFEST"), &manifest.to_bytes());
+        write(
+            &dir.path().join("wal/wal-000001.seg"),
+            &SegmentHeader::new(1, [1u8; 16]).to_bytes(),
+        );
+        write(
+            &dir.path().join("snapshots/snap-000001.chk"),
+            &SnapshotHeader::new(1, 10, 0, [1u8; 16], 0).to_bytes(),
+        );
+        // Too short to hold a version: left to the regular readers
+        write(&dir.path().join("wal/wal-000002.seg"), b"ST");
+        assert!(check_format_versions(dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_newer_versions_rejected() {
+        let dir = tempdir().unwrap();
+        let mut header = SegmentHeader::new(1, [1u8; 16]);
+        header.format_version = SEGMENT_FORMAT_VERSION + 1;
+        write(&dir.path().join("wal/wal-000001.seg"), &header.to_bytes());
+        match check_format_versions(dir.path()) {
+            Err(FormatVersionError::Unsupported {
+                format,
+                found,
+                supported,
+                ..
+            }) => {
+                assert_eq!(format, "WAL segment");
+                assert_eq!(found, SEGMENT_FORMAT_VERSION + 1);
+                assert_eq!(supported, SEGMENT_FORMAT_VERSION);
+            }
+            other => panic!("expected Unsupported, got {:?}", other),
+        }
+
+        let dir = tempdir().unwrap();
+        let mut header = SnapshotHeader::new(1, 10, 0, [1u8; 16], 0);
+        header.format_version = SNAPSHOT_FORMAT_VERSION + 1;
+        write(
+            &dir.path().join("snapshots/snap-000001.chk"),
+            &header.to_bytes(),
+        );
+        assert!(matches!(
+            check_format_versions(dir.path()),
+            Err(FormatVersionError::Unsupported {
+                format: "snapshot",
+                ..
+            })
+        ));
+
+        let dir = tempdir().unwrap();
+        let mut bytes = Manifest::new([1u8; 16], "identity".to_string()).to_bytes();
+        bytes[4..8].copy_from_slice(&(MANIFEST_FORMAT_VERSION + 1).to_le_bytes());
+        write(&dir.path().join("MANIFEST"), &bytes);
+        assert!(matches!(
+            check_format_versions(dir.path()),
+            Err(FormatVersionError::Unsupported {
+                format: "MANIFEST",
+                ..
+            })
+        ));
+    }
+}
@@ -162,6 +162,17 @@ impl SegmentHeader {
     }
 }
 
+/// Error for a segment written by a newer format version.
+fn unsupported_version(version: u32) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!(
+            "Unsupported segment format version {}, max supported is {}",
+            version, SEGMENT_FORMAT_VERSION
+        ),
+    )
+}
+
 /// WAL segment file handle.
 ///
 /// A segment is a single WAL file containing multiple records.
@@ -269,6 +280,10 @@ impl WalSegment {
             ));
         }
 
+        if header.format_version > SEGMENT_FORMAT_VERSION {
+            return Err(unsupported_version(header.format_version));
+        }
+
         if header.segment_number != segment_number {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
@@ -346,6 +361,10 @@ impl WalSegment {
             ));
         }
 
+        if header.format_version > SEGMENT_FORMAT_VERSION {
+            return Err(unsupported_version(header.format_version));
+        }
+
         let actual_header_size = if header.format_version >= 2 {
             SEGMENT_HEADER_SIZE_V2
         } else {
@@ -672,6 +691,21 @@ mod tests {
         assert!(!header.is_valid());
     }
 
+    #[test]
+    fn test_segment_newer_version_rejected() {
+        let dir = tempdir().unwrap();
+        let mut header = SegmentHeader::new(1, [1u8; 16]);
+        header.format_version = SEGMENT_FORMAT_VERSION + 1;
+        header.header_crc = header.compute_crc();
+        std::fs::write(WalSegment::segment_path(dir.path(), 1), header.to_bytes()).unwrap();
+
+        let err = WalSegment::open_read(dir.path(), 1).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("Unsupported segment format version"));
+        assert!(WalSegment::open_append(dir.path(), 1).is_err());
+    }
+
     #[test]
     fn test_segment_path_format() {
         let dir = Path::new("/tmp/wal");
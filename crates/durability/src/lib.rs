@@ -69,17 +69,21 @@ pub use disk_snapshot::{
 
 // Format types
 pub use format::{
+    // Format version checks
+    check_format_versions,
     // Snapshot format
     find_latest_snapshot,
     list_snapshots,
     parse_snapshot_id,
     primitive_tags,
+    read_snapshot_header,
     snapshot_path,
     BranchSnapshotEntry,
     // Watermark tracking
     CheckpointInfo,
     // Primitive serialization
     EventSnapshotEntry,
+    FormatVersionError,
     JsonSnapshotEntry,
     KvSnapshotEntry,
     // MANIFEST format
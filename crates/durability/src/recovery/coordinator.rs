@@ -212,9 +212,9 @@ impl RecoveryCoordinator {
 
             // Scan segment records and build metadata
             match reader.read_segment(&wal_dir, seg_num) {
-                Ok((records, _, _, _)) => {
+                Ok(segment_read) => {
                     let mut meta = SegmentMeta::new_empty(seg_num);
-                    for record in &records {
+                    for record in &segment_read.records {
                         meta.track_record(record.txn_id, record.timestamp);
                     }
                     if !meta.is_empty() {
@@ -95,14 +95,14 @@ impl WalReplayer {
         stats.segments_read = segments.len();
 
         for segment_number in segments {
-            let (records, _valid_end, _stop_reason, skipped_corrupted) = self
+            let segment_read = self
                 .reader
                 .read_segment(&self.wal_dir, segment_number)
                 .map_err(WalReplayError::from)?;
 
-            stats.records_skipped_corrupted += skipped_corrupted;
+            stats.records_skipped_corrupted += segment_read.skipped_corrupted;
 
-            for record in records {
+            for record in segment_read.records {
                 stats.records_read += 1;
 
                 // Skip records at or before watermark
@@ -22,6 +22,8 @@
 
 use std::time::Duration;
 
+use super::reference_model::StateMismatch;
+
 /// Configuration for crash injection
 #[derive(Debug, Clone)]
 pub struct CrashConfig {
@@ -258,17 +260,6 @@ impl VerificationResult {
     }
 }
 
-/// State mismatch found during verification
-#[derive(Debug, Clone)]
-pub struct StateMismatch {
-    /// Entity identifier (e.g., "kv:branch:key")
-    pub entity: String,
-    /// Expected value
-    pub expected: String,
-    /// Actual value
-    pub actual: String,
-}
-
 /// Crash test errors
 #[derive(Debug, thiserror::Error)]
 pub enum CrashTestError {
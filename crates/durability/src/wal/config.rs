@@ -15,13 +15,24 @@ pub struct WalConfig {
     /// For Standard durability mode, fsync is triggered when this many
     /// bytes have been written since the last fsync.
     pub buffered_sync_bytes: u64,
+
+    /// Largest transaction payload accepted into a WAL record, in bytes
+    /// (default: 64MB).
+    ///
+    /// Commits whose serialized payload exceeds this are rejected before
+    /// anything is written.
+    pub max_record_bytes: u64,
 }
 
+/// Default for [`WalConfig::max_record_bytes`]: 64MB.
+pub const DEFAULT_MAX_RECORD_BYTES: u64 = 64 * 1024 * 1024;
+
 impl Default for WalConfig {
     fn default() -> Self {
         WalConfig {
             segment_size: 64 * 1024 * 1024,       // 64MB
             buffered_sync_bytes: 4 * 1024 * 1024, // 4MB
+            max_record_bytes: DEFAULT_MAX_RECORD_BYTES,
         }
     }
 }
@@ -44,6 +55,12 @@ impl WalConfig {
         self
     }
 
+    /// Set the maximum WAL record payload size (builder pattern).
+    pub fn with_max_record_bytes(mut self, bytes: u64) -> Self {
+        self.max_record_bytes = bytes;
+        self
+    }
+
     /// Validate configuration.
     pub fn validate(&self) -> Result<(), WalConfigError> {
         if self.segment_size < 1024 {
@@ -60,6 +77,7 @@ impl WalConfig {
         WalConfig {
             segment_size: 64 * 1024,        // 64KB for faster rotation in tests
             buffered_sync_bytes: 16 * 1024, // 16KB
+            max_record_bytes: DEFAULT_MAX_RECORD_BYTES,
         }
     }
 }
@@ -85,16 +103,19 @@ mod tests {
         let config = WalConfig::default();
         assert_eq!(config.segment_size, 64 * 1024 * 1024);
         assert_eq!(config.buffered_sync_bytes, 4 * 1024 * 1024);
+        assert_eq!(config.max_record_bytes, 64 * 1024 * 1024);
     }
 
     #[test]
     fn test_builder_pattern() {
         let config = WalConfig::new()
             .with_segment_size(128 * 1024 * 1024)
-            .with_buffered_sync_bytes(8 * 1024 * 1024);
+            .with_buffered_sync_bytes(8 * 1024 * 1024)
+            .with_max_record_bytes(1024);
 
         assert_eq!(config.segment_size, 128 * 1024 * 1024);
         assert_eq!(config.buffered_sync_bytes, 8 * 1024 * 1024);
+        assert_eq!(config.max_record_bytes, 1024);
     }
 
     #[test]
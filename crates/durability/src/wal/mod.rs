@@ -14,6 +14,6 @@ pub mod writer;
 pub use mode::DurabilityMode;
 
 // Segmented WAL types (primary API)
-pub use config::{WalConfig, WalConfigError};
-pub use reader::{ReadStopReason, TruncateInfo, WalReader, WalReaderError};
+pub use config::{WalConfig, WalConfigError, DEFAULT_MAX_RECORD_BYTES};
+pub use reader::{ReadStopReason, SegmentReadResult, TruncateInfo, WalReader, WalReaderError};
 pub use writer::{WalCounters, WalWriter};
@@ -4,7 +4,7 @@
 
 use crate::codec::StorageCodec;
 use crate::format::segment_meta::SegmentMeta;
-use crate::format::{WalRecord, WalRecordError, WalSegment};
+use crate::format::{WalRecord, WalRecordError, WalSegment, WAL_RECORD_FORMAT_VERSION};
 use std::io::Read;
 use std::path::Path;
 use tracing::warn;
@@ -23,23 +23,41 @@ pub struct WalReader {
     /// decoding is implemented. The identity codec passes through unchanged.
     #[allow(dead_code)]
     codec: Box<dyn StorageCodec>,
+
+    /// Fail on records in a newer record format instead of skipping them
+    reject_newer_format: bool,
 }
 
 impl WalReader {
     /// Create a new WAL reader.
     pub fn new(codec: Box<dyn StorageCodec>) -> Self {
-        WalReader { codec }
+        WalReader {
+            codec,
+            reject_newer_format: false,
+        }
+    }
+
+    /// Fail on records written in a newer record format.
+    ///
+    /// Such records have a valid length and checksum, so by default they
+    /// are skipped with a warning and the records after them are still
+    /// read. This lets an older build recover what it understands from a
+    /// WAL that a newer build also wrote to.
+    pub fn with_reject_newer_format(mut self, reject: bool) -> Self {
+        self.reject_newer_format = reject;
+        self
     }
 
     /// Read all records from a single segment.
     ///
     /// Returns records in order, stopping at the first invalid/incomplete record.
-    /// The returned position indicates where valid records end (for truncation).
+    /// [`SegmentReadResult::valid_end`] is where valid records end (for
+    /// truncation).
     pub fn read_segment(
         &self,
         wal_dir: &Path,
         segment_number: u64,
-    ) -> Result<(Vec<WalRecord>, u64, ReadStopReason, usize), WalReaderError> {
+    ) -> Result<SegmentReadResult, WalReaderError> {
         let mut segment = WalSegment::open_read(wal_dir, segment_number)
             .map_err(|e: std::io::Error| WalReaderError::IoError(e.to_string()))?;
 
@@ -50,7 +68,7 @@ impl WalReader {
     pub fn read_segment_from(
         &self,
         segment: &mut WalSegment,
-    ) -> Result<(Vec<WalRecord>, u64, ReadStopReason, usize), WalReaderError> {
+    ) -> Result<SegmentReadResult, WalReaderError> {
         let mut records = Vec::new();
         let mut buffer = Vec::new();
         let hdr_size = segment.header_size() as u64;
@@ -70,6 +88,7 @@ impl WalReader {
         let mut offset = 0;
         let mut stop_reason = ReadStopReason::EndOfData;
         let mut skipped_corrupted = 0usize;
+        let mut skipped_newer_format = 0usize;
 
         while offset < buffer.len() {
             // Try to decode through codec first
@@ -120,6 +139,32 @@ impl WalReader {
                     stop_reason = ReadStopReason::ChecksumMismatch { offset };
                     break;
                 }
+                Err(WalRecordError::UnsupportedVersion(version))
+                    if version > WAL_RECORD_FORMAT_VERSION =>
+                {
+                    // Framed and checksummed like any other record, so its
+                    // length is trustworthy and the records after it can be
+                    // read.
+                    if self.reject_newer_format {
+                        return Err(WalReaderError::ParseError(format!(
+                            "segment {} has a record in newer format version {} at offset {}",
+                            segment.segment_number(),
+                            version,
+                            offset
+                        )));
+                    }
+                    let length = u32::from_le_bytes(remaining[0..4].try_into().unwrap()) as usize;
+                    warn!(
+                        target: "strata::recovery",
+                        segment = segment.segment_number(),
+                        offset,
+                        format_version = version,
+                        "Skipped WAL record in a newer record format"
+                    );
+                    offset += 4 + length;
+                    valid_end = hdr_size + offset as u64;
+                    skipped_newer_format += 1;
+                }
                 Err(e) => {
                     // CRC was valid but payload couldn't be parsed.
                     // This indicates codec mismatch or format version incompatibility,
@@ -133,7 +178,13 @@ impl WalReader {
             }
         }
 
-        Ok((records, valid_end, stop_reason, skipped_corrupted))
+        Ok(SegmentReadResult {
+            records,
+            valid_end,
+            stop_reason,
+            skipped_corrupted,
+            skipped_newer_format,
+        })
     }
 
     /// Read all records from all segments in a WAL directory.
@@ -148,13 +199,15 @@ impl WalReader {
         let mut truncate_info = None;
         let mut last_stop_reason = ReadStopReason::EndOfData;
         let mut total_skipped_corrupted = 0usize;
+        let mut total_skipped_newer_format = 0usize;
 
         for (idx, segment_num) in segments.iter().enumerate() {
-            let (records, valid_end, stop_reason, skipped) =
-                self.read_segment(wal_dir, *segment_num)?;
-            all_records.extend(records);
-            last_stop_reason = stop_reason;
-            total_skipped_corrupted += skipped;
+            let segment_read = self.read_segment(wal_dir, *segment_num)?;
+            let valid_end = segment_read.valid_end;
+            all_records.extend(segment_read.records);
+            last_stop_reason = segment_read.stop_reason;
+            total_skipped_corrupted += segment_read.skipped_corrupted;
+            total_skipped_newer_format += segment_read.skipped_newer_format;
 
             // Check if this segment needs truncation (only the last one can)
             if idx == segments.len() - 1 {
@@ -176,6 +229,7 @@ impl WalReader {
             truncate_info,
             stop_reason: last_stop_reason,
             skipped_corrupted: total_skipped_corrupted,
+            skipped_newer_format: total_skipped_newer_format,
         })
     }
 
@@ -188,7 +242,7 @@ impl WalReader {
         segment_number: u64,
         watermark: u64,
     ) -> Result<Vec<WalRecord>, WalReaderError> {
-        let (records, _, _, _) = self.read_segment(wal_dir, segment_number)?;
+        let records = self.read_segment(wal_dir, segment_number)?.records;
 
         Ok(records
             .into_iter()
@@ -242,7 +296,7 @@ impl WalReader {
         wal_dir: &Path,
         segment_number: u64,
     ) -> Result<Option<u64>, WalReaderError> {
-        let (records, _, _, _) = self.read_segment(wal_dir, segment_number)?;
+        let records = self.read_segment(wal_dir, segment_number)?.records;
         Ok(records.iter().map(|r| r.txn_id).max())
     }
 
@@ -397,6 +451,28 @@ pub struct WalReadResult {
 
     /// Number of corrupted records that were skipped during reading
     pub skipped_corrupted: usize,
+
+    /// Number of records in a newer record format that were skipped
+    pub skipped_newer_format: usize,
+}
+
+/// Result of reading one WAL segment.
+#[derive(Debug)]
+pub struct SegmentReadResult {
+    /// Valid records in order
+    pub records: Vec<WalRecord>,
+
+    /// Position where valid records end
+    pub valid_end: u64,
+
+    /// Why reading stopped (for diagnostics)
+    pub stop_reason: ReadStopReason,
+
+    /// Number of corrupted records that were skipped
+    pub skipped_corrupted: usize,
+
+    /// Number of records in a newer record format that were skipped
+    pub skipped_newer_format: usize,
 }
 
 /// Information about a segment that needs truncation.
@@ -475,7 +551,7 @@ mod tests {
         WalSegment::create(&wal_dir, 1, [1u8; 16]).unwrap();
 
         let reader = WalReader::new(make_codec());
-        let (records, _, _, _) = reader.read_segment(&wal_dir, 1).unwrap();
+        let records = reader.read_segment(&wal_dir, 1).unwrap().records;
 
         assert!(records.is_empty());
     }
@@ -489,7 +565,7 @@ mod tests {
         write_records(&wal_dir, &[record.clone()]);
 
         let reader = WalReader::new(make_codec());
-        let (records, _, _, _) = reader.read_segment(&wal_dir, 1).unwrap();
+        let records = reader.read_segment(&wal_dir, 1).unwrap().records;
 
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].txn_id, 1);
@@ -600,6 +676,47 @@ mod tests {
         assert_eq!(truncate.bytes_to_truncate(), 10);
     }
 
+    /// A well-framed record in a record format newer than this build reads
+    fn future_record_bytes(txn_id: u64) -> Vec<u8> {
+        let mut bytes = WalRecord::new(txn_id, [1u8; 16], 0, vec![0xAB; 8]).to_bytes();
+        bytes[4] = WAL_RECORD_FORMAT_VERSION + 1;
+        let crc_at = bytes.len() - 4;
+        let crc = crc32fast::hash(&bytes[4..crc_at]);
+        bytes[crc_at..].copy_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_newer_record_format_skipped() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+        std::fs::create_dir_all(&wal_dir).unwrap();
+
+        let mut segment = WalSegment::create(&wal_dir, 1, [1u8; 16]).unwrap();
+        segment
+            .write(&WalRecord::new(1, [1u8; 16], 0, vec![1]).to_bytes())
+            .unwrap();
+        segment.write(&future_record_bytes(2)).unwrap();
+        segment
+            .write(&WalRecord::new(3, [1u8; 16], 0, vec![3]).to_bytes())
+            .unwrap();
+        segment.close().unwrap();
+
+        let result = WalReader::new(make_codec()).read_all(&wal_dir).unwrap();
+        let txn_ids: Vec<u64> = result.records.iter().map(|r| r.txn_id).collect();
+        assert_eq!(txn_ids, vec![1, 3]);
+        assert_eq!(result.skipped_newer_format, 1);
+        assert_eq!(result.stop_reason, ReadStopReason::EndOfData);
+        // The skipped record is kept on disk, not truncated away
+        assert!(result.truncate_info.is_none());
+
+        let strict = WalReader::new(make_codec()).with_reject_newer_format(true);
+        assert!(matches!(
+            strict.read_all(&wal_dir),
+            Err(WalReaderError::ParseError(_))
+        ));
+    }
+
     // ---- Metadata query tests ----
 
     /// Helper: write records using WalWriter (which emits .meta on close)
@@ -18,7 +18,7 @@ use tracing::{debug, info, warn};
 /// These counters accumulate over the lifetime of the WalWriter
 /// and are never reset. Use them to observe how many WAL operations
 /// a workload triggers.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct WalCounters {
     /// Total WAL record appends (calls to append() that did work)
     pub wal_appends: u64,
@@ -28,6 +28,10 @@ pub struct WalCounters {
     pub bytes_written: u64,
     /// Total nanoseconds spent in sync/fsync calls
     pub sync_nanos: u64,
+    /// Bytes appended since the last sync (current sync lag, not cumulative)
+    pub unsynced_bytes: u64,
+    /// Records appended since the last sync (current sync lag, not cumulative)
+    pub unsynced_writes: u64,
 }
 
 /// WAL writer with configurable durability modes.
@@ -322,6 +326,20 @@ impl WalWriter {
         Ok(())
     }
 
+    /// Durability barrier: fsync the active segment and the WAL directory.
+    ///
+    /// Like `flush()`, but also syncs the directory entry so a segment
+    /// created by a recent rotation is guaranteed to survive power loss.
+    /// When this returns `Ok`, every record appended so far is durable.
+    pub fn sync(&mut self) -> std::io::Result<()> {
+        self.flush()?;
+        #[cfg(unix)]
+        if self.segment.is_some() {
+            std::fs::File::open(&self.wal_dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
     /// Sync if the batched interval has elapsed and there is unsynced data.
     ///
     /// Call this periodically (e.g., from a maintenance timer) to ensure
@@ -350,6 +368,24 @@ impl WalWriter {
         Ok(false)
     }
 
+    /// Change the Standard-mode sync interval.
+    ///
+    /// Takes effect on the next `sync_if_overdue()` check. Has no effect in
+    /// `Always` or `Cache` mode. Returns `false` if the writer is not in
+    /// Standard mode.
+    pub fn set_sync_interval(&mut self, interval_ms: u64) -> bool {
+        match &mut self.durability {
+            DurabilityMode::Standard {
+                interval_ms: current,
+                ..
+            } => {
+                *current = interval_ms;
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Get the current segment number.
     pub fn current_segment(&self) -> u64 {
         self.current_segment_number
@@ -363,6 +399,14 @@ impl WalWriter {
             .unwrap_or(SEGMENT_HEADER_SIZE_V2 as u64)
     }
 
+    /// Largest transaction payload this writer accepts, in bytes.
+    ///
+    /// Enforced by the commit path before a record is built; see
+    /// [`WalConfig::max_record_bytes`].
+    pub fn max_record_bytes(&self) -> u64 {
+        self.config.max_record_bytes
+    }
+
     /// Get a snapshot of cumulative WAL counters.
     pub fn counters(&self) -> WalCounters {
         WalCounters {
@@ -370,6 +414,8 @@ impl WalWriter {
             sync_calls: self.total_sync_calls,
             bytes_written: self.total_bytes_written,
             sync_nanos: self.total_sync_nanos,
+            unsynced_bytes: self.bytes_since_sync,
+            unsynced_writes: self.writes_since_sync as u64,
         }
     }
 
@@ -392,9 +438,9 @@ impl WalWriter {
     fn rebuild_meta_for_segment(wal_dir: &Path, segment_number: u64) -> Option<SegmentMeta> {
         let reader = WalReader::new(Box::new(crate::codec::IdentityCodec));
         match reader.read_segment(wal_dir, segment_number) {
-            Ok((records, _, _, _)) => {
+            Ok(segment_read) => {
                 let mut meta = SegmentMeta::new_empty(segment_number);
-                for record in &records {
+                for record in &segment_read.records {
                     meta.track_record(record.txn_id, record.timestamp);
                 }
                 Some(meta)
@@ -639,4 +685,52 @@ mod tests {
         // Segment should have data
         assert!(writer.current_segment_size() > SEGMENT_HEADER_SIZE_V2 as u64);
     }
+
+    #[test]
+    fn test_sync_is_durability_barrier() {
+        let dir = tempdir().unwrap();
+        let mut writer = make_writer(
+            dir.path(),
+            DurabilityMode::Standard {
+                interval_ms: 60_000,
+                batch_size: 1000,
+            },
+        );
+
+        writer.append(&make_record(1)).unwrap();
+        let before = writer.counters();
+        assert_eq!(before.unsynced_writes, 1);
+        assert!(before.unsynced_bytes > 0);
+        writer.sync().unwrap();
+        let after = writer.counters();
+        assert_eq!(after.sync_calls, before.sync_calls + 1);
+        assert_eq!(after.unsynced_writes, 0);
+        assert_eq!(after.unsynced_bytes, 0);
+
+        // Nothing left for the background thread to sync
+        assert!(!writer.sync_if_overdue().unwrap());
+    }
+
+    #[test]
+    fn test_set_sync_interval() {
+        let dir = tempdir().unwrap();
+        let mut writer = make_writer(
+            dir.path(),
+            DurabilityMode::Standard {
+                interval_ms: 60_000,
+                batch_size: 1000,
+            },
+        );
+
+        writer.append(&make_record(1)).unwrap();
+        assert!(!writer.sync_if_overdue().unwrap());
+
+        // Shrinking the interval makes the pending data overdue
+        assert!(writer.set_sync_interval(1));
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(writer.sync_if_overdue().unwrap());
+
+        let mut always = make_writer(&dir.path().join("always"), DurabilityMode::Always);
+        assert!(!always.set_sync_interval(1));
+    }
 }
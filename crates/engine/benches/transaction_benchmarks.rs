@@ -5,13 +5,15 @@
 //! - Single-threaded: >5K txns/sec
 //! - Multi-threaded (no conflict): >10K txns/sec
 //! - Multi-threaded (with conflict): >2K txns/sec
+//! - Commit lock shards: throughput across many branches should scale with
+//!   thread count and not regress versus per-branch locks
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use std::sync::Arc;
 use std::thread;
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
-use strata_engine::Database;
+use strata_engine::{Database, StrataConfig};
 use tempfile::TempDir;
 
 fn create_ns(branch_id: BranchId) -> Namespace {
@@ -273,6 +275,71 @@ fn bench_direct_operations(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark: Multi-threaded commits spread over many short-lived branches,
+/// comparing per-branch commit locks against fixed commit lock shards.
+fn bench_commit_lock_shards(c: &mut Criterion) {
+    const BRANCHES_PER_THREAD: u64 = 64;
+    let mut group = c.benchmark_group("commit_lock_shards");
+
+    for shards in [None, Some(1), Some(16), Some(64)] {
+        let label = shards.map_or("per_branch".to_string(), |n| format!("shards_{}", n));
+        for num_threads in [2, 4, 8] {
+            group.throughput(Throughput::Elements(num_threads as u64));
+
+            group.bench_with_input(
+                BenchmarkId::new(label.as_str(), num_threads),
+                &num_threads,
+                |b, &num_threads| {
+                    b.iter_custom(|iters| {
+                        let temp_dir = TempDir::new().unwrap();
+                        let cfg = StrataConfig {
+                            commit_lock_shards: shards,
+                            ..Default::default()
+                        };
+                        let db =
+                            Database::open_with_config(temp_dir.path().join("db"), cfg).unwrap();
+
+                        let start = std::time::Instant::now();
+
+                        let handles: Vec<_> = (0..num_threads)
+                            .map(|_| {
+                                let db = Arc::clone(&db);
+                                let branches: Vec<_> = (0..BRANCHES_PER_THREAD)
+                                    .map(|_| {
+                                        let branch_id = BranchId::new();
+                                        (branch_id, create_ns(branch_id))
+                                    })
+                                    .collect();
+
+                                thread::spawn(move || {
+                                    for i in 0..iters {
+                                        let (branch_id, ns) =
+                                            &branches[(i % BRANCHES_PER_THREAD) as usize];
+                                        let key = Key::new_kv(ns.clone(), format!("k{}", i));
+                                        db.transaction(*branch_id, |txn| {
+                                            txn.put(key.clone(), Value::Int(i as i64))?;
+                                            Ok(())
+                                        })
+                                        .unwrap();
+                                    }
+                                })
+                            })
+                            .collect();
+
+                        for h in handles {
+                            h.join().unwrap();
+                        }
+
+                        start.elapsed()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_single_threaded_transactions,
@@ -280,6 +347,7 @@ criterion_group!(
     bench_multi_threaded_with_conflict,
     bench_read_only_transactions,
     bench_direct_operations,
+    bench_commit_lock_shards,
 );
 
 criterion_main!(benches);
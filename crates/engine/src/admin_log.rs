@@ -0,0 +1,259 @@
+//! Audit trail of administrative actions
+//!
+//! Branch creation and deletion, checkpoints, compaction, restores and
+//! configuration changes are recorded as events in a reserved branch, apart
+//! from user event logs. Like the health probe branch, it is not in the
+//! branch index, so it never shows up in branch listings and cannot be
+//! created, forked or deleted by name.
+//!
+//! ## Tamper Evidence
+//!
+//! Entries are regular events, so each one carries the hash of the entry
+//! before it. [`verify`] recomputes the chain and reports the first entry
+//! that was changed, removed or reordered.
+//!
+//! ## Restores
+//!
+//! A restore rewrites the WAL, which rolls this log back to the snapshot
+//! along with everything else. The restore appends its own entry to the
+//! rewritten WAL, so the log stays one chain: entries up to the snapshot,
+//! then the restore.
+
+use crate::database::{Database, RestoreInfo};
+use crate::primitives::branch::resolve_branch_name;
+use crate::primitives::EventLog;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use strata_core::primitives::{ChainVerification, Event};
+use strata_core::types::{BranchId, Key, Namespace};
+use strata_core::value::Value;
+use strata_core::{StrataError, StrataResult};
+
+/// Branch name reserved for the admin log. Branches with this name, or any
+/// name resolving to the same ID, cannot be created.
+pub const ADMIN_BRANCH_NAME: &str = "_strata_admin";
+
+/// Space holding the admin log events
+const ADMIN_SPACE: &str = "default";
+
+/// ID of the branch reserved for the admin log
+pub fn admin_branch_id() -> BranchId {
+    resolve_branch_name(ADMIN_BRANCH_NAME)
+}
+
+fn admin_namespace() -> Namespace {
+    Namespace::for_branch_space(admin_branch_id(), ADMIN_SPACE)
+}
+
+/// Kind of administrative action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    /// A branch was created
+    BranchCreate,
+    /// A branch was forked from another
+    BranchFork,
+    /// A branch and its data were deleted
+    BranchDelete,
+    /// Derived state was saved to disk
+    Checkpoint,
+    /// The WAL was compacted
+    Compact,
+    /// Old versions were pruned from a branch's in-memory history
+    GcVersions,
+    /// The database was restored to a snapshot
+    Restore,
+    /// A setting was changed at runtime
+    ConfigChange,
+}
+
+impl AdminAction {
+    /// Event type the action is recorded under
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminAction::BranchCreate => "branch_create",
+            AdminAction::BranchFork => "branch_fork",
+            AdminAction::BranchDelete => "branch_delete",
+            AdminAction::Checkpoint => "checkpoint",
+            AdminAction::Compact => "compact",
+            AdminAction::GcVersions => "gc_versions",
+            AdminAction::Restore => "restore",
+            AdminAction::ConfigChange => "config_change",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            AdminAction::BranchCreate,
+            AdminAction::BranchFork,
+            AdminAction::BranchDelete,
+            AdminAction::Checkpoint,
+            AdminAction::Compact,
+            AdminAction::GcVersions,
+            AdminAction::Restore,
+            AdminAction::ConfigChange,
+        ]
+        .into_iter()
+        .find(|action| action.as_str() == name)
+    }
+}
+
+/// One recorded administrative action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AdminEntry {
+    /// Position in the admin log (0 for the first entry)
+    pub sequence: u64,
+    /// When the action happened (microseconds since epoch)
+    pub timestamp: u64,
+    /// What was done
+    pub action: AdminAction,
+    /// What it was done to: a branch name, or a setting for config changes
+    pub target: Option<String>,
+    /// Who did it, as given by the handle that performed the action
+    pub actor: Option<String>,
+    /// Further details, such as the fork source or the new setting value
+    pub detail: Option<String>,
+}
+
+impl AdminEntry {
+    fn from_event(event: &Event) -> StrataResult<Self> {
+        let action = AdminAction::from_name(&event.event_type).ok_or_else(|| {
+            StrataError::serialization(format!(
+                "admin log entry {} has unknown action '{}'",
+                event.sequence, event.event_type
+            ))
+        })?;
+        let fields = match &event.payload {
+            Value::Object(fields) => fields,
+            _ => {
+                return Err(StrataError::serialization(format!(
+                    "admin log entry {} is not an object",
+                    event.sequence
+                )))
+            }
+        };
+        let text = |name: &str| match fields.get(name) {
+            Some(Value::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+        Ok(Self {
+            sequence: event.sequence,
+            timestamp: event.timestamp,
+            action,
+            target: text("target"),
+            actor: text("actor"),
+            detail: text("detail"),
+        })
+    }
+}
+
+/// Append an entry to the admin log and return its sequence number.
+///
+/// The entry commits like any other write, so it is as durable as the
+/// database's durability mode makes it.
+pub fn record(
+    db: &Arc<Database>,
+    action: AdminAction,
+    target: Option<&str>,
+    actor: Option<&str>,
+    detail: Option<&str>,
+) -> StrataResult<u64> {
+    let event = EventLog::new(db.clone()).append_event(
+        &admin_branch_id(),
+        ADMIN_SPACE,
+        action.as_str(),
+        entry_payload(target, actor, detail),
+    )?;
+    Ok(event.sequence)
+}
+
+fn entry_payload(target: Option<&str>, actor: Option<&str>, detail: Option<&str>) -> Value {
+    let mut fields = HashMap::new();
+    for (name, value) in [("target", target), ("actor", actor), ("detail", detail)] {
+        if let Some(value) = value {
+            fields.insert(name.to_string(), Value::String(value.to_string()));
+        }
+    }
+    Value::Object(fields)
+}
+
+/// Key of the admin log metadata, which a restore reads from the WAL
+pub(crate) fn meta_key() -> Key {
+    Key::new_event_meta(admin_namespace())
+}
+
+/// Writes that append the entry for `restore` to an admin log whose
+/// metadata is `meta`.
+///
+/// Used by the restore itself, which cannot go through a transaction
+/// because the database is closed while its WAL is rewritten.
+pub(crate) fn restore_writes(
+    meta: Option<&Value>,
+    restore: &RestoreInfo,
+) -> StrataResult<Vec<(Key, Value)>> {
+    let detail = format!(
+        "snapshot {} at version {}; {} transactions discarded",
+        restore.snapshot_id, restore.watermark_txn, restore.txns_discarded
+    );
+    let (_, writes) = crate::primitives::event::append_writes(
+        &admin_namespace(),
+        meta,
+        AdminAction::Restore.as_str(),
+        entry_payload(None, None, Some(&detail)),
+        restore.restored_at,
+    )?;
+    Ok(writes)
+}
+
+/// Read the `limit` most recent admin log entries, newest first.
+pub fn entries(db: &Arc<Database>, limit: usize) -> StrataResult<Vec<AdminEntry>> {
+    let log = EventLog::new(db.clone());
+    let branch_id = admin_branch_id();
+    let len = log.len(&branch_id, ADMIN_SPACE)?;
+    let mut entries = Vec::new();
+    for sequence in (0..len).rev().take(limit) {
+        if let Some(event) = log.get(&branch_id, ADMIN_SPACE, sequence)? {
+            entries.push(AdminEntry::from_event(&event.value)?);
+        }
+    }
+    Ok(entries)
+}
+
+/// Recompute the admin log's hash chain.
+///
+/// See [`EventLog::verify_chain`].
+pub fn verify(db: &Arc<Database>) -> StrataResult<ChainVerification> {
+    EventLog::new(db.clone()).verify_chain(&admin_branch_id(), ADMIN_SPACE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_read_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path()).unwrap();
+        record(&db, AdminAction::BranchCreate, Some("a"), Some("ops"), None).unwrap();
+        record(&db, AdminAction::BranchDelete, Some("a"), None, None).unwrap();
+        record(&db, AdminAction::Compact, None, None, None).unwrap();
+
+        let log = entries(&db, 2).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].action, AdminAction::Compact);
+        assert_eq!(log[0].sequence, 2);
+        assert_eq!(log[1].action, AdminAction::BranchDelete);
+
+        let first = &entries(&db, 10).unwrap()[2];
+        assert_eq!(first.target.as_deref(), Some("a"));
+        assert_eq!(first.actor.as_deref(), Some("ops"));
+        assert!(verify(&db).unwrap().is_valid);
+
+        // Never listed as a user branch
+        let index = crate::BranchIndex::new(db.clone());
+        assert!(!index.exists(ADMIN_BRANCH_NAME).unwrap());
+        assert!(index.create_branch(ADMIN_BRANCH_NAME).is_err());
+    }
+}
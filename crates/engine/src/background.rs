@@ -2,15 +2,35 @@
 //!
 //! Provides a priority-based task queue with configurable worker threads.
 //! Designed for deferred work like embedding, GC, compaction, and index rebuilds.
+//!
+//! # Starvation avoidance
+//!
+//! Higher-priority tasks normally run first, so a steady stream of `High`
+//! work (e.g. interactive requests) could postpone `Low` work forever. To
+//! prevent that, the queue counts how many times the oldest waiting task was
+//! passed over for a newer, higher-priority one. After
+//! [`STARVATION_LIMIT`] consecutive bypasses the oldest task runs next,
+//! regardless of priority. Under sustained high-priority load, lower
+//! priorities therefore still get at least one slot in every
+//! `STARVATION_LIMIT + 1` dispatched tasks.
 
 use parking_lot::Mutex as ParkingMutex;
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::thread::JoinHandle;
 use tracing::error;
 
+/// Default number of scheduler worker threads.
+pub const DEFAULT_BACKGROUND_THREADS: usize = 2;
+
+/// Default maximum number of queued scheduler tasks.
+pub const DEFAULT_BACKGROUND_QUEUE_DEPTH: usize = 4096;
+
+/// Consecutive times the oldest queued task may be passed over for a
+/// higher-priority one before it is forced to run next.
+pub const STARVATION_LIMIT: usize = 16;
+
 /// Priority levels for background work.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TaskPriority {
@@ -42,8 +62,12 @@ pub struct SchedulerStats {
     pub active_tasks: usize,
     /// Total number of tasks completed since scheduler creation.
     pub tasks_completed: u64,
+    /// Total number of submissions rejected with [`BackpressureError`].
+    pub tasks_rejected: u64,
     /// Number of worker threads.
     pub worker_count: usize,
+    /// Maximum number of queued tasks before submissions are rejected.
+    pub max_queue_depth: usize,
 }
 
 struct TaskEnvelope {
@@ -52,31 +76,52 @@ struct TaskEnvelope {
     work: Box<dyn FnOnce() + Send>,
 }
 
-impl Eq for TaskEnvelope {}
+/// Pending tasks, one FIFO lane per priority (indexed by `TaskPriority as usize`).
+struct TaskQueue {
+    lanes: [VecDeque<TaskEnvelope>; 3],
+    /// Consecutive pops that skipped the oldest waiting task.
+    bypassed: usize,
+}
 
-impl PartialEq for TaskEnvelope {
-    fn eq(&self, other: &Self) -> bool {
-        self.priority == other.priority && self.sequence == other.sequence
+impl TaskQueue {
+    fn new() -> Self {
+        Self {
+            lanes: [VecDeque::new(), VecDeque::new(), VecDeque::new()],
+            bypassed: 0,
+        }
     }
-}
 
-// Higher priority first, then lower sequence (older) first
-impl Ord for TaskEnvelope {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.priority
-            .cmp(&other.priority)
-            .then(other.sequence.cmp(&self.sequence))
+    fn push(&mut self, task: TaskEnvelope) {
+        self.lanes[task.priority as usize].push_back(task);
     }
-}
 
-impl PartialOrd for TaskEnvelope {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Pop the next task: the head of the highest non-empty lane, or the
+    /// oldest task overall once it has been bypassed `STARVATION_LIMIT` times.
+    fn pop(&mut self) -> Option<TaskEnvelope> {
+        let highest = (0..self.lanes.len())
+            .rev()
+            .find(|&i| !self.lanes[i].is_empty())?;
+        let oldest = (0..self.lanes.len())
+            .filter_map(|i| self.lanes[i].front().map(|t| (t.sequence, i)))
+            .min()
+            .map(|(_, i)| i)?;
+
+        let lane = if self.bypassed >= STARVATION_LIMIT {
+            oldest
+        } else {
+            highest
+        };
+        if lane == oldest {
+            self.bypassed = 0;
+        } else {
+            self.bypassed += 1;
+        }
+        self.lanes[lane].pop_front()
     }
 }
 
 struct SchedulerInner {
-    queue: ParkingMutex<BinaryHeap<TaskEnvelope>>,
+    queue: ParkingMutex<TaskQueue>,
     work_ready: parking_lot::Condvar,
     drain_cond: parking_lot::Condvar,
     shutdown: AtomicBool,
@@ -85,12 +130,14 @@ struct SchedulerInner {
     active_tasks: AtomicUsize,
     max_queue_depth: usize,
     tasks_completed: AtomicU64,
+    tasks_rejected: AtomicU64,
 }
 
 /// A general-purpose background task scheduler with priority ordering.
 ///
 /// Tasks are executed by a fixed pool of worker threads. Higher-priority tasks
 /// run first; within the same priority, tasks are executed in FIFO order.
+/// Lower-priority tasks are protected from starvation (see the module docs).
 pub struct BackgroundScheduler {
     inner: Arc<SchedulerInner>,
     workers: ParkingMutex<Vec<JoinHandle<()>>>,
@@ -100,10 +147,13 @@ pub struct BackgroundScheduler {
 impl BackgroundScheduler {
     /// Create a new scheduler with the given number of worker threads.
     ///
-    /// Workers are named `strata-bg-0`, `strata-bg-1`, etc.
+    /// Workers are named `strata-bg-0`, `strata-bg-1`, etc. Both
+    /// `num_threads` and `max_queue_depth` are clamped to at least 1.
     pub fn new(num_threads: usize, max_queue_depth: usize) -> Self {
+        let num_threads = num_threads.max(1);
+        let max_queue_depth = max_queue_depth.max(1);
         let inner = Arc::new(SchedulerInner {
-            queue: ParkingMutex::new(BinaryHeap::new()),
+            queue: ParkingMutex::new(TaskQueue::new()),
             work_ready: parking_lot::Condvar::new(),
             drain_cond: parking_lot::Condvar::new(),
             shutdown: AtomicBool::new(false),
@@ -112,6 +162,7 @@ impl BackgroundScheduler {
             active_tasks: AtomicUsize::new(0),
             max_queue_depth,
             tasks_completed: AtomicU64::new(0),
+            tasks_rejected: AtomicU64::new(0),
         });
 
         let mut workers = Vec::with_capacity(num_threads);
@@ -133,8 +184,9 @@ impl BackgroundScheduler {
 
     /// Submit a task to the background scheduler.
     ///
-    /// Returns `Err(BackpressureError)` if the queue is at capacity or
-    /// the scheduler has been shut down.
+    /// Never blocks: returns `Err(BackpressureError)` immediately if the
+    /// queue is at capacity or the scheduler has been shut down. Use
+    /// [`is_shutdown`](Self::is_shutdown) to tell the two apart.
     pub fn submit(
         &self,
         priority: TaskPriority,
@@ -147,6 +199,9 @@ impl BackgroundScheduler {
 
         // Check backpressure before acquiring the lock
         if self.inner.queue_depth.load(AtomicOrdering::Acquire) >= self.inner.max_queue_depth {
+            self.inner
+                .tasks_rejected
+                .fetch_add(1, AtomicOrdering::Relaxed);
             return Err(BackpressureError);
         }
 
@@ -195,19 +250,31 @@ impl BackgroundScheduler {
             self.inner.work_ready.notify_all();
         }
 
+        // A task that drops the last owner of the scheduler runs this on a
+        // worker thread; that worker exits on its own once the task returns.
+        let current = std::thread::current().id();
         let mut workers = self.workers.lock();
         for handle in workers.drain(..) {
-            let _ = handle.join();
+            if handle.thread().id() != current {
+                let _ = handle.join();
+            }
         }
     }
 
+    /// Whether [`shutdown`](Self::shutdown) has been called.
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.shutdown.load(AtomicOrdering::Acquire)
+    }
+
     /// Return a snapshot of scheduler metrics.
     pub fn stats(&self) -> SchedulerStats {
         SchedulerStats {
             queue_depth: self.inner.queue_depth.load(AtomicOrdering::Relaxed),
             active_tasks: self.inner.active_tasks.load(AtomicOrdering::Relaxed),
             tasks_completed: self.inner.tasks_completed.load(AtomicOrdering::Relaxed),
+            tasks_rejected: self.inner.tasks_rejected.load(AtomicOrdering::Relaxed),
             worker_count: self.num_threads,
+            max_queue_depth: self.inner.max_queue_depth,
         }
     }
 }
@@ -389,6 +456,48 @@ mod tests {
         scheduler.shutdown();
     }
 
+    #[test]
+    fn test_low_priority_not_starved() {
+        let scheduler = BackgroundScheduler::new(1, 4096);
+
+        // Block the single worker so we can queue tasks
+        let barrier = Arc::new(Barrier::new(2));
+        let b = Arc::clone(&barrier);
+        scheduler
+            .submit(TaskPriority::Low, move || {
+                b.wait();
+            })
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        let order = Arc::new(ParkingMutex::new(Vec::new()));
+        let o = Arc::clone(&order);
+        scheduler
+            .submit(TaskPriority::Low, move || {
+                o.lock().push("low");
+            })
+            .unwrap();
+        for _ in 0..(STARVATION_LIMIT + 4) {
+            let o = Arc::clone(&order);
+            scheduler
+                .submit(TaskPriority::High, move || {
+                    o.lock().push("high");
+                })
+                .unwrap();
+        }
+
+        barrier.wait();
+        scheduler.drain();
+
+        // The low task runs after exactly STARVATION_LIMIT bypasses
+        let result = order.lock().clone();
+        assert_eq!(
+            result.iter().position(|t| *t == "low"),
+            Some(STARVATION_LIMIT)
+        );
+        scheduler.shutdown();
+    }
+
     #[test]
     fn test_backpressure() {
         let scheduler = BackgroundScheduler::new(1, 2);
@@ -424,6 +533,8 @@ mod tests {
         // Third submit should fail — queue is full
         let result = scheduler.submit(TaskPriority::Normal, || {});
         assert!(result.is_err());
+        assert_eq!(scheduler.stats().tasks_rejected, 1);
+        assert!(!scheduler.is_shutdown());
 
         // Release barrier, drain, verify the 2 queued tasks ran
         barrier.wait();
@@ -432,6 +543,26 @@ mod tests {
         scheduler.shutdown();
     }
 
+    #[test]
+    fn test_zero_sizes_are_clamped() {
+        let scheduler = BackgroundScheduler::new(0, 0);
+        let stats = scheduler.stats();
+        assert_eq!(stats.worker_count, 1);
+        assert_eq!(stats.max_queue_depth, 1);
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let c = Arc::clone(&counter);
+        scheduler
+            .submit(TaskPriority::Normal, move || {
+                c.fetch_add(1, AtomicOrdering::Relaxed);
+            })
+            .unwrap();
+        scheduler.drain();
+        assert_eq!(counter.load(AtomicOrdering::Relaxed), 1);
+        scheduler.shutdown();
+        assert!(scheduler.is_shutdown());
+    }
+
     #[test]
     fn test_shutdown_drains_remaining() {
         let scheduler = BackgroundScheduler::new(1, 4096);
@@ -6,22 +6,33 @@
 //! ## Operations
 //!
 //! - `fork_branch` — Create a copy of a branch with all its data
+//! - `fork_branch_cow` — Create a branch that reads through to its parent until it writes
 //! - `diff_branches` — Compare two branches and return structured differences
 //! - `merge_branches` — Merge data from one branch into another
+//! - `delete_branch` — Delete a branch and reclaim its storage
+//! - `content_hash` — Compute a deterministic fingerprint of a branch's data
 
 use crate::database::Database;
 use crate::primitives::branch::resolve_branch_name;
+use crate::primitives::vector::{CollectionRecord, VectorRecord};
+use crate::primitives::JsonStore;
+use crate::util::dir_size;
 use crate::BranchIndex;
 use crate::SpaceIndex;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
+use sha2::{Digest, Sha256};
+use strata_core::contract::Version;
+use strata_core::primitives::{Event, State};
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::value::Value;
 use strata_core::PrimitiveType;
 use strata_core::StrataError;
 use strata_core::StrataResult;
-use tracing::info;
+use strata_storage::ForkBase;
+use tracing::{info, warn};
 
 // =============================================================================
 // Data TypeTags to scan (all user data types)
@@ -47,7 +58,7 @@ pub struct ForkInfo {
     pub source: String,
     /// Destination branch name
     pub destination: String,
-    /// Number of keys copied
+    /// Number of keys copied (always 0 for copy-on-write forks)
     pub keys_copied: u64,
     /// Number of spaces copied
     pub spaces_copied: u64,
@@ -138,6 +149,17 @@ pub struct ConflictEntry {
     pub target_value: String,
 }
 
+/// How [`merge_branches_with`] resolves one conflict.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// Write the source branch's value to the target
+    TakeSource,
+    /// Keep the target branch's value (nothing is written)
+    TakeTarget,
+    /// Write this value to the target instead; only valid for KV entries
+    Custom(Value),
+}
+
 /// Information returned after merging branches.
 #[derive(Debug, Clone)]
 pub struct MergeInfo {
@@ -147,12 +169,39 @@ pub struct MergeInfo {
     pub target: String,
     /// Number of keys written to target
     pub keys_applied: u64,
-    /// Conflicts encountered (empty for LWW, populated for Strict failures)
+    /// Conflicts encountered and resolved (empty for Strict, which fails on conflicts)
     pub conflicts: Vec<ConflictEntry>,
     /// Number of spaces merged
     pub spaces_merged: u64,
 }
 
+/// Information returned after deleting a branch.
+#[derive(Debug, Clone)]
+pub struct DeleteInfo {
+    /// Deleted branch name
+    pub branch: String,
+    /// Number of keys physically removed from storage (tombstones included)
+    pub keys_reclaimed: u64,
+    /// Approximate bytes freed: in-memory versions plus on-disk vector files
+    pub bytes_reclaimed: u64,
+    /// Whether removal of the branch's versions waits on open snapshots
+    ///
+    /// Those versions are freed once the snapshots are released and are not
+    /// counted above.
+    pub reclaim_pending: bool,
+}
+
+/// Approximate storage used by a branch, returned by [`branch_size`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchSize {
+    /// Keys stored in the branch itself (tombstones included)
+    pub keys: u64,
+    /// Approximate bytes of keys and retained versions held in memory
+    pub memory_bytes: u64,
+    /// Bytes of the branch's vector files on disk (0 for ephemeral databases)
+    pub disk_bytes: u64,
+}
+
 // =============================================================================
 // Helpers
 // =============================================================================
@@ -229,7 +278,7 @@ pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> Strat
     }
 
     // 3. Create destination branch
-    branch_index.create_branch(destination)?;
+    branch_index.create_fork(destination, source)?;
 
     // 4. Resolve BranchIds
     let source_id = resolve_branch_name(source);
@@ -311,6 +360,478 @@ pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> Strat
     })
 }
 
+/// Fork a branch lazily, deferring data duplication until the child writes.
+///
+/// The destination branch is pointed at the source's current version: reads
+/// of any key the destination has not written resolve against the source as
+/// of the fork point, so later writes to the source stay invisible to the
+/// fork. Writes and deletes in the destination only materialize the touched
+/// keys. Forking is O(spaces) regardless of how much data the source holds.
+///
+/// The parent link is persisted in the destination's branch metadata and
+/// re-established on recovery.
+///
+/// # Errors
+///
+/// - Source branch does not exist
+/// - Destination branch already exists
+pub fn fork_branch_cow(
+    db: &Arc<Database>,
+    source: &str,
+    destination: &str,
+) -> StrataResult<ForkInfo> {
+    let branch_index = BranchIndex::new(db.clone());
+    let space_index = SpaceIndex::new(db.clone());
+
+    branch_index.get_branch(source)?.ok_or_else(|| {
+        StrataError::invalid_input(format!("Source branch '{}' not found", source))
+    })?;
+
+    let source_id = resolve_branch_name(source);
+    let dest_id = resolve_branch_name(destination);
+    let base_version = db.storage().version();
+
+    // Creation fails if the destination already exists
+    branch_index.create_cow_fork(destination, source, base_version)?;
+    db.storage().set_fork_base(
+        dest_id,
+        ForkBase {
+            parent: source_id,
+            version: base_version,
+        },
+    );
+
+    // Space registrations are inherited through the fork base; count them
+    // for parity with eager forks.
+    let spaces_copied = space_index.list(dest_id)?.len() as u64;
+
+    // Load inherited vectors into the destination's in-memory backends so
+    // they are searchable without a restart.
+    {
+        use crate::primitives::vector::store::VectorStore;
+        let vector_store = VectorStore::new(db.clone());
+        if let Err(e) = vector_store.post_merge_reload_vectors_from(dest_id, Some(source_id)) {
+            tracing::warn!(
+                target: "strata::branch_ops",
+                error = %e,
+                "Failed to reload vector backends after copy-on-write fork"
+            );
+        }
+    }
+
+    info!(
+        target: "strata::branch_ops",
+        source,
+        destination,
+        base_version,
+        spaces_copied,
+        "Branch forked (copy-on-write)"
+    );
+
+    Ok(ForkInfo {
+        source: source.to_string(),
+        destination: destination.to_string(),
+        keys_copied: 0,
+        spaces_copied,
+    })
+}
+
+/// Re-register copy-on-write fork bases with storage after recovery.
+///
+/// Storage only keeps parent links in memory; the branch metadata is the
+/// durable record.
+pub(crate) fn restore_cow_forks(db: &Arc<Database>) -> StrataResult<()> {
+    let branch_index = BranchIndex::new(db.clone());
+    for name in branch_index.list_branches()? {
+        let Some(meta) = branch_index.get_branch(&name)? else {
+            continue;
+        };
+        if let (Some(parent), Some(version)) = (
+            meta.value.parent_branch.as_deref(),
+            meta.value.fork_base_version,
+        ) {
+            db.recovering_storage().set_fork_base(
+                resolve_branch_name(&name),
+                ForkBase {
+                    parent: resolve_branch_name(parent),
+                    version,
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Delete
+// =============================================================================
+
+/// How long `delete_branch` waits for snapshots that can read the deleted
+/// branch before queueing physical removal of its versions.
+const RECLAIM_IDLE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Delete a branch and reclaim its storage.
+///
+/// The branch metadata and all branch data are deleted in one transaction,
+/// then the branch's resources are released:
+///
+/// - vector collections in every space, including their mmap files on disk
+/// - the branch's per-branch commit lock
+/// - the branch's versions in storage, once no snapshot can still read them
+///
+/// The snapshots waited for are those of open transactions on the deleted
+/// branch and pinned snapshots (exports, `scan_all`) taken before the
+/// delete; load on other branches does not delay it. If they are not
+/// released within a short timeout the branch is still deleted, and its
+/// versions are freed later, when the last of them is released. The
+/// returned [`DeleteInfo`] then has `reclaim_pending` set and does not
+/// count them.
+///
+/// # Errors
+///
+/// - Branch is `default`
+/// - Branch does not exist
+/// - Branch is the parent of a copy-on-write fork that still reads its data
+pub fn delete_branch(db: &Arc<Database>, name: &str) -> StrataResult<DeleteInfo> {
+    if name == "default" {
+        return Err(StrataError::invalid_input(
+            "Cannot delete the default branch",
+        ));
+    }
+
+    let branch_index = BranchIndex::new(db.clone());
+    let space_index = SpaceIndex::new(db.clone());
+
+    let meta = branch_index
+        .get_branch(name)?
+        .ok_or_else(|| StrataError::invalid_input(format!("Branch '{}' not found", name)))?
+        .value;
+    let branch_id = resolve_branch_name(name);
+
+    let storage = db.storage();
+    let fork_children = storage.fork_children(&branch_id);
+    if !fork_children.is_empty() {
+        return Err(StrataError::invalid_input(format!(
+            "Branch '{}' is the parent of {} copy-on-write fork(s) and cannot be deleted",
+            name,
+            fork_children.len()
+        )));
+    }
+
+    // Drop vector collections first so their in-memory backends are released.
+    // Best-effort: the cascading delete below removes the vectors' KV records
+    // regardless.
+    {
+        use crate::primitives::vector::store::VectorStore;
+        let vector_store = VectorStore::new(db.clone());
+        for space in space_index.list(branch_id)? {
+            if let Ok(collections) = vector_store.list_collections(branch_id, &space) {
+                for collection in collections {
+                    let _ = vector_store.delete_collection(branch_id, &space, &collection.name);
+                }
+            }
+        }
+    }
+
+    branch_index.delete_branch(name)?;
+    db.remove_branch_lock(&branch_id);
+
+    let mut bytes_reclaimed = remove_vector_files(db, branch_id);
+    let mut keys_reclaimed = 0u64;
+
+    // The metadata BranchId can differ from the name-derived one; both
+    // namespaces were cleared by the cascading delete.
+    let mut shard_ids = vec![branch_id];
+    if let Some(meta_id) = BranchId::from_string(&meta.branch_id) {
+        if meta_id != branch_id {
+            shard_ids.push(meta_id);
+        }
+    }
+
+    let deleted_at = db.current_version();
+    let reclaimed = db.reclaim_deleted_branch(name, shard_ids, deleted_at, RECLAIM_IDLE_TIMEOUT);
+    let reclaim_pending = reclaimed.is_none();
+    if let Some((keys, bytes)) = reclaimed {
+        keys_reclaimed += keys;
+        bytes_reclaimed += bytes;
+    } else {
+        warn!(
+            target: "strata::branch_ops",
+            branch = name,
+            "Snapshots still read the deleted branch; freeing its versions once they are released"
+        );
+    }
+
+    info!(
+        target: "strata::branch_ops",
+        branch = name,
+        keys_reclaimed,
+        bytes_reclaimed,
+        "Branch deleted"
+    );
+
+    Ok(DeleteInfo {
+        branch: name.to_string(),
+        keys_reclaimed,
+        bytes_reclaimed,
+        reclaim_pending,
+    })
+}
+
+/// Remove a branch's vector mmap directory, returning the bytes freed.
+fn remove_vector_files(db: &Database, branch_id: BranchId) -> u64 {
+    let data_dir = db.data_dir();
+    if data_dir.as_os_str().is_empty() {
+        return 0; // Ephemeral database — nothing on disk
+    }
+    let dir = crate::primitives::vector::branch_vector_dir(data_dir, branch_id);
+    if !dir.exists() {
+        return 0;
+    }
+    let size = dir_size(&dir);
+    match std::fs::remove_dir_all(&dir) {
+        Ok(()) => size,
+        Err(e) => {
+            warn!(
+                target: "strata::branch_ops",
+                path = %dir.display(),
+                error = %e,
+                "Failed to remove vector files of deleted branch"
+            );
+            0
+        }
+    }
+}
+
+// =============================================================================
+// Size
+// =============================================================================
+
+/// Estimate how much storage a branch uses.
+///
+/// `memory_bytes` sums the estimated size of every key and retained version
+/// in the branch's storage shard, tombstones included, the same figure
+/// [`delete_branch`] reports as reclaimed. It covers KV, JSON, State, Event
+/// and vector records alike. Data a copy-on-write fork reads through to its
+/// parent is counted against the parent only. `disk_bytes` is the size of
+/// the branch's vector directory. WAL and snapshot files are shared by all
+/// branches and are not attributed.
+///
+/// The result is an estimate, cheap enough for quota checks but not exact.
+///
+/// # Errors
+///
+/// - Branch does not exist
+pub fn branch_size(db: &Arc<Database>, name: &str) -> StrataResult<BranchSize> {
+    let branch_id = resolve_and_verify(db, name)?;
+    let storage = db.storage();
+
+    let data_dir = db.data_dir();
+    let disk_bytes = if data_dir.as_os_str().is_empty() {
+        0
+    } else {
+        dir_size(&crate::primitives::vector::branch_vector_dir(
+            data_dir, branch_id,
+        ))
+    };
+
+    Ok(BranchSize {
+        keys: storage.branch_entry_count(&branch_id) as u64,
+        memory_bytes: storage.branch_size_estimate(&branch_id) as u64,
+        disk_bytes,
+    })
+}
+
+// =============================================================================
+// Content hash
+// =============================================================================
+
+/// Compute a deterministic SHA-256 fingerprint of a branch's committed data.
+///
+/// Covers the latest visible value of every KV, Event, State, JSON, Vector
+/// and VectorConfig entry in the branch, across all spaces, including data
+/// a copy-on-write fork reads through to its parent. Each entry contributes
+/// its space, type, key, version and content; entries are sorted before
+/// hashing, so the result does not depend on storage iteration order.
+/// Object fields are hashed in key order.
+///
+/// Excluded, because they differ between runs of the same operations:
+///
+/// - Wall-clock timestamps (`updated_at`, `created_at`, event timestamps)
+/// - Event hash-chain fields, which are derived from timestamps
+/// - Event log metadata and type-index records, which are derived data
+///
+/// Branch and space metadata are not included, and neither are embeddings
+/// held only in the vector index: a vector contributes its committed record
+/// (ID, metadata, source reference and version).
+///
+/// Two databases that applied the same operations in the same order produce
+/// the same hash.
+///
+/// # Errors
+///
+/// - Branch does not exist
+pub fn content_hash(db: &Arc<Database>, name: &str) -> StrataResult<[u8; 32]> {
+    let branch_id = resolve_and_verify(db, name)?;
+    let storage = db.storage();
+
+    let mut entries: Vec<Vec<u8>> = Vec::new();
+    for type_tag in DATA_TYPE_TAGS {
+        for (key, vv) in storage.list_by_type(&branch_id, type_tag) {
+            if let Some(entry) = encode_entry(&key, &vv.version, &vv.value)? {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.sort_unstable();
+
+    let mut hasher = Sha256::new();
+    for entry in &entries {
+        hasher.update((entry.len() as u64).to_be_bytes());
+        hasher.update(entry);
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Canonical encoding of one entry, or `None` for derived records.
+///
+/// The encoding starts with space, type tag and user key, so sorting the
+/// encodings sorts entries by key.
+fn encode_entry(key: &Key, version: &Version, value: &Value) -> StrataResult<Option<Vec<u8>>> {
+    let mut out = Vec::new();
+    encode_bytes(&mut out, key.namespace.space.as_bytes());
+    out.push(key.type_tag.as_byte());
+    encode_bytes(&mut out, &key.user_key);
+    let kind = match version {
+        Version::Txn(_) => 0u8,
+        Version::Sequence(_) => 1,
+        Version::Counter(_) => 2,
+    };
+    out.push(kind);
+    out.extend_from_slice(&version.as_u64().to_be_bytes());
+
+    match key.type_tag {
+        TypeTag::Event => {
+            if key.is_internal() {
+                return Ok(None);
+            }
+            match decode_json_string::<Event>(value) {
+                Some(event) => {
+                    out.extend_from_slice(&event.sequence.to_be_bytes());
+                    encode_bytes(&mut out, event.event_type.as_bytes());
+                    encode_value(&mut out, &event.payload);
+                }
+                None => encode_value(&mut out, value),
+            }
+        }
+        TypeTag::State => match decode_json_string::<State>(value) {
+            Some(state) => {
+                out.extend_from_slice(&state.version.as_u64().to_be_bytes());
+                encode_value(&mut out, &state.value);
+            }
+            None => encode_value(&mut out, value),
+        },
+        TypeTag::Json => {
+            let doc = JsonStore::deserialize_doc(value)?;
+            encode_bytes(&mut out, doc.id.as_bytes());
+            out.extend_from_slice(&doc.version.to_be_bytes());
+            let json = serde_json::to_vec(&doc.value)
+                .map_err(|e| StrataError::serialization(e.to_string()))?;
+            encode_bytes(&mut out, &json);
+        }
+        TypeTag::Vector => {
+            let mut record = decode_record(value, VectorRecord::from_bytes)?;
+            record.created_at = 0;
+            record.updated_at = 0;
+            let bytes = record
+                .to_bytes()
+                .map_err(|e| StrataError::serialization(e.to_string()))?;
+            encode_bytes(&mut out, &bytes);
+        }
+        TypeTag::VectorConfig => {
+            let mut record = decode_record(value, CollectionRecord::from_bytes)?;
+            record.created_at = 0;
+            let bytes = record
+                .to_bytes()
+                .map_err(|e| StrataError::serialization(e.to_string()))?;
+            encode_bytes(&mut out, &bytes);
+        }
+        _ => encode_value(&mut out, value),
+    }
+    Ok(Some(out))
+}
+
+/// Decode a struct stored as a JSON string, as events and state cells are.
+fn decode_json_string<T: serde::de::DeserializeOwned>(value: &Value) -> Option<T> {
+    match value {
+        Value::String(s) => serde_json::from_str(s).ok(),
+        _ => None,
+    }
+}
+
+/// Decode a MessagePack vector record stored as `Value::Bytes`.
+fn decode_record<T, E: std::fmt::Display>(
+    value: &Value,
+    decode: impl FnOnce(&[u8]) -> Result<T, E>,
+) -> StrataResult<T> {
+    match value {
+        Value::Bytes(bytes) => decode(bytes).map_err(|e| StrataError::serialization(e.to_string())),
+        _ => Err(StrataError::serialization(
+            "expected bytes for vector record".to_string(),
+        )),
+    }
+}
+
+/// Append a length-prefixed byte string.
+fn encode_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// Append a canonical encoding of a value, with object fields in key order.
+fn encode_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => out.push(0),
+        Value::Bool(b) => {
+            out.push(1);
+            out.push(*b as u8);
+        }
+        Value::Int(i) => {
+            out.push(2);
+            out.extend_from_slice(&i.to_be_bytes());
+        }
+        Value::Float(f) => {
+            out.push(3);
+            out.extend_from_slice(&f.to_bits().to_be_bytes());
+        }
+        Value::String(s) => {
+            out.push(4);
+            encode_bytes(out, s.as_bytes());
+        }
+        Value::Bytes(b) => {
+            out.push(5);
+            encode_bytes(out, b);
+        }
+        Value::Array(items) => {
+            out.push(6);
+            out.extend_from_slice(&(items.len() as u64).to_be_bytes());
+            for item in items {
+                encode_value(out, item);
+            }
+        }
+        Value::Object(map) => {
+            out.push(7);
+            out.extend_from_slice(&(map.len() as u64).to_be_bytes());
+            let mut fields: Vec<_> = map.iter().collect();
+            fields.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            for (k, v) in fields {
+                encode_bytes(out, k.as_bytes());
+                encode_value(out, v);
+            }
+        }
+    }
+}
+
 // =============================================================================
 // Diff
 // =============================================================================
@@ -449,6 +970,38 @@ pub fn diff_branches(
     })
 }
 
+/// Diff a forked branch against the branch it was forked from.
+///
+/// The parent is branch A (base) and `branch` is branch B, so `added` holds
+/// entries written only in the fork and `removed` holds entries the fork
+/// deleted. The parent is compared as it is now: writes made to the parent
+/// after the fork show up as differences too.
+///
+/// # Errors
+///
+/// - `branch` does not exist
+/// - `branch` was not created by a fork (for example `default`)
+/// - The parent branch has since been deleted
+pub fn diff_from_parent(db: &Arc<Database>, branch: &str) -> StrataResult<BranchDiffResult> {
+    let branch_index = BranchIndex::new(db.clone());
+    let meta = branch_index
+        .get_branch(branch)?
+        .ok_or_else(|| StrataError::invalid_input(format!("Branch '{}' not found", branch)))?;
+    let parent = meta.value.parent_branch.ok_or_else(|| {
+        StrataError::invalid_input(format!(
+            "Branch '{}' was not forked from another branch",
+            branch
+        ))
+    })?;
+    if !branch_index.exists(&parent)? {
+        return Err(StrataError::invalid_input(format!(
+            "Branch '{}' was forked from '{}', which no longer exists",
+            branch, parent
+        )));
+    }
+    diff_branches(db, &parent, branch)
+}
+
 // =============================================================================
 // Merge
 // =============================================================================
@@ -477,54 +1030,94 @@ pub fn merge_branches(
     target: &str,
     strategy: MergeStrategy,
 ) -> StrataResult<MergeInfo> {
-    let space_index = SpaceIndex::new(db.clone());
-
     // 1. Diff: target is A (base), source is B (incoming)
     let diff = diff_branches(db, target, source)?;
 
     // 2. Check for conflicts in Strict mode
     if strategy == MergeStrategy::Strict && diff.summary.total_modified > 0 {
-        let conflicts: Vec<ConflictEntry> = diff
-            .spaces
-            .iter()
-            .flat_map(|sd| {
-                sd.modified.iter().map(|entry| ConflictEntry {
-                    key: entry.key.clone(),
-                    primitive: entry.primitive,
-                    space: entry.space.clone(),
-                    source_value: entry.value_b.clone().unwrap_or_default(),
-                    target_value: entry.value_a.clone().unwrap_or_default(),
-                })
-            })
-            .collect();
-
         return Err(StrataError::invalid_input(format!(
             "Merge conflict: {} keys differ between '{}' and '{}'. Use LastWriterWins strategy or resolve conflicts manually.",
-            conflicts.len(),
+            diff.summary.total_modified,
             source,
             target
         )));
     }
 
-    // Collect conflicts for reporting (LWW resolves them, Strict already returned error)
-    let conflicts: Vec<ConflictEntry> = if strategy == MergeStrategy::LastWriterWins {
-        diff.spaces
-            .iter()
-            .flat_map(|sd| {
-                sd.modified.iter().map(|entry| ConflictEntry {
-                    key: entry.key.clone(),
-                    primitive: entry.primitive,
-                    space: entry.space.clone(),
-                    source_value: entry.value_b.clone().unwrap_or_default(),
-                    target_value: entry.value_a.clone().unwrap_or_default(),
-                })
-            })
-            .collect()
-    } else {
-        vec![]
-    };
+    // Past the Strict check every conflict goes to the source
+    apply_merge(
+        db,
+        source,
+        target,
+        &diff,
+        &format!("{:?}", strategy),
+        &mut |_| Resolution::TakeSource,
+    )
+}
+
+/// Merge source into target, letting `resolve` decide each conflict.
+///
+/// Works like [`merge_branches`], except that every entry modified on both
+/// branches is passed to `resolve` as a [`ConflictEntry`], and the returned
+/// [`Resolution`] picks the value written to the target. All conflicts are
+/// resolved before anything is written, so an error leaves the target
+/// untouched. Every conflict is listed in [`MergeInfo::conflicts`].
+///
+/// # Errors
+///
+/// - Either branch does not exist
+/// - `Resolution::Custom` returned for a conflict that is not a KV entry
+pub fn merge_branches_with<F>(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+    mut resolve: F,
+) -> StrataResult<MergeInfo>
+where
+    F: FnMut(ConflictEntry) -> Resolution,
+{
+    let diff = diff_branches(db, target, source)?;
+    apply_merge(db, source, target, &diff, "Callback", &mut resolve)
+}
+
+/// Write the diff's added entries and resolved conflicts to the target.
+fn apply_merge(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+    diff: &BranchDiffResult,
+    strategy: &str,
+    resolve: &mut dyn FnMut(ConflictEntry) -> Resolution,
+) -> StrataResult<MergeInfo> {
+    let space_index = SpaceIndex::new(db.clone());
+
+    // 3. Resolve every conflict up front, so a rejected resolution
+    // fails the merge before any space is written
+    let mut conflicts = Vec::new();
+    let mut resolutions: Vec<Vec<Resolution>> = Vec::with_capacity(diff.spaces.len());
+    for sd in &diff.spaces {
+        let mut space_resolutions = Vec::with_capacity(sd.modified.len());
+        for entry in &sd.modified {
+            let conflict = ConflictEntry {
+                key: entry.key.clone(),
+                primitive: entry.primitive,
+                space: entry.space.clone(),
+                source_value: entry.value_b.clone().unwrap_or_default(),
+                target_value: entry.value_a.clone().unwrap_or_default(),
+            };
+            let resolution = resolve(conflict.clone());
+            if matches!(resolution, Resolution::Custom(_)) && entry.primitive != PrimitiveType::Kv {
+                return Err(StrataError::invalid_input(format!(
+                    "Custom merge value for '{}' in space '{}': only KV conflicts accept custom values, not {:?}",
+                    entry.key, entry.space, entry.primitive
+                )));
+            }
+            conflicts.push(conflict);
+            space_resolutions.push(resolution);
+        }
+        resolutions.push(space_resolutions);
+    }
 
-    // 3. Resolve IDs
+    // 4. Resolve IDs
     let source_id = resolve_branch_name(source);
     let target_id = resolve_branch_name(target);
     let storage = db.storage();
@@ -532,9 +1125,10 @@ pub fn merge_branches(
     let mut keys_applied = 0u64;
     let mut spaces_merged = 0u64;
 
-    // 4. Apply changes
-    for space_diff in &diff.spaces {
+    // 5. Apply changes
+    for (space_diff, space_resolutions) in diff.spaces.iter().zip(&resolutions) {
         let space = &space_diff.space;
+        let target_ns = Namespace::for_branch_space(target_id, space);
 
         // Ensure target has this space
         if space != "default" {
@@ -542,43 +1136,47 @@ pub fn merge_branches(
         }
         spaces_merged += 1;
 
-        // Collect entries to write from source: added + modified (for LWW)
-        let entries_to_apply: Vec<&BranchDiffEntry> = space_diff
-            .added
-            .iter()
-            .chain(if strategy == MergeStrategy::LastWriterWins {
-                space_diff.modified.iter()
-            } else {
-                [].iter()
-            })
-            .collect();
+        // Collect entries to write from source: added + conflicts resolved
+        // to the source value. Custom values go straight into the batch.
+        let mut batch: Vec<(Key, Value)> = Vec::new();
+        let mut entries_to_apply: Vec<&BranchDiffEntry> = space_diff.added.iter().collect();
+        for (entry, resolution) in space_diff.modified.iter().zip(space_resolutions) {
+            match resolution {
+                Resolution::TakeSource => entries_to_apply.push(entry),
+                Resolution::TakeTarget => {}
+                Resolution::Custom(value) => batch.push((
+                    Key::new(target_ns.clone(), TypeTag::KV, entry.raw_key.clone()),
+                    value.clone(),
+                )),
+            }
+        }
 
-        if entries_to_apply.is_empty() {
+        if entries_to_apply.is_empty() && batch.is_empty() {
             continue;
         }
 
         // Re-scan source data for this space to get actual values
         // (diff only stores string representations)
         let mut source_values: HashMap<(Vec<u8>, TypeTag), Value> = HashMap::new();
-        for type_tag in DATA_TYPE_TAGS {
-            let entries = storage.list_by_type(&source_id, type_tag);
-            for (key, vv) in entries {
-                if key.namespace.space == *space {
-                    source_values.insert((key.user_key.clone(), type_tag), vv.value);
+        if !entries_to_apply.is_empty() {
+            for type_tag in DATA_TYPE_TAGS {
+                let entries = storage.list_by_type(&source_id, type_tag);
+                for (key, vv) in entries {
+                    if key.namespace.space == *space {
+                        source_values.insert((key.user_key.clone(), type_tag), vv.value);
+                    }
                 }
             }
         }
 
         // Write to target
-        let mut batch: Vec<(Key, Value)> = Vec::new();
         for diff_entry in &entries_to_apply {
             // Find the matching source value
             for type_tag in DATA_TYPE_TAGS {
                 if type_tag_to_primitive(type_tag) == diff_entry.primitive {
                     let user_key_bytes = diff_entry.raw_key.clone();
                     if let Some(value) = source_values.get(&(user_key_bytes.clone(), type_tag)) {
-                        let target_ns = Namespace::for_branch_space(target_id, space);
-                        let target_key = Key::new(target_ns, type_tag, user_key_bytes);
+                        let target_key = Key::new(target_ns.clone(), type_tag, user_key_bytes);
                         batch.push((target_key, value.clone()));
                         break;
                     }
@@ -618,7 +1216,7 @@ pub fn merge_branches(
         target,
         keys_applied,
         spaces_merged,
-        strategy = ?strategy,
+        strategy,
         "Branches merged"
     );
 
@@ -854,6 +1452,275 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fork_records_parent() {
+        let (_temp, db) = setup_with_branch("source");
+
+        fork_branch(&db, "source", "dest").unwrap();
+
+        let meta = BranchIndex::new(db.clone())
+            .get_branch("dest")
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.value.parent_branch.as_deref(), Some("source"));
+        assert_eq!(meta.value.fork_base_version, None);
+    }
+
+    #[test]
+    fn test_fork_cow_reads_through_without_copying() {
+        let (_temp, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+        write_state(&db, "source", "default", "s1", Value::Bool(true));
+
+        let info = fork_branch_cow(&db, "source", "dest").unwrap();
+        assert_eq!(info.keys_copied, 0);
+        assert!(BranchIndex::new(db.clone()).exists("dest").unwrap());
+
+        // Nothing was materialized, but reads see the source's data
+        let dest_id = resolve_branch_name("dest");
+        assert_eq!(db.storage().branch_entry_count(&dest_id), 0);
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(db.storage().list_by_type(&dest_id, TypeTag::State).len(), 1);
+
+        // Transactional reads resolve through the fork base as well
+        let ns = Namespace::for_branch_space(dest_id, "default");
+        let value = db
+            .transaction(dest_id, |txn| {
+                txn.get(&Key::new(ns.clone(), TypeTag::KV, b"k1".to_vec()))
+            })
+            .unwrap();
+        assert_eq!(value, Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_fork_cow_materializes_only_writes() {
+        let (_temp, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+        write_kv(&db, "source", "default", "k2", Value::Int(2));
+
+        fork_branch_cow(&db, "source", "dest").unwrap();
+        write_kv(&db, "dest", "default", "k1", Value::Int(10));
+        write_kv(&db, "source", "default", "k2", Value::Int(20));
+
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(10)));
+        // Source writes after the fork point are not visible in the fork
+        assert_eq!(read_kv(&db, "dest", "default", "k2"), Some(Value::Int(2)));
+        assert_eq!(read_kv(&db, "source", "default", "k1"), Some(Value::Int(1)));
+
+        let dest_id = resolve_branch_name("dest");
+        assert_eq!(db.storage().count_by_type(&dest_id, TypeTag::KV), 2);
+        assert_eq!(db.storage().branch_entry_count(&dest_id), 1);
+
+        let diff = diff_branches(&db, "source", "dest").unwrap();
+        assert_eq!(diff.summary.total_modified, 2);
+    }
+
+    #[test]
+    fn test_fork_cow_errors() {
+        let (_temp, db) = setup_with_branch("source");
+        assert!(fork_branch_cow(&db, "missing", "dest").is_err());
+
+        BranchIndex::new(db.clone()).create_branch("dest").unwrap();
+        let err = fork_branch_cow(&db, "source", "dest").unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn test_fork_cow_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        {
+            let db = Database::open(&path).unwrap();
+            BranchIndex::new(db.clone())
+                .create_branch("source")
+                .unwrap();
+            write_kv(&db, "source", "default", "k1", Value::Int(1));
+            fork_branch_cow(&db, "source", "dest").unwrap();
+            write_kv(&db, "source", "default", "k1", Value::Int(2));
+            db.flush().unwrap();
+        }
+
+        let db = Database::open(&path).unwrap();
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "source", "default", "k1"), Some(Value::Int(2)));
+    }
+
+    // =========================================================================
+    // Delete Tests
+    // =========================================================================
+
+    #[test]
+    fn test_delete_branch_reclaims_storage() {
+        let (_temp, db) = setup_with_branch("doomed");
+        write_kv(&db, "doomed", "default", "k1", Value::Int(1));
+        write_kv(
+            &db,
+            "doomed",
+            "default",
+            "k2",
+            Value::String("x".repeat(512)),
+        );
+
+        let info = delete_branch(&db, "doomed").unwrap();
+        assert_eq!(info.branch, "doomed");
+        // Two values plus their delete tombstones, in one chain per key
+        assert!(info.keys_reclaimed >= 2);
+        assert!(info.bytes_reclaimed >= 512);
+
+        let branch_id = resolve_branch_name("doomed");
+        assert!(!db.storage().has_branch(&branch_id));
+        assert!(!BranchIndex::new(db.clone()).exists("doomed").unwrap());
+        assert_eq!(read_kv(&db, "doomed", "default", "k1"), None);
+    }
+
+    #[test]
+    fn test_delete_branch_not_delayed_by_other_branches() {
+        let (_temp, db) = setup_with_branch("doomed");
+        write_kv(&db, "doomed", "default", "k1", Value::Int(1));
+
+        // An open transaction on another branch neither delays nor blocks
+        // reclaiming the deleted one
+        let open = db
+            .begin_transaction(resolve_branch_name("default"))
+            .unwrap();
+        let start = std::time::Instant::now();
+        let info = delete_branch(&db, "doomed").unwrap();
+        assert!(start.elapsed() < RECLAIM_IDLE_TIMEOUT);
+        assert!(info.keys_reclaimed >= 1);
+        assert!(!db.storage().has_branch(&resolve_branch_name("doomed")));
+        db.end_transaction(open);
+    }
+
+    #[test]
+    fn test_delete_branch_waits_for_pinned_snapshot() {
+        let (_temp, db) = setup_with_branch("doomed");
+        write_kv(&db, "doomed", "default", "k1", Value::Int(1));
+        let branch_id = resolve_branch_name("doomed");
+        let key = Key::new_kv(Namespace::for_branch_space(branch_id, "default"), "k1");
+
+        let snapshot = db.pinned_snapshot();
+        let info = delete_branch(&db, "doomed").unwrap();
+        assert!(info.reclaim_pending);
+        assert_eq!(info.keys_reclaimed, 0);
+        assert_eq!(db.pending_reclaim_count(), 1);
+        // The snapshot still reads the deleted branch
+        assert_eq!(snapshot.get(&key).unwrap().unwrap().value, Value::Int(1));
+
+        drop(snapshot);
+        assert_eq!(db.pending_reclaim_count(), 0);
+        assert!(!db.storage().has_branch(&branch_id));
+    }
+
+    #[test]
+    fn test_pending_reclaim_keeps_recreated_branch() {
+        let (_temp, db) = setup_with_branch("doomed");
+        write_kv(&db, "doomed", "default", "k1", Value::Int(1));
+
+        let snapshot = db.pinned_snapshot();
+        assert!(delete_branch(&db, "doomed").unwrap().reclaim_pending);
+        BranchIndex::new(db.clone())
+            .create_branch("doomed")
+            .unwrap();
+        write_kv(&db, "doomed", "default", "k2", Value::Int(2));
+
+        drop(snapshot);
+        assert_eq!(db.pending_reclaim_count(), 0);
+        assert_eq!(read_kv(&db, "doomed", "default", "k2"), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn test_delete_branch_removes_vector_files() {
+        let (_temp, db) = setup_with_branch("doomed");
+        let branch_id = resolve_branch_name("doomed");
+        let dir = crate::primitives::vector::branch_vector_dir(db.data_dir(), branch_id);
+        std::fs::create_dir_all(dir.join("c_graphs")).unwrap();
+        std::fs::write(dir.join("c.vec"), vec![0u8; 100]).unwrap();
+        std::fs::write(dir.join("c_graphs").join("seg_0.hgr"), vec![0u8; 50]).unwrap();
+
+        let info = delete_branch(&db, "doomed").unwrap();
+        assert!(!dir.exists());
+        assert!(info.bytes_reclaimed >= 150);
+    }
+
+    #[test]
+    fn test_branch_size() {
+        let (_temp, db) = setup_with_branch("sized");
+        let empty = branch_size(&db, "sized").unwrap();
+
+        write_kv(
+            &db,
+            "sized",
+            "default",
+            "k1",
+            Value::String("x".repeat(512)),
+        );
+        let branch_id = resolve_branch_name("sized");
+        let dir = crate::primitives::vector::branch_vector_dir(db.data_dir(), branch_id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("c.vec"), vec![0u8; 100]).unwrap();
+
+        let size = branch_size(&db, "sized").unwrap();
+        assert!(size.keys > empty.keys);
+        assert!(size.memory_bytes >= empty.memory_bytes + 512);
+        assert_eq!(size.disk_bytes, 100);
+
+        assert!(branch_size(&db, "missing").is_err());
+    }
+
+    #[test]
+    fn test_content_hash_is_deterministic() {
+        use crate::primitives::{EventLog, StateCell};
+
+        let build = |value: i64| {
+            let (temp, db) = setup_with_branch("fp");
+            let branch_id = resolve_branch_name("fp");
+            write_kv(&db, "fp", "default", "k1", Value::Int(value));
+            let mut obj = std::collections::HashMap::new();
+            for i in 0..16 {
+                obj.insert(format!("f{}", i), Value::Int(i));
+            }
+            write_kv(&db, "fp", "other", "k2", Value::Object(obj.clone()));
+            StateCell::new(db.clone())
+                .set(&branch_id, "default", "cell", Value::Int(1))
+                .unwrap();
+            EventLog::new(db.clone())
+                .append(&branch_id, "default", "ev", Value::Object(obj))
+                .unwrap();
+            (temp, db)
+        };
+
+        let (_t1, db1) = build(1);
+        std::thread::sleep(Duration::from_millis(2));
+        let (_t2, db2) = build(1);
+        let (_t3, db3) = build(2);
+
+        let h1 = content_hash(&db1, "fp").unwrap();
+        assert_eq!(h1, content_hash(&db1, "fp").unwrap());
+        assert_eq!(h1, content_hash(&db2, "fp").unwrap());
+        assert_ne!(h1, content_hash(&db3, "fp").unwrap());
+        BranchIndex::new(db1.clone())
+            .create_branch("empty")
+            .unwrap();
+        assert_ne!(h1, content_hash(&db1, "empty").unwrap());
+        assert!(content_hash(&db1, "missing").is_err());
+    }
+
+    #[test]
+    fn test_delete_branch_rejections() {
+        let (_temp, db) = setup_with_branch("parent");
+        assert!(delete_branch(&db, "default").is_err());
+        assert!(delete_branch(&db, "missing").is_err());
+
+        fork_branch_cow(&db, "parent", "child").unwrap();
+        let err = delete_branch(&db, "parent").unwrap_err();
+        assert!(err.to_string().contains("copy-on-write"));
+        assert!(BranchIndex::new(db.clone()).exists("parent").unwrap());
+
+        // Deleting the fork releases the parent
+        delete_branch(&db, "child").unwrap();
+        delete_branch(&db, "parent").unwrap();
+    }
+
     // =========================================================================
     // Diff Tests
     // =========================================================================
@@ -947,6 +1814,36 @@ mod tests {
         assert_eq!(diff.summary.total_added, 1);
     }
 
+    #[test]
+    fn test_diff_from_parent() {
+        let (_temp, db) = setup_with_branch("parent");
+        write_kv(&db, "parent", "default", "kept", Value::Int(1));
+        write_kv(&db, "parent", "default", "changed", Value::Int(1));
+
+        type Fork = fn(&Arc<Database>, &str, &str) -> StrataResult<ForkInfo>;
+        let forks: [(&str, Fork); 2] = [("eager", fork_branch), ("cow", fork_branch_cow)];
+        for (child, fork) in forks {
+            fork(&db, "parent", child).unwrap();
+            assert_eq!(diff_from_parent(&db, child).unwrap().summary.total_added, 0);
+
+            write_kv(&db, child, "default", "changed", Value::Int(2));
+            write_kv(&db, child, "default", "new", Value::Int(3));
+
+            let diff = diff_from_parent(&db, child).unwrap();
+            assert_eq!(diff.branch_a, "parent");
+            assert_eq!(diff.branch_b, child);
+            assert_eq!(diff.summary.total_added, 1);
+            assert_eq!(diff.summary.total_modified, 1);
+            assert_eq!(diff.summary.total_removed, 0);
+            assert_eq!(diff.spaces[0].modified[0].key, "changed");
+        }
+
+        // A branch that was never forked has no parent
+        let err = diff_from_parent(&db, "parent").unwrap_err();
+        assert!(err.to_string().contains("not forked"));
+        assert!(diff_from_parent(&db, "missing").is_err());
+    }
+
     // =========================================================================
     // Merge Tests
     // =========================================================================
@@ -1162,6 +2059,85 @@ mod tests {
         assert_eq!(info.conflicts[0].primitive, PrimitiveType::Kv);
     }
 
+    /// Target and source with conflicts on "a", "b" and "c" plus a
+    /// source-only key
+    fn setup_conflicting_branches() -> (TempDir, Arc<Database>) {
+        let (temp, db) = setup_with_branch("target");
+        BranchIndex::new(db.clone())
+            .create_branch("source")
+            .unwrap();
+        for (key, n) in [("a", 1), ("b", 2), ("c", 3)] {
+            write_kv(&db, "target", "default", key, Value::Int(n));
+            write_kv(&db, "source", "default", key, Value::Int(n * 10));
+        }
+        write_kv(&db, "source", "default", "new_key", Value::Int(4));
+        (temp, db)
+    }
+
+    #[test]
+    fn test_merge_with_callback_resolutions() {
+        let (_temp, db) = setup_conflicting_branches();
+
+        let mut seen = Vec::new();
+        let info = merge_branches_with(&db, "source", "target", |conflict| {
+            seen.push(conflict.key.clone());
+            match conflict.key.as_str() {
+                "a" => Resolution::TakeSource,
+                "b" => Resolution::TakeTarget,
+                _ => Resolution::Custom(Value::Int(33)),
+            }
+        })
+        .unwrap();
+
+        seen.sort();
+        assert_eq!(seen, vec!["a", "b", "c"]);
+        assert_eq!(info.conflicts.len(), 3);
+        // new_key, a and c are written; b is left alone
+        assert_eq!(info.keys_applied, 3);
+
+        let read = |key| read_kv(&db, "target", "default", key);
+        assert_eq!(read("a"), Some(Value::Int(10)));
+        assert_eq!(read("b"), Some(Value::Int(2)));
+        assert_eq!(read("c"), Some(Value::Int(33)));
+        assert_eq!(read("new_key"), Some(Value::Int(4)));
+    }
+
+    #[test]
+    fn test_merge_with_callback_sees_both_values() {
+        let (_temp, db) = setup_conflicting_branches();
+
+        merge_branches_with(&db, "source", "target", |conflict| {
+            if conflict.key == "a" {
+                assert!(conflict.source_value.contains("10"));
+                assert!(conflict.target_value.contains('1'));
+                assert_eq!(conflict.primitive, PrimitiveType::Kv);
+                assert_eq!(conflict.space, "default");
+            }
+            Resolution::TakeTarget
+        })
+        .unwrap();
+
+        for (key, n) in [("a", 1), ("b", 2), ("c", 3)] {
+            assert_eq!(read_kv(&db, "target", "default", key), Some(Value::Int(n)));
+        }
+    }
+
+    #[test]
+    fn test_merge_with_rejects_custom_value_for_non_kv() {
+        let (_temp, db) = setup_conflicting_branches();
+        write_state(&db, "target", "default", "cell", Value::Int(1));
+        write_state(&db, "source", "default", "cell", Value::Int(2));
+
+        let result = merge_branches_with(&db, "source", "target", |_| {
+            Resolution::Custom(Value::Int(0))
+        });
+        assert!(result.is_err());
+
+        // Nothing was written, not even the KV conflicts resolved first
+        assert_eq!(read_kv(&db, "target", "default", "a"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "target", "default", "new_key"), None);
+    }
+
     // =========================================================================
     // Post-Merge Vector Reload Tests (Phase 2)
     // =========================================================================
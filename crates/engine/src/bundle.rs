@@ -7,12 +7,22 @@
 //! ## Export
 //!
 //! Exports scan the KV store for all keys in a branch's namespace and
-//! reconstruct `BranchlogPayload` records grouped by version.
+//! reconstruct `BranchlogPayload` records grouped by version. The scan reads
+//! through a [`ReadOnlySnapshot`](crate::ReadOnlySnapshot), so commits made
+//! while an export runs are left out of it entirely.
 //!
 //! ## Import
 //!
 //! Imports replay each `BranchlogPayload` as a transaction, writing puts
 //! and deletes into the target database.
+//!
+//! ## Tamper Evidence
+//!
+//! [`ExportOptions::include_digest`] records a SHA-256 of each bundle
+//! section and a digest over them; imports reject bundles whose digest no
+//! longer matches. With the `bundle-signing` feature the digest can also be
+//! signed, and `import_signed_branch` only accepts bundles signed by a
+//! trusted key. Key management is the caller's responsibility.
 
 use crate::database::Database;
 use crate::BranchIndex;
@@ -25,9 +35,13 @@ use strata_core::types::{BranchId, Key, TypeTag};
 use strata_core::StrataError;
 use strata_core::StrataResult;
 use strata_durability::branch_bundle::{
-    BranchBundleReader, BranchBundleWriter, BranchlogPayload, BundleBranchInfo, ExportOptions,
+    BranchBundleReader, BranchBundleWriter, BranchlogPayload, BundleBranchInfo, ReadBundleContents,
 };
 
+pub use strata_durability::branch_bundle::ExportOptions;
+#[cfg(feature = "bundle-signing")]
+pub use strata_durability::branch_bundle::{SigningKey, VerifyingKey};
+
 // =============================================================================
 // Public result types
 // =============================================================================
@@ -67,6 +81,9 @@ pub struct BundleInfo {
     pub entry_count: u64,
     /// Whether all checksums are valid
     pub checksums_valid: bool,
+    /// Whether the SHA-256 section hashes match the digest; `None` if the
+    /// bundle has no digest
+    pub digest_valid: Option<bool>,
 }
 
 // =============================================================================
@@ -140,9 +157,10 @@ fn scan_branch_data(
     core_branch_id: BranchId,
     branch_id_str: &str,
 ) -> StrataResult<Vec<BranchlogPayload>> {
-    let storage = db.storage();
+    // Pin one version so every primitive is read at the same point
+    let snapshot = db.pinned_snapshot();
 
-    // Discover all current keys across all type tags
+    // Discover all keys live at the snapshot across all type tags
     let type_tags = [
         TypeTag::KV,
         TypeTag::Event,
@@ -153,7 +171,7 @@ fn scan_branch_data(
 
     let mut all_keys: Vec<Key> = Vec::new();
     for type_tag in type_tags {
-        let entries = storage.list_by_type(&core_branch_id, type_tag);
+        let entries = snapshot.list_by_type(&core_branch_id, type_tag);
         all_keys.extend(entries.into_iter().map(|(k, _)| k));
     }
 
@@ -173,6 +191,9 @@ fn scan_branch_data(
                 | strata_core::Version::Txn(v)
                 | strata_core::Version::Sequence(v) => v,
             };
+            if ver > snapshot.version() {
+                continue;
+            }
             version_groups
                 .entry(ver)
                 .or_default()
@@ -204,14 +225,33 @@ fn scan_branch_data(
 ///
 /// # Errors
 ///
-/// - Bundle is invalid or corrupt
+/// - Bundle is invalid or corrupt, or its digest does not match its contents
 /// - Branch with same ID already exists
 /// - I/O errors reading the archive
 pub fn import_branch(db: &Arc<Database>, path: &Path) -> StrataResult<ImportInfo> {
     // 1. Read and validate bundle
     let contents = BranchBundleReader::read_all(path)
         .map_err(|e| StrataError::storage(format!("Failed to read bundle: {}", e)))?;
+    import_contents(db, &contents)
+}
+
+/// Import a branch from a bundle whose digest was signed by `trusted_key`
+///
+/// Unsigned bundles, bundles signed by another key, and bundles altered
+/// after signing are rejected before anything is written.
+#[cfg(feature = "bundle-signing")]
+pub fn import_signed_branch(
+    db: &Arc<Database>,
+    path: &Path,
+    trusted_key: &VerifyingKey,
+) -> StrataResult<ImportInfo> {
+    let contents = BranchBundleReader::read_all_signed(path, trusted_key)
+        .map_err(|e| StrataError::storage(format!("Failed to read bundle: {}", e)))?;
+    import_contents(db, &contents)
+}
 
+/// Create the bundle's branch and replay its payloads
+fn import_contents(db: &Arc<Database>, contents: &ReadBundleContents) -> StrataResult<ImportInfo> {
     let branch_id_str = &contents.branch_info.name;
     let branch_index = BranchIndex::new(db.clone());
 
@@ -277,7 +317,7 @@ pub fn import_branch(db: &Arc<Database>, path: &Path) -> StrataResult<ImportInfo
 
 /// Validate a bundle without importing it
 ///
-/// Checks the archive structure, checksums, and format version.
+/// Checks the archive structure, checksums, digest, and format version.
 pub fn validate_bundle(path: &Path) -> StrataResult<BundleInfo> {
     let verify = BranchBundleReader::validate(path)
         .map_err(|e| StrataError::storage(format!("Bundle validation failed: {}", e)))?;
@@ -287,6 +327,7 @@ pub fn validate_bundle(path: &Path) -> StrataResult<BundleInfo> {
         format_version: verify.format_version,
         entry_count: verify.wal_entry_count,
         checksums_valid: verify.checksums_valid,
+        digest_valid: verify.digest_valid,
     })
 }
 
@@ -447,6 +488,53 @@ mod tests {
         assert!(import_info.transactions_applied > 0);
     }
 
+    #[test]
+    fn test_export_with_digest() {
+        let (temp_dir, db) = setup_with_branch("digest-branch");
+        let path = temp_dir.path().join("digest.branchbundle.tar.zst");
+        let options = ExportOptions {
+            include_digest: true,
+            ..ExportOptions::default()
+        };
+        export_branch_with_options(&db, "digest-branch", &path, &options).unwrap();
+        assert_eq!(validate_bundle(&path).unwrap().digest_valid, Some(true));
+
+        let plain = temp_dir.path().join("plain.branchbundle.tar.zst");
+        export_branch(&db, "digest-branch", &plain).unwrap();
+        assert_eq!(validate_bundle(&plain).unwrap().digest_valid, None);
+
+        let import_dir = TempDir::new().unwrap();
+        let import_db = Database::open(import_dir.path()).unwrap();
+        import_branch(&import_db, &path).unwrap();
+    }
+
+    #[cfg(feature = "bundle-signing")]
+    #[test]
+    fn test_import_signed_branch() {
+        let (temp_dir, db) = setup_with_branch("signed-branch");
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let signed = temp_dir.path().join("signed.branchbundle.tar.zst");
+        let options = ExportOptions {
+            signing_key: Some(key.clone()),
+            ..ExportOptions::default()
+        };
+        export_branch_with_options(&db, "signed-branch", &signed, &options).unwrap();
+        let unsigned = temp_dir.path().join("unsigned.branchbundle.tar.zst");
+        export_branch(&db, "signed-branch", &unsigned).unwrap();
+
+        let import_dir = TempDir::new().unwrap();
+        let import_db = Database::open(import_dir.path()).unwrap();
+        let other = SigningKey::from_bytes(&[10u8; 32]);
+        assert!(import_signed_branch(&import_db, &unsigned, &key.verifying_key()).is_err());
+        assert!(import_signed_branch(&import_db, &signed, &other.verifying_key()).is_err());
+        assert!(!BranchIndex::new(import_db.clone())
+            .exists("signed-branch")
+            .unwrap());
+
+        let info = import_signed_branch(&import_db, &signed, &key.verifying_key()).unwrap();
+        assert_eq!(info.branch_id, "signed-branch");
+    }
+
     #[test]
     fn test_import_duplicate_branch_fails() {
         let (temp_dir, db) = setup_with_branch("dup-branch");
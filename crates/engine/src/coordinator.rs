@@ -6,12 +6,16 @@
 //!
 //! The TransactionCoordinator wraps TransactionManager and adds:
 //! - Active transaction tracking
-//! - Transaction metrics (started, committed, aborted)
+//! - Transaction metrics (started, committed, aborted, conflicts)
 //! - Commit rate calculation
 
+use dashmap::DashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use strata_concurrency::{RecoveryResult, TransactionContext, TransactionManager};
+use std::time::{Duration, Instant};
+use strata_concurrency::{
+    CommitError, RecoveryResult, RecoveryStats, TransactionContext, TransactionManager,
+};
 use strata_core::traits::Storage;
 use strata_core::types::BranchId;
 use strata_core::StrataError;
@@ -20,6 +24,10 @@ use strata_durability::wal::WalWriter;
 use strata_storage::ShardedStore;
 use tracing::{debug, info, warn};
 
+/// Transaction snapshots held longer than this are logged when released,
+/// to help find callers that leave transactions open.
+const SNAPSHOT_HOLD_WARN_AFTER: Duration = Duration::from_secs(60);
+
 /// Transaction coordinator for the database
 ///
 /// Manages transaction lifecycle, ID allocation, version tracking, and metrics.
@@ -27,7 +35,8 @@ use tracing::{debug, info, warn};
 ///
 /// # Memory Ordering
 ///
-/// The metric counters (active_count, total_started, total_committed, total_aborted)
+/// The metric counters (active_count, total_started, total_committed, total_aborted,
+/// total_conflicts)
 /// use Relaxed ordering intentionally because:
 /// 1. They are purely observational metrics for monitoring/debugging
 /// 2. They do not synchronize any other memory operations
@@ -44,6 +53,19 @@ pub struct TransactionCoordinator {
     total_committed: AtomicU64,
     /// Total transactions aborted - uses Relaxed ordering
     total_aborted: AtomicU64,
+    /// Total commits rejected by conflict validation - uses Relaxed ordering
+    total_conflicts: AtomicU64,
+    /// Maximum number of active transactions, or 0 for no limit
+    max_active: AtomicU64,
+    /// Snapshots held by open transactions, by transaction ID
+    snapshots: DashMap<u64, SnapshotHold>,
+}
+
+/// Where and since when an open transaction holds its snapshot
+struct SnapshotHold {
+    branch_id: BranchId,
+    version: u64,
+    since: Instant,
 }
 
 impl TransactionCoordinator {
@@ -58,6 +80,9 @@ impl TransactionCoordinator {
             total_started: AtomicU64::new(0),
             total_committed: AtomicU64::new(0),
             total_aborted: AtomicU64::new(0),
+            total_conflicts: AtomicU64::new(0),
+            max_active: AtomicU64::new(0),
+            snapshots: DashMap::new(),
         }
     }
 
@@ -82,9 +107,35 @@ impl TransactionCoordinator {
             total_started: AtomicU64::new(0),
             total_committed: AtomicU64::new(0),
             total_aborted: AtomicU64::new(0),
+            total_conflicts: AtomicU64::new(0),
+            max_active: AtomicU64::new(0),
+            snapshots: DashMap::new(),
         }
     }
 
+    /// Advance the version and transaction ID counters past a WAL read
+    /// after the coordinator was created
+    ///
+    /// Used by background recovery, which opens the database before the WAL
+    /// is read. Must run before any transaction on the recovered data starts.
+    pub fn advance_to_recovered(&self, stats: &RecoveryStats) {
+        self.manager
+            .advance_to(stats.final_version, stats.max_txn_id);
+    }
+
+    /// Use a fixed number of commit lock shards instead of one lock per branch
+    ///
+    /// See `TransactionManager::with_commit_lock_shards`.
+    pub fn with_commit_lock_shards(mut self, shards: usize) -> Self {
+        self.manager = self.manager.with_commit_lock_shards(shards);
+        self
+    }
+
+    /// Number of commit lock shards, or `None` when using per-branch locks
+    pub fn commit_lock_shards(&self) -> Option<usize> {
+        self.manager.commit_lock_shards()
+    }
+
     /// Start a new transaction
     ///
     /// Creates a TransactionContext with a snapshot of the current storage state.
@@ -155,6 +206,9 @@ impl TransactionCoordinator {
                 Ok(version)
             }
             Err(e) => {
+                if matches!(e, CommitError::ValidationFailed(_)) {
+                    self.total_conflicts.fetch_add(1, Ordering::Relaxed);
+                }
                 self.record_abort();
                 warn!(target: "strata::txn", error = %e, "Transaction aborted");
                 Err(StrataError::from(e))
@@ -171,6 +225,42 @@ impl TransactionCoordinator {
         self.total_started.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record transaction start if it stays within the active limit
+    ///
+    /// Like `record_start`, but fails with `TooManyTransactions` instead of
+    /// counting the transaction when `max_active` are already active.
+    pub fn try_record_start(&self) -> StrataResult<()> {
+        let limit = self.max_active.load(Ordering::Relaxed);
+        if limit == 0 {
+            self.record_start();
+            return Ok(());
+        }
+        self.active_count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |x| {
+                (x < limit).then_some(x + 1)
+            })
+            .map_err(|active| StrataError::too_many_transactions(active, limit))?;
+        self.total_started.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Limit the number of concurrently active transactions, or lift the
+    /// limit with `None`
+    ///
+    /// Transactions already active are unaffected; only new starts are
+    /// rejected while the count is at or above the limit.
+    pub fn set_max_active(&self, limit: Option<u64>) {
+        self.max_active.store(limit.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Configured active transaction limit, if any
+    pub fn max_active(&self) -> Option<u64> {
+        match self.max_active.load(Ordering::Relaxed) {
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
     /// Record transaction commit
     ///
     /// Decrements active count (saturating at 0) and increments committed count.
@@ -197,6 +287,55 @@ impl TransactionCoordinator {
         self.total_aborted.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record that transaction `txn_id` holds a snapshot at `version`
+    ///
+    /// Reported by `active_snapshots` until `release_snapshot` is called.
+    pub fn record_snapshot(&self, txn_id: u64, branch_id: BranchId, version: u64) {
+        self.snapshots.insert(
+            txn_id,
+            SnapshotHold {
+                branch_id,
+                version,
+                since: Instant::now(),
+            },
+        );
+    }
+
+    /// Forget the snapshot held by transaction `txn_id`
+    ///
+    /// Logs a warning if it was held longer than a minute.
+    pub fn release_snapshot(&self, txn_id: u64) {
+        if let Some((_, hold)) = self.snapshots.remove(&txn_id) {
+            let held = hold.since.elapsed();
+            if held >= SNAPSHOT_HOLD_WARN_AFTER {
+                warn!(
+                    target: "strata::txn",
+                    txn_id,
+                    branch_id = %hold.branch_id,
+                    version = hold.version,
+                    held_secs = held.as_secs(),
+                    "Transaction held its snapshot for a long time"
+                );
+            }
+        }
+    }
+
+    /// Snapshots held by open transactions, oldest first
+    pub fn active_snapshots(&self) -> Vec<SnapshotDebugInfo> {
+        let mut snapshots: Vec<SnapshotDebugInfo> = self
+            .snapshots
+            .iter()
+            .map(|entry| SnapshotDebugInfo {
+                txn_id: *entry.key(),
+                branch_id: entry.branch_id,
+                version: entry.version,
+                age: entry.since.elapsed(),
+            })
+            .collect();
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.age));
+        snapshots
+    }
+
     /// Get current global version
     pub fn current_version(&self) -> u64 {
         self.manager.current_version()
@@ -227,6 +366,7 @@ impl TransactionCoordinator {
             total_started: started,
             total_committed: committed,
             total_aborted: self.total_aborted.load(Ordering::Relaxed),
+            total_conflicts: self.total_conflicts.load(Ordering::Relaxed),
             commit_rate: if started > 0 {
                 committed as f64 / started as f64
             } else {
@@ -264,12 +404,34 @@ impl TransactionCoordinator {
         }
         true
     }
+
+    /// Whether an open transaction holds a snapshot on any of `branches`
+    pub fn has_snapshots_on(&self, branches: &[BranchId]) -> bool {
+        self.snapshots
+            .iter()
+            .any(|hold| branches.contains(&hold.branch_id))
+    }
+}
+
+/// A snapshot held by an open transaction
+///
+/// Returned by `TransactionCoordinator::active_snapshots`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotDebugInfo {
+    /// Transaction holding the snapshot
+    pub txn_id: u64,
+    /// Branch the transaction runs on
+    pub branch_id: BranchId,
+    /// Version the snapshot reads at
+    pub version: u64,
+    /// How long the snapshot has been held
+    pub age: Duration,
 }
 
 /// Transaction metrics
 ///
 /// Provides statistics about transaction lifecycle.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct TransactionMetrics {
     /// Number of currently active transactions
     pub active_count: u64,
@@ -279,6 +441,11 @@ pub struct TransactionMetrics {
     pub total_committed: u64,
     /// Total number of transactions aborted
     pub total_aborted: u64,
+    /// Total number of commits aborted because of a read/write conflict
+    ///
+    /// A subset of `total_aborted`; the remainder are closure errors and
+    /// WAL/storage failures.
+    pub total_conflicts: u64,
     /// Commit success rate (committed / started)
     pub commit_rate: f64,
 }
@@ -297,6 +464,18 @@ impl TransactionMetrics {
             0.0
         }
     }
+
+    /// Conflict rate (conflicts / started)
+    ///
+    /// Useful for tuning retry behavior: a high conflict rate means
+    /// transactions frequently race on the same keys.
+    pub fn conflict_rate(&self) -> f64 {
+        if self.total_started > 0 {
+            self.total_conflicts as f64 / self.total_started as f64
+        } else {
+            0.0
+        }
+    }
 }
 
 #[cfg(test)]
@@ -331,6 +510,7 @@ mod tests {
             deletes_applied: 2,
             final_version: 100,
             max_txn_id: 6,
+            records_skipped: 0,
             from_checkpoint: false,
         };
 
@@ -390,6 +570,52 @@ mod tests {
         assert_eq!(metrics.commit_rate, 0.0);
     }
 
+    #[test]
+    fn test_try_record_start_respects_max_active() {
+        let coordinator = TransactionCoordinator::new(0);
+        coordinator.set_max_active(Some(2));
+        assert_eq!(coordinator.max_active(), Some(2));
+
+        coordinator.try_record_start().unwrap();
+        coordinator.try_record_start().unwrap();
+        let err = coordinator.try_record_start().unwrap_err();
+        assert!(matches!(
+            err,
+            StrataError::TooManyTransactions {
+                active: 2,
+                limit: 2
+            }
+        ));
+        assert_eq!(coordinator.metrics().total_started, 2);
+
+        coordinator.record_commit();
+        coordinator.try_record_start().unwrap();
+
+        coordinator.set_max_active(None);
+        coordinator.try_record_start().unwrap();
+        assert_eq!(coordinator.active_count(), 3);
+    }
+
+    #[test]
+    fn test_active_snapshots_oldest_first() {
+        let coordinator = TransactionCoordinator::new(0);
+        let branch_id = BranchId::new();
+
+        coordinator.record_snapshot(1, branch_id, 10);
+        std::thread::sleep(Duration::from_millis(5));
+        coordinator.record_snapshot(2, branch_id, 12);
+
+        let snapshots = coordinator.active_snapshots();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!((snapshots[0].txn_id, snapshots[0].version), (1, 10));
+        assert_eq!(snapshots[0].branch_id, branch_id);
+        assert!(snapshots[0].age > snapshots[1].age);
+
+        coordinator.release_snapshot(1);
+        coordinator.release_snapshot(1);
+        assert_eq!(coordinator.active_snapshots()[0].txn_id, 2);
+    }
+
     #[test]
     fn test_version_monotonic() {
         let coordinator = TransactionCoordinator::new(100);
@@ -405,6 +631,21 @@ mod tests {
         assert_eq!(v3, 103);
     }
 
+    #[test]
+    fn test_has_snapshots_on_ignores_other_branches() {
+        let coordinator = TransactionCoordinator::new(0);
+        let busy = BranchId::new();
+        let target = BranchId::new();
+
+        coordinator.record_snapshot(1, busy, 5);
+        assert!(!coordinator.has_snapshots_on(&[target]));
+
+        coordinator.record_snapshot(2, target, 5);
+        assert!(coordinator.has_snapshots_on(&[target]));
+        coordinator.release_snapshot(2);
+        assert!(!coordinator.has_snapshots_on(&[target]));
+    }
+
     #[test]
     fn test_metrics_helpers() {
         let coordinator = TransactionCoordinator::new(0);
@@ -721,6 +962,7 @@ mod tests {
             deletes_applied: 5,
             final_version: 500,
             max_txn_id: 15,
+            records_skipped: 0,
             from_checkpoint: false,
         };
 
@@ -4,9 +4,12 @@
 //! On first open, a default `strata.toml` is created. To change settings,
 //! edit the file and restart — same model as Redis.
 
+use crate::primitives::branch::resolve_branch_name;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::Path;
-use strata_core::{StrataError, StrataResult};
+use strata_core::types::BranchId;
+use strata_core::{JsonLimits, KeyNormalization, StrataError, StrataResult, VectorStorage};
 use strata_durability::wal::DurabilityMode;
 
 // ============================================================================
@@ -48,6 +51,65 @@ fn default_timeout_ms() -> u64 {
     5000
 }
 
+/// Embedding dimension of the bundled MiniLM-L6-v2 model.
+pub const DEFAULT_EMBED_DIMENSION: usize = 384;
+
+/// Configuration for the local embedding model used by auto-embed and text
+/// search.
+///
+/// Any SafeTensors BERT-family encoder works: the directory must hold
+/// `model.safetensors`, `vocab.txt` and, for models other than MiniLM, a
+/// HuggingFace-style `config.json` describing the architecture. Persisted in
+/// `strata.toml` under the `[embed_model]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EmbedModelConfig {
+    /// Model directory. Relative paths resolve against the data directory.
+    /// Unset means the bundled MiniLM-L6-v2 download location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Expected embedding dimension (default: 384). Shadow collections are
+    /// created with this dimension and a model producing a different one is
+    /// rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<usize>,
+}
+
+/// Overrides of the JSON document limits.
+///
+/// Unset fields keep the built-in default (see [`JsonLimits`]); values
+/// above the hard ceilings are lowered to them. Persisted in `strata.toml`
+/// under the `[json_limits]` section.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct JsonLimitsConfig {
+    /// Maximum document size in bytes (default: 16MB, ceiling: 256MB).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_document_size: Option<usize>,
+    /// Maximum nesting depth (default: 100, ceiling: 512).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_nesting_depth: Option<usize>,
+    /// Maximum path length in segments (default: 256, ceiling: 1024).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_path_length: Option<usize>,
+    /// Maximum array size in elements (default: 1M, ceiling: 100M).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_array_size: Option<usize>,
+}
+
+impl JsonLimitsConfig {
+    /// The limits in force: defaults for unset fields, clamped to the hard
+    /// ceilings.
+    pub fn resolve(&self) -> JsonLimits {
+        let defaults = JsonLimits::default();
+        JsonLimits {
+            max_document_size: self.max_document_size.unwrap_or(defaults.max_document_size),
+            max_nesting_depth: self.max_nesting_depth.unwrap_or(defaults.max_nesting_depth),
+            max_path_length: self.max_path_length.unwrap_or(defaults.max_path_length),
+            max_array_size: self.max_array_size.unwrap_or(defaults.max_array_size),
+        }
+        .clamped()
+    }
+}
+
 /// Database configuration loaded from `strata.toml`.
 ///
 /// # Example
@@ -73,6 +135,12 @@ pub struct StrataConfig {
     /// Optional model configuration for query expansion and re-ranking.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<ModelConfig>,
+    /// Optional local embedding model override (default: MiniLM-L6-v2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embed_model: Option<EmbedModelConfig>,
+    /// Optional overrides of the JSON document limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_limits: Option<JsonLimitsConfig>,
     /// Embedding batch size for auto-embed.
     /// When opened via `OpenOptions`, defaults to 512.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -85,6 +153,102 @@ pub struct StrataConfig {
     /// Default: 0.4 (Anserini/Pyserini BEIR standard).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bm25_b: Option<f32>,
+    /// Number of commit lock shards.
+    /// Default: unset (one commit lock per branch).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_lock_shards: Option<usize>,
+    /// Number of background scheduler worker threads.
+    /// Default: unset (2 threads).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_threads: Option<usize>,
+    /// Maximum number of queued background tasks.
+    /// Default: unset (4096 tasks).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_queue_depth: Option<usize>,
+    /// Write a snapshot checkpoint during a clean shutdown.
+    /// Default: unset (off).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_on_shutdown: Option<bool>,
+    /// Replay the WAL for different branches in parallel on open.
+    /// Default: unset (sequential replay).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel_recovery: Option<bool>,
+    /// Return from open before the WAL is read and replay it in the
+    /// background, blocking operations until the branch they need is in.
+    /// Default: unset (replay finishes before open returns).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_recovery: Option<bool>,
+    /// Fail to open when the WAL holds records this version cannot read,
+    /// such as writes from a newer version, instead of skipping them.
+    /// Default: unset (skip them with a warning).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_recovery: Option<bool>,
+    /// Number of checkpoint snapshots to keep when `compact()` prunes.
+    /// Default: unset (every snapshot is kept).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_snapshots: Option<usize>,
+    /// Largest transaction accepted into a single WAL record, in bytes.
+    /// Default: unset (64MB).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_record_bytes: Option<u64>,
+    /// Maintain the inverted index for keyword (BM25) search.
+    /// Default: unset (on).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword_index: Option<bool>,
+    /// Keep vector embeddings memory-mapped (`"mmap"`) or fully resident
+    /// (`"in_memory"`).
+    /// Default: unset (mmap).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_storage: Option<VectorStorage>,
+    /// Store values of at least this many bytes once per distinct content,
+    /// shared by every version that holds them.
+    /// Default: unset (every version holds its own copy).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_threshold: Option<usize>,
+    /// How long a deleted key is kept, in milliseconds, before GC may
+    /// remove its tombstone.
+    /// Default: unset (tombstones are kept).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tombstone_ttl_ms: Option<u64>,
+    /// Maximum number of transactions open at once. Beginning one more
+    /// fails with `TooManyTransactions`.
+    /// Default: unset (no limit).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_active_transactions: Option<u64>,
+    /// Result count for list and search requests that do not give one.
+    /// Default: unset (lists return every key, search returns 10 hits).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_result_limit: Option<u64>,
+    /// Largest result count a list or search request may ask for. Larger
+    /// requests are clamped and their results marked truncated.
+    /// Default: unset (no cap).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_result_limit: Option<u64>,
+    /// Canonicalize user keys on write and read (`"identity"` or
+    /// `"lowercase"`).
+    /// Default: unset (identity).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_normalization: Option<KeyNormalization>,
+    /// Branches whose commits skip the WAL. Their writes are lost on
+    /// restart or crash unless a checkpoint captured them.
+    /// Default: unset (every branch uses the database durability mode).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ephemeral_branches: Option<Vec<String>>,
+}
+
+/// Resolve a branch name that is to be made ephemeral.
+///
+/// The `"default"` branch, which also holds the branch registry, and the
+/// admin log branch must stay durable.
+pub(crate) fn ephemeral_branch_id(name: &str) -> StrataResult<BranchId> {
+    let id = resolve_branch_name(name);
+    if name == "default" || id == crate::admin_log::admin_branch_id() {
+        return Err(StrataError::invalid_input(format!(
+            "Branch '{}' cannot be ephemeral",
+            name
+        )));
+    }
+    Ok(id)
 }
 
 fn default_durability_str() -> String {
@@ -97,9 +261,29 @@ impl Default for StrataConfig {
             durability: default_durability_str(),
             auto_embed: false,
             model: None,
+            embed_model: None,
+            json_limits: None,
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            commit_lock_shards: None,
+            background_threads: None,
+            background_queue_depth: None,
+            checkpoint_on_shutdown: None,
+            parallel_recovery: None,
+            background_recovery: None,
+            strict_recovery: None,
+            keep_snapshots: None,
+            max_record_bytes: None,
+            keyword_index: None,
+            vector_storage: None,
+            dedup_threshold: None,
+            tombstone_ttl_ms: None,
+            max_active_transactions: None,
+            default_result_limit: None,
+            max_result_limit: None,
+            key_normalization: None,
+            ephemeral_branches: None,
         }
     }
 }
@@ -133,6 +317,45 @@ impl StrataConfig {
         }
     }
 
+    /// Whether the inverted index for keyword search is maintained.
+    pub fn keyword_index_enabled(&self) -> bool {
+        self.keyword_index.unwrap_or(true)
+    }
+
+    /// Where vector embeddings are held while the database is open.
+    pub fn vector_storage(&self) -> VectorStorage {
+        self.vector_storage.unwrap_or_default()
+    }
+
+    /// How user keys are canonicalized before reaching storage.
+    pub fn key_normalization(&self) -> KeyNormalization {
+        self.key_normalization.unwrap_or_default()
+    }
+
+    /// Resolve `ephemeral_branches` to branch IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list names a branch that must stay durable;
+    /// see [`ephemeral_branch_id`].
+    pub fn ephemeral_branch_ids(&self) -> StrataResult<HashSet<BranchId>> {
+        let names = self.ephemeral_branches.as_deref().unwrap_or_default();
+        names.iter().map(|name| ephemeral_branch_id(name)).collect()
+    }
+
+    /// JSON document limits in force, with overrides applied.
+    pub fn json_limits(&self) -> JsonLimits {
+        self.json_limits.unwrap_or_default().resolve()
+    }
+
+    /// Embedding dimension of the configured local model.
+    pub fn embed_dimension(&self) -> usize {
+        self.embed_model
+            .as_ref()
+            .and_then(|m| m.dimension)
+            .unwrap_or(DEFAULT_EMBED_DIMENSION)
+    }
+
     /// Returns the default config file content with comments.
     pub fn default_toml() -> &'static str {
         r#"# Strata database configuration
@@ -156,6 +379,110 @@ auto_embed = false
 # bm25_k1 = 0.9
 # bm25_b = 0.4
 
+# Commit lock shards (default: one lock per branch).
+# Set to hash branches onto a fixed pool of commit locks, which bounds
+# memory when many short-lived branches commit concurrently.
+# commit_lock_shards = 64
+
+# Background scheduler sizing (defaults: 2 threads, 4096 queued tasks).
+# More threads speed up embedding-heavy workloads; a deeper queue absorbs
+# bursts. Shrink both for memory-constrained deployments.
+# background_threads = 2
+# background_queue_depth = 4096
+
+# Checkpoint on shutdown (default: false).
+# Writes a snapshot during a clean shutdown so the next open does not
+# replay the whole WAL. Makes shutdown slower and restarts faster.
+# checkpoint_on_shutdown = false
+
+# Parallel recovery (default: false).
+# Replays WAL records for different branches concurrently on open, which
+# speeds up cold starts of databases with many branches.
+# parallel_recovery = false
+
+# Background recovery (default: false).
+# Opens the database before reading the WAL, then reads it and applies it to
+# storage in the background. Every operation waits until the WAL is read;
+# after that, reads of a branch wait until the whole branch is replayed and
+# writes wait until recovery has finished.
+# background_recovery = false
+
+# Strict recovery (default: false).
+# WAL records written by a newer version in a format this version cannot
+# read are normally skipped with a warning, so everything else is still
+# recovered. With strict recovery the open fails instead.
+# strict_recovery = false
+
+# Snapshot retention (default: unset, keep every snapshot).
+# After compact() removes the WAL covered by the latest checkpoint, older
+# snapshots beyond this many are deleted. Each kept snapshot is a full copy
+# of the data, so disk usage grows with this number.
+# keep_snapshots = 2
+
+# Maximum WAL record size in bytes (default: 67108864, i.e. 64MB).
+# A transaction whose serialized writes exceed this is rejected with a
+# ValueTooLarge error before anything is written. Store large data with the
+# chunked blob API instead.
+# max_record_bytes = 67108864
+
+# Keyword index (default: true).
+# Set to false to skip inverted index maintenance on every write, saving the
+# CPU to tokenize written text and the memory of the posting lists. Keyword
+# search then fails and hybrid search returns vector results only. Turning
+# it back on rebuilds the index from all stored data on the next open.
+# keyword_index = true
+
+# Vector storage (default: "mmap").
+# "mmap" memory-maps each collection's embeddings and lets the OS page them
+# in on demand, so resident memory stays small but a search that touches
+# cold pages waits on disk. "in_memory" reads every embedding into memory on
+# open: searches never wait on disk, but memory grows with vectors x dims.
+# vector_storage = "mmap"
+
+# Large value dedup (default: unset, off).
+# Values of at least this many bytes are stored once per distinct content
+# and shared by every version, key and branch that holds them, so
+# re-storing the same large document or blob does not add another copy.
+# Each such write is hashed and compared, which costs CPU proportional to
+# the value size.
+# dedup_threshold = 4096
+
+# Tombstone retention in milliseconds (default: unset, keep tombstones).
+# A delete leaves a tombstone so the deletion stays visible in the key's
+# history. With a TTL, retention GC removes keys deleted longer ago than
+# this, reclaiming their memory. A replica or late reader that has not yet
+# seen the delete by then may write the old value back.
+# tombstone_ttl_ms = 86400000
+
+# Maximum open transactions (default: unset, no limit).
+# Beginning a transaction beyond this fails with TooManyTransactions
+# instead of growing memory. Every open transaction holds the snapshot it
+# started from, so a long-running read transaction also holds a slot
+# until it commits or rolls back.
+# max_active_transactions = 1024
+
+# Result limits for kv list, vector search and search (default: unset).
+# A request without a limit (or k) uses default_result_limit; a request
+# above max_result_limit is clamped to it and its results are marked
+# truncated instead of failing. A list without a limit is also capped at
+# max_result_limit, so no request can scan a whole table by accident.
+# default_result_limit = 100
+# max_result_limit = 10000
+
+# Key normalization (default: "identity").
+# "lowercase" lowercases KV keys, JSON document keys, state cell names,
+# vector keys and blob keys on every write and read, so "User:123" and
+# "user:123" are the same key. Existing keys are not rewritten: switching
+# on a database that already holds mixed-case keys makes them unreachable
+# until they are migrated to their lowercase form.
+# key_normalization = "identity"
+
+# Ephemeral branches (default: unset, none).
+# Commits to these branches skip the WAL, so they cost no disk I/O, but
+# their data is lost on restart or crash unless a checkpoint captured it.
+# Merge into a durable branch to keep results. "default" cannot be listed.
+# ephemeral_branches = ["scratch"]
+
 # Model configuration for query expansion and re-ranking.
 # Uncomment and configure to enable intelligent search features.
 # [model]
@@ -163,6 +490,24 @@ auto_embed = false
 # model = "qwen3:1.7b"
 # api_key = "your-api-key"      # optional
 # timeout_ms = 5000              # optional, default 5000
+
+# Local embedding model for auto-embed and text search (default: the
+# bundled MiniLM-L6-v2, 384 dimensions). Point `path` at a directory with
+# model.safetensors, vocab.txt and config.json from any BERT-family encoder.
+# [embed_model]
+# path = "models/my-encoder"     # relative to the data directory
+# dimension = 768
+
+# JSON document limits (defaults: 16MB documents, 100 levels of nesting,
+# 256-segment paths, 1M-element arrays). Raise them for legitimately large
+# or deep documents. Values above the hard ceilings (256MB, 512 levels,
+# 1024 segments, 100M elements) are lowered to the ceiling, so deep
+# documents cannot overflow the stack.
+# [json_limits]
+# max_document_size = 16777216
+# max_nesting_depth = 100
+# max_path_length = 256
+# max_array_size = 1000000
 "#
     }
 
@@ -188,6 +533,12 @@ auto_embed = false
         })?;
         // Validate the durability value eagerly
         config.durability_mode()?;
+        if config.embed_dimension() == 0 {
+            return Err(StrataError::invalid_input(format!(
+                "Invalid embed_model.dimension 0 in '{}'",
+                path.display()
+            )));
+        }
         Ok(config)
     }
 
@@ -312,9 +663,29 @@ mod tests {
                 api_key: Some("sk-test".to_string()),
                 timeout_ms: 3000,
             }),
+            embed_model: None,
+            json_limits: None,
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            commit_lock_shards: None,
+            background_threads: None,
+            background_queue_depth: None,
+            checkpoint_on_shutdown: None,
+            parallel_recovery: None,
+            background_recovery: None,
+            strict_recovery: None,
+            keep_snapshots: None,
+            max_record_bytes: None,
+            keyword_index: None,
+            vector_storage: None,
+            dedup_threshold: None,
+            tombstone_ttl_ms: None,
+            max_active_transactions: None,
+            default_result_limit: None,
+            max_result_limit: None,
+            key_normalization: None,
+            ephemeral_branches: None,
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -335,9 +706,29 @@ mod tests {
             durability: "standard".to_string(),
             auto_embed: false,
             model: None,
+            embed_model: None,
+            json_limits: None,
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            commit_lock_shards: None,
+            background_threads: None,
+            background_queue_depth: None,
+            checkpoint_on_shutdown: None,
+            parallel_recovery: None,
+            background_recovery: None,
+            strict_recovery: None,
+            keep_snapshots: None,
+            max_record_bytes: None,
+            keyword_index: None,
+            vector_storage: None,
+            dedup_threshold: None,
+            tombstone_ttl_ms: None,
+            max_active_transactions: None,
+            default_result_limit: None,
+            max_result_limit: None,
+            key_normalization: None,
+            ephemeral_branches: None,
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -390,9 +781,29 @@ model = "qwen3:1.7b"
                 api_key: None,
                 timeout_ms: 5000,
             }),
+            embed_model: None,
+            json_limits: None,
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            commit_lock_shards: None,
+            background_threads: None,
+            background_queue_depth: None,
+            checkpoint_on_shutdown: None,
+            parallel_recovery: None,
+            background_recovery: None,
+            strict_recovery: None,
+            keep_snapshots: None,
+            max_record_bytes: None,
+            keyword_index: None,
+            vector_storage: None,
+            dedup_threshold: None,
+            tombstone_ttl_ms: None,
+            max_active_transactions: None,
+            default_result_limit: None,
+            max_result_limit: None,
+            key_normalization: None,
+            ephemeral_branches: None,
         };
 
         config.write_to_file(&path).unwrap();
@@ -404,6 +815,28 @@ model = "qwen3:1.7b"
         assert_eq!(model.model, "llama3");
     }
 
+    #[test]
+    fn embed_model_config_parses() {
+        let toml_str = r#"
+[embed_model]
+path = "models/bge-base"
+dimension = 768
+"#;
+        let config: StrataConfig = toml::from_str(toml_str).unwrap();
+        let embed = config.embed_model.as_ref().unwrap();
+        assert_eq!(embed.path.as_deref(), Some("models/bge-base"));
+        assert_eq!(config.embed_dimension(), 768);
+        assert_eq!(StrataConfig::default().embed_dimension(), 384);
+    }
+
+    #[test]
+    fn embed_model_zero_dimension_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[embed_model]\ndimension = 0\n").unwrap();
+        assert!(StrataConfig::from_file(&path).is_err());
+    }
+
     #[test]
     fn bm25_scorer_uses_defaults_when_none() {
         let config = StrataConfig::default();
@@ -451,4 +884,83 @@ auto_embed = false
         assert!((parsed.bm25_k1.unwrap() - 1.5).abs() < 0.001);
         assert!((parsed.bm25_b.unwrap() - 0.6).abs() < 0.001);
     }
+
+    #[test]
+    fn key_normalization_parses() {
+        let config: StrataConfig = toml::from_str("key_normalization = \"lowercase\"\n").unwrap();
+        assert_eq!(config.key_normalization(), KeyNormalization::Lowercase);
+        assert_eq!(
+            StrataConfig::default().key_normalization(),
+            KeyNormalization::Identity
+        );
+        assert!(toml::from_str::<StrataConfig>("key_normalization = \"upper\"\n").is_err());
+    }
+
+    #[test]
+    fn ephemeral_branches_parse() {
+        let config: StrataConfig = toml::from_str("ephemeral_branches = [\"scratch\"]\n").unwrap();
+        let ids = config.ephemeral_branch_ids().unwrap();
+        assert_eq!(ids.len(), 1);
+        assert!(ids.contains(&resolve_branch_name("scratch")));
+        assert!(StrataConfig::default()
+            .ephemeral_branch_ids()
+            .unwrap()
+            .is_empty());
+
+        let config: StrataConfig = toml::from_str("ephemeral_branches = [\"default\"]\n").unwrap();
+        assert!(config.ephemeral_branch_ids().is_err());
+    }
+
+    #[test]
+    fn vector_storage_parses() {
+        let config: StrataConfig = toml::from_str("vector_storage = \"in_memory\"\n").unwrap();
+        assert_eq!(config.vector_storage(), VectorStorage::InMemory);
+        assert_eq!(
+            StrataConfig::default().vector_storage(),
+            VectorStorage::Mmap
+        );
+        assert!(toml::from_str::<StrataConfig>("vector_storage = \"disk\"\n").is_err());
+    }
+
+    #[test]
+    fn dedup_threshold_parses() {
+        let config: StrataConfig = toml::from_str("dedup_threshold = 4096\n").unwrap();
+        assert_eq!(config.dedup_threshold, Some(4096));
+        assert_eq!(StrataConfig::default().dedup_threshold, None);
+    }
+
+    #[test]
+    fn tombstone_ttl_parses() {
+        let config: StrataConfig = toml::from_str("tombstone_ttl_ms = 60000\n").unwrap();
+        assert_eq!(config.tombstone_ttl_ms, Some(60_000));
+        assert_eq!(StrataConfig::default().tombstone_ttl_ms, None);
+    }
+
+    #[test]
+    fn max_active_transactions_parses() {
+        let config: StrataConfig = toml::from_str("max_active_transactions = 64\n").unwrap();
+        assert_eq!(config.max_active_transactions, Some(64));
+        assert_eq!(StrataConfig::default().max_active_transactions, None);
+    }
+
+    #[test]
+    fn result_limits_parse() {
+        let config: StrataConfig =
+            toml::from_str("default_result_limit = 50\nmax_result_limit = 500\n").unwrap();
+        assert_eq!(config.default_result_limit, Some(50));
+        assert_eq!(config.max_result_limit, Some(500));
+        assert_eq!(StrataConfig::default().max_result_limit, None);
+    }
+
+    #[test]
+    fn json_limits_parse() {
+        let config: StrataConfig =
+            toml::from_str("[json_limits]\nmax_nesting_depth = 200\nmax_array_size = 1000000000\n")
+                .unwrap();
+        let limits = config.json_limits();
+        assert_eq!(limits.max_nesting_depth, 200);
+        assert_eq!(limits.max_array_size, strata_core::HARD_MAX_ARRAY_SIZE);
+        assert_eq!(limits.max_document_size, strata_core::MAX_DOCUMENT_SIZE);
+        assert_eq!(StrataConfig::default().json_limits(), JsonLimits::default());
+    }
 }
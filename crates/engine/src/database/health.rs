@@ -0,0 +1,70 @@
+//! Write-path health probe
+//!
+//! Liveness probes need to know that the database can still commit, not
+//! just that it is open. The probe commits a tiny value under a reserved key
+//! in a reserved branch, reads it back and fsyncs the WAL. The branch is not
+//! in the branch index, so it never shows up in branch listings, and each
+//! probe prunes the versions left by earlier ones.
+
+use super::Database;
+use crate::primitives::branch::resolve_branch_name;
+use std::time::{Duration, Instant};
+use strata_core::types::{BranchId, Key, Namespace};
+use strata_core::value::Value;
+use strata_core::{StrataError, StrataResult};
+
+/// Branch name reserved for health probes. Branches with this name, or any
+/// name resolving to the same ID, cannot be created.
+pub const HEALTH_BRANCH_NAME: &str = "_strata_health";
+
+/// Key written by every health probe
+const HEALTH_KEY: &str = "_strata/health";
+
+/// ID of the branch reserved for health probes
+pub fn health_branch_id() -> BranchId {
+    resolve_branch_name(HEALTH_BRANCH_NAME)
+}
+
+/// Outcome of [`Database::probe_write_path`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthProbe {
+    /// Time to commit, read back and sync the probe value
+    pub latency: Duration,
+    /// Whether the WAL was fsynced; false for cache databases, which have none
+    pub wal_synced: bool,
+}
+
+impl Database {
+    /// Commit a probe value in the reserved health branch and read it back.
+    ///
+    /// On disk-backed databases the WAL is then fsynced, so a full disk or
+    /// a failing device surfaces here as an error. Only the newest probe
+    /// version is kept. Like any write, the probe blocks while background
+    /// recovery is still running.
+    pub fn probe_write_path(&self) -> StrataResult<HealthProbe> {
+        let started = Instant::now();
+        let branch_id = health_branch_id();
+        let key = Key::new_kv(Namespace::for_branch(branch_id), HEALTH_KEY);
+
+        let ((), version) = self
+            .transaction_with_version(branch_id, |txn| txn.put(key.clone(), Value::Bool(true)))?;
+        let read = self.read(branch_id, |view| view.get_versioned(&key))?;
+        if !read.is_some_and(|vv| vv.version.as_u64() >= version) {
+            return Err(StrataError::internal(format!(
+                "Health probe committed at version {} but could not read it back",
+                version
+            )));
+        }
+
+        let wal_synced = !self.is_cache();
+        if wal_synced {
+            self.sync()?;
+        }
+        self.gc_versions_before(branch_id, version);
+
+        Ok(HealthProbe {
+            latency: started.elapsed(),
+            wal_synced,
+        })
+    }
+}
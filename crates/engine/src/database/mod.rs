@@ -20,38 +20,100 @@
 //! Per spec Section 4: Implicit transactions wrap legacy-style operations.
 
 pub mod config;
+mod health;
+mod read_snapshot;
+mod recovery_gate;
 mod registry;
+mod restore;
 mod transactions;
 
-pub use config::{ModelConfig, StrataConfig, SHADOW_EVENT, SHADOW_JSON, SHADOW_KV, SHADOW_STATE};
+pub use config::{
+    EmbedModelConfig, JsonLimitsConfig, ModelConfig, StrataConfig, DEFAULT_EMBED_DIMENSION,
+    SHADOW_EVENT, SHADOW_JSON, SHADOW_KV, SHADOW_STATE,
+};
+pub use health::{health_branch_id, HealthProbe, HEALTH_BRANCH_NAME};
+pub use read_snapshot::ReadOnlySnapshot;
 pub use registry::OPEN_DATABASES;
+pub use restore::RestoreInfo;
 pub use transactions::RetryConfig;
 
-use crate::background::BackgroundScheduler;
-use crate::coordinator::TransactionCoordinator;
+use crate::background::{
+    BackgroundScheduler, TaskPriority, DEFAULT_BACKGROUND_QUEUE_DEPTH, DEFAULT_BACKGROUND_THREADS,
+};
+use crate::coordinator::{SnapshotDebugInfo, TransactionCoordinator, TransactionMetrics};
+use crate::primitives::branch::global_branch_id;
 use crate::transaction::TransactionPool;
+use crate::util::dir_size;
 use dashmap::DashMap;
 use parking_lot::Mutex as ParkingMutex;
+use recovery_gate::RecoveryGate;
 use std::any::{Any, TypeId};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use strata_concurrency::{RecoveryCoordinator, TransactionContext};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use strata_concurrency::{BranchReplay, RecoveryCoordinator, TransactionContext};
 use strata_core::types::TypeTag;
 use strata_core::types::{BranchId, Key};
 use strata_core::StrataError;
-use strata_core::{StrataResult, VersionedValue};
+use strata_core::{Clock, KeyNormalization, StrataResult, VectorStorage, VersionedValue};
 use strata_durability::codec::IdentityCodec;
 use strata_durability::wal::{DurabilityMode, WalConfig, WalWriter};
 use strata_durability::{
     BranchSnapshotEntry, EventSnapshotEntry, JsonSnapshotEntry, KvSnapshotEntry, StateSnapshotEntry,
 };
 use strata_durability::{
-    CheckpointCoordinator, CheckpointData, CheckpointError, CompactionError, ManifestError,
-    ManifestManager, WalOnlyCompactor,
+    CheckpointCoordinator, CheckpointData, CheckpointError, CompactInfo, CompactMode,
+    CompactionError, FormatVersionError, ManifestError, ManifestManager, WalOnlyCompactor,
 };
-use strata_storage::ShardedStore;
-use tracing::{info, warn};
+use strata_storage::{CacheLimits, PinnedSnapshotInfo, ShardedStore};
+use tracing::{error, info, warn};
+
+/// Longest single sleep of the background WAL flush thread.
+///
+/// Bounds how long an interval change or shutdown waits to be noticed.
+const FLUSH_POLL_SLICE: std::time::Duration = std::time::Duration::from_millis(100);
+
+// ============================================================================
+// Metrics
+// ============================================================================
+
+/// Point-in-time snapshot of database metrics.
+///
+/// Returned by [`Database::metrics_snapshot`]. Each field is read
+/// independently, so values may be skewed slightly under concurrent load.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MetricsSnapshot {
+    /// Transaction lifecycle counters
+    pub transactions: TransactionMetrics,
+    /// WAL counters (`None` for ephemeral databases)
+    pub durability: Option<strata_durability::WalCounters>,
+    /// Highest version allocated so far
+    pub current_version: u64,
+    /// Total size of the WAL directory on disk (`None` for ephemeral databases)
+    pub wal_size_bytes: Option<u64>,
+    /// Version covered by the latest checkpoint (`None` if none was taken)
+    pub snapshot_watermark: Option<u64>,
+}
+
+/// A checkpoint snapshot on disk.
+///
+/// Returned by [`Database::list_snapshots`] and [`Database::snapshot_info`].
+/// Built from the snapshot file header; section contents are not read.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SnapshotInfo {
+    /// Snapshot identifier (increases with every checkpoint)
+    pub snapshot_id: u64,
+    /// Version covered by the snapshot (all commits up to it are included)
+    pub watermark_txn: u64,
+    /// Creation time in microseconds since epoch
+    pub created_at: u64,
+    /// Size of the snapshot file in bytes
+    pub size_bytes: u64,
+    /// Whether the MANIFEST points at this snapshot
+    pub current: bool,
+}
 
 // ============================================================================
 // Persistence Mode (Storage/Durability Split)
@@ -98,6 +160,16 @@ pub(crate) enum PersistenceMode {
     Disk,
 }
 
+/// A deleted branch whose storage waits for snapshots to be released
+struct PendingReclaim {
+    /// Branch name, for logging
+    name: String,
+    /// Storage shards to clear
+    branches: Vec<BranchId>,
+    /// Version that deleted the branch
+    deleted_at: u64,
+}
+
 // ============================================================================
 // Database Struct
 // ============================================================================
@@ -170,9 +242,28 @@ pub struct Database {
     /// Unified configuration (mirrors strata.toml).
     config: parking_lot::RwLock<StrataConfig>,
 
+    /// Branches whose commits skip the WAL (from `ephemeral_branches`)
+    ephemeral_branches: parking_lot::RwLock<HashSet<BranchId>>,
+
+    /// Set once a commit skipped the WAL because its branch is ephemeral
+    ///
+    /// The search index then holds documents that will not survive a
+    /// restart, so it is no longer frozen to disk.
+    ephemeral_writes: AtomicBool,
+
+    /// Auto-embedding switched off for this process because the model is
+    /// unavailable; strata.toml still records the configured value.
+    auto_embed_suspended: AtomicBool,
+
     /// Shutdown signal for the background WAL flush thread (Standard mode only)
     flush_shutdown: Arc<AtomicBool>,
 
+    /// Sleep interval of the background WAL flush thread in milliseconds
+    ///
+    /// Read by the flush thread on every iteration so it can be adjusted at
+    /// runtime via `set_flush_interval()`.
+    flush_interval_ms: Arc<AtomicU64>,
+
     /// Handle for the background WAL flush thread
     ///
     /// In Standard mode, a background thread periodically calls sync_if_overdue()
@@ -182,6 +273,21 @@ pub struct Database {
     /// Background task scheduler for deferred work (embedding, GC, etc.)
     scheduler: BackgroundScheduler,
 
+    /// Progress of background WAL recovery
+    ///
+    /// Already complete unless the database was opened with
+    /// `background_recovery` and replay is still catching up.
+    recovery_gate: RecoveryGate,
+
+    /// Deleted branches whose versions a snapshot could still read
+    ///
+    /// Removed from storage once the snapshots are released; see
+    /// [`reclaim_deleted_branch`](Self::reclaim_deleted_branch).
+    pending_reclaims: ParkingMutex<Vec<PendingReclaim>>,
+
+    /// Whether `pending_reclaims` is non-empty, checked without the lock
+    reclaims_pending: AtomicBool,
+
     /// Exclusive lock file preventing concurrent process access to the same database.
     ///
     /// Held for the lifetime of the Database. Dropped automatically when the
@@ -333,6 +439,8 @@ impl Database {
         durability_mode: DurabilityMode,
         cfg: StrataConfig,
     ) -> StrataResult<Arc<Self>> {
+        let ephemeral_branches = cfg.ephemeral_branch_ids()?;
+
         // Create directory first so we can canonicalize the path
         let data_dir = path.as_ref().to_path_buf();
         std::fs::create_dir_all(&data_dir).map_err(StrataError::from)?;
@@ -371,60 +479,134 @@ impl Database {
                 canonical_path.display()
             ))
         })?;
+        // Refuse files from a newer format before anything reads them, so a
+        // downgraded build never misreads them or falls back to empty state
+        strata_durability::check_format_versions(&canonical_path).map_err(|e| match e {
+            FormatVersionError::Unsupported {
+                format,
+                path,
+                found,
+                supported,
+            } => StrataError::unsupported_format_version(
+                format!("{} '{}'", format, path.display()),
+                found,
+                supported,
+            ),
+            FormatVersionError::Io(e) => StrataError::from(e),
+        })?;
+
         // Create WAL directory
         let wal_dir = data_dir.join("wal");
         std::fs::create_dir_all(&wal_dir).map_err(StrataError::from)?;
 
         // Use RecoveryCoordinator for proper transaction-aware recovery
         // This reads all WalRecords from the segmented WAL directory
-        let recovery = RecoveryCoordinator::new(wal_dir.clone());
-        let result = match recovery.recover() {
+        let mut recovery = RecoveryCoordinator::new(wal_dir.clone());
+        if cfg.parallel_recovery.unwrap_or(false) {
+            let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+            recovery = recovery.with_replay_threads(threads);
+        }
+        let strict_recovery = cfg.strict_recovery.unwrap_or(false);
+        recovery = recovery
+            .with_dedup_threshold(cfg.dedup_threshold)
+            .with_strict(strict_recovery);
+        // In background mode the WAL is neither read nor applied here: open
+        // returns with empty storage and the catch-up task does both.
+        let background = cfg.background_recovery.unwrap_or(false);
+        let recovered = if background {
+            Ok(strata_concurrency::RecoveryResult {
+                storage: recovery.new_storage(),
+                ..strata_concurrency::RecoveryResult::empty()
+            })
+        } else {
+            recovery.recover()
+        };
+        let result = match recovered {
             Ok(result) => result,
+            // Strict recovery never trades WAL data for an empty database
+            Err(e) if strict_recovery => return Err(e),
             Err(e) => {
                 warn!(
                     target: "strata::db",
                     error = %e,
                     "Recovery failed — starting with empty state. Data from WAL may be lost."
                 );
-                strata_concurrency::RecoveryResult::empty()
+                let empty = strata_concurrency::RecoveryResult::empty();
+                empty.storage.set_dedup_threshold(cfg.dedup_threshold);
+                empty
             }
         };
 
-        info!(
-            target: "strata::db",
-            txns_replayed = result.stats.txns_replayed,
-            writes_applied = result.stats.writes_applied,
-            deletes_applied = result.stats.deletes_applied,
-            final_version = result.stats.final_version,
-            "Recovery complete"
-        );
+        let recovery_gate = if background {
+            RecoveryGate::reading()
+        } else {
+            if result.stats.records_skipped > 0 {
+                warn!(
+                    target: "strata::db",
+                    records_skipped = result.stats.records_skipped,
+                    "Skipped WAL records written by a newer version; their writes are not recovered"
+                );
+            }
+            info!(
+                target: "strata::db",
+                txns_replayed = result.stats.txns_replayed,
+                writes_applied = result.stats.writes_applied,
+                deletes_applied = result.stats.deletes_applied,
+                final_version = result.stats.final_version,
+                "Recovery complete"
+            );
+            RecoveryGate::open()
+        };
 
         // Open segmented WAL writer for appending
         let wal_writer = WalWriter::new(
             wal_dir,
             [0u8; 16], // database UUID placeholder
             durability_mode,
-            WalConfig::default(),
+            WalConfig::default().with_max_record_bytes(
+                cfg.max_record_bytes
+                    .unwrap_or(strata_durability::wal::DEFAULT_MAX_RECORD_BYTES),
+            ),
             Box::new(IdentityCodec),
         )?;
 
         // Create coordinator from recovery result (preserves version continuity)
-        let coordinator = TransactionCoordinator::from_recovery(&result);
+        let mut coordinator = TransactionCoordinator::from_recovery(&result);
+        if let Some(shards) = cfg.commit_lock_shards {
+            coordinator = coordinator.with_commit_lock_shards(shards);
+        }
+        coordinator.set_max_active(cfg.max_active_transactions);
 
         let wal_arc = Arc::new(ParkingMutex::new(wal_writer));
         let flush_shutdown = Arc::new(AtomicBool::new(false));
+        let flush_interval_ms = Arc::new(AtomicU64::new(match durability_mode {
+            DurabilityMode::Standard { interval_ms, .. } => interval_ms,
+            _ => 0,
+        }));
 
         // Spawn background WAL flush thread for Standard mode (#969)
-        let flush_handle = if let DurabilityMode::Standard { interval_ms, .. } = durability_mode {
+        let flush_handle = if let DurabilityMode::Standard { .. } = durability_mode {
             let wal = Arc::clone(&wal_arc);
             let shutdown = Arc::clone(&flush_shutdown);
-            let interval = std::time::Duration::from_millis(interval_ms);
+            let interval_ms = Arc::clone(&flush_interval_ms);
 
             let handle = std::thread::Builder::new()
                 .name("strata-wal-flush".to_string())
                 .spawn(move || {
                     while !shutdown.load(Ordering::Relaxed) {
-                        std::thread::sleep(interval);
+                        // Sleep in bounded slices so interval changes and
+                        // shutdown are noticed without waiting out a long window.
+                        let start = std::time::Instant::now();
+                        loop {
+                            let interval = std::time::Duration::from_millis(
+                                interval_ms.load(Ordering::Relaxed),
+                            );
+                            let elapsed = start.elapsed();
+                            if elapsed >= interval || shutdown.load(Ordering::Relaxed) {
+                                break;
+                            }
+                            std::thread::sleep((interval - elapsed).min(FLUSH_POLL_SLICE));
+                        }
                         if shutdown.load(Ordering::Relaxed) {
                             break;
                         }
@@ -440,6 +622,16 @@ impl Database {
             None
         };
 
+        result
+            .storage
+            .set_tombstone_ttl(cfg.tombstone_ttl_ms.map(Duration::from_millis));
+
+        let scheduler = BackgroundScheduler::new(
+            cfg.background_threads.unwrap_or(DEFAULT_BACKGROUND_THREADS),
+            cfg.background_queue_depth
+                .unwrap_or(DEFAULT_BACKGROUND_QUEUE_DEPTH),
+        );
+
         let db = Arc::new(Self {
             data_dir: canonical_path.clone(),
             storage: Arc::new(result.storage),
@@ -450,9 +642,16 @@ impl Database {
             accepting_transactions: AtomicBool::new(true),
             extensions: DashMap::new(),
             config: parking_lot::RwLock::new(cfg),
+            ephemeral_branches: parking_lot::RwLock::new(ephemeral_branches),
+            ephemeral_writes: AtomicBool::new(false),
             flush_shutdown,
+            flush_interval_ms,
+            auto_embed_suspended: AtomicBool::new(false),
             flush_handle: ParkingMutex::new(flush_handle),
-            scheduler: BackgroundScheduler::new(2, 4096),
+            scheduler,
+            recovery_gate,
+            pending_reclaims: ParkingMutex::new(Vec::new()),
+            reclaims_pending: AtomicBool::new(false),
             _lock_file: Some(lock_file),
         });
 
@@ -462,21 +661,171 @@ impl Database {
         // Release lock before running primitive recovery (may be slow)
         drop(registry);
 
+        if background {
+            Self::spawn_catch_up(&db, recovery, strict_recovery);
+        } else {
+            // Re-link copy-on-write forks to their parents before primitives
+            // rebuild state, so inherited data is visible to their scans.
+            crate::branch_ops::restore_cow_forks(&db)?;
+            Self::recover_primitives(&db)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Rebuild primitive state once storage holds the whole WAL.
+    fn recover_primitives(db: &Arc<Self>) -> StrataResult<()> {
         // Run primitive recovery (e.g., VectorStore, Search Index)
         // This must happen AFTER KV recovery completes, as primitives may
         // depend on config data stored in KV.
-        crate::recovery::recover_all_participants(&db)?;
+        crate::recovery::recover_all_participants(db)?;
 
-        // Ensure the inverted index is enabled. If the search recovery
-        // participant was registered, it already loaded state and enabled it.
-        // If not (e.g., unit tests that don't register participants), we
-        // enable it here as a fallback.
+        // Ensure the inverted index is enabled unless keyword indexing is
+        // switched off. If the search recovery participant was registered,
+        // it already loaded state and enabled it. If not (e.g., unit tests
+        // that don't register participants), we enable it here as a fallback.
         let index = db.extension::<crate::search::InvertedIndex>()?;
-        if !index.is_enabled() {
+        if !index.is_enabled() && db.keyword_index_enabled() {
             index.enable();
         }
+        Ok(())
+    }
 
-        Ok(db)
+    /// Read the WAL and replay its branches on the background scheduler.
+    ///
+    /// If the task cannot be scheduled, recovery runs before open returns.
+    fn spawn_catch_up(db: &Arc<Self>, recovery: RecoveryCoordinator, strict: bool) {
+        let pending = Arc::new(ParkingMutex::new(Some(recovery)));
+        let task = {
+            let weak = Arc::downgrade(db);
+            let pending = Arc::clone(&pending);
+            move || {
+                if let Some(recovery) = pending.lock().take() {
+                    Self::catch_up(&weak, &recovery, strict);
+                }
+            }
+        };
+        if let Err(e) = db.scheduler.submit(TaskPriority::High, task) {
+            warn!(
+                target: "strata::db",
+                error = %e,
+                "Could not schedule background recovery, recovering before open returns"
+            );
+            if let Some(recovery) = pending.lock().take() {
+                Self::catch_up(&Arc::downgrade(db), &recovery, strict);
+            }
+        }
+    }
+
+    /// Read the WAL, apply its branches to storage, then finish recovery.
+    ///
+    /// Nothing is served until the WAL is read and the version counters have
+    /// caught up with it. The branch metadata is applied first so fork links
+    /// can be restored before any other branch is released. Each step holds
+    /// the database only while it runs; if the database is dropped meanwhile,
+    /// catch-up stops.
+    ///
+    /// If the WAL cannot be read, a strict database stops accepting
+    /// transactions; otherwise it continues with empty state, as a
+    /// foreground open would.
+    fn catch_up(db: &Weak<Self>, recovery: &RecoveryCoordinator, strict: bool) {
+        let read = recovery.read_deferred();
+        let Some(strong) = db.upgrade() else {
+            return;
+        };
+        let streams = match read {
+            Ok((stats, streams)) => {
+                if stats.records_skipped > 0 {
+                    warn!(
+                        target: "strata::db",
+                        records_skipped = stats.records_skipped,
+                        "Skipped WAL records written by a newer version; their writes are not recovered"
+                    );
+                }
+                info!(
+                    target: "strata::db",
+                    txns_replayed = stats.txns_replayed,
+                    branches_pending = streams.len(),
+                    final_version = stats.final_version,
+                    "WAL read, replaying branches in the background"
+                );
+                strong.coordinator.advance_to_recovered(&stats);
+                streams
+            }
+            Err(e) if strict => {
+                error!(
+                    target: "strata::db",
+                    error = %e,
+                    "Background recovery could not read the WAL; refusing further transactions"
+                );
+                strong.accepting_transactions.store(false, Ordering::SeqCst);
+                strong.recovery_gate.finish();
+                return;
+            }
+            Err(e) => {
+                warn!(
+                    target: "strata::db",
+                    error = %e,
+                    "Recovery failed — starting with empty state. Data from WAL may be lost."
+                );
+                Vec::new()
+            }
+        };
+        strong
+            .recovery_gate
+            .wal_read(streams.iter().map(BranchReplay::branch_id));
+        drop(strong);
+
+        let (metadata, branches): (Vec<_>, Vec<_>) = streams
+            .into_iter()
+            .partition(|stream| stream.branch_id() == global_branch_id());
+
+        for stream in metadata {
+            if !Self::replay_deferred(db, stream) {
+                return;
+            }
+        }
+        let Some(strong) = db.upgrade() else {
+            return;
+        };
+        if let Err(e) = crate::branch_ops::restore_cow_forks(&strong) {
+            warn!(target: "strata::db", error = %e, "Failed to restore fork links");
+        }
+        strong.recovery_gate.forks_restored();
+        drop(strong);
+
+        for stream in branches {
+            if !Self::replay_deferred(db, stream) {
+                return;
+            }
+        }
+
+        let Some(strong) = db.upgrade() else {
+            return;
+        };
+        if let Err(e) = Self::recover_primitives(&strong) {
+            warn!(target: "strata::db", error = %e, "Primitive recovery failed");
+        }
+        strong.recovery_gate.finish();
+        info!(target: "strata::db", "Background recovery complete");
+    }
+
+    /// Apply one deferred branch. Returns false once the database is gone.
+    fn replay_deferred(db: &Weak<Self>, stream: BranchReplay) -> bool {
+        let Some(db) = db.upgrade() else {
+            return false;
+        };
+        let branch_id = stream.branch_id();
+        if let Err(e) = stream.apply(&db.storage) {
+            warn!(
+                target: "strata::db",
+                branch_id = %branch_id,
+                error = %e,
+                "Background replay failed for branch"
+            );
+        }
+        db.recovery_gate.branch_replayed(&branch_id);
+        true
     }
 
     /// Create a cache database with no disk I/O
@@ -519,8 +868,28 @@ impl Database {
     /// | `cache()` | None | None | No |
     /// | `open(path)` | Yes | Yes (per config) | Yes |
     pub fn cache() -> StrataResult<Arc<Self>> {
+        Self::cache_with_limits(CacheLimits::new())
+    }
+
+    /// Create a cache database with memory and entry budgets
+    ///
+    /// Behaves like [`cache()`](Self::cache), except that once either budget
+    /// in `limits` is exceeded, finishing a transaction evicts the
+    /// least-recently-used KV keys until usage drops back under 90% of the
+    /// budget. Evicted keys disappear as if never written and are removed
+    /// from the search index. Other primitives are never evicted.
+    ///
+    /// Keys still visible to an open transaction's snapshot are not evicted
+    /// until that transaction ends.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let db = Database::cache_with_limits(CacheLimits::new().max_entries(10_000))?;
+    /// ```
+    pub fn cache_with_limits(limits: CacheLimits) -> StrataResult<Arc<Self>> {
         // Create fresh storage
-        let storage = ShardedStore::new();
+        let storage = ShardedStore::with_limits(limits);
 
         // Create coordinator starting at version 1 (no recovery needed)
         let coordinator = TransactionCoordinator::new(1);
@@ -535,9 +904,19 @@ impl Database {
             accepting_transactions: AtomicBool::new(true),
             extensions: DashMap::new(),
             config: parking_lot::RwLock::new(StrataConfig::default()),
+            ephemeral_branches: parking_lot::RwLock::new(HashSet::new()),
+            ephemeral_writes: AtomicBool::new(false),
             flush_shutdown: Arc::new(AtomicBool::new(false)),
+            flush_interval_ms: Arc::new(AtomicU64::new(0)),
+            auto_embed_suspended: AtomicBool::new(false),
             flush_handle: ParkingMutex::new(None),
-            scheduler: BackgroundScheduler::new(2, 4096),
+            scheduler: BackgroundScheduler::new(
+                DEFAULT_BACKGROUND_THREADS,
+                DEFAULT_BACKGROUND_QUEUE_DEPTH,
+            ),
+            recovery_gate: RecoveryGate::open(),
+            pending_reclaims: ParkingMutex::new(Vec::new()),
+            reclaims_pending: AtomicBool::new(false),
             _lock_file: None, // No lock for ephemeral databases
         });
 
@@ -561,10 +940,55 @@ impl Database {
     ///
     /// This is for internal engine use. External users should use
     /// primitives (KVStore, EventLog, etc.) which go through transactions.
+    ///
+    /// During background recovery this waits until every branch has been
+    /// replayed, since callers may look at any branch.
     pub(crate) fn storage(&self) -> &Arc<ShardedStore> {
+        self.recovery_gate.wait_replayed();
+        &self.storage
+    }
+
+    /// Storage without waiting for background recovery.
+    ///
+    /// For recovery itself, which fills storage in while the gate is closed.
+    pub(crate) fn recovering_storage(&self) -> &Arc<ShardedStore> {
         &self.storage
     }
 
+    /// Whether background recovery is still replaying the WAL.
+    ///
+    /// Always false unless the database was opened with
+    /// `background_recovery`.
+    pub fn is_recovering(&self) -> bool {
+        !self.recovery_gate.is_complete()
+    }
+
+    /// Block until background recovery has finished.
+    ///
+    /// Returns immediately when recovery already completed during open.
+    pub fn wait_for_recovery(&self) {
+        self.recovery_gate.wait_complete();
+    }
+
+    /// Block until `branch_id` and the branches it was forked from have
+    /// been replayed.
+    fn wait_for_branch(&self, branch_id: BranchId) {
+        if self.recovery_gate.is_complete() {
+            return;
+        }
+        self.recovery_gate.wait_branch(&branch_id);
+        if branch_id == global_branch_id() {
+            return;
+        }
+        // Fork children read through to their parents
+        self.recovery_gate.wait_forks();
+        let mut branch = branch_id;
+        while let Some(base) = self.storage.fork_base(&branch) {
+            self.recovery_gate.wait_branch(&base.parent);
+            branch = base.parent;
+        }
+    }
+
     /// Get version history for a key directly from storage.
     ///
     /// History reads bypass the transaction layer because they are
@@ -579,6 +1003,7 @@ impl Database {
         before_version: Option<u64>,
     ) -> StrataResult<Vec<VersionedValue>> {
         use strata_core::Storage;
+        self.wait_for_branch(key.namespace.branch_id);
         self.storage.get_history(key, limit, before_version)
     }
 
@@ -590,6 +1015,7 @@ impl Database {
         key: &Key,
         max_timestamp: u64,
     ) -> StrataResult<Option<VersionedValue>> {
+        self.wait_for_branch(key.namespace.branch_id);
         self.storage.get_at_timestamp(key, max_timestamp)
     }
 
@@ -601,6 +1027,7 @@ impl Database {
         prefix: &Key,
         max_timestamp: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.wait_for_branch(prefix.namespace.branch_id);
         self.storage.scan_prefix_at_timestamp(prefix, max_timestamp)
     }
 
@@ -609,6 +1036,7 @@ impl Database {
     /// Returns (oldest_ts, latest_ts) in microseconds since epoch.
     /// Returns None if the branch has no data.
     pub fn time_range(&self, branch_id: BranchId) -> StrataResult<Option<(u64, u64)>> {
+        self.wait_for_branch(branch_id);
         self.storage.time_range(branch_id)
     }
 
@@ -632,12 +1060,173 @@ impl Database {
         self.wal_writer.as_ref().map(|w| w.lock().counters())
     }
 
+    /// Get a snapshot of transaction metrics.
+    ///
+    /// Reads the coordinator's counters (commits, aborts, conflicts, active
+    /// count). Cheap: no locks are taken.
+    pub fn transaction_metrics(&self) -> TransactionMetrics {
+        self.coordinator.metrics()
+    }
+
+    /// Get a point-in-time snapshot of database metrics.
+    ///
+    /// Combines transaction metrics, WAL counters, and the current version.
+    /// For disk-backed databases it also sizes the WAL directory and reads
+    /// the checkpoint watermark from the MANIFEST, so it touches the
+    /// filesystem.
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        let (wal_size_bytes, snapshot_watermark) = if self.is_cache() {
+            (None, None)
+        } else {
+            (
+                Some(dir_size(&self.data_dir.join("wal"))),
+                self.snapshot_watermark(),
+            )
+        };
+        MetricsSnapshot {
+            transactions: self.transaction_metrics(),
+            durability: self.durability_counters(),
+            current_version: self.current_version(),
+            wal_size_bytes,
+            snapshot_watermark,
+        }
+    }
+
+    /// Watermark of the latest checkpoint recorded in the MANIFEST.
+    fn snapshot_watermark(&self) -> Option<u64> {
+        let manifest_path = self.data_dir.join("MANIFEST");
+        if !ManifestManager::exists(&manifest_path) {
+            return None;
+        }
+        ManifestManager::load(manifest_path)
+            .ok()?
+            .manifest()
+            .snapshot_watermark
+    }
+
+    /// List the checkpoint snapshots on disk, oldest first.
+    ///
+    /// Snapshot files whose header cannot be read are skipped with a
+    /// warning. Returns an empty list for ephemeral databases.
+    pub fn list_snapshots(&self) -> StrataResult<Vec<SnapshotInfo>> {
+        if self.is_cache() {
+            return Ok(Vec::new());
+        }
+        let current = self.current_snapshot_id();
+        let files = strata_durability::list_snapshots(&self.data_dir.join("snapshots"))?;
+        Ok(files
+            .into_iter()
+            .filter_map(
+                |(id, path)| match Self::read_snapshot_info(&path, current) {
+                    Ok(info) => Some(info),
+                    Err(e) => {
+                        warn!(
+                            target: "strata::db",
+                            snapshot_id = id,
+                            error = %e,
+                            "Skipping unreadable snapshot"
+                        );
+                        None
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Describe the checkpoint snapshot with the given ID.
+    ///
+    /// Returns `None` if no snapshot file with that ID exists.
+    pub fn snapshot_info(&self, snapshot_id: u64) -> StrataResult<Option<SnapshotInfo>> {
+        if self.is_cache() {
+            return Ok(None);
+        }
+        let path = strata_durability::snapshot_path(&self.data_dir.join("snapshots"), snapshot_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(Self::read_snapshot_info(
+            &path,
+            self.current_snapshot_id(),
+        )?))
+    }
+
+    /// ID of the snapshot the MANIFEST points at.
+    fn current_snapshot_id(&self) -> Option<u64> {
+        let manifest_path = self.data_dir.join("MANIFEST");
+        if !ManifestManager::exists(&manifest_path) {
+            return None;
+        }
+        ManifestManager::load(manifest_path)
+            .ok()?
+            .manifest()
+            .snapshot_id
+    }
+
+    fn read_snapshot_info(path: &Path, current: Option<u64>) -> std::io::Result<SnapshotInfo> {
+        let header = strata_durability::read_snapshot_header(path)?;
+        Ok(SnapshotInfo {
+            snapshot_id: header.snapshot_id,
+            watermark_txn: header.watermark_txn,
+            created_at: header.created_at,
+            size_bytes: std::fs::metadata(path)?.len(),
+            current: current == Some(header.snapshot_id),
+        })
+    }
+
+    /// Get the background WAL flush interval in milliseconds.
+    ///
+    /// Returns `None` unless the database uses Standard durability.
+    pub fn flush_interval(&self) -> Option<u64> {
+        match self.durability_mode {
+            DurabilityMode::Standard { .. } => Some(self.flush_interval_ms.load(Ordering::Relaxed)),
+            _ => None,
+        }
+    }
+
+    /// Adjust the background WAL flush interval without reopening.
+    ///
+    /// In Standard durability, committed writes are fsynced by a background
+    /// thread at most every `interval_ms`. A larger window batches more
+    /// commits per fsync (higher throughput) but widens the amount of
+    /// acknowledged data that can be lost on a crash or power failure to up
+    /// to `interval_ms` worth of commits. The flush thread picks up the new
+    /// interval within about 100ms.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `interval_ms` is zero or the database is not
+    /// using Standard durability (Always syncs every commit; ephemeral
+    /// databases have no WAL).
+    pub fn set_flush_interval(&self, interval_ms: u64) -> StrataResult<()> {
+        if interval_ms == 0 {
+            return Err(StrataError::invalid_input(
+                "Flush interval must be greater than zero".to_string(),
+            ));
+        }
+        let wal = match (&self.wal_writer, self.durability_mode) {
+            (Some(wal), DurabilityMode::Standard { .. }) => wal,
+            _ => {
+                return Err(StrataError::invalid_input(
+                    "Flush interval only applies to standard durability mode".to_string(),
+                ))
+            }
+        };
+        wal.lock().set_sync_interval(interval_ms);
+        self.flush_interval_ms.store(interval_ms, Ordering::Relaxed);
+        info!(target: "strata::db", interval_ms, "WAL flush interval updated");
+        Ok(())
+    }
+
     /// Check if the database is currently open and accepting transactions
     pub fn is_open(&self) -> bool {
         self.accepting_transactions.load(Ordering::SeqCst)
     }
 
     /// Returns a reference to the background task scheduler.
+    ///
+    /// Sized by `background_threads` and `background_queue_depth` in
+    /// `strata.toml` (or the matching `OpenOptions`); defaults to 2 threads
+    /// and 4096 queued tasks.
     pub fn scheduler(&self) -> &BackgroundScheduler {
         &self.scheduler
     }
@@ -729,13 +1318,186 @@ impl Database {
 
     /// Check if auto-embedding is enabled.
     pub fn auto_embed_enabled(&self) -> bool {
-        self.config.read().auto_embed
+        self.config.read().auto_embed && !self.auto_embed_suspended.load(Ordering::Relaxed)
+    }
+
+    /// Whether the inverted index for keyword search is maintained.
+    ///
+    /// When false, writes skip tokenizing and indexing their text, keyword
+    /// search fails and hybrid search returns vector results only.
+    pub fn keyword_index_enabled(&self) -> bool {
+        self.config.read().keyword_index_enabled()
+    }
+
+    /// Where vector embeddings are held while the database is open.
+    pub fn vector_storage(&self) -> VectorStorage {
+        self.config.read().vector_storage()
+    }
+
+    /// How user keys are canonicalized before reaching storage.
+    pub fn key_normalization(&self) -> KeyNormalization {
+        self.config.read().key_normalization()
+    }
+
+    /// Mark the branch `name` ephemeral, or make it durable again.
+    ///
+    /// Commits to an ephemeral branch skip the WAL: they are visible at
+    /// once but lost on restart or crash unless a checkpoint captured them.
+    /// Switching back only makes later commits durable. The setting is kept
+    /// in `ephemeral_branches` in `strata.toml`, by name, so it outlives the
+    /// branch and applies again if a branch of that name is recreated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for the `"default"` branch, which also holds the
+    /// branch registry, for the admin log branch, or if `strata.toml`
+    /// cannot be written.
+    pub fn set_branch_ephemeral(&self, name: &str, ephemeral: bool) -> StrataResult<()> {
+        let id = config::ephemeral_branch_id(name)?;
+        self.update_config(|cfg| {
+            let names = cfg.ephemeral_branches.get_or_insert_with(Vec::new);
+            names.retain(|n| n != name);
+            if ephemeral {
+                names.push(name.to_string());
+            }
+            if names.is_empty() {
+                cfg.ephemeral_branches = None;
+            }
+        })?;
+        let mut ephemeral_branches = self.ephemeral_branches.write();
+        if ephemeral {
+            ephemeral_branches.insert(id);
+        } else {
+            ephemeral_branches.remove(&id);
+        }
+        Ok(())
+    }
+
+    /// Whether commits to `branch_id` skip the WAL.
+    pub fn is_branch_ephemeral(&self, branch_id: BranchId) -> bool {
+        self.ephemeral_branches.read().contains(&branch_id)
+    }
+
+    /// Store values of at least `bytes` once per distinct content, or stop
+    /// with `None`.
+    ///
+    /// Versions that write an identical large value, in any key or branch,
+    /// then share one copy, which is freed once GC or branch deletion drops
+    /// its last version. Only later writes are affected. Not persisted: set
+    /// `dedup_threshold` in `strata.toml` (or the matching `OpenOptions`) to
+    /// apply it on open, including to the values replayed from the WAL.
+    pub fn set_dedup_threshold(&self, bytes: Option<usize>) {
+        self.config.write().dedup_threshold = bytes;
+        self.storage.set_dedup_threshold(bytes);
+    }
+
+    /// Let retention GC remove deleted keys once their delete is older than
+    /// `ttl`, or keep every tombstone with `None`.
+    ///
+    /// Until then the delete stays observable in the key's history. Not
+    /// persisted: set `tombstone_ttl_ms` in `strata.toml` (or the matching
+    /// `OpenOptions`) to apply it on open.
+    pub fn set_tombstone_ttl(&self, ttl: Option<Duration>) {
+        self.config.write().tombstone_ttl_ms = ttl.map(|ttl| ttl.as_millis() as u64);
+        self.storage.set_tombstone_ttl(ttl);
+    }
+
+    /// Stop maintaining the inverted index for keyword search.
+    ///
+    /// Clears the index and deletes its on-disk cache. Not persisted: set
+    /// `keyword_index = false` in `strata.toml` (or the matching
+    /// `OpenOptions`) to keep it off across opens. The next open with the
+    /// index on rebuilds it from all stored data.
+    pub fn disable_keyword_index(&self) -> StrataResult<()> {
+        self.config.write().keyword_index = Some(false);
+        let index = self.extension::<crate::search::InvertedIndex>()?;
+        index.disable();
+        index.clear();
+        crate::search::recovery::discard_index_cache(self);
+        Ok(())
+    }
+
+    /// Freeze the inverted index to disk now rather than only at shutdown.
+    ///
+    /// The frozen index records the current database version. The next open
+    /// loads it directly if no write has committed since; otherwise, as
+    /// after a crash that followed later writes, it rebuilds the index from
+    /// storage. Calling this periodically keeps cold starts after a crash
+    /// fast for workloads that go quiet between bursts of writes.
+    ///
+    /// Each call seals the in-memory segment, so calling it after every
+    /// write leaves many small segments. A no-op for cache databases, when
+    /// keyword indexing is off, and once an ephemeral branch has been
+    /// written to.
+    pub fn checkpoint_search_index(&self) -> StrataResult<()> {
+        let data_dir = self.data_dir();
+        if data_dir.as_os_str().is_empty() {
+            return Ok(()); // Ephemeral database — no persistence
+        }
+
+        let index = self.extension::<crate::search::InvertedIndex>()?;
+        // A disabled index is missing recent writes, and one that indexed
+        // ephemeral-branch writes holds documents the WAL will not restore;
+        // never persist either
+        if !index.is_enabled() || self.ephemeral_writes.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        // Read before sealing: a write committed later carries a higher
+        // version, so it makes this checkpoint stale rather than lost.
+        let data_version = self.current_version();
+        index
+            .freeze_to_disk(data_version)
+            .map_err(|e| StrataError::storage(format!("Failed to freeze search index: {}", e)))
+    }
+
+    /// Rebuild the inverted index from all stored KV, State and Event data.
+    ///
+    /// Use this when the on-disk index is suspected to have drifted from
+    /// storage. The rebuilt index is frozen to disk so the next open loads
+    /// it directly. Keyword searches that run concurrently may see partial
+    /// results until the rebuild finishes.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the database was opened with `keyword_index = false`, or if
+    /// the rebuilt index cannot be written to disk.
+    pub fn rebuild_keyword_index(&self) -> StrataResult<crate::search::IndexStats> {
+        if !self.keyword_index_enabled() {
+            return Err(StrataError::invalid_input(
+                "Cannot rebuild the keyword index: the database was opened with keyword_index = false",
+            ));
+        }
+        crate::search::recovery::rebuild_index(self)
+    }
+
+    /// Replace the clock that timestamps writes.
+    ///
+    /// Version, WAL record, event and snapshot timestamps of later writes
+    /// are read from `clock`; existing data keeps its timestamps. Not
+    /// persisted: a reopened database uses system time again.
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.storage.set_clock(clock);
+    }
+
+    /// The clock that timestamps writes.
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.storage.clock()
+    }
+
+    /// Switch auto-embedding off for the lifetime of this instance.
+    ///
+    /// Unlike [`set_auto_embed`](Self::set_auto_embed) this is not persisted,
+    /// so a later open honors `strata.toml` again. Used when the model is
+    /// unavailable at open.
+    pub fn suspend_auto_embed(&self) {
+        self.auto_embed_suspended.store(true, Ordering::Relaxed);
     }
 
     /// Enable or disable auto-embedding.
     ///
     /// Persists to `strata.toml` for disk-backed databases.
     pub fn set_auto_embed(&self, enabled: bool) {
+        self.auto_embed_suspended.store(false, Ordering::Relaxed);
         // Use update_config for persistence; ignore error since
         // auto_embed never triggers the durability rejection.
         let _ = self.update_config(|cfg| {
@@ -748,13 +1510,24 @@ impl Database {
         self.config.read().embed_batch_size.unwrap_or(64)
     }
 
-    /// Path to the model directory for MiniLM-L6-v2.
+    /// Path to the embedding model directory.
     ///
     /// Checks in order:
-    /// 1. Database-local `{data_dir}/models/minilm-l6-v2/`
-    /// 2. System-wide `~/.stratadb/models/minilm-l6-v2/`
-    /// 3. Falls back to the local path (for error messages)
+    /// 1. `[embed_model] path` from the config, resolved against the data
+    ///    directory when relative (returned even if missing)
+    /// 2. Database-local `{data_dir}/models/minilm-l6-v2/`
+    /// 3. System-wide `~/.stratadb/models/minilm-l6-v2/`
+    /// 4. Falls back to the local path (for error messages)
     pub fn model_dir(&self) -> PathBuf {
+        let configured = self
+            .config
+            .read()
+            .embed_model
+            .as_ref()
+            .and_then(|m| m.path.clone());
+        if let Some(path) = configured {
+            return self.data_dir.join(path);
+        }
         let local = self.data_dir.join("models/minilm-l6-v2");
         if local.join("model.safetensors").exists() && local.join("vocab.txt").exists() {
             return local;
@@ -771,6 +1544,17 @@ impl Database {
         local
     }
 
+    /// Embedding dimension the configured model is expected to produce.
+    pub fn embed_dimension(&self) -> usize {
+        self.config.read().embed_dimension()
+    }
+
+    /// Whether the model files are present in [`model_dir`](Self::model_dir).
+    pub fn model_available(&self) -> bool {
+        let dir = self.model_dir();
+        dir.join("model.safetensors").exists() && dir.join("vocab.txt").exists()
+    }
+
     // ========================================================================
     // Branch Lifecycle Cleanup
     // ========================================================================
@@ -783,6 +1567,25 @@ impl Database {
         self.storage.gc_branch(branch_id, min_version)
     }
 
+    /// Garbage-collect all but the newest `keep_last` versions of each entry
+    /// in the branch.
+    ///
+    /// The latest version of every entry is always kept. Versions visible to
+    /// the snapshot of any open transaction, to pinned snapshots and to
+    /// copy-on-write forks are kept as well. Pruned versions are no longer
+    /// reachable by version history or time-travel reads. Returns the number
+    /// of pruned versions.
+    pub fn gc_versions_keep_last(&self, branch_id: BranchId, keep_last: usize) -> usize {
+        let oldest_snapshot = self
+            .coordinator
+            .active_snapshots()
+            .iter()
+            .map(|s| s.version)
+            .min();
+        self.storage
+            .gc_branch_keep_last(branch_id, keep_last, oldest_snapshot)
+    }
+
     /// Get the current global version from the coordinator.
     ///
     /// This is the highest version allocated so far and serves as
@@ -801,17 +1604,166 @@ impl Database {
         self.coordinator.remove_branch_lock(branch_id);
     }
 
-    // ========================================================================
-    // Flush
-    // ========================================================================
-
-    /// Flush WAL to disk
+    /// Number of commit lock shards, or `None` when using one lock per branch.
     ///
-    /// Forces all buffered WAL entries to be written to disk.
-    /// This is automatically done based on durability mode, but can
-    /// be called manually to ensure durability at a specific point.
+    /// Configured via `commit_lock_shards` in `strata.toml` or
+    /// `OpenOptions::commit_lock_shards`.
+    pub fn commit_lock_shards(&self) -> Option<usize> {
+        self.coordinator.commit_lock_shards()
+    }
+
+    /// Reject new transactions while `limit` are already open, or lift the
+    /// limit with `None`.
     ///
-    /// For ephemeral databases, this is a no-op.
+    /// Transactions already open are unaffected. Not persisted: set
+    /// `max_active_transactions` in `strata.toml` (or the matching
+    /// `OpenOptions`) to apply it on open.
+    pub fn set_max_active_transactions(&self, limit: Option<u64>) {
+        self.config.write().max_active_transactions = limit;
+        self.coordinator.set_max_active(limit);
+    }
+
+    /// Default and maximum result counts for list and search requests, as
+    /// `(default_result_limit, max_result_limit)`.
+    pub fn result_limits(&self) -> (Option<u64>, Option<u64>) {
+        let cfg = self.config.read();
+        (cfg.default_result_limit, cfg.max_result_limit)
+    }
+
+    /// JSON document limits enforced on writes, with configured overrides
+    /// applied.
+    pub fn json_limits(&self) -> strata_core::JsonLimits {
+        self.config.read().json_limits()
+    }
+
+    /// Number of transactions currently open.
+    pub fn active_transactions(&self) -> u64 {
+        self.coordinator.active_count()
+    }
+
+    /// Snapshots held by open transactions, oldest first.
+    ///
+    /// A transaction holds its snapshot from `begin_transaction()` until
+    /// `end_transaction()`. The oldest entries are the ones to look at when
+    /// memory keeps growing: their snapshots keep old versions in use. Only
+    /// reads coordinator state; snapshots pinned in storage are listed by
+    /// [`pinned_snapshots`](Self::pinned_snapshots).
+    pub fn active_snapshots(&self) -> Vec<SnapshotDebugInfo> {
+        self.coordinator.active_snapshots()
+    }
+
+    /// Versions pinned by storage snapshots, oldest first.
+    ///
+    /// Pins are taken by [`pinned_snapshot`](Self::pinned_snapshot), which
+    /// bundle exports and key scans use, and by vector scans, and held until
+    /// the snapshot is dropped. In a cache-bounded
+    /// database every storage snapshot pins its version, so open
+    /// transactions show up here as well.
+    pub fn pinned_snapshots(&self) -> Vec<PinnedSnapshotInfo> {
+        self.storage.pinned_snapshots()
+    }
+
+    /// Remove the versions of deleted `branches` from storage.
+    ///
+    /// `deleted_at` is the version that deleted them. Removal waits up to
+    /// `timeout` until nothing can read them any more: no open transaction
+    /// on `branches` and no pinned snapshot (see
+    /// [`pinned_snapshot`](Self::pinned_snapshot)) older than `deleted_at`.
+    /// Snapshots taken later already see the branches as deleted. If they
+    /// are still read after `timeout`, removal is queued and retried each
+    /// time a transaction ends or a pinned snapshot is dropped.
+    ///
+    /// Returns the keys and estimated bytes removed, or `None` if queued.
+    pub(crate) fn reclaim_deleted_branch(
+        &self,
+        name: &str,
+        branches: Vec<BranchId>,
+        deleted_at: u64,
+        timeout: std::time::Duration,
+    ) -> Option<(u64, u64)> {
+        let start = std::time::Instant::now();
+        while self.branches_read(&branches, deleted_at) {
+            if start.elapsed() > timeout {
+                self.pending_reclaims.lock().push(PendingReclaim {
+                    name: name.to_string(),
+                    branches,
+                    deleted_at,
+                });
+                self.reclaims_pending.store(true, Ordering::Release);
+                return None;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        Some(self.clear_deleted_branches(&branches, deleted_at))
+    }
+
+    /// Whether a transaction or pinned snapshot can still read the versions
+    /// of `branches`, deleted at `deleted_at`.
+    fn branches_read(&self, branches: &[BranchId], deleted_at: u64) -> bool {
+        self.coordinator.has_snapshots_on(branches)
+            || self
+                .storage
+                .oldest_pinned_version()
+                .is_some_and(|version| version < deleted_at)
+    }
+
+    /// Clear deleted branches from storage, returning keys and bytes removed.
+    ///
+    /// A branch written after `deleted_at` was created again and is kept.
+    fn clear_deleted_branches(&self, branches: &[BranchId], deleted_at: u64) -> (u64, u64) {
+        let mut keys = 0u64;
+        let mut bytes = 0u64;
+        for id in branches {
+            if let Some((k, b)) = self.storage.clear_deleted_branch(id, deleted_at) {
+                keys += k as u64;
+                bytes += b as u64;
+            }
+        }
+        (keys, bytes)
+    }
+
+    /// Remove queued deleted branches that nothing reads any more.
+    ///
+    /// Cheap when nothing is queued: one atomic load.
+    fn retry_pending_reclaims(&self) {
+        if !self.reclaims_pending.load(Ordering::Acquire) {
+            return;
+        }
+        let mut pending = self.pending_reclaims.lock();
+        pending.retain(|reclaim| {
+            if self.branches_read(&reclaim.branches, reclaim.deleted_at) {
+                return true;
+            }
+            let (keys, bytes) = self.clear_deleted_branches(&reclaim.branches, reclaim.deleted_at);
+            info!(
+                target: "strata::db",
+                branch = %reclaim.name,
+                keys_reclaimed = keys,
+                bytes_reclaimed = bytes,
+                "Reclaimed storage of deleted branch"
+            );
+            false
+        });
+        self.reclaims_pending
+            .store(!pending.is_empty(), Ordering::Release);
+    }
+
+    /// Number of deleted branches whose storage is waiting on a snapshot
+    pub fn pending_reclaim_count(&self) -> usize {
+        self.pending_reclaims.lock().len()
+    }
+
+    // ========================================================================
+    // Flush
+    // ========================================================================
+
+    /// Flush WAL to disk
+    ///
+    /// Forces all buffered WAL entries to be written to disk.
+    /// This is automatically done based on durability mode, but can
+    /// be called manually to ensure durability at a specific point.
+    ///
+    /// For ephemeral databases, this is a no-op.
     pub fn flush(&self) -> StrataResult<()> {
         if let Some(ref wal) = self.wal_writer {
             let mut wal = wal.lock();
@@ -822,6 +1774,24 @@ impl Database {
         }
     }
 
+    /// Durability barrier: return only once all committed data is on disk.
+    ///
+    /// Forces an fsync of the WAL (and its directory) regardless of the
+    /// durability mode, so callers of a Standard-mode database can make a
+    /// critical point durable without waiting for the background flush.
+    ///
+    /// Unlike `flush()`, which is a no-op for ephemeral databases, `sync()`
+    /// returns an error there: there is no storage that could make the
+    /// data durable, and silently succeeding would suggest otherwise.
+    pub fn sync(&self) -> StrataResult<()> {
+        match self.wal_writer {
+            Some(ref wal) => wal.lock().sync().map_err(StrataError::from),
+            None => Err(StrataError::invalid_input(
+                "Cannot sync an ephemeral database: data is never persisted".to_string(),
+            )),
+        }
+    }
+
     // ========================================================================
     // Checkpoint & Compaction
     // ========================================================================
@@ -840,6 +1810,9 @@ impl Database {
             return Ok(());
         }
 
+        // A checkpoint must cover the whole WAL
+        self.recovery_gate.wait_complete();
+
         // Flush WAL first to ensure all buffered writes are on disk
         self.flush()?;
 
@@ -854,31 +1827,8 @@ impl Database {
 
         // Load or create MANIFEST
         let mut manifest = self.load_or_create_manifest()?;
-
-        // Build watermark state from existing MANIFEST if present
-        let existing_watermark = {
-            let m = manifest.manifest();
-            match (m.snapshot_id, m.snapshot_watermark) {
-                (Some(sid), Some(wtxn)) => Some(strata_durability::SnapshotWatermark::with_values(
-                    sid, wtxn, 0,
-                )),
-                _ => None,
-            }
-        };
-
-        // Create CheckpointCoordinator
-        let mut coordinator = if let Some(wm) = existing_watermark {
-            CheckpointCoordinator::with_watermark(
-                snapshots_dir,
-                Box::new(IdentityCodec),
-                [0u8; 16],
-                wm,
-            )
-            .map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))?
-        } else {
-            CheckpointCoordinator::new(snapshots_dir, Box::new(IdentityCodec), [0u8; 16])
-                .map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))?
-        };
+        let mut coordinator = Self::checkpoint_coordinator(snapshots_dir, &manifest)?
+            .with_clock(self.storage.clock());
 
         // Create the checkpoint
         let info = coordinator
@@ -904,18 +1854,49 @@ impl Database {
         Ok(())
     }
 
+    /// Create a CheckpointCoordinator starting from the MANIFEST's watermark.
+    fn checkpoint_coordinator(
+        snapshots_dir: PathBuf,
+        manifest: &ManifestManager,
+    ) -> StrataResult<CheckpointCoordinator> {
+        // Build watermark state from existing MANIFEST if present
+        let existing_watermark = {
+            let m = manifest.manifest();
+            match (m.snapshot_id, m.snapshot_watermark) {
+                (Some(sid), Some(wtxn)) => Some(strata_durability::SnapshotWatermark::with_values(
+                    sid, wtxn, 0,
+                )),
+                _ => None,
+            }
+        };
+
+        let coordinator = if let Some(wm) = existing_watermark {
+            CheckpointCoordinator::with_watermark(
+                snapshots_dir,
+                Box::new(IdentityCodec),
+                [0u8; 16],
+                wm,
+            )
+        } else {
+            CheckpointCoordinator::new(snapshots_dir, Box::new(IdentityCodec), [0u8; 16])
+        };
+        coordinator.map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))
+    }
+
     /// Compact WAL segments that are no longer needed for recovery.
     ///
     /// Removes closed WAL segments whose max transaction ID is at or below the
     /// latest snapshot watermark. The active segment is never removed.
     ///
     /// A checkpoint must exist before compaction can run. For ephemeral (cache)
-    /// databases, this is a no-op.
+    /// databases, this is a no-op that reports nothing removed.
+    ///
+    /// Returns what was removed: segment count and bytes reclaimed.
     ///
     /// See: `docs/architecture/STORAGE_DURABILITY_ARCHITECTURE.md` Section 5.6
-    pub fn compact(&self) -> StrataResult<()> {
+    pub fn compact(&self) -> StrataResult<CompactInfo> {
         if self.persistence_mode == PersistenceMode::Ephemeral {
-            return Ok(());
+            return Ok(CompactInfo::new(CompactMode::WALOnly));
         }
 
         let wal_dir = self.data_dir.join("wal");
@@ -925,7 +1906,7 @@ impl Database {
         let manifest_arc = Arc::new(parking_lot::Mutex::new(manifest));
 
         // Create compactor and run
-        let compactor = WalOnlyCompactor::new(wal_dir, manifest_arc);
+        let compactor = WalOnlyCompactor::new(wal_dir, Arc::clone(&manifest_arc));
         let compact_info = compactor.compact().map_err(|e: CompactionError| match e {
             CompactionError::NoSnapshot => StrataError::invalid_input(
                 "No checkpoint exists yet. Run checkpoint() before compact().".to_string(),
@@ -940,7 +1921,57 @@ impl Database {
             "WAL compaction completed"
         );
 
-        Ok(())
+        // The current snapshot is durable and the WAL it covers is gone, so
+        // older snapshots beyond the retention limit can go too.
+        if let Some(keep) = self.config.read().keep_snapshots {
+            let coordinator = Self::checkpoint_coordinator(
+                self.data_dir.join("snapshots"),
+                &manifest_arc.lock(),
+            )?;
+            let pruned = coordinator.prune_snapshots(keep)?;
+            if !pruned.is_empty() {
+                info!(
+                    target: "strata::db",
+                    snapshots_removed = pruned.len(),
+                    keep,
+                    "Old snapshots pruned"
+                );
+            }
+        }
+
+        Ok(compact_info)
+    }
+
+    /// Check whether [`compact`](Self::compact) would remove any WAL segments.
+    ///
+    /// True when at least one closed segment is fully covered by the latest
+    /// snapshot watermark. False for ephemeral databases and before the first
+    /// checkpoint. Unlike `compact()`, this never writes the MANIFEST.
+    pub fn compaction_needed(&self) -> StrataResult<bool> {
+        if self.persistence_mode == PersistenceMode::Ephemeral {
+            return Ok(false);
+        }
+
+        let manifest_path = self.data_dir.join("MANIFEST");
+        if !ManifestManager::exists(&manifest_path) {
+            return Ok(false);
+        }
+        let mut manifest = ManifestManager::load(manifest_path).map_err(|e: ManifestError| {
+            StrataError::internal(format!("failed to load MANIFEST: {}", e))
+        })?;
+        // The MANIFEST may lag behind the writer; use the live segment number
+        // without persisting it.
+        if let Some(ref wal) = self.wal_writer {
+            manifest.manifest_mut().active_wal_segment = wal.lock().current_segment();
+        }
+
+        let compactor = WalOnlyCompactor::new(
+            self.data_dir.join("wal"),
+            Arc::new(parking_lot::Mutex::new(manifest)),
+        );
+        compactor
+            .compaction_needed()
+            .map_err(|e| StrataError::internal(format!("compaction check failed: {}", e)))
     }
 
     /// Collect all primitive data from storage for checkpointing.
@@ -951,14 +1982,14 @@ impl Database {
         let mut branch_entries = Vec::new();
         let mut json_entries = Vec::new();
 
-        let now = strata_durability::now_micros();
+        let now = self.storage.now().as_micros();
 
         for branch_id in self.storage.branch_ids() {
             // KV entries
             for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::KV) {
                 let value_bytes = serde_json::to_vec(&vv.value).unwrap_or_default();
                 kv_entries.push(KvSnapshotEntry {
-                    key: key.user_key_string().unwrap_or_default(),
+                    key: key.user_key.clone(),
                     value: value_bytes,
                     version: vv.version.as_u64(),
                     timestamp: now,
@@ -968,7 +1999,7 @@ impl Database {
             // Event entries
             for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Event) {
                 // Skip metadata keys
-                if key.user_key == b"__meta__" || key.user_key.starts_with(b"__tidx__") {
+                if key.is_internal() {
                     continue;
                 }
                 let sequence = if key.user_key.len() == 8 {
@@ -988,7 +2019,7 @@ impl Database {
             for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::State) {
                 let value_bytes = serde_json::to_vec(&vv.value).unwrap_or_default();
                 state_entries.push(StateSnapshotEntry {
-                    name: key.user_key_string().unwrap_or_default(),
+                    name: key.user_key.clone(),
                     value: value_bytes,
                     counter: vv.version.as_u64(),
                     timestamp: now,
@@ -998,7 +2029,7 @@ impl Database {
             // Branch entries
             for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Branch) {
                 // Skip index keys
-                if key.user_key.starts_with(b"__idx_") {
+                if key.is_internal() {
                     continue;
                 }
                 let branch_id_bytes: [u8; 16] = if key.user_key.len() == 16 {
@@ -1019,7 +2050,7 @@ impl Database {
             for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Json) {
                 let content = serde_json::to_vec(&vv.value).unwrap_or_default();
                 json_entries.push(JsonSnapshotEntry {
-                    doc_id: key.user_key_string().unwrap_or_default(),
+                    doc_id: key.user_key.clone(),
                     content,
                     version: vv.version.as_u64(),
                     timestamp: now,
@@ -1081,12 +2112,10 @@ impl Database {
     // Transaction API
     // ========================================================================
 
-    /// Check if the database is accepting transactions.
+    /// Check that the database has not been shut down.
     fn check_accepting(&self) -> StrataResult<()> {
         if !self.accepting_transactions.load(Ordering::SeqCst) {
-            return Err(StrataError::invalid_input(
-                "Database is shutting down".to_string(),
-            ));
+            return Err(StrataError::database_closed());
         }
         Ok(())
     }
@@ -1146,13 +2175,50 @@ impl Database {
         F: FnOnce(&mut TransactionContext) -> StrataResult<T>,
     {
         self.check_accepting()?;
-        let mut txn = self.begin_transaction(branch_id);
+        let mut txn = self.begin_transaction(branch_id)?;
         let result = f(&mut txn);
         let outcome = self.run_single_attempt(&mut txn, result, self.durability_mode);
         self.end_transaction(txn);
         outcome.map(|(value, _)| value)
     }
 
+    /// Run a read-only closure against a consistent snapshot
+    ///
+    /// Lightweight alternative to `transaction()` for pure multi-read
+    /// consistency. The snapshot is acquired without allocating a transaction
+    /// ID, and nothing goes through the commit path: no validation, no
+    /// version allocation, no WAL, and no coordinator metrics.
+    ///
+    /// Every read inside the closure observes the same snapshot (snapshot
+    /// isolation). The view cannot write: if the closure buffers any write,
+    /// the writes are discarded and an error is returned.
+    ///
+    /// # Example
+    /// ```text
+    /// let (a, b) = db.read(branch_id, |view| {
+    ///     Ok((view.get(&key_a)?, view.get(&key_b)?))
+    /// })?;
+    /// ```
+    pub fn read<F, T>(&self, branch_id: BranchId, f: F) -> StrataResult<T>
+    where
+        F: FnOnce(&mut TransactionContext) -> StrataResult<T>,
+    {
+        self.check_accepting()?;
+        self.wait_for_branch(branch_id);
+        let snapshot = self.storage.create_snapshot();
+        let mut view = TransactionPool::acquire(0, branch_id, Some(Box::new(snapshot)));
+        let result = f(&mut view);
+        let wrote = !view.is_read_only() || !view.json_writes().is_empty();
+        TransactionPool::release(view);
+
+        if wrote {
+            return Err(StrataError::invalid_input(
+                "Cannot write inside a read-only snapshot view".to_string(),
+            ));
+        }
+        result
+    }
+
     /// Execute a transaction and return both the result and commit version
     ///
     /// Like `transaction()` but also returns the commit version assigned to all writes.
@@ -1179,7 +2245,7 @@ impl Database {
         F: FnOnce(&mut TransactionContext) -> StrataResult<T>,
     {
         self.check_accepting()?;
-        let mut txn = self.begin_transaction(branch_id);
+        let mut txn = self.begin_transaction(branch_id)?;
         let result = f(&mut txn);
         let outcome = self.run_single_attempt(&mut txn, result, self.durability_mode);
         self.end_transaction(txn);
@@ -1228,14 +2294,16 @@ impl Database {
         let mut last_error = None;
 
         for attempt in 0..=config.max_retries {
-            let mut txn = self.begin_transaction(branch_id);
+            let mut txn = self.begin_transaction(branch_id)?;
             let result = f(&mut txn);
             let outcome = self.run_single_attempt(&mut txn, result, self.durability_mode);
             self.end_transaction(txn);
 
             match outcome {
                 Ok((value, _)) => return Ok(value),
-                Err(e) if e.is_conflict() && attempt < config.max_retries => {
+                // Retryable covers commit-time validation failures as well
+                // as conflicts raised by the closure itself.
+                Err(e) if e.is_retryable() && attempt < config.max_retries => {
                     last_error = Some(e);
                     std::thread::sleep(config.calculate_delay(attempt));
                     continue;
@@ -1263,21 +2331,31 @@ impl Database {
     /// * `branch_id` - BranchId for namespace isolation
     ///
     /// # Returns
-    /// * `TransactionContext` - Active transaction ready for operations
+    /// * `Ok(TransactionContext)` - Active transaction ready for operations
+    /// * `Err(TooManyTransactions)` - `max_active_transactions` are already open
+    ///
+    /// The transaction holds its snapshot, and its slot against
+    /// `max_active_transactions`, until it is committed or aborted, so a
+    /// long-running read-only transaction counts like any other. Use
+    /// `read()` for reads that should not take a slot.
     ///
     /// # Example
     /// ```text
-    /// let mut txn = db.begin_transaction(branch_id);
+    /// let mut txn = db.begin_transaction(branch_id)?;
     /// txn.put(key, value)?;
     /// db.commit_transaction(&mut txn)?;
     /// db.end_transaction(txn); // Return to pool
     /// ```
-    pub fn begin_transaction(&self, branch_id: BranchId) -> TransactionContext {
+    pub fn begin_transaction(&self, branch_id: BranchId) -> StrataResult<TransactionContext> {
+        self.wait_for_branch(branch_id);
+        self.coordinator.try_record_start()?;
         let txn_id = self.coordinator.next_txn_id();
         let snapshot = self.storage.create_snapshot();
-        self.coordinator.record_start();
 
-        TransactionPool::acquire(txn_id, branch_id, Some(Box::new(snapshot)))
+        let txn = TransactionPool::acquire(txn_id, branch_id, Some(Box::new(snapshot)));
+        self.coordinator
+            .record_snapshot(txn_id, branch_id, txn.start_version);
+        Ok(txn)
     }
 
     /// End a transaction (return to pool)
@@ -1286,20 +2364,54 @@ impl Database {
     /// This avoids allocation overhead on subsequent transactions.
     ///
     /// Should be called after `commit_transaction()` or after aborting.
-    /// The closure API (`transaction()`) calls this automatically.
+    /// The closure API (`transaction()`) calls this automatically. A
+    /// transaction ended while still active is recorded as aborted, which
+    /// frees its slot against `max_active_transactions`.
     ///
     /// # Arguments
     /// * `ctx` - Transaction context to return to pool
     ///
     /// # Example
     /// ```text
-    /// let mut txn = db.begin_transaction(branch_id);
+    /// let mut txn = db.begin_transaction(branch_id)?;
     /// txn.put(key, value)?;
     /// db.commit_transaction(&mut txn)?;
     /// db.end_transaction(txn); // Return to pool for reuse
     /// ```
-    pub fn end_transaction(&self, ctx: TransactionContext) {
+    pub fn end_transaction(&self, mut ctx: TransactionContext) {
+        self.coordinator.release_snapshot(ctx.txn_id);
+        if ctx.is_active() {
+            let _ = ctx.mark_aborted("ended without commit".to_string());
+            self.coordinator.record_abort();
+        }
         TransactionPool::release(ctx);
+        self.enforce_cache_limits();
+        self.retry_pending_reclaims();
+    }
+
+    /// Evict KV keys from a bounded cache database that is over budget
+    ///
+    /// Runs after the transaction's snapshot is released so that its own
+    /// pin does not hold back eviction. No-op for unbounded stores.
+    fn enforce_cache_limits(&self) {
+        let evicted = self.storage.evict_to_limits();
+        if evicted.is_empty() {
+            return;
+        }
+        let Ok(index) = self.extension::<crate::search::InvertedIndex>() else {
+            return;
+        };
+        if !index.is_enabled() {
+            return;
+        }
+        for key in &evicted {
+            if let Some(user_key) = key.user_key_string() {
+                index.remove_document(&crate::search::EntityRef::Kv {
+                    branch_id: key.namespace.branch_id,
+                    key: user_key,
+                });
+            }
+        }
     }
 
     /// Commit a transaction
@@ -1345,8 +2457,17 @@ impl Database {
         txn: &mut TransactionContext,
         durability: DurabilityMode,
     ) -> StrataResult<u64> {
-        let needs_wal =
-            durability.requires_wal() && (!txn.is_read_only() || !txn.json_writes().is_empty());
+        let writes = !txn.is_read_only() || !txn.json_writes().is_empty();
+        if writes {
+            // Recovery participants rebuild from storage; keep it still
+            // until they are done.
+            self.recovery_gate.wait_complete();
+        }
+        let ephemeral = writes && self.is_branch_ephemeral(txn.branch_id);
+        if ephemeral {
+            self.ephemeral_writes.store(true, Ordering::Relaxed);
+        }
+        let needs_wal = durability.requires_wal() && writes && !ephemeral;
 
         let mut wal_guard = if needs_wal {
             self.wal_writer.as_ref().map(|w| w.lock())
@@ -1370,65 +2491,93 @@ impl Database {
     /// for the next recovery to reconstruct embeddings. This is called during
     /// shutdown and drop.
     fn freeze_vector_heaps(&self) {
-        use crate::primitives::vector::VectorBackendState;
+        if let Err(e) = self.checkpoint_vectors() {
+            tracing::warn!(
+                target: "strata::vector",
+                error = %e,
+                "Failed to freeze vectors at shutdown"
+            );
+        }
+    }
+
+    /// Freeze every collection's embedding heap and HNSW graphs to their
+    /// mmap files now rather than only at shutdown.
+    ///
+    /// On the next open the heaps are loaded from these files and the
+    /// graphs reused once checked against the stored vector records, so a
+    /// restart after a crash does not rebuild every graph. Vectors written
+    /// after the checkpoint are recovered into the in-memory buffer; a
+    /// graph holding a vector deleted since is rebuilt.
+    ///
+    /// Vector writes wait while this runs. Returns the number of files
+    /// written; 0 for cache databases. A collection that fails to freeze
+    /// does not stop the others, and the first error is returned.
+    pub fn checkpoint_vectors(&self) -> StrataResult<usize> {
+        use crate::primitives::vector::recovery::mmap_path;
+        use crate::primitives::vector::{graph_dir, VectorBackendState};
 
         let data_dir = self.data_dir();
         if data_dir.as_os_str().is_empty() {
-            return; // Ephemeral database — no mmap
+            return Ok(0); // Ephemeral database — no mmap
         }
 
         let state = match self.extension::<VectorBackendState>() {
             Ok(s) => s,
-            Err(_) => return, // No vector state registered
+            Err(_) => return Ok(0), // No vector state registered
         };
 
+        let mut files_written = 0;
+        let mut first_error = None;
         let backends = state.backends.read();
         for (cid, backend) in backends.iter() {
-            let branch_hex = format!("{:032x}", u128::from_be_bytes(*cid.branch_id.as_bytes()));
-            let vec_path = data_dir
-                .join("vectors")
-                .join(&branch_hex)
-                .join(format!("{}.vec", cid.name));
-            if let Err(e) = backend.freeze_heap_to_disk(&vec_path) {
-                tracing::warn!(
-                    target: "strata::vector",
-                    collection = %cid.name,
-                    error = %e,
-                    "Failed to freeze vector heap at shutdown"
-                );
+            let vec_path = mmap_path(data_dir, cid.branch_id, &cid.name);
+            match backend.freeze_heap_to_disk(&vec_path) {
+                Ok(written) => files_written += usize::from(written),
+                Err(e) => {
+                    tracing::warn!(
+                        target: "strata::vector",
+                        collection = %cid.name,
+                        error = %e,
+                        "Failed to freeze vector heap"
+                    );
+                    first_error.get_or_insert(e);
+                    continue;
+                }
             }
 
-            // Also freeze graphs
-            let gdir = data_dir
-                .join("vectors")
-                .join(&branch_hex)
-                .join(format!("{}_graphs", cid.name));
-            if let Err(e) = backend.freeze_graphs_to_disk(&gdir) {
-                tracing::warn!(
-                    target: "strata::vector",
-                    collection = %cid.name,
-                    error = %e,
-                    "Failed to freeze vector graphs at shutdown"
-                );
+            // Graphs are only valid alongside the heap they were built on
+            let gdir = graph_dir(data_dir, cid.branch_id, &cid.name);
+            match backend.freeze_graphs_to_disk(&gdir) {
+                Ok(written) => files_written += written,
+                Err(e) => {
+                    tracing::warn!(
+                        target: "strata::vector",
+                        collection = %cid.name,
+                        error = %e,
+                        "Failed to freeze vector graphs"
+                    );
+                    first_error.get_or_insert(e);
+                }
             }
         }
+
+        match first_error {
+            Some(e) => Err(StrataError::storage(format!(
+                "Failed to checkpoint vectors: {}",
+                e
+            ))),
+            None => Ok(files_written),
+        }
     }
 
     /// Freeze the search index to disk for fast recovery on next open.
     fn freeze_search_index(&self) {
-        let data_dir = self.data_dir();
-        if data_dir.as_os_str().is_empty() {
-            return; // Ephemeral database — no persistence
-        }
-
-        if let Ok(index) = self.extension::<crate::search::InvertedIndex>() {
-            if let Err(e) = index.freeze_to_disk() {
-                tracing::warn!(
-                    target: "strata::search",
-                    error = %e,
-                    "Failed to freeze search index at shutdown"
-                );
-            }
+        if let Err(e) = self.checkpoint_search_index() {
+            tracing::warn!(
+                target: "strata::search",
+                error = %e,
+                "Failed to freeze search index at shutdown"
+            );
         }
     }
 
@@ -1436,6 +2585,7 @@ impl Database {
     /// 1. Stops accepting new transactions
     /// 2. Waits for pending operations to complete
     /// 3. Flushes WAL based on durability mode
+    /// 4. Writes a snapshot checkpoint if `checkpoint_on_shutdown` is set
     ///
     /// # Example
     ///
@@ -1477,6 +2627,11 @@ impl Database {
         // Freeze search index to disk for fast recovery
         self.freeze_search_index();
 
+        // Checkpoint so the next open skips replaying the WAL
+        if self.config.read().checkpoint_on_shutdown.unwrap_or(false) {
+            self.checkpoint()?;
+        }
+
         Ok(())
     }
 }
@@ -1495,11 +2650,20 @@ impl Drop for Database {
         // Final flush to persist any remaining data
         let _ = self.flush();
 
-        // Freeze vector heaps to mmap so lite KV records can recover
-        self.freeze_vector_heaps();
+        // Derived state from an unfinished background recovery is partial;
+        // the next open rebuilds it from the WAL.
+        if self.recovery_gate.is_complete() {
+            // Freeze vector heaps to mmap so lite KV records can recover
+            self.freeze_vector_heaps();
 
-        // Freeze search index to disk for fast recovery
-        self.freeze_search_index();
+            // Freeze search index to disk for fast recovery
+            self.freeze_search_index();
+
+            // Checkpoint unless shutdown() already did
+            if self.is_open() && self.config.read().checkpoint_on_shutdown.unwrap_or(false) {
+                let _ = self.checkpoint();
+            }
+        }
 
         // Remove from registry if we're disk-backed
         if self.persistence_mode == PersistenceMode::Disk && !self.data_dir.as_os_str().is_empty() {
@@ -1516,6 +2680,7 @@ impl Drop for Database {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::admin_log::AdminAction;
     use strata_concurrency::TransactionPayload;
     use strata_core::types::{Key, Namespace};
     use strata_core::value::Value;
@@ -1886,6 +3051,93 @@ mod tests {
         assert!(db.storage().get(&key).unwrap().is_none());
     }
 
+    #[test]
+    fn test_read_sees_consistent_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key_a = Key::new_kv(ns.clone(), "a");
+        let key_b = Key::new_kv(ns, "b");
+        db.transaction(branch_id, |txn| {
+            txn.put(key_a.clone(), Value::Int(1))?;
+            txn.put(key_b.clone(), Value::Int(1))
+        })
+        .unwrap();
+
+        let version_before = db.current_version();
+        let started_before = db.coordinator.metrics().total_started;
+
+        let (a, b) = db
+            .read(branch_id, |view| {
+                let a = view.get(&key_a)?;
+                // A commit in the middle of the read is not observed
+                db.transaction(branch_id, |txn| txn.put(key_b.clone(), Value::Int(2)))?;
+                let b = view.get(&key_b)?;
+                Ok((a, b))
+            })
+            .unwrap();
+        assert_eq!(a, Some(Value::Int(1)));
+        assert_eq!(b, Some(Value::Int(1)));
+
+        // Only the inner write transaction touched the commit machinery
+        assert_eq!(db.current_version(), version_before + 1);
+        assert_eq!(db.coordinator.metrics().total_started, started_before + 1);
+    }
+
+    #[test]
+    fn test_transaction_metrics_count_conflicts() {
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "counter");
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(0)))
+            .unwrap();
+
+        // Two transactions read the same key, then both write it
+        let mut t1 = db.begin_transaction(branch_id).unwrap();
+        let mut t2 = db.begin_transaction(branch_id).unwrap();
+        t1.get(&key).unwrap();
+        t2.get(&key).unwrap();
+        t1.put(key.clone(), Value::Int(1)).unwrap();
+        t2.put(key.clone(), Value::Int(2)).unwrap();
+        db.commit_transaction(&mut t1).unwrap();
+        assert!(db.commit_transaction(&mut t2).is_err());
+
+        // A closure error is an abort but not a conflict
+        let _ = db.transaction(branch_id, |_txn| -> StrataResult<()> {
+            Err(StrataError::invalid_input("boom".to_string()))
+        });
+
+        let metrics = db.transaction_metrics();
+        assert_eq!(metrics.total_started, 4);
+        assert_eq!(metrics.total_committed, 2);
+        assert_eq!(metrics.total_aborted, 2);
+        assert_eq!(metrics.total_conflicts, 1);
+        assert_eq!(metrics.conflict_rate(), 0.25);
+        assert_eq!(metrics.active_count, 0);
+
+        let snapshot = db.metrics_snapshot();
+        assert_eq!(snapshot.transactions.total_conflicts, 1);
+        assert_eq!(snapshot.current_version, db.current_version());
+        assert!(snapshot.durability.is_none());
+    }
+
+    #[test]
+    fn test_read_rejects_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "k");
+
+        let result = db.read(branch_id, |view| view.put(key.clone(), Value::Int(1)));
+        assert!(result.is_err());
+        assert!(db.storage().get(&key).unwrap().is_none());
+    }
+
     #[test]
     fn test_begin_and_commit_manual() {
         let temp_dir = TempDir::new().unwrap();
@@ -1896,7 +3148,7 @@ mod tests {
         let key = Key::new_kv(ns, "manual_key");
 
         // Manual transaction control
-        let mut txn = db.begin_transaction(branch_id);
+        let mut txn = db.begin_transaction(branch_id).unwrap();
         txn.put(key.clone(), Value::Int(123)).unwrap();
 
         // Commit manually
@@ -1907,6 +3159,78 @@ mod tests {
         assert_eq!(stored.value, Value::Int(123));
     }
 
+    #[test]
+    fn test_max_active_transactions() {
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.set_max_active_transactions(Some(1));
+
+        let mut held = db.begin_transaction(branch_id).unwrap();
+        assert!(matches!(
+            db.begin_transaction(branch_id),
+            Err(StrataError::TooManyTransactions {
+                active: 1,
+                limit: 1
+            })
+        ));
+        let err = db
+            .transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+            .unwrap_err();
+        assert!(matches!(err, StrataError::TooManyTransactions { .. }));
+
+        // Snapshot reads do not take a slot
+        db.read(branch_id, |view| view.get(&key)).unwrap();
+        assert_eq!(db.active_transactions(), 1);
+
+        db.commit_transaction(&mut held).unwrap();
+        db.end_transaction(held);
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(2)))
+            .unwrap();
+        assert_eq!(db.config().max_active_transactions, Some(1));
+    }
+
+    #[test]
+    fn test_pinned_snapshots_lists_storage_pins() {
+        let db = Database::cache().unwrap();
+        assert!(db.pinned_snapshots().is_empty());
+
+        let snapshot = db.pinned_snapshot();
+        let clone = snapshot.clone();
+        let pinned = db.pinned_snapshots();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].version, snapshot.version());
+        assert_eq!(pinned[0].count, 2);
+        assert!(db.active_snapshots().is_empty());
+
+        drop(snapshot);
+        drop(clone);
+        assert!(db.pinned_snapshots().is_empty());
+    }
+
+    #[test]
+    fn test_active_snapshots_lists_open_transactions() {
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+            .unwrap();
+        assert!(db.active_snapshots().is_empty());
+
+        let held = db.begin_transaction(branch_id).unwrap();
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(2)))
+            .unwrap();
+
+        let snapshots = db.active_snapshots();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].txn_id, held.txn_id);
+        assert_eq!(snapshots[0].branch_id, branch_id);
+        assert_eq!(snapshots[0].version, db.current_version() - 1);
+
+        db.end_transaction(held);
+        assert!(db.active_snapshots().is_empty());
+    }
+
     // ========================================================================
     // Retry Tests
     // ========================================================================
@@ -1954,6 +3278,43 @@ mod tests {
         assert_eq!(config.calculate_delay(5).as_millis(), 100); // Still capped
     }
 
+    #[test]
+    fn test_transaction_with_retry_retries_retryable_errors() {
+        use std::sync::atomic::AtomicUsize;
+
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let config = RetryConfig::new().with_max_retries(5).with_base_delay_ms(0);
+
+        // Each retryable kind fails the first attempt, then the retry succeeds
+        let retryable: [fn() -> StrataError; 3] = [
+            || StrataError::conflict("closure conflict"),
+            || StrataError::transaction_aborted("aborted"),
+            || StrataError::write_conflict(strata_core::EntityRef::kv(BranchId::new(), "k")),
+        ];
+        for error in retryable {
+            let attempts = AtomicUsize::new(0);
+            let result =
+                db.transaction_with_retry(branch_id, config.clone(), |_txn| {
+                    match attempts.fetch_add(1, Ordering::SeqCst) {
+                        0 => Err(error()),
+                        _ => Ok(7),
+                    }
+                });
+            assert_eq!(result.unwrap(), 7, "{:?} was not retried", error());
+            assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        }
+
+        // Other errors are returned from the first attempt
+        let attempts = AtomicUsize::new(0);
+        let result: StrataResult<()> = db.transaction_with_retry(branch_id, config, |_txn| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(StrataError::invalid_input("bad"))
+        });
+        assert!(matches!(result, Err(StrataError::InvalidInput { .. })));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
     // ========================================================================
     // Graceful Shutdown Tests
     // ========================================================================
@@ -2001,7 +3362,11 @@ mod tests {
 
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert!(matches!(err, StrataError::InvalidInput { .. }));
+        assert!(matches!(err, StrataError::DatabaseClosed));
+
+        // Snapshot reads are rejected the same way
+        let result = db.read(branch_id, |view| view.get(&key));
+        assert!(matches!(result, Err(StrataError::DatabaseClosed)));
     }
 
     #[test]
@@ -2058,6 +3423,208 @@ mod tests {
         assert!(!Arc::ptr_eq(&db1, &db2));
     }
 
+    #[test]
+    fn test_dedup_threshold_shares_large_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let cfg = StrataConfig {
+            dedup_threshold: Some(1024),
+            ..StrataConfig::default()
+        };
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let doc = Value::Bytes(vec![7; 4096]);
+        {
+            let db = Database::open_with_config(temp_dir.path(), cfg.clone()).unwrap();
+            for name in ["a", "b", "c"] {
+                db.transaction(branch_id, |txn| {
+                    txn.put(Key::new_kv(ns.clone(), name), doc.clone())
+                })
+                .unwrap();
+            }
+            assert_eq!(db.storage().shared_value_count(), 1);
+            db.shutdown().unwrap();
+        }
+
+        // Replayed versions share again
+        let db = Database::open_with_config(temp_dir.path(), cfg).unwrap();
+        assert_eq!(db.storage().shared_value_count(), 1);
+        let read = db.storage().get(&Key::new_kv(ns, "c")).unwrap().unwrap();
+        assert_eq!(read.value, doc);
+
+        db.set_dedup_threshold(None);
+        assert_eq!(db.config().dedup_threshold, None);
+    }
+
+    #[test]
+    fn test_tombstone_ttl_lets_gc_drop_deleted_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        let write_and_delete = |db: &Database| {
+            db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+                .unwrap();
+            db.transaction(branch_id, |txn| txn.delete(key.clone()))
+                .unwrap();
+            db.gc_versions_before(branch_id, db.current_version() + 1);
+        };
+
+        let db = Database::open(temp_dir.path().join("default")).unwrap();
+        write_and_delete(&db);
+        assert_eq!(db.storage().branch_entry_count(&branch_id), 1);
+
+        let cfg = StrataConfig {
+            tombstone_ttl_ms: Some(0),
+            ..StrataConfig::default()
+        };
+        let db = Database::open_with_config(temp_dir.path().join("ttl"), cfg).unwrap();
+        assert_eq!(db.storage().tombstone_ttl(), Some(Duration::ZERO));
+        write_and_delete(&db);
+        assert_eq!(db.storage().branch_entry_count(&branch_id), 0);
+
+        db.set_tombstone_ttl(None);
+        assert_eq!(db.config().tombstone_ttl_ms, None);
+        assert_eq!(db.storage().tombstone_ttl(), None);
+    }
+
+    #[test]
+    fn test_ephemeral_branch_skips_wal() {
+        let temp_dir = TempDir::new().unwrap();
+        let durable = BranchId::new();
+        let scratch = crate::primitives::branch::resolve_branch_name("scratch");
+        let durable_key = Key::new_kv(create_test_namespace(durable), "k");
+        let scratch_key = Key::new_kv(create_test_namespace(scratch), "k");
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            assert!(db.set_branch_ephemeral("default", true).is_err());
+            db.set_branch_ephemeral("scratch", true).unwrap();
+            assert!(db.is_branch_ephemeral(scratch));
+            assert!(!db.is_branch_ephemeral(durable));
+
+            for (branch_id, key) in [(durable, &durable_key), (scratch, &scratch_key)] {
+                db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+                    .unwrap();
+            }
+            // Visible while the database is open
+            assert!(db.storage().get(&scratch_key).unwrap().is_some());
+            db.shutdown().unwrap();
+        }
+
+        // Only the durable branch is replayed; the setting is persisted
+        let db = Database::open(temp_dir.path()).unwrap();
+        assert!(db.storage().get(&durable_key).unwrap().is_some());
+        assert!(db.storage().get(&scratch_key).unwrap().is_none());
+        assert!(db.is_branch_ephemeral(scratch));
+        assert_eq!(
+            db.config().ephemeral_branches,
+            Some(vec!["scratch".to_string()])
+        );
+
+        db.set_branch_ephemeral("scratch", false).unwrap();
+        assert!(!db.is_branch_ephemeral(scratch));
+        assert_eq!(db.config().ephemeral_branches, None);
+    }
+
+    #[test]
+    fn test_open_rejects_newer_segment_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+                .unwrap();
+            db.shutdown().unwrap();
+        }
+
+        let segment = temp_dir.path().join("wal").join("wal-000001.seg");
+        let original = std::fs::read(&segment).unwrap();
+        let mut newer = original.clone();
+        newer[4..8].copy_from_slice(&(strata_durability::SEGMENT_FORMAT_VERSION + 1).to_le_bytes());
+        std::fs::write(&segment, &newer).unwrap();
+
+        match Database::open(temp_dir.path()) {
+            Err(StrataError::UnsupportedFormatVersion {
+                found, supported, ..
+            }) => {
+                assert_eq!(found, strata_durability::SEGMENT_FORMAT_VERSION + 1);
+                assert_eq!(supported, strata_durability::SEGMENT_FORMAT_VERSION);
+            }
+            Err(e) => panic!("expected UnsupportedFormatVersion, got {}", e),
+            Ok(_) => panic!("expected UnsupportedFormatVersion, got a database"),
+        }
+        // The segment was left alone, so the data is still there
+        assert_eq!(std::fs::read(&segment).unwrap(), newer);
+        std::fs::write(&segment, &original).unwrap();
+        let db = Database::open(temp_dir.path()).unwrap();
+        assert!(db.storage().get(&key).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_open_skips_unreadable_wal_records_unless_strict() {
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+                .unwrap();
+            db.shutdown().unwrap();
+        }
+
+        // An intact record holding writes this version cannot decode, as a
+        // newer version would write for a primitive this one lacks
+        let future = WalRecord::new(99, *branch_id.as_bytes(), 0, vec![0xC1]);
+        let segment = temp_dir.path().join("wal").join("wal-000001.seg");
+        let mut bytes = std::fs::read(&segment).unwrap();
+        bytes.extend_from_slice(&future.to_bytes());
+        std::fs::write(&segment, &bytes).unwrap();
+
+        let strict = StrataConfig {
+            strict_recovery: Some(true),
+            ..Default::default()
+        };
+        assert!(Database::open_with_config(temp_dir.path(), strict).is_err());
+
+        let db = Database::open_with_config(temp_dir.path(), StrataConfig::default()).unwrap();
+        assert!(db.storage().get(&key).unwrap().is_some());
+        // New transactions are numbered after the skipped one
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(2)))
+            .unwrap();
+        db.shutdown().unwrap();
+        drop(db);
+        let db = Database::open(temp_dir.path()).unwrap();
+        assert_eq!(
+            db.storage().get(&key).unwrap().unwrap().value,
+            Value::Int(2)
+        );
+    }
+
+    #[test]
+    fn test_cache_with_limits_evicts_lru_kv() {
+        let db = Database::cache_with_limits(CacheLimits::new().max_entries(3)).unwrap();
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = |name: &str| Key::new_kv(ns.clone(), name);
+
+        for name in ["a", "b", "c"] {
+            db.transaction(branch_id, |txn| txn.put(key(name), Value::Int(1)))
+                .unwrap();
+        }
+        // Read "a" so "b" becomes the least recently used
+        db.transaction(branch_id, |txn| txn.get(&key("a")).map(|_| ()))
+            .unwrap();
+        db.transaction(branch_id, |txn| txn.put(key("d"), Value::Int(1)))
+            .unwrap();
+
+        // 4 entries > 3 drains to the low-water mark of 2
+        let present = |name: &str| db.storage().get(&key(name)).unwrap().is_some();
+        assert!(!present("b"));
+        assert!(!present("c"));
+        assert!(present("a"));
+        assert!(present("d"));
+        assert_eq!(db.storage().kv_entry_count(), 2);
+    }
+
     #[test]
     fn test_open_uses_registry() {
         let temp_dir = TempDir::new().unwrap();
@@ -2109,6 +3676,267 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_sync_barrier() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("sync")).unwrap();
+        // Long window: without sync the write would not be fsynced yet
+        db.set_flush_interval(60_000).unwrap();
+
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+            .unwrap();
+
+        let before = db.durability_counters().unwrap().sync_calls;
+        db.sync().unwrap();
+        assert!(db.durability_counters().unwrap().sync_calls > before);
+
+        assert!(Database::cache().unwrap().sync().is_err());
+    }
+
+    #[test]
+    fn test_probe_write_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("health")).unwrap();
+        let before = db.durability_counters().unwrap().sync_calls;
+
+        for _ in 0..3 {
+            assert!(db.probe_write_path().unwrap().wal_synced);
+        }
+        assert!(db.durability_counters().unwrap().sync_calls > before);
+
+        // Earlier probes are pruned, and the branch is never registered
+        let key = Key::new_kv(Namespace::for_branch(health_branch_id()), "_strata/health");
+        assert_eq!(db.get_history(&key, None, None).unwrap().len(), 1);
+        let index = crate::primitives::branch::BranchIndex::new(db.clone());
+        assert!(!index.exists(HEALTH_BRANCH_NAME).unwrap());
+
+        let cache = Database::cache().unwrap();
+        assert!(!cache.probe_write_path().unwrap().wal_synced);
+
+        db.shutdown().unwrap();
+        assert!(matches!(
+            db.probe_write_path(),
+            Err(StrataError::DatabaseClosed)
+        ));
+    }
+
+    #[test]
+    fn test_set_flush_interval() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("flush_interval")).unwrap();
+        assert_eq!(db.flush_interval(), Some(100));
+
+        assert!(db.set_flush_interval(0).is_err());
+        db.set_flush_interval(5).unwrap();
+        assert_eq!(db.flush_interval(), Some(5));
+
+        // A write pending under a long interval is synced soon after the
+        // interval shrinks, well before the old interval would have elapsed
+        db.set_flush_interval(60_000).unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+            .unwrap();
+        let before = db.durability_counters().unwrap().sync_calls;
+        db.set_flush_interval(5).unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while db.durability_counters().unwrap().sync_calls == before {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "background flush never synced"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        let cache = Database::cache().unwrap();
+        assert_eq!(cache.flush_interval(), None);
+        assert!(cache.set_flush_interval(50).is_err());
+    }
+
+    #[test]
+    fn test_embed_model_config_overrides_model_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("embed");
+        let cfg = StrataConfig {
+            embed_model: Some(EmbedModelConfig {
+                path: Some("models/custom".to_string()),
+                dimension: Some(768),
+            }),
+            ..Default::default()
+        };
+        let db = Database::open_with_config(&data_dir, cfg).unwrap();
+        assert_eq!(db.model_dir(), db.data_dir().join("models/custom"));
+        assert_eq!(db.embed_dimension(), 768);
+        assert!(!db.model_available());
+    }
+
+    #[test]
+    fn test_parallel_recovery_restores_all_branches() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("parallel");
+        let cfg = StrataConfig {
+            parallel_recovery: Some(true),
+            ..Default::default()
+        };
+        let keys: Vec<Key> = (0..4)
+            .map(|i| Key::new_kv(create_test_namespace(BranchId::new()), format!("k{}", i)))
+            .collect();
+        {
+            let db = Database::open_with_config(&db_path, cfg.clone()).unwrap();
+            for (i, key) in keys.iter().enumerate() {
+                db.transaction(key.namespace.branch_id, |txn| {
+                    txn.put(key.clone(), Value::Int(i as i64))?;
+                    Ok(())
+                })
+                .unwrap();
+            }
+        }
+
+        let db = Database::open_with_config(&db_path, cfg).unwrap();
+        for (i, key) in keys.iter().enumerate() {
+            let value = db.storage().get(key).unwrap().unwrap().value;
+            assert_eq!(value, Value::Int(i as i64));
+        }
+    }
+
+    #[test]
+    fn test_background_recovery_serves_reads_and_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("background");
+        let cfg = StrataConfig {
+            background_recovery: Some(true),
+            ..Default::default()
+        };
+        let keys: Vec<Key> = (0..4)
+            .map(|i| Key::new_kv(create_test_namespace(BranchId::new()), format!("k{}", i)))
+            .collect();
+        {
+            // Even an empty WAL is read after open returns
+            let db = Database::open_with_config(&db_path, cfg.clone()).unwrap();
+            db.wait_for_recovery();
+            assert!(!db.is_recovering());
+            for (i, key) in keys.iter().enumerate() {
+                db.transaction(key.namespace.branch_id, |txn| {
+                    txn.put(key.clone(), Value::Int(i as i64))?;
+                    Ok(())
+                })
+                .unwrap();
+            }
+        }
+
+        let db = Database::open_with_config(&db_path, cfg.clone()).unwrap();
+        // Reads wait for their branch, whether or not catch-up is done
+        for (i, key) in keys.iter().enumerate() {
+            let value = db
+                .read(key.namespace.branch_id, |view| view.get(key))
+                .unwrap()
+                .unwrap();
+            assert_eq!(value, Value::Int(i as i64));
+        }
+        db.wait_for_recovery();
+        assert!(!db.is_recovering());
+
+        db.transaction(keys[0].namespace.branch_id, |txn| {
+            txn.put(keys[0].clone(), Value::Int(100))?;
+            Ok(())
+        })
+        .unwrap();
+        db.shutdown().unwrap();
+        drop(db);
+
+        let db = Database::open_with_config(&db_path, cfg).unwrap();
+        let history = db.get_history(&keys[0], None, None).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].value, Value::Int(100));
+    }
+
+    #[test]
+    fn test_rebuild_keyword_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("rebuild");
+        let db = Database::open(&db_path).unwrap();
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+
+        // Raw transactional writes bypass the primitives, so nothing is indexed
+        db.transaction(branch_id, |txn| {
+            txn.put(
+                Key::new_kv(ns.clone(), "a"),
+                Value::String("quick brown fox".into()),
+            )?;
+            txn.put(
+                Key::new_kv(ns.clone(), "b"),
+                Value::String("lazy dog".into()),
+            )?;
+            txn.put(Key::new_kv(ns.clone(), "c"), Value::Bool(true))?;
+            Ok(())
+        })
+        .unwrap();
+        let index = db.extension::<crate::search::InvertedIndex>().unwrap();
+        let query = vec!["fox".to_string()];
+        assert!(index
+            .score_top_k(&query, &branch_id, 10, 0.9, 0.4)
+            .is_empty());
+
+        let stats = db.rebuild_keyword_index().unwrap();
+        assert_eq!(stats.documents, 2);
+        assert_eq!(stats.tokens, 5);
+        assert_eq!(index.score_top_k(&query, &branch_id, 10, 0.9, 0.4).len(), 1);
+        assert!(db_path.join("search").join("search.manifest").exists());
+
+        // Rebuilding again replaces the index rather than adding to it
+        assert_eq!(db.rebuild_keyword_index().unwrap(), stats);
+
+        db.disable_keyword_index().unwrap();
+        assert!(matches!(
+            db.rebuild_keyword_index(),
+            Err(StrataError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_open_with_background_scheduler_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let cfg = StrataConfig {
+            background_threads: Some(4),
+            background_queue_depth: Some(16),
+            ..Default::default()
+        };
+        let db = Database::open_with_config(temp_dir.path().join("sched"), cfg).unwrap();
+        let stats = db.scheduler().stats();
+        assert_eq!(stats.worker_count, 4);
+        assert_eq!(stats.max_queue_depth, 16);
+
+        let stats = Database::cache().unwrap().scheduler().stats();
+        assert_eq!(stats.worker_count, DEFAULT_BACKGROUND_THREADS);
+        assert_eq!(stats.max_queue_depth, DEFAULT_BACKGROUND_QUEUE_DEPTH);
+    }
+
+    #[test]
+    fn test_open_with_commit_lock_shards() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("sharded_locks");
+
+        let cfg = StrataConfig {
+            commit_lock_shards: Some(8),
+            ..Default::default()
+        };
+        let db = Database::open_with_config(&db_path, cfg).unwrap();
+        assert_eq!(db.commit_lock_shards(), Some(8));
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "k");
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+            .unwrap();
+        assert!(db.storage().get(&key).unwrap().is_some());
+
+        // Default config keeps per-branch locks
+        assert_eq!(Database::cache().unwrap().commit_lock_shards(), None);
+    }
+
     // ========================================================================
     // Checkpoint & Compaction Tests
     // ========================================================================
@@ -2166,6 +3994,337 @@ mod tests {
         assert!(manifest_path.exists());
     }
 
+    #[test]
+    fn test_suspend_auto_embed_is_not_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        db.set_auto_embed(true);
+
+        db.suspend_auto_embed();
+        assert!(!db.auto_embed_enabled());
+        assert!(db.config().auto_embed);
+
+        // Explicitly enabling again lifts the suspension
+        db.set_auto_embed(true);
+        assert!(db.auto_embed_enabled());
+    }
+
+    #[test]
+    fn test_metrics_snapshot_reports_wal_and_watermark() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(1))?;
+            Ok(())
+        })
+        .unwrap();
+        db.flush().unwrap();
+
+        let before = db.metrics_snapshot();
+        assert!(before.wal_size_bytes.unwrap() > 0);
+        assert_eq!(before.snapshot_watermark, None);
+
+        db.checkpoint().unwrap();
+        let after = db.metrics_snapshot();
+        assert_eq!(after.snapshot_watermark, Some(db.current_version()));
+    }
+
+    #[test]
+    fn test_checkpoint_on_shutdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let write = |db: &Database| {
+            let branch_id = BranchId::new();
+            let key = Key::new_kv(create_test_namespace(branch_id), "k");
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), Value::Int(1))?;
+                Ok(())
+            })
+            .unwrap();
+        };
+
+        // Off by default
+        let db = Database::open(temp_dir.path().join("default")).unwrap();
+        write(&db);
+        db.shutdown().unwrap();
+        assert_eq!(db.metrics_snapshot().snapshot_watermark, None);
+
+        let cfg = StrataConfig {
+            checkpoint_on_shutdown: Some(true),
+            ..Default::default()
+        };
+        let db = Database::open_with_config(temp_dir.path().join("enabled"), cfg).unwrap();
+        write(&db);
+        db.shutdown().unwrap();
+        assert_eq!(
+            db.metrics_snapshot().snapshot_watermark,
+            Some(db.current_version())
+        );
+    }
+
+    #[test]
+    fn test_list_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        assert!(db.list_snapshots().unwrap().is_empty());
+        assert_eq!(db.snapshot_info(1).unwrap(), None);
+
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        for i in 0..2 {
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), Value::Int(i))?;
+                Ok(())
+            })
+            .unwrap();
+            db.checkpoint().unwrap();
+        }
+
+        let snapshots = db.list_snapshots().unwrap();
+        assert!(!snapshots.is_empty());
+        let latest = snapshots.last().unwrap();
+        assert!(latest.current);
+        assert!(latest.size_bytes > 0);
+        assert!(latest.created_at > 0);
+        assert_eq!(latest.watermark_txn, db.current_version());
+        assert_eq!(snapshots.iter().filter(|s| s.current).count(), 1);
+        assert_eq!(
+            db.snapshot_info(latest.snapshot_id).unwrap().as_ref(),
+            Some(latest)
+        );
+
+        assert!(Database::cache()
+            .unwrap()
+            .list_snapshots()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_compact_prunes_old_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let checkpoints = |db: &Database| {
+            let branch_id = BranchId::new();
+            let key = Key::new_kv(create_test_namespace(branch_id), "k");
+            for i in 0..3 {
+                db.transaction(branch_id, |txn| {
+                    txn.put(key.clone(), Value::Int(i))?;
+                    Ok(())
+                })
+                .unwrap();
+                db.checkpoint().unwrap();
+            }
+            db.compact().unwrap();
+            db.list_snapshots().unwrap()
+        };
+
+        // Unset keeps every snapshot
+        let db = Database::open(temp_dir.path().join("all")).unwrap();
+        assert_eq!(checkpoints(&db).len(), 3);
+
+        let cfg = StrataConfig {
+            keep_snapshots: Some(2),
+            ..Default::default()
+        };
+        let db = Database::open_with_config(temp_dir.path().join("two"), cfg).unwrap();
+        let snapshots = checkpoints(&db);
+        let ids: Vec<u64> = snapshots.iter().map(|s| s.snapshot_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert!(snapshots[1].current);
+    }
+
+    #[test]
+    fn test_binary_keys_survive_checkpoint_and_reopen() {
+        use strata_durability::codec::IdentityCodec;
+        use strata_durability::{primitive_tags, DiskSnapshotReader, SnapshotSerializer};
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let user_key = vec![0x00, 0xFF, 0xC3, 0x28];
+        let keys = [
+            Key::new_kv(ns.clone(), &user_key),
+            Key::new_state(ns.clone(), &user_key),
+            Key::new(ns, TypeTag::Json, user_key.clone()),
+        ];
+
+        let snapshot_id = {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| {
+                for (i, key) in keys.iter().enumerate() {
+                    txn.put(key.clone(), Value::Int(i as i64))?;
+                }
+                Ok(())
+            })
+            .unwrap();
+            db.checkpoint().unwrap();
+            db.list_snapshots().unwrap()[0].snapshot_id
+        };
+
+        // Every section keeps the key bytes unchanged
+        let snapshot = DiskSnapshotReader::new(Box::new(IdentityCodec))
+            .load(&strata_durability::snapshot_path(
+                &db_path.join("snapshots"),
+                snapshot_id,
+            ))
+            .unwrap();
+        let serializer = SnapshotSerializer::new(Box::new(IdentityCodec));
+        let section = |tag| &snapshot.find_section(tag).unwrap().data;
+        let kv = serializer
+            .deserialize_kv(section(primitive_tags::KV))
+            .unwrap();
+        assert_eq!(kv[0].key, user_key);
+        let states = serializer
+            .deserialize_states(section(primitive_tags::STATE))
+            .unwrap();
+        assert_eq!(states[0].name, user_key);
+        let docs = serializer
+            .deserialize_json(section(primitive_tags::JSON))
+            .unwrap();
+        assert_eq!(docs[0].doc_id, user_key);
+
+        let db = Database::open(&db_path).unwrap();
+        for (i, key) in keys.iter().enumerate() {
+            let value = db.storage().get(key).unwrap().unwrap().value;
+            assert_eq!(value, Value::Int(i as i64));
+        }
+    }
+
+    #[test]
+    fn test_restore_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        let put = |db: &Database, i: i64| {
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), Value::Int(i))?;
+                Ok(())
+            })
+            .unwrap();
+        };
+
+        let db = Database::open(&db_path).unwrap();
+        let record = |db: &Arc<Database>, target: &str| {
+            crate::admin_log::record(db, AdminAction::Checkpoint, Some(target), None, None)
+                .unwrap();
+        };
+        put(&db, 1);
+        record(&db, "kept");
+        db.checkpoint().unwrap();
+        put(&db, 2);
+        record(&db, "discarded");
+        db.checkpoint().unwrap();
+        put(&db, 3);
+        let first = db.list_snapshots().unwrap()[0].clone();
+
+        // Refused while a handle is open
+        assert!(Database::restore_snapshot(&db_path, first.snapshot_id).is_err());
+        drop(db);
+
+        assert!(Database::restore_snapshot(&db_path, 99).is_err());
+        let restore = Database::restore_snapshot(&db_path, first.snapshot_id).unwrap();
+        assert_eq!(restore.watermark_txn, first.watermark_txn);
+        assert_eq!(restore.txns_kept, 2);
+        assert_eq!(restore.txns_discarded, 3);
+        assert!(restore.moved_to.join("wal").exists());
+
+        let db = Database::open(&db_path).unwrap();
+        let value = db.read(branch_id, |view| view.get(&key)).unwrap();
+        assert_eq!(value, Some(Value::Int(1)));
+
+        // The restore follows the admin log entries the snapshot kept
+        let admin = crate::admin_log::entries(&db, 10).unwrap();
+        assert_eq!(admin.len(), 2);
+        assert_eq!(admin[0].action, AdminAction::Restore);
+        assert_eq!(admin[0].timestamp, restore.restored_at);
+        assert_eq!(admin[1].target.as_deref(), Some("kept"));
+        assert!(crate::admin_log::verify(&db).unwrap().is_valid);
+        let snapshots = db.list_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert!(snapshots[0].current);
+
+        // New commits continue after the restored history
+        put(&db, 4);
+        drop(db);
+        let db = Database::open(&db_path).unwrap();
+        let value = db.read(branch_id, |view| view.get(&key)).unwrap();
+        assert_eq!(value, Some(Value::Int(4)));
+    }
+
+    #[test]
+    fn test_restore_snapshot_after_compaction_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = Database::open(&db_path).unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(1))?;
+            Ok(())
+        })
+        .unwrap();
+        db.checkpoint().unwrap();
+        let snapshot_id = db.list_snapshots().unwrap()[0].snapshot_id;
+        drop(db);
+
+        // Simulate compaction having removed the first segment
+        let wal_dir = db_path.join("wal");
+        for entry in std::fs::read_dir(&wal_dir).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            std::fs::rename(&path, wal_dir.join(name.replace("000001", "000002"))).unwrap();
+        }
+        let err = Database::restore_snapshot(&db_path, snapshot_id).unwrap_err();
+        assert!(err.to_string().contains("compacted"), "{}", err);
+    }
+
+    #[test]
+    fn test_compaction_needed_and_compact_info() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = Database::open(&db_path).unwrap();
+        assert!(!db.compaction_needed().unwrap());
+
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(1))?;
+            Ok(())
+        })
+        .unwrap();
+        db.checkpoint().unwrap();
+        // Everything is still in the active segment
+        assert!(!db.compaction_needed().unwrap());
+        drop(db);
+
+        // Start a new segment so segment 1 is closed on reopen
+        strata_durability::format::WalSegment::create(&db_path.join("wal"), 2, [0u8; 16])
+            .unwrap()
+            .close()
+            .unwrap();
+        let db = Database::open(&db_path).unwrap();
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(2))?;
+            Ok(())
+        })
+        .unwrap();
+        db.checkpoint().unwrap();
+        assert!(db.compaction_needed().unwrap());
+
+        let info = db.compact().unwrap();
+        assert_eq!(info.wal_segments_removed, 1);
+        assert!(info.reclaimed_bytes > 0);
+        assert!(!db.compaction_needed().unwrap());
+
+        let cache = Database::cache().unwrap();
+        assert!(!cache.compaction_needed().unwrap());
+        assert_eq!(cache.compact().unwrap().wal_segments_removed, 0);
+    }
+
     #[test]
     fn test_checkpoint_then_compact() {
         let temp_dir = TempDir::new().unwrap();
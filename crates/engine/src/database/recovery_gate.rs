@@ -0,0 +1,215 @@
+//! Readiness tracking for background WAL recovery
+//!
+//! With `background_recovery` enabled, `Database::open` returns before the
+//! WAL has been read. Reading and the per-branch replay run on the
+//! background scheduler, and this gate tells foreground operations what has
+//! been restored so far:
+//!
+//! 1. the WAL is read, which names the branches still to replay; until then
+//!    every branch counts as pending;
+//! 2. each pending branch is marked replayed as its writes land in storage;
+//! 3. fork links are marked restored once the branch metadata is readable;
+//! 4. the gate completes after recovery participants (search, vector) ran.
+//!
+//! Operations wait on the narrowest condition that makes their result
+//! correct. That is a whole branch at the finest: a read of one key waits
+//! for every write to its branch. A gate created with [`RecoveryGate::open`]
+//! is already complete, so the normal open path never blocks.
+
+use parking_lot::{Condvar, Mutex};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use strata_core::types::BranchId;
+
+#[derive(Default)]
+struct GateState {
+    /// The WAL has been read and `pending` lists every branch to replay
+    wal_read: bool,
+    /// Branches whose WAL writes are not in storage yet
+    pending: HashSet<BranchId>,
+    /// Fork bases have been re-registered with storage
+    forks_restored: bool,
+    /// All of recovery has finished
+    complete: bool,
+}
+
+/// Tracks the progress of background recovery.
+pub(crate) struct RecoveryGate {
+    /// Lock-free fast path once recovery has finished
+    complete: AtomicBool,
+    state: Mutex<GateState>,
+    changed: Condvar,
+}
+
+impl RecoveryGate {
+    /// A gate with nothing left to recover.
+    pub(crate) fn open() -> Self {
+        RecoveryGate {
+            complete: AtomicBool::new(true),
+            state: Mutex::new(GateState {
+                wal_read: true,
+                pending: HashSet::new(),
+                forks_restored: true,
+                complete: true,
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// A gate waiting for the WAL to be read and every branch replayed.
+    pub(crate) fn reading() -> Self {
+        RecoveryGate {
+            complete: AtomicBool::new(false),
+            state: Mutex::new(GateState::default()),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Whether recovery has finished.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.complete.load(Ordering::Acquire)
+    }
+
+    /// Block until `branch` has been replayed into storage.
+    pub(crate) fn wait_branch(&self, branch: &BranchId) {
+        self.wait_until(|state| state.wal_read && !state.pending.contains(branch));
+    }
+
+    /// Block until fork bases have been restored.
+    pub(crate) fn wait_forks(&self) {
+        self.wait_until(|state| state.forks_restored);
+    }
+
+    /// Block until every branch has been replayed into storage.
+    pub(crate) fn wait_replayed(&self) {
+        self.wait_until(|state| state.wal_read && state.pending.is_empty());
+    }
+
+    /// Block until recovery has finished.
+    pub(crate) fn wait_complete(&self) {
+        self.wait_until(|state| state.complete);
+    }
+
+    /// Mark the WAL as read, leaving `branches` to be replayed.
+    pub(crate) fn wal_read(&self, branches: impl IntoIterator<Item = BranchId>) {
+        self.update(|state| {
+            state.pending.extend(branches);
+            state.wal_read = true;
+        });
+    }
+
+    /// Mark `branch` as replayed into storage.
+    pub(crate) fn branch_replayed(&self, branch: &BranchId) {
+        self.update(|state| {
+            state.pending.remove(branch);
+        });
+    }
+
+    /// Mark fork bases as restored.
+    pub(crate) fn forks_restored(&self) {
+        self.update(|state| state.forks_restored = true);
+    }
+
+    /// Mark recovery as finished, releasing every waiter.
+    ///
+    /// Also called when background recovery stops early, so that nothing
+    /// stays blocked on a branch that will never be replayed.
+    pub(crate) fn finish(&self) {
+        self.update(|state| {
+            state.wal_read = true;
+            state.pending.clear();
+            state.forks_restored = true;
+            state.complete = true;
+            // Set under the lock, before waiters wake, so a released
+            // waiter never sees `is_complete()` still false
+            self.complete.store(true, Ordering::Release);
+        });
+    }
+
+    fn wait_until(&self, ready: impl Fn(&GateState) -> bool) {
+        if self.is_complete() {
+            return;
+        }
+        let mut state = self.state.lock();
+        while !ready(&state) {
+            self.changed.wait(&mut state);
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut GateState)) {
+        let mut state = self.state.lock();
+        change(&mut state);
+        self.changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_open_gate_never_blocks() {
+        let gate = RecoveryGate::open();
+        assert!(gate.is_complete());
+        gate.wait_branch(&BranchId::new());
+        gate.wait_forks();
+        gate.wait_replayed();
+        gate.wait_complete();
+    }
+
+    #[test]
+    fn test_waiters_released_in_stages() {
+        let a = BranchId::new();
+        let b = BranchId::new();
+        let gate = Arc::new(RecoveryGate::reading());
+        assert!(!gate.is_complete());
+
+        // Before the WAL is read, even an unknown branch waits
+        let unknown = BranchId::new();
+        let early = {
+            let gate = Arc::clone(&gate);
+            std::thread::spawn(move || gate.wait_branch(&unknown))
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!early.is_finished());
+        gate.wal_read([a, b]);
+        early.join().unwrap();
+
+        // A branch that was never pending is ready immediately
+        gate.wait_branch(&BranchId::new());
+
+        let waiter = {
+            let gate = Arc::clone(&gate);
+            std::thread::spawn(move || gate.wait_branch(&b))
+        };
+        gate.branch_replayed(&a);
+        gate.wait_branch(&a);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!waiter.is_finished());
+
+        gate.branch_replayed(&b);
+        waiter.join().unwrap();
+        gate.wait_replayed();
+        assert!(!gate.is_complete());
+
+        gate.finish();
+        gate.wait_forks();
+        gate.wait_complete();
+        assert!(gate.is_complete());
+    }
+
+    #[test]
+    fn test_finish_releases_pending_branches() {
+        let a = BranchId::new();
+        let gate = Arc::new(RecoveryGate::reading());
+        gate.wal_read([a]);
+        let waiter = {
+            let gate = Arc::clone(&gate);
+            std::thread::spawn(move || gate.wait_branch(&a))
+        };
+        gate.finish();
+        waiter.join().unwrap();
+    }
+}
@@ -0,0 +1,261 @@
+//! Restore a closed database to an earlier checkpoint
+//!
+//! Recovery rebuilds state by replaying the WAL, so the state captured by a
+//! snapshot is the WAL prefix up to the snapshot's watermark. Restoring
+//! rewrites the WAL to that prefix. Nothing is deleted: the original WAL,
+//! snapshots newer than the restored one and the search and vector graph
+//! caches are moved under `restored/` in the data directory. The rewritten
+//! WAL ends with an admin log entry recording the restore.
+
+use super::{Database, OPEN_DATABASES};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use strata_concurrency::TransactionPayload;
+use strata_core::{StrataError, StrataResult};
+use strata_durability::codec::IdentityCodec;
+use strata_durability::format::WalRecord;
+use strata_durability::wal::{DurabilityMode, WalConfig, WalReader, WalWriter};
+use strata_durability::{ManifestError, ManifestManager};
+use tracing::info;
+
+/// Outcome of [`Database::restore_snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestoreInfo {
+    /// Snapshot the database was restored to
+    pub snapshot_id: u64,
+    /// Version covered by that snapshot
+    pub watermark_txn: u64,
+    /// Transactions kept in the rewritten WAL
+    pub txns_kept: usize,
+    /// Transactions committed after the watermark, now moved aside
+    pub txns_discarded: usize,
+    /// Directory holding everything the restore moved aside
+    pub moved_to: PathBuf,
+    /// When the restore ran (microseconds since epoch)
+    pub restored_at: u64,
+}
+
+impl Database {
+    /// Roll a closed database back to the state of checkpoint `snapshot_id`.
+    ///
+    /// Transactions committed after the snapshot's watermark are removed
+    /// from the WAL. The original WAL, newer snapshots and derived index
+    /// caches are moved to `restored/<timestamp>-snap-<id>/`, and the restore
+    /// is recorded in the admin log.
+    ///
+    /// # Errors
+    ///
+    /// - the database is open in this process or locked by another one
+    /// - the snapshot does not exist
+    /// - the WAL leading up to the snapshot was removed by `compact()`, so
+    ///   the snapshot's state can no longer be rebuilt
+    pub fn restore_snapshot<P: AsRef<Path>>(
+        path: P,
+        snapshot_id: u64,
+    ) -> StrataResult<RestoreInfo> {
+        let data_dir = path.as_ref().canonicalize().map_err(StrataError::from)?;
+
+        // Hold the registry lock so this process cannot open the database
+        // while it is being rewritten.
+        let registry = OPEN_DATABASES.lock();
+        if registry
+            .get(&data_dir)
+            .is_some_and(|weak| weak.strong_count() > 0)
+        {
+            return Err(StrataError::invalid_input(format!(
+                "database at '{}' is open; close every handle before restoring",
+                data_dir.display()
+            )));
+        }
+        let lock_file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(data_dir.join(".lock"))
+            .map_err(|e| StrataError::storage(format!("failed to open lock file: {}", e)))?;
+        fs2::FileExt::try_lock_exclusive(&lock_file).map_err(|_| {
+            StrataError::storage(format!(
+                "database at '{}' is already in use by another process",
+                data_dir.display()
+            ))
+        })?;
+
+        let snapshots_dir = data_dir.join("snapshots");
+        let snapshot_file = strata_durability::snapshot_path(&snapshots_dir, snapshot_id);
+        if !snapshot_file.exists() {
+            return Err(StrataError::invalid_input(format!(
+                "snapshot {} does not exist",
+                snapshot_id
+            )));
+        }
+        let watermark_txn = strata_durability::read_snapshot_header(&snapshot_file)?.watermark_txn;
+
+        // Split the WAL at the watermark
+        let wal_dir = data_dir.join("wal");
+        let reader = WalReader::new(Box::new(IdentityCodec));
+        let wal_error = |e| StrataError::storage(format!("WAL read failed: {}", e));
+        let mut segments = if wal_dir.exists() {
+            reader.list_segments(&wal_dir).map_err(wal_error)?
+        } else {
+            Vec::new()
+        };
+        segments.sort_unstable();
+        if let Some(&first) = segments.first().filter(|&&first| first != 1) {
+            return Err(StrataError::invalid_input(format!(
+                "WAL segments before {} were compacted; the state at snapshot {} cannot be rebuilt",
+                first, snapshot_id
+            )));
+        }
+        let records = if segments.is_empty() {
+            Vec::new()
+        } else {
+            reader.read_all(&wal_dir).map_err(wal_error)?.records
+        };
+        let mut kept = Vec::new();
+        let mut txns_discarded = 0;
+        let admin_meta_key = crate::admin_log::meta_key();
+        let mut admin_meta = None;
+        for record in records {
+            let payload = TransactionPayload::from_bytes(&record.writeset).map_err(|e| {
+                StrataError::storage(format!(
+                    "Failed to decode transaction payload for txn {}: {}",
+                    record.txn_id, e
+                ))
+            })?;
+            if payload.version <= watermark_txn {
+                if payload.deletes.contains(&admin_meta_key) {
+                    admin_meta = None;
+                }
+                if let Some((_, meta)) = payload
+                    .puts
+                    .into_iter()
+                    .find(|(key, _)| *key == admin_meta_key)
+                {
+                    admin_meta = Some(meta);
+                }
+                kept.push(record);
+            } else {
+                txns_discarded += 1;
+            }
+        }
+
+        let restored_at = strata_durability::now_micros();
+        let moved_to = data_dir
+            .join("restored")
+            .join(format!("{}-snap-{:06}", restored_at, snapshot_id));
+        let restore = RestoreInfo {
+            snapshot_id,
+            watermark_txn,
+            txns_kept: kept.len(),
+            txns_discarded,
+            moved_to,
+            restored_at,
+        };
+
+        // The admin log entries after the snapshot are discarded with
+        // everything else, so the restore is recorded as one more
+        // transaction after the kept prefix.
+        let admin_entry = TransactionPayload {
+            version: watermark_txn + 1,
+            puts: crate::admin_log::restore_writes(admin_meta.as_ref(), &restore)?,
+            deletes: Vec::new(),
+        };
+        let admin_record = WalRecord::new(
+            kept.iter().map(|record| record.txn_id).max().unwrap_or(0) + 1,
+            *crate::admin_log::admin_branch_id().as_bytes(),
+            restored_at,
+            admin_entry.to_bytes(),
+        );
+
+        // Write the kept prefix to a staging directory first, so a failure
+        // here leaves the database untouched.
+        let staging = data_dir.join("wal.restore");
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+        let mut writer = WalWriter::new(
+            staging.clone(),
+            [0u8; 16],
+            DurabilityMode::Always,
+            WalConfig::default(),
+            Box::new(IdentityCodec),
+        )?;
+        for record in kept.iter().chain([&admin_record]) {
+            writer.append(record)?;
+        }
+        writer.close()?;
+
+        let moved_to = &restore.moved_to;
+        std::fs::create_dir_all(moved_to)?;
+        if wal_dir.exists() {
+            std::fs::rename(&wal_dir, moved_to.join("wal"))?;
+        }
+        std::fs::rename(&staging, &wal_dir)?;
+
+        // Snapshots past the restore point describe discarded history
+        for (id, file) in strata_durability::list_snapshots(&snapshots_dir)? {
+            if id > snapshot_id {
+                let aside = moved_to.join("snapshots");
+                std::fs::create_dir_all(&aside)?;
+                std::fs::rename(&file, aside.join(file.file_name().unwrap_or_default()))?;
+            }
+        }
+
+        // The search index cache and prebuilt vector graphs would bring back
+        // discarded entries; both are rebuilt on open. Vector heaps stay,
+        // since lite vector records keep their embeddings only there.
+        let search_dir = data_dir.join("search");
+        if search_dir.exists() {
+            std::fs::rename(&search_dir, moved_to.join("search"))?;
+        }
+        move_vector_graphs(&data_dir.join("vectors"), &moved_to.join("vectors"))?;
+
+        let manifest_path = data_dir.join("MANIFEST");
+        if ManifestManager::exists(&manifest_path) {
+            ManifestManager::load(manifest_path)
+                .and_then(|mut manifest| {
+                    manifest.set_snapshot_watermark(snapshot_id, watermark_txn)
+                })
+                .map_err(|e: ManifestError| {
+                    StrataError::internal(format!("manifest update failed: {}", e))
+                })?;
+        }
+
+        info!(
+            target: "strata::db",
+            snapshot_id,
+            watermark_txn,
+            txns_kept = restore.txns_kept,
+            txns_discarded,
+            "Database restored to snapshot"
+        );
+
+        drop(lock_file);
+        drop(registry);
+        Ok(restore)
+    }
+}
+
+/// Move every `{collection}_graphs` directory under `vectors/` aside.
+fn move_vector_graphs(vectors_dir: &Path, aside: &Path) -> std::io::Result<()> {
+    if !vectors_dir.exists() {
+        return Ok(());
+    }
+    for branch in std::fs::read_dir(vectors_dir)? {
+        let branch = branch?;
+        if !branch.file_type()?.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(branch.path())? {
+            let entry = entry?;
+            let name = entry.file_name();
+            if entry.file_type()?.is_dir() && name.to_string_lossy().ends_with("_graphs") {
+                let target = aside.join(branch.file_name());
+                std::fs::create_dir_all(&target)?;
+                std::fs::rename(entry.path(), target.join(name))?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -31,9 +31,16 @@ pub mod recovery;
 pub mod transaction;
 pub mod transaction_ops; // TransactionOps Trait Definition
 
-pub use background::{BackgroundScheduler, BackpressureError, SchedulerStats, TaskPriority};
-pub use coordinator::{TransactionCoordinator, TransactionMetrics};
-pub use database::{Database, ModelConfig, RetryConfig, StrataConfig};
+pub use background::{
+    BackgroundScheduler, BackpressureError, SchedulerStats, TaskPriority,
+    DEFAULT_BACKGROUND_QUEUE_DEPTH, DEFAULT_BACKGROUND_THREADS, STARVATION_LIMIT,
+};
+pub use coordinator::{SnapshotDebugInfo, TransactionCoordinator, TransactionMetrics};
+pub use database::{
+    health_branch_id, Database, EmbedModelConfig, HealthProbe, JsonLimitsConfig, MetricsSnapshot,
+    ModelConfig, ReadOnlySnapshot, RestoreInfo, RetryConfig, SnapshotInfo, StrataConfig,
+    HEALTH_BRANCH_NAME,
+};
 pub use instrumentation::PerfTrace;
 pub use recovery::{
     diff_views, recover_all_participants, register_recovery_participant, BranchDiff, BranchError,
@@ -41,18 +48,26 @@ pub use recovery::{
 };
 pub use strata_durability::wal::DurabilityMode;
 pub use strata_durability::WalCounters;
+pub use strata_durability::{
+    BRANCHBUNDLE_FORMAT_VERSION, SEGMENT_FORMAT_VERSION, SNAPSHOT_FORMAT_VERSION,
+};
+pub use strata_storage::{CacheLimits, PinnedSnapshotInfo};
 // Note: Use strata_core::PrimitiveType for DiffEntry.primitive field
 pub use strata_concurrency::TransactionContext;
 pub use transaction::{Transaction, TransactionPool, MAX_POOL_SIZE};
 pub use transaction_ops::TransactionOps;
 
+pub mod admin_log;
 pub mod branch_ops;
 pub mod bundle;
 pub mod primitives;
 pub mod search;
+pub(crate) mod util;
 
 // Re-export search types at crate root for convenience
-pub use search::{SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse, SearchStats};
+pub use search::{
+    DedupMode, SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse, SearchStats,
+};
 
 // Re-export search recovery registration
 pub use search::register_search_recovery;
@@ -93,6 +108,7 @@ pub use primitives::{
     // Index
     InvertedIndex,
     JsonDoc,
+    JsonDocInfo,
     JsonHandle,
     JsonScalar,
     JsonStore,
@@ -131,6 +147,8 @@ pub use primitives::{
     VectorMatchWithSource,
     VectorRecord,
     VectorResult,
+    VectorScan,
+    VectorStorage,
     VectorStore,
     VectorStoreExt,
 };
@@ -140,8 +158,8 @@ pub use bundle::{BundleInfo, ExportInfo, ImportInfo};
 
 // Re-export branch_ops types at crate root
 pub use branch_ops::{
-    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff,
+    BranchDiffEntry, BranchDiffResult, BranchSize, ConflictEntry, DeleteInfo, DiffSummary,
+    ForkInfo, MergeInfo, MergeStrategy, Resolution, SpaceDiff,
 };
 
 #[cfg(feature = "perf-trace")]
@@ -7,6 +7,7 @@
 //!
 //! - `kv()`, `events()`, `state()`, `json()`, `vectors()` - primitive handles
 //! - `transaction()` - execute atomic cross-primitive transactions
+//! - `all()`, `entries()` - walk every branch and its data for maintenance
 //!
 //! ## Usage
 //!
@@ -33,9 +34,9 @@ use std::sync::Arc;
 use strata_concurrency::TransactionContext;
 use strata_core::contract::{Timestamp, Version, Versioned};
 use strata_core::primitives::json::{JsonPath, JsonValue};
-use strata_core::types::BranchId;
+use strata_core::types::{BranchId, Key, TypeTag};
 use strata_core::value::Value;
-use strata_core::StrataResult;
+use strata_core::{StrataResult, VersionedValue};
 
 // ============================================================================
 // BranchHandle
@@ -81,6 +82,19 @@ impl BranchHandle {
         Self { db, branch_id }
     }
 
+    /// Handles for every branch that holds data in storage
+    ///
+    /// The branch list is taken when this is called; branches created
+    /// afterwards are not yielded. Includes the nil branch, which holds the
+    /// default branch's data together with the branch index.
+    pub fn all(db: &Arc<Database>) -> impl Iterator<Item = BranchHandle> {
+        let db = Arc::clone(db);
+        db.storage()
+            .branch_ids()
+            .into_iter()
+            .map(move |branch_id| BranchHandle::new(Arc::clone(&db), branch_id))
+    }
+
     /// Get the branch ID
     pub fn branch_id(&self) -> &BranchId {
         &self.branch_id
@@ -136,6 +150,29 @@ impl BranchHandle {
     {
         self.db.transaction(self.branch_id, f)
     }
+
+    // === Maintenance ===
+
+    /// Every live entry in this branch, across all spaces and primitives
+    ///
+    /// Read from a single storage snapshot, so the result is a consistent
+    /// view of this branch. Separate calls, including calls on other
+    /// branches, may observe different points in time. Entries inherited
+    /// from a fork source are included; internal bookkeeping keys are too.
+    pub fn entries(&self) -> Vec<(Key, VersionedValue)> {
+        self.db
+            .storage()
+            .create_snapshot()
+            .list_branch(&self.branch_id)
+    }
+
+    /// Like [`entries`](Self::entries), limited to one primitive type
+    pub fn entries_of_type(&self, type_tag: TypeTag) -> Vec<(Key, VersionedValue)> {
+        self.db
+            .storage()
+            .create_snapshot()
+            .list_by_type(&self.branch_id, type_tag)
+    }
 }
 
 // ============================================================================
@@ -309,4 +346,30 @@ mod tests {
         assert_clone_send_sync::<StateHandle>();
         assert_clone_send_sync::<JsonHandle>();
     }
+
+    #[test]
+    fn test_all_branches_and_entries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path()).unwrap();
+        let a = BranchId::new();
+        let b = BranchId::new();
+        BranchHandle::new(db.clone(), a)
+            .kv()
+            .put("k1", Value::Int(1))
+            .unwrap();
+        let handle_b = BranchHandle::new(db.clone(), b);
+        handle_b.kv().put("k2", Value::Int(2)).unwrap();
+        handle_b.state().set("cell", Value::Int(3)).unwrap();
+
+        let ids: Vec<BranchId> = BranchHandle::all(&db).map(|h| *h.branch_id()).collect();
+        assert!(ids.contains(&a));
+        assert!(ids.contains(&b));
+
+        let entries = handle_b.entries();
+        assert_eq!(entries.len(), 2);
+        let kv = handle_b.entries_of_type(TypeTag::KV);
+        assert_eq!(kv.len(), 1);
+        assert_eq!(kv[0].0.user_key_string().as_deref(), Some("k2"));
+        assert_eq!(kv[0].1.value, Value::Int(2));
+    }
 }
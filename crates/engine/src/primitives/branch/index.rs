@@ -21,7 +21,7 @@ use crate::database::Database;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use strata_core::contract::{Timestamp, Version, Versioned};
-use strata_core::types::{BranchId, Key, Namespace, TypeTag};
+use strata_core::types::{BranchId, Key, Namespace, TypeTag, BRANCH_INDEX_PREFIX};
 use strata_core::value::Value;
 use strata_core::StrataError;
 use strata_core::StrataResult;
@@ -56,10 +56,29 @@ pub fn resolve_branch_name(name: &str) -> BranchId {
 ///
 /// BranchIndex is a global index (not scoped to any particular branch),
 /// so we use a nil UUID as a sentinel value.
-fn global_branch_id() -> BranchId {
+pub(crate) fn global_branch_id() -> BranchId {
     BranchId::from_bytes([0; 16])
 }
 
+/// Reject names that resolve to the branches reserved for health probes and
+/// the admin log, and names that would be mistaken for branch index entries
+fn check_not_reserved(branch_id: &str) -> StrataResult<()> {
+    let id = resolve_branch_name(branch_id);
+    if id == crate::database::health_branch_id() || id == crate::admin_log::admin_branch_id() {
+        return Err(StrataError::invalid_input(format!(
+            "Branch name '{}' is reserved",
+            branch_id
+        )));
+    }
+    if branch_id.contains(BRANCH_INDEX_PREFIX) {
+        return Err(StrataError::invalid_input(format!(
+            "Branch name '{}' must not contain reserved marker '{}'",
+            branch_id, BRANCH_INDEX_PREFIX
+        )));
+    }
+    Ok(())
+}
+
 /// Get the global namespace for BranchIndex operations
 fn global_namespace() -> Namespace {
     Namespace::for_branch(global_branch_id())
@@ -98,6 +117,11 @@ pub struct BranchMetadata {
     pub branch_id: String,
     /// Parent branch name if forked (post-MVP)
     pub parent_branch: Option<String>,
+    /// Parent version a copy-on-write fork reads through to
+    ///
+    /// `None` for regular branches and eager forks, which own all their data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_base_version: Option<u64>,
 
     /// Current status
     pub status: BranchStatus,
@@ -127,6 +151,7 @@ impl BranchMetadata {
             name: name.to_string(),
             branch_id: branch_id.to_string(),
             parent_branch: None,
+            fork_base_version: None,
             status: BranchStatus::Active,
             created_at: now,
             updated_at: now,
@@ -232,6 +257,7 @@ impl BranchIndex {
     /// ## Errors
     /// - `InvalidInput` if branch already exists
     pub fn create_branch(&self, branch_id: &str) -> StrataResult<Versioned<BranchMetadata>> {
+        check_not_reserved(branch_id)?;
         self.db.transaction(global_branch_id(), |txn| {
             let key = self.key_for(branch_id);
 
@@ -251,6 +277,74 @@ impl BranchIndex {
         })
     }
 
+    /// Create an eagerly copied fork of `parent`
+    ///
+    /// Like [`create_branch`](Self::create_branch), but records the parent
+    /// link so fork lineage can be reconstructed. No fork base version is
+    /// set, so the branch never reads through to its parent.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if branch already exists
+    pub(crate) fn create_fork(
+        &self,
+        branch_id: &str,
+        parent: &str,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        check_not_reserved(branch_id)?;
+        self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(branch_id);
+
+            if txn.get(&key)?.is_some() {
+                return Err(StrataError::invalid_input(format!(
+                    "Branch '{}' already exists",
+                    branch_id
+                )));
+            }
+
+            let mut branch_meta = BranchMetadata::new(branch_id);
+            branch_meta.parent_branch = Some(parent.to_string());
+            txn.put(key, to_stored_value(&branch_meta)?)?;
+
+            info!(target: "strata::branch", %branch_id, %parent, "Forked branch created");
+            Ok(branch_meta.into_versioned())
+        })
+    }
+
+    /// Create a copy-on-write fork of `parent`
+    ///
+    /// Records the parent link and fork base version in the branch metadata
+    /// so the read-through can be re-established on recovery. The caller is
+    /// responsible for registering the fork base with storage.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if branch already exists
+    pub(crate) fn create_cow_fork(
+        &self,
+        branch_id: &str,
+        parent: &str,
+        base_version: u64,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        check_not_reserved(branch_id)?;
+        self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(branch_id);
+
+            if txn.get(&key)?.is_some() {
+                return Err(StrataError::invalid_input(format!(
+                    "Branch '{}' already exists",
+                    branch_id
+                )));
+            }
+
+            let mut branch_meta = BranchMetadata::new(branch_id);
+            branch_meta.parent_branch = Some(parent.to_string());
+            branch_meta.fork_base_version = Some(base_version);
+            txn.put(key, to_stored_value(&branch_meta)?)?;
+
+            info!(target: "strata::branch", %branch_id, %parent, base_version, "Copy-on-write branch created");
+            Ok(branch_meta.into_versioned())
+        })
+    }
+
     /// Get branch metadata
     ///
     /// ## Returns
@@ -289,7 +383,7 @@ impl BranchIndex {
                 .filter_map(|(k, _)| {
                     let key_str = String::from_utf8(k.user_key.clone()).ok()?;
                     // Filter out any index keys (legacy data)
-                    if key_str.contains("__idx_") {
+                    if key_str.contains(BRANCH_INDEX_PREFIX) {
                         None
                     } else {
                         Some(key_str)
@@ -421,6 +515,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_create_reserved_branch_fails() {
+        let (_temp, _db, ri) = setup();
+
+        let health_uuid = uuid::Uuid::from_bytes(*crate::database::health_branch_id().as_bytes());
+        for name in [
+            crate::database::HEALTH_BRANCH_NAME.to_string(),
+            health_uuid.to_string(),
+            "__idx_status__Active__x".to_string(),
+            "my__idx_branch".to_string(),
+        ] {
+            assert!(matches!(
+                ri.create_branch(&name),
+                Err(StrataError::InvalidInput { .. })
+            ));
+        }
+        assert!(ri.list_branches().unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_branch() {
         let (_temp, _db, ri) = setup();
@@ -8,4 +8,5 @@ mod handle;
 mod index;
 
 pub use handle::{BranchHandle, EventHandle, JsonHandle, KvHandle, StateHandle};
+pub(crate) use index::global_branch_id;
 pub use index::{resolve_branch_name, BranchIndex, BranchMetadata, BranchStatus};
@@ -48,7 +48,7 @@ use strata_core::StrataError;
 use strata_core::StrataResult;
 
 // Re-export Event from core
-pub use strata_core::primitives::Event;
+pub use strata_core::primitives::{ChainVerification, Event};
 
 /// Hash version constants
 pub(crate) const HASH_VERSION_SHA256: u8 = 1; // SHA-256
@@ -142,8 +142,12 @@ pub fn compute_event_hash(
     // Timestamp (8 bytes, little-endian)
     hasher.update(timestamp.to_le_bytes());
 
-    // Payload as canonical JSON with length prefix
-    let payload_bytes = serde_json::to_vec(payload).unwrap_or_default();
+    // Payload as canonical JSON with length prefix. Converting to a
+    // serde_json::Value first sorts object keys, so the bytes do not depend
+    // on the iteration order of the payload's maps.
+    let payload_bytes = serde_json::to_value(payload)
+        .and_then(|json| serde_json::to_vec(&json))
+        .unwrap_or_default();
     hasher.update((payload_bytes.len() as u32).to_le_bytes());
     hasher.update(&payload_bytes);
 
@@ -164,6 +168,8 @@ pub enum EventLogValidationError {
     EmptyEventType,
     /// Event type cannot exceed maximum length
     EventTypeTooLong(usize),
+    /// Event type cannot contain NUL, which terminates type index keys
+    EventTypeContainsNul,
 }
 
 impl std::fmt::Display for EventLogValidationError {
@@ -173,6 +179,7 @@ impl std::fmt::Display for EventLogValidationError {
             Self::PayloadContainsNonFiniteFloat => write!(f, "payload contains NaN or Infinity"),
             Self::EmptyEventType => write!(f, "event_type cannot be empty"),
             Self::EventTypeTooLong(len) => write!(f, "event_type exceeds maximum length ({})", len),
+            Self::EventTypeContainsNul => write!(f, "event_type must not contain NUL bytes"),
         }
     }
 }
@@ -188,6 +195,9 @@ fn validate_event_type(event_type: &str) -> std::result::Result<(), EventLogVali
     if event_type.len() > MAX_EVENT_TYPE_LENGTH {
         return Err(EventLogValidationError::EventTypeTooLong(event_type.len()));
     }
+    if event_type.contains('\0') {
+        return Err(EventLogValidationError::EventTypeContainsNul);
+    }
     Ok(())
 }
 
@@ -233,6 +243,60 @@ fn from_stored_value<T: for<'de> Deserialize<'de>>(
     }
 }
 
+/// Build the event appended after log metadata `meta`, and the writes that
+/// store it: the event, its per-type index key and the updated metadata.
+///
+/// Shared with restores, which append to the admin log while the database
+/// is closed.
+pub(crate) fn append_writes(
+    ns: &Namespace,
+    meta: Option<&Value>,
+    event_type: &str,
+    payload: Value,
+    timestamp: u64,
+) -> StrataResult<(Event, Vec<(Key, Value)>)> {
+    let mut meta: EventLogMeta = meta
+        .and_then(|v| from_stored_value(v).ok())
+        .unwrap_or_default();
+
+    // Compute event hash using current hash version
+    let sequence = meta.next_sequence;
+    let hash = compute_event_hash(sequence, event_type, &payload, timestamp, &meta.head_hash);
+    let event = Event {
+        sequence,
+        event_type: event_type.to_string(),
+        payload,
+        timestamp,
+        prev_hash: meta.head_hash,
+        hash,
+    };
+
+    match meta.streams.get_mut(event_type) {
+        Some(stream_meta) => stream_meta.update(sequence, timestamp),
+        None => {
+            meta.streams
+                .insert(event_type.to_string(), StreamMeta::new(sequence, timestamp));
+        }
+    }
+    meta.next_sequence = sequence + 1;
+    meta.head_hash = hash;
+
+    let writes = vec![
+        (
+            Key::new_event(ns.clone(), sequence),
+            to_stored_value(&event)?,
+        ),
+        // Per-type index key for efficient get_by_type lookups (#972)
+        (
+            Key::new_event_type_idx(ns.clone(), event_type, sequence),
+            Value::Null,
+        ),
+        // Metadata update (CAS semantics through the transaction)
+        (Key::new_event_meta(ns.clone()), to_stored_value(&meta)?),
+    ];
+    Ok((event, writes))
+}
+
 /// Immutable append-only event stream
 ///
 /// DESIGN: Single-writer-ordered per branch.
@@ -309,6 +373,22 @@ impl EventLog {
         event_type: &str,
         payload: Value,
     ) -> StrataResult<Version> {
+        self.append_event(branch_id, space, event_type, payload)
+            .map(|event| Version::Sequence(event.sequence))
+    }
+
+    /// Append a new event to the log and return it as stored
+    ///
+    /// Same as [`append`](Self::append), but returns the constructed
+    /// [`Event`] with its assigned sequence, timestamp and chain hashes, so
+    /// callers can reference the event without reading it back.
+    pub fn append_event(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+        payload: Value,
+    ) -> StrataResult<Event> {
         // Validate inputs before entering transaction
         validate_event_type(event_type).map_err(|e| StrataError::invalid_input(e.to_string()))?;
         validate_payload(&payload).map_err(|e| StrataError::invalid_input(e.to_string()))?;
@@ -328,80 +408,35 @@ impl EventLog {
         let result = self
             .db
             .transaction_with_retry(*branch_id, retry_config, |txn| {
-                // Read current metadata (or default)
-                let meta_key = Key::new_event_meta(ns.clone());
-                let mut meta: EventLogMeta = match txn.get(&meta_key)? {
-                    Some(v) => from_stored_value(&v).unwrap_or_else(|_| EventLogMeta::default()),
-                    None => EventLogMeta::default(),
-                };
-
-                // Compute event hash using current hash version
-                let sequence = meta.next_sequence;
-                let timestamp = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_micros() as u64;
-
-                let hash = compute_event_hash(
-                    sequence,
+                let meta = txn.get(&Key::new_event_meta(ns.clone()))?;
+                let (event, writes) = append_writes(
+                    &ns,
+                    meta.as_ref(),
                     &event_type_owned,
-                    &payload,
-                    timestamp,
-                    &meta.head_hash,
-                );
-
-                // Build event
-                let event = Event {
-                    sequence,
-                    event_type: event_type_owned.clone(),
-                    payload: payload.clone(),
-                    timestamp,
-                    prev_hash: meta.head_hash,
-                    hash,
-                };
-
-                // Write event
-                let event_key = Key::new_event(ns.clone(), sequence);
-                txn.put(event_key, to_stored_value(&event)?)?;
-
-                // Write per-type index key for efficient get_by_type lookups (#972)
-                let idx_key = Key::new_event_type_idx(ns.clone(), &event_type_owned, sequence);
-                txn.put(idx_key, Value::Null)?;
-
-                // Update stream metadata
-                match meta.streams.get_mut(&event_type_owned) {
-                    Some(stream_meta) => stream_meta.update(sequence, timestamp),
-                    None => {
-                        meta.streams.insert(
-                            event_type_owned.clone(),
-                            StreamMeta::new(sequence, timestamp),
-                        );
-                    }
+                    payload.clone(),
+                    txn.now().as_micros(),
+                )?;
+                for (key, value) in writes {
+                    txn.put(key, value)?;
                 }
 
-                // Update metadata (CAS semantics through transaction)
-                meta.next_sequence = sequence + 1;
-                meta.head_hash = hash;
-                txn.put(meta_key, to_stored_value(&meta)?)?;
-
-                Ok(Version::Sequence(sequence))
+                Ok(event)
             })?;
 
-        // Update inverted index (zero overhead when disabled)
+        // Update inverted index (zero overhead when disabled). The admin log
+        // is not user data and stays out of keyword search.
         let idx = self.db.extension::<crate::search::InvertedIndex>()?;
-        if idx.is_enabled() {
+        if idx.is_enabled() && *branch_id != crate::admin_log::admin_branch_id() {
             let text = format!(
                 "{} {}",
                 event_type,
                 serde_json::to_string(&payload).unwrap_or_default()
             );
-            if let Version::Sequence(seq) = result {
-                let entity_ref = crate::search::EntityRef::Event {
-                    branch_id: *branch_id,
-                    sequence: seq,
-                };
-                idx.index_document(&entity_ref, &text, None);
-            }
+            let entity_ref = crate::search::EntityRef::Event {
+                branch_id: *branch_id,
+                sequence: result.sequence,
+            };
+            idx.index_document(&entity_ref, &text, None);
         }
 
         Ok(result)
@@ -474,10 +509,7 @@ impl EventLog {
                 for &i in &valid_indices {
                     let (event_type, payload) = &entries[i];
                     let sequence = meta.next_sequence;
-                    let timestamp = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_micros() as u64;
+                    let timestamp = txn.now().as_micros();
 
                     let hash = compute_event_hash(
                         sequence,
@@ -604,6 +636,105 @@ impl EventLog {
         })
     }
 
+    // ========== Integrity ==========
+
+    /// Walk the log and recompute its hash chain.
+    ///
+    /// Every event must exist, link to the hash of the event before it and
+    /// hash to its stored value, and the last hash must match the log head.
+    /// The walk runs over a single snapshot, so concurrent appends do not
+    /// affect the result. The first break is reported with the expected and
+    /// actual hash where it is a mismatch.
+    pub fn verify_chain(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+    ) -> StrataResult<ChainVerification> {
+        self.db.read(*branch_id, |view| {
+            let ns = self.namespace_for(branch_id, space);
+            let meta: EventLogMeta = match view.get(&Key::new_event_meta(ns.clone()))? {
+                Some(v) => {
+                    from_stored_value(&v).map_err(|e| StrataError::serialization(e.to_string()))?
+                }
+                None => return Ok(ChainVerification::valid(0)),
+            };
+            if meta.hash_version != HASH_VERSION_SHA256 {
+                return Err(StrataError::invalid_input(format!(
+                    "Event log uses hash version {}, which cannot be verified",
+                    meta.hash_version
+                )));
+            }
+
+            let length = meta.next_sequence;
+            let mut prev_hash = [0u8; 32];
+            for seq in 0..length {
+                let event: Event = match view.get(&Key::new_event(ns.clone(), seq))? {
+                    Some(v) => match from_stored_value(&v) {
+                        Ok(event) => event,
+                        Err(e) => {
+                            return Ok(ChainVerification::invalid(
+                                length,
+                                seq,
+                                format!("event {} is unreadable: {}", seq, e),
+                            ))
+                        }
+                    },
+                    None => {
+                        return Ok(ChainVerification::invalid(
+                            length,
+                            seq,
+                            format!("event {} is missing", seq),
+                        ))
+                    }
+                };
+                if event.sequence != seq {
+                    return Ok(ChainVerification::invalid(
+                        length,
+                        seq,
+                        format!("event {} records sequence {}", seq, event.sequence),
+                    ));
+                }
+                if event.prev_hash != prev_hash {
+                    return Ok(ChainVerification::hash_mismatch(
+                        length,
+                        seq,
+                        format!("event {} does not link to the previous event", seq),
+                        prev_hash,
+                        event.prev_hash,
+                    ));
+                }
+                let hash = compute_event_hash(
+                    seq,
+                    &event.event_type,
+                    &event.payload,
+                    event.timestamp,
+                    &prev_hash,
+                );
+                if event.hash != hash {
+                    return Ok(ChainVerification::hash_mismatch(
+                        length,
+                        seq,
+                        format!("event {} does not match its hash", seq),
+                        hash,
+                        event.hash,
+                    ));
+                }
+                prev_hash = hash;
+            }
+
+            if meta.head_hash != prev_hash {
+                return Ok(ChainVerification::hash_mismatch(
+                    length,
+                    length.saturating_sub(1),
+                    "log head does not match the last event",
+                    prev_hash,
+                    meta.head_hash,
+                ));
+            }
+            Ok(ChainVerification::valid(length))
+        })
+    }
+
     // ========== Query by Type ==========
 
     /// Read events filtered by type
@@ -773,10 +904,7 @@ impl EventLogExt for TransactionContext {
 
         // Compute event hash using current hash version
         let sequence = meta.next_sequence;
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_micros() as u64;
+        let timestamp = self.now().as_micros();
 
         let hash = compute_event_hash(sequence, event_type, &payload, timestamp, &meta.head_hash);
 
@@ -985,6 +1113,23 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("length"));
     }
 
+    #[test]
+    fn test_validation_rejects_nul_in_event_type() {
+        let (_temp, _db, log) = setup();
+        let branch_id = BranchId::new();
+
+        log.append(&branch_id, "default", "a", empty_payload())
+            .unwrap();
+        let result = log.append(&branch_id, "default", "a\0b", empty_payload());
+        assert!(result.unwrap_err().to_string().contains("NUL"));
+        assert_eq!(
+            log.get_by_type(&branch_id, "default", "a", None, None)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
     #[test]
     fn test_validation_accepts_valid_object_payload() {
         let (_temp, _db, log) = setup();
@@ -1048,6 +1193,57 @@ mod tests {
         assert_eq!(event2.value.prev_hash, event1.value.hash);
     }
 
+    #[test]
+    fn test_verify_chain() {
+        let (_temp, db, log) = setup();
+        let branch_id = BranchId::new();
+
+        assert_eq!(
+            log.verify_chain(&branch_id, "default").unwrap(),
+            ChainVerification::valid(0)
+        );
+
+        log.append(&branch_id, "default", "a", int_payload(1))
+            .unwrap();
+        log.batch_append(
+            &branch_id,
+            "default",
+            vec![
+                ("b".to_string(), int_payload(2)),
+                ("a".to_string(), int_payload(3)),
+            ],
+        )
+        .unwrap();
+        db.transaction(branch_id, |txn| {
+            txn.event_append("c", int_payload(4)).map(drop)
+        })
+        .unwrap();
+        assert_eq!(
+            log.verify_chain(&branch_id, "default").unwrap(),
+            ChainVerification::valid(4)
+        );
+
+        // Rewrite the payload of event 2 behind the log's back
+        let mut tampered = log.get(&branch_id, "default", 2).unwrap().unwrap().value;
+        let stored_hash = tampered.hash;
+        tampered.payload = int_payload(99);
+        let key = Key::new_event(log.namespace_for(&branch_id, "default"), 2);
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), to_stored_value(&tampered)?)
+        })
+        .unwrap();
+
+        let cv = log.verify_chain(&branch_id, "default").unwrap();
+        assert!(!cv.is_valid);
+        assert_eq!(cv.length, 4);
+        assert_eq!(cv.first_invalid, Some(2));
+        assert_eq!(cv.actual_hash, Some(stored_hash));
+        assert_ne!(cv.expected_hash, Some(stored_hash));
+
+        // Other spaces are verified on their own
+        assert!(log.verify_chain(&branch_id, "other").unwrap().is_valid);
+    }
+
     #[test]
     fn test_append_with_payload() {
         let (_temp, _db, log) = setup();
@@ -1153,6 +1349,18 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    #[test]
+    fn test_sha256_hash_ignores_field_order() {
+        let fields: Vec<(String, Value)> =
+            (0..32).map(|i| (format!("f{i}"), Value::Int(i))).collect();
+        let a = Value::Object(fields.iter().cloned().collect());
+        let b = Value::Object(fields.into_iter().rev().collect());
+        assert_eq!(
+            compute_event_hash(1, "test", &a, 1234567890, &[0u8; 32]),
+            compute_event_hash(1, "test", &b, 1234567890, &[0u8; 32])
+        );
+    }
+
     #[test]
     fn test_sha256_hash_differs_for_different_inputs() {
         let base = compute_event_hash(42, "test", &empty_payload(), 1234567890, &[0u8; 32]);
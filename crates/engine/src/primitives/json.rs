@@ -104,6 +104,32 @@ pub struct JsonListResult {
     pub next_cursor: Option<String>,
 }
 
+/// Per-document metadata returned by
+/// [`JsonStore::list_with_metadata`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonDocInfo {
+    /// Document ID (user-provided string key)
+    pub doc_id: String,
+    /// Document version (increments on any change)
+    pub version: u64,
+    /// Size of the stored document in bytes
+    pub size_bytes: u64,
+}
+
+impl JsonDocInfo {
+    fn from_stored(doc: JsonDoc, stored: &Value) -> Self {
+        let size_bytes = match stored {
+            Value::Bytes(bytes) => bytes.len() as u64,
+            _ => 0,
+        };
+        JsonDocInfo {
+            doc_id: doc.id,
+            version: doc.version,
+            size_bytes,
+        }
+    }
+}
+
 impl JsonDoc {
     /// Create a new document with initial value
     ///
@@ -135,6 +161,9 @@ impl JsonDoc {
     }
 }
 
+/// One page of `(doc_id, stored value)` pairs and the cursor after it
+type ListPage = (Vec<(String, Value)>, Option<String>);
+
 /// JSON document storage primitive
 ///
 /// STATELESS FACADE over Database - all state lives in unified ShardedStore.
@@ -250,7 +279,9 @@ impl JsonStore {
         value: JsonValue,
     ) -> StrataResult<Version> {
         // Validate document limits (Issue #440)
-        value.validate().map_err(limit_error_to_error)?;
+        value
+            .validate_with(&self.db.json_limits())
+            .map_err(limit_error_to_error)?;
 
         let key = self.key_for(branch_id, space, doc_id);
         let doc = JsonDoc::new(doc_id, value.clone());
@@ -298,7 +329,8 @@ impl JsonStore {
         path: &JsonPath,
     ) -> StrataResult<Option<JsonValue>> {
         // Validate path limits (Issue #440)
-        path.validate().map_err(limit_error_to_error)?;
+        path.validate_with(&self.db.json_limits())
+            .map_err(limit_error_to_error)?;
 
         let key = self.key_for(branch_id, space, doc_id);
 
@@ -388,8 +420,9 @@ impl JsonStore {
         path: &JsonPath,
         value: JsonValue,
     ) -> StrataResult<Version> {
-        path.validate().map_err(limit_error_to_error)?;
-        value.validate().map_err(limit_error_to_error)?;
+        let limits = self.db.json_limits();
+        path.validate_with(&limits).map_err(limit_error_to_error)?;
+        value.validate_with(&limits).map_err(limit_error_to_error)?;
 
         let key = self.key_for(branch_id, space, doc_id);
 
@@ -516,8 +549,9 @@ impl JsonStore {
         value: JsonValue,
     ) -> StrataResult<Version> {
         // Validate path and value limits (Issue #440)
-        path.validate().map_err(limit_error_to_error)?;
-        value.validate().map_err(limit_error_to_error)?;
+        let limits = self.db.json_limits();
+        path.validate_with(&limits).map_err(limit_error_to_error)?;
+        value.validate_with(&limits).map_err(limit_error_to_error)?;
 
         let key = self.key_for(branch_id, space, doc_id);
 
@@ -572,7 +606,8 @@ impl JsonStore {
         path: &JsonPath,
     ) -> StrataResult<Version> {
         // Validate path limits (Issue #440)
-        path.validate().map_err(limit_error_to_error)?;
+        path.validate_with(&self.db.json_limits())
+            .map_err(limit_error_to_error)?;
 
         let key = self.key_for(branch_id, space, doc_id);
 
@@ -676,15 +711,55 @@ impl JsonStore {
         cursor: Option<&str>,
         limit: usize,
     ) -> StrataResult<JsonListResult> {
+        let (docs, next_cursor) = self.list_page(branch_id, space, prefix, cursor, limit)?;
+        Ok(JsonListResult {
+            doc_ids: docs.into_iter().map(|(doc_id, _)| doc_id).collect(),
+            next_cursor,
+        })
+    }
+
+    /// List documents with their version and stored size.
+    ///
+    /// Same filtering and pagination as [`list`](Self::list), but each
+    /// document is decoded during the scan to read its version, so it costs
+    /// more than listing IDs alone.
+    pub fn list_with_metadata(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        prefix: Option<&str>,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> StrataResult<(Vec<JsonDocInfo>, Option<String>)> {
+        let (docs, next_cursor) = self.list_page(branch_id, space, prefix, cursor, limit)?;
+        let docs = docs
+            .into_iter()
+            .map(|(_, stored)| {
+                let doc = Self::deserialize_doc(&stored)?;
+                Ok(JsonDocInfo::from_stored(doc, &stored))
+            })
+            .collect::<StrataResult<Vec<_>>>()?;
+        Ok((docs, next_cursor))
+    }
+
+    /// One page of `(doc_id, stored value)` pairs and the next cursor.
+    fn list_page(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        prefix: Option<&str>,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> StrataResult<ListPage> {
         let ns = self.namespace_for(branch_id, space);
         // Narrow scan at storage level: if prefix is given, only scan matching keys
         let scan_prefix = Key::new_json(ns, prefix.unwrap_or(""));
 
         self.db.transaction(*branch_id, |txn| {
-            let mut doc_ids = Vec::with_capacity(limit + 1);
+            let mut docs = Vec::with_capacity(limit + 1);
             let mut past_cursor = cursor.is_none();
 
-            for (key, _value) in txn.scan_prefix(&scan_prefix)? {
+            for (key, value) in txn.scan_prefix(&scan_prefix)? {
                 // Extract doc_id from Key instead of deserializing the full Value
                 let doc_id = match key.user_key_string() {
                     Some(id) => id,
@@ -699,26 +774,23 @@ impl JsonStore {
                     continue;
                 }
 
-                doc_ids.push(doc_id);
+                docs.push((doc_id, value));
 
                 // Collect limit + 1 to detect if there are more
-                if doc_ids.len() > limit {
+                if docs.len() > limit {
                     break;
                 }
             }
 
             // If we have more than limit, pop the last and use it as cursor
-            let next_cursor = if doc_ids.len() > limit {
-                doc_ids.pop();
-                doc_ids.last().cloned()
+            let next_cursor = if docs.len() > limit {
+                docs.pop();
+                docs.last().map(|(doc_id, _)| doc_id.clone())
             } else {
                 None
             };
 
-            Ok(JsonListResult {
-                doc_ids,
-                next_cursor,
-            })
+            Ok((docs, next_cursor))
         })
     }
     // ========== Time-Travel API ==========
@@ -735,7 +807,8 @@ impl JsonStore {
         path: &JsonPath,
         as_of_ts: u64,
     ) -> StrataResult<Option<JsonValue>> {
-        path.validate().map_err(limit_error_to_error)?;
+        path.validate_with(&self.db.json_limits())
+            .map_err(limit_error_to_error)?;
 
         let key = self.key_for(branch_id, space, doc_id);
         let result = self.db.get_at_timestamp(&key, as_of_ts)?;
@@ -756,22 +829,34 @@ impl JsonStore {
         prefix: Option<&str>,
         as_of_ts: u64,
     ) -> StrataResult<Vec<String>> {
+        Ok(self
+            .list_at_with_metadata(branch_id, space, prefix, as_of_ts)?
+            .into_iter()
+            .map(|info| info.doc_id)
+            .collect())
+    }
+
+    /// List documents as of a past timestamp, with the version and stored
+    /// size each had then.
+    pub fn list_at_with_metadata(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        prefix: Option<&str>,
+        as_of_ts: u64,
+    ) -> StrataResult<Vec<JsonDocInfo>> {
         let ns = self.namespace_for(branch_id, space);
         let scan_prefix = Key::new_json_prefix(ns);
         let results = self.db.scan_prefix_at_timestamp(&scan_prefix, as_of_ts)?;
-        let mut doc_ids = Vec::new();
+        let mut docs = Vec::new();
         for (_, vv) in results {
             if let Ok(doc) = Self::deserialize_doc(&vv.value) {
-                if let Some(p) = prefix {
-                    if doc.id.starts_with(p) {
-                        doc_ids.push(doc.id);
-                    }
-                } else {
-                    doc_ids.push(doc.id);
+                if prefix.map_or(true, |p| doc.id.starts_with(p)) {
+                    docs.push(JsonDocInfo::from_stored(doc, &vv.value));
                 }
             }
         }
-        Ok(doc_ids)
+        Ok(docs)
     }
 }
 
@@ -1969,6 +2054,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_list_with_metadata() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        let root = JsonPath::root();
+
+        store
+            .create(&branch_id, "default", "a", JsonValue::from("x"))
+            .unwrap();
+        store
+            .create(&branch_id, "default", "b", JsonValue::from("short"))
+            .unwrap();
+        store
+            .set(
+                &branch_id,
+                "default",
+                "b",
+                &root,
+                JsonValue::from("x".repeat(100)),
+            )
+            .unwrap();
+        store
+            .create(&branch_id, "default", "c", JsonValue::from(1i64))
+            .unwrap();
+
+        let (page, cursor) = store
+            .list_with_metadata(&branch_id, "default", None, None, 2)
+            .unwrap();
+        assert_eq!(
+            page.iter().map(|d| d.doc_id.as_str()).collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+        assert_eq!(page[0].version, 1);
+        assert_eq!(page[1].version, 2);
+        assert!(page[1].size_bytes > page[0].size_bytes + 90);
+        assert_eq!(cursor.as_deref(), Some("b"));
+
+        // Pages line up with the keys-only listing
+        let ids = store
+            .list(&branch_id, "default", None, cursor.as_deref(), 2)
+            .unwrap();
+        let (rest, end) = store
+            .list_with_metadata(&branch_id, "default", None, cursor.as_deref(), 2)
+            .unwrap();
+        assert_eq!(ids.doc_ids, ["c"]);
+        assert_eq!(rest[0].doc_id, "c");
+        assert_eq!(end, None);
+
+        let now = u64::MAX;
+        let at = store
+            .list_at_with_metadata(&branch_id, "default", Some("b"), now)
+            .unwrap();
+        assert_eq!(at, vec![page[1].clone()]);
+    }
+
     // ========== Time-Travel Boundary Tests ==========
 
     #[test]
@@ -22,7 +22,7 @@
 //! - `delete(branch_id, key)` - Delete a key
 //! - `list(branch_id, prefix)` - List keys with prefix
 
-use crate::database::Database;
+use crate::database::{Database, RetryConfig};
 use crate::primitives::extensions::KVStoreExt;
 use std::sync::Arc;
 use strata_concurrency::TransactionContext;
@@ -31,6 +31,22 @@ use strata_core::value::Value;
 use strata_core::StrataResult;
 use strata_core::{Version, VersionedHistory};
 
+/// Candidates scored per requested hit when a search has a time window;
+/// doubled while the window leaves fewer than k hits.
+const WINDOW_OVERFETCH: usize = 4;
+
+/// Text the inverted index holds for a KV value
+///
+/// Strings are indexed as-is and other structured values as JSON; nulls,
+/// booleans and bytes have no text.
+fn index_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Null | Value::Bool(_) | Value::Bytes(_) => None,
+        other => serde_json::to_string(other).ok(),
+    }
+}
+
 /// General-purpose key-value store primitive
 ///
 /// Stateless facade over Database - all state lives in storage.
@@ -64,7 +80,7 @@ impl KVStore {
     }
 
     /// Build key for KV operation
-    fn key_for(&self, branch_id: &BranchId, space: &str, user_key: &str) -> Key {
+    fn key_for(&self, branch_id: &BranchId, space: &str, user_key: impl AsRef<[u8]>) -> Key {
         Key::new_kv(self.namespace_for(branch_id, space), user_key)
     }
 
@@ -139,11 +155,7 @@ impl KVStore {
         value: Value,
     ) -> StrataResult<Version> {
         // Extract text for indexing before the value is consumed by the transaction
-        let text_for_index = match &value {
-            Value::String(s) => Some(s.clone()),
-            Value::Null | Value::Bool(_) | Value::Bytes(_) => None,
-            other => serde_json::to_string(other).ok(),
-        };
+        let text_for_index = index_text(&value);
 
         let ((), commit_version) = self.db.transaction_with_version(*branch_id, |txn| {
             let storage_key = self.key_for(branch_id, space, key);
@@ -152,19 +164,47 @@ impl KVStore {
 
         // Update inverted index for BM25 search (zero overhead when disabled)
         if let Some(text) = text_for_index {
-            let index = self.db.extension::<crate::search::InvertedIndex>()?;
-            if index.is_enabled() {
-                let entity_ref = crate::search::EntityRef::Kv {
-                    branch_id: *branch_id,
-                    key: key.to_string(),
-                };
-                index.index_document(&entity_ref, &text, None);
-            }
+            self.index_kv_text(branch_id, key, &text)?;
         }
 
         Ok(Version::Txn(commit_version))
     }
 
+    /// Get a value by binary key
+    ///
+    /// Like [`get`](Self::get), for keys that are not valid UTF-8 (hashes,
+    /// encoded ids). The key bytes are stored as-is.
+    pub fn get_bytes(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &[u8],
+    ) -> StrataResult<Option<Value>> {
+        self.db.transaction(*branch_id, |txn| {
+            let storage_key = self.key_for(branch_id, space, key);
+            txn.get(&storage_key)
+        })
+    }
+
+    /// Put a value under a binary key
+    ///
+    /// Like [`put`](Self::put), for keys that are not valid UTF-8. Values
+    /// written this way are not indexed for search, since search results
+    /// identify KV entries by string key.
+    pub fn put_bytes(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &[u8],
+        value: Value,
+    ) -> StrataResult<Version> {
+        let ((), commit_version) = self.db.transaction_with_version(*branch_id, |txn| {
+            let storage_key = self.key_for(branch_id, space, key);
+            txn.put(storage_key, value)
+        })?;
+        Ok(Version::Txn(commit_version))
+    }
+
     /// Delete a key
     ///
     /// Returns `true` if the key existed and was deleted, `false` if it didn't exist.
@@ -185,6 +225,86 @@ impl KVStore {
         })
     }
 
+    /// Delete a key only if it currently holds `expected`
+    ///
+    /// The comparison and the delete run in one transaction, retried on
+    /// conflict, so a value written concurrently by another caller is never
+    /// deleted by mistake. Returns `true` if the key was deleted.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let deleted = kv.delete_if_equals(&branch_id, "default", "lock", Value::String("me".into()))?;
+    /// ```
+    pub fn delete_if_equals(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &str,
+        expected: &Value,
+    ) -> StrataResult<bool> {
+        let retry_config = RetryConfig::default()
+            .with_max_retries(50)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(50);
+        self.db
+            .transaction_with_retry(*branch_id, retry_config, |txn| {
+                let storage_key = self.key_for(branch_id, space, key);
+                match txn.get(&storage_key)? {
+                    Some(current) if current == *expected => {
+                        txn.delete(storage_key)?;
+                        Ok(true)
+                    }
+                    _ => Ok(false),
+                }
+            })
+    }
+
+    /// Get a value, inserting `default` if the key is missing
+    ///
+    /// The read and the conditional write run in one transaction, retried on
+    /// conflict, so concurrent callers all observe the same value. Returns
+    /// the stored value and whether this call inserted it.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let (value, inserted) = kv.get_or_put(&branch_id, "default", "counter", Value::Int(0))?;
+    /// ```
+    pub fn get_or_put(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &str,
+        default: Value,
+    ) -> StrataResult<(Value, bool)> {
+        let retry_config = RetryConfig::default()
+            .with_max_retries(50)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(50);
+        let (value, inserted) =
+            self.db
+                .transaction_with_retry(*branch_id, retry_config, |txn| {
+                    let storage_key = self.key_for(branch_id, space, key);
+                    match txn.get(&storage_key)? {
+                        Some(existing) => Ok((existing, false)),
+                        None => {
+                            txn.put(storage_key, default.clone())?;
+                            Ok((default.clone(), true))
+                        }
+                    }
+                })?;
+
+        // Only a fresh insert changes what the inverted index should hold
+        if inserted {
+            if let Some(text) = index_text(&value) {
+                self.index_kv_text(branch_id, key, &text)?;
+            }
+        }
+
+        Ok((value, inserted))
+    }
+
     /// List keys with optional prefix filter
     ///
     /// Returns all keys matching the prefix (or all keys if prefix is None).
@@ -235,14 +355,8 @@ impl KVStore {
         }
 
         // Extract text for indexing BEFORE the values are consumed by the transaction
-        let texts: Vec<Option<String>> = entries
-            .iter()
-            .map(|(_, value)| match value {
-                Value::String(s) => Some(s.clone()),
-                Value::Null | Value::Bool(_) | Value::Bytes(_) => None,
-                other => serde_json::to_string(other).ok(),
-            })
-            .collect();
+        let texts: Vec<Option<String>> =
+            entries.iter().map(|(_, value)| index_text(value)).collect();
 
         let ((), commit_version) = self.db.transaction_with_version(*branch_id, |txn| {
             for (key, value) in &entries {
@@ -275,6 +389,51 @@ impl KVStore {
         Ok(results)
     }
 
+    /// Get multiple keys from a single snapshot.
+    ///
+    /// Returns one entry per requested key, in order, with its version
+    /// metadata or `None` if the key doesn't exist.
+    pub fn get_many(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        keys: &[String],
+    ) -> StrataResult<Vec<Option<strata_core::VersionedValue>>> {
+        self.db.transaction(*branch_id, |txn| {
+            keys.iter()
+                .map(|key| txn.get_versioned(&self.key_for(branch_id, space, key)))
+                .collect()
+        })
+    }
+
+    /// Delete multiple keys in a single transaction.
+    ///
+    /// Returns one flag per requested key, in order, telling whether that key
+    /// existed and was deleted. A key repeated in `keys` reports `false` after
+    /// its first occurrence.
+    pub fn delete_many(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        keys: &[String],
+    ) -> StrataResult<Vec<bool>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.db.transaction(*branch_id, |txn| {
+            keys.iter()
+                .map(|key| {
+                    let storage_key = self.key_for(branch_id, space, key);
+                    let exists = txn.get(&storage_key)?.is_some();
+                    if exists {
+                        txn.delete(storage_key)?;
+                    }
+                    Ok(exists)
+                })
+                .collect()
+        })
+    }
+
     // ========== Time-Travel API ==========
 
     /// Get a value by key as of a past timestamp (microseconds since epoch).
@@ -337,36 +496,70 @@ impl crate::search::Searchable for KVStore {
         let query_terms = crate::search::tokenize(&req.query);
         let scorer = self.db.config().bm25_scorer();
 
-        // Score top-k entirely inside the index (zero-copy posting iteration)
-        let top_k = index.score_top_k(&query_terms, &req.branch_id, req.k, scorer.k1, scorer.b);
+        // A time window can discard any number of the best-scoring docs.
+        // Over-fetch a bounded batch and widen it only while the window
+        // leaves fewer than k hits and the index has more matches.
+        let window = req.time_window();
+        let mut fetch_k = match window {
+            Some(_) => req.k.saturating_mul(WINDOW_OVERFETCH),
+            None => req.k,
+        };
 
-        // Only resolve doc_ids and fetch text for the final top-k results
-        let hits: Vec<SearchHit> = top_k
-            .into_iter()
-            .filter_map(|scored| {
-                let entity_ref = index.resolve_doc_id(scored.doc_id)?;
-                let snippet = if let EntityRef::Kv { ref key, .. } = entity_ref {
-                    self.get(&req.branch_id, "default", key)
-                        .ok()
-                        .flatten()
-                        .map(|v| match &v {
-                            strata_core::value::Value::String(s) => truncate_text(s, 100),
-                            other => truncate_text(
-                                &serde_json::to_string(other).unwrap_or_default(),
-                                100,
-                            ),
-                        })
-                } else {
-                    None
-                };
-
-                Some(SearchHit {
-                    doc_ref: entity_ref,
-                    score: scored.score,
-                    rank: 0, // Set below
-                    snippet,
+        let hits: Vec<SearchHit> = loop {
+            // Score top-k entirely inside the index (zero-copy posting iteration)
+            let top_k = index.score_top_k_fuzzy(
+                &query_terms,
+                req.typo_tolerance,
+                &req.branch_id,
+                fetch_k,
+                scorer.k1,
+                scorer.b,
+            );
+            let exhausted = top_k.len() < fetch_k;
+
+            // Only resolve doc_ids and fetch text for the final top-k results
+            let hits: Vec<SearchHit> = top_k
+                .into_iter()
+                .filter_map(|scored| {
+                    let entity_ref = index.resolve_doc_id(scored.doc_id)?;
+                    if let Some((start, end)) = window {
+                        let ts = self.entity_timestamp(&entity_ref, &req.space)?;
+                        if ts < start || ts > end {
+                            return None;
+                        }
+                    }
+                    let snippet = if let EntityRef::Kv { ref key, .. } = entity_ref {
+                        self.get(&req.branch_id, &req.space, key)
+                            .ok()
+                            .flatten()
+                            .map(|v| match &v {
+                                strata_core::value::Value::String(s) => truncate_text(s, 100),
+                                other => truncate_text(
+                                    &serde_json::to_string(other).unwrap_or_default(),
+                                    100,
+                                ),
+                            })
+                    } else {
+                        None
+                    };
+
+                    Some(SearchHit {
+                        doc_ref: entity_ref,
+                        score: scored.score,
+                        rank: 0, // Set below
+                        snippet,
+                    })
                 })
-            })
+                .take(req.k)
+                .collect();
+
+            if window.is_none() || hits.len() >= req.k || exhausted {
+                break hits;
+            }
+            fetch_k = fetch_k.saturating_mul(2);
+        };
+        let hits: Vec<SearchHit> = hits
+            .into_iter()
             .enumerate()
             .map(|(i, mut hit)| {
                 hit.rank = (i + 1) as u32;
@@ -390,6 +583,49 @@ impl crate::search::Searchable for KVStore {
     }
 }
 
+impl KVStore {
+    /// Add `text` to the inverted index as the content of KV `key`.
+    ///
+    /// A no-op when the index is disabled.
+    fn index_kv_text(&self, branch_id: &BranchId, key: &str, text: &str) -> StrataResult<()> {
+        let index = self.db.extension::<crate::search::InvertedIndex>()?;
+        if index.is_enabled() {
+            let entity_ref = crate::search::EntityRef::Kv {
+                branch_id: *branch_id,
+                key: key.to_string(),
+            };
+            index.index_document(&entity_ref, text, None);
+        }
+        Ok(())
+    }
+
+    /// Write timestamp of the current version of an indexed entity in `space`.
+    ///
+    /// Returns `None` for entities that no longer exist or that carry no
+    /// per-entity timestamp (branch metadata, vectors).
+    fn entity_timestamp(&self, doc_ref: &crate::search::EntityRef, space: &str) -> Option<u64> {
+        use crate::search::EntityRef;
+        use strata_core::Storage;
+
+        let key = match doc_ref {
+            EntityRef::Kv { branch_id, key } => self.key_for(branch_id, space, key),
+            EntityRef::State { branch_id, name } => {
+                Key::new_state(self.namespace_for(branch_id, space), name)
+            }
+            EntityRef::Event {
+                branch_id,
+                sequence,
+            } => Key::new_event(self.namespace_for(branch_id, space), *sequence),
+            EntityRef::Json { branch_id, doc_id } => {
+                Key::new_json(self.namespace_for(branch_id, space), doc_id)
+            }
+            EntityRef::Branch { .. } | EntityRef::Vector { .. } => return None,
+        };
+        let vv = self.db.storage().get(&key).ok()??;
+        Some(vv.timestamp.as_micros())
+    }
+}
+
 // ========== KVStoreExt Implementation ==========
 
 impl KVStoreExt for TransactionContext {
@@ -467,6 +703,23 @@ mod tests {
         assert_eq!(result, Some(Value::String("value1".into())));
     }
 
+    #[test]
+    fn test_put_and_get_bytes() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+        let key = [0x00, 0xFF, 0xC3, 0x28];
+
+        kv.put_bytes(&branch_id, "default", &key, Value::Int(1))
+            .unwrap();
+        assert_eq!(
+            kv.get_bytes(&branch_id, "default", &key).unwrap(),
+            Some(Value::Int(1))
+        );
+        // A lossy string form of the key does not alias it
+        let lossy = String::from_utf8_lossy(&key).into_owned();
+        assert_eq!(kv.get(&branch_id, "default", &lossy).unwrap(), None);
+    }
+
     #[test]
     fn test_get_nonexistent() {
         let (_temp, _db, kv) = setup();
@@ -528,6 +781,72 @@ mod tests {
         assert!(!deleted);
     }
 
+    #[test]
+    fn test_delete_if_equals() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        kv.put(&branch_id, "default", "k", Value::Int(1)).unwrap();
+        assert!(!kv
+            .delete_if_equals(&branch_id, "default", "k", &Value::Int(2))
+            .unwrap());
+        assert!(kv.get(&branch_id, "default", "k").unwrap().is_some());
+
+        assert!(kv
+            .delete_if_equals(&branch_id, "default", "k", &Value::Int(1))
+            .unwrap());
+        assert!(kv.get(&branch_id, "default", "k").unwrap().is_none());
+
+        assert!(!kv
+            .delete_if_equals(&branch_id, "default", "k", &Value::Int(1))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_get_or_put() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        let (value, inserted) = kv
+            .get_or_put(&branch_id, "default", "k", Value::Int(1))
+            .unwrap();
+        assert_eq!(value, Value::Int(1));
+        assert!(inserted);
+
+        let (value, inserted) = kv
+            .get_or_put(&branch_id, "default", "k", Value::Int(2))
+            .unwrap();
+        assert_eq!(value, Value::Int(1));
+        assert!(!inserted);
+        assert_eq!(
+            kv.get(&branch_id, "default", "k").unwrap(),
+            Some(Value::Int(1))
+        );
+    }
+
+    #[test]
+    fn test_get_or_put_concurrent_callers_agree() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        let results: Vec<(Value, bool)> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|i| {
+                    let kv = &kv;
+                    s.spawn(move || {
+                        kv.get_or_put(&branch_id, "default", "k", Value::Int(i))
+                            .unwrap()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(results.iter().filter(|(_, inserted)| *inserted).count(), 1);
+        let winner = &results[0].0;
+        assert!(results.iter().all(|(v, _)| v == winner));
+    }
+
     #[test]
     fn test_branch_isolation() {
         let (_temp, _db, kv) = setup();
@@ -768,7 +1087,7 @@ mod tests {
 
         // Start a manual transaction, read, then check the versioned read
         // is consistent even if a concurrent write happens
-        let mut txn = db.begin_transaction(branch_id);
+        let mut txn = db.begin_transaction(branch_id).unwrap();
         let storage_key =
             strata_core::types::Key::new_kv(Namespace::for_branch(branch_id), "iso_key");
         let vv = txn.get_versioned(&storage_key).unwrap().unwrap();
@@ -867,6 +1186,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_many() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        kv.put(&branch_id, "default", "a", Value::Int(1)).unwrap();
+        kv.put(&branch_id, "default", "c", Value::Int(3)).unwrap();
+
+        let keys = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let values: Vec<Option<Value>> = kv
+            .get_many(&branch_id, "default", &keys)
+            .unwrap()
+            .into_iter()
+            .map(|vv| vv.map(|vv| vv.value))
+            .collect();
+        assert_eq!(values, vec![Some(Value::Int(1)), None, Some(Value::Int(3))]);
+    }
+
+    #[test]
+    fn test_delete_many() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        kv.put(&branch_id, "default", "a", Value::Int(1)).unwrap();
+        kv.put(&branch_id, "default", "b", Value::Int(2)).unwrap();
+
+        let keys = vec![
+            "a".to_string(),
+            "missing".to_string(),
+            "b".to_string(),
+            "a".to_string(),
+        ];
+        let deleted = kv.delete_many(&branch_id, "default", &keys).unwrap();
+        assert_eq!(deleted, vec![true, false, true, false]);
+        assert!(kv.get(&branch_id, "default", "a").unwrap().is_none());
+        assert!(kv.get(&branch_id, "default", "b").unwrap().is_none());
+    }
+
     // ========== KVStore::search() integration tests ==========
 
     /// Setup with index enabled (Database::open enables it by default,
@@ -917,6 +1274,150 @@ mod tests {
         assert!(response.hits[0].score >= response.hits[1].score);
     }
 
+    #[test]
+    fn test_search_time_window() {
+        use crate::search::Searchable;
+
+        let (_temp, _db, kv) = setup_with_index();
+        let branch_id = BranchId::new();
+
+        kv.put(
+            &branch_id,
+            "default",
+            "old",
+            Value::String("lazy morning".into()),
+        )
+        .unwrap();
+        let t_old = kv
+            .get_versioned(&branch_id, "default", "old")
+            .unwrap()
+            .unwrap()
+            .timestamp
+            .as_micros();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        kv.put(
+            &branch_id,
+            "default",
+            "new",
+            Value::String("lazy evening".into()),
+        )
+        .unwrap();
+        let t_new = kv
+            .get_versioned(&branch_id, "default", "new")
+            .unwrap()
+            .unwrap()
+            .timestamp
+            .as_micros();
+
+        let keys = |req: crate::SearchRequest| -> Vec<String> {
+            kv.search(&req)
+                .unwrap()
+                .hits
+                .into_iter()
+                .map(|h| match h.doc_ref {
+                    crate::search::EntityRef::Kv { key, .. } => key,
+                    other => panic!("unexpected hit {:?}", other),
+                })
+                .collect()
+        };
+
+        let base = crate::SearchRequest::new(branch_id, "lazy");
+        assert_eq!(keys(base.clone()).len(), 2);
+        assert_eq!(
+            keys(base.clone().with_time_range(t_new, u64::MAX)),
+            vec!["new"]
+        );
+        assert_eq!(keys(base.clone().with_as_of(t_old)), vec!["old"]);
+        // as_of before the window start leaves nothing
+        assert!(keys(base.with_time_range(t_new, u64::MAX).with_as_of(t_old)).is_empty());
+    }
+
+    #[test]
+    fn test_search_time_window_widens_past_overfetch() {
+        use crate::search::Searchable;
+
+        let (_temp, _db, kv) = setup_with_index();
+        let branch_id = BranchId::new();
+
+        kv.put(&branch_id, "default", "old", Value::String("lazy".into()))
+            .unwrap();
+        let t_old = kv
+            .get_versioned(&branch_id, "default", "old")
+            .unwrap()
+            .unwrap()
+            .timestamp
+            .as_micros();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        // Newer docs that all outscore "old"
+        for i in 0..3 * WINDOW_OVERFETCH {
+            kv.put(
+                &branch_id,
+                "default",
+                &format!("new{}", i),
+                Value::String("lazy lazy lazy".into()),
+            )
+            .unwrap();
+        }
+
+        let req = crate::SearchRequest::new(branch_id, "lazy")
+            .with_k(1)
+            .with_as_of(t_old);
+        let response = kv.search(&req).unwrap();
+        assert_eq!(response.hits.len(), 1);
+        assert!(matches!(
+            &response.hits[0].doc_ref,
+            crate::search::EntityRef::Kv { key, .. } if key == "old"
+        ));
+    }
+
+    #[test]
+    fn test_search_time_window_reads_request_space() {
+        use crate::search::Searchable;
+
+        let (_temp, _db, kv) = setup_with_index();
+        let branch_id = BranchId::new();
+
+        kv.put(&branch_id, "notes", "memo", Value::String("lazy".into()))
+            .unwrap();
+        let written = kv
+            .get_versioned(&branch_id, "notes", "memo")
+            .unwrap()
+            .unwrap()
+            .timestamp
+            .as_micros();
+
+        let req = crate::SearchRequest::new(branch_id, "lazy").with_as_of(written);
+        let response = kv.search(&req.clone().with_space("notes")).unwrap();
+        assert_eq!(response.hits.len(), 1);
+        assert_eq!(response.hits[0].snippet.as_deref(), Some("lazy"));
+
+        // Looked up in the wrong space, the entry has no write time
+        assert!(kv.search(&req).unwrap().hits.is_empty());
+    }
+
+    #[test]
+    fn test_search_typo_tolerance() {
+        use crate::search::Searchable;
+
+        let (_temp, _db, kv) = setup_with_index();
+        let branch_id = BranchId::new();
+
+        kv.put(
+            &branch_id,
+            "default",
+            "doc",
+            Value::String("authentication failed for user".into()),
+        )
+        .unwrap();
+
+        let req = crate::SearchRequest::new(branch_id, "authentcation");
+        assert!(kv.search(&req).unwrap().is_empty());
+
+        let response = kv.search(&req.with_typo_tolerance(2)).unwrap();
+        assert_eq!(response.len(), 1);
+        assert!(response.hits[0].snippet.is_some());
+    }
+
     #[test]
     fn test_search_empty_index() {
         use crate::search::Searchable;
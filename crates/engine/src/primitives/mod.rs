@@ -52,7 +52,7 @@ pub mod vector;
 pub use branch::{BranchHandle, EventHandle, JsonHandle, KvHandle, StateHandle};
 pub use branch::{BranchIndex, BranchMetadata, BranchStatus};
 pub use event::{Event, EventLog};
-pub use json::{JsonDoc, JsonStore};
+pub use json::{JsonDoc, JsonDocInfo, JsonStore};
 pub use kv::KVStore;
 pub use space::SpaceIndex;
 pub use state::{State, StateCell};
@@ -62,7 +62,7 @@ pub use vector::{
     HnswBackend, HnswConfig, IndexBackendFactory, JsonScalar, MetadataFilter, StorageDtype,
     VectorBackendState, VectorConfig, VectorConfigSerde, VectorEntry, VectorError, VectorHeap,
     VectorId, VectorIndexBackend, VectorMatch, VectorMatchWithSource, VectorRecord, VectorResult,
-    VectorStore,
+    VectorScan, VectorStorage, VectorStore,
 };
 
 // Re-export search types for convenience (from search module)
@@ -220,7 +220,8 @@ impl StateCell {
             .with_max_retries(50)
             .with_base_delay_ms(1)
             .with_max_delay_ms(50);
-        self.db
+        let outcome = self
+            .db
             .transaction_with_retry(*branch_id, retry_config, |txn| {
                 let key = self.key_for(branch_id, space, name);
 
@@ -235,11 +236,10 @@ impl StateCell {
                     }
                 };
 
+                // A mismatch cannot resolve itself, so report it after the
+                // retry loop instead of retrying it as a write conflict.
                 if current.version != expected_version {
-                    return Err(strata_core::StrataError::conflict(format!(
-                        "Version mismatch: expected {:?}, got {:?}",
-                        expected_version, current.version
-                    )));
+                    return Ok(Err(current.version));
                 }
 
                 let new_version = current.version.increment();
@@ -250,8 +250,14 @@ impl StateCell {
                 };
 
                 txn.put(key, to_stored_value(&new_state)?)?;
-                Ok(new_state.version)
-            })
+                Ok(Ok(new_state.version))
+            })?;
+        outcome.map_err(|actual| {
+            strata_core::StrataError::conflict(format!(
+                "Version mismatch: expected {:?}, got {:?}",
+                expected_version, actual
+            ))
+        })
     }
 
     /// Unconditional set (force write)
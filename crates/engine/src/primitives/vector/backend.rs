@@ -153,9 +153,11 @@ pub trait VectorIndexBackend: Send + Sync {
     /// Write the embedding heap to a `.vec` mmap cache file.
     ///
     /// Called after recovery to create a disk cache that speeds up subsequent
-    /// starts. Default: no-op (backends that don't support mmap ignore this).
-    fn freeze_heap_to_disk(&self, _path: &std::path::Path) -> Result<(), VectorError> {
-        Ok(())
+    /// starts. Returns whether a file was written; an mmap-backed heap that
+    /// has not changed is already on disk. Default: no-op (backends that
+    /// don't support mmap ignore this).
+    fn freeze_heap_to_disk(&self, _path: &std::path::Path) -> Result<bool, VectorError> {
+        Ok(false)
     }
 
     /// Replace the internal heap with a pre-loaded (mmap-backed) heap.
@@ -185,7 +187,10 @@ pub trait VectorIndexBackend: Send + Sync {
     /// size threshold. Returns `true` if a flush was actually performed.
     ///
     /// Called after segment sealing to keep anonymous memory bounded during
-    /// long-running indexing. Default: no-op.
+    /// long-running indexing. Backends configured for
+    /// [`VectorStorage::InMemory`](super::VectorStorage::InMemory) instead
+    /// read an mmap-backed heap fully into memory and never flush.
+    /// Default: no-op.
     fn flush_heap_to_disk_if_needed(
         &mut self,
         _path: &std::path::Path,
@@ -196,15 +201,20 @@ pub trait VectorIndexBackend: Send + Sync {
     /// Write sealed segment graphs to disk for mmap-accelerated recovery.
     ///
     /// `dir` is the directory for graph files (e.g., `data_dir/vectors/{branch}/{collection}/`).
+    /// Returns the number of files written.
     /// Default: no-op (backends without sealed segments ignore this).
-    fn freeze_graphs_to_disk(&self, _dir: &std::path::Path) -> Result<(), VectorError> {
-        Ok(())
+    fn freeze_graphs_to_disk(&self, _dir: &std::path::Path) -> Result<usize, VectorError> {
+        Ok(0)
     }
 
     /// Load sealed segment graphs from mmap files, skipping `rebuild_index()`.
     ///
+    /// Every vector must already be registered (see `register_mmap_vector`)
+    /// so the graphs can be checked against the stored `VectorRecord`s.
+    ///
     /// Returns `true` if graphs were successfully loaded; `false` if files are
-    /// missing/corrupt and the caller should fall back to `rebuild_index()`.
+    /// missing/corrupt or stale and the caller should fall back to
+    /// `rebuild_index()`.
     /// Default: returns `false` (backends without sealed segments).
     fn load_graphs_from_disk(&mut self, _dir: &std::path::Path) -> Result<bool, VectorError> {
         Ok(false)
@@ -254,6 +264,21 @@ impl Default for IndexBackendFactory {
 }
 
 impl IndexBackendFactory {
+    /// Segmented HNSW factory with a collection's HNSW parameters
+    ///
+    /// `None` selects the default parameters. `storage` is the database's
+    /// [`VectorStorage`](super::VectorStorage) setting.
+    pub fn segmented_hnsw(
+        hnsw: Option<&super::hnsw::HnswConfig>,
+        storage: super::VectorStorage,
+    ) -> Self {
+        IndexBackendFactory::SegmentedHnsw(super::segmented::SegmentedHnswConfig {
+            hnsw: hnsw.cloned().unwrap_or_default(),
+            storage,
+            ..Default::default()
+        })
+    }
+
     /// Create a new backend instance
     pub fn create(&self, config: &VectorConfig) -> Box<dyn VectorIndexBackend> {
         match self {
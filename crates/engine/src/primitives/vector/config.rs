@@ -0,0 +1,317 @@
+//! Typed collection configuration
+//!
+//! [`CollectionConfigBuilder`] assembles every option a collection accepts
+//! and validates them together, so adding an option does not change the
+//! signature of [`VectorStore::create_collection`](super::VectorStore::create_collection).
+//!
+//! ```text
+//! let config = CollectionConfigBuilder::new()
+//!     .dimension(384)
+//!     .metric(DistanceMetric::Cosine)
+//!     .hnsw(HnswConfig { ef_search: 100, ..HnswConfig::default() })
+//!     .normalize(true);
+//! store.create_collection(branch_id, "default", "docs", config)?;
+//! ```
+
+use super::{
+    DistanceMetric, HnswConfig, MetadataFieldType, MetadataSchema, StorageDtype, VectorConfig,
+    VectorError, VectorResult,
+};
+
+/// Largest embedding dimension a collection may be created with
+pub const MAX_DIMENSION: usize = 65536;
+
+/// Fully specified collection configuration
+///
+/// `vector` is immutable after creation. `hnsw` tunes the index of each
+/// sealed segment; `None` uses [`HnswConfig::default`]. With `normalize`
+/// set, embeddings are scaled to unit length on insert (queries are used
+/// as given). A `metadata_schema` restricts search filters to the declared
+/// fields; `None` accepts any filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionConfig {
+    /// Dimension, metric and storage type
+    pub vector: VectorConfig,
+    /// HNSW parameters, `None` for the defaults
+    pub hnsw: Option<HnswConfig>,
+    /// Scale embeddings to unit length on insert
+    pub normalize: bool,
+    /// Filterable metadata fields, `None` for untyped filtering
+    pub metadata_schema: Option<MetadataSchema>,
+}
+
+impl CollectionConfig {
+    /// Check every option, reporting the first invalid field
+    pub fn validate(&self) -> VectorResult<()> {
+        let dimension = self.vector.dimension;
+        if dimension == 0 || dimension > MAX_DIMENSION {
+            return Err(VectorError::InvalidDimension { dimension });
+        }
+        if let Some(hnsw) = &self.hnsw {
+            if hnsw.m < 2 {
+                return Err(invalid(
+                    "hnsw.m",
+                    format!("must be at least 2, got {}", hnsw.m),
+                ));
+            }
+            if hnsw.ef_construction < hnsw.m {
+                return Err(invalid(
+                    "hnsw.ef_construction",
+                    format!(
+                        "must be at least hnsw.m ({}), got {}",
+                        hnsw.m, hnsw.ef_construction
+                    ),
+                ));
+            }
+            if hnsw.ef_search == 0 {
+                return Err(invalid("hnsw.ef_search", "must be greater than 0".into()));
+            }
+            if !(hnsw.ml.is_finite() && hnsw.ml > 0.0) {
+                return Err(invalid(
+                    "hnsw.ml",
+                    format!("must be a positive finite number, got {}", hnsw.ml),
+                ));
+            }
+        }
+        if let Some(schema) = &self.metadata_schema {
+            if schema.fields().any(|(name, _)| name.is_empty()) {
+                return Err(invalid(
+                    "metadata_schema",
+                    "field names must not be empty".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid(field: &str, reason: String) -> VectorError {
+    VectorError::InvalidConfig {
+        field: field.to_string(),
+        reason,
+    }
+}
+
+/// Builder for [`CollectionConfig`]
+///
+/// Only the dimension is required. The metric defaults to cosine, the
+/// storage type to `F32`, HNSW parameters to their defaults,
+/// normalization is off, and no metadata schema is declared.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionConfigBuilder {
+    dimension: Option<usize>,
+    metric: DistanceMetric,
+    dtype: StorageDtype,
+    hnsw: Option<HnswConfig>,
+    normalize: bool,
+    metadata_schema: Option<MetadataSchema>,
+}
+
+impl CollectionConfigBuilder {
+    /// Create a builder with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the embedding dimension
+    pub fn dimension(mut self, dimension: usize) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
+    /// Set the distance metric
+    pub fn metric(mut self, metric: DistanceMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Set the storage data type
+    pub fn dtype(mut self, dtype: StorageDtype) -> Self {
+        self.dtype = dtype;
+        self
+    }
+
+    /// Set the HNSW parameters
+    ///
+    /// `ml` is recomputed from `m`, so only `m`, `ef_construction` and
+    /// `ef_search` need to be set.
+    pub fn hnsw(mut self, config: HnswConfig) -> Self {
+        let ml = 1.0 / (config.m as f64).ln();
+        self.hnsw = Some(HnswConfig { ml, ..config });
+        self
+    }
+
+    /// Scale embeddings to unit length on insert
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Declare a filterable metadata field
+    ///
+    /// Once any field is declared, search filters may only use declared
+    /// fields.
+    pub fn metadata_field(
+        mut self,
+        name: impl Into<String>,
+        field_type: MetadataFieldType,
+    ) -> Self {
+        let schema = self.metadata_schema.take().unwrap_or_default();
+        self.metadata_schema = Some(schema.field(name, field_type));
+        self
+    }
+
+    /// Set the whole metadata schema
+    pub fn metadata_schema(mut self, schema: MetadataSchema) -> Self {
+        self.metadata_schema = Some(schema);
+        self
+    }
+
+    /// Validate the options and produce a [`CollectionConfig`]
+    pub fn build(self) -> VectorResult<CollectionConfig> {
+        let dimension = self
+            .dimension
+            .ok_or_else(|| invalid("dimension", "is required".into()))?;
+        let config = CollectionConfig {
+            vector: VectorConfig {
+                dimension,
+                metric: self.metric,
+                storage_dtype: self.dtype,
+            },
+            hnsw: self.hnsw,
+            normalize: self.normalize,
+            metadata_schema: self.metadata_schema,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Values accepted as a collection configuration
+///
+/// Implemented for a plain [`VectorConfig`], a built [`CollectionConfig`]
+/// and an unbuilt [`CollectionConfigBuilder`].
+pub trait IntoCollectionConfig {
+    /// Convert into a validated [`CollectionConfig`]
+    fn into_collection_config(self) -> VectorResult<CollectionConfig>;
+}
+
+impl IntoCollectionConfig for CollectionConfig {
+    fn into_collection_config(self) -> VectorResult<CollectionConfig> {
+        self.validate()?;
+        Ok(self)
+    }
+}
+
+impl IntoCollectionConfig for CollectionConfigBuilder {
+    fn into_collection_config(self) -> VectorResult<CollectionConfig> {
+        self.build()
+    }
+}
+
+impl IntoCollectionConfig for VectorConfig {
+    fn into_collection_config(self) -> VectorResult<CollectionConfig> {
+        CollectionConfig {
+            vector: self,
+            hnsw: None,
+            normalize: false,
+            metadata_schema: None,
+        }
+        .into_collection_config()
+    }
+}
+
+/// Scale `embedding` to unit length
+///
+/// A zero vector has no direction and is rejected.
+pub(crate) fn normalize_embedding(embedding: &[f32]) -> VectorResult<Vec<f32>> {
+    let norm = embedding
+        .iter()
+        .map(|v| (*v as f64) * (*v as f64))
+        .sum::<f64>()
+        .sqrt();
+    if norm == 0.0 {
+        return Err(VectorError::InvalidEmbedding {
+            reason: "cannot normalize a zero vector".to_string(),
+        });
+    }
+    Ok(embedding
+        .iter()
+        .map(|v| (*v as f64 / norm) as f32)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults() {
+        let config = CollectionConfigBuilder::new().dimension(4).build().unwrap();
+        assert_eq!(config.vector.dimension, 4);
+        assert_eq!(config.vector.metric, DistanceMetric::Cosine);
+        assert_eq!(config.vector.storage_dtype, StorageDtype::F32);
+        assert!(config.hnsw.is_none());
+        assert!(!config.normalize);
+        assert!(config.metadata_schema.is_none());
+    }
+
+    #[test]
+    fn test_builder_requires_dimension() {
+        let err = CollectionConfigBuilder::new().build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid collection config: dimension is required"
+        );
+        assert!(matches!(
+            CollectionConfigBuilder::new().dimension(0).build(),
+            Err(VectorError::InvalidDimension { dimension: 0 })
+        ));
+        assert!(CollectionConfigBuilder::new()
+            .dimension(MAX_DIMENSION + 1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_builder_validates_hnsw() {
+        let hnsw = |m, ef_construction, ef_search| HnswConfig {
+            m,
+            ef_construction,
+            ef_search,
+            ..HnswConfig::default()
+        };
+        let build = |h| CollectionConfigBuilder::new().dimension(4).hnsw(h).build();
+
+        let err = build(hnsw(1, 200, 50)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid collection config: hnsw.m must be at least 2, got 1"
+        );
+        assert!(build(hnsw(32, 16, 50)).is_err());
+        assert!(build(hnsw(16, 200, 0)).is_err());
+
+        let config = build(hnsw(32, 400, 100)).unwrap();
+        let h = config.hnsw.unwrap();
+        assert_eq!(h.m, 32);
+        assert!((h.ml - 1.0 / 32f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vector_config_converts() {
+        let config = VectorConfig::new(8, DistanceMetric::Euclidean)
+            .unwrap()
+            .into_collection_config()
+            .unwrap();
+        assert_eq!(config.vector.dimension, 8);
+        assert!(!config.normalize);
+    }
+
+    #[test]
+    fn test_normalize_embedding() {
+        let unit = normalize_embedding(&[3.0, 4.0]).unwrap();
+        assert!((unit[0] - 0.6).abs() < 1e-6);
+        assert!((unit[1] - 0.8).abs() < 1e-6);
+        assert!(normalize_embedding(&[0.0, 0.0]).is_err());
+    }
+}
@@ -26,6 +26,22 @@ pub fn compute_similarity(a: &[f32], b: &[f32], metric: DistanceMetric) -> f32 {
         DistanceMetric::Cosine => cosine_similarity(a, b),
         DistanceMetric::Euclidean => euclidean_similarity(a, b),
         DistanceMetric::DotProduct => dot_product(a, b),
+        DistanceMetric::Manhattan => manhattan_similarity(a, b),
+    }
+}
+
+/// Convert a similarity score back into the metric's distance
+///
+/// Inverts the score transforms above so callers can reason in distances
+/// ("lower = more similar"):
+/// - Cosine: `1 - cosine_similarity`, range [0, 2]
+/// - Euclidean / Manhattan: the L2 / L1 distance
+/// - DotProduct: the negated dot product (unbounded)
+pub fn similarity_to_distance(score: f32, metric: DistanceMetric) -> f32 {
+    match metric {
+        DistanceMetric::Cosine => 1.0 - score,
+        DistanceMetric::Euclidean | DistanceMetric::Manhattan => 1.0 / score - 1.0,
+        DistanceMetric::DotProduct => -score,
     }
 }
 
@@ -54,6 +70,15 @@ fn euclidean_similarity(a: &[f32], b: &[f32]) -> f32 {
     1.0 / (1.0 + dist)
 }
 
+/// Manhattan similarity: 1 / (1 + l1_distance)
+///
+/// Range: (0, 1], higher = more similar
+/// Transforms distance to similarity (inversely related)
+fn manhattan_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dist = manhattan_distance(a, b);
+    1.0 / (1.0 + dist)
+}
+
 /// Dot product (inner product)
 ///
 /// Range: unbounded, higher = more similar
@@ -76,10 +101,38 @@ fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
         .sqrt()
 }
 
+/// Manhattan distance (L1 distance)
+fn manhattan_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_similarity_to_distance_round_trips() {
+        let a = vec![1.0, 2.0, 3.0];
+        let b = vec![2.0, 0.0, 1.0];
+        let cases = [
+            (DistanceMetric::Cosine, 1.0 - cosine_similarity(&a, &b)),
+            (DistanceMetric::Euclidean, euclidean_distance(&a, &b)),
+            (DistanceMetric::Manhattan, manhattan_distance(&a, &b)),
+            (DistanceMetric::DotProduct, -dot_product(&a, &b)),
+        ];
+        for (metric, expected) in cases {
+            let score = compute_similarity(&a, &b, metric);
+            let distance = similarity_to_distance(score, metric);
+            assert!(
+                (distance - expected).abs() < 1e-4,
+                "{:?}: {} != {}",
+                metric,
+                distance,
+                expected
+            );
+        }
+    }
+
     #[test]
     fn test_cosine_identical_vectors() {
         let v = vec![1.0, 2.0, 3.0];
@@ -120,6 +173,27 @@ mod tests {
         assert!(sim <= 1.0);
     }
 
+    #[test]
+    fn test_manhattan_identical_vectors() {
+        let v = vec![1.0, 2.0, 3.0];
+        let sim = manhattan_similarity(&v, &v);
+        assert!((sim - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_manhattan_sums_absolute_differences() {
+        let v1 = vec![0.0, 0.0];
+        let v2 = vec![3.0, -4.0];
+        assert!((manhattan_distance(&v1, &v2) - 7.0).abs() < 1e-6);
+        assert!((manhattan_similarity(&v1, &v2) - 0.125).abs() < 1e-6);
+
+        // L1 and L2 rank these differently: (3, 0) is L1-closer than (2, 2)
+        let axis = vec![3.0, 0.0];
+        let diagonal = vec![2.0, 2.0];
+        assert!(manhattan_similarity(&v1, &axis) > manhattan_similarity(&v1, &diagonal));
+        assert!(euclidean_similarity(&v1, &axis) < euclidean_similarity(&v1, &diagonal));
+    }
+
     #[test]
     fn test_dot_product_unit_vectors() {
         let v = vec![1.0, 0.0];
@@ -156,5 +230,8 @@ mod tests {
 
         let dot = compute_similarity(&a, &b, DistanceMetric::DotProduct);
         assert!(dot.abs() < 1e-6); // Orthogonal
+
+        let manhattan = compute_similarity(&a, &b, DistanceMetric::Manhattan);
+        assert!((manhattan - 1.0 / 3.0).abs() < 1e-6); // L1 distance 2
     }
 }
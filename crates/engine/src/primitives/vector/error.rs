@@ -36,6 +36,15 @@ pub enum VectorError {
         dimension: usize,
     },
 
+    /// Collection configuration is invalid
+    #[error("Invalid collection config: {field} {reason}")]
+    InvalidConfig {
+        /// The offending configuration field
+        field: String,
+        /// Why the value is rejected
+        reason: String,
+    },
+
     /// Vector with given key was not found
     #[error("Vector not found: {key}")]
     VectorNotFound {
@@ -43,6 +52,35 @@ pub enum VectorError {
         key: String,
     },
 
+    /// A vector with the given key already exists and the upsert mode
+    /// forbids replacing it
+    #[error("Vector already exists: {collection}/{key}")]
+    VectorAlreadyExists {
+        /// Collection name
+        collection: String,
+        /// Vector key
+        key: String,
+    },
+
+    /// Search filter names a field the collection's metadata schema does
+    /// not declare
+    #[error("Unknown filter field '{field}' in collection {collection}")]
+    UnknownFilterField {
+        /// Collection name
+        collection: String,
+        /// The undeclared field
+        field: String,
+    },
+
+    /// Search filter condition does not fit the declared field type
+    #[error("Invalid filter on '{field}': {reason}")]
+    InvalidFilter {
+        /// The filtered field
+        field: String,
+        /// Why the condition is rejected
+        reason: String,
+    },
+
     /// Embedding vector is empty
     #[error("Empty embedding")]
     EmptyEmbedding,
@@ -130,11 +168,14 @@ impl VectorError {
             self,
             VectorError::DimensionMismatch { .. }
                 | VectorError::InvalidDimension { .. }
+                | VectorError::InvalidConfig { .. }
                 | VectorError::EmptyEmbedding
                 | VectorError::InvalidEmbedding { .. }
                 | VectorError::InvalidCollectionName { .. }
                 | VectorError::InvalidKey { .. }
                 | VectorError::ConfigMismatch { .. }
+                | VectorError::UnknownFilterField { .. }
+                | VectorError::InvalidFilter { .. }
         )
     }
 }
@@ -163,6 +204,10 @@ impl VectorError {
             VectorError::VectorNotFound { key } => StrataError::NotFound {
                 entity_ref: EntityRef::vector(branch_id, "unknown", key),
             },
+            VectorError::VectorAlreadyExists { collection, key } => StrataError::InvalidOperation {
+                entity_ref: EntityRef::vector(branch_id, collection, key),
+                reason: "Vector already exists".to_string(),
+            },
             VectorError::ConfigMismatch { collection, field } => StrataError::InvalidOperation {
                 entity_ref: EntityRef::vector(branch_id, collection, ""),
                 reason: format!("Config field '{}' cannot be changed", field),
@@ -193,9 +238,22 @@ impl From<VectorError> for StrataError {
             VectorError::InvalidDimension { dimension } => StrataError::InvalidInput {
                 message: format!("Invalid dimension: {} (must be > 0)", dimension),
             },
+            VectorError::InvalidConfig { field, reason } => StrataError::InvalidInput {
+                message: format!("Invalid collection config: {} {}", field, reason),
+            },
             VectorError::VectorNotFound { key } => StrataError::NotFound {
                 entity_ref: EntityRef::vector(placeholder_branch_id, "unknown", key),
             },
+            VectorError::VectorAlreadyExists { collection, key } => StrataError::InvalidOperation {
+                entity_ref: EntityRef::vector(placeholder_branch_id, collection, key),
+                reason: "Vector already exists".to_string(),
+            },
+            VectorError::UnknownFilterField { collection, field } => {
+                StrataError::UnknownFilterField { collection, field }
+            }
+            VectorError::InvalidFilter { field, reason } => StrataError::InvalidInput {
+                message: format!("Invalid filter on '{}': {}", field, reason),
+            },
             VectorError::EmptyEmbedding => StrataError::InvalidInput {
                 message: "Empty embedding".to_string(),
             },
@@ -513,7 +513,9 @@ impl VectorHeap {
     /// - `InMemory`: writes all data.
     /// - `Mmap`: no-op (already on disk and unchanged).
     /// - `Tiered`: merges base + overlay into a new file.
-    pub fn freeze_to_disk(&self, path: &Path) -> Result<(), VectorError> {
+    ///
+    /// Returns whether a file was written.
+    pub fn freeze_to_disk(&self, path: &Path) -> Result<bool, VectorError> {
         match &self.data {
             VectorData::InMemory(vec) => mmap::write_mmap_file(
                 path,
@@ -522,12 +524,12 @@ impl VectorHeap {
                 &self.id_to_offset,
                 &self.free_slots,
                 vec,
-            ),
+            )?,
             VectorData::Mmap(mmap_data) => {
                 // If vectors were deleted at runtime (id_to_offset shrank),
                 // write a new mmap excluding deleted vectors. Otherwise no-op.
                 if self.id_to_offset.len() == mmap_data.len() {
-                    return Ok(()); // Unchanged, already on disk
+                    return Ok(false); // Unchanged, already on disk
                 }
                 // Build compacted data with only live vectors
                 let dim = self.config.dimension;
@@ -548,7 +550,7 @@ impl VectorHeap {
                     &compacted_offsets,
                     &[], // compacted: no free slots
                     &compacted_data,
-                )
+                )?;
             }
             VectorData::Tiered {
                 base,
@@ -584,9 +586,10 @@ impl VectorHeap {
                     &merged_offsets,
                     &merged_free_slots,
                     &merged_data,
-                )
+                )?;
             }
         }
+        Ok(true)
     }
 
     /// Flush the overlay to disk and swap to a fresh Tiered state.
@@ -647,6 +650,27 @@ impl VectorHeap {
         }
     }
 
+    /// Read an `Mmap` or `Tiered` heap fully into anonymous memory.
+    ///
+    /// Live vectors are copied into a contiguous `Vec` (dropping free slots)
+    /// and the mapping is released, so later reads never page from disk.
+    /// `next_id` is unchanged. No-op if already `InMemory`.
+    pub fn load_into_memory(&mut self) {
+        if !self.is_mmap() {
+            return;
+        }
+        let dim = self.config.dimension;
+        let mut data = Vec::with_capacity(self.id_to_offset.len() * dim);
+        let mut id_to_offset = BTreeMap::new();
+        for (id, embedding) in self.iter() {
+            id_to_offset.insert(id, data.len());
+            data.extend_from_slice(embedding);
+        }
+        self.data = VectorData::InMemory(data);
+        self.id_to_offset = id_to_offset;
+        self.free_slots.clear();
+    }
+
     /// Promote an `Mmap` heap to `Tiered` so that new inserts go to an overlay
     /// while reads fall through to the mmap base.
     ///
@@ -1088,6 +1112,30 @@ mod tests {
         assert_eq!(heap.get(VectorId::new(1)).unwrap(), &[1.0, 0.0, 0.0]);
     }
 
+    #[test]
+    fn test_load_into_memory_from_tiered() {
+        let (mut heap, _dir) = make_tiered_heap(3);
+        heap.upsert(VectorId::new(10), &[10.0, 10.0, 10.0]).unwrap();
+        heap.delete(VectorId::new(2));
+        let next_id = heap.next_id_value();
+
+        heap.load_into_memory();
+        assert!(!heap.is_mmap());
+        assert_eq!(heap.anon_data_bytes(), 3 * 3 * std::mem::size_of::<f32>());
+        let ids: Vec<_> = heap.ids().collect();
+        assert_eq!(
+            ids,
+            vec![VectorId::new(1), VectorId::new(3), VectorId::new(10)]
+        );
+        assert_eq!(heap.get(VectorId::new(3)).unwrap(), &[3.0, 3.0, 3.0]);
+        assert_eq!(heap.get(VectorId::new(10)).unwrap(), &[10.0, 10.0, 10.0]);
+        assert_eq!(heap.next_id_value(), next_id);
+
+        // Mutations now go straight to the in-memory Vec
+        heap.upsert(VectorId::new(11), &[11.0, 11.0, 11.0]).unwrap();
+        assert_eq!(heap.len(), 4);
+    }
+
     #[test]
     fn test_tiered_insert_goes_to_overlay() {
         let (mut heap, _dir) = make_tiered_heap(3);
@@ -26,13 +26,15 @@
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
 
+use serde::{Deserialize, Serialize};
+
 use crate::primitives::vector::backend::VectorIndexBackend;
 use crate::primitives::vector::distance::compute_similarity;
 use crate::primitives::vector::heap::VectorHeap;
 use crate::primitives::vector::{DistanceMetric, VectorConfig, VectorError, VectorId};
 
 /// HNSW configuration parameters
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HnswConfig {
     /// Max connections per layer (default: 16)
     pub m: usize,
@@ -1332,6 +1334,15 @@ impl CompactHnswGraph {
         self.neighbor_data.is_mmap()
     }
 
+    /// Copy memory-mapped neighbor data into an owned `Vec`.
+    ///
+    /// No-op if the neighbor data is already owned.
+    pub(crate) fn load_neighbors_into_memory(&mut self) {
+        if self.neighbor_data.is_mmap() {
+            self.neighbor_data = NeighborData::Owned(self.neighbor_data.as_slice().to_vec());
+        }
+    }
+
     /// Memory usage of the compact graph (excludes embedding data)
     pub(crate) fn memory_usage(&self) -> usize {
         // Neighbor data: 0 for mmap (OS manages those pages)
@@ -1559,7 +1570,7 @@ impl VectorIndexBackend for HnswBackend {
         self.heap.restore_snapshot_state(next_id, free_slots);
     }
 
-    fn freeze_heap_to_disk(&self, path: &std::path::Path) -> Result<(), VectorError> {
+    fn freeze_heap_to_disk(&self, path: &std::path::Path) -> Result<bool, VectorError> {
         self.heap.freeze_to_disk(path)
     }
 
@@ -1873,6 +1884,7 @@ mod tests {
             DistanceMetric::Cosine,
             DistanceMetric::Euclidean,
             DistanceMetric::DotProduct,
+            DistanceMetric::Manhattan,
         ] {
             let mut backend = make_backend(3, metric);
             backend.insert(VectorId::new(1), &[1.0, 0.0, 0.0]).unwrap();
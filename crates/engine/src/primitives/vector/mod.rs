@@ -5,12 +5,13 @@
 //!
 //! - **VectorStore**: Main facade for vector storage and search
 //! - **VectorConfig**: Collection configuration (dimension, metric, storage type)
-//! - **DistanceMetric**: Similarity metrics (Cosine, Euclidean, DotProduct)
+//! - **DistanceMetric**: Similarity metrics (Cosine, Euclidean, DotProduct, Manhattan)
 //! - **VectorEntry/Match**: Vector storage and search result types
 //! - **VectorHeap**: Contiguous embedding storage with slot reuse
 //! - **VectorIndexBackend**: Trait for swappable index implementations
 //! - **BruteForceBackend**: O(n) brute-force search
 //! - **MetadataFilter**: Equality-based metadata filtering
+//! - **MetadataSchema**: Declared, typed filterable metadata fields
 //! - **VectorError**: Error types for vector operations
 //!
 //! ## Recovery
@@ -22,6 +23,7 @@
 pub mod backend;
 pub mod brute_force;
 pub mod collection;
+pub mod config;
 pub mod distance;
 pub mod error;
 pub mod filter;
@@ -30,6 +32,7 @@ pub mod hnsw;
 pub(crate) mod mmap;
 pub(crate) mod mmap_graph;
 pub mod recovery;
+pub mod schema;
 pub mod segmented;
 pub mod snapshot;
 pub mod store;
@@ -41,17 +44,23 @@ pub use brute_force::BruteForceBackend;
 pub use collection::{
     validate_collection_name, validate_system_collection_name, validate_vector_key,
 };
+pub use config::{CollectionConfig, CollectionConfigBuilder, IntoCollectionConfig, MAX_DIMENSION};
 pub use error::{VectorError, VectorResult};
 pub use filter::{FilterCondition, FilterOp, JsonScalar, MetadataFilter};
 pub use heap::VectorHeap;
 pub use hnsw::{HnswBackend, HnswConfig};
 pub use recovery::register_vector_recovery;
+pub use schema::{MetadataFieldType, MetadataSchema};
 pub use segmented::{SegmentedHnswBackend, SegmentedHnswConfig};
 pub use snapshot::{CollectionSnapshotHeader, VECTOR_SNAPSHOT_VERSION};
-pub use store::{RecoveryStats, VectorBackendState, VectorStore};
+pub use store::{
+    NovelInsert, RecoveryStats, UpsertMode, Upserted, VectorBackendState, VectorScan, VectorStore,
+    DEFAULT_OVERFETCH_FACTOR, OVERFETCH_ROUNDS,
+};
 pub use types::{
     CollectionId, CollectionInfo, CollectionRecord, DistanceMetric, StorageDtype, VectorConfig,
     VectorConfigSerde, VectorEntry, VectorId, VectorMatch, VectorMatchWithSource, VectorRecord,
+    VectorStorage,
 };
 pub use wal::{
     create_wal_collection_create, create_wal_collection_delete, create_wal_delete,
@@ -59,15 +68,20 @@ pub use wal::{
     WalVectorCollectionDelete, WalVectorDelete, WalVectorUpsert,
 };
 
+/// Compute the directory holding all of a branch's vector mmap files.
+pub(crate) fn branch_vector_dir(
+    data_dir: &std::path::Path,
+    branch_id: strata_core::types::BranchId,
+) -> std::path::PathBuf {
+    let branch_hex = format!("{:032x}", u128::from_be_bytes(*branch_id.as_bytes()));
+    data_dir.join("vectors").join(branch_hex)
+}
+
 /// Compute the directory for sealed-segment graph mmap files.
 pub(crate) fn graph_dir(
     data_dir: &std::path::Path,
     branch_id: strata_core::types::BranchId,
     collection_name: &str,
 ) -> std::path::PathBuf {
-    let branch_hex = format!("{:032x}", u128::from_be_bytes(*branch_id.as_bytes()));
-    data_dir
-        .join("vectors")
-        .join(branch_hex)
-        .join(format!("{}_graphs", collection_name))
+    branch_vector_dir(data_dir, branch_id).join(format!("{}_graphs", collection_name))
 }
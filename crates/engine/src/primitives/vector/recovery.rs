@@ -27,6 +27,12 @@
 //! On subsequent opens, `load_graphs_from_disk()` loads the pre-built graphs
 //! (with neighbor data mmap-backed), skipping the expensive `rebuild_index()`.
 //!
+//! Both caches are written at shutdown and by `Database::checkpoint_vectors()`,
+//! so after a crash they may be older than the vector records. Recovery
+//! reconciles them: records missing from the heap are inserted from their
+//! stored embedding, heap entries without a record are deleted, and graphs
+//! holding a vector that was deleted or re-created since are not loaded.
+//!
 //! All mmap files are **caches** — if missing, corrupt, or with a dimension
 //! mismatch, recovery falls back transparently to full KV-based rebuild
 //! with no data loss.
@@ -71,12 +77,12 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
 
     // Get access to the shared backend state
     let state = db.extension::<VectorBackendState>()?;
-    let factory = IndexBackendFactory::default();
 
     let snapshot = db.storage().create_snapshot();
     let mut stats = super::RecoveryStats::default();
     let data_dir = db.data_dir();
     let use_mmap = !data_dir.as_os_str().is_empty();
+    let storage = db.vector_storage();
 
     // Iterate all branch_ids in storage
     for branch_id in db.storage().branch_ids() {
@@ -124,6 +130,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                 None => continue,
             };
 
+            let hnsw = record.hnsw.clone();
             let config: VectorConfig = match record.config.try_into() {
                 Ok(c) => c,
                 Err(e) => {
@@ -139,7 +146,8 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
             let collection_id = CollectionId::new(branch_id, &collection_name);
 
             // Create backend for this collection
-            let mut backend = factory.create(&config);
+            let mut backend =
+                IndexBackendFactory::segmented_hnsw(hnsw.as_ref(), storage).create(&config);
 
             // -----------------------------------------------------------
             // Try mmap-accelerated recovery: load heap from disk cache.
@@ -194,6 +202,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                 }
             };
 
+            let mut recorded_ids = std::collections::BTreeSet::new();
             for (_vec_key, vec_versioned) in &vector_entries {
                 let vec_bytes = match &vec_versioned.value {
                     Value::Bytes(b) => b,
@@ -214,8 +223,9 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                 };
 
                 let vid = VectorId::new(vec_record.vector_id);
+                recorded_ids.insert(vid);
 
-                if loaded_from_mmap {
+                if loaded_from_mmap && backend.contains(vid) {
                     // Heap already has the embedding — just register ID + timestamp
                     backend.register_mmap_vector(vid, vec_record.created_at);
                     stats.vectors_mmap_registered += 1;
@@ -242,6 +252,33 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                 }
             }
 
+            if loaded_from_mmap {
+                // The cache may predate deletes committed before a crash
+                for vid in backend.vector_ids() {
+                    if !recorded_ids.contains(&vid) {
+                        let _ = backend.delete(vid);
+                        stats.vectors_deleted += 1;
+                    }
+                }
+            }
+
+            // Reuse frozen graphs if they still match the vector records
+            if use_mmap {
+                let gdir = super::graph_dir(data_dir, branch_id, &collection_name);
+                match backend.load_graphs_from_disk(&gdir) {
+                    Ok(true) => stats.graphs_loaded += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::warn!(
+                            target: "strata::vector",
+                            collection = %collection_name,
+                            error = %e,
+                            "Failed to load graph mmap cache, graphs will be rebuilt"
+                        );
+                    }
+                }
+            }
+
             state
                 .backends
                 .write()
@@ -268,7 +305,8 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                 }
             }
             // Configure periodic flush path so the backend can flush
-            // its overlay during long-running indexing operations.
+            // its overlay during long-running indexing operations. With
+            // in-memory storage this reads the heap into memory instead.
             let _ = backend.flush_heap_to_disk_if_needed(&vec_path);
         }
     }
@@ -282,8 +320,11 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
             collections_created = stats.collections_created,
             vectors_upserted = stats.vectors_upserted,
             vectors_mmap_registered = stats.vectors_mmap_registered,
+            vectors_deleted = stats.vectors_deleted,
+            graphs_loaded = stats.graphs_loaded,
             lite_records_skipped = stats.lite_records_skipped,
             mmap_cache = use_mmap,
+            vector_storage = ?storage,
             "Vector recovery complete"
         );
     }
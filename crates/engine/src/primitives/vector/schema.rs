@@ -0,0 +1,172 @@
+//! Typed metadata schema for filterable fields
+//!
+//! A collection may declare which metadata fields search filters can use
+//! and what type each holds. Filters are checked against the schema before
+//! a search runs, so a misspelled field fails with
+//! [`VectorError::UnknownFilterField`] instead of silently matching nothing.
+//!
+//! Collections created without a schema accept any filter. Stored metadata
+//! is never checked: undeclared fields may be stored, they just cannot be
+//! filtered on.
+//!
+//! ```text
+//! let config = CollectionConfigBuilder::new()
+//!     .dimension(384)
+//!     .metadata_field("category", MetadataFieldType::String)
+//!     .metadata_field("year", MetadataFieldType::Number);
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{FilterOp, JsonScalar, MetadataFilter, VectorError, VectorResult};
+
+/// Type of a declared metadata field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetadataFieldType {
+    /// JSON string
+    String,
+    /// JSON number
+    Number,
+    /// JSON boolean
+    Bool,
+}
+
+impl MetadataFieldType {
+    fn name(self) -> &'static str {
+        match self {
+            MetadataFieldType::String => "string",
+            MetadataFieldType::Number => "number",
+            MetadataFieldType::Bool => "bool",
+        }
+    }
+
+    /// Whether a filter value can be compared with this field; null always can
+    fn accepts(self, value: &JsonScalar) -> bool {
+        matches!(
+            (self, value),
+            (_, JsonScalar::Null)
+                | (MetadataFieldType::String, JsonScalar::String(_))
+                | (MetadataFieldType::Number, JsonScalar::Number(_))
+                | (MetadataFieldType::Bool, JsonScalar::Bool(_))
+        )
+    }
+}
+
+/// Filterable metadata fields of a collection, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataSchema {
+    fields: BTreeMap<String, MetadataFieldType>,
+}
+
+impl MetadataSchema {
+    /// Create an empty schema, under which no field is filterable
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a field, replacing any earlier declaration of the same name
+    pub fn field(mut self, name: impl Into<String>, field_type: MetadataFieldType) -> Self {
+        self.fields.insert(name.into(), field_type);
+        self
+    }
+
+    /// Type of a declared field
+    pub fn get(&self, name: &str) -> Option<MetadataFieldType> {
+        self.fields.get(name).copied()
+    }
+
+    /// Declared fields, sorted by name
+    pub fn fields(&self) -> impl Iterator<Item = (&str, MetadataFieldType)> {
+        self.fields.iter().map(|(name, ty)| (name.as_str(), *ty))
+    }
+
+    /// Check that every condition of `filter` names a declared field and
+    /// compares it with a value of the declared type
+    ///
+    /// # Errors
+    /// - `UnknownFilterField` if a field is not declared
+    /// - `InvalidFilter` if an operator or value does not fit the field type
+    pub fn validate_filter(&self, collection: &str, filter: &MetadataFilter) -> VectorResult<()> {
+        let equals = filter
+            .equals
+            .iter()
+            .map(|(field, value)| (field, FilterOp::Eq, value));
+        let conditions = filter.conditions.iter().map(|c| (&c.field, c.op, &c.value));
+        for (field, op, value) in equals.chain(conditions) {
+            let field_type = self
+                .get(field)
+                .ok_or_else(|| VectorError::UnknownFilterField {
+                    collection: collection.to_string(),
+                    field: field.clone(),
+                })?;
+            let op_fits = match op {
+                FilterOp::Gt | FilterOp::Gte | FilterOp::Lt | FilterOp::Lte => {
+                    field_type == MetadataFieldType::Number
+                }
+                FilterOp::Contains => field_type == MetadataFieldType::String,
+                FilterOp::Eq | FilterOp::Ne | FilterOp::In | FilterOp::NotIn => true,
+            };
+            let reason = if !op_fits {
+                format!("{:?} does not apply to a {} field", op, field_type.name())
+            } else if !field_type.accepts(value) {
+                format!("expected a {} value, got {:?}", field_type.name(), value)
+            } else {
+                continue;
+            };
+            return Err(VectorError::InvalidFilter {
+                field: field.clone(),
+                reason,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> MetadataSchema {
+        MetadataSchema::new()
+            .field("category", MetadataFieldType::String)
+            .field("year", MetadataFieldType::Number)
+    }
+
+    #[test]
+    fn test_declared_fields_pass() {
+        let filter = MetadataFilter::new()
+            .eq("category", "doc")
+            .gte("year", 2020)
+            .contains("category", "do")
+            .eq("year", JsonScalar::Null);
+        schema().validate_filter("docs", &filter).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_field_rejected() {
+        let filter = MetadataFilter::new().eq("categroy", "doc");
+        match schema().validate_filter("docs", &filter) {
+            Err(VectorError::UnknownFilterField { collection, field }) => {
+                assert_eq!(collection, "docs");
+                assert_eq!(field, "categroy");
+            }
+            other => panic!("expected UnknownFilterField, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_type_mismatch_rejected() {
+        for filter in [
+            MetadataFilter::new().eq("year", "2020"),
+            MetadataFilter::new().gt("category", 1),
+            MetadataFilter::new().contains("year", "20"),
+        ] {
+            assert!(matches!(
+                schema().validate_filter("docs", &filter),
+                Err(VectorError::InvalidFilter { .. })
+            ));
+        }
+    }
+}
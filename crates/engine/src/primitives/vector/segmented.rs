@@ -30,7 +30,9 @@ use crate::primitives::vector::backend::VectorIndexBackend;
 use crate::primitives::vector::distance::compute_similarity;
 use crate::primitives::vector::heap::VectorHeap;
 use crate::primitives::vector::hnsw::{CompactHnswGraph, HnswConfig, HnswGraph};
-use crate::primitives::vector::{DistanceMetric, VectorConfig, VectorError, VectorId};
+use crate::primitives::vector::{
+    DistanceMetric, VectorConfig, VectorError, VectorId, VectorStorage,
+};
 
 /// Dedicated thread pool for parallel vector search.
 ///
@@ -69,6 +71,9 @@ pub struct SegmentedHnswConfig {
     /// Number of overlay vectors before flushing heap to mmap (default: 500_000).
     /// Set to 0 to disable periodic flushing.
     pub heap_flush_threshold: usize,
+    /// Whether the heap stays memory-mapped or is held fully in memory.
+    /// `InMemory` also disables periodic flushing.
+    pub storage: VectorStorage,
 }
 
 impl Default for SegmentedHnswConfig {
@@ -77,6 +82,7 @@ impl Default for SegmentedHnswConfig {
             hnsw: HnswConfig::default(),
             seal_threshold: 50_000,
             heap_flush_threshold: 500_000,
+            storage: VectorStorage::default(),
         }
     }
 }
@@ -270,7 +276,7 @@ impl SegmentedHnswBackend {
     ///   subsequent inserts go to a small overlay instead of growing anon memory.
     fn flush_heap_if_needed(&mut self) {
         let threshold = self.config.heap_flush_threshold;
-        if threshold == 0 {
+        if threshold == 0 || self.config.storage == VectorStorage::InMemory {
             return; // Flushing disabled
         }
         let Some(path) = self.flush_path.clone() else {
@@ -902,7 +908,7 @@ impl VectorIndexBackend for SegmentedHnswBackend {
         self.heap.restore_snapshot_state(next_id, free_slots);
     }
 
-    fn freeze_heap_to_disk(&self, path: &std::path::Path) -> Result<(), VectorError> {
+    fn freeze_heap_to_disk(&self, path: &std::path::Path) -> Result<bool, VectorError> {
         self.heap.freeze_to_disk(path)
     }
 
@@ -910,6 +916,13 @@ impl VectorIndexBackend for SegmentedHnswBackend {
         &mut self,
         path: &std::path::Path,
     ) -> Result<bool, VectorError> {
+        if self.config.storage == VectorStorage::InMemory {
+            // Keep every embedding resident; the `.vec` file is only
+            // rewritten by explicit freezes
+            self.heap.load_into_memory();
+            return Ok(false);
+        }
+
         // Store the flush path for future periodic flushes
         self.flush_path = Some(path.to_path_buf());
 
@@ -941,7 +954,7 @@ impl VectorIndexBackend for SegmentedHnswBackend {
         self.heap.is_mmap()
     }
 
-    fn freeze_graphs_to_disk(&self, dir: &std::path::Path) -> Result<(), VectorError> {
+    fn freeze_graphs_to_disk(&self, dir: &std::path::Path) -> Result<usize, VectorError> {
         use crate::primitives::vector::mmap_graph;
 
         // Always write every segment — even previously mmap-backed ones may
@@ -951,9 +964,9 @@ impl VectorIndexBackend for SegmentedHnswBackend {
             mmap_graph::write_graph_file(&path, &seg.graph)?;
         }
 
-        // Write a manifest recording the total vector count in the heap so
-        // that load_graphs_from_disk() can detect staleness (e.g., vectors
-        // deleted via WAL replay after graphs were frozen).
+        // Write a manifest listing the segments. The leading heap vector
+        // count is informational; load_graphs_from_disk() checks staleness
+        // against the registered vectors instead.
         let manifest_path = dir.join("segments.manifest");
         let heap_vector_count = self.heap.len() as u64;
         let mut manifest = Vec::with_capacity(8 + self.sealed.len() * 24);
@@ -969,7 +982,7 @@ impl VectorIndexBackend for SegmentedHnswBackend {
         }
         std::fs::write(&manifest_path, &manifest).map_err(|e| VectorError::Io(e.to_string()))?;
 
-        Ok(())
+        Ok(self.sealed.len() + 1)
     }
 
     fn load_graphs_from_disk(&mut self, dir: &std::path::Path) -> Result<bool, VectorError> {
@@ -992,21 +1005,6 @@ impl VectorIndexBackend for SegmentedHnswBackend {
             return Ok(false);
         }
 
-        // Staleness check: if the heap vector count changed since the graphs
-        // were frozen (e.g. vectors deleted via WAL replay), the graphs are
-        // stale and must be rebuilt.
-        let frozen_heap_count =
-            u64::from_le_bytes(manifest_data[0..8].try_into().unwrap()) as usize;
-        if frozen_heap_count != self.heap.len() {
-            tracing::info!(
-                target: "strata::vector",
-                frozen = frozen_heap_count,
-                current = self.heap.len(),
-                "Graph mmap stale (heap size changed), rebuilding"
-            );
-            return Ok(false);
-        }
-
         let segment_count = (manifest_data.len() - 8) / 24;
         let mut loaded_segments = Vec::with_capacity(segment_count);
         let mut max_segment_id = 0u64;
@@ -1058,6 +1056,34 @@ impl VectorIndexBackend for SegmentedHnswBackend {
             }
         }
 
+        // Staleness check: every vector live in a frozen graph must still be
+        // registered with the same creation time and have an embedding.
+        // Vectors deleted or re-created after the graphs were frozen (e.g.
+        // before a crash) make them stale. Vectors added since simply stay
+        // in the active buffer.
+        let stale = loaded_segments.iter().find_map(|seg| {
+            seg.graph.nodes.iter().find_map(|(&id, node)| {
+                let current = self.active.timestamps.get(&id);
+                let matches = node.deleted_at.is_some()
+                    || (current == Some(&(node.created_at, None)) && self.heap.contains(id));
+                (!matches).then_some(id)
+            })
+        });
+        if let Some(id) = stale {
+            tracing::info!(
+                target: "strata::vector",
+                vector_id = id.as_u64(),
+                "Graph mmap stale (vector changed since freeze), rebuilding"
+            );
+            return Ok(false);
+        }
+
+        if self.config.storage == VectorStorage::InMemory {
+            for seg in &mut loaded_segments {
+                seg.graph.load_neighbors_into_memory();
+            }
+        }
+
         // Success: replace sealed segments and clear pending timestamps
         self.sealed = loaded_segments;
         self.next_segment_id = max_segment_id;
@@ -1066,11 +1092,19 @@ impl VectorIndexBackend for SegmentedHnswBackend {
         // Move any remaining active buffer entries that belong to loaded
         // segments out of the active buffer (they're already in sealed graphs).
         // On recovery, all vectors start in active buffer; after loading graphs,
-        // only vectors NOT in any sealed segment should remain in active.
+        // only vectors NOT live in any sealed segment should remain in active.
+        // A vector updated after its segment was sealed is deleted there and
+        // stays in active.
         let sealed_ids: std::collections::BTreeSet<VectorId> = self
             .sealed
             .iter()
-            .flat_map(|seg| seg.graph.nodes.keys().copied())
+            .flat_map(|seg| {
+                seg.graph
+                    .nodes
+                    .iter()
+                    .filter(|(_, node)| node.deleted_at.is_none())
+                    .map(|(&id, _)| id)
+            })
             .collect();
         self.active.ids.retain(|id| !sealed_ids.contains(id));
         self.active
@@ -1100,6 +1134,7 @@ mod tests {
             hnsw: HnswConfig::default(),
             seal_threshold,
             heap_flush_threshold: 0, // Disable flushing in tests
+            storage: VectorStorage::Mmap,
         };
         SegmentedHnswBackend::new(&config, seg_config)
     }
@@ -1894,6 +1929,73 @@ mod tests {
         assert!(!loaded, "Should detect stale graphs and return false");
     }
 
+    #[test]
+    fn test_load_graphs_keeps_vectors_added_after_freeze() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().join("graphs");
+
+        let mut backend = make_backend_with_threshold(3, DistanceMetric::Cosine, 3);
+        for (i, emb) in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+            .iter()
+            .enumerate()
+        {
+            let id = i as u64 + 1;
+            backend
+                .insert_with_timestamp(VectorId::new(id), emb, id * 10)
+                .unwrap();
+        }
+        // One manifest plus one graph file per sealed segment
+        assert_eq!(backend.freeze_graphs_to_disk(&dir).unwrap(), 2);
+
+        // Recovery registers a fourth vector written after the freeze
+        let mut backend2 = make_backend_with_threshold(3, DistanceMetric::Cosine, 3);
+        for (i, emb) in [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 1.0, 0.0],
+        ]
+        .iter()
+        .enumerate()
+        {
+            let id = i as u64 + 1;
+            backend2
+                .insert_with_id_and_timestamp(VectorId::new(id), emb, id * 10)
+                .unwrap();
+        }
+
+        assert!(backend2.load_graphs_from_disk(&dir).unwrap());
+        assert_eq!(backend2.segment_count(), 1);
+        assert_eq!(backend2.active_buffer_len(), 1);
+        assert_eq!(backend2.search(&[1.0, 1.0, 0.0], 1)[0].0, VectorId::new(4));
+    }
+
+    #[test]
+    fn test_load_graphs_rejects_recreated_vector() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().join("graphs");
+
+        let mut backend = make_backend_with_threshold(3, DistanceMetric::Cosine, 3);
+        for id in 1..=3u64 {
+            backend
+                .insert_with_timestamp(VectorId::new(id), &[id as f32, 1.0, 0.0], id * 10)
+                .unwrap();
+        }
+        backend.freeze_graphs_to_disk(&dir).unwrap();
+
+        // Vector 3 was deleted and written again after the freeze
+        let mut backend2 = make_backend_with_threshold(3, DistanceMetric::Cosine, 3);
+        for id in 1..=3u64 {
+            let created_at = if id == 3 { 99 } else { id * 10 };
+            backend2
+                .insert_with_id_and_timestamp(VectorId::new(id), &[id as f32, 1.0, 0.0], created_at)
+                .unwrap();
+        }
+
+        assert!(!backend2.load_graphs_from_disk(&dir).unwrap());
+        assert_eq!(backend2.segment_count(), 0);
+    }
+
     #[test]
     fn test_load_graphs_missing_manifest() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -1935,6 +2037,7 @@ mod tests {
             hnsw: HnswConfig::default(),
             seal_threshold: 100,     // high seal threshold to avoid sealing
             heap_flush_threshold: 5, // flush after 5 overlay vectors
+            storage: VectorStorage::Mmap,
         };
         let mut backend = SegmentedHnswBackend::new(&config, seg_config);
 
@@ -1985,6 +2088,7 @@ mod tests {
             hnsw: HnswConfig::default(),
             seal_threshold: 100,
             heap_flush_threshold: 0, // disabled
+            storage: VectorStorage::Mmap,
         };
         let mut backend = SegmentedHnswBackend::new(&config, seg_config);
         backend.flush_path = Some(std::path::PathBuf::from("/tmp/dummy.vec"));
@@ -2007,6 +2111,7 @@ mod tests {
             hnsw: HnswConfig::default(),
             seal_threshold: 100,
             heap_flush_threshold: 1,
+            storage: VectorStorage::Mmap,
         };
         let mut backend = SegmentedHnswBackend::new(&config, seg_config);
         // flush_path is None (in-memory database)
@@ -2019,6 +2124,55 @@ mod tests {
         backend.flush_heap_if_needed();
     }
 
+    #[test]
+    fn test_in_memory_storage_keeps_heap_and_graphs_resident() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vec_path = temp_dir.path().join("test.vec");
+        let dir = temp_dir.path().join("graphs");
+
+        let mut backend = make_backend_with_threshold(3, DistanceMetric::Cosine, 3);
+        for id in 1..=3u64 {
+            backend
+                .insert_with_timestamp(VectorId::new(id), &[id as f32, 1.0, 0.0], id * 10)
+                .unwrap();
+        }
+        backend.freeze_heap_to_disk(&vec_path).unwrap();
+        backend.freeze_graphs_to_disk(&dir).unwrap();
+
+        // Recover the way vector recovery does, with in-memory storage
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        let seg_config = SegmentedHnswConfig {
+            hnsw: HnswConfig::default(),
+            seal_threshold: 3,
+            heap_flush_threshold: 1,
+            storage: VectorStorage::InMemory,
+        };
+        let mut backend2 = SegmentedHnswBackend::new(&config, seg_config);
+        backend2.replace_heap(VectorHeap::from_mmap(&vec_path, config.clone()).unwrap());
+        for id in 1..=3u64 {
+            backend2.register_mmap_vector(VectorId::new(id), id * 10);
+        }
+        assert!(backend2.load_graphs_from_disk(&dir).unwrap());
+        assert!(!backend2.sealed[0].graph.is_neighbor_data_mmap());
+        assert!(backend2.is_heap_mmap());
+
+        assert!(!backend2.flush_heap_to_disk_if_needed(&vec_path).unwrap());
+        assert!(!backend2.is_heap_mmap());
+        assert!(backend2.flush_path.is_none());
+        assert_eq!(
+            backend2.heap.get(VectorId::new(2)).unwrap(),
+            &[2.0, 1.0, 0.0]
+        );
+
+        // Inserts past the flush threshold stay in memory
+        backend2
+            .insert_with_timestamp(VectorId::new(4), &[4.0, 1.0, 0.0], 40)
+            .unwrap();
+        backend2.flush_heap_if_needed();
+        assert!(!backend2.is_heap_mmap());
+        assert_eq!(backend2.search(&[4.0, 1.0, 0.0], 1)[0].0, VectorId::new(4));
+    }
+
     #[test]
     fn test_vectors_searchable_after_mid_indexing_flush() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -2029,6 +2183,7 @@ mod tests {
             hnsw: HnswConfig::default(),
             seal_threshold: 3,
             heap_flush_threshold: 5,
+            storage: VectorStorage::Mmap,
         };
         let mut backend = SegmentedHnswBackend::new(&config, seg_config);
 
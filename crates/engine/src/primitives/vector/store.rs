@@ -27,10 +27,12 @@
 use crate::database::Database;
 use crate::primitives::extensions::VectorStoreExt;
 use crate::primitives::vector::collection::{validate_collection_name, validate_vector_key};
+use crate::primitives::vector::distance::similarity_to_distance;
 use crate::primitives::vector::{
-    CollectionId, CollectionInfo, CollectionRecord, IndexBackendFactory, MetadataFilter,
-    VectorConfig, VectorEntry, VectorError, VectorId, VectorIndexBackend, VectorMatch,
-    VectorMatchWithSource, VectorRecord, VectorResult,
+    CollectionConfig, CollectionId, CollectionInfo, CollectionRecord, DistanceMetric, HnswConfig,
+    IndexBackendFactory, IntoCollectionConfig, MetadataFilter, StorageDtype, VectorConfig,
+    VectorEntry, VectorError, VectorId, VectorIndexBackend, VectorMatch, VectorMatchWithSource,
+    VectorRecord, VectorResult,
 };
 use parking_lot::RwLock;
 use serde_json::Value as JsonValue;
@@ -41,6 +43,7 @@ use strata_core::contract::{Timestamp, Version, Versioned};
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
 use strata_core::EntityRef;
+use strata_storage::ShardedSnapshot;
 use tracing::{debug, info};
 
 /// Statistics from vector recovery
@@ -58,6 +61,72 @@ pub struct RecoveryStats {
     pub vectors_deleted: usize,
     /// Number of lite records skipped (no mmap cache available)
     pub lite_records_skipped: usize,
+    /// Number of collections whose sealed graphs were loaded from mmap cache
+    pub graphs_loaded: usize,
+}
+
+/// Outcome of [`VectorStore::insert_if_novel`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum NovelInsert {
+    /// No existing vector was within the threshold; the vector was written
+    Inserted(Version),
+    /// The nearest existing vector was closer than the threshold; nothing
+    /// was written
+    Duplicate {
+        /// Key of the nearest existing vector
+        key: String,
+        /// Its distance from the candidate (see
+        /// [`similarity_to_distance`](crate::primitives::vector::distance::similarity_to_distance))
+        distance: f32,
+    },
+}
+
+/// How [`VectorStore::upsert`] treats a key that already holds a vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpsertMode {
+    /// Overwrite the embedding and metadata, keeping the existing `VectorId`
+    #[default]
+    Replace,
+    /// Fail with [`VectorError::VectorAlreadyExists`] and leave the existing
+    /// vector untouched
+    Error,
+}
+
+/// Outcome of [`VectorStore::upsert`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upserted {
+    /// Version of the write
+    pub version: Version,
+    /// Whether the key was new; `false` if an existing vector was replaced
+    pub created: bool,
+}
+
+/// Outcome of `insert_inner`
+enum InsertOutcome {
+    Written(Upserted),
+    Duplicate { key: String, distance: f32 },
+}
+
+impl InsertOutcome {
+    /// Unwrap the result of an insert made without a novelty threshold,
+    /// which never reports a duplicate.
+    fn expect_written(self) -> Upserted {
+        match self {
+            InsertOutcome::Written(upserted) => upserted,
+            InsertOutcome::Duplicate { .. } => {
+                unreachable!("insert without a novelty threshold never skips")
+            }
+        }
+    }
+}
+
+impl From<InsertOutcome> for NovelInsert {
+    fn from(outcome: InsertOutcome) -> Self {
+        match outcome {
+            InsertOutcome::Written(upserted) => NovelInsert::Inserted(upserted.version),
+            InsertOutcome::Duplicate { key, distance } => NovelInsert::Duplicate { key, distance },
+        }
+    }
 }
 
 /// Shared backend state for VectorStore
@@ -138,6 +207,9 @@ impl VectorStore {
     /// This returns the shared `VectorBackendState` stored in the Database.
     /// All VectorStore instances for the same Database share this state.
     fn state(&self) -> Result<Arc<VectorBackendState>, VectorError> {
+        // Backends are rebuilt by vector recovery; wait for it after a
+        // background open
+        self.db.wait_for_recovery();
         self.db
             .extension::<VectorBackendState>()
             .map_err(|e| VectorError::Storage(e.to_string()))
@@ -148,9 +220,11 @@ impl VectorStore {
         Namespace::for_branch_space(branch_id, space)
     }
 
-    /// Get the backend factory (hardcoded currently, configurable in future versions)
-    fn backend_factory(&self) -> IndexBackendFactory {
-        IndexBackendFactory::SegmentedHnsw(super::segmented::SegmentedHnswConfig::default())
+    /// Get the backend factory for a collection's HNSW parameters
+    ///
+    /// `None` selects the default parameters.
+    fn backend_factory(&self, hnsw: Option<&HnswConfig>) -> IndexBackendFactory {
+        IndexBackendFactory::segmented_hnsw(hnsw, self.db.vector_storage())
     }
 
     // ========================================================================
@@ -159,36 +233,71 @@ impl VectorStore {
 
     /// Create a new collection
     ///
-    /// Creates a collection with the specified configuration.
-    /// The configuration (dimension, metric, dtype) is immutable after creation.
+    /// Accepts a plain [`VectorConfig`] or, for HNSW tuning and insert-time
+    /// normalization, a [`CollectionConfigBuilder`](super::CollectionConfigBuilder)
+    /// or [`CollectionConfig`]. The configuration is immutable after creation.
     ///
     /// # Errors
     /// - `CollectionAlreadyExists` if a collection with this name exists
     /// - `InvalidCollectionName` if name is invalid
-    /// - `InvalidDimension` if dimension is 0
+    /// - `InvalidDimension` if dimension is 0 or above [`MAX_DIMENSION`](super::MAX_DIMENSION)
+    /// - `InvalidConfig` if any other option is out of range
     pub fn create_collection(
         &self,
         branch_id: BranchId,
         space: &str,
         name: &str,
-        config: VectorConfig,
+        config: impl IntoCollectionConfig,
     ) -> VectorResult<Versioned<CollectionInfo>> {
         // Validate name
         validate_collection_name(name)?;
 
-        // Validate config (dimension must be > 0 and <= MAX_DIMENSION)
-        const MAX_DIMENSION: usize = 65536;
-        if config.dimension == 0 {
-            return Err(VectorError::InvalidDimension {
-                dimension: config.dimension,
-            });
-        }
-        if config.dimension > MAX_DIMENSION {
-            return Err(VectorError::InvalidDimension {
-                dimension: config.dimension,
-            });
-        }
+        // Validate config
+        let collection_config = config.into_collection_config()?;
+
+        self.store_collection(branch_id, space, name, collection_config)
+    }
+
+    /// Create a collection whose dimension is set by its first insert
+    ///
+    /// The collection is stored with dimension 0 and locked to the length of
+    /// the first embedding inserted into it; from then on it behaves like a
+    /// collection created with that dimension, and mismatched inserts fail
+    /// with `DimensionMismatch`. Until then searches return no matches.
+    ///
+    /// # Errors
+    /// - `CollectionAlreadyExists` if a collection with this name exists
+    /// - `InvalidCollectionName` if name is invalid
+    pub fn create_collection_auto(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        name: &str,
+        metric: DistanceMetric,
+    ) -> VectorResult<Versioned<CollectionInfo>> {
+        validate_collection_name(name)?;
+
+        let collection_config = CollectionConfig {
+            vector: VectorConfig {
+                dimension: 0,
+                metric,
+                storage_dtype: StorageDtype::F32,
+            },
+            hnsw: None,
+            normalize: false,
+            metadata_schema: None,
+        };
+        self.store_collection(branch_id, space, name, collection_config)
+    }
 
+    /// Store a validated collection record and initialize its backend
+    fn store_collection(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        name: &str,
+        collection_config: CollectionConfig,
+    ) -> VectorResult<Versioned<CollectionInfo>> {
         let collection_id = CollectionId::new(branch_id, name);
 
         // Check if collection already exists
@@ -201,7 +310,7 @@ impl VectorStore {
         let now = now_micros();
 
         // Create collection record
-        let record = CollectionRecord::new(&config);
+        let record = CollectionRecord::from_collection_config(&collection_config);
 
         // Store config in KV
         let config_key = Key::new_vector_config(self.namespace_for(branch_id, space), name);
@@ -215,7 +324,8 @@ impl VectorStore {
             .map_err(|e| VectorError::Storage(e.to_string()))?;
 
         // Initialize in-memory backend
-        self.init_backend(&collection_id, &config)?;
+        let config = collection_config.vector;
+        self.init_backend(&collection_id, &config, collection_config.hnsw.as_ref())?;
 
         let info = CollectionInfo {
             name: name.to_string(),
@@ -277,6 +387,93 @@ impl VectorStore {
         Ok(())
     }
 
+    /// Check that `upsert` would accept `key` and `embedding`, without
+    /// loading the collection or writing anything.
+    ///
+    /// Runs the checks `upsert` makes before writing, in the same order.
+    ///
+    /// # Errors
+    /// - `InvalidKey` if key is invalid
+    /// - `InvalidEmbedding` for NaN or Infinity values, or a zero vector in a
+    ///   normalizing collection
+    /// - `CollectionNotFound` if collection doesn't exist
+    /// - `DimensionMismatch` if embedding dimension doesn't match config
+    pub fn check_insert(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        key: &str,
+        embedding: &[f32],
+    ) -> VectorResult<()> {
+        validate_vector_key(key)?;
+        if embedding.iter().any(|v| v.is_nan() || v.is_infinite()) {
+            return Err(VectorError::InvalidEmbedding {
+                reason: "embedding contains NaN or Infinity values".to_string(),
+            });
+        }
+        let record = self.get_collection_record_required(branch_id, space, collection)?;
+        let normalize = record.normalize;
+        let config = VectorConfig::try_from(record.config)?;
+        if config.dimension == 0 {
+            check_dimension(embedding.len())?;
+        } else if embedding.len() != config.dimension {
+            return Err(VectorError::DimensionMismatch {
+                expected: config.dimension,
+                got: embedding.len(),
+            });
+        }
+        if normalize {
+            super::config::normalize_embedding(embedding)?;
+        }
+        Ok(())
+    }
+
+    /// Check that `search`, or `search_at` when `as_of_ts` is set, would
+    /// accept `query` and `filter`, without loading the collection.
+    ///
+    /// # Errors
+    /// - `CollectionNotFound` if collection doesn't exist
+    /// - `UnknownFilterField` or `InvalidFilter` if the collection schema
+    ///   rejects the filter
+    /// - `DimensionMismatch` if query dimension doesn't match config
+    /// - `InvalidEmbedding` for NaN or Infinity values in a `search_at` query
+    #[allow(clippy::too_many_arguments)]
+    pub fn check_search(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        query: &[f32],
+        k: usize,
+        filter: Option<&MetadataFilter>,
+        as_of_ts: Option<u64>,
+    ) -> VectorResult<()> {
+        if k == 0 && as_of_ts.is_none() {
+            return Ok(());
+        }
+        let record = self.get_collection_record_required(branch_id, space, collection)?;
+        if let (Some(schema), Some(filter)) = (&record.metadata_schema, filter) {
+            schema.validate_filter(collection, filter)?;
+        }
+        let config = VectorConfig::try_from(record.config)?;
+        if config.dimension == 0 {
+            return Ok(());
+        }
+        if query.len() != config.dimension {
+            return Err(VectorError::DimensionMismatch {
+                expected: config.dimension,
+                got: query.len(),
+            });
+        }
+        if as_of_ts.is_some() && query.iter().any(|v| v.is_nan() || v.is_infinite()) {
+            return Err(VectorError::InvalidEmbedding {
+                reason: "query contains NaN or Infinity values".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     /// List all collections for a branch
     ///
     /// Returns CollectionInfo for each collection, including current vector count.
@@ -421,10 +618,87 @@ impl VectorStore {
         embedding: &[f32],
         metadata: Option<JsonValue>,
     ) -> VectorResult<Version> {
-        self.insert_inner(branch_id, space, collection, key, embedding, metadata, None)
+        self.upsert(
+            branch_id,
+            space,
+            collection,
+            key,
+            embedding,
+            metadata,
+            UpsertMode::Replace,
+        )
+        .map(|upserted| upserted.version)
+    }
+
+    /// Insert a vector, choosing what happens if the key already exists
+    ///
+    /// With [`UpsertMode::Replace`] an existing vector's embedding and
+    /// metadata are overwritten and its `VectorId` is kept, so the index
+    /// entry is updated in place. With [`UpsertMode::Error`] an existing key
+    /// fails with [`VectorError::VectorAlreadyExists`]. The result says
+    /// whether the key was new.
+    ///
+    /// # Errors
+    /// Same as [`insert`](Self::insert), plus `VectorAlreadyExists`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn upsert(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        key: &str,
+        embedding: &[f32],
+        metadata: Option<JsonValue>,
+        mode: UpsertMode,
+    ) -> VectorResult<Upserted> {
+        let outcome = self.insert_inner(
+            branch_id, space, collection, key, embedding, metadata, None, None, mode,
+        )?;
+        Ok(outcome.expect_written())
+    }
+
+    /// Insert a vector unless a near-duplicate already exists
+    ///
+    /// Searches for the nearest existing vector and skips the write if its
+    /// distance from `embedding` is below `min_distance`. Distances follow
+    /// the collection metric (`1 - cosine` for Cosine, L2 for Euclidean, see
+    /// [`similarity_to_distance`](crate::primitives::vector::distance::similarity_to_distance)).
+    ///
+    /// The search and the write happen under the collection's write lock,
+    /// so concurrent novel inserts cannot both slip past the check.
+    ///
+    /// # Errors
+    /// Same as [`insert`](Self::insert).
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_if_novel(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        key: &str,
+        embedding: &[f32],
+        metadata: Option<JsonValue>,
+        min_distance: f32,
+    ) -> VectorResult<NovelInsert> {
+        self.insert_inner(
+            branch_id,
+            space,
+            collection,
+            key,
+            embedding,
+            metadata,
+            None,
+            Some(min_distance),
+            UpsertMode::Replace,
+        )
+        .map(NovelInsert::from)
     }
 
-    /// Common insert implementation used by both `insert()` and `system_insert_with_source()`.
+    /// Common insert implementation used by `insert()`, `insert_if_novel()`
+    /// and `system_insert_with_source()`.
+    ///
+    /// With `min_distance` set, skips the write when the nearest existing
+    /// vector is closer than that distance.
     #[allow(clippy::too_many_arguments)]
     fn insert_inner(
         &self,
@@ -435,7 +709,9 @@ impl VectorStore {
         embedding: &[f32],
         metadata: Option<JsonValue>,
         source_ref: Option<EntityRef>,
-    ) -> VectorResult<Version> {
+        min_distance: Option<f32>,
+        mode: UpsertMode,
+    ) -> VectorResult<InsertOutcome> {
         // Validate key
         validate_vector_key(key)?;
 
@@ -451,8 +727,16 @@ impl VectorStore {
 
         let collection_id = CollectionId::new(branch_id, collection);
 
-        // Validate dimension
-        let config = self.get_collection_config_required(branch_id, space, collection)?;
+        // Validate dimension; a collection created without one takes this
+        // embedding's
+        let record = self.get_collection_record_required(branch_id, space, collection)?;
+        let normalize = record.normalize;
+        let mut config = VectorConfig::try_from(record.config)?;
+        let pending_dimension = config.dimension == 0;
+        if pending_dimension {
+            check_dimension(embedding.len())?;
+            config.dimension = embedding.len();
+        }
         if embedding.len() != config.dimension {
             return Err(VectorError::DimensionMismatch {
                 expected: config.dimension,
@@ -460,6 +744,14 @@ impl VectorStore {
             });
         }
 
+        let normalized;
+        let embedding = if normalize {
+            normalized = super::config::normalize_embedding(embedding)?;
+            normalized.as_slice()
+        } else {
+            embedding
+        };
+
         // Serialize metadata to bytes for WAL storage (before it's consumed)
         let _metadata_bytes = metadata
             .as_ref()
@@ -475,6 +767,17 @@ impl VectorStore {
         // state (fixes #937).
         let state = self.state()?;
         let mut backends = state.backends.write();
+        let dimension_lock = if pending_dimension {
+            self.lock_dimension(
+                &mut backends,
+                branch_id,
+                space,
+                collection,
+                config.dimension,
+            )?
+        } else {
+            None
+        };
         let backend =
             backends
                 .get_mut(&collection_id)
@@ -485,6 +788,37 @@ impl VectorStore {
         // Check existence under write lock
         let existing = self.get_vector_record_by_key(&kv_key)?;
 
+        // Novelty check under the same write lock as the insert, so the
+        // nearest neighbour cannot change between the search and the write.
+        // An update must not match the key's own current vector, so search
+        // one extra neighbour and skip it.
+        if let Some(min_distance) = min_distance {
+            let own_id = existing.as_ref().map(|r| VectorId(r.vector_id));
+            let nearest = backend
+                .search(embedding, 2)
+                .into_iter()
+                .find(|(id, _)| Some(*id) != own_id);
+            if let Some((nearest_id, score)) = nearest {
+                let distance = similarity_to_distance(score, config.metric);
+                if distance < min_distance {
+                    let (nearest_key, _) =
+                        self.get_key_and_metadata(branch_id, space, collection, nearest_id)?;
+                    return Ok(InsertOutcome::Duplicate {
+                        key: nearest_key,
+                        distance,
+                    });
+                }
+            }
+        }
+
+        if existing.is_some() && mode == UpsertMode::Error {
+            return Err(VectorError::VectorAlreadyExists {
+                collection: collection.to_string(),
+                key: key.to_string(),
+            });
+        }
+        let created = existing.is_none();
+
         let (vector_id, record) = if let Some(existing_record) = existing {
             // Update existing: keep the same VectorId
             let mut updated = existing_record;
@@ -508,6 +842,9 @@ impl VectorStore {
         let record_bytes = record.to_bytes()?;
         self.db
             .transaction(branch_id, |txn| {
+                if let Some((config_key, config_value)) = &dimension_lock {
+                    txn.put(config_key.clone(), config_value.clone())?;
+                }
                 txn.put(kv_key.clone(), Value::Bytes(record_bytes.clone()))
             })
             .map_err(|e| VectorError::Storage(e.to_string()))?;
@@ -519,7 +856,10 @@ impl VectorStore {
 
         debug!(target: "strata::vector", collection, branch_id = %branch_id, "Vector upserted");
 
-        Ok(Version::counter(record_version))
+        Ok(InsertOutcome::Written(Upserted {
+            version: Version::counter(record_version),
+            created,
+        }))
     }
 
     /// Get a vector by key
@@ -659,6 +999,48 @@ impl VectorStore {
         }))
     }
 
+    /// Iterate over every vector in a collection in `VectorId` order
+    ///
+    /// The scan pins a storage snapshot, so writes made after the call are
+    /// not observed and deleted vectors are skipped. Only an index from
+    /// `VectorId` to key is built up front; each record is read, decoded and
+    /// given its embedding as the iterator advances. Version garbage
+    /// collection is held back until the iterator is dropped.
+    pub fn scan(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+    ) -> VectorResult<VectorScan> {
+        use strata_core::traits::SnapshotView;
+
+        self.ensure_collection_loaded(branch_id, space, collection)?;
+        self.get_collection_config_required(branch_id, space, collection)?;
+
+        let prefix =
+            Key::vector_collection_prefix(self.namespace_for(branch_id, space), collection);
+        let snapshot = self.db.storage().pinned_snapshot();
+        let ids: BTreeMap<VectorId, Key> = snapshot
+            .scan_prefix(&prefix)
+            .map_err(|e| VectorError::Storage(e.to_string()))?
+            .into_iter()
+            .filter_map(|(key, versioned)| match versioned.value {
+                Value::Bytes(bytes) => VectorRecord::from_bytes(&bytes)
+                    .ok()
+                    .map(|record| (VectorId(record.vector_id), key)),
+                _ => None,
+            })
+            .collect();
+
+        Ok(VectorScan {
+            collection: collection.to_string(),
+            collection_id: CollectionId::new(branch_id, collection),
+            state: self.state()?,
+            snapshot,
+            ids: ids.into_iter(),
+        })
+    }
+
     /// Delete a vector by key
     ///
     /// Returns true if the vector existed and was deleted.
@@ -722,7 +1104,15 @@ impl VectorStore {
         }
 
         // Validate all entries before acquiring locks
-        let config = self.get_collection_config_required(branch_id, space, collection)?;
+        let collection_record =
+            self.get_collection_record_required(branch_id, space, collection)?;
+        let normalize = collection_record.normalize;
+        let mut config = VectorConfig::try_from(collection_record.config)?;
+        let pending_dimension = config.dimension == 0;
+        if pending_dimension {
+            config.dimension = entries[0].1.len();
+            check_dimension(config.dimension)?;
+        }
         for (key, embedding, _) in &entries {
             validate_vector_key(key)?;
             if embedding.iter().any(|v| v.is_nan() || v.is_infinite()) {
@@ -739,6 +1129,11 @@ impl VectorStore {
                     got: embedding.len(),
                 });
             }
+            if normalize && embedding.iter().all(|v| *v == 0.0) {
+                return Err(VectorError::InvalidEmbedding {
+                    reason: format!("cannot normalize zero vector for key '{}'", key),
+                });
+            }
         }
 
         // Ensure collection is loaded
@@ -748,6 +1143,17 @@ impl VectorStore {
         // Acquire write lock once for the entire batch
         let state = self.state()?;
         let mut backends = state.backends.write();
+        let dimension_lock = if pending_dimension {
+            self.lock_dimension(
+                &mut backends,
+                branch_id,
+                space,
+                collection,
+                config.dimension,
+            )?
+        } else {
+            None
+        };
         let backend =
             backends
                 .get_mut(&collection_id)
@@ -759,7 +1165,8 @@ impl VectorStore {
         let batch_count = entries.len();
 
         // Prepare all records and accumulate KV writes for a single transaction
-        let mut kv_writes: Vec<(Key, Value)> = Vec::with_capacity(entries.len());
+        let mut kv_writes: Vec<(Key, Value)> = Vec::with_capacity(entries.len() + 1);
+        kv_writes.extend(dimension_lock);
         let mut backend_updates: Vec<(VectorId, Vec<f32>, u64)> = Vec::with_capacity(entries.len());
 
         for (key, embedding, metadata) in entries {
@@ -781,6 +1188,11 @@ impl VectorStore {
             let record_version = record.version;
             let record_bytes = record.to_bytes()?;
             kv_writes.push((kv_key, Value::Bytes(record_bytes)));
+            let embedding = if normalize {
+                super::config::normalize_embedding(&embedding)?
+            } else {
+                embedding
+            };
             backend_updates.push((vector_id, embedding, record.created_at));
             versions.push(Version::counter(record_version));
         }
@@ -812,6 +1224,9 @@ impl VectorStore {
     /// Returns top-k vectors most similar to the query.
     /// Metadata filtering is applied as post-filter.
     ///
+    /// A collection that exists but holds no vectors returns an empty
+    /// result; a missing collection is `CollectionNotFound`, including for k=0.
+    ///
     /// # Invariants Satisfied
     /// - R1: Dimension validated against collection config
     /// - R2: Scores normalized to "higher = more similar"
@@ -827,20 +1242,57 @@ impl VectorStore {
         k: usize,
         filter: Option<MetadataFilter>,
     ) -> VectorResult<Vec<VectorMatch>> {
-        let start = std::time::Instant::now();
+        self.search_with_overfetch(
+            branch_id,
+            space,
+            collection,
+            query,
+            k,
+            filter,
+            DEFAULT_OVERFETCH_FACTOR,
+        )
+    }
 
-        // k=0 returns empty
-        if k == 0 {
-            return Ok(Vec::new());
-        }
+    /// Search for k nearest neighbors, controlling filter over-fetch
+    ///
+    /// Like [`search`](Self::search), but when `filter` is set the backend is
+    /// first asked for `k * overfetch_factor` candidates, doubling up to
+    /// [`OVERFETCH_ROUNDS`] times until k candidates pass the filter. Higher
+    /// factors return a full k for selective filters at the cost of scoring
+    /// and resolving more candidates. Factors below 1.0 are treated as 1.0.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_with_overfetch(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        query: &[f32],
+        k: usize,
+        filter: Option<MetadataFilter>,
+        overfetch_factor: f32,
+    ) -> VectorResult<Vec<VectorMatch>> {
+        let start = std::time::Instant::now();
 
-        // Ensure collection is loaded
+        // Ensure collection is loaded (a missing collection is an error even for k=0)
         self.ensure_collection_loaded(branch_id, space, collection)?;
 
         let collection_id = CollectionId::new(branch_id, collection);
 
-        // Validate query dimension
-        let config = self.get_collection_config_required(branch_id, space, collection)?;
+        // Validate query dimension and filter fields
+        let record = self.get_collection_record_required(branch_id, space, collection)?;
+
+        // k=0 returns empty
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        if let (Some(schema), Some(filter)) = (&record.metadata_schema, &filter) {
+            schema.validate_filter(collection, filter)?;
+        }
+        let config = VectorConfig::try_from(record.config)?;
+        if config.dimension == 0 {
+            // Nothing inserted yet, so no dimension to check against
+            return Ok(Vec::new());
+        }
         if query.len() != config.dimension {
             return Err(VectorError::DimensionMismatch {
                 expected: config.dimension,
@@ -854,7 +1306,7 @@ impl VectorStore {
         // for filtered-out results. If the initial fetch doesn't yield enough results,
         // we retry with a higher multiplier up to a max limit.
         //
-        // Multiplier strategy: 3x -> 6x -> 12x -> all (capped at collection size)
+        // Multiplier strategy (default factor 3): 3x -> 6x -> 12x, capped at collection size
         let mut matches = Vec::with_capacity(k);
 
         if filter.is_none() {
@@ -871,25 +1323,20 @@ impl VectorStore {
             };
 
             for (vector_id, score) in candidates {
-                let (key, metadata) =
-                    self.get_key_and_metadata(branch_id, space, collection, vector_id)?;
-                matches.push(VectorMatch {
-                    key,
-                    score,
-                    metadata,
-                });
+                let (key, record) =
+                    self.get_key_and_record(branch_id, space, collection, vector_id)?;
+                matches.push(self.to_match(&collection_id, vector_id, key, score, record)?);
             }
         } else {
             // Filter active - use adaptive over-fetch
-            let multipliers = [3, 6, 12];
             let collection_size = {
                 let state = self.state()?;
                 let backends = state.backends.read();
                 backends.get(&collection_id).map(|b| b.len()).unwrap_or(0)
             };
 
-            for &mult in &multipliers {
-                let fetch_k = (k * mult).min(collection_size);
+            for mult in overfetch_multipliers(overfetch_factor) {
+                let fetch_k = overfetch_k(k, mult).min(collection_size);
                 if fetch_k == 0 {
                     break;
                 }
@@ -907,21 +1354,17 @@ impl VectorStore {
 
                 matches.clear();
                 for (vector_id, score) in candidates {
-                    let (key, metadata) =
-                        self.get_key_and_metadata(branch_id, space, collection, vector_id)?;
+                    let (key, record) =
+                        self.get_key_and_record(branch_id, space, collection, vector_id)?;
 
                     // Apply filter
                     if let Some(ref f) = filter {
-                        if !f.matches(&metadata) {
+                        if !f.matches(&record.metadata) {
                             continue;
                         }
                     }
 
-                    matches.push(VectorMatch {
-                        key,
-                        score,
-                        metadata,
-                    });
+                    matches.push(self.to_match(&collection_id, vector_id, key, score, record)?);
                     if matches.len() >= k {
                         break;
                     }
@@ -954,7 +1397,8 @@ impl VectorStore {
     /// Search for k nearest neighbors as of a given timestamp.
     ///
     /// Uses temporal filtering in the backend (HNSW nodes alive at as_of_ts)
-    /// and historical metadata from the version chain.
+    /// and historical metadata from the version chain. `overfetch_factor`
+    /// behaves as in [`search_with_overfetch`](Self::search_with_overfetch).
     #[allow(clippy::too_many_arguments)]
     pub fn search_at(
         &self,
@@ -965,14 +1409,22 @@ impl VectorStore {
         k: usize,
         filter: Option<MetadataFilter>,
         as_of_ts: u64,
+        overfetch_factor: f32,
     ) -> VectorResult<Vec<VectorMatch>> {
         // Ensure collection is loaded
         self.ensure_collection_loaded(branch_id, space, collection)?;
 
         let collection_id = CollectionId::new(branch_id, collection);
 
-        // Validate dimension
-        let config = self.get_collection_config_required(branch_id, space, collection)?;
+        // Validate dimension and filter fields
+        let record = self.get_collection_record_required(branch_id, space, collection)?;
+        if let (Some(schema), Some(filter)) = (&record.metadata_schema, &filter) {
+            schema.validate_filter(collection, filter)?;
+        }
+        let config = VectorConfig::try_from(record.config)?;
+        if config.dimension == 0 {
+            return Ok(Vec::new());
+        }
         if query.len() != config.dimension {
             return Err(VectorError::DimensionMismatch {
                 expected: config.dimension,
@@ -987,56 +1439,96 @@ impl VectorStore {
             });
         }
 
-        // Search backend with temporal filtering
-        let fetch_k = if filter.is_some() { k * 4 } else { k };
+        // Search backend with temporal filtering, over-fetching when a
+        // metadata filter may discard candidates
+        let multipliers = if filter.is_some() {
+            overfetch_multipliers(overfetch_factor)
+        } else {
+            vec![1.0]
+        };
         let state = self.state()?;
-        let backends = state.backends.read();
-        let backend =
-            backends
-                .get(&collection_id)
-                .ok_or_else(|| VectorError::CollectionNotFound {
-                    name: collection.to_string(),
-                })?;
-
-        let candidates = backend.search_at(query, fetch_k, as_of_ts);
-        drop(backends);
-
-        // Resolve keys and metadata from historical records
         let mut matches = Vec::new();
-        for (vector_id, score) in candidates {
-            // Find the key for this vector_id by scanning KV at timestamp
-            if let Some((key, metadata)) =
-                self.find_vector_key_metadata_at(branch_id, space, collection, vector_id, as_of_ts)?
-            {
-                // Apply metadata filter
-                if let Some(ref f) = filter {
-                    if !f.matches(&metadata) {
-                        continue;
+
+        for mult in multipliers {
+            let (candidates, collection_size) = {
+                let backends = state.backends.read();
+                let backend = backends.get(&collection_id).ok_or_else(|| {
+                    VectorError::CollectionNotFound {
+                        name: collection.to_string(),
+                    }
+                })?;
+                let fetch_k = overfetch_k(k, mult);
+                (backend.search_at(query, fetch_k, as_of_ts), backend.len())
+            };
+            let exhausted = overfetch_k(k, mult) >= collection_size;
+
+            // Resolve keys and metadata from historical records
+            matches.clear();
+            for (vector_id, score) in candidates {
+                // Find the key for this vector_id by scanning KV at timestamp
+                if let Some((key, record)) = self
+                    .find_vector_key_record_at(branch_id, space, collection, vector_id, as_of_ts)?
+                {
+                    // Apply metadata filter
+                    if let Some(ref f) = filter {
+                        if !f.matches(&record.metadata) {
+                            continue;
+                        }
+                    }
+                    matches.push(self.to_match(&collection_id, vector_id, key, score, record)?);
+                    if matches.len() >= k {
+                        break;
                     }
-                }
-                matches.push(VectorMatch {
-                    key,
-                    score,
-                    metadata,
-                });
-                if matches.len() >= k {
-                    break;
                 }
             }
+
+            if matches.len() >= k || exhausted {
+                break;
+            }
         }
 
         Ok(matches)
     }
 
-    /// Find a vector's key and metadata by VectorId at a given timestamp (internal helper).
-    fn find_vector_key_metadata_at(
+    /// Build a search match from a resolved record.
+    ///
+    /// Records written without their embedding take it from the collection's
+    /// in-memory heap, so matches never cost an extra storage read.
+    fn to_match(
+        &self,
+        collection_id: &CollectionId,
+        vector_id: VectorId,
+        key: String,
+        score: f32,
+        record: VectorRecord,
+    ) -> VectorResult<VectorMatch> {
+        let embedding = if record.embedding.is_empty() {
+            let state = self.state()?;
+            let backends = state.backends.read();
+            backends
+                .get(collection_id)
+                .and_then(|backend| backend.get(vector_id))
+                .map(|e| e.to_vec())
+        } else {
+            Some(record.embedding)
+        };
+        Ok(VectorMatch {
+            key,
+            score,
+            metadata: record.metadata,
+            embedding,
+        })
+    }
+
+    /// Find a vector's key and record by VectorId at a given timestamp (internal helper).
+    fn find_vector_key_record_at(
         &self,
         branch_id: BranchId,
         space: &str,
         collection: &str,
         target_id: VectorId,
         as_of_ts: u64,
-    ) -> VectorResult<Option<(String, Option<JsonValue>)>> {
+    ) -> VectorResult<Option<(String, VectorRecord)>> {
         let namespace = self.namespace_for(branch_id, space);
         let prefix = Key::vector_collection_prefix(namespace, collection);
         let results = self
@@ -1060,7 +1552,7 @@ impl VectorStore {
                     .strip_prefix(&format!("{}/", collection))
                     .unwrap_or(&user_key)
                     .to_string();
-                return Ok(Some((vector_key, record.metadata)));
+                return Ok(Some((vector_key, record)));
             }
         }
         Ok(None)
@@ -1071,8 +1563,26 @@ impl VectorStore {
     // ========================================================================
 
     /// Initialize the index backend for a collection
-    fn init_backend(&self, id: &CollectionId, config: &VectorConfig) -> Result<(), VectorError> {
-        let mut backend = self.backend_factory().create(config);
+    fn init_backend(
+        &self,
+        id: &CollectionId,
+        config: &VectorConfig,
+        hnsw: Option<&HnswConfig>,
+    ) -> Result<(), VectorError> {
+        let backend = self.new_backend(id, config, hnsw);
+        let state = self.state()?;
+        state.backends.write().insert(id.clone(), backend);
+        Ok(())
+    }
+
+    /// Create an empty index backend for a collection
+    fn new_backend(
+        &self,
+        id: &CollectionId,
+        config: &VectorConfig,
+        hnsw: Option<&HnswConfig>,
+    ) -> Box<dyn VectorIndexBackend> {
+        let mut backend = self.backend_factory(hnsw).create(config);
 
         // Set flush_path so the tiered heap can flush overlays during fresh
         // indexing (not just during recovery). Without this, fresh inserts
@@ -1085,10 +1595,48 @@ impl VectorStore {
             }
             let _ = backend.flush_heap_to_disk_if_needed(&vec_path);
         }
+        backend
+    }
 
-        let state = self.state()?;
-        state.backends.write().insert(id.clone(), backend);
-        Ok(())
+    /// Lock a collection created by [`create_collection_auto`](Self::create_collection_auto)
+    /// to `dimension`, replacing its empty backend
+    ///
+    /// Must be called with the backends write lock held, so concurrent first
+    /// inserts settle on one dimension. Returns the updated collection record
+    /// to commit with the insert, or `None` if the collection is already
+    /// locked to `dimension`.
+    ///
+    /// # Errors
+    /// - `DimensionMismatch` if the collection was locked to another dimension
+    fn lock_dimension(
+        &self,
+        backends: &mut BTreeMap<CollectionId, Box<dyn VectorIndexBackend>>,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        dimension: usize,
+    ) -> VectorResult<Option<(Key, Value)>> {
+        let mut record = self.get_collection_record_required(branch_id, space, collection)?;
+        match record.config.dimension {
+            0 => {}
+            locked if locked == dimension => return Ok(None),
+            locked => {
+                return Err(VectorError::DimensionMismatch {
+                    expected: locked,
+                    got: dimension,
+                })
+            }
+        }
+        record.config.dimension = dimension;
+        let config = VectorConfig::try_from(record.config.clone())?;
+        let collection_id = CollectionId::new(branch_id, collection);
+        let backend = self.new_backend(&collection_id, &config, record.hnsw.as_ref());
+        backends.insert(collection_id, backend);
+
+        info!(target: "strata::vector", collection, dimension, branch_id = %branch_id, "Collection dimension locked");
+
+        let config_key = Key::new_vector_config(self.namespace_for(branch_id, space), collection);
+        Ok(Some((config_key, Value::Bytes(record.to_bytes()?))))
     }
 
     /// Get collection config (required version that errors if not found)
@@ -1104,6 +1652,19 @@ impl VectorStore {
             })
     }
 
+    /// Get the full collection record (required version that errors if not found)
+    fn get_collection_record_required(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        name: &str,
+    ) -> VectorResult<CollectionRecord> {
+        self.load_collection_record(branch_id, space, name)?
+            .ok_or_else(|| VectorError::CollectionNotFound {
+                name: name.to_string(),
+            })
+    }
+
     /// Get a vector record by KV key
     fn get_vector_record_by_key(&self, key: &Key) -> VectorResult<Option<VectorRecord>> {
         use strata_core::traits::SnapshotView;
@@ -1137,6 +1698,18 @@ impl VectorStore {
         collection: &str,
         target_id: VectorId,
     ) -> VectorResult<(String, Option<JsonValue>)> {
+        let (key, record) = self.get_key_and_record(branch_id, space, collection, target_id)?;
+        Ok((key, record.metadata))
+    }
+
+    /// Get key and decoded record for a VectorId by scanning KV (internal)
+    fn get_key_and_record(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        target_id: VectorId,
+    ) -> VectorResult<(String, VectorRecord)> {
         use strata_core::traits::SnapshotView;
 
         let namespace = self.namespace_for(branch_id, space);
@@ -1170,7 +1743,7 @@ impl VectorStore {
                     .unwrap_or(&user_key)
                     .to_string();
 
-                return Ok((vector_key, record.metadata));
+                return Ok((vector_key, record));
             }
         }
 
@@ -1378,6 +1951,19 @@ impl VectorStore {
         space: &str,
         name: &str,
     ) -> VectorResult<Option<VectorConfig>> {
+        match self.load_collection_record(branch_id, space, name)? {
+            Some(record) => Ok(Some(VectorConfig::try_from(record.config)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Load the full collection record (config plus options) from KV
+    fn load_collection_record(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        name: &str,
+    ) -> VectorResult<Option<CollectionRecord>> {
         use strata_core::traits::SnapshotView;
 
         let config_key = Key::new_vector_config(self.namespace_for(branch_id, space), name);
@@ -1399,9 +1985,7 @@ impl VectorStore {
             }
         };
 
-        let record = CollectionRecord::from_bytes(&bytes)?;
-        let config = VectorConfig::try_from(record.config)?;
-        Ok(Some(config))
+        Ok(Some(CollectionRecord::from_bytes(&bytes)?))
     }
 
     /// Ensure collection is loaded into memory
@@ -1425,14 +2009,16 @@ impl VectorStore {
         }
 
         // Load from KV
-        let config = self
-            .load_collection_config(branch_id, space, name)?
+        let record = self
+            .load_collection_record(branch_id, space, name)?
             .ok_or_else(|| VectorError::CollectionNotFound {
                 name: name.to_string(),
             })?;
+        let hnsw = record.hnsw.clone();
+        let config = VectorConfig::try_from(record.config)?;
 
         // Initialize backend
-        self.init_backend(&collection_id, &config)?;
+        self.init_backend(&collection_id, &config, hnsw.as_ref())?;
 
         // Note: Loading vectors into backend happens during recovery
 
@@ -1501,7 +2087,7 @@ impl VectorStore {
         }
 
         // Initialize backend (no KV write - KV is replayed separately)
-        let backend = self.backend_factory().create(&config);
+        let backend = self.backend_factory(None).create(&config);
         let state = self.state()?;
         state.backends.write().insert(collection_id, backend);
 
@@ -1596,7 +2182,9 @@ impl VectorStore {
 
     /// Create a system collection (internal use only, bypasses `_` prefix check)
     ///
-    /// System collections must have names starting with `_system_`.
+    /// System collections must have names starting with `_system_`. If the
+    /// collection already exists with a different dimension, returns
+    /// `DimensionMismatch` rather than `CollectionAlreadyExists`.
     pub fn create_system_collection(
         &self,
         branch_id: BranchId,
@@ -1607,8 +2195,7 @@ impl VectorStore {
 
         validate_system_collection_name(name)?;
 
-        const MAX_DIMENSION: usize = 65536;
-        if config.dimension == 0 || config.dimension > MAX_DIMENSION {
+        if config.dimension == 0 || config.dimension > super::MAX_DIMENSION {
             return Err(VectorError::InvalidDimension {
                 dimension: config.dimension,
             });
@@ -1616,7 +2203,13 @@ impl VectorStore {
 
         let collection_id = CollectionId::new(branch_id, name);
 
-        if self.collection_exists(branch_id, "default", name)? {
+        if let Some(existing) = self.get_collection(branch_id, "default", name)? {
+            if existing.value.config.dimension != config.dimension {
+                return Err(VectorError::DimensionMismatch {
+                    expected: existing.value.config.dimension,
+                    got: config.dimension,
+                });
+            }
             return Err(VectorError::CollectionAlreadyExists {
                 name: name.to_string(),
             });
@@ -1633,7 +2226,7 @@ impl VectorStore {
             })
             .map_err(|e| VectorError::Storage(e.to_string()))?;
 
-        self.init_backend(&collection_id, &config)?;
+        self.init_backend(&collection_id, &config, None)?;
 
         let info = CollectionInfo {
             name: name.to_string(),
@@ -1679,7 +2272,7 @@ impl VectorStore {
     ) -> VectorResult<Version> {
         use crate::primitives::vector::collection::validate_system_collection_name;
         validate_system_collection_name(collection)?;
-        self.insert_inner(
+        let outcome = self.insert_inner(
             branch_id,
             "default",
             collection,
@@ -1687,7 +2280,10 @@ impl VectorStore {
             embedding,
             metadata,
             Some(source_ref),
-        )
+            None,
+            UpsertMode::Replace,
+        )?;
+        Ok(outcome.expect_written().version)
     }
 
     /// Search a system collection (internal use only)
@@ -1942,11 +2538,31 @@ impl VectorStore {
         &self,
         branch_id: BranchId,
         source_branch_id: Option<BranchId>,
+    ) -> VectorResult<()> {
+        self.reload_vectors(branch_id, source_branch_id, None)
+    }
+
+    /// Rebuild one collection's index from its stored records.
+    ///
+    /// Same as the post-merge reload, restricted to `collection`: a fresh
+    /// backend is filled from KV and its graph built from scratch. Used
+    /// after bulk re-embedding, where patching the old graph one upsert at
+    /// a time would leave it shaped by the previous embeddings. Writes to
+    /// the collection while the rebuild runs may be missing from search
+    /// until the next recovery.
+    pub fn rebuild_collection(&self, branch_id: BranchId, collection: &str) -> VectorResult<()> {
+        self.reload_vectors(branch_id, None, Some(collection))
+    }
+
+    fn reload_vectors(
+        &self,
+        branch_id: BranchId,
+        source_branch_id: Option<BranchId>,
+        only_collection: Option<&str>,
     ) -> VectorResult<()> {
         use strata_core::traits::SnapshotView;
 
         let state = self.state()?;
-        let factory = self.backend_factory();
         let snapshot = self.db.storage().create_snapshot();
 
         // Get all spaces for this branch (SpaceIndex.list always includes "default")
@@ -2001,7 +2617,11 @@ impl VectorStore {
                     Some(name) => name,
                     None => continue,
                 };
+                if only_collection.is_some_and(|name| name != collection_name) {
+                    continue;
+                }
 
+                let record_hnsw = record.hnsw.clone();
                 let config: VectorConfig = match record.config.try_into() {
                     Ok(c) => c,
                     Err(e) => {
@@ -2049,7 +2669,7 @@ impl VectorStore {
                     };
 
                 // Create fresh backend
-                let mut backend = factory.create(&config);
+                let mut backend = self.backend_factory(record_hnsw.as_ref()).create(&config);
 
                 // Scan all vector entries in this collection
                 let vector_prefix = Key::new_vector(ns.clone(), &collection_name, "");
@@ -2241,7 +2861,7 @@ impl VectorStore {
                 branch_id = %branch_id,
                 total_collections,
                 total_vectors,
-                "Post-merge vector reload complete"
+                "Vector reload complete"
             );
         }
 
@@ -2249,6 +2869,83 @@ impl VectorStore {
     }
 }
 
+/// Iterator over the vectors of a collection, returned by [`VectorStore::scan`]
+pub struct VectorScan {
+    collection: String,
+    collection_id: CollectionId,
+    state: Arc<VectorBackendState>,
+    /// Pinned snapshot the records are read from
+    snapshot: ShardedSnapshot,
+    /// Keys of the remaining vectors in `VectorId` order
+    ids: std::collections::btree_map::IntoIter<VectorId, Key>,
+}
+
+impl Iterator for VectorScan {
+    type Item = VectorResult<VectorEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use strata_core::traits::SnapshotView;
+
+        let (_, key) = self.ids.next()?;
+        let record = match self.snapshot.get(&key) {
+            Ok(Some(versioned)) => match versioned.value {
+                Value::Bytes(bytes) => VectorRecord::from_bytes(&bytes),
+                _ => Err(VectorError::Serialization(
+                    "Expected Bytes value for vector record".to_string(),
+                )),
+            },
+            // The pin keeps every indexed record readable
+            Ok(None) => Err(VectorError::Internal(
+                "Vector record missing from pinned snapshot".to_string(),
+            )),
+            Err(e) => Err(VectorError::Storage(e.to_string())),
+        };
+        Some(record.and_then(|record| self.entry(key, record)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+impl VectorScan {
+    fn entry(&self, key: Key, record: VectorRecord) -> VectorResult<VectorEntry> {
+        let vector_id = VectorId(record.vector_id);
+
+        // Key format: collection/key
+        let user_key = String::from_utf8(key.user_key.clone())
+            .map_err(|e| VectorError::Serialization(e.to_string()))?;
+        let vector_key = user_key
+            .strip_prefix(&format!("{}/", self.collection))
+            .unwrap_or(&user_key)
+            .to_string();
+
+        // Records written before embeddings were stored in KV only have
+        // their embedding in the backend.
+        let embedding = if record.embedding.is_empty() {
+            let backends = self.state.backends.read();
+            backends
+                .get(&self.collection_id)
+                .and_then(|backend| backend.get(vector_id))
+                .map(|embedding| embedding.to_vec())
+                .ok_or_else(|| {
+                    VectorError::Internal("Embedding missing from backend".to_string())
+                })?
+        } else {
+            record.embedding
+        };
+
+        Ok(VectorEntry {
+            key: vector_key,
+            embedding,
+            metadata: record.metadata,
+            vector_id,
+            version: Version::counter(record.version),
+            source_ref: record.source_ref,
+        })
+    }
+}
+
 // ========== Searchable Trait Implementation ==========
 
 impl crate::search::Searchable for VectorStore {
@@ -2428,6 +3125,40 @@ impl strata_storage::PrimitiveStorageExt for VectorStore {
     }
 }
 
+/// Default ratio of backend candidates to requested results when a
+/// metadata filter is active
+pub const DEFAULT_OVERFETCH_FACTOR: f32 = 3.0;
+
+/// Number of over-fetch attempts for filtered search; each doubles the
+/// previous multiplier
+pub const OVERFETCH_ROUNDS: u32 = 3;
+
+/// Multipliers tried by filtered search for a given over-fetch factor
+fn overfetch_multipliers(overfetch_factor: f32) -> Vec<f32> {
+    let base = overfetch_factor.max(1.0);
+    (0..OVERFETCH_ROUNDS)
+        .map(|round| base * (1u32 << round) as f32)
+        .collect()
+}
+
+/// Number of candidates to fetch for `k` results at multiplier `mult`
+fn overfetch_k(k: usize, mult: f32) -> usize {
+    let fetch = (k as f64 * mult as f64).ceil();
+    if fetch >= usize::MAX as f64 {
+        usize::MAX
+    } else {
+        fetch as usize
+    }
+}
+
+/// Reject dimensions a collection cannot be locked to
+fn check_dimension(dimension: usize) -> VectorResult<()> {
+    if dimension == 0 || dimension > super::MAX_DIMENSION {
+        return Err(VectorError::InvalidDimension { dimension });
+    }
+    Ok(())
+}
+
 /// Get current time in microseconds since Unix epoch
 ///
 /// Returns 0 if system clock is before Unix epoch (clock went backwards).
@@ -2553,112 +3284,409 @@ mod tests {
     }
 
     #[test]
-    fn test_delete_collection() {
+    fn test_system_collection_dimension_mismatch() {
         let (_temp, _db, store) = setup();
         let branch_id = BranchId::new();
 
-        let config = VectorConfig::for_minilm();
-        store
-            .create_collection(branch_id, "default", "test", config.clone())
-            .unwrap();
-
-        // Delete should succeed
         store
-            .delete_collection(branch_id, "default", "test")
+            .create_system_collection(branch_id, "_system_test", VectorConfig::for_minilm())
             .unwrap();
 
-        // Collection should no longer exist
-        assert!(!store
-            .collection_exists(branch_id, "default", "test")
-            .unwrap());
-    }
-
-    #[test]
-    fn test_delete_collection_not_found() {
-        let (_temp, _db, store) = setup();
-        let branch_id = BranchId::new();
+        let same =
+            store.create_system_collection(branch_id, "_system_test", VectorConfig::for_minilm());
+        assert!(matches!(
+            same,
+            Err(VectorError::CollectionAlreadyExists { .. })
+        ));
 
-        let result = store.delete_collection(branch_id, "default", "nonexistent");
+        let wider = VectorConfig::new(768, DistanceMetric::Cosine).unwrap();
+        let result = store.create_system_collection(branch_id, "_system_test", wider);
         assert!(matches!(
             result,
-            Err(VectorError::CollectionNotFound { .. })
+            Err(VectorError::DimensionMismatch {
+                expected: 384,
+                got: 768
+            })
         ));
     }
 
-    // ========================================
-    // Collection Discovery Tests (#349)
-    // ========================================
-
     #[test]
-    fn test_list_collections() {
+    fn test_create_collection_with_builder() {
         let (_temp, _db, store) = setup();
         let branch_id = BranchId::new();
 
-        // Create multiple collections
-        store
-            .create_collection(branch_id, "default", "zeta", VectorConfig::for_minilm())
+        let hnsw = HnswConfig {
+            m: 8,
+            ef_construction: 64,
+            ef_search: 32,
+            ..HnswConfig::default()
+        };
+        let builder = crate::primitives::vector::CollectionConfigBuilder::new()
+            .dimension(2)
+            .metric(DistanceMetric::DotProduct)
+            .hnsw(hnsw)
+            .normalize(true);
+        let info = store
+            .create_collection(branch_id, "default", "unit", builder)
+            .unwrap()
+            .value;
+        assert_eq!(info.config.dimension, 2);
+        assert_eq!(info.config.metric, DistanceMetric::DotProduct);
+
+        let record = store
+            .load_collection_record(branch_id, "default", "unit")
+            .unwrap()
             .unwrap();
+        assert!(record.normalize);
+        assert_eq!(record.hnsw.as_ref().map(|h| h.m), Some(8));
+        assert_eq!(record.hnsw.as_ref().map(|h| h.ef_search), Some(32));
+
+        // Embeddings are stored at unit length
         store
-            .create_collection(branch_id, "default", "alpha", VectorConfig::for_mpnet())
+            .insert(branch_id, "default", "unit", "a", &[3.0, 4.0], None)
             .unwrap();
         store
-            .create_collection(branch_id, "default", "beta", VectorConfig::for_openai_ada())
+            .batch_insert(
+                branch_id,
+                "default",
+                "unit",
+                vec![("b".to_string(), vec![0.0, 2.0], None)],
+            )
             .unwrap();
+        let a = store
+            .get(branch_id, "default", "unit", "a")
+            .unwrap()
+            .unwrap();
+        assert!((a.value.embedding[0] - 0.6).abs() < 1e-6);
+        assert!((a.value.embedding[1] - 0.8).abs() < 1e-6);
+        let b = store
+            .get(branch_id, "default", "unit", "b")
+            .unwrap()
+            .unwrap();
+        assert_eq!(b.value.embedding, vec![0.0, 1.0]);
 
-        let collections = store.list_collections(branch_id, "default").unwrap();
-
-        // Should be sorted by name
-        assert_eq!(collections.len(), 3);
-        assert_eq!(collections[0].name, "alpha");
-        assert_eq!(collections[1].name, "beta");
-        assert_eq!(collections[2].name, "zeta");
+        // A zero vector cannot be normalized
+        assert!(matches!(
+            store.insert(branch_id, "default", "unit", "z", &[0.0, 0.0], None),
+            Err(VectorError::InvalidEmbedding { .. })
+        ));
+        assert!(matches!(
+            store.batch_insert(
+                branch_id,
+                "default",
+                "unit",
+                vec![("z".to_string(), vec![0.0, 0.0], None)],
+            ),
+            Err(VectorError::InvalidEmbedding { .. })
+        ));
     }
 
     #[test]
-    fn test_list_collections_empty() {
+    fn test_create_collection_rejects_invalid_config() {
         let (_temp, _db, store) = setup();
         let branch_id = BranchId::new();
 
-        let collections = store.list_collections(branch_id, "default").unwrap();
-        assert!(collections.is_empty());
+        let builder = crate::primitives::vector::CollectionConfigBuilder::new()
+            .dimension(4)
+            .hnsw(HnswConfig {
+                ef_search: 0,
+                ..HnswConfig::default()
+            });
+        let err = store
+            .create_collection(branch_id, "default", "bad", builder)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VectorError::InvalidConfig { ref field, .. } if field == "hnsw.ef_search"
+        ));
+        assert!(!store
+            .collection_exists(branch_id, "default", "bad")
+            .unwrap());
     }
 
     #[test]
-    fn test_get_collection() {
+    fn test_search_filter_checked_against_metadata_schema() {
+        use crate::primitives::vector::MetadataFieldType;
+
         let (_temp, _db, store) = setup();
         let branch_id = BranchId::new();
 
-        let config = VectorConfig::new(768, DistanceMetric::Euclidean).unwrap();
+        let builder = crate::primitives::vector::CollectionConfigBuilder::new()
+            .dimension(3)
+            .metadata_field("category", MetadataFieldType::String);
         store
-            .create_collection(branch_id, "default", "embeddings", config)
+            .create_collection(branch_id, "default", "typed", builder)
+            .unwrap();
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "untyped", config)
             .unwrap();
+        for collection in ["typed", "untyped"] {
+            store
+                .insert(
+                    branch_id,
+                    "default",
+                    collection,
+                    "a",
+                    &[1.0, 0.0, 0.0],
+                    Some(serde_json::json!({"category": "doc", "extra": 1})),
+                )
+                .unwrap();
+        }
+        let search = |collection, filter| {
+            store.search(
+                branch_id,
+                "default",
+                collection,
+                &[1.0, 0.0, 0.0],
+                10,
+                Some(filter),
+            )
+        };
 
-        let info = store
-            .get_collection(branch_id, "default", "embeddings")
-            .unwrap()
-            .unwrap()
-            .value;
-        assert_eq!(info.name, "embeddings");
-        assert_eq!(info.config.dimension, 768);
-        assert_eq!(info.config.metric, DistanceMetric::Euclidean);
+        let hits = search("typed", MetadataFilter::new().eq("category", "doc")).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(matches!(
+            search("typed", MetadataFilter::new().eq("categroy", "doc")),
+            Err(VectorError::UnknownFilterField { ref field, .. }) if field == "categroy"
+        ));
+        // Stored but undeclared fields are not filterable
+        assert!(matches!(
+            search("typed", MetadataFilter::new().eq("extra", 1)),
+            Err(VectorError::UnknownFilterField { .. })
+        ));
+        assert!(matches!(
+            search("typed", MetadataFilter::new().gt("category", 1)),
+            Err(VectorError::InvalidFilter { .. })
+        ));
+
+        // Without a schema any field may be filtered on
+        let hits = search("untyped", MetadataFilter::new().eq("categroy", "doc")).unwrap();
+        assert!(hits.is_empty());
     }
 
     #[test]
-    fn test_get_collection_not_found() {
+    fn test_insert_if_novel() {
         let (_temp, _db, store) = setup();
         let branch_id = BranchId::new();
-
-        let info = store
-            .get_collection(branch_id, "default", "nonexistent")
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "mem", config)
             .unwrap();
-        assert!(info.is_none());
-    }
-
-    // ========================================
-    // Branch Isolation Tests (Rule #2)
-    // ========================================
 
-    #[test]
+        // Empty collection: always novel
+        let first = store
+            .insert_if_novel(
+                branch_id,
+                "default",
+                "mem",
+                "a",
+                &[1.0, 0.0, 0.0],
+                None,
+                0.1,
+            )
+            .unwrap();
+        assert!(matches!(first, NovelInsert::Inserted(_)));
+
+        // Nearly parallel to "a": skipped, reporting "a"
+        let dup = store
+            .insert_if_novel(
+                branch_id,
+                "default",
+                "mem",
+                "b",
+                &[1.0, 0.01, 0.0],
+                None,
+                0.1,
+            )
+            .unwrap();
+        match dup {
+            NovelInsert::Duplicate { key, distance } => {
+                assert_eq!(key, "a");
+                assert!(distance < 0.1);
+            }
+            other => panic!("expected duplicate, got {:?}", other),
+        }
+        assert!(store
+            .get(branch_id, "default", "mem", "b")
+            .unwrap()
+            .is_none());
+
+        // Orthogonal: inserted
+        let novel = store
+            .insert_if_novel(
+                branch_id,
+                "default",
+                "mem",
+                "c",
+                &[0.0, 1.0, 0.0],
+                None,
+                0.1,
+            )
+            .unwrap();
+        assert!(matches!(novel, NovelInsert::Inserted(_)));
+        assert!(store
+            .get(branch_id, "default", "mem", "c")
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_insert_if_novel_updates_existing_key() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "mem", config)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "mem", "a", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "mem", "c", &[0.0, 1.0, 0.0], None)
+            .unwrap();
+
+        // Close to the key's own current vector only: not a duplicate
+        let update = store
+            .insert_if_novel(
+                branch_id,
+                "default",
+                "mem",
+                "a",
+                &[1.0, 0.01, 0.0],
+                None,
+                0.1,
+            )
+            .unwrap();
+        assert!(matches!(update, NovelInsert::Inserted(_)));
+        let entry = store
+            .get(branch_id, "default", "mem", "a")
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.value.embedding, vec![1.0, 0.01, 0.0]);
+
+        // Close to another key: still a duplicate
+        let dup = store
+            .insert_if_novel(
+                branch_id,
+                "default",
+                "mem",
+                "a",
+                &[0.0, 1.0, 0.01],
+                None,
+                0.1,
+            )
+            .unwrap();
+        assert!(matches!(dup, NovelInsert::Duplicate { ref key, .. } if key == "c"));
+    }
+
+    #[test]
+    fn test_delete_collection() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::for_minilm();
+        store
+            .create_collection(branch_id, "default", "test", config.clone())
+            .unwrap();
+
+        // Delete should succeed
+        store
+            .delete_collection(branch_id, "default", "test")
+            .unwrap();
+
+        // Collection should no longer exist
+        assert!(!store
+            .collection_exists(branch_id, "default", "test")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_delete_collection_not_found() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let result = store.delete_collection(branch_id, "default", "nonexistent");
+        assert!(matches!(
+            result,
+            Err(VectorError::CollectionNotFound { .. })
+        ));
+    }
+
+    // ========================================
+    // Collection Discovery Tests (#349)
+    // ========================================
+
+    #[test]
+    fn test_list_collections() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        // Create multiple collections
+        store
+            .create_collection(branch_id, "default", "zeta", VectorConfig::for_minilm())
+            .unwrap();
+        store
+            .create_collection(branch_id, "default", "alpha", VectorConfig::for_mpnet())
+            .unwrap();
+        store
+            .create_collection(branch_id, "default", "beta", VectorConfig::for_openai_ada())
+            .unwrap();
+
+        let collections = store.list_collections(branch_id, "default").unwrap();
+
+        // Should be sorted by name
+        assert_eq!(collections.len(), 3);
+        assert_eq!(collections[0].name, "alpha");
+        assert_eq!(collections[1].name, "beta");
+        assert_eq!(collections[2].name, "zeta");
+    }
+
+    #[test]
+    fn test_list_collections_empty() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let collections = store.list_collections(branch_id, "default").unwrap();
+        assert!(collections.is_empty());
+    }
+
+    #[test]
+    fn test_get_collection() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(768, DistanceMetric::Euclidean).unwrap();
+        store
+            .create_collection(branch_id, "default", "embeddings", config)
+            .unwrap();
+
+        let info = store
+            .get_collection(branch_id, "default", "embeddings")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(info.name, "embeddings");
+        assert_eq!(info.config.dimension, 768);
+        assert_eq!(info.config.metric, DistanceMetric::Euclidean);
+    }
+
+    #[test]
+    fn test_get_collection_not_found() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let info = store
+            .get_collection(branch_id, "default", "nonexistent")
+            .unwrap();
+        assert!(info.is_none());
+    }
+
+    // ========================================
+    // Branch Isolation Tests (Rule #2)
+    // ========================================
+
+    #[test]
     fn test_branch_isolation() {
         let (_temp, _db, store) = setup();
         let branch1 = BranchId::new();
@@ -2746,6 +3774,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_manhattan_collection_survives_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+
+            let config = VectorConfig::new(2, DistanceMetric::Manhattan).unwrap();
+            store
+                .create_collection(branch_id, "default", "l1", config)
+                .unwrap();
+        }
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+
+            let info = store
+                .get_collection(branch_id, "default", "l1")
+                .unwrap()
+                .unwrap()
+                .value;
+            assert_eq!(info.config.metric, DistanceMetric::Manhattan);
+        }
+    }
+
     // ========================================
     // Validation Tests
     // ========================================
@@ -2907,6 +3963,59 @@ mod tests {
         assert_eq!(entry.embedding, vec![0.0, 1.0, 0.0]);
     }
 
+    #[test]
+    fn test_upsert_modes() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+        let upsert = |embedding: &[f32], metadata, mode| {
+            store.upsert(
+                branch_id, "default", "test", "doc1", embedding, metadata, mode,
+            )
+        };
+        let get = || {
+            store
+                .get(branch_id, "default", "test", "doc1")
+                .unwrap()
+                .unwrap()
+                .value
+        };
+
+        let first = upsert(&[1.0, 0.0, 0.0], None, UpsertMode::Error).unwrap();
+        assert!(first.created);
+        let original_id = get().vector_id;
+
+        // Error mode leaves the existing vector untouched
+        let err = upsert(&[0.0, 1.0, 0.0], None, UpsertMode::Error).unwrap_err();
+        assert!(matches!(err, VectorError::VectorAlreadyExists { .. }));
+        assert_eq!(get().embedding, vec![1.0, 0.0, 0.0]);
+
+        // Replace mode overwrites in place, keeping the VectorId
+        let replaced = upsert(
+            &[0.0, 1.0, 0.0],
+            Some(serde_json::json!({"v": 2})),
+            UpsertMode::Replace,
+        )
+        .unwrap();
+        assert!(!replaced.created);
+        assert!(replaced.version > first.version);
+        let entry = get();
+        assert_eq!(entry.vector_id, original_id);
+        assert_eq!(entry.embedding, vec![0.0, 1.0, 0.0]);
+        assert_eq!(entry.metadata, Some(serde_json::json!({"v": 2})));
+        assert_eq!(
+            store
+                .search(branch_id, "default", "test", &[0.0, 1.0, 0.0], 10, None)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
     #[test]
     fn test_delete_vector() {
         let (_temp, _db, store) = setup();
@@ -2934,6 +4043,63 @@ mod tests {
         assert!(!deleted);
     }
 
+    #[test]
+    fn test_scan_in_vector_id_order() {
+        let (_temp, db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        // Insert in non-alphabetical order so key order differs from id order
+        for (key, v) in [("c", 1.0), ("a", 2.0), ("b", 3.0), ("d", 4.0)] {
+            store
+                .insert(
+                    branch_id,
+                    "default",
+                    "test",
+                    key,
+                    &[v, 0.0, 0.0],
+                    Some(serde_json::json!({ "v": v })),
+                )
+                .unwrap();
+        }
+        store.delete(branch_id, "default", "test", "b").unwrap();
+
+        let scan = store.scan(branch_id, "default", "test").unwrap();
+        assert_eq!(db.storage().pinned_snapshot_count(), 1);
+        // Later writes are not visible to an open scan
+        store
+            .insert(branch_id, "default", "test", "e", &[5.0, 0.0, 0.0], None)
+            .unwrap();
+        store
+            .insert(
+                branch_id,
+                "default",
+                "test",
+                "a",
+                &[2.0, 0.0, 0.0],
+                Some(serde_json::json!({ "v": 9.0 })),
+            )
+            .unwrap();
+
+        let entries: Vec<VectorEntry> = scan.map(|e| e.unwrap()).collect();
+        assert_eq!(db.storage().pinned_snapshot_count(), 0);
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["c", "a", "d"]);
+        assert!(entries.windows(2).all(|w| w[0].vector_id < w[1].vector_id));
+        assert_eq!(entries[1].embedding, vec![2.0, 0.0, 0.0]);
+        assert_eq!(entries[1].metadata, Some(serde_json::json!({ "v": 2.0 })));
+
+        let result = store.scan(branch_id, "default", "missing");
+        assert!(matches!(
+            result,
+            Err(VectorError::CollectionNotFound { .. })
+        ));
+    }
+
     #[test]
     fn test_dimension_mismatch() {
         let (_temp, _db, store) = setup();
@@ -2955,6 +4121,109 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_auto_dimension_locked_by_first_insert() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        store
+            .create_collection_auto(branch_id, "default", "auto", DistanceMetric::Cosine)
+            .unwrap();
+        let info = store
+            .get_collection(branch_id, "default", "auto")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(info.config.dimension, 0);
+
+        // Nothing to match before the first insert
+        let matches = store
+            .search(branch_id, "default", "auto", &[1.0, 0.0], 5, None)
+            .unwrap();
+        assert!(matches.is_empty());
+        assert!(matches!(
+            store.insert(branch_id, "default", "auto", "empty", &[], None),
+            Err(VectorError::InvalidDimension { dimension: 0 })
+        ));
+
+        store
+            .insert(branch_id, "default", "auto", "a", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        let info = store
+            .get_collection(branch_id, "default", "auto")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(info.config.dimension, 3);
+        assert_eq!(info.count, 1);
+
+        assert!(matches!(
+            store.insert(branch_id, "default", "auto", "b", &[1.0, 0.0], None),
+            Err(VectorError::DimensionMismatch {
+                expected: 3,
+                got: 2
+            })
+        ));
+        let matches = store
+            .search(branch_id, "default", "auto", &[1.0, 0.0, 0.0], 5, None)
+            .unwrap();
+        assert_eq!(matches[0].key, "a");
+    }
+
+    #[test]
+    fn test_auto_dimension_batch_insert_and_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+            store
+                .create_collection_auto(branch_id, "default", "auto", DistanceMetric::Euclidean)
+                .unwrap();
+
+            // The whole batch must share the first entry's dimension
+            let mixed = vec![
+                ("a".to_string(), vec![1.0, 0.0], None),
+                ("b".to_string(), vec![1.0, 0.0, 0.0], None),
+            ];
+            assert!(matches!(
+                store.batch_insert(branch_id, "default", "auto", mixed),
+                Err(VectorError::DimensionMismatch {
+                    expected: 2,
+                    got: 3
+                })
+            ));
+
+            let entries = vec![
+                ("a".to_string(), vec![1.0, 0.0], None),
+                ("b".to_string(), vec![0.0, 1.0], None),
+            ];
+            store
+                .batch_insert(branch_id, "default", "auto", entries)
+                .unwrap();
+        }
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+            let info = store
+                .get_collection(branch_id, "default", "auto")
+                .unwrap()
+                .unwrap()
+                .value;
+            assert_eq!(info.config.dimension, 2);
+            assert_eq!(info.count, 2);
+            assert!(matches!(
+                store.insert(branch_id, "default", "auto", "c", &[1.0], None),
+                Err(VectorError::DimensionMismatch {
+                    expected: 2,
+                    got: 1
+                })
+            ));
+        }
+    }
+
     // ========================================
     // Vector Search Tests
     // ========================================
@@ -3009,6 +4278,89 @@ mod tests {
             .search(branch_id, "default", "test", &[1.0, 0.0, 0.0], 0, None)
             .unwrap();
         assert!(results.is_empty());
+
+        // k=0 does not mask a missing collection
+        let result = store.search(branch_id, "default", "missing", &[1.0, 0.0, 0.0], 0, None);
+        assert!(matches!(
+            result,
+            Err(VectorError::CollectionNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_search_empty_vs_missing_collection() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "empty", config)
+            .unwrap();
+
+        let filter = MetadataFilter::new().eq("tag", "x");
+        for filter in [None, Some(filter)] {
+            let results = store
+                .search(
+                    branch_id,
+                    "default",
+                    "empty",
+                    &[1.0, 0.0, 0.0],
+                    5,
+                    filter.clone(),
+                )
+                .unwrap();
+            assert!(results.is_empty());
+            let results = store
+                .search_at(
+                    branch_id,
+                    "default",
+                    "empty",
+                    &[1.0, 0.0, 0.0],
+                    5,
+                    filter.clone(),
+                    u64::MAX,
+                    DEFAULT_OVERFETCH_FACTOR,
+                )
+                .unwrap();
+            assert!(results.is_empty());
+
+            let result = store.search(
+                branch_id,
+                "default",
+                "missing",
+                &[1.0, 0.0, 0.0],
+                5,
+                filter.clone(),
+            );
+            assert!(matches!(
+                result,
+                Err(VectorError::CollectionNotFound { .. })
+            ));
+            let result = store.search_at(
+                branch_id,
+                "default",
+                "missing",
+                &[1.0, 0.0, 0.0],
+                5,
+                filter,
+                u64::MAX,
+                DEFAULT_OVERFETCH_FACTOR,
+            );
+            assert!(matches!(
+                result,
+                Err(VectorError::CollectionNotFound { .. })
+            ));
+        }
+
+        // Emptied by deletes reads the same as never filled
+        store
+            .insert(branch_id, "default", "empty", "a", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        store.delete(branch_id, "default", "empty", "a").unwrap();
+        let results = store
+            .search(branch_id, "default", "empty", &[1.0, 0.0, 0.0], 5, None)
+            .unwrap();
+        assert!(results.is_empty());
     }
 
     #[test]
@@ -3072,6 +4424,152 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_range_and_set_filters() {
+        use crate::primitives::vector::JsonScalar;
+
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(2, DistanceMetric::Euclidean).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+        let docs = [
+            ("a", serde_json::json!({"score": 0.95, "tag": "news"})),
+            ("b", serde_json::json!({"score": 0.85, "tag": "blog"})),
+            ("c", serde_json::json!({"score": 0.4, "tag": "news"})),
+            ("d", serde_json::json!({"score": "n/a", "tag": "wiki"})),
+        ];
+        for (i, (key, metadata)) in docs.iter().enumerate() {
+            store
+                .insert(
+                    branch_id,
+                    "default",
+                    "test",
+                    key,
+                    &[i as f32, 0.0],
+                    Some(metadata.clone()),
+                )
+                .unwrap();
+        }
+
+        // The live and time-travel search paths apply the same filter
+        let keys = |filter: MetadataFilter| {
+            let live = store
+                .search(
+                    branch_id,
+                    "default",
+                    "test",
+                    &[0.0, 0.0],
+                    10,
+                    Some(filter.clone()),
+                )
+                .unwrap();
+            let historical = store
+                .search_at(
+                    branch_id,
+                    "default",
+                    "test",
+                    &[0.0, 0.0],
+                    10,
+                    Some(filter),
+                    u64::MAX,
+                    DEFAULT_OVERFETCH_FACTOR,
+                )
+                .unwrap();
+            let live: Vec<String> = live.into_iter().map(|m| m.key).collect();
+            let historical: Vec<String> = historical.into_iter().map(|m| m.key).collect();
+            assert_eq!(live, historical);
+            live
+        };
+
+        assert_eq!(keys(MetadataFilter::new().gt("score", 0.8)), ["a", "b"]);
+        assert_eq!(
+            keys(MetadataFilter::new().gte("score", 0.4).lt("score", 0.9)),
+            ["b", "c"]
+        );
+        assert_eq!(
+            keys(MetadataFilter::new().in_values(
+                "tag",
+                vec![JsonScalar::from("blog"), JsonScalar::from("wiki")]
+            )),
+            ["b", "d"]
+        );
+        assert_eq!(
+            keys(
+                MetadataFilter::new()
+                    .not_in_values("tag", vec![JsonScalar::from("news")])
+                    .lte("score", 1)
+            ),
+            ["b"]
+        );
+    }
+
+    #[test]
+    fn test_search_overfetch_factor_fills_k() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(2, DistanceMetric::Euclidean).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        // 40 near vectors are filtered out; the 2 matching ones are the farthest
+        for i in 0..40 {
+            store
+                .insert(
+                    branch_id,
+                    "default",
+                    "test",
+                    &format!("near{:02}", i),
+                    &[i as f32 * 0.01, 0.0],
+                    Some(serde_json::json!({"keep": false})),
+                )
+                .unwrap();
+        }
+        for key in ["far1", "far2"] {
+            store
+                .insert(
+                    branch_id,
+                    "default",
+                    "test",
+                    key,
+                    &[100.0, 0.0],
+                    Some(serde_json::json!({"keep": true})),
+                )
+                .unwrap();
+        }
+
+        let filter = MetadataFilter::new().eq("keep", true);
+        let default = store
+            .search(
+                branch_id,
+                "default",
+                "test",
+                &[0.0, 0.0],
+                2,
+                Some(filter.clone()),
+            )
+            .unwrap();
+        assert!(default.is_empty());
+
+        let results = store
+            .search_with_overfetch(
+                branch_id,
+                "default",
+                "test",
+                &[0.0, 0.0],
+                2,
+                Some(filter),
+                21.0,
+            )
+            .unwrap();
+        let keys: Vec<&str> = results.iter().map(|m| m.key.as_str()).collect();
+        assert_eq!(keys, vec!["far1", "far2"]);
+    }
+
     #[test]
     fn test_search_deterministic_order() {
         let (_temp, _db, store) = setup();
@@ -3398,6 +4896,46 @@ mod tests {
         assert_eq!(results[0].key, "a"); // exact match
     }
 
+    #[test]
+    fn test_rebuild_collection_only_touches_that_collection() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        for name in ["test", "other"] {
+            store
+                .create_collection(branch_id, "default", name, config.clone())
+                .unwrap();
+            store
+                .insert(branch_id, "default", name, "a", &[1.0, 0.0, 0.0], None)
+                .unwrap();
+            store
+                .insert(branch_id, "default", name, "b", &[0.0, 1.0, 0.0], None)
+                .unwrap();
+        }
+        store
+            .insert(branch_id, "default", "test", "a", &[0.0, 0.0, 1.0], None)
+            .unwrap();
+
+        let backend_addr = |name: &str| {
+            let state = store.state().unwrap();
+            let backends = state.backends.read();
+            let backend = backends.get(&CollectionId::new(branch_id, name)).unwrap();
+            &**backend as *const dyn VectorIndexBackend as *const u8 as usize
+        };
+        let other_before = backend_addr("other");
+        let test_before = backend_addr("test");
+
+        store.rebuild_collection(branch_id, "test").unwrap();
+
+        assert_eq!(backend_addr("other"), other_before);
+        assert_ne!(backend_addr("test"), test_before);
+        let results = store
+            .search(branch_id, "default", "test", &[0.0, 0.0, 1.0], 2, None)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].key, "a");
+    }
+
     #[test]
     fn test_post_merge_reload_empty_branch() {
         let (_temp, _db, store) = setup();
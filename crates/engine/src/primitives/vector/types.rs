@@ -8,7 +8,7 @@ use serde_json::Value as JsonValue;
 // Re-export canonical vector types from core
 pub use strata_core::primitives::{
     CollectionId, CollectionInfo, DistanceMetric, FilterCondition, FilterOp, JsonScalar,
-    MetadataFilter, StorageDtype, VectorConfig, VectorEntry, VectorId, VectorMatch,
+    MetadataFilter, StorageDtype, VectorConfig, VectorEntry, VectorId, VectorMatch, VectorStorage,
 };
 
 // Re-export EntityRef for source reference linking
@@ -264,10 +264,26 @@ impl VectorMatchWithSource {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionRecord {
     /// Collection configuration (serializable form)
+    ///
+    /// `dimension` is 0 for a collection created by
+    /// [`VectorStore::create_collection_auto`](crate::primitives::vector::VectorStore::create_collection_auto)
+    /// until its first insert sets it.
     pub config: VectorConfigSerde,
 
     /// Creation timestamp
     pub created_at: u64,
+
+    /// HNSW parameters (`None` = defaults; absent in older records)
+    #[serde(default)]
+    pub hnsw: Option<crate::primitives::vector::HnswConfig>,
+
+    /// Scale embeddings to unit length on insert (absent in older records)
+    #[serde(default)]
+    pub normalize: bool,
+
+    /// Filterable metadata fields (`None` = any filter; absent in older records)
+    #[serde(default)]
+    pub metadata_schema: Option<crate::primitives::vector::MetadataSchema>,
 }
 
 impl CollectionRecord {
@@ -276,6 +292,20 @@ impl CollectionRecord {
         CollectionRecord {
             config: VectorConfigSerde::from(config),
             created_at: now_micros(),
+            hnsw: None,
+            normalize: false,
+            metadata_schema: None,
+        }
+    }
+
+    /// Create a CollectionRecord carrying every collection option
+    pub fn from_collection_config(config: &crate::primitives::vector::CollectionConfig) -> Self {
+        CollectionRecord {
+            config: VectorConfigSerde::from(&config.vector),
+            created_at: now_micros(),
+            hnsw: config.hnsw.clone(),
+            normalize: config.normalize,
+            metadata_schema: config.metadata_schema.clone(),
         }
     }
 
@@ -350,6 +380,7 @@ mod tests {
             DistanceMetric::Cosine,
             DistanceMetric::Euclidean,
             DistanceMetric::DotProduct,
+            DistanceMetric::Manhattan,
         ] {
             let byte = metric.to_byte();
             let parsed = DistanceMetric::from_byte(byte).unwrap();
@@ -401,7 +432,7 @@ mod tests {
 
     #[test]
     fn test_distance_metric_from_byte_invalid() {
-        assert_eq!(DistanceMetric::from_byte(3), None);
+        assert_eq!(DistanceMetric::from_byte(4), None);
         assert_eq!(DistanceMetric::from_byte(255), None);
     }
 
@@ -602,4 +633,27 @@ mod tests {
 
         assert_eq!(set.len(), 2);
     }
+
+    #[test]
+    fn test_collection_record_decodes_old_format() {
+        // Records written before hnsw/normalize existed carry two fields
+        #[derive(Serialize)]
+        struct OldRecord {
+            config: VectorConfigSerde,
+            created_at: u64,
+        }
+        let config = VectorConfig::new(8, DistanceMetric::Euclidean).unwrap();
+        let bytes = rmp_serde::to_vec(&OldRecord {
+            config: VectorConfigSerde::from(&config),
+            created_at: 42,
+        })
+        .unwrap();
+
+        let record = CollectionRecord::from_bytes(&bytes).unwrap();
+        assert_eq!(record.created_at, 42);
+        assert!(record.hnsw.is_none());
+        assert!(!record.normalize);
+        assert!(record.metadata_schema.is_none());
+        assert_eq!(VectorConfig::try_from(record.config).unwrap(), config);
+    }
 }
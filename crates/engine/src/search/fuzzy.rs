@@ -0,0 +1,93 @@
+//! Edit-distance matching for typo-tolerant keyword search
+//!
+//! With typo tolerance enabled, a query term also matches index terms within
+//! a small Levenshtein distance. Candidates are limited to index terms that
+//! share the query term's first character: sealed segments answer that with
+//! one binary search into their sorted term dictionary, and the first letter
+//! is the one people mistype least.
+//!
+//! Short terms get fewer edits than requested, since one edit turns most
+//! three-letter words into other real words:
+//!
+//! | Term length | Edits allowed |
+//! |-------------|---------------|
+//! | 1–3         | 0             |
+//! | 4–7         | up to 1       |
+//! | 8+          | up to 2       |
+
+/// Largest typo tolerance a search request can ask for
+pub const MAX_TYPO_TOLERANCE: u8 = 2;
+
+/// Edits allowed for `term` when a request asks for `requested`
+pub(crate) fn allowed_edits(term: &str, requested: u8) -> u8 {
+    let cap = match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => MAX_TYPO_TOLERANCE,
+    };
+    requested.min(cap)
+}
+
+/// Score multiplier for a match `edits` away from the query term
+///
+/// Exact matches keep full weight, so a document containing the term as
+/// typed always outranks one that only contains a near miss.
+pub(crate) fn edit_weight(edits: u8) -> f32 {
+    1.0 / (1.0 + edits as f32)
+}
+
+/// Levenshtein distance between `a` and `b`, or `None` if it exceeds `max`
+///
+/// Stops as soon as every cell of a row exceeds `max`, so distant pairs cost
+/// little more than a length check.
+pub(crate) fn bounded_distance(a: &str, b: &str, max: u8) -> Option<u8> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max = max as usize;
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        let mut row_min = cur[0];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            cur[j + 1] = substitution.min(prev[j + 1] + 1).min(cur[j] + 1);
+            row_min = row_min.min(cur[j + 1]);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    let distance = prev[b.len()];
+    (distance <= max).then_some(distance as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_distance() {
+        assert_eq!(bounded_distance("search", "search", 2), Some(0));
+        assert_eq!(bounded_distance("search", "serch", 2), Some(1));
+        assert_eq!(bounded_distance("search", "seacrh", 2), Some(2));
+        assert_eq!(bounded_distance("search", "seacrh", 1), None);
+        assert_eq!(bounded_distance("search", "sea", 2), None);
+        assert_eq!(bounded_distance("", "ab", 2), Some(2));
+    }
+
+    #[test]
+    fn test_allowed_edits_scales_with_length() {
+        assert_eq!(allowed_edits("cat", 2), 0);
+        assert_eq!(allowed_edits("index", 2), 1);
+        assert_eq!(allowed_edits("database", 2), 2);
+        assert_eq!(allowed_edits("database", 1), 1);
+        assert_eq!(allowed_edits("database", 0), 0);
+    }
+}
@@ -34,6 +34,7 @@
 //! Indexing is OPTIONAL. Search works without it (via full scan).
 //! When enabled, search uses the index for candidate lookup.
 
+use super::fuzzy;
 use super::manifest::{self, ManifestData, SegmentManifestEntry};
 use super::segment::{self, SealedSegment};
 use super::tokenizer::tokenize;
@@ -80,6 +81,15 @@ impl PostingEntry {
 // ScoredDocId
 // ============================================================================
 
+/// Size of the index, as reported by `InvertedIndex::stats()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    /// Documents in the index (across all segments)
+    pub documents: u64,
+    /// Tokens across all indexed documents, after stemming and stopword removal
+    pub tokens: u64,
+}
+
 /// Result of in-index BM25 scoring: doc_id + score.
 ///
 /// Returned by `InvertedIndex::score_top_k()` to avoid exposing
@@ -396,6 +406,14 @@ impl InvertedIndex {
         self.total_docs.load(Ordering::Acquire)
     }
 
+    /// Get document and token totals (across all segments)
+    pub fn stats(&self) -> IndexStats {
+        IndexStats {
+            documents: self.total_docs.load(Ordering::Acquire) as u64,
+            tokens: self.total_doc_len.load(Ordering::Acquire) as u64,
+        }
+    }
+
     /// Get document frequency for a term (across all segments)
     pub fn doc_freq(&self, term: &str) -> usize {
         // Active segment df
@@ -468,6 +486,92 @@ impl InvertedIndex {
         k: usize,
         scorer_k1: f32,
         scorer_b: f32,
+    ) -> Vec<ScoredDocId> {
+        let weighted: Vec<(&str, f32)> = query_terms.iter().map(|t| (t.as_str(), 1.0)).collect();
+        self.score_weighted_top_k(&weighted, branch_id, k, scorer_k1, scorer_b)
+    }
+
+    /// Score documents like [`score_top_k`](Self::score_top_k), letting each
+    /// query term also match index terms up to `max_edits` edits away.
+    ///
+    /// A near match contributes its BM25 score scaled down by its edit
+    /// distance, so exact matches still rank first. The edits a term may use
+    /// shrink with its length and candidates must share its first character;
+    /// see the `fuzzy` module. `max_edits == 0` is exactly `score_top_k`.
+    ///
+    /// Expanding a term walks the active segment's vocabulary and one prefix
+    /// range of each sealed segment's dictionary, then scores the postings of
+    /// every match, so latency grows with vocabulary size and with how many
+    /// near matches a term has.
+    pub fn score_top_k_fuzzy(
+        &self,
+        query_terms: &[String],
+        max_edits: u8,
+        branch_id: &BranchId,
+        k: usize,
+        scorer_k1: f32,
+        scorer_b: f32,
+    ) -> Vec<ScoredDocId> {
+        if max_edits == 0 || !self.is_enabled() {
+            return self.score_top_k(query_terms, branch_id, k, scorer_k1, scorer_b);
+        }
+        let expanded: Vec<(String, f32)> = query_terms
+            .iter()
+            .flat_map(|t| match fuzzy::allowed_edits(t, max_edits) {
+                0 => vec![(t.clone(), 1.0)],
+                edits => self
+                    .fuzzy_terms(t, edits)
+                    .into_iter()
+                    .map(|(term, d)| (term, fuzzy::edit_weight(d)))
+                    .collect(),
+            })
+            .collect();
+        let weighted: Vec<(&str, f32)> = expanded.iter().map(|(t, w)| (t.as_str(), *w)).collect();
+        self.score_weighted_top_k(&weighted, branch_id, k, scorer_k1, scorer_b)
+    }
+
+    /// Index terms within `max_edits` edits of `term`, with their distance
+    ///
+    /// Includes `term` itself at distance 0 when it is indexed. Only terms
+    /// sharing the first character of `term` are considered.
+    pub fn fuzzy_terms(&self, term: &str, max_edits: u8) -> Vec<(String, u8)> {
+        let first = match term.chars().next() {
+            Some(c) => c,
+            None => return Vec::new(),
+        };
+        let prefix = &term[..first.len_utf8()];
+
+        let mut found: BTreeMap<String, u8> = BTreeMap::new();
+        let mut consider = |candidate: &str| {
+            if found.contains_key(candidate) {
+                return;
+            }
+            if let Some(d) = fuzzy::bounded_distance(term, candidate, max_edits) {
+                found.insert(candidate.to_string(), d);
+            }
+        };
+        for entry in self.postings.iter() {
+            if entry.key().starts_with(prefix) {
+                consider(entry.key());
+            }
+        }
+        for seg in self.sealed.read().unwrap().iter() {
+            for candidate in seg.terms_with_prefix(prefix) {
+                consider(candidate);
+            }
+        }
+        found.into_iter().collect()
+    }
+
+    /// BM25 top-k over `(term, weight)` pairs; each term's IDF is scaled by
+    /// its weight.
+    fn score_weighted_top_k(
+        &self,
+        query_terms: &[(&str, f32)],
+        branch_id: &BranchId,
+        k: usize,
+        scorer_k1: f32,
+        scorer_b: f32,
     ) -> Vec<ScoredDocId> {
         if !self.is_enabled() || query_terms.is_empty() || k == 0 {
             return Vec::new();
@@ -485,8 +589,8 @@ impl InvertedIndex {
         // Each entry: (term_str, idf, Vec<Option<(posting_offset, posting_byte_len)>>)
         let term_data: Vec<(&str, f32, Vec<Option<(u32, u32)>>)> = query_terms
             .iter()
-            .map(|t| {
-                let active_df = self.doc_freqs.get(t.as_str()).map(|r| *r).unwrap_or(0);
+            .map(|&(t, weight)| {
+                let active_df = self.doc_freqs.get(t).map(|r| *r).unwrap_or(0);
                 let mut seg_locations = Vec::with_capacity(num_sealed);
                 let mut sealed_df: usize = 0;
                 for seg in sealed.iter() {
@@ -502,7 +606,7 @@ impl InvertedIndex {
                 }
                 let df = (active_df + sealed_df) as f32;
                 let idf = ((total_docs - df + 0.5) / (df + 0.5) + 1.0).ln();
-                (t.as_str(), idf, seg_locations)
+                (t, idf * weight, seg_locations)
             })
             .collect();
 
@@ -619,12 +723,13 @@ impl InvertedIndex {
             })
             .collect();
 
-        let cmp =
-            |a: &ScoredDocId, b: &ScoredDocId| -> std::cmp::Ordering {
-                b.score
-                    .partial_cmp(&a.score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            };
+        // Ties break on doc_id so the top-k cut is the same on every run
+        let cmp = |a: &ScoredDocId, b: &ScoredDocId| -> std::cmp::Ordering {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.doc_id.cmp(&b.doc_id))
+        };
 
         if result.len() > k {
             // O(n) partition: puts top-k elements in [0..k] (unordered)
@@ -878,7 +983,11 @@ impl InvertedIndex {
     /// 1. Seal active segment if non-empty
     /// 2. Flush any in-memory sealed segments to .sidx files
     /// 3. Write search.manifest with DocIdMap + stats
-    pub fn freeze_to_disk(&self) -> std::io::Result<()> {
+    ///
+    /// `data_version` is the database version the index reflects, read
+    /// before sealing. `load_from_disk` only accepts the manifest while the
+    /// database is still at that version.
+    pub fn freeze_to_disk(&self, data_version: u64) -> std::io::Result<()> {
         let search_dir = match self.search_dir() {
             Some(d) => d,
             None => return Ok(()), // No data dir — ephemeral
@@ -919,6 +1028,7 @@ impl InvertedIndex {
             segments: segment_entries,
             doc_id_map: doc_id_map_vec,
             doc_lengths: doc_lengths_vec,
+            data_version: Some(data_version),
         };
 
         let manifest_path = search_dir.join("search.manifest");
@@ -936,8 +1046,10 @@ impl InvertedIndex {
 
     /// Load index state from manifest and mmap'd segments.
     ///
-    /// Returns true if successfully loaded, false if no manifest found.
-    pub fn load_from_disk(&self) -> std::io::Result<bool> {
+    /// Returns true if successfully loaded, false if no manifest found or
+    /// the manifest was frozen at a version other than `data_version`. A
+    /// stale manifest leaves the index untouched.
+    pub fn load_from_disk(&self, data_version: u64) -> std::io::Result<bool> {
         let search_dir = match self.search_dir() {
             Some(d) => d,
             None => return Ok(false),
@@ -949,6 +1061,15 @@ impl InvertedIndex {
         }
 
         let data = manifest::load_manifest(&manifest_path)?;
+        if data.data_version != Some(data_version) {
+            tracing::info!(
+                target: "strata::search",
+                frozen_at = ?data.data_version,
+                current = data_version,
+                "Search index on disk is stale"
+            );
+            return Ok(false);
+        }
 
         // Restore DocIdMap
         self.doc_id_map.restore_from_vec(data.doc_id_map);
@@ -1599,6 +1720,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_score_top_k_fuzzy_matches_across_segments() {
+        let index = InvertedIndex::new();
+        index.enable();
+        let branch_id = BranchId::new();
+
+        index.index_document(&kv_ref(branch_id, "sealed"), "database replication", None);
+        index.seal_active();
+        index.index_document(&kv_ref(branch_id, "active"), "database backup", None);
+        index.index_document(&kv_ref(branch_id, "other"), "unrelated text", None);
+
+        let terms = tokenize("databse");
+        assert!(index
+            .score_top_k_fuzzy(&terms, 0, &branch_id, 10, 0.9, 0.4)
+            .is_empty());
+
+        let result = index.score_top_k_fuzzy(&terms, 2, &branch_id, 10, 0.9, 0.4);
+        let mut keys: Vec<String> = result
+            .iter()
+            .map(|s| match index.resolve_doc_id(s.doc_id).unwrap() {
+                EntityRef::Kv { key, .. } => key,
+                other => panic!("unexpected ref {:?}", other),
+            })
+            .collect();
+        keys.sort();
+        assert_eq!(keys, vec!["active", "sealed"]);
+    }
+
+    #[test]
+    fn test_score_top_k_fuzzy_ranks_exact_match_first() {
+        let index = InvertedIndex::new();
+        index.enable();
+        let branch_id = BranchId::new();
+
+        let exact = kv_ref(branch_id, "exact");
+        index.index_document(&exact, "kernel panic", None);
+        index.index_document(&kv_ref(branch_id, "near"), "kennel panic", None);
+
+        let terms = tokenize("kernel");
+        let result = index.score_top_k_fuzzy(&terms, 1, &branch_id, 10, 0.9, 0.4);
+        assert_eq!(result.len(), 2);
+        assert_eq!(index.resolve_doc_id(result[0].doc_id), Some(exact));
+        assert!(result[0].score > result[1].score);
+    }
+
     #[test]
     fn test_score_top_k_nonexistent_branch() {
         let index = InvertedIndex::new();
@@ -1904,14 +2070,14 @@ mod tests {
             index.remove_document(&doc1);
             assert_eq!(index.total_docs(), 1);
 
-            index.freeze_to_disk().unwrap();
+            index.freeze_to_disk(1).unwrap();
         }
 
         // Load from disk and verify tombstones survived
         {
             let index = InvertedIndex::new();
             index.set_data_dir(tmp.path().to_path_buf());
-            let loaded = index.load_from_disk().unwrap();
+            let loaded = index.load_from_disk(1).unwrap();
             assert!(loaded);
             index.enable();
 
@@ -1943,13 +2109,13 @@ mod tests {
             index.index_document(&doc1, "hello world", None);
             index.index_document(&doc2, "hello planet", None);
 
-            index.freeze_to_disk().unwrap();
+            index.freeze_to_disk(1).unwrap();
         }
 
         {
             let index = InvertedIndex::new();
             index.set_data_dir(tmp.path().to_path_buf());
-            index.load_from_disk().unwrap();
+            index.load_from_disk(1).unwrap();
             index.enable();
 
             let terms = vec!["hello".to_string()];
@@ -1966,6 +2132,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_load_from_disk_rejects_stale_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let branch_id = BranchId::new();
+
+        {
+            let index = InvertedIndex::new();
+            index.enable();
+            index.set_data_dir(tmp.path().to_path_buf());
+            index.index_document(&kv_ref(branch_id, "doc1"), "hello world", None);
+            index.freeze_to_disk(5).unwrap();
+        }
+
+        let index = InvertedIndex::new();
+        index.set_data_dir(tmp.path().to_path_buf());
+        assert!(!index.load_from_disk(6).unwrap());
+        assert_eq!(index.total_docs(), 0);
+        assert!(index.load_from_disk(5).unwrap());
+        assert_eq!(index.total_docs(), 1);
+    }
+
     #[test]
     fn test_score_top_k_sealed_matches_bm25_formula() {
         // Verify the precomputed BM25 constants produce correct scores
@@ -2118,14 +2305,14 @@ mod tests {
                 index.index_document(&doc, &format!("hello world extra{}", i), None);
             }
 
-            index.freeze_to_disk().unwrap();
+            index.freeze_to_disk(1).unwrap();
         }
 
         // Create new index, load from disk
         {
             let index = InvertedIndex::new();
             index.set_data_dir(tmp.path().to_path_buf());
-            let loaded = index.load_from_disk().unwrap();
+            let loaded = index.load_from_disk(1).unwrap();
             assert!(loaded);
 
             assert_eq!(index.total_docs(), 20);
@@ -2287,13 +2474,13 @@ mod tests {
                 let doc = kv_ref(branch_id, &format!("doc{}", i));
                 index.index_document(&doc, "hello world", None);
             }
-            index.freeze_to_disk().unwrap();
+            index.freeze_to_disk(1).unwrap();
         }
 
         {
             let index = InvertedIndex::new();
             index.set_data_dir(tmp.path().to_path_buf());
-            index.load_from_disk().unwrap();
+            index.load_from_disk(1).unwrap();
             index.enable();
 
             // branch_ids should be rebuilt from DocIdMap
@@ -2327,13 +2514,13 @@ mod tests {
             index.index_document(&doc2, "hello planet", None);
             index.seal_active();
             index.remove_document(&doc1);
-            index.freeze_to_disk().unwrap();
+            index.freeze_to_disk(1).unwrap();
         }
 
         {
             let index = InvertedIndex::new();
             index.set_data_dir(tmp.path().to_path_buf());
-            index.load_from_disk().unwrap();
+            index.load_from_disk(1).unwrap();
             index.enable();
 
             // has_tombstones should be true after loading tombstoned segment
@@ -5,6 +5,7 @@
 //! - Global DocIdMap (EntityRef ↔ u32)
 //! - Global stats (total_docs, total_doc_len, next_segment_id)
 //! - Per-segment tombstone sets (deleted doc_ids)
+//! - The database version the index was frozen at, to detect a stale index
 //!
 //! Written atomically via temp + rename (same as vector mmap pattern).
 
@@ -42,6 +43,10 @@ pub(crate) struct ManifestData {
     /// for re-index detection and accurate total_doc_len on removal.
     #[serde(default)]
     pub doc_lengths: Vec<Option<u32>>,
+    /// Database version the index reflects. `None` for manifests written
+    /// before this was recorded, which are treated as stale.
+    #[serde(default)]
+    pub data_version: Option<u64>,
 }
 
 /// Manifest entry for a single sealed segment.
@@ -165,6 +170,7 @@ mod tests {
                 },
             ],
             doc_lengths: vec![Some(10), Some(20), Some(15)],
+            data_version: Some(77),
         }
     }
 
@@ -186,6 +192,7 @@ mod tests {
         assert!(loaded.segments[1].tombstones.contains(&42));
         assert_eq!(loaded.doc_id_map.len(), 3);
         assert_eq!(loaded.doc_lengths, vec![Some(10), Some(20), Some(15)]);
+        assert_eq!(loaded.data_version, Some(77));
     }
 
     #[test]
@@ -201,6 +208,7 @@ mod tests {
             segments: vec![],
             doc_id_map: vec![],
             doc_lengths: vec![],
+            data_version: None,
         };
         write_manifest(&path, &data).unwrap();
 
@@ -8,7 +8,9 @@
 //! - `manifest`: Search manifest for persisting segmented index state
 //! - `recovery`: Recovery participant for restoring index on startup
 //! - `tokenizer`: Basic text tokenization
+//! - `fuzzy`: Edit-distance matching for typo-tolerant keyword search
 
+mod fuzzy;
 mod index;
 pub(crate) mod manifest;
 pub(crate) mod recovery;
@@ -18,7 +20,8 @@ pub mod stemmer;
 pub mod tokenizer;
 mod types;
 
-pub use index::{InvertedIndex, PostingEntry, PostingList, ScoredDocId};
+pub use fuzzy::MAX_TYPO_TOLERANCE;
+pub use index::{IndexStats, InvertedIndex, PostingEntry, PostingList, ScoredDocId};
 pub use recovery::register_search_recovery;
 pub use searchable::{
     build_search_response, build_search_response_with_index, build_search_response_with_scorer,
@@ -27,6 +30,6 @@ pub use searchable::{
 };
 pub use tokenizer::{tokenize, tokenize_unique};
 pub use types::{
-    EntityRef, PrimitiveType, SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse,
-    SearchStats,
+    cmp_ranked, tie_break_cmp, DedupMode, EntityRef, PrimitiveType, SearchBudget, SearchHit,
+    SearchMode, SearchRequest, SearchResponse, SearchStats,
 };
@@ -11,8 +11,17 @@
 //!    - **Fast path**: Loads manifest + mmap'd sealed segments (sub-second)
 //!    - **Slow path**: Scans all KV/State/Event entries, indexes them, and
 //!      freezes to disk for the next open
+//!
+//!    The fast path is taken only if the manifest was frozen at the
+//!    database's current version. Writes committed after the last freeze,
+//!    e.g. before a crash, make the manifest stale and force the slow path.
 //! 4. Enables the index for search operations
 //!
+//! When the database is opened with `keyword_index = false`, recovery instead
+//! deletes the on-disk cache and leaves the index disabled. Writes made while
+//! it is off are never indexed, so the cache would be stale; deleting it
+//! forces the slow path the next time the index is turned on.
+//!
 //! ## mmap Acceleration
 //!
 //! After the first KV-scan recovery, the index is frozen to `{data_dir}/search/`
@@ -24,10 +33,10 @@
 
 use crate::database::Database;
 use crate::recovery::{register_recovery_participant, RecoveryParticipant};
-use crate::search::InvertedIndex;
+use crate::search::{IndexStats, InvertedIndex};
 use strata_core::types::TypeTag;
 use strata_core::value::Value;
-use strata_core::StrataResult;
+use strata_core::{StrataError, StrataResult};
 use tracing::info;
 
 /// Recovery function for the InvertedIndex.
@@ -49,6 +58,12 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
     let data_dir = db.data_dir();
     let use_disk = !data_dir.as_os_str().is_empty();
 
+    if !db.keyword_index_enabled() {
+        discard_index_cache(db);
+        info!(target: "strata::search", "Keyword index disabled, skipping search index recovery");
+        return Ok(());
+    }
+
     // Configure data directory for persistence
     if use_disk {
         index.set_data_dir(data_dir.to_path_buf());
@@ -57,8 +72,11 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
     // ---------------------------------------------------------------
     // Fast path: load from manifest + mmap'd sealed segments
     // ---------------------------------------------------------------
+    // Storage holds the whole WAL by now, so this is the version of the
+    // last committed write. A manifest frozen at any other version is stale.
+    let data_version = db.current_version();
     if use_disk {
-        match index.load_from_disk() {
+        match index.load_from_disk(data_version) {
             Ok(true) => {
                 info!(
                     target: "strata::search",
@@ -69,7 +87,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                 return Ok(());
             }
             Ok(false) => {
-                // No manifest found — fall through to slow path
+                // No manifest, or a stale one — fall through to slow path
             }
             Err(e) => {
                 tracing::warn!(
@@ -84,10 +102,50 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
     // ---------------------------------------------------------------
     // Slow path: rebuild from KV/State/Event entries
     // ---------------------------------------------------------------
+    // A failed load may have restored part of the manifest, and leftover
+    // segment files would collide with the ids of new segments.
+    index.clear();
+    discard_index_cache(db);
+
+    // index_document() is a no-op on a disabled index
+    index.enable();
+    let (docs_indexed, branches_scanned) = index_stored_documents(db, &index);
+
+    // Freeze to disk for next startup (fast path)
+    if use_disk && docs_indexed > 0 {
+        if let Err(e) = index.freeze_to_disk(data_version) {
+            tracing::warn!(
+                target: "strata::search",
+                error = %e,
+                "Failed to freeze search index after rebuild"
+            );
+        }
+    }
+
+    if docs_indexed > 0 || branches_scanned > 0 {
+        info!(
+            target: "strata::search",
+            docs_indexed = docs_indexed,
+            branches_scanned = branches_scanned,
+            "Search index recovery complete (slow path)"
+        );
+    }
+
+    Ok(())
+}
+
+/// Index every searchable KV, State and Event entry on every branch.
+///
+/// Returns the number of documents indexed and branches scanned. The index
+/// must already be enabled.
+fn index_stored_documents(db: &Database, index: &InvertedIndex) -> (u64, u64) {
     let mut docs_indexed: u64 = 0;
     let mut branches_scanned: u64 = 0;
 
     for branch_id in db.storage().branch_ids() {
+        if branch_id == crate::admin_log::admin_branch_id() {
+            continue;
+        }
         branches_scanned += 1;
 
         // --- KV entries ---
@@ -141,7 +199,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
         // --- Event entries ---
         for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::Event) {
             // Skip metadata keys (same as checkpoint logic)
-            if key.user_key == b"__meta__" || key.user_key.starts_with(b"__tidx__") {
+            if key.is_internal() {
                 continue;
             }
 
@@ -170,29 +228,59 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
         }
     }
 
-    // Freeze to disk for next startup (fast path)
-    if use_disk && docs_indexed > 0 {
-        if let Err(e) = index.freeze_to_disk() {
-            tracing::warn!(
-                target: "strata::search",
-                error = %e,
-                "Failed to freeze search index after rebuild"
-            );
-        }
-    }
+    (docs_indexed, branches_scanned)
+}
 
+/// Rebuild the keyword index from scratch and freeze it to disk.
+///
+/// Drops every in-memory segment and the on-disk cache, then re-indexes all
+/// stored documents. Searches that run during the rebuild may miss documents
+/// that have not been re-indexed yet.
+pub(crate) fn rebuild_index(db: &Database) -> StrataResult<IndexStats> {
+    let index = db.extension::<InvertedIndex>()?;
+    let data_dir = db.data_dir();
+    if !data_dir.as_os_str().is_empty() {
+        index.set_data_dir(data_dir.to_path_buf());
+    }
+    index.clear();
+    discard_index_cache(db);
     index.enable();
+    let data_version = db.current_version();
+    let (docs_indexed, branches_scanned) = index_stored_documents(db, &index);
 
-    if docs_indexed > 0 || branches_scanned > 0 {
-        info!(
+    index.freeze_to_disk(data_version).map_err(|e| {
+        StrataError::storage(format!("Failed to freeze rebuilt search index: {}", e))
+    })?;
+
+    info!(
+        target: "strata::search",
+        docs_indexed = docs_indexed,
+        branches_scanned = branches_scanned,
+        "Search index rebuilt"
+    );
+    Ok(index.stats())
+}
+
+/// Delete the persisted search index of a disk-backed database, if any.
+///
+/// The index is a cache of stored data, so this never loses data; the next
+/// recovery with the index enabled rebuilds it from storage.
+pub(crate) fn discard_index_cache(db: &Database) {
+    let data_dir = db.data_dir();
+    if data_dir.as_os_str().is_empty() {
+        return;
+    }
+    let search_dir = data_dir.join("search");
+    if !search_dir.exists() {
+        return;
+    }
+    if let Err(e) = std::fs::remove_dir_all(&search_dir) {
+        tracing::warn!(
             target: "strata::search",
-            docs_indexed = docs_indexed,
-            branches_scanned = branches_scanned,
-            "Search index recovery complete (slow path)"
+            error = %e,
+            "Failed to remove search index cache"
         );
     }
-
-    Ok(())
 }
 
 /// Register the InvertedIndex as a recovery participant.
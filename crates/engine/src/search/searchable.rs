@@ -11,7 +11,7 @@
 
 use super::index::InvertedIndex;
 use super::tokenizer::tokenize;
-use super::types::{EntityRef, SearchHit, SearchRequest, SearchResponse, SearchStats};
+use super::types::{cmp_ranked, EntityRef, SearchHit, SearchRequest, SearchResponse, SearchStats};
 use std::collections::HashMap;
 use strata_core::PrimitiveType;
 use strata_core::StrataResult;
@@ -455,7 +455,7 @@ impl SimpleScorer {
             .filter(|(_, score)| *score > 0.0)
             .collect();
 
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.sort_by(|a, b| cmp_ranked(a.1, &a.0.doc_ref, b.1, &b.0.doc_ref));
 
         scored
             .into_iter()
@@ -561,7 +561,7 @@ pub fn build_search_response_with_scorer(
                 .filter(|(_, score)| *score > 0.0)
                 .collect();
 
-            scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            scored.sort_by(|a, b| cmp_ranked(a.1, &a.0.doc_ref, b.1, &b.0.doc_ref));
 
             scored
                 .into_iter()
@@ -656,6 +656,25 @@ mod tests {
         assert!(hits[0].score >= hits.last().map(|h| h.score).unwrap_or(0.0));
     }
 
+    #[test]
+    fn test_score_and_rank_breaks_ties_by_entity() {
+        let branch_id = BranchId::new();
+        let candidates: Vec<_> = ["c", "a", "b"]
+            .iter()
+            .map(|key| {
+                SearchCandidate::new(EntityRef::kv(branch_id, *key), "hello".to_string(), None)
+            })
+            .collect();
+
+        let response = build_search_response(candidates, "hello", 2, false, 0);
+        let keys: Vec<_> = response
+            .hits
+            .iter()
+            .map(|h| h.doc_ref.kv_key().unwrap())
+            .collect();
+        assert_eq!(keys, vec!["a", "b"]);
+    }
+
     #[test]
     fn test_score_and_rank_respects_k() {
         let branch_id = BranchId::new();
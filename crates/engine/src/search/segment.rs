@@ -382,6 +382,49 @@ impl SealedSegment {
         None
     }
 
+    /// Term stored at position `idx` of the sorted term dictionary.
+    fn term_at(&self, idx: usize) -> Option<&str> {
+        let bytes = self.data.as_bytes();
+        let offset_pos = self.term_offsets_offset as usize + idx * 4;
+        if offset_pos + 4 > bytes.len() {
+            return None;
+        }
+        let dict_offset =
+            u32::from_le_bytes(bytes[offset_pos..offset_pos + 4].try_into().unwrap()) as usize;
+        let abs_pos = HEADER_SIZE + dict_offset;
+        if abs_pos + 2 > bytes.len() {
+            return None;
+        }
+        let term_len = u16::from_le_bytes(bytes[abs_pos..abs_pos + 2].try_into().unwrap()) as usize;
+        let term_start = abs_pos + 2;
+        let term_end = term_start + term_len;
+        if term_end > bytes.len() {
+            return None;
+        }
+        std::str::from_utf8(&bytes[term_start..term_end]).ok()
+    }
+
+    /// All terms in this segment that start with `prefix`, in sorted order.
+    ///
+    /// One binary search to the first candidate, then a linear walk while the
+    /// prefix still matches.
+    pub fn terms_with_prefix(&self, prefix: &str) -> Vec<&str> {
+        let tc = self.term_count as usize;
+        let mut lo = 0usize;
+        let mut hi = tc;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.term_at(mid) {
+                Some(term) if term < prefix => lo = mid + 1,
+                Some(_) => hi = mid,
+                None => return Vec::new(),
+            }
+        }
+        (lo..tc)
+            .map_while(|idx| self.term_at(idx).filter(|term| term.starts_with(prefix)))
+            .collect()
+    }
+
     /// Write this segment's data to a file (atomic temp+rename).
     pub fn write_to_file(&self, path: &Path) -> io::Result<()> {
         let dir = path.parent().unwrap_or(Path::new("."));
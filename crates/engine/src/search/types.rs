@@ -11,6 +11,7 @@
 //! These types define the interface contracts for search operations.
 //! See `the architecture documentation` for authoritative specification.
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use strata_core::types::BranchId;
 
@@ -103,6 +104,37 @@ pub enum SearchMode {
     Hybrid,
 }
 
+// ============================================================================
+// DedupMode
+// ============================================================================
+
+/// How fusion treats a document returned by more than one result list
+///
+/// In hybrid search the same document often surfaces from both the keyword
+/// index and the vector index: an auto-embedded KV value is found by BM25
+/// and, through its shadow embedding, by vector similarity.
+///
+/// # Document identity
+///
+/// Two hits are the same document when their [`EntityRef`]s are equal:
+/// same primitive, same branch, and same primitive-specific address (KV key,
+/// JSON document id, state cell name, event sequence, or vector collection
+/// and key). Hits from different primitives are never the same document,
+/// even if they share a key. Spaces are not part of the identity, so equal
+/// keys in two spaces of one branch count as one document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupMode {
+    /// One hit per document, scored by the sum of its fused contributions,
+    /// so agreement between sources raises it (default)
+    #[default]
+    Merge,
+    /// One hit per appearance; a document found by two sources is returned
+    /// twice, each scored by its own list
+    KeepBoth,
+    /// One hit per document, scored by its best single contribution
+    KeepHigher,
+}
+
 // ============================================================================
 // SearchRequest
 // ============================================================================
@@ -137,6 +169,12 @@ pub struct SearchRequest {
     /// Run to search within
     pub branch_id: BranchId,
 
+    /// Space whose entries are read for snippets and write times
+    ///
+    /// The index does not separate spaces (see [`DedupMode`]); this names
+    /// the space a hit's current value is looked up in.
+    pub space: String,
+
     /// Query string (interpreted by scorer)
     pub query: String,
 
@@ -153,35 +191,71 @@ pub struct SearchRequest {
     pub primitive_filter: Option<Vec<PrimitiveType>>,
 
     /// Optional: time range filter (microseconds since epoch)
+    ///
+    /// Keeps only candidates whose current version was written within the
+    /// inclusive range. See [`time_window`](Self::time_window) for how this
+    /// combines with `as_of`.
     pub time_range: Option<(u64, u64)>,
 
+    /// Optional: point-in-time bound (microseconds since epoch)
+    ///
+    /// Excludes candidates written after this timestamp.
+    pub as_of: Option<u64>,
+
     /// Optional: tag filter (match any)
     pub tags_any: Vec<String>,
+
+    /// How fusion handles a document found by several sources
+    pub dedup: DedupMode,
+
+    /// Maximum edits per query term for keyword matching (0 = exact only)
+    ///
+    /// Lets a misspelled query term match index terms up to this many
+    /// insertions, deletions or substitutions away, scored below exact
+    /// matches. Short terms get fewer edits than requested. Raises recall on
+    /// messy input at the cost of extra vocabulary scanning and posting
+    /// lists scored per term, and can surface unrelated words one edit away.
+    /// Capped at [`MAX_TYPO_TOLERANCE`](crate::search::MAX_TYPO_TOLERANCE).
+    pub typo_tolerance: u8,
 }
 
 impl SearchRequest {
     /// Create a new SearchRequest with defaults
     ///
     /// Default values:
+    /// - space: "default"
     /// - k: 10
     /// - budget: SearchBudget::default()
     /// - mode: SearchMode::Keyword
     /// - primitive_filter: None (search all primitives)
     /// - time_range: None
+    /// - as_of: None
     /// - tags_any: empty
+    /// - dedup: DedupMode::Merge
+    /// - typo_tolerance: 0 (exact matching)
     pub fn new(branch_id: BranchId, query: impl Into<String>) -> Self {
         SearchRequest {
             branch_id,
+            space: "default".to_string(),
             query: query.into(),
             k: 10,
             budget: SearchBudget::default(),
             mode: SearchMode::default(),
             primitive_filter: None,
             time_range: None,
+            as_of: None,
             tags_any: vec![],
+            dedup: DedupMode::default(),
+            typo_tolerance: 0,
         }
     }
 
+    /// Builder: set the space hits are read from
+    pub fn with_space(mut self, space: impl Into<String>) -> Self {
+        self.space = space.into();
+        self
+    }
+
     /// Builder: set top-k results count
     pub fn with_k(mut self, k: usize) -> Self {
         self.k = k;
@@ -212,12 +286,46 @@ impl SearchRequest {
         self
     }
 
+    /// Builder: set point-in-time bound
+    pub fn with_as_of(mut self, as_of: u64) -> Self {
+        self.as_of = Some(as_of);
+        self
+    }
+
     /// Builder: set tags filter
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
         self.tags_any = tags;
         self
     }
 
+    /// Builder: set deduplication policy
+    pub fn with_dedup(mut self, dedup: DedupMode) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Builder: set typo tolerance, capped at
+    /// [`MAX_TYPO_TOLERANCE`](crate::search::MAX_TYPO_TOLERANCE)
+    pub fn with_typo_tolerance(mut self, max_edits: u8) -> Self {
+        self.typo_tolerance = max_edits.min(super::MAX_TYPO_TOLERANCE);
+        self
+    }
+
+    /// Effective write-time window for candidates
+    ///
+    /// Combines `time_range` and `as_of`: `as_of` caps the upper bound of the
+    /// range, and on its own acts as a range from the epoch to `as_of`.
+    /// Returns `None` when neither is set. A window whose start is after its
+    /// capped end matches nothing.
+    pub fn time_window(&self) -> Option<(u64, u64)> {
+        match (self.time_range, self.as_of) {
+            (Some((start, end)), Some(as_of)) => Some((start, end.min(as_of))),
+            (Some(range), None) => Some(range),
+            (None, Some(as_of)) => Some((0, as_of)),
+            (None, None) => None,
+        }
+    }
+
     /// Check if a primitive is included in this request
     pub fn includes_primitive(&self, kind: PrimitiveType) -> bool {
         match &self.primitive_filter {
@@ -266,6 +374,67 @@ impl SearchHit {
         self.snippet = Some(snippet);
         self
     }
+
+    /// Ranking order: score descending, then [`tie_break_cmp`]
+    pub fn cmp_rank(&self, other: &SearchHit) -> Ordering {
+        cmp_ranked(self.score, &self.doc_ref, other.score, &other.doc_ref)
+    }
+}
+
+// ============================================================================
+// Tie-breaking
+// ============================================================================
+
+/// Order two scored entities: score descending, then [`tie_break_cmp`]
+///
+/// Every ranking stage (scoring, score merge, RRF fusion, rerank blending)
+/// sorts with this comparator, so hits with equal scores come out in the
+/// same order on every run and every node, and pages of results do not
+/// shift between requests.
+pub fn cmp_ranked(a_score: f32, a: &EntityRef, b_score: f32, b: &EntityRef) -> Ordering {
+    b_score
+        .partial_cmp(&a_score)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| tie_break_cmp(a, b))
+}
+
+/// Stable total order on entity references, used to break score ties
+///
+/// Compares, in order:
+/// 1. Primitive kind, in [`PrimitiveType::ALL`] order (kv, event, state,
+///    branch, json, vector)
+/// 2. Identifier within the primitive, lexicographically (key, cell name,
+///    doc id, collection then key) or numerically (event sequence)
+/// 3. Branch id bytes
+pub fn tie_break_cmp(a: &EntityRef, b: &EntityRef) -> Ordering {
+    let kind = |r: &EntityRef| {
+        let t = r.primitive_type();
+        PrimitiveType::ALL.iter().position(|p| *p == t)
+    };
+    kind(a)
+        .cmp(&kind(b))
+        .then_with(|| match (a, b) {
+            (EntityRef::Kv { key: ka, .. }, EntityRef::Kv { key: kb, .. }) => ka.cmp(kb),
+            (EntityRef::Event { sequence: sa, .. }, EntityRef::Event { sequence: sb, .. }) => {
+                sa.cmp(sb)
+            }
+            (EntityRef::State { name: na, .. }, EntityRef::State { name: nb, .. }) => na.cmp(nb),
+            (EntityRef::Json { doc_id: da, .. }, EntityRef::Json { doc_id: db, .. }) => da.cmp(db),
+            (
+                EntityRef::Vector {
+                    collection: ca,
+                    key: ka,
+                    ..
+                },
+                EntityRef::Vector {
+                    collection: cb,
+                    key: kb,
+                    ..
+                },
+            ) => (ca, ka).cmp(&(cb, kb)),
+            _ => Ordering::Equal,
+        })
+        .then_with(|| a.branch_id().as_bytes().cmp(b.branch_id().as_bytes()))
 }
 
 // ============================================================================
@@ -415,6 +584,49 @@ mod tests {
         assert_eq!(budget.max_candidates_per_primitive, 3_333);
     }
 
+    // ========================================
+    // Tie-break Tests
+    // ========================================
+
+    #[test]
+    fn test_tie_break_cmp_kind_then_id_then_branch() {
+        let b1 = BranchId::from_bytes([1; 16]);
+        let b2 = BranchId::from_bytes([2; 16]);
+        let mut refs = vec![
+            EntityRef::vector(b1, "col", "a"),
+            EntityRef::json(b1, "a"),
+            EntityRef::kv(b2, "a"),
+            EntityRef::event(b1, 10),
+            EntityRef::kv(b1, "b"),
+            EntityRef::event(b1, 9),
+            EntityRef::kv(b1, "a"),
+        ];
+        refs.sort_by(tie_break_cmp);
+        assert_eq!(
+            refs,
+            vec![
+                EntityRef::kv(b1, "a"),
+                EntityRef::kv(b2, "a"),
+                EntityRef::kv(b1, "b"),
+                EntityRef::event(b1, 9),
+                EntityRef::event(b1, 10),
+                EntityRef::json(b1, "a"),
+                EntityRef::vector(b1, "col", "a"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cmp_ranked_score_first() {
+        let branch_id = BranchId::from_bytes([0; 16]);
+        let a = SearchHit::new(EntityRef::kv(branch_id, "z"), 0.9, 0);
+        let b = SearchHit::new(EntityRef::kv(branch_id, "a"), 0.5, 0);
+        let c = SearchHit::new(EntityRef::kv(branch_id, "b"), 0.5, 0);
+        assert_eq!(a.cmp_rank(&b), Ordering::Less);
+        assert_eq!(b.cmp_rank(&c), Ordering::Less);
+        assert_eq!(c.cmp_rank(&b), Ordering::Greater);
+    }
+
     // ========================================
     // SearchMode Tests
     // ========================================
@@ -448,6 +660,7 @@ mod tests {
         assert!(req.primitive_filter.is_none());
         assert!(req.time_range.is_none());
         assert!(req.tags_any.is_empty());
+        assert_eq!(req.typo_tolerance, 0);
     }
 
     #[test]
@@ -469,6 +682,31 @@ mod tests {
         );
         assert_eq!(req.time_range, Some((1000, 2000)));
         assert_eq!(req.tags_any, vec!["important".to_string()]);
+
+        let req = SearchRequest::new(branch_id, "test query").with_typo_tolerance(5);
+        assert_eq!(req.typo_tolerance, crate::search::MAX_TYPO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_search_request_time_window() {
+        let branch_id = BranchId::new();
+        let req = SearchRequest::new(branch_id, "q");
+        assert_eq!(req.time_window(), None);
+
+        let req = req.with_time_range(1000, 2000);
+        assert_eq!(req.time_window(), Some((1000, 2000)));
+
+        // as_of caps the upper bound
+        let capped = req.clone().with_as_of(1500);
+        assert_eq!(capped.time_window(), Some((1000, 1500)));
+
+        // as_of past the window leaves it unchanged
+        let later = req.with_as_of(5000);
+        assert_eq!(later.time_window(), Some((1000, 2000)));
+
+        // as_of alone bounds from the epoch
+        let as_of_only = SearchRequest::new(branch_id, "q").with_as_of(700);
+        assert_eq!(as_of_only.time_window(), Some((0, 700)));
     }
 
     #[test]
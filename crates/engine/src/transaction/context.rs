@@ -22,8 +22,8 @@ use crate::transaction_ops::TransactionOps;
 use strata_concurrency::{JsonStoreExt, TransactionContext};
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::{
-    BranchMetadata, BranchStatus, EntityRef, Event, JsonPatch, JsonPath, JsonValue, MetadataFilter,
-    State, StrataError, Timestamp, Value, VectorEntry, VectorMatch, Version, Versioned,
+    BranchMetadata, BranchStatus, EntityRef, Event, JsonPatch, JsonPath, JsonPathValue, JsonValue,
+    MetadataFilter, State, StrataError, Value, VectorEntry, VectorMatch, Version, Versioned,
 };
 
 /// Transaction wrapper that implements TransactionOps
@@ -250,7 +250,7 @@ impl<'a> TransactionOps for Transaction<'a> {
 
     fn event_append(&mut self, event_type: &str, payload: Value) -> Result<Version, StrataError> {
         let sequence = self.next_sequence();
-        let timestamp = Timestamp::now().as_micros();
+        let timestamp = self.ctx.now().as_micros();
         let prev_hash = self.last_hash;
 
         // Create the event
@@ -495,11 +495,7 @@ impl<'a> TransactionOps for Transaction<'a> {
         Ok(None)
     }
 
-    fn json_get_path(
-        &self,
-        doc_id: &str,
-        path: &JsonPath,
-    ) -> Result<Option<JsonValue>, StrataError> {
+    fn json_get_path(&self, doc_id: &str, path: &JsonPath) -> Result<JsonPathValue, StrataError> {
         let full_key = self.json_key(doc_id);
 
         // Check json_writes for writes affecting this path (read-your-writes)
@@ -513,7 +509,7 @@ impl<'a> TransactionOps for Transaction<'a> {
                         // If the set path is an ancestor of or equal to our path
                         if set_path.is_ancestor_of(path) || set_path == path {
                             if set_path == path {
-                                return Ok(Some(value.clone()));
+                                return Ok(JsonPathValue::Present(value.clone()));
                             }
                             // Navigate into the written value using relative path
                             let relative_segments: Vec<_> = path
@@ -523,20 +519,20 @@ impl<'a> TransactionOps for Transaction<'a> {
                                 .cloned()
                                 .collect();
                             let relative_path = JsonPath::from_segments(relative_segments);
-                            return Ok(strata_core::get_at_path(value, &relative_path).cloned());
+                            return Ok(strata_core::get_at_path(value, &relative_path).into());
                         }
                     }
                     JsonPatch::Delete { path: del_path } => {
                         if del_path.is_ancestor_of(path) || del_path == path {
-                            return Ok(None);
+                            return Ok(JsonPathValue::Missing);
                         }
                     }
                 }
             }
         }
 
-        // For paths not in json_writes, return None
-        Ok(None)
+        // For paths not in json_writes, report Missing
+        Ok(JsonPathValue::Missing)
     }
 
     fn json_set(
@@ -1106,7 +1102,27 @@ mod tests {
         // Get the path value back
         let result = txn.json_get_path("doc", &path).unwrap();
         let expected: JsonValue = serde_json::json!(25).into();
-        assert_eq!(result, Some(expected));
+        assert_eq!(result, JsonPathValue::Present(expected));
+    }
+
+    #[test]
+    fn test_json_get_path_distinguishes_null_from_missing() {
+        let ns = create_test_namespace();
+        let mut ctx = create_test_context(&ns);
+        let mut txn = Transaction::new(&mut ctx, ns.clone());
+
+        let doc: JsonValue = serde_json::json!({"a": null, "b": {"c": null}}).into();
+        txn.json_create("doc", doc).unwrap();
+
+        let read = |p: &str| txn.json_get_path("doc", &p.parse().unwrap()).unwrap();
+        assert_eq!(read("a"), JsonPathValue::Present(JsonValue::null()));
+        assert!(read("b.c").is_null());
+        assert!(read("missing").is_missing());
+        assert!(read("b.missing").is_missing());
+        assert!(txn
+            .json_get_path("other", &JsonPath::root())
+            .unwrap()
+            .is_missing());
     }
 
     #[test]
@@ -1180,6 +1196,6 @@ mod tests {
 
         // Get at root path returns the whole document
         let result = txn.json_get_path("doc", &JsonPath::root()).unwrap();
-        assert_eq!(result, Some(doc));
+        assert_eq!(result, JsonPathValue::Present(doc));
     }
 }
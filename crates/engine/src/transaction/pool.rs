@@ -89,10 +89,12 @@ impl TransactionPool {
     /// Context is returned if pool has room, dropped otherwise.
     /// Contexts that have grown large internal collections are
     /// valuable to keep as they avoid future allocations.
+    /// The context's snapshot is dropped either way.
     ///
     /// # Arguments
     /// * `ctx` - Transaction context to return to pool
-    pub fn release(ctx: TransactionContext) {
+    pub fn release(mut ctx: TransactionContext) {
+        ctx.release_snapshot();
         TXN_POOL.with(|pool| {
             let mut pool = pool.borrow_mut();
             if pool.len() < MAX_POOL_SIZE {
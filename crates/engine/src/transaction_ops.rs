@@ -30,8 +30,8 @@
 //! ```
 
 use strata_core::{
-    BranchMetadata, BranchStatus, Event, JsonPath, JsonValue, MetadataFilter, State, StrataError,
-    Value, VectorEntry, VectorMatch, Version, Versioned,
+    BranchMetadata, BranchStatus, Event, JsonPath, JsonPathValue, JsonValue, MetadataFilter, State,
+    StrataError, Value, VectorEntry, VectorMatch, Version, Versioned,
 };
 
 /// Operations available within a transaction
@@ -119,11 +119,11 @@ pub trait TransactionOps {
     fn json_get(&self, doc_id: &str) -> Result<Option<Versioned<JsonValue>>, StrataError>;
 
     /// Get a value at a path within a JSON document
-    fn json_get_path(
-        &self,
-        doc_id: &str,
-        path: &JsonPath,
-    ) -> Result<Option<JsonValue>, StrataError>;
+    ///
+    /// Returns [`JsonPathValue::Missing`] when the document or path does not
+    /// exist and [`JsonPathValue::Present`] otherwise, including when the
+    /// path holds JSON `null`.
+    fn json_get_path(&self, doc_id: &str, path: &JsonPath) -> Result<JsonPathValue, StrataError>;
 
     /// Set a value at a path within a JSON document
     fn json_set(
@@ -341,16 +341,10 @@ mod tests {
             &self,
             doc_id: &str,
             path: &JsonPath,
-        ) -> Result<Option<JsonValue>, StrataError> {
+        ) -> Result<JsonPathValue, StrataError> {
             match self.json_data.get(doc_id) {
-                Some(doc) => {
-                    if path.is_root() {
-                        Ok(Some(doc.clone()))
-                    } else {
-                        Ok(strata_core::get_at_path(doc, path).cloned())
-                    }
-                }
-                None => Ok(None),
+                Some(doc) => Ok(strata_core::get_at_path(doc, path).into()),
+                None => Ok(JsonPathValue::Missing),
             }
         }
 
@@ -0,0 +1,38 @@
+//! Small filesystem helpers shared across the engine.
+
+use std::path::Path;
+
+/// Total size in bytes of all files under `path`.
+///
+/// Entries that cannot be read count as 0, and a missing directory is 0.
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| match entry.metadata() {
+                    Ok(m) if m.is_dir() => dir_size(&entry.path()),
+                    Ok(m) => m.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dir_size_sums_nested_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a"), [0u8; 10]).unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub").join("b"), [0u8; 32]).unwrap();
+
+        assert_eq!(dir_size(dir.path()), 42);
+        assert_eq!(dir_size(&dir.path().join("missing")), 0);
+    }
+}
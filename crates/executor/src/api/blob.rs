@@ -0,0 +1,361 @@
+//! Chunked blob storage for large values.
+//!
+//! A blob is stored as a manifest plus a series of fixed-size chunks, all in
+//! the KV namespace of the current branch and space under the reserved
+//! `_strata/blob/` prefix. Writing streams the input one chunk per
+//! transaction, so no WAL record holds more than [`BLOB_CHUNK_SIZE`] bytes of
+//! blob data and the whole blob is never in memory. The manifest is written
+//! last and is the commit point: a blob becomes visible, or replaces its
+//! previous contents, only once every chunk is durable.
+//!
+//! Layout:
+//!
+//! - `_strata/blob/meta/<key>`: `{"id": <uuid>, "chunks": <n>, "len": <bytes>}`
+//! - `_strata/blob/chunk/<uuid>/<index>`: `Value::Bytes` of one chunk
+//!
+//! Chunks are keyed by a fresh id per write rather than by blob key, so an
+//! overwrite never mixes old and new chunks.
+
+use std::borrow::Cow;
+use std::io::Read;
+use std::sync::Arc;
+
+use strata_core::types::{Key, Namespace};
+use strata_engine::Database;
+
+use super::Strata;
+use crate::bridge::{to_core_branch_id, validate_key_with_limits};
+use crate::convert::convert_result;
+use crate::{AccessMode, Error, Result, Value};
+
+/// Size of each stored blob chunk in bytes.
+pub const BLOB_CHUNK_SIZE: usize = 1024 * 1024;
+
+const META_PREFIX: &str = "_strata/blob/meta/";
+const CHUNK_PREFIX: &str = "_strata/blob/chunk/";
+
+fn meta_key(ns: &Namespace, key: &str) -> Key {
+    Key::new_kv(ns.clone(), format!("{}{}", META_PREFIX, key))
+}
+
+fn chunk_key(ns: &Namespace, id: &str, index: u64) -> Key {
+    Key::new_kv(ns.clone(), format!("{}{}/{:010}", CHUNK_PREFIX, id, index))
+}
+
+/// Location of a blob's chunks, decoded from its manifest.
+struct Manifest {
+    id: String,
+    chunks: u64,
+    len: u64,
+}
+
+impl Manifest {
+    fn to_value(&self) -> Value {
+        Value::Object(
+            [
+                ("id".to_string(), Value::String(self.id.clone())),
+                ("chunks".to_string(), Value::Int(self.chunks as i64)),
+                ("len".to_string(), Value::Int(self.len as i64)),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+
+    fn from_value(value: &Value) -> Result<Self> {
+        let corrupt = || Error::Internal {
+            reason: "Corrupt blob manifest".into(),
+        };
+        let Value::Object(map) = value else {
+            return Err(corrupt());
+        };
+        match (map.get("id"), map.get("chunks"), map.get("len")) {
+            (Some(Value::String(id)), Some(Value::Int(chunks)), Some(Value::Int(len))) => {
+                Ok(Self {
+                    id: id.clone(),
+                    chunks: *chunks as u64,
+                    len: *len as u64,
+                })
+            }
+            _ => Err(corrupt()),
+        }
+    }
+}
+
+impl Strata {
+    // =========================================================================
+    // Blob Operations
+    // =========================================================================
+
+    /// Store a large value read from `reader` under `key`.
+    ///
+    /// The input is split into [`BLOB_CHUNK_SIZE`] chunks, each written in
+    /// its own transaction, so memory use and WAL record size stay bounded
+    /// regardless of blob size. An existing blob under `key` is replaced
+    /// atomically once the new contents are fully written; if reading or
+    /// writing fails part-way, the previous blob is left untouched.
+    ///
+    /// Blob keys are separate from KV keys: `kv_get(key)` does not see a blob.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let file = std::fs::File::open("model.bin")?;
+    /// db.blob_put("model", file)?;
+    /// ```
+    pub fn blob_put(&self, key: &str, mut reader: impl Read) -> Result<()> {
+        self.check_direct_write("BlobPut")?;
+        let key = &*self.normalized_key(key);
+        let limits = &self.executor.primitives().limits;
+        convert_result(validate_key_with_limits(key, limits))?;
+        let (db, ns) = self.direct_namespace()?;
+        let branch_id = ns.branch_id;
+
+        let manifest = Manifest {
+            id: uuid::Uuid::new_v4().to_string(),
+            chunks: 0,
+            len: 0,
+        };
+        let written = write_chunks(&db, &ns, &manifest.id, &mut reader);
+        let manifest = match written {
+            Ok((chunks, len)) => Manifest {
+                chunks,
+                len,
+                ..manifest
+            },
+            Err((chunks, e)) => {
+                discard_chunks(&db, &ns, &manifest.id, chunks);
+                return Err(e);
+            }
+        };
+
+        let meta = meta_key(&ns, key);
+        let committed = db.transaction(branch_id, |txn| {
+            let previous = txn.get(&meta)?;
+            txn.put(meta.clone(), manifest.to_value())?;
+            Ok(previous)
+        });
+        match committed {
+            Ok(Some(previous)) => {
+                // The old chunks are unreachable now; reclaim them.
+                if let Ok(old) = Manifest::from_value(&previous) {
+                    discard_chunks(&db, &ns, &old.id, old.chunks);
+                }
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(e) => {
+                discard_chunks(&db, &ns, &manifest.id, manifest.chunks);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Open the blob stored under `key` for streaming reads.
+    ///
+    /// Returns `None` if no blob exists. Chunks are fetched one at a time as
+    /// the returned reader is consumed. If the blob is replaced or deleted
+    /// while it is being read, the reader fails with an I/O error rather
+    /// than returning mixed contents.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// if let Some(mut blob) = db.blob_get("model")? {
+    ///     std::io::copy(&mut blob, &mut std::fs::File::create("model.bin")?)?;
+    /// }
+    /// ```
+    pub fn blob_get(&self, key: &str) -> Result<Option<BlobReader>> {
+        self.executor.check_open()?;
+        let (db, ns) = self.direct_namespace()?;
+        let meta = meta_key(&ns, &self.normalized_key(key));
+        let Some(value) = db.transaction(ns.branch_id, |txn| txn.get(&meta))? else {
+            return Ok(None);
+        };
+        let manifest = Manifest::from_value(&value)?;
+        Ok(Some(BlobReader {
+            db,
+            ns,
+            manifest,
+            next_chunk: 0,
+            buf: Vec::new(),
+            pos: 0,
+        }))
+    }
+
+    /// Delete the blob stored under `key`.
+    ///
+    /// The manifest and every chunk are removed in one transaction, so the
+    /// blob is either fully present or fully gone. Returns `true` if a blob
+    /// existed.
+    pub fn blob_delete(&self, key: &str) -> Result<bool> {
+        self.check_direct_write("BlobDelete")?;
+        let (db, ns) = self.direct_namespace()?;
+        let meta = meta_key(&ns, &self.normalized_key(key));
+        let deleted = db.transaction(ns.branch_id, |txn| {
+            let Some(value) = txn.get(&meta)? else {
+                return Ok(false);
+            };
+            if let Ok(manifest) = Manifest::from_value(&value) {
+                for index in 0..manifest.chunks {
+                    txn.delete(chunk_key(&ns, &manifest.id, index))?;
+                }
+            }
+            txn.delete(meta.clone())?;
+            Ok(true)
+        })?;
+        Ok(deleted)
+    }
+
+    /// Reject writes that bypass the command layer (blobs, counters) on
+    /// closed or read-only databases and missing branches.
+    pub(super) fn check_direct_write(&self, command: &str) -> Result<()> {
+        self.executor.check_open()?;
+        if self.access_mode == AccessMode::ReadOnly {
+            return Err(Error::AccessDenied {
+                command: command.to_string(),
+            });
+        }
+        if !self.current_branch.is_default() && !self.branches().exists(self.current_branch())? {
+            return Err(Error::BranchNotFound {
+                branch: self.current_branch().to_string(),
+            });
+        }
+        if self.current_space != "default" {
+            let branch_id = to_core_branch_id(&self.current_branch)?;
+            convert_result(
+                self.executor
+                    .primitives()
+                    .space
+                    .register(branch_id, &self.current_space),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// `key` under the database's key normalization.
+    pub(super) fn normalized_key<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        self.executor.primitives().db.key_normalization().apply(key)
+    }
+
+    /// The database and the namespace of the current branch and space.
+    pub(super) fn direct_namespace(&self) -> Result<(Arc<Database>, Namespace)> {
+        let branch_id = to_core_branch_id(&self.current_branch)?;
+        Ok((
+            self.executor.primitives().db.clone(),
+            Namespace::for_branch_space(branch_id, &self.current_space),
+        ))
+    }
+}
+
+/// Write `reader` as chunks of blob `id`, returning (chunks, bytes).
+///
+/// On failure, returns the number of chunks already written so the
+/// caller can discard them.
+fn write_chunks(
+    db: &Database,
+    ns: &Namespace,
+    id: &str,
+    reader: &mut impl Read,
+) -> std::result::Result<(u64, u64), (u64, Error)> {
+    let mut chunks = 0u64;
+    let mut len = 0u64;
+    loop {
+        let mut buf = Vec::with_capacity(BLOB_CHUNK_SIZE);
+        let read = reader
+            .by_ref()
+            .take(BLOB_CHUNK_SIZE as u64)
+            .read_to_end(&mut buf)
+            .map_err(|e| {
+                (
+                    chunks,
+                    Error::Io {
+                        reason: format!("Failed to read blob input: {}", e),
+                    },
+                )
+            })?;
+        if read == 0 {
+            return Ok((chunks, len));
+        }
+        let key = chunk_key(ns, id, chunks);
+        db.transaction(ns.branch_id, |txn| txn.put(key, Value::Bytes(buf)))
+            .map_err(|e| (chunks, e.into()))?;
+        chunks += 1;
+        len += read as u64;
+        if read < BLOB_CHUNK_SIZE {
+            return Ok((chunks, len));
+        }
+    }
+}
+
+/// Best-effort removal of chunks no manifest points to.
+fn discard_chunks(db: &Database, ns: &Namespace, id: &str, chunks: u64) {
+    if chunks == 0 {
+        return;
+    }
+    let _ = db.transaction(ns.branch_id, |txn| {
+        for index in 0..chunks {
+            txn.delete(chunk_key(ns, id, index))?;
+        }
+        Ok(())
+    });
+}
+
+/// Streaming reader over a stored blob, returned by [`Strata::blob_get`].
+///
+/// Holds at most one chunk in memory at a time.
+pub struct BlobReader {
+    db: Arc<Database>,
+    ns: Namespace,
+    manifest: Manifest,
+    next_chunk: u64,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl BlobReader {
+    /// Total size of the blob in bytes.
+    pub fn len(&self) -> u64 {
+        self.manifest.len
+    }
+
+    /// Whether the blob is empty.
+    pub fn is_empty(&self) -> bool {
+        self.manifest.len == 0
+    }
+
+    fn fetch_next_chunk(&mut self) -> std::io::Result<()> {
+        let key = chunk_key(&self.ns, &self.manifest.id, self.next_chunk);
+        let value = self
+            .db
+            .transaction(self.ns.branch_id, |txn| txn.get(&key))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        match value {
+            Some(Value::Bytes(bytes)) => {
+                self.buf = bytes;
+                self.pos = 0;
+                self.next_chunk += 1;
+                Ok(())
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "blob was replaced or deleted while reading",
+            )),
+        }
+    }
+}
+
+impl Read for BlobReader {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos == self.buf.len() {
+            if self.next_chunk == self.manifest.chunks {
+                return Ok(0);
+            }
+            self.fetch_next_chunk()?;
+        }
+        let n = out.len().min(self.buf.len() - self.pos);
+        out[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
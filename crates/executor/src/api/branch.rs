@@ -93,11 +93,11 @@ impl Strata {
     /// - All branch-scoped data (KV, Events, States, JSON, Vectors)
     ///
     /// USE WITH CAUTION - this is irreversible!
-    pub fn branch_delete(&self, name: &str) -> Result<()> {
+    pub fn branch_delete(&self, name: &str) -> Result<DeleteBranchInfo> {
         match self.executor.execute(Command::BranchDelete {
             branch: BranchId::from(name),
         })? {
-            Output::Unit => Ok(()),
+            Output::BranchDeleted(info) => Ok(info),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for BranchDelete".into(),
             }),
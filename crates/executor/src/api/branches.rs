@@ -29,9 +29,13 @@
 //! db.branches().merge("experiment-2", "main", MergeStrategy::LastWriterWins)?;
 //! ```
 
-use crate::types::BranchId;
-use crate::{Command, Error, Executor, Output, Result};
-use strata_engine::branch_ops::{BranchDiffResult, ForkInfo, MergeInfo, MergeStrategy};
+use crate::types::{BranchId, DeleteBranchInfo};
+use crate::{AccessMode, Command, Error, Executor, Output, Result};
+use strata_engine::admin_log::AdminAction;
+use strata_engine::branch_ops::{
+    BranchDiffResult, BranchSize, ConflictEntry, ForkInfo, MergeInfo, MergeStrategy, Resolution,
+};
+use strata_engine::BranchHandle;
 
 /// Handle for branch management operations.
 ///
@@ -70,6 +74,32 @@ impl<'a> Branches<'a> {
         }
     }
 
+    /// Handles to every branch, for maintenance tools that walk all data.
+    ///
+    /// Yields `(name, handle)` pairs in the order returned by [`list`](Self::list).
+    /// Each handle reads its branch through
+    /// [`BranchHandle::entries`], which sees one consistent snapshot of that
+    /// branch. The iteration as a whole is not a global snapshot: branches are
+    /// read at different moments, so writes made during the walk may show up
+    /// in some branches and not in others.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// for (name, handle) in db.branches().handles()? {
+    ///     println!("{}: {} entries", name, handle.entries().len());
+    /// }
+    /// ```
+    pub fn handles(&self) -> Result<impl Iterator<Item = (String, BranchHandle)>> {
+        let db = self.executor.primitives().db.clone();
+        let mut handles = Vec::new();
+        for name in self.list()? {
+            let id = crate::bridge::to_core_branch_id(&BranchId::from(name.as_str()))?;
+            handles.push((name, BranchHandle::new(db.clone(), id)));
+        }
+        Ok(handles.into_iter())
+    }
+
     /// Check if a branch exists.
     pub fn exists(&self, name: &str) -> Result<bool> {
         match self.executor.execute(Command::BranchExists {
@@ -82,6 +112,17 @@ impl<'a> Branches<'a> {
         }
     }
 
+    /// Fail with [`Error::BranchNotFound`] unless `name` exists.
+    fn require(&self, name: &str) -> Result<()> {
+        if self.exists(name)? {
+            Ok(())
+        } else {
+            Err(Error::BranchNotFound {
+                branch: name.to_string(),
+            })
+        }
+    }
+
     /// Create a new empty branch.
     ///
     /// The branch starts with no data. Use `fork()` to create a branch
@@ -106,11 +147,18 @@ impl<'a> Branches<'a> {
     ///
     /// **WARNING**: This is irreversible! All data in the branch will be deleted.
     ///
+    /// The branch's storage, commit lock and vector files are reclaimed; the
+    /// returned [`DeleteBranchInfo`] reports how much was freed. While an
+    /// open transaction or pinned snapshot (an export, a `scan_all`
+    /// iterator) can still read the branch, its versions stay in memory
+    /// and `reclaim_pending` is set; they are freed when it is released.
+    ///
     /// # Errors
     ///
     /// - Returns an error if trying to delete the "default" branch
-    /// - Returns an error if the branch doesn't exist
-    pub fn delete(&self, name: &str) -> Result<()> {
+    /// - Returns [`Error::BranchNotFound`] if the branch doesn't exist
+    /// - Returns an error if a copy-on-write fork still reads from the branch
+    pub fn delete(&self, name: &str) -> Result<DeleteBranchInfo> {
         if name == "default" {
             return Err(Error::ConstraintViolation {
                 reason: "Cannot delete the default branch".into(),
@@ -120,7 +168,7 @@ impl<'a> Branches<'a> {
         match self.executor.execute(Command::BranchDelete {
             branch: BranchId::from(name),
         })? {
-            Output::Unit => Ok(()),
+            Output::BranchDeleted(info) => Ok(info),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for BranchDelete".into(),
             }),
@@ -139,7 +187,7 @@ impl<'a> Branches<'a> {
     ///
     /// # Errors
     ///
-    /// - Source branch does not exist
+    /// - [`Error::BranchNotFound`] if the source branch does not exist
     /// - Destination branch already exists
     ///
     /// # Example
@@ -148,12 +196,56 @@ impl<'a> Branches<'a> {
     /// db.branches().fork("main", "experiment")?;
     /// ```
     pub fn fork(&self, source: &str, destination: &str) -> Result<ForkInfo> {
+        self.executor.check_open()?;
+        self.require(source)?;
         let db = &self.executor.primitives().db;
-        strata_engine::branch_ops::fork_branch(db, source, destination).map_err(|e| {
-            Error::Internal {
-                reason: e.to_string(),
-            }
-        })
+        let info =
+            strata_engine::branch_ops::fork_branch(db, source, destination).map_err(|e| {
+                Error::Internal {
+                    reason: e.to_string(),
+                }
+            })?;
+        self.executor.record_admin(
+            AdminAction::BranchFork,
+            Some(destination),
+            Some(&format!("from {}", source)),
+        );
+        Ok(info)
+    }
+
+    /// Fork a branch without copying its data.
+    ///
+    /// The new branch reads through to `source` as of the fork point and
+    /// only stores keys it writes itself, so forking is cheap regardless of
+    /// branch size. Later writes to `source` are not visible in the fork.
+    /// `ForkInfo::keys_copied` is always 0.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::BranchNotFound`] if the source branch does not exist
+    /// - Destination branch already exists
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.branches().fork_cow("main", "experiment")?;
+    /// ```
+    pub fn fork_cow(&self, source: &str, destination: &str) -> Result<ForkInfo> {
+        self.executor.check_open()?;
+        self.require(source)?;
+        let db = &self.executor.primitives().db;
+        let info =
+            strata_engine::branch_ops::fork_branch_cow(db, source, destination).map_err(|e| {
+                Error::Internal {
+                    reason: e.to_string(),
+                }
+            })?;
+        self.executor.record_admin(
+            AdminAction::BranchFork,
+            Some(destination),
+            Some(&format!("from {} (copy-on-write)", source)),
+        );
+        Ok(info)
     }
 
     /// Compare two branches and return their differences.
@@ -169,7 +261,14 @@ impl<'a> Branches<'a> {
     /// println!("Removed: {}", diff.summary.total_removed);
     /// println!("Modified: {}", diff.summary.total_modified);
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BranchNotFound`] if either branch does not exist.
     pub fn diff(&self, branch_a: &str, branch_b: &str) -> Result<BranchDiffResult> {
+        self.executor.check_open()?;
+        self.require(branch_a)?;
+        self.require(branch_b)?;
         let db = &self.executor.primitives().db;
         strata_engine::branch_ops::diff_branches(db, branch_a, branch_b).map_err(|e| {
             Error::Internal {
@@ -178,6 +277,132 @@ impl<'a> Branches<'a> {
         })
     }
 
+    /// Compare a forked branch with the branch it was forked from.
+    ///
+    /// The parent is the base (`branch_a`), so `added` and `modified` show
+    /// what was written in the fork. The parent is read as it is now, so
+    /// writes made to it after the fork also appear as differences.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::BranchNotFound`] if the branch does not exist
+    /// - [`Error::InvalidInput`] if the branch was not forked (such as
+    ///   `default`) or its parent has been deleted
+    pub fn diff_from_parent(&self, name: &str) -> Result<BranchDiffResult> {
+        self.executor.check_open()?;
+        self.require(name)?;
+        let db = &self.executor.primitives().db;
+        strata_engine::branch_ops::diff_from_parent(db, name).map_err(Error::from)
+    }
+
+    /// Estimate how much storage a branch uses.
+    ///
+    /// Reports the branch's key count, the approximate in-memory bytes of
+    /// its keys and retained versions, and the on-disk bytes of its vector
+    /// files. WAL and snapshot space is shared and not attributed. The
+    /// figures are estimates, suitable for quota checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BranchNotFound`] if the branch does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let size = db.branches().size("tenant-42")?;
+    /// if size.memory_bytes + size.disk_bytes > QUOTA {
+    ///     // reject the write
+    /// }
+    /// ```
+    pub fn size(&self, name: &str) -> Result<BranchSize> {
+        self.executor.check_open()?;
+        self.require(name)?;
+        let db = &self.executor.primitives().db;
+        strata_engine::branch_ops::branch_size(db, name).map_err(Error::from)
+    }
+
+    /// Compute a deterministic fingerprint of a branch's committed data.
+    ///
+    /// Returns a SHA-256 hash over the latest value, version and key of
+    /// every entry in the branch, sorted by key, so the result does not
+    /// depend on write or iteration order. Two databases that applied the
+    /// same operations produce the same hash, which makes it useful for
+    /// checking backups, replicas and deterministic replays.
+    ///
+    /// Wall-clock timestamps, event hash chains, derived event indexes,
+    /// branch and space metadata, and embeddings held only in the vector
+    /// index are excluded. See [`strata_engine::branch_ops::content_hash`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BranchNotFound`] if the branch does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let before = db.branches().content_hash("main")?;
+    /// let restored = Strata::open(backup_dir)?;
+    /// assert_eq!(restored.branches().content_hash("main")?, before);
+    /// ```
+    pub fn content_hash(&self, name: &str) -> Result<[u8; 32]> {
+        self.executor.check_open()?;
+        self.require(name)?;
+        let db = &self.executor.primitives().db;
+        strata_engine::branch_ops::content_hash(db, name).map_err(Error::from)
+    }
+
+    /// Mark a branch ephemeral, or make it durable again.
+    ///
+    /// Commits to an ephemeral branch skip the WAL, so scratch work costs no
+    /// disk I/O. The price is crash safety: the branch's writes are lost on
+    /// restart or crash unless a checkpoint captured them. Merge an
+    /// ephemeral branch into a durable one to keep its results. Making a
+    /// branch durable again only protects later commits.
+    ///
+    /// The setting is recorded by name in `strata.toml` and is kept when the
+    /// branch is deleted, so a recreated branch of the same name is
+    /// ephemeral too. It has no effect on cache databases.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::AccessDenied`] on a read-only handle
+    /// - [`Error::BranchNotFound`] if the branch does not exist
+    /// - Returns an error for the `"default"` branch
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.branches().fork("main", "scratch")?;
+    /// db.branches().set_ephemeral("scratch", true)?;
+    /// ```
+    pub fn set_ephemeral(&self, name: &str, ephemeral: bool) -> Result<()> {
+        self.executor.check_open()?;
+        if self.executor.access_mode() == AccessMode::ReadOnly {
+            return Err(Error::AccessDenied {
+                command: "BranchSetEphemeral".to_string(),
+            });
+        }
+        self.require(name)?;
+        let db = &self.executor.primitives().db;
+        db.set_branch_ephemeral(name, ephemeral)
+            .map_err(Error::from)?;
+        self.executor.record_admin(
+            AdminAction::ConfigChange,
+            Some("ephemeral_branches"),
+            Some(&format!("{} = {}", name, ephemeral)),
+        );
+        Ok(())
+    }
+
+    /// Check whether commits to a branch skip the WAL.
+    ///
+    /// See [`set_ephemeral`](Self::set_ephemeral).
+    pub fn is_ephemeral(&self, name: &str) -> Result<bool> {
+        self.executor.check_open()?;
+        let id = crate::bridge::to_core_branch_id(&BranchId::from(name))?;
+        Ok(self.executor.primitives().db.is_branch_ephemeral(id))
+    }
+
     /// Merge data from source branch into target branch.
     ///
     /// Applies changes from `source` into `target`:
@@ -196,7 +421,15 @@ impl<'a> Branches<'a> {
     /// let info = db.branches().merge("feature", "main", MergeStrategy::LastWriterWins)?;
     /// println!("Applied {} keys", info.keys_applied);
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::BranchNotFound`] if either branch does not exist
+    /// - `Strict` strategy and the branches have conflicting entries
     pub fn merge(&self, source: &str, target: &str, strategy: MergeStrategy) -> Result<MergeInfo> {
+        self.executor.check_open()?;
+        self.require(source)?;
+        self.require(target)?;
         let db = &self.executor.primitives().db;
         strata_engine::branch_ops::merge_branches(db, source, target, strategy).map_err(|e| {
             Error::Internal {
@@ -204,4 +437,46 @@ impl<'a> Branches<'a> {
             }
         })
     }
+
+    /// Merge source into target, resolving each conflict with `resolve`.
+    ///
+    /// Added entries are written as with [`merge`](Self::merge). Every entry
+    /// modified on both branches is passed to `resolve`, which returns the
+    /// [`Resolution`] to apply: the source value, the target value, or a
+    /// custom value. Custom values are accepted for KV entries only. All
+    /// conflicts are resolved before anything is written, so a rejected
+    /// resolution leaves the target unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// use strata_engine::Resolution;
+    ///
+    /// let info = db.branches().merge_with("feature", "main", |conflict| {
+    ///     if conflict.key.starts_with("config:") {
+    ///         Resolution::TakeTarget
+    ///     } else {
+    ///         Resolution::TakeSource
+    ///     }
+    /// })?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::BranchNotFound`] if either branch does not exist
+    /// - `resolve` returned a custom value for a non-KV entry
+    pub fn merge_with<F>(&self, source: &str, target: &str, resolve: F) -> Result<MergeInfo>
+    where
+        F: FnMut(ConflictEntry) -> Resolution,
+    {
+        self.executor.check_open()?;
+        self.require(source)?;
+        self.require(target)?;
+        let db = &self.executor.primitives().db;
+        strata_engine::branch_ops::merge_branches_with(db, source, target, resolve).map_err(|e| {
+            Error::Internal {
+                reason: e.to_string(),
+            }
+        })
+    }
 }
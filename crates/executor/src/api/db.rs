@@ -1,9 +1,11 @@
 //! Database operations: ping, info, flush, compact, configuration.
 
 use super::Strata;
-use crate::output::EmbedStatusInfo;
+use crate::output::{CompactInfo, EmbedStatusInfo, IndexStats};
 use crate::types::*;
-use crate::{Command, Error, Output, Result};
+use crate::{AccessMode, ChainVerification, Command, Error, Output, Result};
+use std::collections::HashMap;
+use strata_engine::admin_log::{AdminAction, AdminEntry};
 use strata_engine::{ModelConfig, StrataConfig};
 
 impl Strata {
@@ -31,7 +33,39 @@ impl Strata {
         }
     }
 
+    /// Report the features, limits and format versions of this build.
+    ///
+    /// Reads configuration only; it does not touch storage or allocate.
+    pub fn capabilities(&self) -> Capabilities {
+        let limits = &self.executor.primitives().limits;
+        Capabilities {
+            version: env!("CARGO_PKG_VERSION"),
+            wire_format_version: WIRE_FORMAT_VERSION,
+            wal_format_version: strata_engine::SEGMENT_FORMAT_VERSION,
+            snapshot_format_version: strata_engine::SNAPSHOT_FORMAT_VERSION,
+            bundle_format_version: strata_engine::BRANCHBUNDLE_FORMAT_VERSION,
+            limits: CapabilityLimits {
+                max_key_bytes: limits.max_key_bytes,
+                max_string_bytes: limits.max_string_bytes,
+                max_bytes_len: limits.max_bytes_len,
+                max_value_bytes: limits.max_value_bytes_encoded,
+                max_nesting_depth: limits.max_nesting_depth,
+                // Collections cap the dimension separately from write limits
+                max_vector_dim: limits
+                    .max_vector_dim
+                    .min(strata_engine::vector::MAX_DIMENSION),
+            },
+            distance_metrics: SUPPORTED_DISTANCE_METRICS,
+            embedding: cfg!(feature = "embed"),
+        }
+    }
+
     /// Flush the database to disk.
+    ///
+    /// Also drains the embedding pipeline: pending auto-embed texts are
+    /// embedded and every queued background task runs to completion before
+    /// the WAL is flushed. Texts deferred because the background queue was
+    /// full (see [`embed_status`](Self::embed_status)) are embedded here too.
     pub fn flush(&self) -> Result<()> {
         match self.executor.execute(Command::Flush)? {
             Output::Unit => Ok(()),
@@ -41,25 +75,330 @@ impl Strata {
         }
     }
 
+    /// Block until all committed data is durable on disk.
+    ///
+    /// Forces an fsync of the write-ahead log regardless of durability mode,
+    /// establishing a durability barrier even on a `"standard"` database
+    /// whose background flush has not run yet.
+    ///
+    /// Differs from [`flush`](Self::flush): `flush` also drains pending
+    /// embeddings and background work and is a no-op on cache databases,
+    /// while `sync` only makes committed writes durable and returns an error
+    /// on cache databases, which can never be durable.
+    pub fn sync(&self) -> Result<()> {
+        self.executor.check_open()?;
+        self.executor.primitives().db.sync().map_err(Error::from)
+    }
+
+    /// Liveness probe that exercises the write path.
+    ///
+    /// Commits a small value to a reserved key in a reserved branch, reads
+    /// it back and, on disk-backed databases, fsyncs the WAL. The branch
+    /// never appears in branch listings and cannot be created by users, and
+    /// only the latest probe is kept, so user data is untouched. Returns an
+    /// error if the database is closed or the write, read or sync fails.
+    ///
+    /// The report is `Degraded` when the database works but something is
+    /// impaired: auto-embedding is enabled but its model cannot be used, or
+    /// background recovery is still running. During recovery and on
+    /// read-only handles no write is attempted, `write_checked` is false and
+    /// `latency_us` is 0.
+    pub fn health_check(&self) -> Result<HealthReport> {
+        self.executor.check_open()?;
+        let db = &self.executor.primitives().db;
+        let mut issues = Vec::new();
+
+        let probe = if db.is_recovering() {
+            issues.push("background recovery in progress; write path not probed".to_string());
+            None
+        } else if self.access_mode == AccessMode::ReadOnly {
+            None
+        } else {
+            Some(db.probe_write_path().map_err(Error::from)?)
+        };
+
+        let config = db.config();
+        if config.auto_embed {
+            if let Some(reason) = super::embed_model_problem(db) {
+                issues.push(reason);
+            }
+        }
+
+        Ok(HealthReport {
+            status: if issues.is_empty() {
+                HealthStatus::Healthy
+            } else {
+                HealthStatus::Degraded
+            },
+            latency_us: probe.map_or(0, |p| p.latency.as_micros() as u64),
+            write_checked: probe.is_some(),
+            wal_synced: probe.is_some_and(|p| p.wal_synced),
+            durability: if db.is_cache() {
+                "cache".to_string()
+            } else {
+                config.durability
+            },
+            issues,
+        })
+    }
+
+    /// Save the keyword search index to disk now.
+    ///
+    /// The index is otherwise saved only at shutdown, so after a crash the
+    /// next open rebuilds it from all stored data. A saved index is reused
+    /// on open as long as no write has committed since it was saved; call
+    /// this periodically to keep restarts after a crash fast.
+    ///
+    /// A no-op on cache databases and when keyword indexing is off.
+    pub fn checkpoint_search_index(&self) -> Result<()> {
+        self.executor.check_open()?;
+        self.executor
+            .primitives()
+            .db
+            .checkpoint_search_index()
+            .map_err(Error::from)?;
+        self.executor
+            .record_admin(AdminAction::Checkpoint, Some("search_index"), None);
+        Ok(())
+    }
+
+    /// Save every vector collection's embeddings and HNSW graphs to disk now.
+    ///
+    /// They are otherwise saved only at shutdown, so after a crash the next
+    /// open rebuilds each collection's graphs. Saved graphs are checked
+    /// against the stored vectors on open and reused while they still
+    /// match; call this periodically for large collections.
+    ///
+    /// Returns the number of files written; 0 on cache databases.
+    pub fn checkpoint_vectors(&self) -> Result<usize> {
+        self.executor.check_open()?;
+        let files = self
+            .executor
+            .primitives()
+            .db
+            .checkpoint_vectors()
+            .map_err(Error::from)?;
+        self.executor.record_admin(
+            AdminAction::Checkpoint,
+            Some("vectors"),
+            Some(&format!("{} files written", files)),
+        );
+        Ok(files)
+    }
+
     /// Compact the database.
-    pub fn compact(&self) -> Result<()> {
+    ///
+    /// Removes closed WAL segments already covered by the latest checkpoint
+    /// and reports how many were removed and how many bytes were freed.
+    /// Fails if no checkpoint exists yet. On cache databases, nothing is
+    /// removed.
+    pub fn compact(&self) -> Result<CompactInfo> {
         match self.executor.execute(Command::Compact)? {
-            Output::Unit => Ok(()),
+            Output::Compacted(info) => Ok(info),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for Compact".into(),
             }),
         }
     }
 
+    /// Whether [`compact`](Self::compact) would remove anything right now.
+    ///
+    /// True when at least one closed WAL segment is covered by the latest
+    /// checkpoint. False before the first checkpoint and on cache databases.
+    pub fn compaction_needed(&self) -> bool {
+        self.executor
+            .primitives()
+            .db
+            .compaction_needed()
+            .unwrap_or(false)
+    }
+
+    /// Prune in-memory version history on the current branch, keeping the
+    /// newest `keep_last` versions of each key.
+    ///
+    /// Covers every primitive's entries in the branch, across all spaces.
+    /// The latest version of a key is always kept, so `keep_last` of 0
+    /// behaves like 1. Versions still visible to an open transaction or a
+    /// pinned snapshot are kept until it ends; see
+    /// [`active_snapshots`](Self::active_snapshots).
+    ///
+    /// Pruned versions disappear from `*_getv` history and time-travel reads
+    /// (`as_of`) before the oldest kept version find nothing. Pruning only
+    /// touches memory: the WAL still holds the pruned versions, so they come
+    /// back when the database is reopened unless a checkpoint and
+    /// [`compact`](Self::compact) have dropped those WAL records first.
+    /// Returns the number of versions pruned.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// for i in 0..10i64 {
+    ///     db.kv_put("counter", i)?;
+    /// }
+    /// assert_eq!(db.gc_versions(3)?, 7);
+    /// assert_eq!(db.kv_getv("counter")?.unwrap().len(), 3);
+    /// ```
+    pub fn gc_versions(&self, keep_last: usize) -> Result<usize> {
+        self.executor.check_open()?;
+        if self.access_mode == AccessMode::ReadOnly {
+            return Err(Error::AccessDenied {
+                command: "GcVersions".to_string(),
+            });
+        }
+        let branch_id = crate::bridge::to_core_branch_id(&self.current_branch)?;
+        let pruned = self
+            .executor
+            .primitives()
+            .db
+            .gc_versions_keep_last(branch_id, keep_last);
+        self.executor.record_admin(
+            AdminAction::GcVersions,
+            Some(self.current_branch.as_str()),
+            Some(&format!(
+                "{} versions pruned, keeping the last {}",
+                pruned, keep_last
+            )),
+        );
+        Ok(pruned)
+    }
+
+    /// Snapshots held by open transactions and pinned in storage, oldest
+    /// first.
+    ///
+    /// Use it when memory keeps growing: a transaction that was begun and
+    /// never committed or rolled back keeps its snapshot, and so does an
+    /// unfinished [`scan_all`](Self::scan_all) or vector scan. The oldest
+    /// entry is usually the culprit. Only reads transaction and pin
+    /// bookkeeping; branch names are looked up only when a transaction is
+    /// listed. In a cache database with `cache_max_bytes` or
+    /// `cache_max_entries` set, every transaction also pins its version, so
+    /// it is listed twice.
+    pub fn active_snapshots(&self) -> Result<Vec<SnapshotDebugInfo>> {
+        self.executor.check_open()?;
+        let primitives = self.executor.primitives();
+        let transactions = primitives.db.active_snapshots();
+        let mut names = HashMap::new();
+        if !transactions.is_empty() {
+            for name in primitives.branch.list_branches().map_err(Error::from)? {
+                let id = crate::bridge::to_core_branch_id(&BranchId::from(name.as_str()))?;
+                names.insert(id, name);
+            }
+        }
+
+        let mut snapshots: Vec<SnapshotDebugInfo> = transactions
+            .into_iter()
+            .map(|s| SnapshotDebugInfo {
+                holder: SnapshotHolder::Transaction {
+                    txn_id: s.txn_id,
+                    branch: names
+                        .get(&s.branch_id)
+                        .cloned()
+                        .unwrap_or_else(|| s.branch_id.to_string()),
+                },
+                version: s.version,
+                age_ms: s.age.as_millis() as u64,
+            })
+            .collect();
+        snapshots.extend(
+            primitives
+                .db
+                .pinned_snapshots()
+                .into_iter()
+                .map(|p| SnapshotDebugInfo {
+                    holder: SnapshotHolder::Pinned {
+                        count: p.count as u64,
+                    },
+                    version: p.version,
+                    age_ms: p.age.as_millis() as u64,
+                }),
+        );
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.age_ms));
+        Ok(snapshots)
+    }
+
+    /// Rebuild the keyword index from all stored KV, State and Event data.
+    ///
+    /// Discards the current inverted index, re-indexes every searchable
+    /// entry on every branch, and freezes the result to disk. Use it when
+    /// keyword search results look out of step with stored data. Keyword
+    /// searches that run during the rebuild may see partial results.
+    ///
+    /// Fails if the database was opened with `keyword_index(false)`.
+    pub fn rebuild_keyword_index(&self) -> Result<IndexStats> {
+        match self.executor.execute(Command::RebuildKeywordIndex)? {
+            Output::KeywordIndexRebuilt(stats) => Ok(stats),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for RebuildKeywordIndex".into(),
+            }),
+        }
+    }
+
+    /// Read the `limit` most recent administrative actions, newest first.
+    ///
+    /// Branch creation, forking and deletion, checkpoints, compaction,
+    /// snapshot restores and runtime configuration changes made through the
+    /// API or commands are recorded with what was done, to what, when, and
+    /// by which actor (see `OpenOptions::actor`). The log is kept in a
+    /// reserved branch, separate from user event logs, and is as durable as
+    /// the database itself. Restores roll the log back to the restored
+    /// snapshot like all other data; the restore itself is recorded on the
+    /// next read-write open.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// for entry in db.admin_log(20)? {
+    ///     println!("{} {:?} {:?} by {:?}", entry.timestamp, entry.action, entry.target, entry.actor);
+    /// }
+    /// ```
+    pub fn admin_log(&self, limit: usize) -> Result<Vec<AdminEntry>> {
+        self.executor.check_open()?;
+        strata_engine::admin_log::entries(&self.executor.primitives().db, limit)
+            .map_err(Error::from)
+    }
+
+    /// Verify the hash chain of the admin log.
+    ///
+    /// Each entry is re-hashed and checked against the entry before it, so
+    /// an edited, removed or reordered entry breaks the chain at that point.
+    pub fn verify_admin_log(&self) -> Result<ChainVerification> {
+        self.executor.check_open()?;
+        strata_engine::admin_log::verify(&self.executor.primitives().db).map_err(Error::from)
+    }
+
     // =========================================================================
-    // Bundle Operations (3)
+    // Bundle Operations (4)
     // =========================================================================
 
     /// Export a branch to a .branchbundle.tar.zst archive.
     pub fn branch_export(&self, branch_id: &str, path: &str) -> Result<BranchExportResult> {
+        self.export_bundle(branch_id, path, false)
+    }
+
+    /// Export a branch with a SHA-256 of each bundle section and an overall
+    /// digest recorded in the manifest.
+    ///
+    /// Import and validation recompute the hashes and reject a bundle whose
+    /// contents or recorded hashes were changed. The digest alone does not
+    /// prove who wrote the bundle; see `branch_export_signed`.
+    pub fn branch_export_with_digest(
+        &self,
+        branch_id: &str,
+        path: &str,
+    ) -> Result<BranchExportResult> {
+        self.export_bundle(branch_id, path, true)
+    }
+
+    fn export_bundle(
+        &self,
+        branch_id: &str,
+        path: &str,
+        digest: bool,
+    ) -> Result<BranchExportResult> {
         match self.executor.execute(Command::BranchExport {
             branch_id: branch_id.to_string(),
             path: path.to_string(),
+            digest,
         })? {
             Output::BranchExported(result) => Ok(result),
             _ => Err(Error::Internal {
@@ -80,6 +419,69 @@ impl Strata {
         }
     }
 
+    /// Export a branch with a digest signed by `key` (Ed25519).
+    ///
+    /// Key generation, storage and distribution are up to the caller; the
+    /// matching public key is embedded in the manifest only so a reader can
+    /// check it against the one it trusts.
+    #[cfg(feature = "bundle-signing")]
+    pub fn branch_export_signed(
+        &self,
+        branch_id: &str,
+        path: &str,
+        key: &crate::SigningKey,
+    ) -> Result<BranchExportResult> {
+        self.executor.check_open()?;
+        let options = strata_engine::bundle::ExportOptions {
+            signing_key: Some(key.clone()),
+            ..Default::default()
+        };
+        let info = strata_engine::bundle::export_branch_with_options(
+            &self.executor.primitives().db,
+            branch_id,
+            std::path::Path::new(path),
+            &options,
+        )
+        .map_err(|e| Error::Io {
+            reason: format!("Export failed: {}", e),
+        })?;
+        Ok(BranchExportResult {
+            branch_id: info.branch_id,
+            path: info.path.to_string_lossy().to_string(),
+            entry_count: info.entry_count,
+            bundle_size: info.bundle_size,
+        })
+    }
+
+    /// Import a branch only if its bundle is signed by `trusted_key` and
+    /// its contents match the signed digest.
+    #[cfg(feature = "bundle-signing")]
+    pub fn branch_import_signed(
+        &self,
+        path: &str,
+        trusted_key: &crate::VerifyingKey,
+    ) -> Result<BranchImportResult> {
+        self.executor.check_open()?;
+        if self.access_mode == AccessMode::ReadOnly {
+            return Err(Error::AccessDenied {
+                command: "BranchImport".to_string(),
+            });
+        }
+        let info = strata_engine::bundle::import_signed_branch(
+            &self.executor.primitives().db,
+            std::path::Path::new(path),
+            trusted_key,
+        )
+        .map_err(|e| Error::Io {
+            reason: format!("Import failed: {}", e),
+        })?;
+        Ok(BranchImportResult {
+            branch_id: info.branch_id,
+            transactions_applied: info.transactions_applied,
+            keys_written: info.keys_written,
+        })
+    }
+
     /// Validate a .branchbundle.tar.zst archive without importing.
     pub fn branch_validate_bundle(&self, path: &str) -> Result<BundleValidateResult> {
         match self.executor.execute(Command::BranchBundleValidate {
@@ -119,6 +521,7 @@ impl Strata {
         api_key: Option<&str>,
         timeout_ms: Option<u64>,
     ) -> Result<()> {
+        self.executor.check_open()?;
         self.executor
             .primitives()
             .db
@@ -130,10 +533,23 @@ impl Strata {
                     timeout_ms: timeout_ms.unwrap_or(5000),
                 });
             })
-            .map_err(Error::from)
+            .map_err(Error::from)?;
+        self.executor.record_admin(
+            AdminAction::ConfigChange,
+            Some("model"),
+            Some(&format!("{} at {}", model, endpoint)),
+        );
+        Ok(())
     }
 
     /// Get a snapshot of the embedding pipeline status.
+    ///
+    /// `scheduler_tasks_rejected` counts submissions refused because the
+    /// background queue was full. Writes never block on a full queue; the
+    /// affected texts stay pending until the next flush. Once
+    /// `MAX_PENDING_BATCHES` batches are pending and the queue still rejects
+    /// a flush, writes carrying text fail with [`Error::Backpressure`]
+    /// without being applied; [`flush`](Self::flush) drains the buffer.
     pub fn embed_status(&self) -> Result<EmbedStatusInfo> {
         match self.executor.execute(Command::EmbedStatus)? {
             Output::EmbedStatus(info) => Ok(info),
@@ -143,6 +559,31 @@ impl Strata {
         }
     }
 
+    /// Adjust the background WAL flush interval without reopening.
+    ///
+    /// Only applies to `"standard"` durability, where committed writes are
+    /// fsynced in batches every `interval_ms`. Raising the interval increases
+    /// write throughput under load but means up to `interval_ms` of
+    /// acknowledged commits can be lost on a crash; lowering it does the
+    /// reverse. The setting is not persisted to `strata.toml`.
+    ///
+    /// Returns an error for an interval of zero, for `"always"` durability,
+    /// and for cache databases.
+    pub fn set_flush_interval(&self, interval_ms: u64) -> Result<()> {
+        self.executor.check_open()?;
+        self.executor
+            .primitives()
+            .db
+            .set_flush_interval(interval_ms)
+            .map_err(Error::from)?;
+        self.executor.record_admin(
+            AdminAction::ConfigChange,
+            Some("flush_interval_ms"),
+            Some(&interval_ms.to_string()),
+        );
+        Ok(())
+    }
+
     /// Check whether auto-embedding is enabled.
     pub fn auto_embed_enabled(&self) -> bool {
         self.executor.primitives().db.auto_embed_enabled()
@@ -152,12 +593,44 @@ impl Strata {
     ///
     /// Persisted to `strata.toml` for disk-backed databases.
     pub fn set_auto_embed(&self, enabled: bool) -> Result<()> {
+        self.executor.check_open()?;
         self.executor
             .primitives()
             .db
             .update_config(|cfg| {
                 cfg.auto_embed = enabled;
             })
-            .map_err(Error::from)
+            .map_err(Error::from)?;
+        self.executor.record_admin(
+            AdminAction::ConfigChange,
+            Some("auto_embed"),
+            Some(&enabled.to_string()),
+        );
+        Ok(())
+    }
+
+    /// Load the embedding model and run one throwaway embedding.
+    ///
+    /// The first embedding otherwise pays for reading the weights and, on
+    /// GPU backends, compiling compute pipelines. Servers can call this at
+    /// startup so the first real write or text query is fast.
+    ///
+    /// After warm-up the model stays resident for the life of the database:
+    /// roughly the size of `model.safetensors` (about 90 MB for the default
+    /// MiniLM-L6-v2). On GPU backends the transformer layers move to device
+    /// memory and only the embedding tables (about 47 MB for MiniLM) stay on
+    /// the host.
+    ///
+    /// A no-op when auto-embedding is disabled or the `embed` feature is not
+    /// compiled in. Returns `ModelUnavailable` if the model cannot be loaded.
+    pub fn warm_up(&self) -> Result<()> {
+        if !self.auto_embed_enabled() {
+            return Ok(());
+        }
+        #[cfg(feature = "embed")]
+        {
+            self.embed_text("warm up")?;
+        }
+        Ok(())
     }
 }
@@ -1,10 +1,10 @@
 //! Event log operations (4 MVP).
 //!
-//! MVP: append, read, get_by_type, len
+//! MVP: append, read, get_by_type, len, plus hash chain verification
 
 use super::Strata;
 use crate::types::*;
-use crate::{Command, Error, Output, Result, Value};
+use crate::{ChainVerification, Command, Error, Event, Output, Result, Value};
 
 impl Strata {
     // =========================================================================
@@ -12,14 +12,30 @@ impl Strata {
     // =========================================================================
 
     /// Append an event to the log.
-    pub fn event_append(&self, event_type: &str, payload: Value) -> Result<u64> {
+    ///
+    /// Returns the event as stored, including its assigned sequence number,
+    /// timestamp and chain hashes, so it can be referenced (for example as
+    /// the parent of a later event) without reading it back.
+    pub fn event_append(&self, event_type: &str, payload: Value) -> Result<Event> {
         match self.executor.execute(Command::EventAppend {
             branch: self.branch_id(),
             space: self.space_id(),
             event_type: event_type.to_string(),
-            payload,
+            payload: payload.clone(),
         })? {
-            Output::Version(v) => Ok(v),
+            Output::EventAppended {
+                sequence,
+                timestamp,
+                prev_hash,
+                hash,
+            } => Ok(Event {
+                sequence,
+                event_type: event_type.to_string(),
+                payload,
+                timestamp,
+                prev_hash,
+                hash,
+            }),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for EventAppend".into(),
             }),
@@ -70,4 +86,21 @@ impl Strata {
             }),
         }
     }
+
+    /// Verify the hash chain of the event log in the current branch and space.
+    ///
+    /// Every event is re-hashed and checked against the event before it, so
+    /// any edit to a stored event breaks the chain at that sequence. The
+    /// whole walk reads one snapshot.
+    pub fn verify_event_chain(&self) -> Result<ChainVerification> {
+        match self.executor.execute(Command::EventVerifyChain {
+            branch: self.branch_id(),
+            space: self.space_id(),
+        })? {
+            Output::ChainVerification(verification) => Ok(verification),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventVerifyChain".into(),
+            }),
+        }
+    }
 }
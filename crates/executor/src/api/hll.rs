@@ -0,0 +1,134 @@
+//! Distinct-count estimates with HyperLogLog sketches.
+//!
+//! A sketch is a fixed array of 4096 one-byte registers stored
+//! as `Value::Bytes` in the KV namespace of the current branch and space,
+//! under the reserved `_strata/hll/` prefix. Adding an item hashes it, picks
+//! a register from the top bits of the hash and raises that register to the
+//! position of the first set bit in the rest. The sketch never stores the
+//! items themselves, so it stays 4 KiB however many are added.
+//!
+//! Adds do not read the sketch. Each transaction buffers its registers and
+//! the commit path merges them into the committed sketch by taking the
+//! element-wise max, under the branch commit lock. Like counters, concurrent
+//! adds never conflict or retry, and adding the same item twice is a no-op.
+//!
+//! With 2^12 registers the standard error of the estimate is
+//! `1.04 / sqrt(4096)`, about 1.6%, so counts are typically within 2% of
+//! the true number of distinct items.
+
+use strata_core::types::{Key, Namespace};
+
+use super::Strata;
+use crate::bridge::validate_key_with_limits;
+use crate::convert::convert_result;
+use crate::{Error, Result, Value};
+
+const HLL_PREFIX: &str = "_strata/hll/";
+
+/// Number of index bits taken from the top of an item's hash
+const HLL_PRECISION: u32 = 12;
+
+/// Registers per sketch, and its size in bytes
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+fn hll_key(ns: &Namespace, key: &str) -> Key {
+    Key::new_kv(ns.clone(), format!("{}{}", HLL_PREFIX, key))
+}
+
+/// Stable 64-bit hash of an item: FNV-1a followed by the MurmurHash3
+/// finalizer, which spreads FNV's weak high bits across the whole word.
+fn hash_item(item: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in item {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+/// Register index and rank for an item
+fn register_for(item: &[u8]) -> (usize, u8) {
+    let hash = hash_item(item);
+    let index = (hash >> (64 - HLL_PRECISION)) as usize;
+    let rest = hash << HLL_PRECISION;
+    let rank = rest.leading_zeros().min(64 - HLL_PRECISION) + 1;
+    (index, rank as u8)
+}
+
+/// Cardinality estimate from a full register array
+fn estimate(registers: &[u8]) -> u64 {
+    let m = registers.len() as f64;
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum: f64 = registers.iter().map(|r| 2f64.powi(-i32::from(*r))).sum();
+    let raw = alpha * m * m / sum;
+    let zeros = registers.iter().filter(|r| **r == 0).count();
+    // Small-range correction: linear counting is more accurate while many
+    // registers are still empty.
+    let estimate = if raw <= 2.5 * m && zeros > 0 {
+        m * (m / zeros as f64).ln()
+    } else {
+        raw
+    };
+    estimate.round() as u64
+}
+
+impl Strata {
+    // =========================================================================
+    // HyperLogLog Operations
+    // =========================================================================
+
+    /// Add `item` to the distinct-count sketch `key`.
+    ///
+    /// The sketch is created on first add. Items are compared by their bytes,
+    /// so `"a"` and `b"a"` count as the same item. Adds merge into the
+    /// committed sketch at commit time, so concurrent adds to one sketch
+    /// never conflict. Sketch keys are separate from KV keys: `kv_get(key)`
+    /// does not see a sketch.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// for tool in ["search", "fetch", "search"] {
+    ///     db.hll_add("tools_used", tool)?;
+    /// }
+    /// assert_eq!(db.hll_count("tools_used")?, 2);
+    /// ```
+    pub fn hll_add(&self, key: &str, item: impl AsRef<[u8]>) -> Result<()> {
+        self.check_direct_write("HllAdd")?;
+        let key = &*self.normalized_key(key);
+        let limits = &self.executor.primitives().limits;
+        convert_result(validate_key_with_limits(key, limits))?;
+        let (index, rank) = register_for(item.as_ref());
+        let mut registers = vec![0u8; HLL_REGISTERS];
+        registers[index] = rank;
+        let (db, ns) = self.direct_namespace()?;
+        db.transaction(ns.branch_id, |txn| {
+            txn.merge_max(hll_key(&ns, key), registers.clone())
+        })?;
+        Ok(())
+    }
+
+    /// Estimated number of distinct items added to the sketch `key`, or 0
+    /// if nothing was ever added.
+    ///
+    /// The estimate has a standard error of about 1.6%: expect it within
+    /// roughly 2% of the exact count. Small counts are close to exact.
+    pub fn hll_count(&self, key: &str) -> Result<u64> {
+        self.executor.check_open()?;
+        let (db, ns) = self.direct_namespace()?;
+        let sketch = hll_key(&ns, &self.normalized_key(key));
+        match db.transaction(ns.branch_id, |txn| txn.get(&sketch))? {
+            Some(Value::Bytes(registers)) if registers.len() == HLL_REGISTERS => {
+                Ok(estimate(&registers))
+            }
+            Some(_) => Err(Error::Internal {
+                reason: "Corrupt HyperLogLog sketch".into(),
+            }),
+            None => Ok(0),
+        }
+    }
+}
@@ -23,6 +23,7 @@
 //! ```
 
 use super::Strata;
+use crate::types::JsonDocInfo;
 use crate::{Command, Error, Output, Result, Value};
 
 impl Strata {
@@ -195,6 +196,7 @@ impl Strata {
             cursor,
             limit,
             as_of: None,
+            include_metadata: false,
         })? {
             Output::JsonListResult { keys, cursor } => Ok((keys, cursor)),
             _ => Err(Error::Internal {
@@ -202,4 +204,32 @@ impl Strata {
             }),
         }
     }
+
+    /// List JSON documents with their version and stored size.
+    ///
+    /// Pages the same way as [`json_list`](Self::json_list), but each entry
+    /// also carries the document's version and size in bytes, so a UI can
+    /// show them without fetching every document. Each listed document is
+    /// decoded during the scan, so this costs more than listing keys.
+    pub fn json_list_with_metadata(
+        &self,
+        prefix: Option<String>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<(Vec<JsonDocInfo>, Option<String>)> {
+        match self.executor.execute(Command::JsonList {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            prefix,
+            cursor,
+            limit,
+            as_of: None,
+            include_metadata: true,
+        })? {
+            Output::JsonListDetailed { docs, cursor } => Ok((docs, cursor)),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonList".into(),
+            }),
+        }
+    }
 }
@@ -0,0 +1,255 @@
+//! Primitive-agnostic key lookups.
+//!
+//! For tooling that is handed a key without knowing which primitive wrote
+//! it. KV entries, state cells and JSON documents share a name space per
+//! branch and space but live under different type tags, so the same name
+//! may exist in several of them at once.
+//!
+//! [`Strata::scan_all`] goes the other way and lists every key of the
+//! current branch and space, whatever primitive holds it.
+
+use strata_core::types::{Key, Namespace, TypeTag};
+use strata_engine::ReadOnlySnapshot;
+
+use super::Strata;
+use crate::bridge::{extract_version, is_internal_collection, is_reserved_key, to_core_branch_id};
+use crate::types::{KeyInfo, KeyLocation, VersionedValue};
+use crate::{Command, Error, Output, PrimitiveType, Result};
+
+/// Primitives whose keys are plain names, in the order `key_info` reports them.
+const NAMED_PRIMITIVES: [PrimitiveType; 3] =
+    [PrimitiveType::Kv, PrimitiveType::State, PrimitiveType::Json];
+
+/// Primitives `scan_all` walks, with their type tag, in the order it reports them.
+const SCANNED_PRIMITIVES: [(PrimitiveType, TypeTag); 5] = [
+    (PrimitiveType::Kv, TypeTag::KV),
+    (PrimitiveType::Event, TypeTag::Event),
+    (PrimitiveType::State, TypeTag::State),
+    (PrimitiveType::Json, TypeTag::Json),
+    (PrimitiveType::Vector, TypeTag::Vector),
+];
+
+impl Strata {
+    /// Check whether `key` exists under one primitive.
+    ///
+    /// How `key` is read depends on the primitive:
+    /// - `Kv`, `State`, `Json`: the key, cell or document name
+    /// - `Event`: the sequence number, as a decimal string
+    /// - `Vector`: `collection/key`; a missing collection reads as absent
+    /// - `Branch`: the branch name (same as [`branch_exists`](Self::branch_exists))
+    ///
+    /// Reads the current branch and space. Returns `InvalidInput` if `key`
+    /// is not in the form the primitive expects.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_put("user:1", "Alice")?;
+    /// assert!(db.exists(PrimitiveType::Kv, "user:1")?);
+    /// assert!(!db.exists(PrimitiveType::Json, "user:1")?);
+    /// ```
+    pub fn exists(&self, primitive: PrimitiveType, key: &str) -> Result<bool> {
+        match primitive {
+            PrimitiveType::Kv | PrimitiveType::State | PrimitiveType::Json => {
+                Ok(self.named_version(primitive, key)?.is_some())
+            }
+            PrimitiveType::Event => {
+                let sequence = key.parse::<u64>().map_err(|_| Error::InvalidInput {
+                    reason: format!("Event key must be a sequence number, got '{}'", key),
+                })?;
+                Ok(self.event_get(sequence)?.is_some())
+            }
+            PrimitiveType::Vector => {
+                let (collection, vector_key) =
+                    key.split_once('/').ok_or_else(|| Error::InvalidInput {
+                        reason: format!("Vector key must be 'collection/key', got '{}'", key),
+                    })?;
+                match self.vector_get(collection, vector_key) {
+                    Ok(data) => Ok(data.is_some()),
+                    Err(Error::CollectionNotFound { .. }) => Ok(false),
+                    Err(e) => Err(e),
+                }
+            }
+            PrimitiveType::Branch => self.branch_exists(key),
+        }
+    }
+
+    /// Report which primitives hold `key`, with the latest version in each.
+    ///
+    /// Checks the KV store, state cells and JSON documents of the current
+    /// branch and space, and returns `None` if the key is in none of them.
+    /// Versions are those each primitive reports from its own reads: the
+    /// commit version for KV, the cell counter for state and the document
+    /// version for JSON. Keys never expire, so no TTL is reported.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_put("config", 1i64)?;
+    /// db.state_set("config", "ready")?;
+    /// let info = db.key_info("config")?.unwrap();
+    /// assert_eq!(info.primitives(), vec![PrimitiveType::Kv, PrimitiveType::State]);
+    /// ```
+    pub fn key_info(&self, key: &str) -> Result<Option<KeyInfo>> {
+        let mut locations = Vec::new();
+        for primitive in NAMED_PRIMITIVES {
+            if let Some(vv) = self.named_version(primitive, key)? {
+                locations.push(KeyLocation {
+                    primitive,
+                    version: vv.version,
+                    timestamp: vv.timestamp,
+                });
+            }
+        }
+        if locations.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(KeyInfo {
+            key: key.to_string(),
+            locations,
+        }))
+    }
+
+    /// Walk every key in the current branch and space, across primitives.
+    ///
+    /// Yields `(primitive, key, version)` grouped by primitive (KV, events,
+    /// state cells, JSON documents, vectors) and sorted by key within each
+    /// group. Keys are rendered the way [`exists`](Self::exists) reads them:
+    /// events as their sequence number and vectors as `collection/key`.
+    /// Reserved KV keys and internal vector collections are skipped. The
+    /// version is the commit version of the key's latest write.
+    ///
+    /// The whole walk reads one pinned snapshot taken when `scan_all` is
+    /// called, so it is a consistent cut even while other writers commit.
+    /// The snapshot holds back version garbage collection until the
+    /// iterator is dropped. An error ends the iteration.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_put("config", 1i64)?;
+    /// db.state_set("config", "ready")?;
+    /// for entry in db.scan_all() {
+    ///     let (primitive, key, version) = entry?;
+    ///     println!("{} {} @{}", primitive, key, version);
+    /// }
+    /// ```
+    pub fn scan_all(&self) -> impl Iterator<Item = Result<(PrimitiveType, String, u64)>> {
+        let start = self.executor.check_open().and_then(|()| {
+            let branch_id = to_core_branch_id(&self.current_branch)?;
+            Ok((
+                self.executor.primitives().db.pinned_snapshot(),
+                Namespace::for_branch_space(branch_id, &self.current_space),
+            ))
+        });
+        KeyScan {
+            start: Some(start),
+            snapshot: None,
+            primitives: SCANNED_PRIMITIVES.iter(),
+            batch: Vec::new().into_iter(),
+        }
+    }
+
+    /// Latest version of a named KV entry, state cell or JSON document.
+    fn named_version(&self, primitive: PrimitiveType, key: &str) -> Result<Option<VersionedValue>> {
+        let command = match primitive {
+            PrimitiveType::Kv => Command::KvGet {
+                branch: self.branch_id(),
+                space: self.space_id(),
+                key: key.to_string(),
+                as_of: None,
+            },
+            PrimitiveType::State => Command::StateGet {
+                branch: self.branch_id(),
+                space: self.space_id(),
+                cell: key.to_string(),
+                as_of: None,
+            },
+            PrimitiveType::Json => Command::JsonGet {
+                branch: self.branch_id(),
+                space: self.space_id(),
+                key: key.to_string(),
+                path: "$".to_string(),
+                as_of: None,
+            },
+            other => {
+                return Err(Error::Internal {
+                    reason: format!("{} keys are not plain names", other),
+                })
+            }
+        };
+        match self.executor.execute(command)? {
+            Output::MaybeVersioned(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: format!("Unexpected output for {} get", primitive),
+            }),
+        }
+    }
+}
+
+/// Iterator behind [`Strata::scan_all`], scanning one primitive at a time.
+struct KeyScan {
+    start: Option<Result<(ReadOnlySnapshot, Namespace)>>,
+    snapshot: Option<(ReadOnlySnapshot, Namespace)>,
+    primitives: std::slice::Iter<'static, (PrimitiveType, TypeTag)>,
+    batch: std::vec::IntoIter<(PrimitiveType, String, u64)>,
+}
+
+impl Iterator for KeyScan {
+    type Item = Result<(PrimitiveType, String, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.batch.next() {
+                return Some(Ok(entry));
+            }
+            match self.start.take() {
+                Some(Ok(start)) => self.snapshot = Some(start),
+                Some(Err(e)) => return Some(Err(e)),
+                None => {}
+            }
+            let (snapshot, ns) = self.snapshot.as_ref()?;
+            let Some(&(primitive, type_tag)) = self.primitives.next() else {
+                // Release the pinned version as soon as the walk is done
+                self.snapshot = None;
+                return None;
+            };
+            match scan_primitive(snapshot, ns, primitive, type_tag) {
+                Ok(entries) => self.batch = entries.into_iter(),
+                Err(e) => {
+                    self.snapshot = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// All user-visible keys of one primitive in `ns`, sorted by key.
+fn scan_primitive(
+    snapshot: &ReadOnlySnapshot,
+    ns: &Namespace,
+    primitive: PrimitiveType,
+    type_tag: TypeTag,
+) -> Result<Vec<(PrimitiveType, String, u64)>> {
+    let entries = snapshot.scan_prefix(&Key::new(ns.clone(), type_tag, Vec::new()))?;
+    Ok(entries
+        .into_iter()
+        .filter(|(key, _)| !key.is_internal())
+        .filter_map(|(key, vv)| {
+            let name = match primitive {
+                PrimitiveType::Event => {
+                    let sequence: [u8; 8] = key.user_key.as_slice().try_into().ok()?;
+                    u64::from_be_bytes(sequence).to_string()
+                }
+                _ => String::from_utf8_lossy(&key.user_key).into_owned(),
+            };
+            let hidden = match primitive {
+                PrimitiveType::Kv => is_reserved_key(&name),
+                PrimitiveType::Vector => is_internal_collection(&name),
+                _ => false,
+            };
+            (!hidden).then(|| (primitive, name, extract_version(&vv.version)))
+        })
+        .collect())
+}
@@ -42,6 +42,33 @@ impl Strata {
         }
     }
 
+    /// Put a value parsed from loosely typed JSON, coercing scalar strings.
+    ///
+    /// For ingesting external data where numbers and booleans arrive as
+    /// strings. `json` is parsed, then strings anywhere in it that are
+    /// exactly a JSON number or `"true"`/`"false"` become `Int`, `Float` or
+    /// `Bool` values; everything else, including `"007"`, `"+42"` and
+    /// integers beyond `i64`, stays a string. The stored value is an
+    /// ordinary `Value`: reads, comparisons and `kv_put` never coerce.
+    ///
+    /// Returns `InvalidInput` if `json` does not parse.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_put_coerced("order", r#"{"qty": "3", "paid": "true", "zip": "02134"}"#)?;
+    /// // stored as {"qty": 3, "paid": true, "zip": "02134"}
+    /// ```
+    pub fn kv_put_coerced(&self, key: &str, json: &str) -> Result<u64> {
+        let parsed: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| Error::InvalidInput {
+                reason: format!("Invalid JSON: {}", e),
+            })?;
+        let value = crate::json::coerce_json_to_value(&parsed)
+            .map_err(|reason| Error::InvalidInput { reason })?;
+        self.kv_put(key, value)
+    }
+
     /// Get a value from the KV store.
     ///
     /// Returns the latest value for the key, or None if it doesn't exist.
@@ -62,6 +89,49 @@ impl Strata {
         }
     }
 
+    /// Put a value under a binary key.
+    ///
+    /// For keys that are not valid UTF-8, such as hashes. The bytes are
+    /// stored as-is and may include NUL. Entries written this way are not
+    /// indexed for search and are skipped by [`kv_list`](Self::kv_list).
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let digest: [u8; 32] = sha256(b"payload");
+    /// db.kv_put_bytes(&digest, "payload")?;
+    /// ```
+    pub fn kv_put_bytes(&self, key: &[u8], value: impl Into<Value>) -> Result<u64> {
+        match self.executor.execute(Command::KvPutBytes {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            key: key.to_vec(),
+            value: value.into(),
+        })? {
+            Output::Version(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvPutBytes".into(),
+            }),
+        }
+    }
+
+    /// Get a value by binary key.
+    ///
+    /// Returns the latest value written with [`kv_put_bytes`](Self::kv_put_bytes),
+    /// or None if the key doesn't exist.
+    pub fn kv_get_bytes(&self, key: &[u8]) -> Result<Option<Value>> {
+        match self.executor.execute(Command::KvGetBytes {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            key: key.to_vec(),
+        })? {
+            Output::Maybe(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvGetBytes".into(),
+            }),
+        }
+    }
+
     /// Delete a key from the KV store.
     ///
     /// Returns `true` if the key existed and was deleted, `false` if it didn't exist.
@@ -80,6 +150,141 @@ impl Strata {
         }
     }
 
+    /// Delete a key only if it currently holds `expected`.
+    ///
+    /// Returns `true` if the key was deleted, `false` if it was missing or
+    /// held a different value. The comparison and the delete happen in one
+    /// transaction, so a value a concurrent writer just stored is never
+    /// deleted.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// // Release a lock only if we still own it
+    /// db.kv_delete_if_equals("lock", "worker-1")?;
+    /// ```
+    pub fn kv_delete_if_equals(&self, key: &str, expected: impl Into<Value>) -> Result<bool> {
+        match self.executor.execute(Command::KvDeleteIfEquals {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            key: key.to_string(),
+            expected: expected.into(),
+        })? {
+            Output::Bool(deleted) => Ok(deleted),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvDeleteIfEquals".into(),
+            }),
+        }
+    }
+
+    /// Get a value, inserting `default` if the key doesn't exist.
+    ///
+    /// The read and the conditional write happen in one transaction, so
+    /// concurrent callers all get the same value back: whichever default was
+    /// inserted first. Returns the existing or newly inserted value.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let settings = db.kv_get_or_put("settings", Value::Object(Default::default()))?;
+    /// ```
+    pub fn kv_get_or_put(&self, key: &str, default: impl Into<Value>) -> Result<Value> {
+        match self.executor.execute(Command::KvGetOrPut {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            key: key.to_string(),
+            default: default.into(),
+        })? {
+            Output::Maybe(Some(v)) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvGetOrPut".into(),
+            }),
+        }
+    }
+
+    /// Like [`kv_get_or_put`](Self::kv_get_or_put), but only builds the
+    /// default when the key is missing.
+    ///
+    /// `f` is not called if the key already exists. If it does run, another
+    /// writer may still insert first, in which case that value is returned
+    /// and `f`'s result is discarded.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let profile = db.kv_get_or_put_with("profile:42", || load_profile(42))?;
+    /// ```
+    pub fn kv_get_or_put_with<V, F>(&self, key: &str, f: F) -> Result<Value>
+    where
+        V: Into<Value>,
+        F: FnOnce() -> V,
+    {
+        if let Some(existing) = self.kv_get(key)? {
+            return Ok(existing);
+        }
+        self.kv_get_or_put(key, f())
+    }
+
+    /// Put multiple key-value pairs in one transaction.
+    ///
+    /// Either every entry is written or none is: an invalid key or value
+    /// fails the whole call. Returns one version per entry, in order.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_put_many(vec![
+    ///     BatchKvEntry { key: "a".into(), value: Value::Int(1) },
+    ///     BatchKvEntry { key: "b".into(), value: Value::Int(2) },
+    /// ])?;
+    /// ```
+    pub fn kv_put_many(&self, entries: Vec<crate::types::BatchKvEntry>) -> Result<Vec<u64>> {
+        match self.executor.execute(Command::KvPutMany {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            entries,
+        })? {
+            Output::Versions(versions) => Ok(versions),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvPutMany".into(),
+            }),
+        }
+    }
+
+    /// Get multiple keys from one consistent snapshot.
+    ///
+    /// Returns one entry per key, in order, or `None` for missing keys.
+    pub fn kv_get_many(&self, keys: &[&str]) -> Result<Vec<Option<Value>>> {
+        match self.executor.execute(Command::KvGetMany {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+        })? {
+            Output::MaybeVersionedValues(values) => {
+                Ok(values.into_iter().map(|v| v.map(|vv| vv.value)).collect())
+            }
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvGetMany".into(),
+            }),
+        }
+    }
+
+    /// Delete multiple keys in one transaction.
+    ///
+    /// Returns one flag per key, in order, telling whether it existed.
+    pub fn kv_delete_many(&self, keys: &[&str]) -> Result<Vec<bool>> {
+        match self.executor.execute(Command::KvDeleteMany {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+        })? {
+            Output::Bools(deleted) => Ok(deleted),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvDeleteMany".into(),
+            }),
+        }
+    }
+
     /// Get the full version history for a key.
     ///
     /// Returns all versions of the key, newest first, or None if the key
@@ -109,20 +314,91 @@ impl Strata {
         }
     }
 
+    /// Get the values of a key at two versions, for single-key audit.
+    ///
+    /// Each side is the value as of that version: the newest write at or
+    /// before it, read from the key's version chain (see
+    /// [`kv_getv`](Self::kv_getv)). A deletion reads as `Value::Null`, as in
+    /// `kv_getv`. Returns `None` if the key had not been written by
+    /// `from_version` or `to_version`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let v1 = db.kv_put("cfg", 1i64)?;
+    /// let v2 = db.kv_put("cfg", 2i64)?;
+    /// let (before, after) = db.kv_diff_versions("cfg", v1, v2)?.unwrap();
+    /// assert_eq!((before, after), (Value::Int(1), Value::Int(2)));
+    /// ```
+    pub fn kv_diff_versions(
+        &self,
+        key: &str,
+        from_version: u64,
+        to_version: u64,
+    ) -> Result<Option<(Value, Value)>> {
+        if from_version > to_version {
+            return Err(Error::InvalidInput {
+                reason: format!(
+                    "from_version ({}) must not exceed to_version ({})",
+                    from_version, to_version
+                ),
+            });
+        }
+        let Some(history) = self.kv_getv(key)? else {
+            return Ok(None);
+        };
+        // History is newest first
+        let at = |version: u64| {
+            history
+                .iter()
+                .find(|entry| entry.version <= version)
+                .map(|entry| entry.value.clone())
+        };
+        Ok(at(from_version).zip(at(to_version)))
+    }
+
+    /// Describe how a key changed between two versions as an RFC 6902 patch.
+    ///
+    /// Resolves both sides like [`kv_diff_versions`](Self::kv_diff_versions)
+    /// and diffs their canonical JSON forms. Objects and arrays are diffed
+    /// structurally; other changes are a single `replace` at the root path
+    /// `""`. An unchanged value gives an empty patch.
+    pub fn kv_diff_versions_patch(
+        &self,
+        key: &str,
+        from_version: u64,
+        to_version: u64,
+    ) -> Result<Option<Vec<crate::types::JsonPatchOp>>> {
+        Ok(self
+            .kv_diff_versions(key, from_version, to_version)?
+            .map(|(before, after)| {
+                crate::json::diff(
+                    &crate::json::value_to_json(&before),
+                    &crate::json::value_to_json(&after),
+                )
+            }))
+    }
+
     /// List keys with optional prefix filter.
     ///
-    /// Returns all keys matching the prefix (or all keys if prefix is None).
+    /// Returns all keys matching the prefix (or all keys if prefix is None),
+    /// cut to `default_result_limit` or `max_result_limit` when either is
+    /// configured.
     ///
     /// Lists from the current branch context.
     pub fn kv_list(&self, prefix: Option<&str>) -> Result<Vec<String>> {
-        match self.executor.execute(Command::KvList {
-            branch: self.branch_id(),
-            space: self.space_id(),
-            prefix: prefix.map(|s| s.to_string()),
-            cursor: None,
-            limit: None,
-            as_of: None,
-        })? {
+        match self
+            .executor
+            .execute(Command::KvList {
+                branch: self.branch_id(),
+                space: self.space_id(),
+                prefix: prefix.map(|s| s.to_string()),
+                cursor: None,
+                limit: None,
+                as_of: None,
+            })?
+            .untruncated()
+        {
             Output::Keys(keys) => Ok(keys),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for KvList".into(),
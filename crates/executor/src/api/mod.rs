@@ -36,30 +36,47 @@
 //! assert_eq!(db.kv_get("key")?, Some(Value::String("hello".into())));
 //! ```
 
+mod blob;
 mod branch;
 mod branches;
+mod counter;
 mod db;
 mod event;
+mod hll;
 mod json;
+mod keys;
 mod kv;
+mod read;
+mod search;
+#[cfg(feature = "testing")]
+mod self_test;
 mod state;
+mod trace;
 mod vector;
+#[cfg(feature = "testing")]
+mod verify;
 
+pub use blob::{BlobReader, BLOB_CHUNK_SIZE};
 pub use branches::Branches;
+pub use read::ReadView;
+pub use state::DEFAULT_STATE_UPDATE_RETRIES;
 pub use strata_engine::branch_ops::{
-    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff,
+    BranchDiffEntry, BranchDiffResult, BranchSize, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
+    MergeStrategy, Resolution, SpaceDiff,
 };
+pub use trace::{Trace, TraceTree, TraceType, TRACE_EVENT_TYPE};
+#[cfg(feature = "testing")]
+pub use verify::{encode_model_value, MODEL_EVENT_TYPE};
 
 use std::path::Path;
 use std::sync::Arc;
 
-use strata_engine::{Database, ModelConfig};
-use strata_security::{AccessMode, OpenOptions};
+use strata_engine::{CacheLimits, Database, JsonLimitsConfig, ModelConfig};
+use strata_security::{AccessMode, MissingModelPolicy, OpenOptions};
 
 use std::sync::Once;
 
-use crate::types::BranchId;
+use crate::types::{BranchId, DeleteBranchInfo};
 use crate::{Command, Error, Executor, Output, Result, Session};
 
 /// Ensure recovery participants are registered before opening any database.
@@ -72,6 +89,137 @@ fn ensure_vector_recovery() {
     });
 }
 
+/// Why auto-embedding cannot run with the configured model, if it cannot.
+fn embed_model_problem(db: &Database) -> Option<String> {
+    if !db.model_available() {
+        return Some(format!(
+            "auto_embed is enabled but the embedding model is missing from {}; \
+             run `strata setup` to download it",
+            db.model_dir().display()
+        ));
+    }
+    #[cfg(feature = "embed")]
+    {
+        use strata_intelligence::embed::manifest::ModelManifest;
+        match ModelManifest::from_dir(&db.model_dir()) {
+            Ok(manifest) if manifest.hidden_size != db.embed_dimension() => {
+                return Some(format!(
+                    "embedding model in {} produces {}-dimensional vectors but \
+                     embed_model.dimension is {}",
+                    db.model_dir().display(),
+                    manifest.hidden_size,
+                    db.embed_dimension()
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => return Some(e),
+        }
+    }
+    None
+}
+
+/// `base` with the `json_max_*` options of `opts` applied, or `None` when
+/// none is set.
+fn json_limits_override(
+    opts: &OpenOptions,
+    base: Option<JsonLimitsConfig>,
+) -> Option<JsonLimitsConfig> {
+    let overrides = [
+        opts.json_max_document_size,
+        opts.json_max_nesting_depth,
+        opts.json_max_path_length,
+        opts.json_max_array_size,
+    ];
+    if overrides.iter().all(Option::is_none) {
+        return None;
+    }
+    let mut limits = base.unwrap_or_default();
+    limits.max_document_size = opts.json_max_document_size.or(limits.max_document_size);
+    limits.max_nesting_depth = opts.json_max_nesting_depth.or(limits.max_nesting_depth);
+    limits.max_path_length = opts.json_max_path_length.or(limits.max_path_length);
+    limits.max_array_size = opts.json_max_array_size.or(limits.max_array_size);
+    Some(limits)
+}
+
+/// Reject options that have no effect on a cache database.
+///
+/// This is the one place that decides what [`Strata::cache_with`] supports.
+/// Every field is named, so a new option must be sorted into one group or
+/// the other before the crate builds.
+fn check_cache_options(opts: &OpenOptions) -> Result<()> {
+    let OpenOptions {
+        // Applied to cache databases
+        cache_max_bytes: _,
+        cache_max_entries: _,
+        keyword_index: _,
+        dedup_threshold: _,
+        tombstone_ttl: _,
+        max_active_transactions: _,
+        default_result_limit: _,
+        max_result_limit: _,
+        json_max_document_size: _,
+        json_max_nesting_depth: _,
+        json_max_path_length: _,
+        json_max_array_size: _,
+        key_normalization: _,
+        clock: _,
+        actor: _,
+        // Disk, WAL, model and access settings that a cache has no use for
+        access_mode,
+        auto_embed,
+        durability,
+        model_endpoint,
+        model_name,
+        model_api_key,
+        model_timeout_ms,
+        embed_batch_size,
+        commit_lock_shards,
+        background_threads,
+        background_queue_depth,
+        checkpoint_on_shutdown,
+        strict_recovery,
+        keep_snapshots,
+        max_record_bytes,
+        vector_storage,
+        ephemeral_branches,
+        missing_model,
+    } = opts;
+    let rejected = [
+        ("access_mode", *access_mode != AccessMode::default()),
+        ("auto_embed", auto_embed.is_some()),
+        ("durability", durability.is_some()),
+        ("model_endpoint", model_endpoint.is_some()),
+        ("model_name", model_name.is_some()),
+        ("model_api_key", model_api_key.is_some()),
+        ("model_timeout_ms", model_timeout_ms.is_some()),
+        ("embed_batch_size", embed_batch_size.is_some()),
+        ("commit_lock_shards", commit_lock_shards.is_some()),
+        ("background_threads", background_threads.is_some()),
+        ("background_queue_depth", background_queue_depth.is_some()),
+        ("checkpoint_on_shutdown", checkpoint_on_shutdown.is_some()),
+        ("strict_recovery", strict_recovery.is_some()),
+        ("keep_snapshots", keep_snapshots.is_some()),
+        ("max_record_bytes", max_record_bytes.is_some()),
+        ("vector_storage", vector_storage.is_some()),
+        ("ephemeral_branches", !ephemeral_branches.is_empty()),
+        (
+            "missing_model",
+            *missing_model != MissingModelPolicy::default(),
+        ),
+    ];
+    let set: Vec<&str> = rejected
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| *name)
+        .collect();
+    if set.is_empty() {
+        return Ok(());
+    }
+    Err(Error::InvalidInput {
+        reason: format!("Not supported by cache databases: {}", set.join(", ")),
+    })
+}
+
 /// High-level typed wrapper for database operations.
 ///
 /// `Strata` provides a convenient Rust API that wraps the executor's
@@ -180,16 +328,91 @@ impl Strata {
             opts.embed_batch_size
                 .unwrap_or(cfg.embed_batch_size.unwrap_or(512)),
         );
+        if let Some(shards) = opts.commit_lock_shards {
+            cfg.commit_lock_shards = Some(shards);
+        }
+        if let Some(n) = opts.background_threads {
+            cfg.background_threads = Some(n);
+        }
+        if let Some(n) = opts.background_queue_depth {
+            cfg.background_queue_depth = Some(n);
+        }
+        if let Some(enabled) = opts.checkpoint_on_shutdown {
+            cfg.checkpoint_on_shutdown = Some(enabled);
+        }
+        if let Some(enabled) = opts.strict_recovery {
+            cfg.strict_recovery = Some(enabled);
+        }
+        if let Some(n) = opts.keep_snapshots {
+            cfg.keep_snapshots = Some(n);
+        }
+        if let Some(bytes) = opts.max_record_bytes {
+            cfg.max_record_bytes = Some(bytes);
+        }
+        if let Some(enabled) = opts.keyword_index {
+            cfg.keyword_index = Some(enabled);
+        }
+        if let Some(storage) = opts.vector_storage {
+            cfg.vector_storage = Some(storage);
+        }
+        if let Some(bytes) = opts.dedup_threshold {
+            cfg.dedup_threshold = Some(bytes);
+        }
+        if let Some(ttl) = opts.tombstone_ttl {
+            cfg.tombstone_ttl_ms = Some(ttl.as_millis() as u64);
+        }
+        if let Some(n) = opts.max_active_transactions {
+            cfg.max_active_transactions = Some(n);
+        }
+        if let Some(n) = opts.default_result_limit {
+            cfg.default_result_limit = Some(n);
+        }
+        if let Some(n) = opts.max_result_limit {
+            cfg.max_result_limit = Some(n);
+        }
+        if let Some(overrides) = json_limits_override(&opts, cfg.json_limits) {
+            cfg.json_limits = Some(overrides);
+        }
+        if let Some(mode) = opts.key_normalization {
+            cfg.key_normalization = Some(mode);
+        }
+        if !opts.ephemeral_branches.is_empty() {
+            let names = cfg.ephemeral_branches.get_or_insert_with(Vec::new);
+            for name in opts.ephemeral_branches {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
 
         let db = Database::open_with_config(&data_dir, cfg).map_err(|e| Error::Internal {
             reason: format!("Failed to open database: {}", e),
         })?;
+        if let Some(clock) = opts.clock {
+            db.set_clock(clock);
+        }
+
+        if let Some(reason) = db
+            .auto_embed_enabled()
+            .then(|| embed_model_problem(&db))
+            .flatten()
+        {
+            match opts.missing_model {
+                MissingModelPolicy::Fail => return Err(Error::ModelUnavailable { reason }),
+                MissingModelPolicy::Disable => {
+                    tracing::warn!(target: "strata::embed", "{}; auto-embedding is disabled", reason);
+                    db.suspend_auto_embed();
+                }
+            }
+        }
 
         let access_mode = opts.access_mode;
-        let executor = Executor::new_with_mode(db, access_mode);
+        let executor = Executor::new_with_mode(db, access_mode).with_actor(opts.actor);
 
         match access_mode {
-            AccessMode::ReadWrite => Self::ensure_default_branch(&executor)?,
+            AccessMode::ReadWrite => {
+                Self::ensure_default_branch(&executor)?;
+            }
             AccessMode::ReadOnly => Self::verify_default_branch(&executor)?,
         }
 
@@ -212,11 +435,70 @@ impl Strata {
     /// db.kv_put("key", Value::Int(42))?;
     /// ```
     pub fn cache() -> Result<Self> {
+        Self::cache_with(OpenOptions::new())
+    }
+
+    /// Create an ephemeral in-memory database with memory or entry budgets.
+    ///
+    /// Only `cache_max_bytes`, `cache_max_entries`, `keyword_index`,
+    /// `dedup_threshold`, `tombstone_ttl`, `max_active_transactions`,
+    /// `default_result_limit`, `max_result_limit`, the `json_max_*` limits,
+    /// `key_normalization`, `clock` and `actor` apply. Once a budget is
+    /// exceeded, least-recently-used KV keys are evicted and disappear as if
+    /// never written. Other primitives are never evicted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` naming every other option that is set, such
+    /// as `durability` or `access_mode(ReadOnly)`, since a cache database
+    /// cannot honour them.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let db = Strata::cache_with(OpenOptions::new().cache_max_entries(10_000))?;
+    /// ```
+    pub fn cache_with(opts: OpenOptions) -> Result<Self> {
+        check_cache_options(&opts)?;
         ensure_vector_recovery();
-        let db = Database::cache().map_err(|e| Error::Internal {
+        let json_limits = json_limits_override(&opts, None);
+        let mut limits = CacheLimits::new();
+        limits.max_bytes = opts.cache_max_bytes;
+        limits.max_entries = opts.cache_max_entries;
+        let db = Database::cache_with_limits(limits).map_err(|e| Error::Internal {
             reason: format!("Failed to open cache database: {}", e),
         })?;
-        let executor = Executor::new(db);
+        if let Some(clock) = opts.clock {
+            db.set_clock(clock);
+        }
+        if opts.keyword_index == Some(false) {
+            db.disable_keyword_index().map_err(Error::from)?;
+        }
+        if opts.dedup_threshold.is_some() {
+            db.set_dedup_threshold(opts.dedup_threshold);
+        }
+        if opts.tombstone_ttl.is_some() {
+            db.set_tombstone_ttl(opts.tombstone_ttl);
+        }
+        if opts.max_active_transactions.is_some() {
+            db.set_max_active_transactions(opts.max_active_transactions);
+        }
+        if let Some(mode) = opts.key_normalization {
+            db.update_config(|cfg| cfg.key_normalization = Some(mode))
+                .map_err(Error::from)?;
+        }
+        if let Some(overrides) = json_limits {
+            db.update_config(|cfg| cfg.json_limits = Some(overrides))
+                .map_err(Error::from)?;
+        }
+        if opts.default_result_limit.is_some() || opts.max_result_limit.is_some() {
+            db.update_config(|cfg| {
+                cfg.default_result_limit = opts.default_result_limit;
+                cfg.max_result_limit = opts.max_result_limit;
+            })
+            .map_err(Error::from)?;
+        }
+        let executor = Executor::new(db).with_actor(opts.actor);
 
         // Ensure the default branch exists
         Self::ensure_default_branch(&executor)?;
@@ -334,6 +616,68 @@ impl Strata {
         self.executor.primitives().db.durability_counters()
     }
 
+    /// Get transaction metrics: commits, aborts, conflicts, and active count.
+    ///
+    /// Use [`TransactionMetrics::conflict_rate`](strata_engine::TransactionMetrics::conflict_rate)
+    /// to tune retry behavior. This reads a handful of atomics and is cheap.
+    pub fn transaction_metrics(&self) -> strata_engine::TransactionMetrics {
+        self.executor.primitives().db.transaction_metrics()
+    }
+
+    /// Get a point-in-time snapshot of database metrics.
+    ///
+    /// Includes transaction metrics, WAL counters, and the current version.
+    pub fn metrics_snapshot(&self) -> strata_engine::MetricsSnapshot {
+        self.executor.primitives().db.metrics_snapshot()
+    }
+
+    /// List the checkpoint snapshots on disk, oldest first.
+    ///
+    /// Each entry carries the snapshot ID, the version it covers, its
+    /// creation time and file size, and whether the MANIFEST points at it
+    /// (the snapshot recovery would start from). Read-only. Cache databases
+    /// have no snapshots.
+    pub fn list_snapshots(&self) -> Result<Vec<strata_engine::SnapshotInfo>> {
+        self.executor
+            .primitives()
+            .db
+            .list_snapshots()
+            .map_err(Error::from)
+    }
+
+    /// Describe one checkpoint snapshot, or `None` if it does not exist.
+    pub fn snapshot_info(&self, snapshot_id: u64) -> Result<Option<strata_engine::SnapshotInfo>> {
+        self.executor
+            .primitives()
+            .db
+            .snapshot_info(snapshot_id)
+            .map_err(Error::from)
+    }
+
+    /// Roll a closed database back to checkpoint `snapshot_id`.
+    ///
+    /// A disaster-recovery escape hatch: commits made after the snapshot
+    /// are removed from the WAL, and the original WAL, newer snapshots and
+    /// index caches are moved to `restored/` in the data directory rather
+    /// than deleted. Each restore is recorded in the admin log.
+    ///
+    /// Refuses to run while any handle in this process or another process
+    /// has the database open, or once `compact()` has removed the WAL the
+    /// snapshot's state is rebuilt from.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let id = Strata::open(path)?.list_snapshots()?[0].snapshot_id;
+    /// // ... drop every handle ...
+    /// Strata::restore_snapshot(path, id)?;
+    /// ```
+    pub fn restore_snapshot<P: AsRef<Path>>(path: P, snapshot_id: u64) -> Result<()> {
+        Database::restore_snapshot(path, snapshot_id)
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+
     /// Get a handle for branch management operations.
     ///
     /// The returned [`Branches`] handle provides the "power API" for branch
@@ -385,7 +729,7 @@ impl Strata {
     /// # Errors
     ///
     /// Returns an error if the branch doesn't exist. Use `create_branch()` first
-    /// to create a new branch.
+    /// to create a new branch, or `set_branch_create()` to do both at once.
     ///
     /// # Example
     ///
@@ -409,6 +753,35 @@ impl Strata {
         Ok(())
     }
 
+    /// Switch to a branch, creating it first if it doesn't exist.
+    ///
+    /// Like `git checkout -b`, but an existing branch is simply switched to.
+    /// A newly created branch starts with no data. Returns `true` if the
+    /// branch was created by this call.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let created = db.set_branch_create("experiment")?;
+    /// db.kv_put("key", "value")?;  // Data goes to experiment
+    /// ```
+    pub fn set_branch_create(&mut self, branch_name: &str) -> Result<bool> {
+        let branches = self.branches();
+        let created = if branches.exists(branch_name)? {
+            false
+        } else {
+            match branches.create(branch_name) {
+                Ok(()) => true,
+                // Lost a race with another creator; the branch is there now.
+                Err(_) if branches.exists(branch_name)? => false,
+                Err(e) => return Err(e),
+            }
+        };
+
+        self.current_branch = BranchId::from(branch_name);
+        Ok(created)
+    }
+
     /// Create a new blank branch.
     ///
     /// The new branch starts with no data. Stays on the current branch after creation.
@@ -451,6 +824,15 @@ impl Strata {
         self.branches().fork(self.current_branch(), destination)
     }
 
+    /// Fork the current branch into a new copy-on-write branch.
+    ///
+    /// Like [`fork_branch`](Self::fork_branch), but no data is copied: the
+    /// fork reads through to the current branch as of the fork point and
+    /// materializes only the keys it changes. See [`Branches::fork_cow`].
+    pub fn fork_branch_cow(&self, destination: &str) -> Result<ForkInfo> {
+        self.branches().fork_cow(self.current_branch(), destination)
+    }
+
     /// Compare two branches and return their differences.
     ///
     /// Returns a structured diff showing per-space added, removed, and
@@ -459,6 +841,40 @@ impl Strata {
         self.branches().diff(branch_a, branch_b)
     }
 
+    /// Show what changed in the current branch since it was forked.
+    ///
+    /// Diffs the current branch against its fork parent; see
+    /// [`Branches::diff_from_parent`]. Fails with [`Error::InvalidInput`]
+    /// if the current branch was not created by a fork, such as `default`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.fork_branch("experiment")?;
+    /// db.set_branch("experiment")?;
+    /// db.kv_put("lr", 0.01)?;
+    /// let diff = db.diff_from_parent()?;
+    /// assert_eq!(diff.summary.total_added, 1);
+    /// ```
+    pub fn diff_from_parent(&self) -> Result<BranchDiffResult> {
+        self.branches()
+            .diff_from_parent(self.current_branch.as_str())
+    }
+
+    /// Estimate how much storage a branch uses.
+    ///
+    /// See [`Branches::size`] for what is counted.
+    pub fn branch_size(&self, branch_name: &str) -> Result<BranchSize> {
+        self.branches().size(branch_name)
+    }
+
+    /// Compute a deterministic fingerprint of a branch's committed data.
+    ///
+    /// See [`Branches::content_hash`] for what is included.
+    pub fn content_hash(&self, branch_name: &str) -> Result<[u8; 32]> {
+        self.branches().content_hash(branch_name)
+    }
+
     /// Merge data from source branch into target branch.
     ///
     /// See [`Branches::merge`] for details on merge strategies.
@@ -471,6 +887,30 @@ impl Strata {
         self.branches().merge(source, target, strategy)
     }
 
+    /// Merge `source` into the current branch, resolving each conflict
+    /// with `resolve`.
+    ///
+    /// See [`Branches::merge_with`] for how resolutions are applied.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// use stratadb::Resolution;
+    ///
+    /// db.merge_with("experiment", |conflict| match conflict.key.as_str() {
+    ///     "best_score" => Resolution::TakeSource,
+    ///     "notes" => Resolution::Custom(Value::String("reviewed".into())),
+    ///     _ => Resolution::TakeTarget,
+    /// })?;
+    /// ```
+    pub fn merge_with<F>(&self, source: &str, resolve: F) -> Result<MergeInfo>
+    where
+        F: FnMut(ConflictEntry) -> Resolution,
+    {
+        self.branches()
+            .merge_with(source, self.current_branch.as_str(), resolve)
+    }
+
     /// List all available branches.
     ///
     /// Returns a list of branch names.
@@ -478,15 +918,19 @@ impl Strata {
         self.branches().list()
     }
 
-    /// Delete a branch and all its data.
+    /// Delete a branch and all its data, reclaiming its storage.
     ///
     /// **WARNING**: This is irreversible! All data in the branch will be deleted.
     ///
+    /// Frees the branch's in-memory versions, commit lock and vector
+    /// collection files, and reports the keys and bytes reclaimed. See
+    /// [`Branches::delete`].
+    ///
     /// # Errors
     ///
     /// - Returns an error if trying to delete the current branch
     /// - Returns an error if trying to delete the "default" branch
-    pub fn delete_branch(&self, branch_name: &str) -> Result<()> {
+    pub fn delete_branch(&self, branch_name: &str) -> Result<DeleteBranchInfo> {
         // Cannot delete the current branch
         if branch_name == self.current_branch.as_str() {
             return Err(Error::ConstraintViolation {
@@ -592,12 +1036,61 @@ impl Strata {
 mod tests {
     use super::*;
     use crate::types::*;
-    use crate::Value;
+    use crate::{
+        AdminAction, ChainVerification, CompactInfo, KeyNormalization, Value, VectorStorage,
+    };
 
     fn create_strata() -> Strata {
         Strata::cache().unwrap()
     }
 
+    #[test]
+    fn test_operations_after_shutdown_return_database_closed() {
+        let db = create_strata();
+        db.kv_put("k", 1i64).unwrap();
+        db.vector_create_collection("vecs", 2, DistanceMetric::Cosine)
+            .unwrap();
+        let mut session = db.session();
+        db.executor.primitives().db.shutdown().unwrap();
+
+        let closed = |result: Result<()>| assert_eq!(result, Err(Error::DatabaseClosed));
+        closed(db.ping().map(drop));
+        closed(db.kv_put("k", 2i64).map(drop));
+        closed(db.kv_get("k").map(drop));
+        closed(db.kv_delete("k").map(drop));
+        closed(db.kv_list(None).map(drop));
+        closed(db.kv_getv("k").map(drop));
+        closed(db.state_set("cell", 1i64).map(drop));
+        closed(db.state_get("cell").map(drop));
+        closed(db.event_append("type", Value::Null).map(drop));
+        closed(db.event_get(0).map(drop));
+        closed(db.event_len().map(drop));
+        closed(db.json_set("doc", "$", 1i64).map(drop));
+        closed(db.json_get("doc", "$").map(drop));
+        closed(
+            db.vector_upsert("vecs", "v", vec![1.0, 0.0], None)
+                .map(drop),
+        );
+        closed(db.vector_search("vecs", vec![1.0, 0.0], 1).map(drop));
+        closed(db.vector_list_collections().map(drop));
+        closed(db.list_branches().map(drop));
+        closed(db.create_branch("other"));
+        closed(db.branches().fork("default", "copy").map(drop));
+        closed(db.branches().diff("default", "other").map(drop));
+        closed(db.read(|view| view.kv_get("k")).map(drop));
+        closed(db.flush());
+        closed(db.set_auto_embed(true));
+        closed(db.new_handle().map(drop));
+        closed(
+            session
+                .execute(Command::TxnBegin {
+                    branch: None,
+                    options: None,
+                })
+                .map(drop),
+        );
+    }
+
     #[test]
     fn test_ping() {
         let db = create_strata();
@@ -612,6 +1105,84 @@ mod tests {
         assert!(!info.version.is_empty());
     }
 
+    #[test]
+    fn test_capabilities() {
+        let db = create_strata();
+        let caps = db.capabilities();
+        assert_eq!(caps.version, db.ping().unwrap());
+        assert_eq!(caps.wire_format_version, WIRE_FORMAT_VERSION);
+        assert_eq!(caps.limits.max_key_bytes, 1024);
+        assert_eq!(caps.limits.max_vector_dim, 8192);
+        assert!(caps.distance_metrics.contains(&DistanceMetric::Cosine));
+        assert_eq!(caps.embedding, cfg!(feature = "embed"));
+
+        // The reported key limit is the one enforced
+        let at_limit = "k".repeat(caps.limits.max_key_bytes);
+        db.kv_put(&at_limit, 1i64).unwrap();
+        assert!(db.kv_put(&format!("{}k", at_limit), 1i64).is_err());
+    }
+
+    #[test]
+    fn test_kv_diff_versions() {
+        let db = create_strata();
+        let v1 = db.kv_put("cfg", 1i64).unwrap();
+        let v2 = db.kv_put("other", 0i64).unwrap();
+        let v3 = db.kv_put("cfg", 2i64).unwrap();
+
+        assert_eq!(
+            db.kv_diff_versions("cfg", v1, v3).unwrap(),
+            Some((Value::Int(1), Value::Int(2)))
+        );
+        // A version between writes resolves to the value as of that version
+        assert_eq!(
+            db.kv_diff_versions("cfg", v2, v2).unwrap(),
+            Some((Value::Int(1), Value::Int(1)))
+        );
+        assert_eq!(db.kv_diff_versions("cfg", v1 - 1, v3).unwrap(), None);
+        assert_eq!(db.kv_diff_versions("missing", v1, v3).unwrap(), None);
+        assert!(matches!(
+            db.kv_diff_versions("cfg", v3, v1),
+            Err(Error::InvalidInput { .. })
+        ));
+
+        let v4 = db.kv_delete("cfg").map(|_| v3 + 1).unwrap();
+        let (_, after) = db.kv_diff_versions("cfg", v3, v4).unwrap().unwrap();
+        assert_eq!(after, Value::Null);
+    }
+
+    #[test]
+    fn test_kv_diff_versions_patch() {
+        let db = create_strata();
+        let before = serde_json::json!({"name": "a", "tags": ["x"]});
+        let after = serde_json::json!({"name": "b", "tags": ["x", "y"]});
+        let v1 = db
+            .kv_put("doc", crate::json::json_to_value(&before).unwrap())
+            .unwrap();
+        let v2 = db
+            .kv_put("doc", crate::json::json_to_value(&after).unwrap())
+            .unwrap();
+
+        let patch = db.kv_diff_versions_patch("doc", v1, v2).unwrap().unwrap();
+        assert_eq!(
+            patch,
+            vec![
+                JsonPatchOp::Replace {
+                    path: "/name".into(),
+                    value: serde_json::json!("b"),
+                },
+                JsonPatchOp::Add {
+                    path: "/tags/1".into(),
+                    value: serde_json::json!("y"),
+                },
+            ]
+        );
+        assert!(db
+            .kv_diff_versions_patch("doc", v2, v2)
+            .unwrap()
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_kv_put_get() {
         let db = create_strata();
@@ -626,450 +1197,2944 @@ mod tests {
     }
 
     #[test]
-    fn test_kv_delete() {
+    fn test_kv_put_coerced() {
         let db = create_strata();
 
-        // Simplified API: just pass i64 directly
-        db.kv_put("key1", 42i64).unwrap();
-        assert!(db.kv_get("key1").unwrap().is_some());
+        db.kv_put_coerced("qty", "\"42\"").unwrap();
+        assert_eq!(db.kv_get("qty").unwrap(), Some(Value::Int(42)));
 
-        let existed = db.kv_delete("key1").unwrap();
-        assert!(existed);
-        assert!(db.kv_get("key1").unwrap().is_none());
+        db.kv_put_coerced("order", r#"{"paid": "true", "zip": "02134"}"#)
+            .unwrap();
+        let Some(Value::Object(order)) = db.kv_get("order").unwrap() else {
+            panic!("expected an object");
+        };
+        assert_eq!(order["paid"], Value::Bool(true));
+        assert_eq!(order["zip"], Value::String("02134".into()));
+
+        // The strict path is unchanged
+        db.kv_put("raw", "42").unwrap();
+        assert_eq!(db.kv_get("raw").unwrap(), Some(Value::String("42".into())));
+
+        assert!(matches!(
+            db.kv_put_coerced("bad", "{not json"),
+            Err(Error::InvalidInput { .. })
+        ));
     }
 
     #[test]
-    fn test_kv_list() {
-        let db = create_strata();
-
-        db.kv_put("user:1", 1i64).unwrap();
-        db.kv_put("user:2", 2i64).unwrap();
-        db.kv_put("task:1", 3i64).unwrap();
-
-        let user_keys = db.kv_list(Some("user:")).unwrap();
-        assert_eq!(user_keys.len(), 2);
+    fn test_exists_per_primitive() {
+        use crate::PrimitiveType;
 
-        let all_keys = db.kv_list(None).unwrap();
-        assert_eq!(all_keys.len(), 3);
+        let db = create_strata();
+        db.kv_put("k", 1i64).unwrap();
+        db.state_set("cell", 1i64).unwrap();
+        db.json_set("doc", "$", Value::Object(Default::default()))
+            .unwrap();
+        let event = db.event_append("e", Value::Object(Default::default()));
+        let sequence = event.unwrap().sequence;
+        db.vector_create_collection("vecs", 2, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert("vecs", "v", vec![1.0, 0.0], None).unwrap();
+
+        assert!(db.exists(PrimitiveType::Kv, "k").unwrap());
+        assert!(!db.exists(PrimitiveType::Kv, "cell").unwrap());
+        assert!(db.exists(PrimitiveType::State, "cell").unwrap());
+        assert!(db.exists(PrimitiveType::Json, "doc").unwrap());
+        assert!(!db.exists(PrimitiveType::Json, "k").unwrap());
+        assert!(db
+            .exists(PrimitiveType::Event, &sequence.to_string())
+            .unwrap());
+        assert!(!db.exists(PrimitiveType::Event, "999").unwrap());
+        assert!(db.exists(PrimitiveType::Vector, "vecs/v").unwrap());
+        assert!(!db.exists(PrimitiveType::Vector, "vecs/w").unwrap());
+        assert!(!db.exists(PrimitiveType::Vector, "missing/v").unwrap());
+        assert!(db.exists(PrimitiveType::Branch, "default").unwrap());
+
+        assert!(matches!(
+            db.exists(PrimitiveType::Event, "first"),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            db.exists(PrimitiveType::Vector, "v"),
+            Err(Error::InvalidInput { .. })
+        ));
     }
 
     #[test]
-    fn test_state_set_get() {
+    fn test_key_info() {
+        use crate::PrimitiveType;
+
         let db = create_strata();
+        assert!(db.key_info("config").unwrap().is_none());
 
-        // Simplified API: just pass &str directly
-        db.state_set("cell", "state").unwrap();
-        let value = db.state_get("cell").unwrap();
-        assert!(value.is_some());
-        assert_eq!(value.unwrap(), Value::String("state".into()));
+        let kv_version = db.kv_put("config", 1i64).unwrap();
+        db.state_set("config", "ready").unwrap();
+        db.state_set("config", "done").unwrap();
+
+        let info = db.key_info("config").unwrap().unwrap();
+        assert_eq!(info.key, "config");
+        assert_eq!(
+            info.primitives(),
+            vec![PrimitiveType::Kv, PrimitiveType::State]
+        );
+        assert_eq!(info.locations[0].version, kv_version);
+        assert_eq!(info.locations[1].version, 2);
+
+        // Other spaces are a separate keyspace
+        let mut db = db;
+        db.set_space("other").unwrap();
+        assert!(db.key_info("config").unwrap().is_none());
     }
 
     #[test]
-    fn test_event_append_range() {
+    fn test_scan_all() {
+        use crate::PrimitiveType;
+
         let db = create_strata();
+        assert_eq!(db.scan_all().count(), 0);
+
+        let kv_version = db.kv_put("b", 1i64).unwrap();
+        db.kv_put("a", 1i64).unwrap();
+        db.kv_put("gone", 1i64).unwrap();
+        db.kv_delete("gone").unwrap();
+        db.blob_put("blob", &b"bytes"[..]).unwrap();
+        db.state_set("b", 1i64).unwrap();
+        db.json_set("doc", "$", Value::Object(Default::default()))
+            .unwrap();
+        for _ in 0..2 {
+            db.event_append("e", Value::Object(Default::default()))
+                .unwrap();
+        }
+        db.vector_create_collection("vecs", 2, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert("vecs", "v", vec![1.0, 0.0], None).unwrap();
 
-        // Event payloads must be Objects
-        db.event_append(
-            "stream",
-            Value::Object([("value".to_string(), Value::Int(1))].into_iter().collect()),
-        )
-        .unwrap();
-        db.event_append(
-            "stream",
-            Value::Object([("value".to_string(), Value::Int(2))].into_iter().collect()),
-        )
-        .unwrap();
+        let mut scan = db.scan_all();
+        let first = scan.next().unwrap().unwrap();
+        assert_eq!(first, (PrimitiveType::Kv, "a".to_string(), kv_version + 1));
 
-        let events = db.event_get_by_type("stream").unwrap();
-        assert_eq!(events.len(), 2);
+        // Writes after the scan started are not part of it
+        db.json_set("late", "$", Value::Object(Default::default()))
+            .unwrap();
+
+        let rest: Vec<_> = scan
+            .map(|entry| {
+                let (primitive, key, _) = entry.unwrap();
+                (primitive, key)
+            })
+            .collect();
+        let expected = [
+            // Blobs live under reserved KV keys and do not show up
+            (PrimitiveType::Kv, "b"),
+            (PrimitiveType::Event, "0"),
+            (PrimitiveType::Event, "1"),
+            (PrimitiveType::State, "b"),
+            (PrimitiveType::Json, "doc"),
+            (PrimitiveType::Vector, "vecs/v"),
+        ];
+        assert_eq!(
+            rest,
+            expected
+                .iter()
+                .map(|(p, k)| (*p, k.to_string()))
+                .collect::<Vec<_>>()
+        );
+
+        // Other spaces are a separate keyspace
+        let mut db = db;
+        db.set_space("other").unwrap();
+        assert_eq!(db.scan_all().count(), 0);
     }
 
     #[test]
-    fn test_vector_operations() {
+    fn test_kv_delete() {
         let db = create_strata();
 
-        db.vector_create_collection("vecs", 4u64, DistanceMetric::Cosine)
-            .unwrap();
-        db.vector_upsert("vecs", "v1", vec![1.0, 0.0, 0.0, 0.0], None)
-            .unwrap();
-        db.vector_upsert("vecs", "v2", vec![0.0, 1.0, 0.0, 0.0], None)
-            .unwrap();
+        // Simplified API: just pass i64 directly
+        db.kv_put("key1", 42i64).unwrap();
+        assert!(db.kv_get("key1").unwrap().is_some());
 
-        let matches = db
-            .vector_search("vecs", vec![1.0, 0.0, 0.0, 0.0], 10u64)
-            .unwrap();
-        assert_eq!(matches.len(), 2);
-        assert_eq!(matches[0].key, "v1");
+        let existed = db.kv_delete("key1").unwrap();
+        assert!(existed);
+        assert!(db.kv_get("key1").unwrap().is_none());
     }
 
     #[test]
-    fn test_branch_create_list() {
+    fn test_kv_many() {
         let db = create_strata();
 
-        let (info, _version) = db
-            .branch_create(
-                Some("550e8400-e29b-41d4-a716-446655440099".to_string()),
-                None,
-            )
+        let versions = db
+            .kv_put_many(vec![
+                BatchKvEntry {
+                    key: "a".into(),
+                    value: Value::Int(1),
+                },
+                BatchKvEntry {
+                    key: "b".into(),
+                    value: Value::Int(2),
+                },
+            ])
             .unwrap();
-        assert_eq!(info.id.as_str(), "550e8400-e29b-41d4-a716-446655440099");
+        assert_eq!(versions.len(), 2);
 
-        let branches = db.branch_list(None, None, None).unwrap();
-        assert!(!branches.is_empty());
+        assert_eq!(
+            db.kv_get_many(&["a", "missing", "b"]).unwrap(),
+            vec![Some(Value::Int(1)), None, Some(Value::Int(2))]
+        );
+        assert_eq!(
+            db.kv_delete_many(&["a", "missing"]).unwrap(),
+            vec![true, false]
+        );
+        assert!(db.kv_get("a").unwrap().is_none());
     }
 
-    // =========================================================================
-    // Branch Context Tests
-    // =========================================================================
-
     #[test]
-    fn test_current_branch_default() {
+    fn test_kv_put_many_is_all_or_nothing() {
         let db = create_strata();
-        assert_eq!(db.current_branch(), "default");
+
+        let result = db.kv_put_many(vec![
+            BatchKvEntry {
+                key: "ok".into(),
+                value: Value::Int(1),
+            },
+            BatchKvEntry {
+                key: "".into(),
+                value: Value::Int(2),
+            },
+        ]);
+        assert!(result.is_err());
+        assert!(db.kv_get("ok").unwrap().is_none());
     }
 
     #[test]
-    fn test_create_branch() {
+    fn test_kv_bytes_keys() {
         let db = create_strata();
+        let key = [0x00, 0xFF, 0xC3, 0x28];
 
-        // Create a new branch (stays on current branch)
-        db.create_branch("experiment-1").unwrap();
+        db.kv_put_bytes(&key, 1i64).unwrap();
+        assert_eq!(db.kv_get_bytes(&key).unwrap(), Some(Value::Int(1)));
+        assert_eq!(db.kv_get_bytes(&[0x00, 0xFF]).unwrap(), None);
 
-        // Still on default branch
-        assert_eq!(db.current_branch(), "default");
+        // A UTF-8 key written either way is the same entry
+        db.kv_put_bytes(b"plain", "v").unwrap();
+        assert_eq!(db.kv_get("plain").unwrap(), Some(Value::String("v".into())));
 
-        // But the branch exists
-        assert!(db.branch_exists("experiment-1").unwrap());
+        assert!(db.kv_put_bytes(&[], 1i64).is_err());
+        assert!(db.kv_put_bytes(b"_strata/x", 1i64).is_err());
     }
 
     #[test]
-    fn test_set_branch_to_existing() {
-        let mut db = create_strata();
+    fn test_reserved_keys_rejected() {
+        let db = create_strata();
 
-        // Create a branch first
-        db.create_branch("my-branch").unwrap();
+        let invalid = |r: Result<u64>| assert!(matches!(r, Err(Error::InvalidInput { .. })));
+        invalid(db.kv_put("_strata/x", 1i64));
+        invalid(db.state_set("_strata/x", 1i64));
+        invalid(db.json_set("_strata/x", "$", 1i64));
+
+        let payload = || Value::Object([("v".to_string(), Value::Int(1))].into_iter().collect());
+        db.event_append("a", payload()).unwrap();
+        assert!(matches!(
+            db.event_append("a\0b", payload()),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert_eq!(db.event_get_by_type("a").unwrap().len(), 1);
+
+        for name in ["__idx_status__Active__x", "a__idx_b"] {
+            assert!(matches!(
+                db.create_branch(name),
+                Err(Error::InvalidInput { .. })
+            ));
+        }
 
-        // Switch to it
-        db.set_branch("my-branch").unwrap();
-        assert_eq!(db.current_branch(), "my-branch");
+        // Internal markers of other primitives are ordinary user keys
+        db.kv_put("__meta__", 1i64).unwrap();
+        db.state_set("__tidx__x", 1i64).unwrap();
+        assert_eq!(db.event_len().unwrap(), 1);
+        assert_eq!(db.kv_get("__meta__").unwrap(), Some(Value::Int(1)));
     }
 
-    #[test]
-    fn test_set_branch_nonexistent_fails() {
-        let mut db = create_strata();
-
-        // Try to switch to a branch that doesn't exist
-        let result = db.set_branch("nonexistent");
-        assert!(result.is_err());
+    fn blob_bytes(db: &Strata, key: &str) -> Option<Vec<u8>> {
+        let mut reader = db.blob_get(key).unwrap()?;
+        let mut out = Vec::new();
+        std::io::Read::read_to_end(&mut reader, &mut out).unwrap();
+        assert_eq!(out.len() as u64, reader.len());
+        Some(out)
     }
 
     #[test]
-    fn test_list_branches() {
-        let db = create_strata();
-
-        // Create a few branches
-        db.create_branch("branch-a").unwrap();
-        db.create_branch("branch-b").unwrap();
-        db.create_branch("branch-c").unwrap();
-
-        let branches = db.list_branches().unwrap();
-        assert!(branches.contains(&"branch-a".to_string()));
-        assert!(branches.contains(&"branch-b".to_string()));
-        assert!(branches.contains(&"branch-c".to_string()));
+    fn test_blob_roundtrip_across_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let data: Vec<u8> = (0..BLOB_CHUNK_SIZE * 5 / 2).map(|i| i as u8).collect();
+
+        let db = Strata::open(dir.path()).unwrap();
+        db.blob_put("big", data.as_slice()).unwrap();
+        db.blob_put("empty", std::io::empty()).unwrap();
+        assert_eq!(blob_bytes(&db, "big"), Some(data.clone()));
+        assert_eq!(blob_bytes(&db, "empty"), Some(Vec::new()));
+        assert_eq!(blob_bytes(&db, "missing"), None);
+
+        // Blob storage is invisible to the KV API
+        assert_eq!(db.kv_get("big").unwrap(), None);
+        assert!(db.kv_list(None).unwrap().is_empty());
+        drop(db);
+
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(blob_bytes(&db, "big"), Some(data));
     }
 
     #[test]
-    fn test_delete_branch() {
+    fn test_blob_overwrite_and_delete() {
         let db = create_strata();
+        db.blob_put("b", vec![1u8; BLOB_CHUNK_SIZE * 2].as_slice())
+            .unwrap();
+        db.blob_put("b", &b"short"[..]).unwrap();
+        assert_eq!(blob_bytes(&db, "b"), Some(b"short".to_vec()));
 
-        // Create a branch
-        db.create_branch("to-delete").unwrap();
-
-        // Delete the branch
-        db.delete_branch("to-delete").unwrap();
-
-        // Verify it's gone
-        assert!(!db.branch_exists("to-delete").unwrap());
+        assert!(db.blob_delete("b").unwrap());
+        assert_eq!(blob_bytes(&db, "b"), None);
+        assert!(!db.blob_delete("b").unwrap());
+        assert!(db.blob_put("_strata/x", &b"x"[..]).is_err());
     }
 
     #[test]
-    fn test_delete_current_branch_fails() {
-        let mut db = create_strata();
-
-        db.create_branch("current-branch").unwrap();
-        db.set_branch("current-branch").unwrap();
+    fn test_blob_failed_put_keeps_previous() {
+        struct Failing(usize);
+        impl std::io::Read for Failing {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.0 == 0 {
+                    return Err(std::io::Error::new(std::io::ErrorKind::Other, "disk gone"));
+                }
+                let n = buf.len().min(self.0);
+                self.0 -= n;
+                Ok(n)
+            }
+        }
 
-        // Trying to delete the current branch should fail
-        let result = db.delete_branch("current-branch");
-        assert!(result.is_err());
+        let db = create_strata();
+        db.blob_put("b", &b"original"[..]).unwrap();
+        let err = db.blob_put("b", Failing(BLOB_CHUNK_SIZE + 10)).unwrap_err();
+        assert!(matches!(err, Error::Io { .. }));
+        assert_eq!(blob_bytes(&db, "b"), Some(b"original".to_vec()));
     }
 
     #[test]
-    fn test_delete_default_branch_fails() {
+    fn test_blob_reader_fails_if_blob_deleted() {
         let db = create_strata();
+        db.blob_put("b", vec![7u8; BLOB_CHUNK_SIZE + 1].as_slice())
+            .unwrap();
+        let mut reader = db.blob_get("b").unwrap().unwrap();
+        let mut first = vec![0u8; BLOB_CHUNK_SIZE];
+        std::io::Read::read_exact(&mut reader, &mut first).unwrap();
 
-        // Trying to delete the default branch should fail
-        let result = db.delete_branch("default");
-        assert!(result.is_err());
+        db.blob_delete("b").unwrap();
+        let mut rest = Vec::new();
+        assert!(std::io::Read::read_to_end(&mut reader, &mut rest).is_err());
     }
 
     #[test]
-    fn test_branch_context_data_isolation() {
-        let mut db = create_strata();
-
-        // Put data in default branch (simplified API)
-        db.kv_put("key", "default-value").unwrap();
+    fn test_kv_delete_if_equals() {
+        let db = create_strata();
 
-        // Create and switch to another branch
-        db.create_branch("experiment").unwrap();
-        db.set_branch("experiment").unwrap();
+        db.kv_put("lock", "worker-1").unwrap();
+        assert!(!db.kv_delete_if_equals("lock", "worker-2").unwrap());
+        assert!(db.kv_get("lock").unwrap().is_some());
 
-        // The key should not exist in this branch
-        assert!(db.kv_get("key").unwrap().is_none());
+        assert!(db.kv_delete_if_equals("lock", "worker-1").unwrap());
+        assert!(db.kv_get("lock").unwrap().is_none());
+        assert!(!db.kv_delete_if_equals("lock", "worker-1").unwrap());
+    }
 
-        // Put different data
-        db.kv_put("key", "experiment-value").unwrap();
+    #[test]
+    fn test_kv_get_or_put() {
+        let db = create_strata();
 
-        // Switch back to default
-        db.set_branch("default").unwrap();
+        assert_eq!(db.kv_get_or_put("k", 1i64).unwrap(), Value::Int(1));
+        assert_eq!(db.kv_get_or_put("k", 2i64).unwrap(), Value::Int(1));
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
 
-        // Original value should still be there
-        let value = db.kv_get("key").unwrap();
-        assert_eq!(value, Some(Value::String("default-value".into())));
+        let value = db
+            .kv_get_or_put_with("k", || -> i64 { panic!("default built for existing key") })
+            .unwrap();
+        assert_eq!(value, Value::Int(1));
+        assert_eq!(
+            db.kv_get_or_put_with("new", || "fresh").unwrap(),
+            Value::String("fresh".into())
+        );
     }
 
     #[test]
-    fn test_branch_context_isolation_all_primitives() {
-        let mut db = create_strata();
-
-        // Put data in default branch (simplified API)
-        db.kv_put("kv-key", 1i64).unwrap();
-        db.state_set("state-cell", 10i64).unwrap();
-        db.event_append(
-            "stream",
-            Value::Object([("x".to_string(), Value::Int(100))].into_iter().collect()),
-        )
-        .unwrap();
+    fn test_cache_with_entry_budget_evicts() {
+        let db = Strata::cache_with(OpenOptions::new().cache_max_entries(10)).unwrap();
 
-        // Create and switch to another branch
-        db.create_branch("isolated").unwrap();
-        db.set_branch("isolated").unwrap();
+        for i in 0..20i64 {
+            db.kv_put(&format!("k{}", i), i).unwrap();
+        }
 
-        // None of the data should exist in this branch
-        assert!(db.kv_get("kv-key").unwrap().is_none());
-        assert!(db.state_get("state-cell").unwrap().is_none());
-        assert_eq!(db.event_len().unwrap(), 0);
+        assert!(db.kv_list(None).unwrap().len() <= 10);
+        assert_eq!(db.kv_get("k19").unwrap(), Some(Value::Int(19)));
+        assert!(db.kv_get("k0").unwrap().is_none());
     }
 
-    // =========================================================================
-    // db.branches() Power API Tests
-    // =========================================================================
+    #[test]
+    fn test_cache_with_rejects_unsupported_options() {
+        let opts = OpenOptions::new()
+            .durability("always")
+            .access_mode(AccessMode::ReadOnly)
+            .cache_max_entries(10);
+        match Strata::cache_with(opts) {
+            Err(Error::InvalidInput { reason }) => {
+                assert!(reason.contains("durability"), "{}", reason);
+                assert!(reason.contains("access_mode"), "{}", reason);
+                assert!(!reason.contains("cache_max_entries"), "{}", reason);
+            }
+            other => panic!("expected InvalidInput, got {:?}", other.map(|_| ())),
+        }
+    }
 
     #[test]
-    fn test_branches_list() {
+    fn test_kv_list() {
         let db = create_strata();
 
-        // Create some branches
-        db.branches().create("branch-a").unwrap();
-        db.branches().create("branch-b").unwrap();
+        db.kv_put("user:1", 1i64).unwrap();
+        db.kv_put("user:2", 2i64).unwrap();
+        db.kv_put("task:1", 3i64).unwrap();
 
-        let branches = db.branches().list().unwrap();
-        assert!(branches.contains(&"branch-a".to_string()));
-        assert!(branches.contains(&"branch-b".to_string()));
+        let user_keys = db.kv_list(Some("user:")).unwrap();
+        assert_eq!(user_keys.len(), 2);
+
+        let all_keys = db.kv_list(None).unwrap();
+        assert_eq!(all_keys.len(), 3);
     }
 
     #[test]
-    fn test_branches_exists() {
+    fn test_state_set_get() {
         let db = create_strata();
 
-        assert!(!db.branches().exists("nonexistent").unwrap());
-
-        db.branches().create("my-branch").unwrap();
-        assert!(db.branches().exists("my-branch").unwrap());
+        // Simplified API: just pass &str directly
+        db.state_set("cell", "state").unwrap();
+        let value = db.state_get("cell").unwrap();
+        assert!(value.is_some());
+        assert_eq!(value.unwrap(), Value::String("state".into()));
     }
 
     #[test]
-    fn test_branches_create() {
+    fn test_state_update() {
         let db = create_strata();
-
-        db.branches().create("new-branch").unwrap();
-        assert!(db.branches().exists("new-branch").unwrap());
+        let incr = |current: Option<Value>| match current {
+            Some(Value::Int(n)) => Value::Int(n + 1),
+            _ => Value::Int(1),
+        };
+
+        let first = db.state_update("hits", incr).unwrap();
+        assert_eq!(first.value, Value::Int(1));
+        let second = db.state_update("hits", incr).unwrap();
+        assert_eq!(second.value, Value::Int(2));
+        assert!(second.version > first.version);
+        assert_eq!(db.state_get("hits").unwrap(), Some(Value::Int(2)));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let handle = db.new_handle().unwrap();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        handle
+                            .state_update_with_retries("hits", 1000, incr)
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(db.state_get("hits").unwrap(), Some(Value::Int(42)));
     }
 
     #[test]
-    fn test_branches_create_duplicate_fails() {
+    fn test_counter_add_concurrent() {
         let db = create_strata();
-
-        db.branches().create("my-branch").unwrap();
-        let result = db.branches().create("my-branch");
-        assert!(result.is_err());
+        assert_eq!(db.counter_get("hits").unwrap(), 0);
+        db.counter_add("hits", 5).unwrap();
+        db.counter_add("hits", -3).unwrap();
+        assert_eq!(db.counter_get("hits").unwrap(), 2);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let handle = db.new_handle().unwrap();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        handle.counter_add("hits", 1).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(db.counter_get("hits").unwrap(), 202);
+
+        // Counters are not KV keys
+        assert_eq!(db.kv_get("hits").unwrap(), None);
+        assert!(db.kv_list(None).unwrap().is_empty());
+
+        // An overflowing add is rejected and leaves the total unchanged
+        assert!(matches!(
+            db.counter_add("hits", i64::MAX),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert_eq!(db.counter_get("hits").unwrap(), 202);
     }
 
     #[test]
-    fn test_branches_delete() {
+    fn test_counter_shards_sum_on_get() {
         let db = create_strata();
+        db.counter_add("big", i64::MAX).unwrap();
+
+        // Another thread adds into its own shard, so its add succeeds even
+        // though the total no longer fits; retry in case it drew ours
+        let added = (0..64).any(|_| {
+            let handle = db.new_handle().unwrap();
+            std::thread::spawn(move || handle.counter_add("big", 1))
+                .join()
+                .unwrap()
+                .is_ok()
+        });
+        assert!(added);
+        assert!(matches!(db.counter_get("big"), Err(Error::InvalidInput { .. })));
+
+        db.counter_add("big", -1).unwrap();
+        assert_eq!(db.counter_get("big").unwrap(), i64::MAX);
+    }
 
-        db.branches().create("to-delete").unwrap();
-        assert!(db.branches().exists("to-delete").unwrap());
+    #[test]
+    fn test_hll_count_distinct() {
+        let db = create_strata();
+        assert_eq!(db.hll_count("users").unwrap(), 0);
+        for user in ["ada", "bob", "ada", "cy", "bob"] {
+            db.hll_add("users", user).unwrap();
+        }
+        assert_eq!(db.hll_count("users").unwrap(), 3);
+        db.hll_add("users", b"cy").unwrap();
+        assert_eq!(db.hll_count("users").unwrap(), 3);
+
+        // Concurrent adds all land; the estimate stays within the error bound
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let handle = db.new_handle().unwrap();
+                std::thread::spawn(move || {
+                    for i in 0..2500 {
+                        handle
+                            .hll_add("items", format!("item-{}", t * 2500 + i))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        let count = db.hll_count("items").unwrap();
+        assert!((9_500..=10_500).contains(&count), "estimate {}", count);
 
-        db.branches().delete("to-delete").unwrap();
-        assert!(!db.branches().exists("to-delete").unwrap());
+        // Sketches are not KV keys
+        assert_eq!(db.kv_get("users").unwrap(), None);
+        assert!(db.kv_list(None).unwrap().is_empty());
     }
 
     #[test]
-    fn test_branches_delete_default_fails() {
+    fn test_state_update_gives_up_after_max_retries() {
         let db = create_strata();
-
-        let result = db.branches().delete("default");
-        assert!(result.is_err());
+        db.state_set("cell", 0i64).unwrap();
+
+        // A writer that always races ahead of the update
+        let other = db.new_handle().unwrap();
+        let mut calls = 0;
+        let result = db.state_update_with_retries("cell", 2, |_| {
+            calls += 1;
+            other.state_set("cell", calls as i64).unwrap();
+            Value::Int(-1)
+        });
+        assert!(matches!(result, Err(Error::Conflict { .. })));
+        assert_eq!(calls, 3);
     }
 
     #[test]
-    fn test_branches_fork() {
+    fn test_event_append_range() {
         let db = create_strata();
 
-        // Write some data to default branch
-        db.kv_put("key1", "value1").unwrap();
-        db.kv_put("key2", 42i64).unwrap();
+        // Event payloads must be Objects
+        db.event_append(
+            "stream",
+            Value::Object([("value".to_string(), Value::Int(1))].into_iter().collect()),
+        )
+        .unwrap();
+        db.event_append(
+            "stream",
+            Value::Object([("value".to_string(), Value::Int(2))].into_iter().collect()),
+        )
+        .unwrap();
 
-        // Fork default branch to "forked"
-        let info = db.fork_branch("forked").unwrap();
-        assert_eq!(info.source, "default");
-        assert_eq!(info.destination, "forked");
-        assert!(info.keys_copied >= 2);
+        let events = db.event_get_by_type("stream").unwrap();
+        assert_eq!(events.len(), 2);
     }
 
     #[test]
-    fn test_branches_diff() {
-        let mut db = create_strata();
+    fn test_event_append_returns_event() {
+        let db = create_strata();
+        let payload =
+            |n: i64| Value::Object([("n".to_string(), Value::Int(n))].into_iter().collect());
 
-        // Write data to default branch
-        db.kv_put("shared", "value-a").unwrap();
-        db.kv_put("only-default", 1i64).unwrap();
+        let parent = db.event_append("span", payload(1)).unwrap();
+        let child = db.event_append("span", payload(2)).unwrap();
 
-        // Create another branch with different data
-        db.create_branch("other").unwrap();
-        db.set_branch("other").unwrap();
-        db.kv_put("shared", "value-b").unwrap();
-        db.kv_put("only-other", 2i64).unwrap();
+        assert_eq!(child.sequence, parent.sequence + 1);
+        assert_eq!(child.prev_hash, parent.hash);
+        assert_eq!(child.event_type, "span");
+        assert_eq!(child.payload, payload(2));
 
-        let diff = db.diff_branches("default", "other").unwrap();
-        assert_eq!(diff.branch_a, "default");
-        assert_eq!(diff.branch_b, "other");
-        // "shared" should be modified, "only-default" removed, "only-other" added
-        assert!(diff.summary.total_modified >= 1);
-        assert!(diff.summary.total_removed >= 1);
-        assert!(diff.summary.total_added >= 1);
+        let stored = db.event_get(child.sequence).unwrap().unwrap();
+        assert_eq!(stored.value, child.payload);
+        assert_eq!(stored.timestamp, child.timestamp);
     }
 
     #[test]
-    fn test_branches_merge() {
+    fn test_verify_event_chain() {
         let mut db = create_strata();
+        let payload =
+            |n: i64| Value::Object([("n".to_string(), Value::Int(n))].into_iter().collect());
 
-        // Write data to default
-        db.kv_put("base-key", "base-value").unwrap();
-
-        // Create and populate source branch
-        db.create_branch("source").unwrap();
-        db.set_branch("source").unwrap();
-        db.kv_put("new-key", "new-value").unwrap();
-
-        // Merge source into default
-        db.set_branch("default").unwrap();
-        let info = db
-            .merge_branches("source", "default", MergeStrategy::LastWriterWins)
-            .unwrap();
-        assert!(info.keys_applied >= 1);
+        assert_eq!(
+            db.verify_event_chain().unwrap(),
+            ChainVerification::valid(0)
+        );
 
-        // Verify merged data
+        db.event_append("a", payload(1)).unwrap();
+        db.event_append("b", payload(2)).unwrap();
+        db.event_append("a", payload(3)).unwrap();
         assert_eq!(
-            db.kv_get("new-key").unwrap(),
-            Some(Value::String("new-value".into()))
+            db.verify_event_chain().unwrap(),
+            ChainVerification::valid(3)
         );
-        // Original data should still be there
+
+        // Each space has its own chain
+        db.set_space("other").unwrap();
         assert_eq!(
-            db.kv_get("base-key").unwrap(),
-            Some(Value::String("base-value".into()))
+            db.verify_event_chain().unwrap(),
+            ChainVerification::valid(0)
         );
     }
 
-    // =========================================================================
-    // Configuration Tests
-    // =========================================================================
+    #[test]
+    fn test_branch_export_with_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.branchbundle.tar.zst");
+        let hashed = dir.path().join("hashed.branchbundle.tar.zst");
+        let mut db = create_strata();
+        db.branches().create("exported").unwrap();
+        db.set_branch("exported").unwrap();
+        db.kv_put("k", 1i64).unwrap();
+        db.set_branch("default").unwrap();
+
+        db.branch_export("exported", plain.to_str().unwrap())
+            .unwrap();
+        db.branch_export_with_digest("exported", hashed.to_str().unwrap())
+            .unwrap();
+        let plain_info = db.branch_validate_bundle(plain.to_str().unwrap()).unwrap();
+        let hashed_info = db.branch_validate_bundle(hashed.to_str().unwrap()).unwrap();
+        assert_eq!(plain_info.digest_valid, None);
+        assert_eq!(hashed_info.digest_valid, Some(true));
+
+        db.branches().delete("exported").unwrap();
+        db.branch_import(hashed.to_str().unwrap()).unwrap();
+        db.set_branch("exported").unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+    }
 
+    #[cfg(feature = "bundle-signing")]
     #[test]
-    fn test_config_defaults() {
+    fn test_branch_export_signed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signed.branchbundle.tar.zst");
+        let path = path.to_str().unwrap();
+        let key = crate::SigningKey::from_bytes(&[7u8; 32]);
+        let other = crate::SigningKey::from_bytes(&[8u8; 32]);
         let db = create_strata();
-        let cfg = db.config();
-        assert_eq!(cfg.durability, "standard");
-        assert!(!cfg.auto_embed);
-        assert!(cfg.model.is_none());
+        db.branches().create("signed").unwrap();
+
+        db.branch_export_signed("signed", path, &key).unwrap();
+        db.branches().delete("signed").unwrap();
+        assert!(db
+            .branch_import_signed(path, &other.verifying_key())
+            .is_err());
+        db.branch_import_signed(path, &key.verifying_key()).unwrap();
+        assert!(db.branches().exists("signed").unwrap());
     }
 
     #[test]
-    fn test_configure_model() {
+    fn test_trace_tree() {
         let db = create_strata();
-        db.configure_model("http://localhost:11434/v1", "qwen3:1.7b", None, None)
+
+        let root = db
+            .trace_append(None, TraceType::Thought, "plan".into())
+            .unwrap();
+        let call = db
+            .trace_append(Some(root.id), TraceType::ToolCall, "search".into())
+            .unwrap();
+        let other = db
+            .trace_append(None, TraceType::Custom("note".into()), Value::Null)
+            .unwrap();
+        db.trace_append(Some(call.id), TraceType::ToolResult, Value::Int(3))
+            .unwrap();
+        db.trace_append(Some(root.id), TraceType::Decision, "answer".into())
             .unwrap();
 
-        let cfg = db.config();
-        let model = cfg.model.unwrap();
-        assert_eq!(model.endpoint, "http://localhost:11434/v1");
-        assert_eq!(model.model, "qwen3:1.7b");
-        assert!(model.api_key.is_none());
-        assert_eq!(model.timeout_ms, 5000);
+        let tree = db.trace_tree(root.id).unwrap().unwrap();
+        assert_eq!(tree.trace, root);
+        let kinds: Vec<_> = tree.children.iter().map(|c| &c.trace.trace_type).collect();
+        assert_eq!(kinds, vec![&TraceType::ToolCall, &TraceType::Decision]);
+        assert_eq!(tree.children[0].children.len(), 1);
+        assert_eq!(tree.children[0].children[0].trace.payload, Value::Int(3));
+
+        let lone = db.trace_tree(other.id).unwrap().unwrap();
+        assert_eq!(lone.trace.trace_type, TraceType::Custom("note".into()));
+        assert!(lone.children.is_empty());
+
+        // Traces live in the event log
+        assert_eq!(db.event_get_by_type(TRACE_EVENT_TYPE).unwrap().len(), 5);
+
+        // Parents must be existing traces
+        let plain = db
+            .event_append(
+                "log",
+                Value::Object([("a".to_string(), Value::Int(1))].into_iter().collect()),
+            )
+            .unwrap();
+        assert!(db.trace_tree(plain.sequence).unwrap().is_none());
+        assert!(matches!(
+            db.trace_append(Some(plain.sequence), TraceType::Thought, Value::Null),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert!(db
+            .trace_append(Some(999), TraceType::Thought, Value::Null)
+            .is_err());
     }
 
     #[test]
-    fn test_configure_model_with_api_key() {
+    fn test_vector_operations() {
         let db = create_strata();
-        db.configure_model(
-            "https://api.openai.com/v1",
+
+        db.vector_create_collection("vecs", 4u64, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert("vecs", "v1", vec![1.0, 0.0, 0.0, 0.0], None)
+            .unwrap();
+        db.vector_upsert("vecs", "v2", vec![0.0, 1.0, 0.0, 0.0], None)
+            .unwrap();
+
+        let matches = db
+            .vector_search("vecs", vec![1.0, 0.0, 0.0, 0.0], 10u64)
+            .unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].key, "v1");
+    }
+
+    #[test]
+    fn test_vector_create_collection_auto() {
+        let db = create_strata();
+
+        db.vector_create_collection_auto("vecs", DistanceMetric::Cosine)
+            .unwrap();
+        assert_eq!(db.vector_collection_stats("vecs").unwrap().dimension, 0);
+        assert!(db
+            .vector_search("vecs", vec![1.0, 0.0, 0.0], 10u64)
+            .unwrap()
+            .is_empty());
+
+        db.vector_upsert("vecs", "v1", vec![1.0, 0.0, 0.0], None)
+            .unwrap();
+        assert_eq!(db.vector_collection_stats("vecs").unwrap().dimension, 3);
+        assert!(matches!(
+            db.vector_upsert("vecs", "v2", vec![1.0, 0.0], None),
+            Err(Error::DimensionMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        let matches = db
+            .vector_search("vecs", vec![1.0, 0.0, 0.0], 10u64)
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+
+        assert!(db
+            .vector_create_collection_auto("vecs", DistanceMetric::Cosine)
+            .is_err());
+    }
+
+    #[test]
+    fn test_vector_upsert_with_mode() {
+        let db = create_strata();
+
+        db.vector_create_collection("vecs", 2u64, DistanceMetric::Cosine)
+            .unwrap();
+        let first = db
+            .vector_upsert_with_mode("vecs", "v", vec![1.0, 0.0], None, UpsertMode::Error)
+            .unwrap();
+        assert!(first.created);
+
+        assert!(matches!(
+            db.vector_upsert_with_mode("vecs", "v", vec![0.0, 1.0], None, UpsertMode::Error),
+            Err(Error::ConstraintViolation { .. })
+        ));
+
+        let second = db
+            .vector_upsert_with_mode("vecs", "v", vec![0.0, 1.0], None, UpsertMode::Replace)
+            .unwrap();
+        assert!(!second.created);
+        assert!(second.version > first.version);
+        let stored = db.vector_get("vecs", "v").unwrap().unwrap();
+        assert_eq!(stored.data.embedding, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_vector_search_include_fields() {
+        let db = create_strata();
+
+        db.vector_create_collection("docs", 3u64, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert(
+            "docs",
+            "d1",
+            vec![1.0, 0.0, 0.0],
+            Some(Value::String("source text".into())),
+        )
+        .unwrap();
+
+        let plain = db.vector_search("docs", vec![1.0, 0.0, 0.0], 1u64).unwrap();
+        assert_eq!(plain[0].metadata, Some(Value::String("source text".into())));
+        assert_eq!(plain[0].embedding, None);
+
+        let bare = db
+            .vector_search_with("docs", vec![1.0, 0.0, 0.0], 1u64, false, false)
+            .unwrap();
+        assert_eq!(bare[0].metadata, None);
+        assert_eq!(bare[0].embedding, None);
+
+        let full = db
+            .vector_search_with("docs", vec![1.0, 0.0, 0.0], 1u64, true, true)
+            .unwrap();
+        assert_eq!(full[0].key, "d1");
+        assert_eq!(full[0].metadata, Some(Value::String("source text".into())));
+        assert_eq!(full[0].embedding, Some(vec![1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_vector_search_all() {
+        let db = create_strata();
+        let tagged = |keep: bool| {
+            Some(Value::Object(
+                [("keep".to_string(), Value::Bool(keep))]
+                    .into_iter()
+                    .collect(),
+            ))
+        };
+
+        db.vector_create_collection("notes", 2u64, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_create_collection("facts", 2u64, DistanceMetric::Euclidean)
+            .unwrap();
+        db.vector_create_collection("wide", 3u64, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert("notes", "n1", vec![1.0, 0.0], tagged(true))
+            .unwrap();
+        db.vector_upsert("notes", "n2", vec![0.0, 1.0], tagged(false))
+            .unwrap();
+        db.vector_upsert("facts", "f1", vec![0.9, 0.1], tagged(true))
+            .unwrap();
+        db.vector_upsert("facts", "f2", vec![0.0, 1.0], tagged(true))
+            .unwrap();
+        db.vector_upsert("wide", "w1", vec![1.0, 0.0, 0.0], tagged(true))
+            .unwrap();
+
+        // Each collection's best match outranks every second-best match
+        let matches = db.vector_search_all(vec![1.0, 0.0], 3, None).unwrap();
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.collection.as_str(), m.vector_match.key.as_str()))
+            .collect();
+        assert_eq!(found.len(), 3);
+        assert!(found[..2].contains(&("notes", "n1")));
+        assert!(found[..2].contains(&("facts", "f1")));
+        assert!(matches.iter().all(|m| m.collection != "wide"));
+        assert!(matches[0].rrf_score >= matches[2].rrf_score);
+
+        let filter = vec![MetadataFilter {
+            field: "keep".to_string(),
+            op: FilterOp::Eq,
+            value: Value::Bool(true),
+        }];
+        let matches = db
+            .vector_search_all(vec![0.0, 1.0], 10, Some(filter))
+            .unwrap();
+        assert_eq!(matches.len(), 3);
+        assert!(matches.iter().all(|m| m.vector_match.key != "n2"));
+    }
+
+    #[test]
+    fn test_vector_scan() {
+        let db = create_strata();
+
+        db.vector_create_collection("docs", 2u64, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert("docs", "z", vec![1.0, 0.0], Some(Value::Int(1)))
+            .unwrap();
+        db.vector_upsert("docs", "y", vec![0.0, 1.0], None).unwrap();
+        db.vector_upsert("docs", "x", vec![1.0, 1.0], None).unwrap();
+        db.vector_delete("docs", "y").unwrap();
+
+        let scanned: Vec<_> = db
+            .vector_scan("docs")
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(
+            scanned,
+            vec![
+                ("z".to_string(), vec![1.0, 0.0], Some(Value::Int(1))),
+                ("x".to_string(), vec![1.0, 1.0], None),
+            ]
+        );
+
+        assert!(db.vector_scan("missing").is_err());
+    }
+
+    #[test]
+    fn test_vector_reembed() {
+        let db = create_strata();
+        db.vector_create_collection("docs", 2u64, DistanceMetric::Cosine)
+            .unwrap();
+        let meta = |text: &str| Some(Value::from(serde_json::json!({ "text": text })));
+        db.vector_upsert("docs", "n", vec![1.0, 0.0], meta("north"))
+            .unwrap();
+        db.vector_upsert("docs", "e", vec![0.0, 1.0], meta("east"))
+            .unwrap();
+        db.vector_upsert("docs", "bare", vec![1.0, 1.0], None)
+            .unwrap();
+
+        // A new "model" that swaps the two directions
+        let fake_model = |texts: Vec<String>| {
+            Ok(texts
+                .iter()
+                .map(|t| match t.as_str() {
+                    "north" => vec![0.0, 1.0],
+                    _ => vec![1.0, 0.0],
+                })
+                .collect())
+        };
+
+        let err = db.reembed_with("docs", "text", 3, fake_model).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::DimensionMismatch {
+                expected: 2,
+                actual: 3
+            }
+        ));
+        let n = db.vector_get("docs", "n").unwrap().unwrap();
+        assert_eq!(n.data.embedding, vec![1.0, 0.0]);
+
+        let stats = db.reembed_with("docs", "text", 2, fake_model).unwrap();
+        assert_eq!(stats.reembedded, 2);
+        assert_eq!(stats.skipped, vec!["bare".to_string()]);
+
+        let n = db.vector_get("docs", "n").unwrap().unwrap();
+        assert_eq!(n.data.embedding, vec![0.0, 1.0]);
+        assert_eq!(n.data.metadata, meta("north"));
+        let bare = db.vector_get("docs", "bare").unwrap().unwrap();
+        assert_eq!(bare.data.embedding, vec![1.0, 1.0]);
+
+        let hits = db.vector_search("docs", vec![0.0, 1.0], 1).unwrap();
+        assert_eq!(hits[0].key, "n");
+
+        // Without a loadable model nothing is re-embedded
+        assert!(db.vector_reembed("docs", "text").is_err());
+    }
+
+    #[test]
+    fn test_vector_upsert_text_embeds_on_scheduler() {
+        let db = create_strata();
+        db.vector_create_collection("docs", 2u64, DistanceMetric::Cosine)
+            .unwrap();
+
+        let urgent = SetOptions::new().priority(crate::TaskPriority::High);
+        assert_eq!(urgent.embed_priority(), crate::TaskPriority::High);
+        assert_eq!(
+            SetOptions::default().embed_priority(),
+            crate::TaskPriority::Normal
+        );
+        let version = db
+            .upsert_text_with("docs", "q", "question", None, urgent, |text| {
+                let worker = std::thread::current().name().unwrap_or("").to_string();
+                assert!(worker.starts_with("strata-bg-"), "ran on {}", worker);
+                assert_eq!(text, "question");
+                Ok(vec![0.0, 1.0])
+            })
+            .unwrap();
+        assert!(version > 0);
+        let q = db.vector_get("docs", "q").unwrap().unwrap();
+        assert_eq!(q.data.embedding, vec![0.0, 1.0]);
+
+        // Without a loadable model nothing is written
+        assert!(db
+            .vector_upsert_text("docs", "r", "other", None, SetOptions::new())
+            .is_err());
+        assert!(db.vector_get("docs", "r").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_vector_upsert_if_novel() {
+        let db = create_strata();
+        db.vector_create_collection("mem", 2u64, DistanceMetric::Cosine)
+            .unwrap();
+
+        let first = db
+            .vector_upsert_if_novel("mem", "a", vec![1.0, 0.0], None, 0.05)
+            .unwrap();
+        assert!(first.inserted);
+        assert_eq!(first.key, "a");
+        assert!(first.version.is_some());
+
+        let dup = db
+            .vector_upsert_if_novel("mem", "b", vec![1.0, 0.01], None, 0.05)
+            .unwrap();
+        assert!(!dup.inserted);
+        assert_eq!(dup.key, "a");
+        assert!(dup.distance.unwrap() < 0.05);
+        assert!(db.vector_get("mem", "b").unwrap().is_none());
+
+        let novel = db
+            .vector_upsert_if_novel("mem", "c", vec![0.0, 1.0], None, 0.05)
+            .unwrap();
+        assert!(novel.inserted);
+        assert_eq!(novel.key, "c");
+
+        assert!(db
+            .vector_upsert_if_novel("mem", "d", vec![0.5, 0.5], None, f32::NAN)
+            .is_err());
+    }
+
+    #[test]
+    fn test_branch_create_list() {
+        let db = create_strata();
+
+        let (info, _version) = db
+            .branch_create(
+                Some("550e8400-e29b-41d4-a716-446655440099".to_string()),
+                None,
+            )
+            .unwrap();
+        assert_eq!(info.id.as_str(), "550e8400-e29b-41d4-a716-446655440099");
+
+        let branches = db.branch_list(None, None, None).unwrap();
+        assert!(!branches.is_empty());
+    }
+
+    // =========================================================================
+    // Branch Context Tests
+    // =========================================================================
+
+    #[test]
+    fn test_current_branch_default() {
+        let db = create_strata();
+        assert_eq!(db.current_branch(), "default");
+    }
+
+    #[test]
+    fn test_create_branch() {
+        let db = create_strata();
+
+        // Create a new branch (stays on current branch)
+        db.create_branch("experiment-1").unwrap();
+
+        // Still on default branch
+        assert_eq!(db.current_branch(), "default");
+
+        // But the branch exists
+        assert!(db.branch_exists("experiment-1").unwrap());
+    }
+
+    #[test]
+    fn test_set_branch_to_existing() {
+        let mut db = create_strata();
+
+        // Create a branch first
+        db.create_branch("my-branch").unwrap();
+
+        // Switch to it
+        db.set_branch("my-branch").unwrap();
+        assert_eq!(db.current_branch(), "my-branch");
+    }
+
+    #[test]
+    fn test_set_branch_nonexistent_fails() {
+        let mut db = create_strata();
+
+        // Try to switch to a branch that doesn't exist
+        let result = db.set_branch("nonexistent");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_branch_create() {
+        let mut db = create_strata();
+
+        assert!(db.set_branch_create("fresh").unwrap());
+        assert_eq!(db.current_branch(), "fresh");
+        db.kv_put("k", 1i64).unwrap();
+
+        db.set_branch("default").unwrap();
+        assert!(!db.set_branch_create("fresh").unwrap());
+        assert_eq!(db.current_branch(), "fresh");
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_list_branches() {
+        let db = create_strata();
+
+        // Create a few branches
+        db.create_branch("branch-a").unwrap();
+        db.create_branch("branch-b").unwrap();
+        db.create_branch("branch-c").unwrap();
+
+        let branches = db.list_branches().unwrap();
+        assert!(branches.contains(&"branch-a".to_string()));
+        assert!(branches.contains(&"branch-b".to_string()));
+        assert!(branches.contains(&"branch-c".to_string()));
+    }
+
+    #[test]
+    fn test_branch_handles_walk_every_branch() {
+        let mut db = create_strata();
+        db.kv_put("k", 1i64).unwrap();
+        db.create_branch("other").unwrap();
+        db.set_branch("other").unwrap();
+        db.kv_put("a", 2i64).unwrap();
+        db.kv_put("b", 3i64).unwrap();
+
+        let handles: Vec<_> = db.branches().handles().unwrap().collect();
+        let names: Vec<_> = handles.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, db.branches().list().unwrap());
+
+        let (_, other) = handles.iter().find(|(name, _)| name == "other").unwrap();
+        let kv = other.entries_of_type(strata_core::types::TypeTag::KV);
+        let keys: Vec<_> = kv.iter().map(|(key, _)| key.user_key.to_vec()).collect();
+        assert_eq!(keys, vec![b"a".to_vec(), b"b".to_vec()]);
+    }
+
+    #[test]
+    fn test_delete_branch() {
+        let db = create_strata();
+
+        // Create a branch
+        db.create_branch("to-delete").unwrap();
+
+        // Delete the branch
+        db.delete_branch("to-delete").unwrap();
+
+        // Verify it's gone
+        assert!(!db.branch_exists("to-delete").unwrap());
+    }
+
+    #[test]
+    fn test_delete_branch_reports_reclaimed() {
+        let mut db = create_strata();
+
+        db.create_branch("to-reclaim").unwrap();
+        db.set_branch("to-reclaim").unwrap();
+        db.kv_put("key1", "value1").unwrap();
+        db.kv_put("key2", 42i64).unwrap();
+        db.set_branch("default").unwrap();
+
+        let info = db.delete_branch("to-reclaim").unwrap();
+        assert_eq!(info.branch, "to-reclaim");
+        assert!(info.keys_reclaimed >= 2);
+        assert!(info.bytes_reclaimed > 0);
+    }
+
+    #[test]
+    fn test_branch_size_grows_with_data() {
+        let mut db = create_strata();
+        db.create_branch("tenant").unwrap();
+        let before = db.branch_size("tenant").unwrap();
+
+        db.set_branch("tenant").unwrap();
+        db.kv_put("blob", "x".repeat(1024)).unwrap();
+        let after = db.branch_size("tenant").unwrap();
+        assert!(after.keys > before.keys);
+        assert!(after.memory_bytes >= before.memory_bytes + 1024);
+        assert_eq!(after.disk_bytes, 0);
+
+        assert!(matches!(
+            db.branch_size("missing"),
+            Err(Error::BranchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_content_hash_matches_for_same_operations() {
+        let build = |value: &str| {
+            let mut db = create_strata();
+            db.create_branch("fp").unwrap();
+            db.set_branch("fp").unwrap();
+            db.kv_put("k", value).unwrap();
+            db.state_set("cell", 1i64).unwrap();
+            db.json_set("doc", "$", serde_json::json!({"b": 1, "a": [1.5, "x"]}))
+                .unwrap();
+            db
+        };
+
+        let db1 = build("v");
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let db2 = build("v");
+        let db3 = build("w");
+
+        let hash = db1.content_hash("fp").unwrap();
+        assert_eq!(hash, db2.content_hash("fp").unwrap());
+        assert_ne!(hash, db3.content_hash("fp").unwrap());
+        assert_ne!(hash, db1.content_hash("default").unwrap());
+
+        db1.branches().fork_cow("fp", "fp-cow").unwrap();
+        assert_eq!(hash, db1.content_hash("fp-cow").unwrap());
+        assert!(matches!(
+            db1.content_hash("missing"),
+            Err(Error::BranchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_delete_current_branch_fails() {
+        let mut db = create_strata();
+
+        db.create_branch("current-branch").unwrap();
+        db.set_branch("current-branch").unwrap();
+
+        // Trying to delete the current branch should fail
+        let result = db.delete_branch("current-branch");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_delete_default_branch_fails() {
+        let db = create_strata();
+
+        // Trying to delete the default branch should fail
+        let result = db.delete_branch("default");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_branch_context_data_isolation() {
+        let mut db = create_strata();
+
+        // Put data in default branch (simplified API)
+        db.kv_put("key", "default-value").unwrap();
+
+        // Create and switch to another branch
+        db.create_branch("experiment").unwrap();
+        db.set_branch("experiment").unwrap();
+
+        // The key should not exist in this branch
+        assert!(db.kv_get("key").unwrap().is_none());
+
+        // Put different data
+        db.kv_put("key", "experiment-value").unwrap();
+
+        // Switch back to default
+        db.set_branch("default").unwrap();
+
+        // Original value should still be there
+        let value = db.kv_get("key").unwrap();
+        assert_eq!(value, Some(Value::String("default-value".into())));
+    }
+
+    #[test]
+    fn test_branch_context_isolation_all_primitives() {
+        let mut db = create_strata();
+
+        // Put data in default branch (simplified API)
+        db.kv_put("kv-key", 1i64).unwrap();
+        db.state_set("state-cell", 10i64).unwrap();
+        db.event_append(
+            "stream",
+            Value::Object([("x".to_string(), Value::Int(100))].into_iter().collect()),
+        )
+        .unwrap();
+
+        // Create and switch to another branch
+        db.create_branch("isolated").unwrap();
+        db.set_branch("isolated").unwrap();
+
+        // None of the data should exist in this branch
+        assert!(db.kv_get("kv-key").unwrap().is_none());
+        assert!(db.state_get("state-cell").unwrap().is_none());
+        assert_eq!(db.event_len().unwrap(), 0);
+    }
+
+    // =========================================================================
+    // db.branches() Power API Tests
+    // =========================================================================
+
+    #[test]
+    fn test_branches_list() {
+        let db = create_strata();
+
+        // Create some branches
+        db.branches().create("branch-a").unwrap();
+        db.branches().create("branch-b").unwrap();
+
+        let branches = db.branches().list().unwrap();
+        assert!(branches.contains(&"branch-a".to_string()));
+        assert!(branches.contains(&"branch-b".to_string()));
+    }
+
+    #[test]
+    fn test_branches_exists() {
+        let db = create_strata();
+
+        assert!(!db.branches().exists("nonexistent").unwrap());
+
+        db.branches().create("my-branch").unwrap();
+        assert!(db.branches().exists("my-branch").unwrap());
+    }
+
+    #[test]
+    fn test_branches_create() {
+        let db = create_strata();
+
+        db.branches().create("new-branch").unwrap();
+        assert!(db.branches().exists("new-branch").unwrap());
+    }
+
+    #[test]
+    fn test_branches_create_duplicate_fails() {
+        let db = create_strata();
+
+        db.branches().create("my-branch").unwrap();
+        let result = db.branches().create("my-branch");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_branches_delete() {
+        let db = create_strata();
+
+        db.branches().create("to-delete").unwrap();
+        assert!(db.branches().exists("to-delete").unwrap());
+
+        db.branches().delete("to-delete").unwrap();
+        assert!(!db.branches().exists("to-delete").unwrap());
+    }
+
+    #[test]
+    fn test_branches_delete_default_fails() {
+        let db = create_strata();
+
+        let result = db.branches().delete("default");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_branches_fork() {
+        let db = create_strata();
+
+        // Write some data to default branch
+        db.kv_put("key1", "value1").unwrap();
+        db.kv_put("key2", 42i64).unwrap();
+
+        // Fork default branch to "forked"
+        let info = db.fork_branch("forked").unwrap();
+        assert_eq!(info.source, "default");
+        assert_eq!(info.destination, "forked");
+        assert!(info.keys_copied >= 2);
+    }
+
+    #[cfg(not(feature = "embed"))]
+    #[test]
+    fn test_vector_search_text_requires_embed_feature() {
+        let db = create_strata();
+        db.vector_create_collection("docs", 3u64, DistanceMetric::Cosine)
+            .unwrap();
+
+        let err = db.vector_search_text("docs", "hello", 5).unwrap_err();
+        assert!(matches!(err, Error::NotImplemented { .. }));
+    }
+
+    #[test]
+    fn test_branches_fork_records_parent() {
+        let db = create_strata();
+        db.fork_branch("forked").unwrap();
+
+        let forked = db.branch_get("forked").unwrap().unwrap();
+        assert_eq!(forked.info.parent_id, Some(BranchId::from("default")));
+        let default = db.branch_get("default").unwrap().unwrap();
+        assert_eq!(default.info.parent_id, None);
+    }
+
+    #[test]
+    fn test_transaction_metrics() {
+        let db = create_strata();
+        let before = db.transaction_metrics();
+
+        db.kv_put("a", 1i64).unwrap();
+        db.kv_put("b", 2i64).unwrap();
+
+        let after = db.transaction_metrics();
+        assert!(after.total_committed >= before.total_committed + 2);
+        assert_eq!(after.total_conflicts, 0);
+        assert_eq!(after.active_count, 0);
+
+        let snapshot = db.metrics_snapshot();
+        assert_eq!(snapshot.transactions.total_conflicts, 0);
+        assert!(snapshot.current_version > 0);
+    }
+
+    #[test]
+    fn test_read_sees_one_snapshot() {
+        let db = create_strata();
+        db.kv_put("a", 1i64).unwrap();
+        db.state_set("cell", "x").unwrap();
+
+        let (a, cell) = db
+            .read(|view| {
+                let a = view.kv_get("a")?;
+                // Writes committed after the view was opened are not visible
+                db.kv_put("a", 2i64)?;
+                db.state_set("cell", "y")?;
+                Ok((a, view.state_get("cell")?))
+            })
+            .unwrap();
+        assert_eq!(a, Some(Value::Int(1)));
+        assert_eq!(cell, Some(Value::String("x".into())));
+
+        assert_eq!(db.kv_get("a").unwrap(), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn test_read_uses_current_space() {
+        let mut db = create_strata();
+        db.set_space("other").unwrap();
+        db.kv_put("k", "in-other").unwrap();
+        db.json_set("doc", "$", Value::Int(7)).unwrap();
+
+        db.read(|view| {
+            assert_eq!(view.kv_get("k")?, Some(Value::String("in-other".into())));
+            assert_eq!(view.kv_list(None)?, vec!["k".to_string()]);
+            assert_eq!(view.json_get("doc", "$")?, Some(Value::Int(7)));
+            Ok(())
+        })
+        .unwrap();
+
+        db.set_space("default").unwrap();
+        assert_eq!(db.read(|view| view.kv_get("k")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_json_list_with_metadata() {
+        let db = Strata::cache().unwrap();
+        let v1 = db.json_set("a", "$", Value::String("x".into())).unwrap();
+        db.json_set("b", "$", Value::String("x".into())).unwrap();
+        let v2 = db
+            .json_set("b", "$", Value::String("x".repeat(64)))
+            .unwrap();
+
+        let (docs, cursor) = db.json_list_with_metadata(None, None, 1).unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].key, "a");
+        assert_eq!(docs[0].version, v1);
+        let (rest, end) = db.json_list_with_metadata(None, cursor, 10).unwrap();
+        assert_eq!(end, None);
+        assert_eq!(rest[0].key, "b");
+        assert_eq!(rest[0].version, v2);
+        assert!(rest[0].size_bytes > docs[0].size_bytes);
+
+        // Keys-only listing is unchanged
+        let (keys, _) = db.json_list(None, None, 10).unwrap();
+        assert_eq!(keys, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_json_preserves_int_and_float() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut fields: std::collections::HashMap<String, Value> = [
+            ("max".to_string(), Value::Int(i64::MAX)),
+            ("min".to_string(), Value::Int(i64::MIN)),
+            ("one".to_string(), Value::Float(1.0)),
+            (
+                "list".to_string(),
+                Value::Array(vec![Value::Int(i64::MAX - 1)]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let db = Strata::open(dir.path()).unwrap();
+        db.json_set("doc", "$", Value::Object(fields.clone()))
+            .unwrap();
+        db.json_set("doc", "$.patched", i64::MAX).unwrap();
+        assert_eq!(
+            db.json_get("doc", "$.max").unwrap(),
+            Some(Value::Int(i64::MAX))
+        );
+        assert_eq!(
+            db.json_get("doc", "$.one").unwrap(),
+            Some(Value::Float(1.0))
+        );
+        drop(db);
+
+        // Reopen so the document is rebuilt from the WAL
+        let db = Strata::open(dir.path()).unwrap();
+        fields.insert("patched".to_string(), Value::Int(i64::MAX));
+        assert_eq!(
+            db.json_get("doc", "$").unwrap(),
+            Some(Value::Object(fields))
+        );
+    }
+
+    #[test]
+    fn test_branches_fork_cow() {
+        let mut db = create_strata();
+        db.kv_put("key1", "value1").unwrap();
+
+        let info = db.fork_branch_cow("lazy").unwrap();
+        assert_eq!(info.destination, "lazy");
+        assert_eq!(info.keys_copied, 0);
+
+        db.kv_put("key1", "changed").unwrap();
+        db.set_branch("lazy").unwrap();
+        assert_eq!(
+            db.kv_get("key1").unwrap(),
+            Some(Value::String("value1".into()))
+        );
+        assert_eq!(db.kv_list(None).unwrap(), vec!["key1".to_string()]);
+
+        db.kv_put("key2", 2i64).unwrap();
+        db.set_branch("default").unwrap();
+        assert_eq!(db.kv_get("key2").unwrap(), None);
+    }
+
+    #[test]
+    fn test_branches_diff() {
+        let mut db = create_strata();
+
+        // Write data to default branch
+        db.kv_put("shared", "value-a").unwrap();
+        db.kv_put("only-default", 1i64).unwrap();
+
+        // Create another branch with different data
+        db.create_branch("other").unwrap();
+        db.set_branch("other").unwrap();
+        db.kv_put("shared", "value-b").unwrap();
+        db.kv_put("only-other", 2i64).unwrap();
+
+        let diff = db.diff_branches("default", "other").unwrap();
+        assert_eq!(diff.branch_a, "default");
+        assert_eq!(diff.branch_b, "other");
+        // "shared" should be modified, "only-default" removed, "only-other" added
+        assert!(diff.summary.total_modified >= 1);
+        assert!(diff.summary.total_removed >= 1);
+        assert!(diff.summary.total_added >= 1);
+    }
+
+    #[test]
+    fn test_branches_merge() {
+        let mut db = create_strata();
+
+        // Write data to default
+        db.kv_put("base-key", "base-value").unwrap();
+
+        // Create and populate source branch
+        db.create_branch("source").unwrap();
+        db.set_branch("source").unwrap();
+        db.kv_put("new-key", "new-value").unwrap();
+
+        // Merge source into default
+        db.set_branch("default").unwrap();
+        let info = db
+            .merge_branches("source", "default", MergeStrategy::LastWriterWins)
+            .unwrap();
+        assert!(info.keys_applied >= 1);
+
+        // Verify merged data
+        assert_eq!(
+            db.kv_get("new-key").unwrap(),
+            Some(Value::String("new-value".into()))
+        );
+        // Original data should still be there
+        assert_eq!(
+            db.kv_get("base-key").unwrap(),
+            Some(Value::String("base-value".into()))
+        );
+    }
+
+    #[test]
+    fn test_merge_with_callback() {
+        let mut db = create_strata();
+        db.kv_put("kept", 1i64).unwrap();
+        db.kv_put("taken", 1i64).unwrap();
+        db.kv_put("custom", 1i64).unwrap();
+
+        db.create_branch("experiment").unwrap();
+        db.set_branch("experiment").unwrap();
+        for key in ["kept", "taken", "custom"] {
+            db.kv_put(key, 2i64).unwrap();
+        }
+
+        db.set_branch("default").unwrap();
+        let info = db
+            .merge_with("experiment", |conflict| match conflict.key.as_str() {
+                "kept" => Resolution::TakeTarget,
+                "taken" => Resolution::TakeSource,
+                _ => Resolution::Custom(Value::Int(3)),
+            })
+            .unwrap();
+        assert_eq!(info.target, "default");
+        assert_eq!(info.conflicts.len(), 3);
+
+        assert_eq!(db.kv_get("kept").unwrap(), Some(Value::Int(1)));
+        assert_eq!(db.kv_get("taken").unwrap(), Some(Value::Int(2)));
+        assert_eq!(db.kv_get("custom").unwrap(), Some(Value::Int(3)));
+        assert!(matches!(
+            db.merge_with("missing", |_| Resolution::TakeSource),
+            Err(Error::BranchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_diff_from_parent() {
+        let mut db = create_strata();
+        db.kv_put("shared", 1i64).unwrap();
+        assert!(matches!(
+            db.diff_from_parent(),
+            Err(Error::InvalidInput { .. })
+        ));
+
+        db.fork_branch("experiment").unwrap();
+        db.set_branch("experiment").unwrap();
+        db.kv_put("shared", 2i64).unwrap();
+        db.kv_put("lr", 0.01).unwrap();
+
+        let diff = db.diff_from_parent().unwrap();
+        assert_eq!(diff.branch_a, "default");
+        assert_eq!(diff.branch_b, "experiment");
+        assert_eq!(diff.summary.total_added, 1);
+        assert_eq!(diff.summary.total_modified, 1);
+        assert_eq!(diff.summary.total_removed, 0);
+    }
+
+    // =========================================================================
+    // Configuration Tests
+    // =========================================================================
+
+    #[cfg(feature = "embed")]
+    #[test]
+    fn test_open_missing_model_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let degraded = Strata::open_with(dir.path(), OpenOptions::new().auto_embed(true)).unwrap();
+        if degraded.executor.primitives().db.model_available() {
+            // Model installed on this machine; nothing to degrade
+            return;
+        }
+        assert!(!degraded.auto_embed_enabled());
+        assert!(degraded.config().auto_embed);
+        drop(degraded);
+
+        let err = Strata::open_with(
+            dir.path(),
+            OpenOptions::new()
+                .auto_embed(true)
+                .missing_model(MissingModelPolicy::Fail),
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(err, Error::ModelUnavailable { .. }));
+    }
+
+    #[cfg(feature = "embed")]
+    #[test]
+    fn test_open_rejects_model_dimension_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let model_dir = dir.path().join("models/wide");
+        std::fs::create_dir_all(&model_dir).unwrap();
+        std::fs::write(model_dir.join("model.safetensors"), b"").unwrap();
+        std::fs::write(model_dir.join("vocab.txt"), "[PAD]\n").unwrap();
+        std::fs::write(
+            model_dir.join("config.json"),
+            r#"{"hidden_size": 768, "num_attention_heads": 12,
+                "num_hidden_layers": 12, "vocab_size": 30522}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("strata.toml"),
+            "[embed_model]\npath = \"models/wide\"\n",
+        )
+        .unwrap();
+
+        let err = Strata::open_with(
+            dir.path(),
+            OpenOptions::new()
+                .auto_embed(true)
+                .missing_model(MissingModelPolicy::Fail),
+        )
+        .err()
+        .unwrap();
+        match err {
+            Error::ModelUnavailable { reason } => assert!(reason.contains("768"), "{}", reason),
+            other => panic!("expected ModelUnavailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_warm_up_is_noop_without_auto_embed() {
+        let db = create_strata();
+        assert!(!db.auto_embed_enabled());
+        db.warm_up().unwrap();
+    }
+
+    #[cfg(feature = "embed")]
+    #[test]
+    fn test_warm_up_reports_missing_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Strata::open(dir.path()).unwrap();
+        db.set_auto_embed(true).unwrap();
+        if db.executor.primitives().db.model_available() {
+            // Model installed on this machine; warm-up succeeds
+            db.warm_up().unwrap();
+            return;
+        }
+        assert!(matches!(db.warm_up(), Err(Error::ModelUnavailable { .. })));
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let db = create_strata();
+        let cfg = db.config();
+        assert_eq!(cfg.durability, "standard");
+        assert!(!cfg.auto_embed);
+        assert!(cfg.model.is_none());
+    }
+
+    #[test]
+    fn test_configure_model() {
+        let db = create_strata();
+        db.configure_model("http://localhost:11434/v1", "qwen3:1.7b", None, None)
+            .unwrap();
+
+        let cfg = db.config();
+        let model = cfg.model.unwrap();
+        assert_eq!(model.endpoint, "http://localhost:11434/v1");
+        assert_eq!(model.model, "qwen3:1.7b");
+        assert!(model.api_key.is_none());
+        assert_eq!(model.timeout_ms, 5000);
+    }
+
+    #[test]
+    fn test_configure_model_with_api_key() {
+        let db = create_strata();
+        db.configure_model(
+            "https://api.openai.com/v1",
             "gpt-4",
             Some("sk-test-key"),
             Some(10000),
         )
         .unwrap();
 
-        let cfg = db.config();
-        let model = cfg.model.unwrap();
-        assert_eq!(model.api_key.as_deref(), Some("sk-test-key"));
-        assert_eq!(model.timeout_ms, 10000);
+        let cfg = db.config();
+        let model = cfg.model.unwrap();
+        assert_eq!(model.api_key.as_deref(), Some("sk-test-key"));
+        assert_eq!(model.timeout_ms, 10000);
+    }
+
+    #[test]
+    fn test_auto_embed_toggle() {
+        let db = create_strata();
+        assert!(!db.auto_embed_enabled());
+
+        db.set_auto_embed(true).unwrap();
+        assert!(db.auto_embed_enabled());
+
+        db.set_auto_embed(false).unwrap();
+        assert!(!db.auto_embed_enabled());
+    }
+
+    #[test]
+    fn test_config_persists_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // Open, configure model, close
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            db.configure_model("http://localhost:11434/v1", "qwen3:1.7b", None, None)
+                .unwrap();
+        }
+
+        // Reopen — model config should survive via strata.toml
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            let cfg = db.config();
+            let model = cfg
+                .model
+                .expect("model config should persist across reopen");
+            assert_eq!(model.endpoint, "http://localhost:11434/v1");
+            assert_eq!(model.model, "qwen3:1.7b");
+            assert_eq!(model.timeout_ms, 5000);
+        }
+    }
+
+    #[test]
+    fn test_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            db.kv_put("k", 1i64).unwrap();
+            db.sync().unwrap();
+        }
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+
+        assert!(create_strata().sync().is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_self_test_crash_consistency() {
+        use strata_durability::testing::CrashConfig;
+
+        let config = CrashConfig::default()
+            .with_probability(0.2)
+            .with_max_operations(40);
+        let result = Strata::self_test_crash_consistency(config).unwrap();
+        assert!(result.is_valid, "{:?}", result);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_verify_against_model() {
+        use strata_durability::testing::Operation;
+
+        let mut db = create_strata();
+        db.kv_put("a", Value::Bytes(b"1".to_vec())).unwrap();
+        db.kv_put("b", Value::String("two".into())).unwrap();
+        db.state_set("cell", Value::Int(3)).unwrap();
+        let payload = Value::Object(std::collections::HashMap::from([(
+            "n".to_string(),
+            Value::Int(1),
+        )]));
+        db.event_append(MODEL_EVENT_TYPE, payload.clone()).unwrap();
+        db.create_branch("other").unwrap();
+        db.set_branch("other").unwrap();
+        db.kv_put("c", Value::Bytes(b"x".to_vec())).unwrap();
+        db.set_branch("default").unwrap();
+
+        let mut ops = vec![
+            Operation::KvPut {
+                branch: "default".into(),
+                key: "a".into(),
+                value: b"1".to_vec(),
+            },
+            Operation::KvPut {
+                branch: "default".into(),
+                key: "b".into(),
+                value: encode_model_value(&Value::String("two".into())),
+            },
+            Operation::StateSet {
+                branch: "default".into(),
+                key: "cell".into(),
+                value: encode_model_value(&Value::Int(3)),
+            },
+            Operation::EventAppend {
+                branch: "default".into(),
+                payload: encode_model_value(&payload),
+            },
+            Operation::KvPut {
+                branch: "other".into(),
+                key: "c".into(),
+                value: b"x".to_vec(),
+            },
+        ];
+        let result = db.verify_against_model(&ops).unwrap();
+        assert!(result.is_valid, "{:?}", result);
+
+        // A key the model never saw is reported
+        db.kv_put("stray", 0i64).unwrap();
+        ops.push(Operation::KvDelete {
+            branch: "other".into(),
+            key: "c".into(),
+        });
+        let result = db.verify_against_model(&ops).unwrap();
+        assert!(!result.is_valid);
+        let entities: Vec<_> = result
+            .mismatches
+            .iter()
+            .map(|m| m.entity.as_str())
+            .collect();
+        assert!(entities.contains(&"kv:default:stray"));
+        assert!(entities.contains(&"kv:other:c"));
+    }
+
+    #[test]
+    fn test_open_with_background_scheduler_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Strata::open_with(
+            dir.path(),
+            OpenOptions::new()
+                .background_threads(3)
+                .background_queue_depth(8),
+        )
+        .unwrap();
+        let stats = db.executor.primitives().db.scheduler().stats();
+        assert_eq!(stats.worker_count, 3);
+        assert_eq!(stats.max_queue_depth, 8);
+        assert_eq!(db.embed_status().unwrap().scheduler_tasks_rejected, 0);
+    }
+
+    #[test]
+    fn test_open_with_checkpoint_on_shutdown() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open_with(dir.path(), OpenOptions::new().checkpoint_on_shutdown(true))
+                .unwrap();
+            db.kv_put("k", 1i64).unwrap();
+            assert_eq!(db.metrics_snapshot().snapshot_watermark, None);
+        }
+        let db = Strata::open(dir.path()).unwrap();
+        assert!(db.metrics_snapshot().snapshot_watermark.is_some());
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_open_with_keyword_index_disabled() {
+        use crate::types::SearchQuery;
+        use crate::{Command, Output};
+
+        let search = |db: &Strata, mode: &str| {
+            db.executor.execute(Command::Search {
+                branch: None,
+                space: None,
+                search: SearchQuery {
+                    query: "lazy".to_string(),
+                    k: None,
+                    primitives: Some(vec!["kv".to_string()]),
+                    time_range: None,
+                    as_of: None,
+                    typo_tolerance: None,
+                    mode: Some(mode.to_string()),
+                    expand: None,
+                    rerank: None,
+                },
+            })
+        };
+        let hit_count = |result: Result<Output>| match result {
+            Ok(Output::SearchResults(hits)) => hits.len(),
+            other => panic!("Expected SearchResults, got {:?}", other),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            db.kv_put("a", "lazy afternoon").unwrap();
+            assert_eq!(hit_count(search(&db, "keyword")), 1);
+        }
+        {
+            let db =
+                Strata::open_with(dir.path(), OpenOptions::new().keyword_index(false)).unwrap();
+            db.kv_put("b", "lazy evening").unwrap();
+            assert!(matches!(
+                search(&db, "keyword"),
+                Err(Error::InvalidInput { .. })
+            ));
+            assert_eq!(hit_count(search(&db, "hybrid")), 0);
+        }
+        // The setting is saved to strata.toml, so turning the index back on
+        // is explicit. It is rebuilt, including writes made while it was off.
+        let db = Strata::open_with(dir.path(), OpenOptions::new().keyword_index(true)).unwrap();
+        assert_eq!(hit_count(search(&db, "keyword")), 2);
+
+        let cache = Strata::cache_with(OpenOptions::new().keyword_index(false)).unwrap();
+        cache.kv_put("a", "lazy afternoon").unwrap();
+        assert!(matches!(
+            search(&cache, "keyword"),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            cache.rebuild_keyword_index(),
+            Err(Error::InvalidInput { .. })
+        ));
     }
 
     #[test]
-    fn test_auto_embed_toggle() {
+    fn test_rebuild_keyword_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Strata::open(dir.path()).unwrap();
+        db.kv_put("a", "lazy afternoon").unwrap();
+        db.kv_put("b", "quiet lazy evening").unwrap();
+
+        let stats = db.rebuild_keyword_index().unwrap();
+        assert_eq!(stats.documents_indexed, 2);
+        assert_eq!(stats.tokens_indexed, 5);
+        assert!(dir.path().join("search").join("search.manifest").exists());
+
+        // Rebuilding again replaces the index rather than adding to it
+        assert_eq!(db.rebuild_keyword_index().unwrap(), stats);
+    }
+
+    #[test]
+    fn test_search_batch() {
+        use crate::types::SearchQuery;
+
         let db = create_strata();
-        assert!(!db.auto_embed_enabled());
+        db.kv_put("a", "lazy afternoon").unwrap();
+        db.kv_put("b", "quiet lazy evening").unwrap();
+
+        let query = |text: &str| SearchQuery {
+            query: text.into(),
+            mode: Some("keyword".into()),
+            ..Default::default()
+        };
+        let results = db
+            .search_batch(vec![query("lazy"), query("evening"), query("missing")])
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].len(), 2);
+        assert_eq!(results[1].len(), 1);
+        assert_eq!(results[1][0].entity, "b");
+        assert!(results[2].is_empty());
+    }
 
-        db.set_auto_embed(true).unwrap();
-        assert!(db.auto_embed_enabled());
+    #[test]
+    fn test_checkpoint_search_index_survives_crash() {
+        use crate::types::SearchQuery;
+        use crate::{Command, Output};
+
+        let keyword_hits = |db: &Strata| match db.executor.execute(Command::Search {
+            branch: None,
+            space: None,
+            search: SearchQuery {
+                query: "lazy".to_string(),
+                k: None,
+                primitives: Some(vec!["kv".to_string()]),
+                time_range: None,
+                as_of: None,
+                typo_tolerance: None,
+                mode: Some("keyword".to_string()),
+                expand: None,
+                rerank: None,
+            },
+        }) {
+            Ok(Output::SearchResults(hits)) => hits.len(),
+            other => panic!("Expected SearchResults, got {:?}", other),
+        };
+        // Dropping a database saves the index, so a crash is simulated by
+        // putting back the files saved by the last checkpoint.
+        let read_dir = |path: &std::path::Path| -> Vec<(std::ffi::OsString, Vec<u8>)> {
+            std::fs::read_dir(path)
+                .unwrap()
+                .map(|e| {
+                    let e = e.unwrap();
+                    (e.file_name(), std::fs::read(e.path()).unwrap())
+                })
+                .collect()
+        };
+        let restore_dir = |path: &std::path::Path, files: &[(std::ffi::OsString, Vec<u8>)]| {
+            std::fs::remove_dir_all(path).unwrap();
+            std::fs::create_dir_all(path).unwrap();
+            for (name, bytes) in files {
+                std::fs::write(path.join(name), bytes).unwrap();
+            }
+        };
 
-        db.set_auto_embed(false).unwrap();
-        assert!(!db.auto_embed_enabled());
+        let dir = tempfile::tempdir().unwrap();
+        let search_dir = dir.path().join("search");
+
+        // No writes after the checkpoint: the saved index is current
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            db.kv_put("a", "lazy afternoon").unwrap();
+            db.checkpoint_search_index().unwrap();
+            let saved = read_dir(&search_dir);
+            drop(db);
+            restore_dir(&search_dir, &saved);
+        }
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            assert_eq!(keyword_hits(&db), 1);
+        }
+
+        // A write after the checkpoint makes it stale, so it is rebuilt
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            db.checkpoint_search_index().unwrap();
+            let saved = read_dir(&search_dir);
+            db.kv_put("b", "lazy evening").unwrap();
+            drop(db);
+            restore_dir(&search_dir, &saved);
+        }
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(keyword_hits(&db), 2);
+
+        let cache = Strata::cache().unwrap();
+        cache.checkpoint_search_index().unwrap();
     }
 
     #[test]
-    fn test_config_persists_across_reopen() {
+    fn test_health_check() {
         let dir = tempfile::tempdir().unwrap();
+        let db = Strata::open(dir.path()).unwrap();
+        let report = db.health_check().unwrap();
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert!(report.write_checked && report.wal_synced);
+        assert_eq!(report.durability, "standard");
+        assert!(report.issues.is_empty());
+
+        // The probe branch stays hidden and cannot be claimed
+        db.health_check().unwrap();
+        assert_eq!(db.branches().list().unwrap(), vec!["default".to_string()]);
+        assert!(matches!(
+            db.branches().create(strata_engine::HEALTH_BRANCH_NAME),
+            Err(Error::InvalidInput { .. })
+        ));
+        drop(db);
+
+        let read_only = Strata::open_with(
+            dir.path(),
+            OpenOptions::new().access_mode(AccessMode::ReadOnly),
+        )
+        .unwrap();
+        let report = read_only.health_check().unwrap();
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert!(!report.write_checked);
+        drop(read_only);
+
+        // Auto-embedding without a usable model degrades the report. Builds
+        // without the embed feature turn auto-embedding off at open.
+        let db = Strata::open_with(dir.path(), OpenOptions::new().auto_embed(true)).unwrap();
+        let report = db.health_check().unwrap();
+        assert!(report.write_checked);
+        if cfg!(feature = "embed") && !db.executor.primitives().db.model_available() {
+            assert_eq!(report.status, HealthStatus::Degraded);
+            assert_eq!(report.issues.len(), 1);
+        } else {
+            assert_eq!(report.status, HealthStatus::Healthy);
+        }
+        drop(db);
 
-        // Open, configure model, close
+        let cache = Strata::cache().unwrap();
+        let report = cache.health_check().unwrap();
+        assert!(report.write_checked && !report.wal_synced);
+        assert_eq!(report.durability, "cache");
+    }
+
+    #[test]
+    fn test_open_with_vector_storage_in_memory() {
+        let dir = tempfile::tempdir().unwrap();
         {
             let db = Strata::open(dir.path()).unwrap();
-            db.configure_model("http://localhost:11434/v1", "qwen3:1.7b", None, None)
+            db.vector_create_collection("vecs", 3u64, DistanceMetric::Cosine)
+                .unwrap();
+            db.vector_upsert("vecs", "a", vec![1.0, 0.0, 0.0], None)
+                .unwrap();
+            db.vector_upsert("vecs", "b", vec![0.0, 1.0, 0.0], None)
                 .unwrap();
         }
+        let mmap_bytes = {
+            let db = Strata::open(dir.path()).unwrap();
+            assert_eq!(db.config().vector_storage(), VectorStorage::Mmap);
+            db.vector_collection_stats("vecs").unwrap().memory_bytes
+        };
+
+        let in_memory_bytes = {
+            let db = Strata::open_with(
+                dir.path(),
+                OpenOptions::new().vector_storage(VectorStorage::InMemory),
+            )
+            .unwrap();
+            let stats = db.vector_collection_stats("vecs").unwrap();
+            assert_eq!(stats.count, 2);
+            // Inserts after open stay resident too
+            db.vector_upsert("vecs", "c", vec![0.0, 0.0, 1.0], None)
+                .unwrap();
+            stats.memory_bytes
+        };
+        // The embeddings are now counted as anonymous memory
+        assert!(in_memory_bytes.unwrap() > mmap_bytes.unwrap());
+
+        // The setting is saved, and shutdown still wrote the embeddings
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(db.config().vector_storage(), VectorStorage::InMemory);
+        let matches = db.vector_search("vecs", vec![0.0, 0.0, 1.0], 1).unwrap();
+        assert_eq!(matches[0].key, "c");
+        assert_eq!(db.vector_collection_stats("vecs").unwrap().count, 3);
+    }
 
-        // Reopen — model config should survive via strata.toml
+    #[test]
+    fn test_checkpoint_vectors_survives_crash() {
+        fn copy_dir(from: &std::path::Path, to: &std::path::Path) {
+            std::fs::create_dir_all(to).unwrap();
+            for entry in std::fs::read_dir(from).unwrap() {
+                let entry = entry.unwrap();
+                let target = to.join(entry.file_name());
+                if entry.file_type().unwrap().is_dir() {
+                    copy_dir(&entry.path(), &target);
+                } else {
+                    std::fs::copy(entry.path(), target).unwrap();
+                }
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let saved = tempfile::tempdir().unwrap();
+        let vectors_dir = dir.path().join("vectors");
         {
             let db = Strata::open(dir.path()).unwrap();
-            let cfg = db.config();
-            let model = cfg
-                .model
-                .expect("model config should persist across reopen");
-            assert_eq!(model.endpoint, "http://localhost:11434/v1");
-            assert_eq!(model.model, "qwen3:1.7b");
-            assert_eq!(model.timeout_ms, 5000);
+            db.vector_create_collection("vecs", 3u64, DistanceMetric::Cosine)
+                .unwrap();
+            db.vector_upsert("vecs", "a", vec![1.0, 0.0, 0.0], None)
+                .unwrap();
+            db.vector_upsert("vecs", "b", vec![0.0, 1.0, 0.0], None)
+                .unwrap();
+            // The embedding heap and the graph manifest
+            assert_eq!(db.checkpoint_vectors().unwrap(), 2);
+            copy_dir(&vectors_dir, saved.path());
+
+            db.vector_delete("vecs", "b").unwrap();
+        }
+        // Dropping the database saves the vectors again; putting back the
+        // checkpoint simulates a crash after the delete
+        std::fs::remove_dir_all(&vectors_dir).unwrap();
+        copy_dir(saved.path(), &vectors_dir);
+
+        let db = Strata::open(dir.path()).unwrap();
+        let matches = db.vector_search("vecs", vec![0.0, 1.0, 0.0], 10).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].key, "a");
+        assert_eq!(db.vector_collection_stats("vecs").unwrap().count, 1);
+
+        assert_eq!(Strata::cache().unwrap().checkpoint_vectors().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_injected_clock_sets_timestamps() {
+        use crate::{Command, Output};
+        use strata_core::{ManualClock, Timestamp};
+
+        let t1 = Timestamp::from_secs(1_000);
+        let t2 = Timestamp::from_secs(2_000);
+        let t3 = Timestamp::from_secs(3_000);
+        let clock = std::sync::Arc::new(ManualClock::new(t1));
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let opts = OpenOptions::new()
+                .checkpoint_on_shutdown(true)
+                .clock(clock.clone());
+            let db = Strata::open_with(dir.path(), opts).unwrap();
+            db.kv_put("k", 1i64).unwrap();
+            clock.set(t2);
+            db.kv_put("k", 2i64).unwrap();
+            let payload = Value::Object([("a".to_string(), Value::Int(1))].into_iter().collect());
+            let event = db.event_append("ev", payload).unwrap();
+
+            let mut stamps: Vec<u64> = db
+                .kv_getv("k")
+                .unwrap()
+                .unwrap()
+                .iter()
+                .map(|v| v.timestamp)
+                .collect();
+            stamps.sort_unstable();
+            assert_eq!(stamps, vec![t1.as_micros(), t2.as_micros()]);
+            assert_eq!(event.timestamp, t2.as_micros());
+            assert_eq!(
+                db.event_get(event.sequence).unwrap().unwrap().timestamp,
+                t2.as_micros()
+            );
+
+            let as_of = db
+                .executor()
+                .execute(Command::KvGet {
+                    branch: db.branch_id(),
+                    space: db.space_id(),
+                    key: "k".to_string(),
+                    as_of: Some(t1.as_micros()),
+                })
+                .unwrap();
+            assert!(matches!(as_of, Output::Maybe(Some(Value::Int(1)))));
+            clock.set(t3);
+        }
+
+        let db = Strata::open(dir.path()).unwrap();
+        let snapshots = db.list_snapshots().unwrap();
+        assert_eq!(snapshots[0].created_at, t3.as_micros());
+    }
+
+    #[test]
+    fn test_list_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open_with(dir.path(), OpenOptions::new().checkpoint_on_shutdown(true))
+                .unwrap();
+            db.kv_put("k", 1i64).unwrap();
+            assert!(db.list_snapshots().unwrap().is_empty());
+        }
+        let db = Strata::open(dir.path()).unwrap();
+        let snapshots = db.list_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert!(snapshots[0].current);
+        assert_eq!(
+            Some(snapshots[0].watermark_txn),
+            db.metrics_snapshot().snapshot_watermark
+        );
+        assert_eq!(
+            db.snapshot_info(snapshots[0].snapshot_id).unwrap(),
+            Some(snapshots[0].clone())
+        );
+        assert_eq!(db.snapshot_info(999).unwrap(), None);
+    }
+
+    #[test]
+    fn test_open_with_keep_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let opts = || {
+            OpenOptions::new()
+                .checkpoint_on_shutdown(true)
+                .keep_snapshots(1)
+        };
+        for i in 0..3i64 {
+            let db = Strata::open_with(dir.path(), opts()).unwrap();
+            db.kv_put("k", i).unwrap();
+        }
+
+        let db = Strata::open_with(dir.path(), opts()).unwrap();
+        assert_eq!(db.list_snapshots().unwrap().len(), 3);
+        db.compact().unwrap();
+        let snapshots = db.list_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert!(snapshots[0].current);
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn test_compact_reports_info() {
+        let db = Strata::cache().unwrap();
+        assert!(!db.compaction_needed());
+        assert_eq!(
+            db.compact().unwrap(),
+            CompactInfo {
+                segments_removed: 0,
+                bytes_reclaimed: 0,
+            }
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let opts = || OpenOptions::new().checkpoint_on_shutdown(true);
+        let db = Strata::open_with(dir.path(), opts()).unwrap();
+        db.kv_put("k", 1i64).unwrap();
+        // No checkpoint yet: nothing to compact against
+        assert!(!db.compaction_needed());
+        assert!(db.compact().is_err());
+        drop(db);
+
+        let db = Strata::open_with(dir.path(), opts()).unwrap();
+        let info = db.compact().unwrap();
+        assert_eq!(info.segments_removed, 0);
+        assert!(!db.compaction_needed());
+    }
+
+    #[test]
+    fn test_open_with_max_record_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Strata::open_with(dir.path(), OpenOptions::new().max_record_bytes(4096)).unwrap();
+
+        db.kv_put("small", "x".repeat(100)).unwrap();
+        match db.kv_put("big", "x".repeat(8192)) {
+            Err(Error::ValueTooLarge { size, limit }) => {
+                assert!(size > 8192);
+                assert_eq!(limit, 4096);
+            }
+            other => panic!("Expected ValueTooLarge, got {:?}", other),
+        }
+        assert_eq!(db.kv_get("big").unwrap(), None);
+        db.kv_put("after", 1i64).unwrap();
+        drop(db);
+
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(db.kv_get("big").unwrap(), None);
+        assert_eq!(db.kv_get("after").unwrap(), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_open_with_dedup_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = Value::String("x".repeat(8192));
+        {
+            let db =
+                Strata::open_with(dir.path(), OpenOptions::new().dedup_threshold(1024)).unwrap();
+            assert_eq!(db.config().dedup_threshold, Some(1024));
+            db.kv_put("a", doc.clone()).unwrap();
+            db.kv_put("b", doc.clone()).unwrap();
+            db.kv_put("a", 1i64).unwrap();
+            assert_eq!(db.kv_get("b").unwrap(), Some(doc.clone()));
+        }
+
+        let db = Strata::open_with(dir.path(), OpenOptions::new().dedup_threshold(1024)).unwrap();
+        assert_eq!(db.kv_get("a").unwrap(), Some(Value::Int(1)));
+        assert_eq!(db.kv_get("b").unwrap(), Some(doc.clone()));
+
+        let cache = Strata::cache_with(OpenOptions::new().dedup_threshold(1024)).unwrap();
+        assert_eq!(cache.config().dedup_threshold, Some(1024));
+        cache.kv_put("a", doc.clone()).unwrap();
+        assert_eq!(cache.kv_get("a").unwrap(), Some(doc));
+    }
+
+    #[test]
+    fn test_open_with_tombstone_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let opts = || OpenOptions::new().tombstone_ttl(std::time::Duration::from_secs(60));
+        let db = Strata::open_with(dir.path(), opts()).unwrap();
+        assert_eq!(db.config().tombstone_ttl_ms, Some(60_000));
+        db.kv_put("k", 1i64).unwrap();
+        assert!(db.kv_delete("k").unwrap());
+        assert_eq!(db.kv_get("k").unwrap(), None);
+
+        let cache = Strata::cache_with(opts()).unwrap();
+        assert_eq!(cache.config().tombstone_ttl_ms, Some(60_000));
+        assert_eq!(Strata::cache().unwrap().config().tombstone_ttl_ms, None);
+    }
+
+    #[test]
+    fn test_open_with_max_active_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let db =
+            Strata::open_with(dir.path(), OpenOptions::new().max_active_transactions(1)).unwrap();
+        assert_eq!(db.config().max_active_transactions, Some(1));
+
+        let mut first = db.session();
+        first
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        let mut second = db.session();
+        let err = second
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Error::TooManyTransactions {
+                active: 1,
+                limit: 1
+            }
+        );
+
+        first.execute(Command::TxnRollback).unwrap();
+        second
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        second.execute(Command::TxnRollback).unwrap();
+    }
+
+    #[test]
+    fn test_max_result_limit_clamps_lists_and_searches() {
+        let db = Strata::cache_with(OpenOptions::new().max_result_limit(3)).unwrap();
+        assert_eq!(db.config().max_result_limit, Some(3));
+        db.vector_create_collection("vecs", 2, DistanceMetric::Cosine)
+            .unwrap();
+        for i in 0..5 {
+            db.kv_put(&format!("k{}", i), "lazy afternoon").unwrap();
+            db.vector_upsert("vecs", &format!("v{}", i), vec![1.0, i as f32], None)
+                .unwrap();
+        }
+
+        assert_eq!(db.kv_list(None).unwrap(), ["k0", "k1", "k2"]);
+        assert_eq!(
+            db.vector_search("vecs", vec![1.0, 0.0], 10).unwrap().len(),
+            3
+        );
+
+        let output = db
+            .executor
+            .execute(Command::KvList {
+                branch: None,
+                space: None,
+                prefix: None,
+                cursor: None,
+                limit: Some(100),
+                as_of: None,
+            })
+            .unwrap();
+        assert_eq!(
+            output,
+            Output::Truncated(Box::new(Output::Keys(vec![
+                "k0".into(),
+                "k1".into(),
+                "k2".into()
+            ])))
+        );
+        let search = |k| {
+            db.executor
+                .execute(Command::Search {
+                    branch: None,
+                    space: None,
+                    search: SearchQuery {
+                        query: "lazy".into(),
+                        k,
+                        mode: Some("keyword".into()),
+                        ..Default::default()
+                    },
+                })
+                .unwrap()
+        };
+        assert!(matches!(search(Some(100)), Output::Truncated(_)));
+        assert!(matches!(search(Some(2)), Output::SearchResults(hits) if hits.len() == 2));
+
+        // Within the cap nothing is marked truncated
+        let output = db
+            .executor
+            .execute(Command::KvList {
+                branch: None,
+                space: None,
+                prefix: Some("k4".into()),
+                cursor: None,
+                limit: None,
+                as_of: None,
+            })
+            .unwrap();
+        assert_eq!(output, Output::Keys(vec!["k4".into()]));
+    }
+
+    #[test]
+    fn test_default_result_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Strata::open_with(dir.path(), OpenOptions::new().default_result_limit(2)).unwrap();
+        assert_eq!(db.config().default_result_limit, Some(2));
+        for i in 0..4 {
+            db.kv_put(&format!("k{}", i), i as i64).unwrap();
+        }
+        assert_eq!(db.kv_list(None).unwrap(), ["k0", "k1"]);
+
+        // An explicit limit is not capped without max_result_limit
+        let output = db
+            .executor
+            .execute(Command::KvList {
+                branch: None,
+                space: None,
+                prefix: None,
+                cursor: None,
+                limit: Some(4),
+                as_of: None,
+            })
+            .unwrap();
+        assert!(matches!(output, Output::Keys(keys) if keys.len() == 4));
+    }
+
+    fn nested_value(depth: usize) -> Value {
+        (0..depth).fold(Value::Int(1), |inner, _| {
+            Value::Object([("a".to_string(), inner)].into_iter().collect())
+        })
+    }
+
+    #[test]
+    fn test_json_max_nesting_depth() {
+        let db = Strata::cache().unwrap();
+        assert!(db.json_set("doc", "$", nested_value(100)).is_ok());
+        assert!(db.json_set("doc", "$", nested_value(150)).is_err());
+
+        let db = Strata::cache_with(OpenOptions::new().json_max_nesting_depth(200)).unwrap();
+        db.json_set("doc", "$", nested_value(150)).unwrap();
+        assert_eq!(db.json_get("doc", "$").unwrap(), Some(nested_value(150)));
+        assert!(db.json_set("doc", "$", nested_value(201)).is_err());
+
+        // Batches and transactions follow the configured limit too
+        let output = db
+            .executor
+            .execute(Command::JsonBatchSet {
+                branch: None,
+                space: None,
+                entries: vec![
+                    BatchJsonEntry {
+                        key: "ok".into(),
+                        path: "$".into(),
+                        value: nested_value(150),
+                    },
+                    BatchJsonEntry {
+                        key: "deep".into(),
+                        path: "$".into(),
+                        value: nested_value(201),
+                    },
+                ],
+            })
+            .unwrap();
+        let Output::BatchResults(results) = output else {
+            panic!("expected BatchResults, got {:?}", output);
+        };
+        assert!(results[0].error.is_none());
+        assert!(results[1].error.is_some());
+        let mut session = db.session();
+        session
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        assert!(session
+            .execute(Command::JsonSet {
+                branch: None,
+                space: None,
+                key: "txn".into(),
+                path: "$".into(),
+                value: nested_value(201),
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_json_limits_clamped_and_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open_with(
+                dir.path(),
+                OpenOptions::new()
+                    .json_max_nesting_depth(100_000)
+                    .json_max_array_size(2),
+            )
+            .unwrap();
+            let limits = db.config().json_limits();
+            assert_eq!(
+                limits.max_nesting_depth,
+                strata_core::HARD_MAX_NESTING_DEPTH
+            );
+            assert_eq!(limits.max_array_size, 2);
+            let err = db
+                .json_set("doc", "$", Value::Array(vec![Value::Int(1); 3]))
+                .unwrap_err();
+            assert!(
+                matches!(err, Error::ConstraintViolation { .. }),
+                "{:?}",
+                err
+            );
+        }
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(db.config().json_limits().max_array_size, 2);
+    }
+
+    #[test]
+    fn test_active_snapshots_names_branch() {
+        let db = Strata::cache().unwrap();
+        db.kv_put("k", 1i64).unwrap();
+        assert!(db.active_snapshots().unwrap().is_empty());
+
+        let mut session = db.session();
+        session
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        db.kv_put("k", 2i64).unwrap();
+
+        let snapshots = db.active_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert!(matches!(
+            &snapshots[0].holder,
+            SnapshotHolder::Transaction { branch, .. } if branch == "default"
+        ));
+        assert!(snapshots[0].version < db.executor.primitives().db.current_version());
+
+        session.execute(Command::TxnRollback).unwrap();
+        assert!(db.active_snapshots().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_active_snapshots_lists_open_scans() {
+        let db = Strata::cache().unwrap();
+        db.kv_put("a", 1i64).unwrap();
+        db.kv_put("b", 2i64).unwrap();
+
+        let mut scan = db.scan_all();
+        scan.next().unwrap().unwrap();
+        let snapshots = db.active_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].holder, SnapshotHolder::Pinned { count: 1 });
+        assert_eq!(
+            snapshots[0].version,
+            db.executor.primitives().db.current_version()
+        );
+
+        drop(scan);
+        assert!(db.active_snapshots().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_gc_versions_keeps_last_and_snapshot_holds() {
+        let db = Strata::cache().unwrap();
+        for i in 0..5i64 {
+            db.kv_put("counter", i).unwrap();
+        }
+        let mut session = db.session();
+        session
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        for i in 5..10i64 {
+            db.kv_put("counter", i).unwrap();
         }
+
+        // The open transaction still reads 4, so 4 and newer are kept
+        assert_eq!(db.gc_versions(3).unwrap(), 4);
+        assert_eq!(db.kv_getv("counter").unwrap().unwrap().len(), 6);
+        let read = session
+            .execute(Command::KvGet {
+                branch: None,
+                space: None,
+                key: "counter".into(),
+                as_of: None,
+            })
+            .unwrap();
+        assert!(matches!(read, Output::Maybe(Some(Value::Int(4)))));
+        session.execute(Command::TxnRollback).unwrap();
+
+        assert_eq!(db.gc_versions(3).unwrap(), 3);
+        let history = db.kv_getv("counter").unwrap().unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].value, Value::Int(9));
+
+        assert_eq!(db.gc_versions(0).unwrap(), 2);
+        assert_eq!(db.kv_get("counter").unwrap(), Some(Value::Int(9)));
+        assert_eq!(db.gc_versions(3).unwrap(), 0);
+
+        let log = db.admin_log(1).unwrap();
+        assert_eq!(log[0].action, AdminAction::GcVersions);
+        assert_eq!(log[0].target.as_deref(), Some("default"));
+    }
+
+    #[test]
+    fn test_open_with_key_normalization() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open_with(
+                dir.path(),
+                OpenOptions::new().key_normalization(KeyNormalization::Lowercase),
+            )
+            .unwrap();
+            db.kv_put("User:123", 1i64).unwrap();
+            assert_eq!(db.kv_get("USER:123").unwrap(), Some(Value::Int(1)));
+            assert_eq!(db.kv_list(Some("User:")).unwrap(), vec!["user:123"]);
+            db.state_set("Counter", 7i64).unwrap();
+            db.json_set("Doc", "$", 1i64).unwrap();
+            db.blob_put("Model", &b"weights"[..]).unwrap();
+
+            // Transactions and snapshot reads normalize the same way
+            let mut session = db.session();
+            session
+                .execute(Command::TxnBegin {
+                    branch: None,
+                    options: None,
+                })
+                .unwrap();
+            session
+                .execute(Command::KvPut {
+                    branch: None,
+                    space: None,
+                    key: "User:456".into(),
+                    value: Value::Int(2),
+                })
+                .unwrap();
+            session.execute(Command::TxnCommit).unwrap();
+            let (a, b) = db
+                .read(|view| Ok((view.kv_get("user:456")?, view.state_get("COUNTER")?)))
+                .unwrap();
+            assert_eq!(a, Some(Value::Int(2)));
+            assert_eq!(b, Some(Value::Int(7)));
+        }
+
+        // The mode is saved to strata.toml, so a plain reopen keeps it
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(db.config().key_normalization(), KeyNormalization::Lowercase);
+        assert_eq!(db.kv_get("user:123").unwrap(), Some(Value::Int(1)));
+        assert_eq!(db.json_get("DOC", "$").unwrap(), Some(Value::Int(1)));
+        assert!(db.blob_get("model").unwrap().is_some());
+
+        let cache = Strata::cache().unwrap();
+        cache.kv_put("User:123", 1i64).unwrap();
+        assert_eq!(cache.kv_get("user:123").unwrap(), None);
+        let cache =
+            Strata::cache_with(OpenOptions::new().key_normalization(KeyNormalization::Lowercase))
+                .unwrap();
+        cache.kv_put("User:123", 1i64).unwrap();
+        assert_eq!(cache.kv_get("user:123").unwrap(), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_ephemeral_branches() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut db =
+                Strata::open_with(dir.path(), OpenOptions::new().ephemeral_branch("scratch"))
+                    .unwrap();
+            db.branches().create("scratch").unwrap();
+            db.branches().create("work").unwrap();
+            assert!(db.branches().set_ephemeral("default", true).is_err());
+            assert!(matches!(
+                db.branches().set_ephemeral("missing", true),
+                Err(Error::BranchNotFound { .. })
+            ));
+            db.branches().set_ephemeral("work", true).unwrap();
+            assert!(db.branches().is_ephemeral("scratch").unwrap());
+            assert!(!db.branches().is_ephemeral("default").unwrap());
+
+            db.kv_put("k", 1i64).unwrap();
+            for branch in ["scratch", "work"] {
+                db.set_branch(branch).unwrap();
+                db.kv_put("k", 2i64).unwrap();
+                assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(2)));
+            }
+        }
+
+        // Ephemeral writes are gone after a restart; the branches remain
+        let mut db = Strata::open(dir.path()).unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+        for branch in ["scratch", "work"] {
+            assert!(db.branches().is_ephemeral(branch).unwrap());
+            db.set_branch(branch).unwrap();
+            assert_eq!(db.kv_get("k").unwrap(), None);
+        }
+        db.branches().set_ephemeral("work", false).unwrap();
+        assert!(!db.branches().is_ephemeral("work").unwrap());
+    }
+
+    #[test]
+    fn test_restore_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open_with(dir.path(), OpenOptions::new().checkpoint_on_shutdown(true))
+                .unwrap();
+            db.kv_put("k", 1i64).unwrap();
+        }
+        let snapshot_id = {
+            let db = Strata::open(dir.path()).unwrap();
+            db.kv_put("k", 2i64).unwrap();
+            let id = db.list_snapshots().unwrap()[0].snapshot_id;
+            assert!(Strata::restore_snapshot(dir.path(), id).is_err());
+            id
+        };
+
+        Strata::restore_snapshot(dir.path(), snapshot_id).unwrap();
+        let db = Strata::open(dir.path()).unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_admin_log() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open_with(
+                dir.path(),
+                OpenOptions::new().actor("ops").checkpoint_on_shutdown(true),
+            )
+            .unwrap();
+            db.create_branch("a").unwrap();
+            db.fork_branch("b").unwrap();
+            db.delete_branch("a").unwrap();
+            db.set_auto_embed(false).unwrap();
+            // A failed action is not recorded
+            assert!(db.delete_branch("missing").is_err());
+
+            let log = db.admin_log(4).unwrap();
+            let actions: Vec<_> = log
+                .iter()
+                .map(|e| (e.action, e.target.as_deref()))
+                .collect();
+            assert_eq!(
+                actions,
+                vec![
+                    (AdminAction::ConfigChange, Some("auto_embed")),
+                    (AdminAction::BranchDelete, Some("a")),
+                    (AdminAction::BranchFork, Some("b")),
+                    (AdminAction::BranchCreate, Some("a")),
+                ]
+            );
+            assert_eq!(log[2].detail.as_deref(), Some("from default"));
+            assert!(log.iter().all(|e| e.actor.as_deref() == Some("ops")));
+            assert!(db.verify_admin_log().unwrap().is_valid);
+            assert!(!db
+                .branches()
+                .list()
+                .unwrap()
+                .iter()
+                .any(|b| b == "_strata_admin"));
+        }
+        let snapshot_id = {
+            let db = Strata::open(dir.path()).unwrap();
+            db.create_branch("c").unwrap();
+            db.list_snapshots().unwrap()[0].snapshot_id
+        };
+
+        // The restore rolls back the entry for "c" and is recorded on open
+        Strata::restore_snapshot(dir.path(), snapshot_id).unwrap();
+        let db = Strata::open(dir.path()).unwrap();
+        let log = db.admin_log(2).unwrap();
+        assert_eq!(log[0].action, AdminAction::Restore);
+        assert_eq!(log[0].actor, None);
+        assert_eq!(log[1].action, AdminAction::ConfigChange);
+        assert!(db.verify_admin_log().unwrap().is_valid);
+    }
+
+    #[test]
+    fn test_vector_manhattan_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Strata::open(dir.path()).unwrap();
+            db.vector_create_collection("l1", 2u64, DistanceMetric::Manhattan)
+                .unwrap();
+            db.vector_upsert("l1", "axis", vec![3.0, 0.0], None)
+                .unwrap();
+            db.vector_upsert("l1", "diagonal", vec![2.0, 2.0], None)
+                .unwrap();
+        }
+
+        let db = Strata::open(dir.path()).unwrap();
+        let info = db
+            .vector_list_collections()
+            .unwrap()
+            .into_iter()
+            .find(|c| c.name == "l1")
+            .unwrap();
+        assert_eq!(info.metric, DistanceMetric::Manhattan);
+
+        // L1 ranks (3, 0) closer to the origin than (2, 2); L2 would not
+        let results = db.vector_search("l1", vec![0.0, 0.0], 2u64).unwrap();
+        assert_eq!(results[0].key, "axis");
+        assert!((results[0].score - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_set_flush_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Strata::open(dir.path()).unwrap();
+
+        db.set_flush_interval(250).unwrap();
+        assert!(db.set_flush_interval(0).is_err());
+        db.kv_put("k", 1i64).unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+
+        // Cache databases have no WAL to flush
+        assert!(create_strata().set_flush_interval(250).is_err());
     }
 }
@@ -0,0 +1,150 @@
+//! Snapshot reads without transaction overhead.
+//!
+//! [`Strata::read`] runs a closure against a single consistent snapshot of
+//! the current branch. Unlike a [`Session`](crate::Session) transaction it
+//! never allocates a transaction ID, version, or WAL record, which makes it
+//! the cheapest way to perform several reads that must agree with each other.
+
+use std::cell::RefCell;
+
+use strata_core::types::Namespace;
+use strata_engine::TransactionContext;
+
+use super::Strata;
+use crate::bridge::to_core_branch_id;
+use crate::types::*;
+use crate::{Command, Error, Executor, Output, Result, Session, Value};
+
+/// A read-only view of one branch at a fixed snapshot.
+///
+/// Obtained from [`Strata::read`]. All reads observe the same snapshot
+/// (snapshot isolation); commits made after the view was opened are not
+/// visible. The view exposes no write operations.
+pub struct ReadView<'a> {
+    executor: &'a Executor,
+    ctx: RefCell<&'a mut TransactionContext>,
+    branch: BranchId,
+    space: String,
+}
+
+impl ReadView<'_> {
+    fn dispatch(&self, cmd: Command) -> Result<Output> {
+        let branch_id = to_core_branch_id(&self.branch)?;
+        let ns = Namespace::for_branch_space(branch_id, &self.space);
+        let mut ctx = self.ctx.borrow_mut();
+        Session::dispatch_in_txn(self.executor, &mut ctx, ns, cmd)
+    }
+
+    /// Get a value from the KV store.
+    pub fn kv_get(&self, key: &str) -> Result<Option<Value>> {
+        match self.dispatch(Command::KvGet {
+            branch: None,
+            space: None,
+            key: key.to_string(),
+            as_of: None,
+        })? {
+            Output::Maybe(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvGet".into(),
+            }),
+        }
+    }
+
+    /// List keys in the KV store, optionally filtered by prefix.
+    pub fn kv_list(&self, prefix: Option<&str>) -> Result<Vec<String>> {
+        match self
+            .dispatch(Command::KvList {
+                branch: None,
+                space: None,
+                prefix: prefix.map(|s| s.to_string()),
+                cursor: None,
+                limit: None,
+                as_of: None,
+            })?
+            .untruncated()
+        {
+            Output::Keys(keys) => Ok(keys),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvList".into(),
+            }),
+        }
+    }
+
+    /// Get the value of a state cell.
+    pub fn state_get(&self, cell: &str) -> Result<Option<Value>> {
+        match self.dispatch(Command::StateGet {
+            branch: None,
+            space: None,
+            cell: cell.to_string(),
+            as_of: None,
+        })? {
+            Output::Maybe(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for StateGet".into(),
+            }),
+        }
+    }
+
+    /// Get a value from a JSON document at `path` (`"$"` for the whole document).
+    pub fn json_get(&self, key: &str, path: &str) -> Result<Option<Value>> {
+        match self.dispatch(Command::JsonGet {
+            branch: None,
+            space: None,
+            key: key.to_string(),
+            path: path.to_string(),
+            as_of: None,
+        })? {
+            Output::Maybe(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonGet".into(),
+            }),
+        }
+    }
+}
+
+impl Strata {
+    /// Run several reads against one consistent snapshot of the current branch.
+    ///
+    /// This is a lightweight alternative to an explicit transaction for
+    /// read-heavy workloads: the snapshot is taken without allocating a
+    /// transaction ID or version, and nothing is validated or written to the
+    /// WAL when the closure returns.
+    ///
+    /// The view provides snapshot isolation: every read inside the closure
+    /// sees the database as of the moment `read` was called. The view cannot
+    /// write; use a [`Session`](crate::Session) transaction for that.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let (balance, limit) = db.read(|view| {
+    ///     Ok((view.kv_get("balance")?, view.state_get("limit")?))
+    /// })?;
+    /// ```
+    pub fn read<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&ReadView<'_>) -> Result<T>,
+    {
+        let branch_id = to_core_branch_id(&self.current_branch)?;
+        let mut out = None;
+        self.executor
+            .primitives()
+            .db
+            .read(branch_id, |ctx| {
+                let view = ReadView {
+                    executor: &self.executor,
+                    ctx: RefCell::new(ctx),
+                    branch: self.current_branch.clone(),
+                    space: self.current_space.clone(),
+                };
+                out = Some(f(&view));
+                Ok(())
+            })
+            .map_err(Error::from)?;
+        out.unwrap_or_else(|| {
+            Err(Error::Internal {
+                reason: "Read closure did not run".into(),
+            })
+        })
+    }
+}
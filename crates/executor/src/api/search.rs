@@ -0,0 +1,45 @@
+//! Cross-primitive search operations.
+
+use super::Strata;
+use crate::types::*;
+use crate::{Command, Error, Output, Result};
+
+impl Strata {
+    // =========================================================================
+    // Search Operations (1)
+    // =========================================================================
+
+    /// Run several searches on the current branch in one call.
+    ///
+    /// Equivalent to issuing each query as a `Search` command, but the
+    /// queries share one search setup and the text of every hybrid query is
+    /// embedded in a single batched pass, which cuts latency for pipelines
+    /// that fan one question out into several sub-queries. Returns one hit
+    /// list per query, in input order. Fails if any query is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let results = db.search_batch(vec![
+    ///     SearchQuery { query: "refund policy".into(), ..Default::default() },
+    ///     SearchQuery { query: "shipping times".into(), k: Some(5), ..Default::default() },
+    /// ])?;
+    /// assert_eq!(results.len(), 2);
+    /// ```
+    pub fn search_batch(&self, searches: Vec<SearchQuery>) -> Result<Vec<Vec<SearchResultHit>>> {
+        match self
+            .executor
+            .execute(Command::SearchBatch {
+                branch: self.branch_id(),
+                space: self.space_id(),
+                searches,
+            })?
+            .untruncated()
+        {
+            Output::SearchBatchResults(results) => Ok(results),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for SearchBatch".into(),
+            }),
+        }
+    }
+}
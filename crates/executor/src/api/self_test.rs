@@ -0,0 +1,172 @@
+//! Crash-consistency self-test (requires the `testing` feature).
+//!
+//! Runs a scripted workload against a scratch database and, at crash points
+//! chosen by [`CrashConfig`], snapshots the on-disk state into a separate
+//! directory, recovers it, and compares the result with a [`ReferenceModel`].
+//!
+//! The snapshot is a copy of the files as the OS currently sees them, which is
+//! exactly what survives a process kill. Power loss cannot be simulated from
+//! inside the process, so `CrashConfig::crash_types` is not consulted.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use strata_durability::testing::{CrashConfig, CrashPoint, ReferenceModel, VerificationResult};
+use strata_security::OpenOptions;
+
+use super::verify::{encode_model_value, MODEL_EVENT_TYPE};
+use super::Strata;
+use crate::{Error, Result, Value};
+
+/// Branch the self-test workload runs on.
+const BRANCH: &str = "default";
+
+/// Number of distinct KV keys and state cells the workload cycles through.
+const KEY_SPACE: usize = 16;
+
+/// Fixed seed so a given config always injects crashes at the same points.
+const SEED: u64 = 0x5EED_C0DE_2545_F491;
+
+impl Strata {
+    /// Verify crash recovery on this machine's storage stack.
+    ///
+    /// Opens a scratch database in a temporary directory with `"always"`
+    /// durability and runs up to `config.max_operations` KV, state, and event
+    /// writes. Before each operation a crash is injected with probability
+    /// `config.crash_probability`, either before the write reaches the WAL
+    /// ([`CrashPoint::BeforeWalWrite`]) or after it was fsynced
+    /// ([`CrashPoint::AfterFsync`]). A final crash is always injected after
+    /// the workload. At every crash the data directory is copied, the copy
+    /// is recovered, and its contents are checked against the reference model.
+    ///
+    /// Only temporary directories are used; no existing database is touched.
+    /// Returns the first failed verification, or success if every recovery
+    /// matched the model.
+    pub fn self_test_crash_consistency(config: CrashConfig) -> Result<VerificationResult> {
+        let scratch = tempfile::tempdir().map_err(|e| Error::Internal {
+            reason: format!("Failed to create self-test directory: {}", e),
+        })?;
+        let live_dir = scratch.path().join("live");
+        let db = Strata::open_with(&live_dir, OpenOptions::new().durability("always"))?;
+
+        let mut model = ReferenceModel::new();
+        let mut rng = SEED;
+        let mut crash_count = 0usize;
+
+        for op in 0..config.max_operations {
+            let crash_point = if next_unit(&mut rng) < config.crash_probability {
+                Some(if next_u64(&mut rng) % 2 == 0 {
+                    CrashPoint::BeforeWalWrite
+                } else {
+                    CrashPoint::AfterFsync
+                })
+            } else {
+                None
+            };
+
+            if crash_point == Some(CrashPoint::BeforeWalWrite) {
+                let result = crash_and_verify(&live_dir, scratch.path(), crash_count, &model)?;
+                crash_count += 1;
+                if !result.is_valid {
+                    return Ok(result);
+                }
+            }
+
+            apply_operation(&db, &mut model, op)?;
+
+            if crash_point == Some(CrashPoint::AfterFsync) {
+                let result = crash_and_verify(&live_dir, scratch.path(), crash_count, &model)?;
+                crash_count += 1;
+                if !result.is_valid {
+                    return Ok(result);
+                }
+            }
+        }
+
+        crash_and_verify(&live_dir, scratch.path(), crash_count, &model)
+    }
+}
+
+/// Run operation number `op` of the scripted workload and record it in the model.
+fn apply_operation(db: &Strata, model: &mut ReferenceModel, op: usize) -> Result<()> {
+    let key = format!("key-{}", op % KEY_SPACE);
+    let value = format!("value-{}", op).into_bytes();
+    match op % 4 {
+        0 | 1 => {
+            db.kv_put(&key, Value::Bytes(value.clone()))?;
+            model.kv_put(BRANCH, &key, value);
+        }
+        2 => {
+            if op % 8 == 2 {
+                db.kv_delete(&key)?;
+                model.kv_delete(BRANCH, &key);
+            } else {
+                db.state_set(&key, Value::Bytes(value.clone()))?;
+                model.state_set(BRANCH, &key, value);
+            }
+        }
+        _ => {
+            let payload = Value::Object(HashMap::from([("op".to_string(), Value::Int(op as i64))]));
+            model.event_append(BRANCH, encode_model_value(&payload));
+            db.event_append(MODEL_EVENT_TYPE, payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// Simulate a crash by copying the live data directory, then recover the copy
+/// and compare it against the model.
+fn crash_and_verify(
+    live_dir: &Path,
+    scratch: &Path,
+    crash: usize,
+    model: &ReferenceModel,
+) -> Result<VerificationResult> {
+    let crashed_dir = scratch.join(format!("crash-{}", crash));
+    copy_dir(live_dir, &crashed_dir).map_err(|e| Error::Internal {
+        reason: format!("Failed to snapshot data directory: {}", e),
+    })?;
+
+    let recovered = match Strata::open_with(&crashed_dir, OpenOptions::new().durability("always")) {
+        Ok(db) => db,
+        Err(e) => return Ok(VerificationResult::error(format!("Recovery failed: {}", e))),
+    };
+    // Every operation in the model was fsynced before the crash, so all of
+    // it must be present after recovery.
+    let result = recovered.compare_with_model(model);
+    drop(recovered);
+    let _ = std::fs::remove_dir_all(&crashed_dir);
+    result
+}
+
+/// Recursively copy a data directory, skipping the process lock file.
+fn copy_dir(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == ".lock" {
+            continue;
+        }
+        let target = dst.join(&name);
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// xorshift64 step; deterministic and dependency-free.
+fn next_u64(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Uniform sample in `[0, 1)`.
+fn next_unit(state: &mut u64) -> f64 {
+    (next_u64(state) >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -1,8 +1,12 @@
 //! State cell operations.
 
 use super::Strata;
+use crate::types::VersionedValue;
 use crate::{Command, Error, Output, Result, Value};
 
+/// Retries [`Strata::state_update`] makes after a conflicting write.
+pub const DEFAULT_STATE_UPDATE_RETRIES: usize = 16;
+
 impl Strata {
     // =========================================================================
     // State Operations
@@ -78,6 +82,97 @@ impl Strata {
         }
     }
 
+    /// Read-modify-write a state cell.
+    ///
+    /// Reads the cell, passes its current value (`None` if it doesn't exist)
+    /// to `f`, and writes the result with [`state_cas`](Self::state_cas)
+    /// against the counter that was read. If another writer got there
+    /// first, the read and `f` are repeated, up to
+    /// [`DEFAULT_STATE_UPDATE_RETRIES`] times, so `f` should have no side
+    /// effects. Returns the committed value and its counter.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let hits = db.state_update("hits", |current| match current {
+    ///     Some(Value::Int(n)) => Value::Int(n + 1),
+    ///     _ => Value::Int(1),
+    /// })?;
+    /// ```
+    pub fn state_update<F>(&self, cell: &str, f: F) -> Result<VersionedValue>
+    where
+        F: FnMut(Option<Value>) -> Value,
+    {
+        self.state_update_with_retries(cell, DEFAULT_STATE_UPDATE_RETRIES, f)
+    }
+
+    /// [`state_update`](Self::state_update) with an explicit retry bound.
+    ///
+    /// `f` runs at most `max_retries + 1` times. Fails with
+    /// [`Error::Conflict`] if every attempt loses to a concurrent write.
+    pub fn state_update_with_retries<F>(
+        &self,
+        cell: &str,
+        max_retries: usize,
+        mut f: F,
+    ) -> Result<VersionedValue>
+    where
+        F: FnMut(Option<Value>) -> Value,
+    {
+        for _ in 0..=max_retries {
+            let current = self.state_get_versioned(cell)?;
+            let expected = current.as_ref().map(|vv| vv.version);
+            let value = f(current.map(|vv| vv.value));
+            if let Some(version) = self.state_cas(cell, expected, value.clone())? {
+                let timestamp = self.state_timestamp(cell, version)?;
+                return Ok(VersionedValue {
+                    value,
+                    version,
+                    timestamp,
+                });
+            }
+        }
+        Err(Error::Conflict {
+            reason: format!(
+                "State cell '{}' changed concurrently on {} attempts",
+                cell,
+                max_retries + 1
+            ),
+        })
+    }
+
+    fn state_get_versioned(&self, cell: &str) -> Result<Option<VersionedValue>> {
+        match self.executor.execute(Command::StateGet {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            cell: cell.to_string(),
+            as_of: None,
+        })? {
+            Output::MaybeVersioned(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for StateGet".into(),
+            }),
+        }
+    }
+
+    /// Write time of counter `version` of `cell`.
+    fn state_timestamp(&self, cell: &str, version: u64) -> Result<u64> {
+        if let Some(vv) = self.state_get_versioned(cell)? {
+            if vv.version == version {
+                return Ok(vv.timestamp);
+            }
+        }
+        // Overwritten already; look further back.
+        self.state_getv(cell)?
+            .into_iter()
+            .flatten()
+            .find(|vv| vv.version == version)
+            .map(|vv| vv.timestamp)
+            .ok_or_else(|| Error::Internal {
+                reason: format!("Committed version {} of '{}' not found", version, cell),
+            })
+    }
+
     /// Initialize a state cell (only if it doesn't exist).
     pub fn state_init(&self, cell: &str, value: impl Into<Value>) -> Result<u64> {
         match self.executor.execute(Command::StateInit {
@@ -0,0 +1,218 @@
+//! Structured reasoning traces.
+//!
+//! A trace is a typed span with an optional parent, used to record how an
+//! agent reached a result: thoughts, tool calls, their results and the
+//! decisions made from them. Traces are stored as ordinary events in the
+//! current branch and space, so they share the event log's hash chain,
+//! branching and durability.
+//!
+//! Each trace is one event of type [`TRACE_EVENT_TYPE`]. Its payload is
+//!
+//! `{"trace_type": <name>, "parent": <sequence or null>, "payload": <value>}`
+//!
+//! and its id is the event's sequence number. A parent always has a lower
+//! sequence than its children, since it must exist when a child is appended.
+//! Trace events count towards [`Strata::event_len`] and are returned by
+//! [`Strata::event_get_by_type`] like any other event.
+
+use std::collections::HashMap;
+
+use super::Strata;
+use crate::{Error, Result, Value};
+
+/// Event type under which traces are stored.
+pub const TRACE_EVENT_TYPE: &str = "_strata/trace";
+
+/// Kind of step a trace records.
+///
+/// Stored by name; a [`Custom`](TraceType::Custom) name equal to one of the
+/// built-in names reads back as that built-in variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TraceType {
+    /// Intermediate reasoning.
+    Thought,
+    /// Invocation of a tool.
+    ToolCall,
+    /// Output returned by a tool.
+    ToolResult,
+    /// A choice between alternatives.
+    Decision,
+    /// A failure.
+    Error,
+    /// Any other step, identified by name.
+    Custom(String),
+}
+
+impl TraceType {
+    /// Name under which this type is stored.
+    pub fn as_str(&self) -> &str {
+        match self {
+            TraceType::Thought => "thought",
+            TraceType::ToolCall => "tool_call",
+            TraceType::ToolResult => "tool_result",
+            TraceType::Decision => "decision",
+            TraceType::Error => "error",
+            TraceType::Custom(name) => name,
+        }
+    }
+
+    /// Parse a stored type name.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "thought" => TraceType::Thought,
+            "tool_call" => TraceType::ToolCall,
+            "tool_result" => TraceType::ToolResult,
+            "decision" => TraceType::Decision,
+            "error" => TraceType::Error,
+            other => TraceType::Custom(other.to_string()),
+        }
+    }
+}
+
+/// One stored trace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    /// Trace id: the sequence number of the underlying event.
+    pub id: u64,
+    /// Id of the parent trace, or `None` for a root.
+    pub parent: Option<u64>,
+    /// Kind of step.
+    pub trace_type: TraceType,
+    /// User data attached to the trace.
+    pub payload: Value,
+    /// Append time in microseconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl Trace {
+    fn to_event_payload(trace_type: &TraceType, parent: Option<u64>, payload: Value) -> Value {
+        Value::Object(
+            [
+                (
+                    "trace_type".to_string(),
+                    Value::String(trace_type.as_str().to_string()),
+                ),
+                (
+                    "parent".to_string(),
+                    parent.map_or(Value::Null, |p| Value::Int(p as i64)),
+                ),
+                ("payload".to_string(), payload),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+
+    /// Decode a trace event payload, or `None` if it is not one.
+    fn from_event(id: u64, timestamp: u64, value: Value) -> Option<Self> {
+        let Value::Object(mut map) = value else {
+            return None;
+        };
+        let trace_type = match map.remove("trace_type")? {
+            Value::String(name) => TraceType::from_name(&name),
+            _ => return None,
+        };
+        let parent = match map.remove("parent")? {
+            Value::Null => None,
+            Value::Int(p) if p >= 0 => Some(p as u64),
+            _ => return None,
+        };
+        Some(Trace {
+            id,
+            parent,
+            trace_type,
+            payload: map.remove("payload")?,
+            timestamp,
+        })
+    }
+}
+
+/// A trace together with all of its descendants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceTree {
+    /// The trace at this node.
+    pub trace: Trace,
+    /// Direct children, in append order.
+    pub children: Vec<TraceTree>,
+}
+
+impl Strata {
+    // =========================================================================
+    // Trace Operations
+    // =========================================================================
+
+    /// Append a trace, optionally as a child of an existing trace.
+    ///
+    /// `payload` may be any value. Returns the stored trace; its `id` is
+    /// what later traces pass as `parent`. Fails with
+    /// [`Error::InvalidInput`] if `parent` is not a trace in the current
+    /// branch and space.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let root = db.trace_append(None, TraceType::Thought, "plan the query".into())?;
+    /// db.trace_append(Some(root.id), TraceType::ToolCall, "search(\"rust\")".into())?;
+    /// ```
+    pub fn trace_append(
+        &self,
+        parent: Option<u64>,
+        trace_type: TraceType,
+        payload: Value,
+    ) -> Result<Trace> {
+        if let Some(parent) = parent {
+            let is_trace = match self.event_get(parent)? {
+                Some(event) => Trace::from_event(parent, event.timestamp, event.value).is_some(),
+                None => false,
+            };
+            if !is_trace {
+                return Err(Error::InvalidInput {
+                    reason: format!("Parent trace {} not found", parent),
+                });
+            }
+        }
+        let event = self.event_append(
+            TRACE_EVENT_TYPE,
+            Trace::to_event_payload(&trace_type, parent, payload.clone()),
+        )?;
+        Ok(Trace {
+            id: event.sequence,
+            parent,
+            trace_type,
+            payload,
+            timestamp: event.timestamp,
+        })
+    }
+
+    /// Get the trace `root` and all of its descendants.
+    ///
+    /// Returns `None` if `root` is not a trace. Reads every trace in the
+    /// current branch and space.
+    pub fn trace_tree(&self, root: u64) -> Result<Option<TraceTree>> {
+        let mut traces: Vec<Trace> = self
+            .event_get_by_type(TRACE_EVENT_TYPE)?
+            .into_iter()
+            .filter(|e| e.version >= root)
+            .filter_map(|e| Trace::from_event(e.version, e.timestamp, e.value))
+            .collect();
+        traces.sort_by_key(|t| t.id);
+
+        // Children always follow their parent, so building nodes from the
+        // newest trace backwards finishes every subtree before its parent.
+        let mut pending: HashMap<u64, Vec<TraceTree>> = HashMap::new();
+        for trace in traces.into_iter().rev() {
+            let mut children = pending.remove(&trace.id).unwrap_or_default();
+            children.reverse();
+            if trace.id == root {
+                return Ok(Some(TraceTree { trace, children }));
+            }
+            if let Some(parent) = trace.parent {
+                pending
+                    .entry(parent)
+                    .or_default()
+                    .push(TraceTree { trace, children });
+            }
+        }
+        Ok(None)
+    }
+}
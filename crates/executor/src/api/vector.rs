@@ -2,9 +2,14 @@
 //!
 //! MVP: upsert, get, delete, search, create_collection, delete_collection, list_collections
 
+use std::collections::BTreeMap;
+
 use super::Strata;
+use crate::bridge::{
+    serde_json_to_value_public, to_core_branch_id, validate_not_internal_collection,
+};
 use crate::types::*;
-use crate::{Command, Error, Output, Result, Value};
+use crate::{AccessMode, Command, Error, Output, Result, Value};
 
 impl Strata {
     // =========================================================================
@@ -17,6 +22,74 @@ impl Strata {
         collection: &str,
         dimension: u64,
         metric: DistanceMetric,
+    ) -> Result<u64> {
+        self.vector_create_collection_inner(collection, dimension, metric, None)
+    }
+
+    /// Create a vector collection whose search filters are checked against
+    /// declared metadata fields.
+    ///
+    /// A filter on an undeclared field fails with
+    /// [`Error::UnknownFilterField`], and a filter whose operator or value
+    /// does not fit the declared type fails with [`Error::InvalidInput`].
+    /// Vectors may still store undeclared metadata fields; they just cannot
+    /// be filtered on.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let schema = BTreeMap::from([("category".to_string(), MetadataFieldType::String)]);
+    /// db.vector_create_collection_with_schema("docs", 384, DistanceMetric::Cosine, schema)?;
+    /// ```
+    pub fn vector_create_collection_with_schema(
+        &self,
+        collection: &str,
+        dimension: u64,
+        metric: DistanceMetric,
+        metadata_schema: BTreeMap<String, MetadataFieldType>,
+    ) -> Result<u64> {
+        self.vector_create_collection_inner(collection, dimension, metric, Some(metadata_schema))
+    }
+
+    /// Create a vector collection whose dimension is set by its first insert.
+    ///
+    /// The first successful upsert locks the collection to the length of
+    /// its embedding, and the dimension is persisted with the collection.
+    /// After that, embeddings of any other length fail with
+    /// [`Error::DimensionMismatch`]. Until the first insert the collection
+    /// reports dimension 0 and searches return no matches.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.vector_create_collection_auto("docs", DistanceMetric::Cosine)?;
+    /// db.vector_upsert("docs", "a", model.embed("hello")?, None)?; // locks the dimension
+    /// assert_eq!(db.vector_collection_stats("docs")?.dimension, 384);
+    /// ```
+    pub fn vector_create_collection_auto(
+        &self,
+        collection: &str,
+        metric: DistanceMetric,
+    ) -> Result<u64> {
+        match self.executor.execute(Command::VectorCreateCollectionAuto {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            collection: collection.to_string(),
+            metric,
+        })? {
+            Output::Version(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for VectorCreateCollectionAuto".into(),
+            }),
+        }
+    }
+
+    fn vector_create_collection_inner(
+        &self,
+        collection: &str,
+        dimension: u64,
+        metric: DistanceMetric,
+        metadata_schema: Option<BTreeMap<String, MetadataFieldType>>,
     ) -> Result<u64> {
         match self.executor.execute(Command::VectorCreateCollection {
             branch: self.branch_id(),
@@ -24,6 +97,7 @@ impl Strata {
             collection: collection.to_string(),
             dimension,
             metric,
+            metadata_schema,
         })? {
             Output::Version(v) => Ok(v),
             _ => Err(Error::Internal {
@@ -60,6 +134,10 @@ impl Strata {
     }
 
     /// Upsert a vector.
+    ///
+    /// An existing vector under `key` is replaced in place. Use
+    /// [`vector_upsert_with_mode`](Self::vector_upsert_with_mode) to reject
+    /// duplicates or to learn whether the key was new.
     pub fn vector_upsert(
         &self,
         collection: &str,
@@ -67,6 +145,105 @@ impl Strata {
         vector: Vec<f32>,
         metadata: Option<Value>,
     ) -> Result<u64> {
+        self.vector_upsert_with_mode(collection, key, vector, metadata, UpsertMode::Replace)
+            .map(|result| result.version)
+    }
+
+    /// Embed `text` and upsert the vector under `key`.
+    ///
+    /// The text is embedded with the configured embedding model (see
+    /// [`embed_text`](Self::embed_text)) as a task on the background
+    /// scheduler, at the priority given by [`SetOptions::priority`]. Use
+    /// `TaskPriority::High` for interactive writes so they run ahead of
+    /// queued auto-embed batches and [`vector_reembed`](Self::vector_reembed)
+    /// backfills; those lower priorities are never starved outright (see
+    /// [`SetOptions::priority`]). The call waits for the embedding and
+    /// returns the version of the write, like [`vector_upsert`](Self::vector_upsert).
+    ///
+    /// Returns `ModelUnavailable` if the model cannot be loaded and
+    /// `NotImplemented` without the `embed` feature.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let urgent = SetOptions::new().priority(TaskPriority::High);
+    /// db.vector_upsert_text("docs", "q1", "user question", None, urgent)?;
+    /// ```
+    pub fn vector_upsert_text(
+        &self,
+        collection: &str,
+        key: &str,
+        text: &str,
+        metadata: Option<Value>,
+        options: SetOptions,
+    ) -> Result<u64> {
+        #[cfg(feature = "embed")]
+        {
+            self.executor.check_open()?;
+            let p = self.executor.primitives();
+            let model = strata_intelligence::embed::load_model(&p.db).map_err(|e| {
+                Error::ModelUnavailable {
+                    reason: format!("{}; run `strata setup` to download the default model", e),
+                }
+            })?;
+            let p_task = std::sync::Arc::clone(p);
+            self.upsert_text_with(collection, key, text, metadata, options, move |text| {
+                crate::handlers::embed_hook::embed_batch_locked(&p_task, &model, &[text])
+                    .map_err(|reason| Error::Internal { reason })?
+                    .pop()
+                    .ok_or_else(|| Error::Internal {
+                        reason: "embedding returned no vector".into(),
+                    })
+            })
+        }
+        #[cfg(not(feature = "embed"))]
+        {
+            let _ = (collection, key, text, metadata, options);
+            Err(Error::NotImplemented {
+                feature: "embed".into(),
+                reason: "text embedding requires building with the 'embed' feature".into(),
+            })
+        }
+    }
+
+    /// [`vector_upsert_text`](Self::vector_upsert_text) with the embedding
+    /// function supplied by the caller. `embed` runs on the background
+    /// scheduler at the options' priority.
+    #[cfg(any(feature = "embed", test))]
+    pub(crate) fn upsert_text_with(
+        &self,
+        collection: &str,
+        key: &str,
+        text: &str,
+        metadata: Option<Value>,
+        options: SetOptions,
+        embed: impl FnOnce(String) -> Result<Vec<f32>> + Send + 'static,
+    ) -> Result<u64> {
+        let text = text.to_string();
+        let vector = crate::handlers::embed_hook::run_blocking(
+            self.executor.primitives(),
+            options.embed_priority(),
+            move || embed(text),
+        )
+        .map_err(|reason| Error::Internal { reason })??;
+        self.vector_upsert(collection, key, vector, metadata)
+    }
+
+    /// Upsert a vector, choosing what happens if `key` already exists.
+    ///
+    /// [`UpsertMode::Replace`] overwrites the embedding and metadata but
+    /// keeps the vector's internal id, so its index entry is updated rather
+    /// than re-added. [`UpsertMode::Error`] fails with
+    /// [`Error::ConstraintViolation`] and leaves the existing vector as it
+    /// was. The result's `created` flag says whether the key was new.
+    pub fn vector_upsert_with_mode(
+        &self,
+        collection: &str,
+        key: &str,
+        vector: Vec<f32>,
+        metadata: Option<Value>,
+        mode: UpsertMode,
+    ) -> Result<VectorUpsertResult> {
         match self.executor.execute(Command::VectorUpsert {
             branch: self.branch_id(),
             space: self.space_id(),
@@ -74,14 +251,50 @@ impl Strata {
             key: key.to_string(),
             vector,
             metadata,
+            mode: Some(mode),
         })? {
-            Output::Version(v) => Ok(v),
+            Output::VectorUpserted(result) => Ok(result),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for VectorUpsert".into(),
             }),
         }
     }
 
+    /// Upsert a vector unless a near-duplicate already exists.
+    ///
+    /// Searches the collection for the nearest existing vector and skips the
+    /// write if it is closer than `min_distance`, so repeated similar
+    /// observations do not bloat the index. Distance follows the collection
+    /// metric: `1 - cosine similarity` for cosine, L2 for euclidean, L1 for
+    /// manhattan and the negated dot product for dot-product collections.
+    ///
+    /// The result says whether the vector was written; when it was not,
+    /// `key` names the existing near-duplicate. The search and the write
+    /// happen atomically with respect to other writers of the collection.
+    pub fn vector_upsert_if_novel(
+        &self,
+        collection: &str,
+        key: &str,
+        vector: Vec<f32>,
+        metadata: Option<Value>,
+        min_distance: f32,
+    ) -> Result<NovelUpsertResult> {
+        match self.executor.execute(Command::VectorUpsertIfNovel {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            collection: collection.to_string(),
+            key: key.to_string(),
+            vector,
+            metadata,
+            min_distance,
+        })? {
+            Output::NovelUpsert(result) => Ok(result),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for VectorUpsertIfNovel".into(),
+            }),
+        }
+    }
+
     /// Get a vector by key.
     pub fn vector_get(&self, collection: &str, key: &str) -> Result<Option<VersionedVectorData>> {
         match self.executor.execute(Command::VectorGet {
@@ -149,26 +362,339 @@ impl Strata {
     }
 
     /// Search for similar vectors.
+    ///
+    /// Matches carry key, score and stored metadata; use
+    /// [`vector_search_with`](Self::vector_search_with) to leave out the
+    /// metadata or also return the embedding.
+    ///
+    /// An existing collection with no vectors returns an empty list; a
+    /// collection that does not exist returns [`Error::CollectionNotFound`].
     pub fn vector_search(
         &self,
         collection: &str,
         query: Vec<f32>,
         k: u64,
     ) -> Result<Vec<VectorMatch>> {
-        match self.executor.execute(Command::VectorSearch {
-            branch: self.branch_id(),
-            space: self.space_id(),
-            collection: collection.to_string(),
-            query,
-            k,
-            filter: None,
-            metric: None,
-            as_of: None,
-        })? {
+        self.vector_search_with(collection, query, k, true, false)
+    }
+
+    /// Search for similar vectors, optionally returning each match's
+    /// metadata and embedding inline.
+    pub fn vector_search_with(
+        &self,
+        collection: &str,
+        query: Vec<f32>,
+        k: u64,
+        include_metadata: bool,
+        include_vector: bool,
+    ) -> Result<Vec<VectorMatch>> {
+        match self
+            .executor
+            .execute(Command::VectorSearch {
+                branch: self.branch_id(),
+                space: self.space_id(),
+                collection: collection.to_string(),
+                query,
+                k,
+                filter: None,
+                metric: None,
+                as_of: None,
+                overfetch_factor: None,
+                include_metadata: Some(include_metadata),
+                include_vector,
+            })?
+            .untruncated()
+        {
             Output::VectorMatches(matches) => Ok(matches),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for VectorSearch".into(),
             }),
         }
     }
+
+    /// Search for vectors similar to a text query.
+    ///
+    /// The text is embedded with the configured embedding model (see
+    /// [`embed_text`](Self::embed_text)), so the collection must hold
+    /// embeddings from the same model; a collection of another dimension
+    /// returns `DimensionMismatch`.
+    pub fn vector_search_text(
+        &self,
+        collection: &str,
+        text: &str,
+        k: u64,
+    ) -> Result<Vec<VectorMatch>> {
+        let query = self.embed_text(text)?;
+        self.vector_search(collection, query, k)
+    }
+
+    /// Search every collection in the current branch and space at once.
+    ///
+    /// Collections whose dimension differs from the query's are skipped.
+    /// Each remaining collection is searched for its top `k` matches, and the
+    /// per-collection rankings are merged with Reciprocal Rank Fusion (k=60),
+    /// since similarity scores from different metrics are not comparable.
+    /// Returns at most `k` matches, best first, each tagged with its
+    /// collection.
+    pub fn vector_search_all(
+        &self,
+        query: Vec<f32>,
+        k: u64,
+        filter: Option<Vec<MetadataFilter>>,
+    ) -> Result<Vec<CollectionVectorMatch>> {
+        const K_RRF: f32 = 60.0;
+
+        let mut fused = Vec::new();
+        for info in self.vector_list_collections()? {
+            if info.dimension != query.len() {
+                continue;
+            }
+            let matches = match self
+                .executor
+                .execute(Command::VectorSearch {
+                    branch: self.branch_id(),
+                    space: self.space_id(),
+                    collection: info.name.clone(),
+                    query: query.clone(),
+                    k,
+                    filter: filter.clone(),
+                    metric: None,
+                    as_of: None,
+                    overfetch_factor: None,
+                    include_metadata: None,
+                    include_vector: false,
+                })?
+                .untruncated()
+            {
+                Output::VectorMatches(matches) => matches,
+                _ => {
+                    return Err(Error::Internal {
+                        reason: "Unexpected output for VectorSearch".into(),
+                    })
+                }
+            };
+            fused.extend(matches.into_iter().enumerate().map(|(rank, vector_match)| {
+                CollectionVectorMatch {
+                    collection: info.name.clone(),
+                    rrf_score: 1.0 / (K_RRF + (rank + 1) as f32),
+                    vector_match,
+                }
+            }));
+        }
+
+        // Equal ranks tie on RRF score; break ties by similarity, then
+        // collection and key so the order is deterministic.
+        fused.sort_by(|a, b| {
+            b.rrf_score
+                .total_cmp(&a.rrf_score)
+                .then_with(|| b.vector_match.score.total_cmp(&a.vector_match.score))
+                .then_with(|| a.collection.cmp(&b.collection))
+                .then_with(|| a.vector_match.key.cmp(&b.vector_match.key))
+        });
+        fused.truncate(k as usize);
+        Ok(fused)
+    }
+
+    /// Embed text with the configured embedding model (MiniLM-L6-v2 unless
+    /// `[embed_model]` in `strata.toml` says otherwise).
+    ///
+    /// Requires the `embed` feature and downloaded model files (`strata
+    /// setup`). Returns `ModelUnavailable` if the model cannot be loaded or
+    /// its dimension differs from `embed_model.dimension`, and
+    /// `NotImplemented` when the feature is not compiled in.
+    pub fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        #[cfg(feature = "embed")]
+        {
+            let model = strata_intelligence::embed::load_model(&self.executor.primitives().db)
+                .map_err(|e| Error::ModelUnavailable {
+                    reason: format!("{}; run `strata setup` to download the default model", e),
+                })?;
+            Ok(model.embed(text))
+        }
+        #[cfg(not(feature = "embed"))]
+        {
+            let _ = text;
+            Err(Error::NotImplemented {
+                feature: "embed".into(),
+                reason: "text embedding requires building with the 'embed' feature".into(),
+            })
+        }
+    }
+
+    /// Iterate over every vector in a collection.
+    ///
+    /// Yields `(key, embedding, metadata)` in insertion (`VectorId`) order
+    /// from a single read snapshot; deleted vectors are skipped and writes
+    /// made after the call are not observed. Only the keys are indexed when
+    /// the scan starts; each record is read and converted as the iterator
+    /// advances, so this suits bulk export and re-embedding of large
+    /// collections.
+    pub fn vector_scan(
+        &self,
+        collection: &str,
+    ) -> Result<impl Iterator<Item = Result<ScannedVector>>> {
+        let branch_id = to_core_branch_id(&self.current_branch)?;
+        validate_not_internal_collection(collection).map_err(Error::from)?;
+
+        let scan = self
+            .executor
+            .primitives()
+            .vector
+            .scan(branch_id, &self.current_space, collection)
+            .map_err(|e| Error::from(e.into_strata_error(branch_id)))?;
+
+        Ok(scan.map(move |entry| {
+            let entry = entry.map_err(|e| Error::from(e.into_strata_error(branch_id)))?;
+            let metadata = entry
+                .metadata
+                .map(serde_json_to_value_public)
+                .transpose()
+                .map_err(Error::from)?;
+            Ok((entry.key, entry.embedding, metadata))
+        }))
+    }
+
+    /// Re-embed every vector in a collection from text in its metadata.
+    ///
+    /// Use this after switching embedding models. For each vector, the
+    /// string stored under `text_field` in its metadata is embedded with the
+    /// current model (as by [`embed_text`](Self::embed_text)) and upserted
+    /// under the same key with its metadata unchanged. Vectors without a
+    /// string at `text_field` keep their old embedding and are listed in
+    /// [`ReembedStats::skipped`]. Texts are embedded in batches of
+    /// `embed_batch_size` as `Low` priority tasks on the background
+    /// scheduler, so interactive embeddings (see
+    /// [`vector_upsert_text`](Self::vector_upsert_text)) can run ahead of
+    /// them. Once every batch is written the collection's HNSW graph is
+    /// rebuilt from scratch.
+    ///
+    /// Fails with `DimensionMismatch` before writing anything if the model's
+    /// dimension differs from the collection's; a model with a new dimension
+    /// needs a new collection. Returns `ModelUnavailable` if the model
+    /// cannot be loaded, `NotImplemented` without the `embed` feature and
+    /// `AccessDenied` on a read-only handle. If a batch fails, the batches
+    /// before it stay re-embedded.
+    pub fn vector_reembed(&self, collection: &str, text_field: &str) -> Result<ReembedStats> {
+        self.executor.check_open()?;
+        if self.access_mode == AccessMode::ReadOnly {
+            return Err(Error::AccessDenied {
+                command: "VectorReembed".to_string(),
+            });
+        }
+        #[cfg(feature = "embed")]
+        {
+            let p = self.executor.primitives();
+            let model = strata_intelligence::embed::load_model(&p.db).map_err(|e| {
+                Error::ModelUnavailable {
+                    reason: format!("{}; run `strata setup` to download the default model", e),
+                }
+            })?;
+            let dimension = model.dimension();
+            self.reembed_with(collection, text_field, dimension, |texts| {
+                crate::handlers::embed_hook::embed_batch_blocking(
+                    p,
+                    std::sync::Arc::clone(&model),
+                    texts,
+                    strata_engine::TaskPriority::Low,
+                )
+                .map_err(|reason| Error::Internal { reason })
+            })
+        }
+        #[cfg(not(feature = "embed"))]
+        {
+            let _ = (collection, text_field);
+            Err(Error::NotImplemented {
+                feature: "embed".into(),
+                reason: "re-embedding requires building with the 'embed' feature".into(),
+            })
+        }
+    }
+
+    /// [`vector_reembed`](Self::vector_reembed) with the embedding function
+    /// supplied by the caller, producing `dimension`-length vectors.
+    #[cfg(any(feature = "embed", test))]
+    pub(crate) fn reembed_with(
+        &self,
+        collection: &str,
+        text_field: &str,
+        dimension: usize,
+        mut embed: impl FnMut(Vec<String>) -> Result<Vec<Vec<f32>>>,
+    ) -> Result<ReembedStats> {
+        let info = self.vector_collection_stats(collection)?;
+        if info.dimension != dimension {
+            return Err(Error::DimensionMismatch {
+                expected: info.dimension,
+                actual: dimension,
+            });
+        }
+
+        let batch_size = self.executor.primitives().db.embed_batch_size().max(1);
+        let mut stats = ReembedStats::default();
+        let mut batch = Vec::with_capacity(batch_size);
+        for entry in self.vector_scan(collection)? {
+            let (key, _, metadata) = entry?;
+            let text = metadata
+                .as_ref()
+                .and_then(Value::as_object)
+                .and_then(|fields| fields.get(text_field))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            match text {
+                Some(text) => batch.push((key, metadata, text)),
+                None => stats.skipped.push(key),
+            }
+            if batch.len() >= batch_size {
+                self.reembed_batch(collection, std::mem::take(&mut batch), &mut embed)?;
+                stats.reembedded += batch_size as u64;
+            }
+        }
+        if !batch.is_empty() {
+            stats.reembedded += batch.len() as u64;
+            self.reembed_batch(collection, batch, &mut embed)?;
+        }
+
+        if stats.reembedded > 0 {
+            let branch_id = to_core_branch_id(&self.current_branch)?;
+            self.executor
+                .primitives()
+                .vector
+                .rebuild_collection(branch_id, collection)
+                .map_err(|e| Error::from(e.into_strata_error(branch_id)))?;
+        }
+        Ok(stats)
+    }
+
+    #[cfg(any(feature = "embed", test))]
+    fn reembed_batch(
+        &self,
+        collection: &str,
+        batch: Vec<(String, Option<Value>, String)>,
+        embed: &mut impl FnMut(Vec<String>) -> Result<Vec<Vec<f32>>>,
+    ) -> Result<()> {
+        let (entries, texts): (Vec<_>, Vec<_>) = batch
+            .into_iter()
+            .map(|(key, metadata, text)| ((key, metadata), text))
+            .unzip();
+        let vectors = embed(texts)?;
+        if vectors.len() != entries.len() {
+            return Err(Error::Internal {
+                reason: format!(
+                    "embedding returned {} vectors for {} texts",
+                    vectors.len(),
+                    entries.len()
+                ),
+            });
+        }
+        let entries = entries
+            .into_iter()
+            .zip(vectors)
+            .map(|((key, metadata), vector)| BatchVectorEntry {
+                key,
+                vector,
+                metadata,
+            })
+            .collect();
+        self.vector_batch_upsert(collection, entries)?;
+        Ok(())
+    }
 }
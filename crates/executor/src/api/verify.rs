@@ -0,0 +1,117 @@
+//! Reference-model verification (requires the `testing` feature).
+//!
+//! Lets users replay a recorded list of [`Operation`]s into a
+//! [`ReferenceModel`] and check that the database holds exactly that state.
+//! The model stores values as bytes; [`encode_model_value`] defines how a
+//! [`Value`] maps to those bytes.
+
+use std::collections::{BTreeSet, HashMap};
+
+use strata_durability::testing::{Operation, ReferenceModel, StateMismatch, VerificationResult};
+
+use super::Strata;
+use crate::json::value_to_json;
+use crate::types::BranchId;
+use crate::{Result, Value};
+
+/// Event type whose payloads are compared against the model's events.
+///
+/// `Operation::EventAppend` carries no event type, so only events appended
+/// under this type take part in verification.
+pub const MODEL_EVENT_TYPE: &str = "model";
+
+/// Encode a value the way [`Strata::verify_against_model`] compares it.
+///
+/// `Bytes` are used as-is; every other value is encoded as compact JSON
+/// (objects with sorted keys). Use this when recording `Operation`s so the
+/// model's bytes match what the database stores.
+pub fn encode_model_value(value: &Value) -> Vec<u8> {
+    match value {
+        Value::Bytes(bytes) => bytes.clone(),
+        other => value_to_json(other).to_string().into_bytes(),
+    }
+}
+
+impl Strata {
+    /// Check the database against a recorded workload.
+    ///
+    /// Replays `ops` into a fresh [`ReferenceModel`] and compares, for every
+    /// branch the operations touch, the KV store, the state cells written by
+    /// the operations, and the events of type [`MODEL_EVENT_TYPE`] with the
+    /// model. Reads use the current space. Values are compared in the
+    /// encoding produced by [`encode_model_value`].
+    ///
+    /// KV and event comparisons are exact, so unexpected keys or extra events
+    /// are reported. State cells not mentioned in `ops` are not checked.
+    pub fn verify_against_model(&self, ops: &[Operation]) -> Result<VerificationResult> {
+        let mut model = ReferenceModel::new();
+        for op in ops {
+            match op {
+                Operation::KvPut { branch, key, value } => model.kv_put(branch, key, value.clone()),
+                Operation::KvDelete { branch, key } => model.kv_delete(branch, key),
+                Operation::EventAppend { branch, payload } => {
+                    model.event_append(branch, payload.clone())
+                }
+                Operation::StateSet { branch, key, value } => {
+                    model.state_set(branch, key, value.clone())
+                }
+                Operation::Checkpoint => model.checkpoint(),
+            }
+        }
+        self.compare_with_model(&model)
+    }
+
+    /// Compare the database against an already-populated model.
+    pub(super) fn compare_with_model(&self, model: &ReferenceModel) -> Result<VerificationResult> {
+        let mut branches: BTreeSet<&str> = BTreeSet::new();
+        let mut state_cells: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for op in model.operations() {
+            match op {
+                Operation::KvPut { branch, .. }
+                | Operation::KvDelete { branch, .. }
+                | Operation::EventAppend { branch, .. } => {
+                    branches.insert(branch);
+                }
+                Operation::StateSet { branch, key, .. } => {
+                    branches.insert(branch);
+                    state_cells.entry(branch).or_default().insert(key);
+                }
+                Operation::Checkpoint => {}
+            }
+        }
+
+        let mut view = self.new_handle()?;
+        view.current_space = self.current_space.clone();
+
+        let mut mismatches: Vec<StateMismatch> = Vec::new();
+        for branch in branches {
+            let mut kv = HashMap::new();
+            let mut state = HashMap::new();
+            let mut events = Vec::new();
+
+            if self.branches().exists(branch)? {
+                view.current_branch = BranchId::from(branch);
+
+                for key in view.kv_list(None)? {
+                    if let Some(value) = view.kv_get(&key)? {
+                        kv.insert(key, encode_model_value(&value));
+                    }
+                }
+                for cell in state_cells.get(branch).into_iter().flatten() {
+                    if let Some(value) = view.state_get(cell)? {
+                        state.insert(cell.to_string(), encode_model_value(&value));
+                    }
+                }
+                for event in view.event_get_by_type(MODEL_EVENT_TYPE)? {
+                    events.push(encode_model_value(&event.value));
+                }
+            }
+
+            mismatches.extend(model.compare_kv(branch, &kv));
+            mismatches.extend(model.compare_state(branch, &state));
+            mismatches.extend(model.compare_events(branch, &events));
+        }
+
+        Ok(VerificationResult::mismatches(mismatches))
+    }
+}
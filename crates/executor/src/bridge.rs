@@ -6,13 +6,16 @@
 //! - [`Primitives`]: Holds all 6 engine primitives + database reference
 //! - [`to_core_branch_id`]: Converts executor's string-based BranchId to core BranchId
 //! - Validation helpers: Key, stream, event payload, collection name validation
+//! - [`normalize_command_keys`]: Applies the configured key normalization
+//! - [`apply_result_limits`]: Applies the configured result limits
 //! - Type conversion helpers: Value ↔ JsonValue, DistanceMetric, etc.
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use strata_core::limits::Limits;
 use strata_core::primitives::json::{JsonPath, JsonValue};
-use strata_core::{StrataError, StrataResult, Value};
+use strata_core::{KeyNormalization, StrataError, StrataResult, Value};
 use strata_engine::{
     BranchIndex as PrimitiveBranchIndex, Database, EventLog as PrimitiveEventLog,
     JsonStore as PrimitiveJsonStore, KVStore as PrimitiveKVStore,
@@ -21,6 +24,7 @@ use strata_engine::{
 };
 
 use crate::types::BranchId;
+use crate::{Command, Output};
 
 // =============================================================================
 // Primitives
@@ -108,6 +112,12 @@ pub fn to_core_branch_id(branch: &BranchId) -> crate::Result<strata_core::types:
 /// Reserved key prefix that users cannot use.
 const RESERVED_KEY_PREFIX: &str = "_strata/";
 
+/// Whether `key` lives under the reserved prefix, holding internal data
+/// such as blob chunks. Such keys are hidden from KV listings.
+pub(crate) fn is_reserved_key(key: &str) -> bool {
+    key.starts_with(RESERVED_KEY_PREFIX)
+}
+
 /// Validate a KV/JSON key.
 ///
 /// Keys must be non-empty, contain no NUL bytes, not start with `_strata/`,
@@ -136,11 +146,62 @@ pub fn validate_key_with_limits(key: &str, limits: &Limits) -> StrataResult<()>
     Ok(())
 }
 
+/// Validate a binary KV key.
+///
+/// Binary keys may hold any byte, including NUL, but must be non-empty,
+/// not start with `_strata/`, and not exceed the maximum key length.
+pub fn validate_key_bytes(key: &[u8], limits: &Limits) -> StrataResult<()> {
+    if key.is_empty() {
+        return Err(StrataError::invalid_input("Key must not be empty"));
+    }
+    if key.len() > limits.max_key_bytes {
+        return Err(StrataError::capacity_exceeded(
+            "key",
+            limits.max_key_bytes,
+            key.len(),
+        ));
+    }
+    if key.starts_with(RESERVED_KEY_PREFIX.as_bytes()) {
+        return Err(StrataError::invalid_input(format!(
+            "Key must not start with reserved prefix '{}'",
+            RESERVED_KEY_PREFIX
+        )));
+    }
+    Ok(())
+}
+
 /// Validate a value against size limits.
 pub fn validate_value(value: &Value, limits: &Limits) -> StrataResult<()> {
     limits.validate_value(value).map_err(limit_error_to_strata)
 }
 
+/// Validate a JSON document value against size limits.
+///
+/// The database's JSON nesting depth and array size limits take the place
+/// of the general ones, so documents follow the configured JSON limits.
+pub fn validate_json_value(value: &Value, p: &Primitives) -> StrataResult<()> {
+    let json = p.db.json_limits();
+    let limits = Limits {
+        max_nesting_depth: json.max_nesting_depth,
+        max_array_len: json.max_array_size,
+        ..p.limits.clone()
+    };
+    validate_value(value, &limits)
+}
+
+/// Validate a JSON path and, if given, a document value against the
+/// database's JSON document limits, as the JSON store does before writing.
+pub fn validate_json_limits(
+    p: &Primitives,
+    path: &JsonPath,
+    value: Option<&JsonValue>,
+) -> StrataResult<()> {
+    let limits = p.db.json_limits();
+    path.validate_with(&limits)
+        .and_then(|()| value.map_or(Ok(()), |v| v.validate_with(&limits)))
+        .map_err(|e| StrataError::invalid_input(e.to_string()))
+}
+
 /// Validate a vector against dimension limits.
 pub fn validate_vector(vec: &[f32], limits: &Limits) -> StrataResult<()> {
     limits.validate_vector(vec).map_err(limit_error_to_strata)
@@ -166,11 +227,213 @@ pub fn validate_not_internal_collection(name: &str) -> StrataResult<()> {
     Ok(())
 }
 
+// =============================================================================
+// Key Normalization
+// =============================================================================
+
+/// Canonicalize `key` in place under `mode`.
+pub(crate) fn normalize_key(key: &mut String, mode: KeyNormalization) {
+    if let Cow::Owned(normalized) = mode.apply(key) {
+        *key = normalized;
+    }
+}
+
+/// Canonicalize every user key, cell name and list prefix in `cmd`.
+///
+/// Runs before dispatch on both the direct and the transactional path, so
+/// writes and reads agree on the stored form. Binary keys are left as-is.
+pub fn normalize_command_keys(cmd: &mut Command, mode: KeyNormalization) {
+    if mode == KeyNormalization::Identity {
+        return;
+    }
+    match cmd {
+        Command::KvPut { key, .. }
+        | Command::KvGet { key, .. }
+        | Command::KvDelete { key, .. }
+        | Command::KvDeleteIfEquals { key, .. }
+        | Command::KvGetOrPut { key, .. }
+        | Command::KvGetv { key, .. }
+        | Command::JsonSet { key, .. }
+        | Command::JsonGet { key, .. }
+        | Command::JsonDelete { key, .. }
+        | Command::JsonGetv { key, .. }
+        | Command::VectorUpsert { key, .. }
+        | Command::VectorUpsertIfNovel { key, .. }
+        | Command::VectorGet { key, .. }
+        | Command::VectorDelete { key, .. } => normalize_key(key, mode),
+        Command::StateSet { cell, .. }
+        | Command::StateGet { cell, .. }
+        | Command::StateCas { cell, .. }
+        | Command::StateGetv { cell, .. }
+        | Command::StateInit { cell, .. }
+        | Command::StateDelete { cell, .. } => normalize_key(cell, mode),
+        Command::KvList { prefix, .. }
+        | Command::JsonList { prefix, .. }
+        | Command::StateList { prefix, .. } => {
+            if let Some(prefix) = prefix {
+                normalize_key(prefix, mode);
+            }
+        }
+        Command::KvGetMany { keys, .. } | Command::KvDeleteMany { keys, .. } => {
+            keys.iter_mut().for_each(|key| normalize_key(key, mode));
+        }
+        Command::KvBatchPut { entries, .. } | Command::KvPutMany { entries, .. } => {
+            for entry in entries {
+                normalize_key(&mut entry.key, mode);
+            }
+        }
+        Command::JsonBatchSet { entries, .. } => {
+            for entry in entries {
+                normalize_key(&mut entry.key, mode);
+            }
+        }
+        Command::StateBatchSet { entries, .. } => {
+            for entry in entries {
+                normalize_key(&mut entry.cell, mode);
+            }
+        }
+        Command::VectorBatchUpsert { entries, .. } => {
+            for entry in entries {
+                normalize_key(&mut entry.key, mode);
+            }
+        }
+        _ => {}
+    }
+}
+
+// =============================================================================
+// Result Limits
+// =============================================================================
+
+/// Result counts an output must be cut to, one per query (one for
+/// everything but `SearchBatch`). Produced by [`apply_result_limits`].
+#[derive(Debug, Default)]
+pub struct ResultCaps(Vec<Option<usize>>);
+
+/// Clamp the result count of a list or search command to `default` and
+/// `max` (`default_result_limit`, `max_result_limit`).
+///
+/// Runs before dispatch on both the direct and the transactional path. A
+/// lowered count asks for one result more than it allows, so that
+/// [`ResultCaps::apply`] can tell whether the limit dropped anything.
+pub fn apply_result_limits(
+    cmd: &mut Command,
+    (default, max): (Option<u64>, Option<u64>),
+) -> ResultCaps {
+    if default.is_none() && max.is_none() {
+        return ResultCaps::default();
+    }
+    let clamp = |requested: Option<u64>| match (requested, max) {
+        (Some(n), Some(max)) => Some(n.min(max)),
+        (n, None) => n,
+        (None, max) => max,
+    };
+    let cap = match cmd {
+        Command::KvList { limit, .. } => {
+            let allowed = clamp(limit.or(default));
+            if allowed.is_some() && allowed != *limit {
+                *limit = allowed.map(|n| n.saturating_add(1));
+                allowed
+            } else {
+                None
+            }
+        }
+        Command::VectorSearch { k, .. } => match max {
+            Some(max) if *k > max => {
+                *k = max.saturating_add(1);
+                Some(max)
+            }
+            _ => None,
+        },
+        Command::Search { search, .. } => limit_search_k(search, default, max),
+        Command::SearchBatch { searches, .. } => {
+            return ResultCaps(
+                searches
+                    .iter_mut()
+                    .map(|search| limit_search_k(search, default, max).map(|n| n as usize))
+                    .collect(),
+            );
+        }
+        _ => None,
+    };
+    ResultCaps(vec![cap.map(|n| n as usize)])
+}
+
+/// Fill in `default` for a search without `k` and clamp `k` to `max`.
+///
+/// A search without `k` or `default` keeps the engine's default of 10
+/// hits, which is cut to `max` afterwards.
+fn limit_search_k(
+    search: &mut crate::types::SearchQuery,
+    default: Option<u64>,
+    max: Option<u64>,
+) -> Option<u64> {
+    if search.k.is_none() {
+        search.k = default.map(|n| max.map_or(n, |max| n.min(max)));
+    }
+    match (search.k, max) {
+        (Some(k), Some(max)) if k > max => {
+            search.k = Some(max.saturating_add(1));
+            Some(max)
+        }
+        (None, max) => max,
+        _ => None,
+    }
+}
+
+impl ResultCaps {
+    /// Cut `output` to the caps, wrapping it in [`Output::Truncated`] if
+    /// any results were dropped.
+    pub fn apply(&self, output: Output) -> Output {
+        fn cut<T>(items: &mut Vec<T>, cap: Option<usize>) -> bool {
+            match cap {
+                Some(cap) if items.len() > cap => {
+                    items.truncate(cap);
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        let first = self.0.first().copied().flatten();
+        let (output, truncated) = match output {
+            Output::Keys(mut keys) => {
+                let truncated = cut(&mut keys, first);
+                (Output::Keys(keys), truncated)
+            }
+            Output::VectorMatches(mut matches) => {
+                let truncated = cut(&mut matches, first);
+                (Output::VectorMatches(matches), truncated)
+            }
+            Output::SearchResults(mut hits) => {
+                let truncated = cut(&mut hits, first);
+                (Output::SearchResults(hits), truncated)
+            }
+            Output::SearchBatchResults(mut results) => {
+                let mut truncated = false;
+                for (hits, cap) in results.iter_mut().zip(&self.0) {
+                    truncated |= cut(hits, *cap);
+                }
+                (Output::SearchBatchResults(results), truncated)
+            }
+            output => (output, false),
+        };
+        if truncated {
+            Output::Truncated(Box::new(output))
+        } else {
+            output
+        }
+    }
+}
+
 // =============================================================================
 // Type Conversion: Value ↔ JsonValue
 // =============================================================================
 
 /// Convert `strata_core::Value` to `JsonValue` for the JSON primitive.
+///
+/// `Int` becomes an integer JSON number and `Float` a float one, so
+/// [`json_to_value`] restores the same variant and every `i64` is exact.
 pub fn value_to_json(value: Value) -> StrataResult<JsonValue> {
     let json_val = value_to_serde_json(value)?;
     Ok(JsonValue::from(json_val))
@@ -320,6 +583,7 @@ pub fn to_engine_metric(metric: crate::types::DistanceMetric) -> strata_engine::
         crate::types::DistanceMetric::Cosine => strata_engine::DistanceMetric::Cosine,
         crate::types::DistanceMetric::Euclidean => strata_engine::DistanceMetric::Euclidean,
         crate::types::DistanceMetric::DotProduct => strata_engine::DistanceMetric::DotProduct,
+        crate::types::DistanceMetric::Manhattan => strata_engine::DistanceMetric::Manhattan,
     }
 }
 
@@ -329,6 +593,33 @@ pub fn from_engine_metric(metric: strata_engine::DistanceMetric) -> crate::types
         strata_engine::DistanceMetric::Cosine => crate::types::DistanceMetric::Cosine,
         strata_engine::DistanceMetric::Euclidean => crate::types::DistanceMetric::Euclidean,
         strata_engine::DistanceMetric::DotProduct => crate::types::DistanceMetric::DotProduct,
+        strata_engine::DistanceMetric::Manhattan => crate::types::DistanceMetric::Manhattan,
+    }
+}
+
+/// Convert an executor metadata schema to the engine schema.
+pub fn to_engine_metadata_schema(
+    fields: std::collections::BTreeMap<String, crate::types::MetadataFieldType>,
+) -> strata_engine::vector::MetadataSchema {
+    use strata_engine::vector::MetadataFieldType as Engine;
+    fields.into_iter().fold(
+        strata_engine::vector::MetadataSchema::new(),
+        |schema, (name, field_type)| {
+            let field_type = match field_type {
+                crate::types::MetadataFieldType::String => Engine::String,
+                crate::types::MetadataFieldType::Number => Engine::Number,
+                crate::types::MetadataFieldType::Bool => Engine::Bool,
+            };
+            schema.field(name, field_type)
+        },
+    )
+}
+
+/// Convert executor UpsertMode to engine UpsertMode.
+pub fn to_engine_upsert_mode(mode: crate::types::UpsertMode) -> strata_engine::vector::UpsertMode {
+    match mode {
+        crate::types::UpsertMode::Replace => strata_engine::vector::UpsertMode::Replace,
+        crate::types::UpsertMode::Error => strata_engine::vector::UpsertMode::Error,
     }
 }
 
@@ -347,30 +638,37 @@ pub fn to_engine_filter(
     let mut engine_filter = strata_engine::MetadataFilter::new();
 
     for f in filters {
-        let scalar = value_to_json_scalar(&f.value);
-        match f.op {
+        let engine_op = match f.op {
             crate::types::FilterOp::Eq => {
-                engine_filter.equals.insert(f.field.clone(), scalar);
-            }
-            _ => {
-                let engine_op = match f.op {
-                    crate::types::FilterOp::Eq => strata_engine::FilterOp::Eq,
-                    crate::types::FilterOp::Ne => strata_engine::FilterOp::Ne,
-                    crate::types::FilterOp::Gt => strata_engine::FilterOp::Gt,
-                    crate::types::FilterOp::Gte => strata_engine::FilterOp::Gte,
-                    crate::types::FilterOp::Lt => strata_engine::FilterOp::Lt,
-                    crate::types::FilterOp::Lte => strata_engine::FilterOp::Lte,
-                    crate::types::FilterOp::In => strata_engine::FilterOp::In,
-                    crate::types::FilterOp::Contains => strata_engine::FilterOp::Contains,
-                };
                 engine_filter
-                    .conditions
-                    .push(strata_engine::FilterCondition {
-                        field: f.field.clone(),
-                        op: engine_op,
-                        value: scalar,
-                    });
+                    .equals
+                    .insert(f.field.clone(), value_to_json_scalar(&f.value));
+                continue;
+            }
+            crate::types::FilterOp::Ne => strata_engine::FilterOp::Ne,
+            crate::types::FilterOp::Gt => strata_engine::FilterOp::Gt,
+            crate::types::FilterOp::Gte => strata_engine::FilterOp::Gte,
+            crate::types::FilterOp::Lt => strata_engine::FilterOp::Lt,
+            crate::types::FilterOp::Lte => strata_engine::FilterOp::Lte,
+            crate::types::FilterOp::In => strata_engine::FilterOp::In,
+            crate::types::FilterOp::Contains => strata_engine::FilterOp::Contains,
+            crate::types::FilterOp::NotIn => strata_engine::FilterOp::NotIn,
+        };
+        // Set operators take a list; the engine holds one condition per member
+        let values = match (&f.op, &f.value) {
+            (crate::types::FilterOp::In | crate::types::FilterOp::NotIn, Value::Array(items)) => {
+                items.iter().collect()
             }
+            _ => vec![&f.value],
+        };
+        for value in values {
+            engine_filter
+                .conditions
+                .push(strata_engine::FilterCondition {
+                    field: f.field.clone(),
+                    op: engine_op,
+                    value: value_to_json_scalar(value),
+                });
         }
     }
 
@@ -497,4 +795,81 @@ mod tests {
         assert_eq!(extract_version(&Version::Sequence(100)), 100);
         assert_eq!(extract_version(&Version::Counter(7)), 7);
     }
+
+    #[test]
+    fn test_normalize_command_keys() {
+        let mut cmd = Command::KvGetMany {
+            branch: None,
+            space: None,
+            keys: vec!["User:1".into(), "user:2".into()],
+        };
+        normalize_command_keys(&mut cmd, KeyNormalization::Identity);
+        assert!(matches!(&cmd, Command::KvGetMany { keys, .. } if keys[0] == "User:1"));
+        normalize_command_keys(&mut cmd, KeyNormalization::Lowercase);
+        assert!(matches!(&cmd, Command::KvGetMany { keys, .. } if keys == &["user:1", "user:2"]));
+
+        let mut cmd = Command::KvPutBytes {
+            branch: None,
+            space: None,
+            key: b"Raw".to_vec(),
+            value: Value::Null,
+        };
+        normalize_command_keys(&mut cmd, KeyNormalization::Lowercase);
+        assert!(matches!(&cmd, Command::KvPutBytes { key, .. } if key == b"Raw"));
+    }
+
+    #[test]
+    fn test_apply_result_limits() {
+        let list = |limit| Command::KvList {
+            branch: None,
+            space: None,
+            prefix: None,
+            cursor: None,
+            limit,
+            as_of: None,
+        };
+        let keys = |n: usize| Output::Keys((0..n).map(|i| i.to_string()).collect());
+
+        // Within the cap: untouched
+        let mut cmd = list(Some(5));
+        let caps = apply_result_limits(&mut cmd, (None, Some(10)));
+        assert!(matches!(cmd, Command::KvList { limit: Some(5), .. }));
+        assert_eq!(caps.apply(keys(5)), keys(5));
+
+        // Above the cap: clamped, one extra fetched to detect truncation
+        let mut cmd = list(Some(50));
+        let caps = apply_result_limits(&mut cmd, (None, Some(10)));
+        assert!(matches!(
+            cmd,
+            Command::KvList {
+                limit: Some(11),
+                ..
+            }
+        ));
+        assert_eq!(caps.apply(keys(11)), Output::Truncated(Box::new(keys(10))));
+        assert_eq!(caps.apply(keys(7)), keys(7));
+
+        // No limit: the default applies
+        let mut cmd = list(None);
+        let caps = apply_result_limits(&mut cmd, (Some(3), Some(10)));
+        assert!(matches!(cmd, Command::KvList { limit: Some(4), .. }));
+        assert_eq!(caps.apply(keys(4)), Output::Truncated(Box::new(keys(3))));
+
+        // No limits configured
+        let mut cmd = list(None);
+        let caps = apply_result_limits(&mut cmd, (None, None));
+        assert!(matches!(cmd, Command::KvList { limit: None, .. }));
+        assert_eq!(caps.apply(keys(100)), keys(100));
+
+        let mut cmd = Command::Search {
+            branch: None,
+            space: None,
+            search: crate::types::SearchQuery {
+                query: "q".into(),
+                ..Default::default()
+            },
+        };
+        apply_result_limits(&mut cmd, (Some(20), Some(10)));
+        assert!(matches!(&cmd, Command::Search { search, .. } if search.k == Some(10)));
+    }
 }
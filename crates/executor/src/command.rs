@@ -9,6 +9,8 @@
 //! - **Typed**: No generic fallback, every operation has explicit types
 //! - **Pure data**: No closures or executable code
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use strata_core::Value;
 
@@ -97,6 +99,34 @@ pub enum Command {
         as_of: Option<u64>,
     },
 
+    /// Put a value under a binary key.
+    /// Returns: `Output::Version`
+    KvPutBytes {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Key bytes; need not be valid UTF-8.
+        key: Vec<u8>,
+        /// Value to store.
+        value: Value,
+    },
+
+    /// Get a value by binary key.
+    /// Returns: `Output::Maybe`
+    KvGetBytes {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Key bytes; need not be valid UTF-8.
+        key: Vec<u8>,
+    },
+
     /// Delete a key.
     /// Returns: `Output::Bool` (true if key existed)
     KvDelete {
@@ -110,6 +140,36 @@ pub enum Command {
         key: String,
     },
 
+    /// Delete a key only if it currently holds the expected value.
+    /// Returns: `Output::Bool` (true if the key was deleted)
+    KvDeleteIfEquals {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Key to delete.
+        key: String,
+        /// Value the key must hold for the delete to happen.
+        expected: Value,
+    },
+
+    /// Get a value, inserting a default if the key is missing.
+    /// Returns: `Output::Maybe` (always `Some`: the existing or inserted value)
+    KvGetOrPut {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Key to look up.
+        key: String,
+        /// Value to store if the key is missing.
+        default: Value,
+    },
+
     /// List keys with optional prefix filter.
     /// Returns: `Output::Keys`
     KvList {
@@ -145,6 +205,47 @@ pub enum Command {
         entries: Vec<BatchKvEntry>,
     },
 
+    /// Put multiple key-value pairs atomically.
+    /// Unlike `KvBatchPut`, an invalid entry fails the whole command and
+    /// nothing is written.
+    /// Returns: `Output::Versions` (one per entry)
+    KvPutMany {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Entries to write.
+        entries: Vec<BatchKvEntry>,
+    },
+
+    /// Get multiple keys from a single snapshot.
+    /// Returns: `Output::MaybeVersionedValues` (one per key)
+    KvGetMany {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Keys to look up.
+        keys: Vec<String>,
+    },
+
+    /// Delete multiple keys atomically.
+    /// Returns: `Output::Bools` (true for each key that existed)
+    KvDeleteMany {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Keys to delete.
+        keys: Vec<String>,
+    },
+
     /// Get full version history for a key.
     /// Returns: `Output::VersionHistory`
     KvGetv {
@@ -242,7 +343,8 @@ pub enum Command {
     },
 
     /// List JSON documents with cursor-based pagination.
-    /// Returns: `Output::JsonListResult`
+    /// Returns: `Output::JsonListResult`, or `Output::JsonListDetailed` with
+    /// `include_metadata`
     JsonList {
         /// Target branch (defaults to "default").
         #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -259,6 +361,10 @@ pub enum Command {
         /// Optional timestamp for time-travel reads (microseconds since epoch).
         #[serde(default, skip_serializing_if = "Option::is_none")]
         as_of: Option<u64>,
+        /// Return each document's version and size, as
+        /// `Output::JsonListDetailed` (off by default).
+        #[serde(default)]
+        include_metadata: bool,
     },
 
     // ==================== Event (4 MVP + 1 batch) ====================
@@ -340,6 +446,17 @@ pub enum Command {
         space: Option<String>,
     },
 
+    /// Recompute the event log's hash chain and report the first break.
+    /// Returns: `Output::ChainVerification`
+    EventVerifyChain {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+    },
+
     // ==================== State (4 MVP + 1 batch) ====================
     // MVP: set, read, cas, init
     /// Batch set multiple state cells in a single transaction.
@@ -466,7 +583,7 @@ pub enum Command {
     // ==================== Vector (7 MVP) ====================
     // MVP: upsert, get, delete, search, create_collection, delete_collection, list_collections
     /// Insert or update a vector.
-    /// Returns: `Output::Version`
+    /// Returns: `Output::VectorUpserted`
     VectorUpsert {
         /// Target branch (defaults to "default").
         #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -482,6 +599,31 @@ pub enum Command {
         vector: Vec<f32>,
         /// Optional metadata to associate with the vector.
         metadata: Option<Value>,
+        /// What to do if the key already exists (defaults to replace).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<UpsertMode>,
+    },
+
+    /// Insert a vector unless a near-duplicate already exists.
+    /// Returns: `Output::NovelUpsert`
+    VectorUpsertIfNovel {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Collection name.
+        collection: String,
+        /// Vector key.
+        key: String,
+        /// Embedding vector data.
+        vector: Vec<f32>,
+        /// Optional metadata to associate with the vector.
+        metadata: Option<Value>,
+        /// Skip the insert if the nearest existing vector is closer than this
+        /// distance (`1 - cosine similarity` for cosine collections).
+        min_distance: f32,
     },
 
     /// Get a vector by key.
@@ -539,6 +681,21 @@ pub enum Command {
         /// Optional timestamp for time-travel reads (microseconds since epoch).
         #[serde(default, skip_serializing_if = "Option::is_none")]
         as_of: Option<u64>,
+        /// Candidates fetched per requested result when `filter` is set
+        /// (default 3.0, must be >= 1.0).
+        ///
+        /// A selective filter can discard most of the nearest candidates and
+        /// leave fewer than `k` matches; raising the factor restores a full
+        /// `k` at the cost of scoring and resolving more candidates. The
+        /// backend doubles the factor up to two more times if still short.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        overfetch_factor: Option<f32>,
+        /// Return each match's stored metadata (default true).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        include_metadata: Option<bool>,
+        /// Return each match's stored embedding (off by default).
+        #[serde(default)]
+        include_vector: bool,
     },
 
     /// Create a collection with explicit configuration.
@@ -556,6 +713,25 @@ pub enum Command {
         dimension: u64,
         /// Distance metric for similarity search.
         metric: DistanceMetric,
+        /// Filterable metadata fields; when set, search filters may only
+        /// use these fields (defaults to untyped filtering).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        metadata_schema: Option<BTreeMap<String, MetadataFieldType>>,
+    },
+
+    /// Create a collection whose dimension is set by its first insert.
+    /// Returns: `Output::Version`
+    VectorCreateCollectionAuto {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Collection name.
+        collection: String,
+        /// Distance metric for similarity search.
+        metric: DistanceMetric,
     },
 
     /// Delete a collection.
@@ -645,8 +821,8 @@ pub enum Command {
         branch: BranchId,
     },
 
-    /// Delete a branch and all its data (cascading delete).
-    /// Returns: `Output::Unit`
+    /// Delete a branch and all its data (cascading delete), reclaiming its storage.
+    /// Returns: `Output::BranchDeleted`
     BranchDelete {
         /// Branch to delete.
         branch: BranchId,
@@ -717,8 +893,13 @@ pub enum Command {
     Flush,
 
     /// Trigger compaction
+    /// Returns: `Output::Compacted`
     Compact,
 
+    /// Rebuild the keyword index from all stored data and freeze it to disk.
+    /// Returns: `Output::KeywordIndexRebuilt`
+    RebuildKeywordIndex,
+
     /// Get the available time range for a branch.
     /// Returns: `Output::TimeRange`
     TimeRange {
@@ -735,6 +916,10 @@ pub enum Command {
         branch_id: String,
         /// Output file path.
         path: String,
+        /// Record a SHA-256 of each section and an overall digest in the
+        /// manifest, checked on import (off by default).
+        #[serde(default)]
+        digest: bool,
     },
 
     /// Import a branch from a .branchbundle.tar.zst archive.
@@ -780,6 +965,19 @@ pub enum Command {
         search: SearchQuery,
     },
 
+    /// Run several searches in one call, sharing setup and query embedding.
+    /// Returns: `Output::SearchBatchResults`
+    SearchBatch {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Structured search queries, answered in order.
+        searches: Vec<SearchQuery>,
+    },
+
     // ==================== Space (4) ====================
     /// List spaces in a branch.
     /// Returns: `Output::SpaceList`
@@ -836,8 +1034,13 @@ impl Command {
         matches!(
             self,
             Command::KvPut { .. }
+                | Command::KvPutBytes { .. }
                 | Command::KvBatchPut { .. }
+                | Command::KvPutMany { .. }
+                | Command::KvDeleteMany { .. }
                 | Command::KvDelete { .. }
+                | Command::KvDeleteIfEquals { .. }
+                | Command::KvGetOrPut { .. }
                 | Command::JsonSet { .. }
                 | Command::JsonBatchSet { .. }
                 | Command::JsonDelete { .. }
@@ -849,8 +1052,10 @@ impl Command {
                 | Command::StateInit { .. }
                 | Command::StateDelete { .. }
                 | Command::VectorUpsert { .. }
+                | Command::VectorUpsertIfNovel { .. }
                 | Command::VectorDelete { .. }
                 | Command::VectorCreateCollection { .. }
+                | Command::VectorCreateCollectionAuto { .. }
                 | Command::VectorDeleteCollection { .. }
                 | Command::VectorBatchUpsert { .. }
                 | Command::BranchCreate { .. }
@@ -863,6 +1068,7 @@ impl Command {
                 | Command::RetentionApply { .. }
                 | Command::Flush
                 | Command::Compact
+                | Command::RebuildKeywordIndex
                 | Command::BranchExport { .. }
                 | Command::BranchImport { .. }
                 | Command::ConfigureModel { .. }
@@ -876,9 +1082,16 @@ impl Command {
     pub fn name(&self) -> &'static str {
         match self {
             Command::KvPut { .. } => "KvPut",
+            Command::KvPutBytes { .. } => "KvPutBytes",
+            Command::KvGetBytes { .. } => "KvGetBytes",
             Command::KvBatchPut { .. } => "KvBatchPut",
+            Command::KvPutMany { .. } => "KvPutMany",
+            Command::KvGetMany { .. } => "KvGetMany",
+            Command::KvDeleteMany { .. } => "KvDeleteMany",
             Command::KvGet { .. } => "KvGet",
             Command::KvDelete { .. } => "KvDelete",
+            Command::KvDeleteIfEquals { .. } => "KvDeleteIfEquals",
+            Command::KvGetOrPut { .. } => "KvGetOrPut",
             Command::KvList { .. } => "KvList",
             Command::KvGetv { .. } => "KvGetv",
             Command::JsonSet { .. } => "JsonSet",
@@ -892,6 +1105,7 @@ impl Command {
             Command::EventGet { .. } => "EventGet",
             Command::EventGetByType { .. } => "EventGetByType",
             Command::EventLen { .. } => "EventLen",
+            Command::EventVerifyChain { .. } => "EventVerifyChain",
             Command::StateSet { .. } => "StateSet",
             Command::StateBatchSet { .. } => "StateBatchSet",
             Command::StateGet { .. } => "StateGet",
@@ -901,10 +1115,12 @@ impl Command {
             Command::StateDelete { .. } => "StateDelete",
             Command::StateList { .. } => "StateList",
             Command::VectorUpsert { .. } => "VectorUpsert",
+            Command::VectorUpsertIfNovel { .. } => "VectorUpsertIfNovel",
             Command::VectorGet { .. } => "VectorGet",
             Command::VectorDelete { .. } => "VectorDelete",
             Command::VectorSearch { .. } => "VectorSearch",
             Command::VectorCreateCollection { .. } => "VectorCreateCollection",
+            Command::VectorCreateCollectionAuto { .. } => "VectorCreateCollectionAuto",
             Command::VectorDeleteCollection { .. } => "VectorDeleteCollection",
             Command::VectorListCollections { .. } => "VectorListCollections",
             Command::VectorCollectionStats { .. } => "VectorCollectionStats",
@@ -926,12 +1142,14 @@ impl Command {
             Command::Info => "Info",
             Command::Flush => "Flush",
             Command::Compact => "Compact",
+            Command::RebuildKeywordIndex => "RebuildKeywordIndex",
             Command::TimeRange { .. } => "TimeRange",
             Command::BranchExport { .. } => "BranchExport",
             Command::BranchImport { .. } => "BranchImport",
             Command::BranchBundleValidate { .. } => "BranchBundleValidate",
             Command::ConfigureModel { .. } => "ConfigureModel",
             Command::Search { .. } => "Search",
+            Command::SearchBatch { .. } => "SearchBatch",
             Command::EmbedStatus => "EmbedStatus",
             Command::SpaceList { .. } => "SpaceList",
             Command::SpaceCreate { .. } => "SpaceCreate",
@@ -963,9 +1181,16 @@ impl Command {
         match self {
             // KV
             Command::KvPut { branch, space, .. }
+            | Command::KvPutBytes { branch, space, .. }
+            | Command::KvGetBytes { branch, space, .. }
             | Command::KvBatchPut { branch, space, .. }
+            | Command::KvPutMany { branch, space, .. }
+            | Command::KvGetMany { branch, space, .. }
+            | Command::KvDeleteMany { branch, space, .. }
             | Command::KvGet { branch, space, .. }
             | Command::KvDelete { branch, space, .. }
+            | Command::KvDeleteIfEquals { branch, space, .. }
+            | Command::KvGetOrPut { branch, space, .. }
             | Command::KvList { branch, space, .. }
             | Command::KvGetv { branch, space, .. }
             // JSON
@@ -981,6 +1206,7 @@ impl Command {
             | Command::EventGet { branch, space, .. }
             | Command::EventGetByType { branch, space, .. }
             | Command::EventLen { branch, space, .. }
+            | Command::EventVerifyChain { branch, space, .. }
             // State
             | Command::StateSet { branch, space, .. }
             | Command::StateBatchSet { branch, space, .. }
@@ -992,16 +1218,19 @@ impl Command {
             | Command::StateList { branch, space, .. }
             // Vector (7 MVP)
             | Command::VectorUpsert { branch, space, .. }
+            | Command::VectorUpsertIfNovel { branch, space, .. }
             | Command::VectorGet { branch, space, .. }
             | Command::VectorDelete { branch, space, .. }
             | Command::VectorSearch { branch, space, .. }
             | Command::VectorCreateCollection { branch, space, .. }
+            | Command::VectorCreateCollectionAuto { branch, space, .. }
             | Command::VectorDeleteCollection { branch, space, .. }
             | Command::VectorListCollections { branch, space, .. }
             | Command::VectorCollectionStats { branch, space, .. }
             | Command::VectorBatchUpsert { branch, space, .. }
             // Intelligence
-            | Command::Search { branch, space, .. } => {
+            | Command::Search { branch, space, .. }
+            | Command::SearchBatch { branch, space, .. } => {
                 resolve_branch!(branch);
                 resolve_space!(space);
             }
@@ -1038,6 +1267,7 @@ impl Command {
             | Command::Info
             | Command::Flush
             | Command::Compact
+            | Command::RebuildKeywordIndex
             | Command::EmbedStatus
             | Command::BranchExport { .. }
             | Command::BranchImport { .. }
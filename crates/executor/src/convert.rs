@@ -66,6 +66,9 @@ impl From<StrataError> for Error {
 
             StrataError::TransactionNotActive { .. } => Error::TransactionNotActive,
 
+            // Lifecycle errors
+            StrataError::DatabaseClosed => Error::DatabaseClosed,
+
             // Validation errors
             StrataError::InvalidOperation { entity_ref, reason } => Error::ConstraintViolation {
                 reason: format!("Invalid operation on {}: {}", entity_ref, reason),
@@ -79,6 +82,10 @@ impl From<StrataError> for Error {
                 actual: got,
             },
 
+            StrataError::UnknownFilterField { collection, field } => {
+                Error::UnknownFilterField { collection, field }
+            }
+
             StrataError::CapacityExceeded {
                 resource,
                 limit,
@@ -90,10 +97,16 @@ impl From<StrataError> for Error {
                 ),
             },
 
+            StrataError::ValueTooLarge { size, limit } => Error::ValueTooLarge { size, limit },
+
             StrataError::BudgetExceeded { operation } => Error::ConstraintViolation {
                 reason: format!("Budget exceeded for operation: {}", operation),
             },
 
+            StrataError::TooManyTransactions { active, limit } => {
+                Error::TooManyTransactions { active, limit }
+            }
+
             StrataError::PathNotFound { entity_ref, path } => Error::InvalidPath {
                 reason: format!("Path '{}' not found in {}", path, entity_ref),
             },
@@ -124,6 +137,16 @@ impl From<StrataError> for Error {
                 reason: format!("Data corruption: {}", message),
             },
 
+            StrataError::UnsupportedFormatVersion {
+                format,
+                found,
+                supported,
+            } => Error::UnsupportedFormatVersion {
+                format,
+                found,
+                supported,
+            },
+
             StrataError::Internal { message } => Error::Internal { reason: message },
         }
     }
@@ -217,6 +240,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unsupported_format_version() {
+        let err = StrataError::unsupported_format_version("MANIFEST", 2, 1);
+        let converted: Error = err.into();
+        assert_eq!(
+            converted,
+            Error::UnsupportedFormatVersion {
+                format: "MANIFEST".into(),
+                found: 2,
+                supported: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_too_many_transactions() {
+        let err = StrataError::too_many_transactions(4, 4);
+        let converted: Error = err.into();
+        assert_eq!(
+            converted,
+            Error::TooManyTransactions {
+                active: 4,
+                limit: 4
+            }
+        );
+    }
+
     #[test]
     fn test_dimension_mismatch() {
         let err = StrataError::dimension_mismatch(384, 768);
@@ -229,4 +279,17 @@ mod tests {
             _ => panic!("Expected DimensionMismatch"),
         }
     }
+
+    #[test]
+    fn test_unknown_filter_field() {
+        let err = StrataError::unknown_filter_field("docs", "categroy");
+        let converted: Error = err.into();
+        assert_eq!(
+            converted,
+            Error::UnknownFilterField {
+                collection: "docs".into(),
+                field: "categroy".into(),
+            }
+        );
+    }
 }
@@ -21,10 +21,10 @@ use serde::{Deserialize, Serialize};
 /// | Type | `WrongType` | Type mismatch |
 /// | Validation | `InvalidKey`, `InvalidPath`, `InvalidInput` | Bad input |
 /// | Concurrency | `VersionConflict`, `TransitionFailed`, `Conflict` | Race conditions |
-/// | State | `BranchClosed`, `BranchExists`, `CollectionExists` | Invalid state transition |
-/// | Constraint | `DimensionMismatch`, `ConstraintViolation`, etc. | Limits exceeded |
-/// | Transaction | `TransactionNotActive`, `TransactionAlreadyActive` | Transaction state |
-/// | System | `Io`, `Serialization`, `Internal` | Infrastructure errors |
+/// | State | `BranchClosed`, `BranchExists`, `CollectionExists`, `DatabaseClosed` | Invalid state transition |
+/// | Constraint | `DimensionMismatch`, `UnknownFilterField`, `ConstraintViolation`, etc. | Limits exceeded |
+/// | Transaction | `TransactionNotActive`, `TransactionAlreadyActive`, `TooManyTransactions` | Transaction state |
+/// | System | `Io`, `UnsupportedFormatVersion`, `Serialization`, `Internal`, `Backpressure`, `ModelUnavailable` | Infrastructure errors |
 ///
 /// # Example
 ///
@@ -170,6 +170,10 @@ pub enum Error {
         collection: String,
     },
 
+    /// The database has been shut down
+    #[error("database is closed")]
+    DatabaseClosed,
+
     // ==================== Constraint Errors ====================
     /// Vector dimension mismatch
     #[error("dimension mismatch: expected {expected}, got {actual}")]
@@ -180,6 +184,15 @@ pub enum Error {
         actual: usize,
     },
 
+    /// Search filter names a metadata field the collection's schema does not declare
+    #[error("unknown filter field '{field}' in collection {collection}")]
+    UnknownFilterField {
+        /// The searched collection.
+        collection: String,
+        /// The undeclared field.
+        field: String,
+    },
+
     /// Constraint violation
     #[error("constraint violation: {reason}")]
     ConstraintViolation {
@@ -187,6 +200,15 @@ pub enum Error {
         reason: String,
     },
 
+    /// Write exceeds the maximum WAL record size
+    #[error("value too large: {size} bytes exceeds the limit of {limit} bytes")]
+    ValueTooLarge {
+        /// Size of the rejected write in bytes.
+        size: u64,
+        /// Configured `max_record_bytes`.
+        limit: u64,
+    },
+
     /// Requested version was trimmed by retention policy
     #[error("history trimmed: requested version {requested}, earliest is {earliest}")]
     HistoryTrimmed {
@@ -227,6 +249,15 @@ pub enum Error {
         reason: String,
     },
 
+    /// Beginning a transaction would exceed `max_active_transactions`
+    #[error("too many active transactions: {active} open (limit: {limit})")]
+    TooManyTransactions {
+        /// Transactions open when the request was rejected.
+        active: u64,
+        /// The configured limit.
+        limit: u64,
+    },
+
     // ==================== System Errors ====================
     /// I/O error
     #[error("I/O error: {reason}")]
@@ -235,6 +266,17 @@ pub enum Error {
         reason: String,
     },
 
+    /// A database file was written by a newer format than this build reads
+    #[error("unsupported format version: {format} has version {found}, this build supports up to {supported}")]
+    UnsupportedFormatVersion {
+        /// The file whose format is too new.
+        format: String,
+        /// Version found in the file.
+        found: u32,
+        /// Newest version this build can read.
+        supported: u32,
+    },
+
     /// Serialization error
     #[error("serialization error: {reason}")]
     Serialization {
@@ -249,6 +291,22 @@ pub enum Error {
         reason: String,
     },
 
+    /// The embedding buffer is full and the background queue rejected a flush
+    #[error("backpressure: {pending} texts awaiting embedding (limit: {limit}) and the background queue is full")]
+    Backpressure {
+        /// Texts buffered when the write was refused.
+        pending: u64,
+        /// Maximum number of buffered texts.
+        limit: u64,
+    },
+
+    /// Auto-embedding was requested but the embedding model is unavailable
+    #[error("model unavailable: {reason}")]
+    ModelUnavailable {
+        /// Why the model could not be used.
+        reason: String,
+    },
+
     /// Feature not yet implemented
     #[error("not implemented: {feature} - {reason}")]
     NotImplemented {
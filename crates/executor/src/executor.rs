@@ -6,14 +6,15 @@
 use std::sync::Arc;
 use std::time::Instant;
 
+use strata_engine::admin_log::AdminAction;
 use strata_engine::Database;
 use strata_security::AccessMode;
 use tracing::{debug, warn};
 
-use crate::bridge::{to_core_branch_id, Primitives};
+use crate::bridge::{apply_result_limits, normalize_command_keys, to_core_branch_id, Primitives};
 use crate::convert::convert_result;
 use crate::types::BranchId;
-use crate::{Command, Error, Output, Result};
+use crate::{Command, CompactInfo, Error, IndexStats, Output, Result};
 
 /// The command executor - single entry point to Strata's engine.
 ///
@@ -49,6 +50,8 @@ use crate::{Command, Error, Output, Result};
 pub struct Executor {
     primitives: Arc<Primitives>,
     access_mode: AccessMode,
+    /// Name recorded as the actor of administrative actions
+    actor: Option<String>,
     /// Shared state for the embed refresh timer thread (condvar for instant shutdown).
     embed_refresh_state: Arc<EmbedRefreshState>,
     /// Handle for the embed refresh timer thread (joined on drop).
@@ -84,6 +87,7 @@ impl Executor {
         Self {
             primitives,
             access_mode,
+            actor: None,
             embed_refresh_state: state,
             embed_refresh_handle: Some(handle),
         }
@@ -136,6 +140,42 @@ impl Executor {
         self.access_mode
     }
 
+    /// Attribute administrative actions made through this executor to `actor`.
+    pub fn with_actor(mut self, actor: Option<String>) -> Self {
+        self.actor = actor;
+        self
+    }
+
+    /// Append an entry to the admin log, attributed to this executor's actor.
+    ///
+    /// The action has already happened, so failing to record it is logged
+    /// rather than returned.
+    pub(crate) fn record_admin(
+        &self,
+        action: AdminAction,
+        target: Option<&str>,
+        detail: Option<&str>,
+    ) {
+        if let Err(e) = strata_engine::admin_log::record(
+            &self.primitives.db,
+            action,
+            target,
+            self.actor.as_deref(),
+            detail,
+        ) {
+            warn!(target: "strata::admin", action = action.as_str(), error = %e, "Failed to record admin action");
+        }
+    }
+
+    /// Fail with [`Error::DatabaseClosed`] once the database has been shut down.
+    pub(crate) fn check_open(&self) -> Result<()> {
+        if self.primitives.db.is_open() {
+            Ok(())
+        } else {
+            Err(Error::DatabaseClosed)
+        }
+    }
+
     /// Auto-register a space on first write to a non-default space.
     ///
     /// This is idempotent: calling it on an already-registered space just
@@ -153,8 +193,11 @@ impl Executor {
     /// Execute a single command.
     ///
     /// Resolves any `None` branch fields to the default branch before dispatch.
-    /// Returns the command result or an error.
+    /// Returns the command result or an error, and [`Error::DatabaseClosed`]
+    /// for every command once the database has been shut down.
     pub fn execute(&self, mut cmd: Command) -> Result<Output> {
+        self.check_open()?;
+
         if self.access_mode == AccessMode::ReadOnly && cmd.is_write() {
             warn!(target: "strata::command", command = %cmd.name(), "Write rejected in read-only mode");
             return Err(Error::AccessDenied {
@@ -163,6 +206,8 @@ impl Executor {
         }
 
         cmd.resolve_defaults();
+        normalize_command_keys(&mut cmd, self.primitives.db.key_normalization());
+        let caps = apply_result_limits(&mut cmd, self.primitives.db.result_limits());
 
         let cmd_name = cmd.name();
         let start = Instant::now();
@@ -193,8 +238,26 @@ impl Executor {
                 Ok(Output::Unit)
             }
             Command::Compact => {
-                convert_result(self.primitives.db.compact())?;
-                Ok(Output::Unit)
+                let info = convert_result(self.primitives.db.compact())?;
+                self.record_admin(
+                    AdminAction::Compact,
+                    None,
+                    Some(&format!(
+                        "{} segments removed, {} bytes reclaimed",
+                        info.wal_segments_removed, info.reclaimed_bytes
+                    )),
+                );
+                Ok(Output::Compacted(CompactInfo {
+                    segments_removed: info.wal_segments_removed as u64,
+                    bytes_reclaimed: info.reclaimed_bytes,
+                }))
+            }
+            Command::RebuildKeywordIndex => {
+                let stats = convert_result(self.primitives.db.rebuild_keyword_index())?;
+                Ok(Output::KeywordIndexRebuilt(IndexStats {
+                    documents_indexed: stats.documents,
+                    tokens_indexed: stats.tokens,
+                }))
             }
             Command::EmbedStatus => {
                 let info = crate::handlers::embed_hook::embed_status(&self.primitives);
@@ -221,6 +284,26 @@ impl Executor {
                 self.ensure_space_registered(&branch, &space)?;
                 crate::handlers::kv::kv_put(&self.primitives, branch, space, key, value)
             }
+            Command::KvPutBytes {
+                branch,
+                space,
+                key,
+                value,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::kv::kv_put_bytes(&self.primitives, branch, space, key, value)
+            }
+            Command::KvGetBytes { branch, space, key } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::kv::kv_get_bytes(&self.primitives, branch, space, key)
+            }
             Command::KvBatchPut {
                 branch,
                 space,
@@ -233,6 +316,41 @@ impl Executor {
                 self.ensure_space_registered(&branch, &space)?;
                 crate::handlers::kv::kv_batch_put(&self.primitives, branch, space, entries)
             }
+            Command::KvPutMany {
+                branch,
+                space,
+                entries,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::kv::kv_put_many(&self.primitives, branch, space, entries)
+            }
+            Command::KvGetMany {
+                branch,
+                space,
+                keys,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::kv::kv_get_many(&self.primitives, branch, space, keys)
+            }
+            Command::KvDeleteMany {
+                branch,
+                space,
+                keys,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::kv::kv_delete_many(&self.primitives, branch, space, keys)
+            }
             Command::KvGet {
                 branch,
                 space,
@@ -257,6 +375,38 @@ impl Executor {
                 self.ensure_space_registered(&branch, &space)?;
                 crate::handlers::kv::kv_delete(&self.primitives, branch, space, key)
             }
+            Command::KvDeleteIfEquals {
+                branch,
+                space,
+                key,
+                expected,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::kv::kv_delete_if_equals(
+                    &self.primitives,
+                    branch,
+                    space,
+                    key,
+                    expected,
+                )
+            }
+            Command::KvGetOrPut {
+                branch,
+                space,
+                key,
+                default,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::kv::kv_get_or_put(&self.primitives, branch, space, key, default)
+            }
             Command::KvList {
                 branch,
                 space,
@@ -382,13 +532,21 @@ impl Executor {
                 cursor,
                 limit,
                 as_of,
+                include_metadata,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
                 })?;
                 let space = space.unwrap_or_else(|| "default".to_string());
                 if let Some(ts) = as_of {
-                    crate::handlers::json::json_list_at(&self.primitives, branch, space, prefix, ts)
+                    crate::handlers::json::json_list_at(
+                        &self.primitives,
+                        branch,
+                        space,
+                        prefix,
+                        ts,
+                        include_metadata,
+                    )
                 } else {
                     crate::handlers::json::json_list(
                         &self.primitives,
@@ -397,6 +555,7 @@ impl Executor {
                         prefix,
                         cursor,
                         limit,
+                        include_metadata,
                     )
                 }
             }
@@ -493,6 +652,13 @@ impl Executor {
                 let space = space.unwrap_or_else(|| "default".to_string());
                 crate::handlers::event::event_len(&self.primitives, branch, space)
             }
+            Command::EventVerifyChain { branch, space } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::event::event_verify_chain(&self.primitives, branch, space)
+            }
 
             // State commands (4 MVP)
             Command::StateBatchSet {
@@ -627,6 +793,7 @@ impl Executor {
                 key,
                 vector,
                 metadata,
+                mode,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
@@ -641,6 +808,32 @@ impl Executor {
                     key,
                     vector,
                     metadata,
+                    mode.unwrap_or_default(),
+                )
+            }
+            Command::VectorUpsertIfNovel {
+                branch,
+                space,
+                collection,
+                key,
+                vector,
+                metadata,
+                min_distance,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::vector::vector_upsert_if_novel(
+                    &self.primitives,
+                    branch,
+                    space,
+                    collection,
+                    key,
+                    vector,
+                    metadata,
+                    min_distance,
                 )
             }
             Command::VectorGet {
@@ -701,6 +894,9 @@ impl Executor {
                 filter,
                 metric,
                 as_of,
+                overfetch_factor,
+                include_metadata,
+                include_vector,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
@@ -716,7 +912,10 @@ impl Executor {
                         k,
                         filter,
                         metric,
+                        overfetch_factor,
                         ts,
+                        include_metadata,
+                        include_vector,
                     )
                 } else {
                     crate::handlers::vector::vector_search(
@@ -728,6 +927,9 @@ impl Executor {
                         k,
                         filter,
                         metric,
+                        overfetch_factor,
+                        include_metadata,
+                        include_vector,
                     )
                 }
             }
@@ -737,6 +939,7 @@ impl Executor {
                 collection,
                 dimension,
                 metric,
+                metadata_schema,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
@@ -750,6 +953,26 @@ impl Executor {
                     collection,
                     dimension,
                     metric,
+                    metadata_schema,
+                )
+            }
+            Command::VectorCreateCollectionAuto {
+                branch,
+                space,
+                collection,
+                metric,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::vector::vector_create_collection_auto(
+                    &self.primitives,
+                    branch,
+                    space,
+                    collection,
+                    metric,
                 )
             }
             Command::VectorDeleteCollection {
@@ -816,7 +1039,14 @@ impl Executor {
             Command::BranchCreate {
                 branch_id,
                 metadata,
-            } => crate::handlers::branch::branch_create(&self.primitives, branch_id, metadata),
+            } => {
+                let output =
+                    crate::handlers::branch::branch_create(&self.primitives, branch_id, metadata)?;
+                if let Output::BranchWithVersion { info, .. } = &output {
+                    self.record_admin(AdminAction::BranchCreate, Some(info.id.as_str()), None);
+                }
+                Ok(output)
+            }
             Command::BranchGet { branch } => {
                 crate::handlers::branch::branch_get(&self.primitives, branch)
             }
@@ -829,7 +1059,10 @@ impl Executor {
                 crate::handlers::branch::branch_exists(&self.primitives, branch)
             }
             Command::BranchDelete { branch } => {
-                crate::handlers::branch::branch_delete(&self.primitives, branch)
+                let name = branch.as_str().to_string();
+                let output = crate::handlers::branch::branch_delete(&self.primitives, branch)?;
+                self.record_admin(AdminAction::BranchDelete, Some(&name), None);
+                Ok(output)
             }
 
             // Transaction commands - handled by Session, not Executor
@@ -861,9 +1094,11 @@ impl Executor {
             }
 
             // Bundle commands
-            Command::BranchExport { branch_id, path } => {
-                crate::handlers::branch::branch_export(&self.primitives, branch_id, path)
-            }
+            Command::BranchExport {
+                branch_id,
+                path,
+                digest,
+            } => crate::handlers::branch::branch_export(&self.primitives, branch_id, path, digest),
             Command::BranchImport { path } => {
                 crate::handlers::branch::branch_import(&self.primitives, path)
             }
@@ -877,13 +1112,18 @@ impl Executor {
                 model,
                 api_key,
                 timeout_ms,
-            } => crate::handlers::configure_model::configure_model(
-                &self.primitives,
-                endpoint,
-                model,
-                api_key,
-                timeout_ms,
-            ),
+            } => {
+                let detail = format!("{} at {}", model, endpoint);
+                let output = crate::handlers::configure_model::configure_model(
+                    &self.primitives,
+                    endpoint,
+                    model,
+                    api_key,
+                    timeout_ms,
+                )?;
+                self.record_admin(AdminAction::ConfigChange, Some("model"), Some(&detail));
+                Ok(output)
+            }
             Command::Search {
                 branch,
                 space,
@@ -895,6 +1135,17 @@ impl Executor {
                 let space = space.unwrap_or_else(|| "default".to_string());
                 crate::handlers::search::search(&self.primitives, branch, space, search)
             }
+            Command::SearchBatch {
+                branch,
+                space,
+                searches,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::search::search_batch(&self.primitives, branch, space, searches)
+            }
 
             // Space commands
             Command::SpaceList { branch } => {
@@ -936,7 +1187,32 @@ impl Executor {
             }
         }
 
-        result
+        result.map(|output| caps.apply(output))
+    }
+
+    /// Check a command without executing it.
+    ///
+    /// Runs the checks [`execute`](Self::execute) performs before writing or
+    /// reading anything: read-only access, key, value and vector limits,
+    /// JSON path syntax, branch existence and collection dimensions. Returns
+    /// the error `execute` would return for those checks, and `Ok(())` when
+    /// the command would get past them. Nothing is written.
+    ///
+    /// Failures that only show up while running, such as a version conflict
+    /// or a missing key on delete, are not reported.
+    pub fn validate(&self, cmd: &Command) -> Result<()> {
+        self.check_open()?;
+
+        if self.access_mode == AccessMode::ReadOnly && cmd.is_write() {
+            return Err(Error::AccessDenied {
+                command: cmd.name().to_string(),
+            });
+        }
+
+        let mut cmd = cmd.clone();
+        cmd.resolve_defaults();
+        normalize_command_keys(&mut cmd, self.primitives.db.key_normalization());
+        crate::validate::validate_command(&self.primitives, &cmd)
     }
 
     /// Execute multiple commands sequentially.
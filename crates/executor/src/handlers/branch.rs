@@ -9,7 +9,7 @@ use strata_engine::BranchMetadata;
 
 use crate::bridge::{extract_version, from_engine_branch_status, Primitives};
 use crate::convert::convert_result;
-use crate::types::{BranchId, BranchInfo, VersionedBranchInfo};
+use crate::types::{BranchId, BranchInfo, DeleteBranchInfo, VersionedBranchInfo};
 use crate::{Error, Output, Result};
 
 // =============================================================================
@@ -23,7 +23,7 @@ fn metadata_to_branch_info(m: &BranchMetadata) -> BranchInfo {
         status: from_engine_branch_status(m.status),
         created_at: m.created_at,
         updated_at: m.updated_at,
-        parent_id: None,
+        parent_id: m.parent_branch.clone().map(BranchId::from),
     }
 }
 
@@ -41,7 +41,7 @@ fn versioned_to_branch_info(v: strata_core::Versioned<BranchMetadata>) -> Versio
 ///
 /// Rejects empty names, whitespace-only names, and names containing
 /// control characters or NUL bytes.
-fn validate_branch_name(name: &str) -> Result<()> {
+pub(crate) fn validate_branch_name(name: &str) -> Result<()> {
     if name.is_empty() {
         return Err(Error::InvalidInput {
             reason: "Branch name must not be empty".to_string(),
@@ -71,7 +71,7 @@ fn validate_branch_name(name: &str) -> Result<()> {
 }
 
 /// Guard: reject operations on the default branch that would delete it.
-fn reject_default_branch(branch: &BranchId, operation: &str) -> Result<()> {
+pub(crate) fn reject_default_branch(branch: &BranchId, operation: &str) -> Result<()> {
     if branch.is_default() {
         return Err(Error::ConstraintViolation {
             reason: format!("Cannot {} the default branch", operation),
@@ -80,6 +80,18 @@ fn reject_default_branch(branch: &BranchId, operation: &str) -> Result<()> {
     Ok(())
 }
 
+/// Checks a branch delete runs before deleting anything: the branch is
+/// not the default branch and exists.
+pub(crate) fn check_delete(p: &Arc<Primitives>, branch: &BranchId) -> Result<()> {
+    reject_default_branch(branch, "delete")?;
+    if !convert_result(p.branch.exists(branch.as_str()))? {
+        return Err(Error::BranchNotFound {
+            branch: branch.as_str().to_string(),
+        });
+    }
+    Ok(())
+}
+
 // =============================================================================
 // MVP Handlers
 // =============================================================================
@@ -152,31 +164,24 @@ pub fn branch_exists(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
 
 /// Handle BranchDelete command.
 ///
-/// After deleting the branch metadata, performs cleanup:
+/// Delegates to `branch_ops::delete_branch`, which after deleting the branch
+/// metadata and data also:
 /// - Removes the per-branch commit lock to prevent unbounded growth (#944)
-/// - Deletes all vector collections for the branch to free memory (#946)
+/// - Deletes all vector collections and their files to free memory (#946)
+/// - Drops the branch's versions from storage
 pub fn branch_delete(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
-    reject_default_branch(&branch, "delete")?;
-    convert_result(p.branch.delete_branch(branch.as_str()))?;
-
-    // Cleanup: remove per-branch commit lock (#944)
-    // Convert the executor BranchId to core BranchId for the lock cleanup
-    if let Ok(core_branch_id) = crate::bridge::to_core_branch_id(&branch) {
-        p.db.remove_branch_lock(&core_branch_id);
-
-        // Cleanup: delete all vector collections for this branch (#946)
-        // Best-effort: silently continue if vector cleanup fails, since the
-        // branch metadata is already deleted and data will be orphaned but harmless.
-        if let Ok(collections) = p.vector.list_collections(core_branch_id, "default") {
-            for collection in collections {
-                let _ = p
-                    .vector
-                    .delete_collection(core_branch_id, "default", &collection.name);
-            }
-        }
-    }
-
-    Ok(Output::Unit)
+    check_delete(p, &branch)?;
+    let info = convert_result(strata_engine::branch_ops::delete_branch(
+        &p.db,
+        branch.as_str(),
+    ))?;
+
+    Ok(Output::BranchDeleted(DeleteBranchInfo {
+        branch: info.branch,
+        keys_reclaimed: info.keys_reclaimed,
+        bytes_reclaimed: info.bytes_reclaimed,
+        reclaim_pending: info.reclaim_pending,
+    }))
 }
 
 // =============================================================================
@@ -184,14 +189,22 @@ pub fn branch_delete(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
 // =============================================================================
 
 /// Handle BranchExport command.
-pub fn branch_export(p: &Arc<Primitives>, branch_id: String, path: String) -> Result<Output> {
+pub fn branch_export(
+    p: &Arc<Primitives>,
+    branch_id: String,
+    path: String,
+    digest: bool,
+) -> Result<Output> {
     let export_path = std::path::Path::new(&path);
+    let options = strata_engine::bundle::ExportOptions {
+        include_digest: digest,
+        ..Default::default()
+    };
     let info =
-        strata_engine::bundle::export_branch(&p.db, &branch_id, export_path).map_err(|e| {
-            Error::Io {
+        strata_engine::bundle::export_branch_with_options(&p.db, &branch_id, export_path, &options)
+            .map_err(|e| Error::Io {
                 reason: format!("Export failed: {}", e),
-            }
-        })?;
+            })?;
 
     Ok(Output::BranchExported(crate::types::BranchExportResult {
         branch_id: info.branch_id,
@@ -228,6 +241,7 @@ pub fn branch_bundle_validate(path: String) -> Result<Output> {
             format_version: info.format_version,
             entry_count: info.entry_count,
             checksums_valid: info.checksums_valid,
+            digest_valid: info.digest_valid,
         },
     ))
 }
@@ -251,6 +265,7 @@ mod tests {
             name: "test-branch".to_string(),
             branch_id: "some-uuid".to_string(),
             parent_branch: None,
+            fork_base_version: None,
             status: strata_engine::BranchStatus::Active,
             created_at: 1000000,
             updated_at: 2000000,
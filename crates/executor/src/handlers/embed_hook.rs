@@ -74,11 +74,20 @@ struct PendingEmbed {
     source_ref: strata_core::EntityRef,
 }
 
+/// Number of batches the embed buffer holds before writes are refused.
+///
+/// The buffer only grows past one batch while the background queue rejects
+/// flushes; at this many batches [`check_embed_backpressure`] fails writes
+/// that carry text instead of buffering without bound.
+#[cfg(feature = "embed")]
+pub const MAX_PENDING_BATCHES: usize = 16;
+
 /// Write-behind buffer for embedding requests.
 ///
 /// Stored as a `Database` extension (`db.extension::<EmbedBuffer>()`).
 /// Pending items accumulate until `batch_size` is reached (auto-flush) or
-/// [`flush_embed_buffer()`] is called (manual flush on `db.flush()`).
+/// [`flush_embed_buffer()`] is called (manual flush on `db.flush()`). At
+/// most [`MAX_PENDING_BATCHES`] batches are held.
 #[cfg(feature = "embed")]
 pub struct EmbedBuffer {
     pending: std::sync::Mutex<Vec<PendingEmbed>>,
@@ -107,12 +116,81 @@ impl Default for EmbedBuffer {
     }
 }
 
+/// Maximum number of texts the embed buffer holds.
+#[cfg(feature = "embed")]
+fn max_pending_embeds(p: &Arc<Primitives>) -> usize {
+    p.db.embed_batch_size().max(1) * MAX_PENDING_BATCHES
+}
+
+/// Submit a flush of the embed buffer to the background scheduler.
+///
+/// After shutdown the buffer is flushed on the calling thread instead.
+/// Fails only when the scheduler queue is full.
+#[cfg(feature = "embed")]
+fn submit_flush(p: &Arc<Primitives>) -> Result<(), strata_engine::BackpressureError> {
+    let p_clone = Arc::clone(p);
+    let submitted =
+        p.db.scheduler()
+            .submit(strata_engine::TaskPriority::Normal, move || {
+                flush_embed_buffer(&p_clone)
+            });
+    match submitted {
+        Err(_) if p.db.scheduler().is_shutdown() => {
+            // No workers left to run the flush — do it inline
+            flush_embed_buffer(p);
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Refuse a write that carries text while the embed buffer is full.
+///
+/// Called by write handlers before they commit. The buffer only fills while
+/// the background queue rejects flushes, so a flush is submitted again
+/// first; if the queue still rejects it, the write fails with
+/// [`Error::Backpressure`](crate::Error::Backpressure) and nothing is
+/// written. Callers can retry once `Strata::flush` (or the background
+/// workers) have drained the buffer.
+#[cfg(feature = "embed")]
+pub fn check_embed_backpressure(p: &Arc<Primitives>) -> crate::Result<()> {
+    if !p.db.auto_embed_enabled() {
+        return Ok(());
+    }
+    let Ok(buf) = p.db.extension::<EmbedBuffer>() else {
+        return Ok(());
+    };
+    let limit = max_pending_embeds(p);
+    let pending = buf.pending.lock().unwrap_or_else(|e| e.into_inner()).len();
+    if pending < limit || submit_flush(p).is_ok() {
+        return Ok(());
+    }
+    Err(crate::Error::Backpressure {
+        pending: pending as u64,
+        limit: limit as u64,
+    })
+}
+
+/// Always admits the write when the embed feature is not compiled in.
+#[cfg(not(feature = "embed"))]
+pub fn check_embed_backpressure(_p: &Arc<Primitives>) -> crate::Result<()> {
+    Ok(())
+}
+
 /// Buffer a text for embedding in a shadow vector collection.
 ///
 /// Best-effort: failures are logged, never propagated to the caller.
 /// When the buffer reaches `batch_size`, a flush is submitted to the
-/// background scheduler (non-blocking). Falls back to synchronous flush
-/// if the scheduler rejects (backpressure/shutdown).
+/// background scheduler (non-blocking). If the scheduler queue is full the
+/// texts stay buffered for the next flush and the rejection is counted in
+/// `EmbedStatusInfo::scheduler_tasks_rejected`; the write never blocks on
+/// embedding. After shutdown the buffer is flushed synchronously instead.
+///
+/// The buffer is capped at [`MAX_PENDING_BATCHES`] batches. Write handlers
+/// call [`check_embed_backpressure`] before committing, so texts only
+/// arrive at a full buffer from writers that raced past that check or from
+/// re-embedding after a JSON sub-path delete; they are dropped and counted
+/// in `EmbedStatusInfo::total_failed`.
 ///
 /// # Consistency
 ///
@@ -144,6 +222,17 @@ pub fn maybe_embed_text(
 
     let should_flush = {
         let mut pending = buf.pending.lock().unwrap_or_else(|e| e.into_inner());
+        if pending.len() >= max_pending_embeds(p) {
+            buf.total_failed
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            tracing::warn!(
+                target: "strata::embed",
+                collection = shadow_collection,
+                key,
+                "Embed buffer full, dropping text"
+            );
+            return;
+        }
         pending.push(PendingEmbed {
             branch_id,
             space: space.to_owned(),
@@ -157,18 +246,13 @@ pub fn maybe_embed_text(
         pending.len() >= p.db.embed_batch_size()
     };
 
-    if should_flush {
-        let p_clone = Arc::clone(p);
-        if p.db
-            .scheduler()
-            .submit(strata_engine::TaskPriority::Normal, move || {
-                flush_embed_buffer(&p_clone)
-            })
-            .is_err()
-        {
-            // Backpressure or shutdown — fall back to synchronous flush
-            flush_embed_buffer(p);
-        }
+    if should_flush && submit_flush(p).is_err() {
+        // Queue is full — leave the texts buffered; the refresh timer or
+        // the next explicit flush will pick them up
+        tracing::warn!(
+            target: "strata::embed",
+            "Background queue full, deferring embedding flush"
+        );
     }
 }
 
@@ -194,8 +278,6 @@ pub fn maybe_embed_text(
 /// whatever has accumulated in the buffer since the first caller's `mem::take`.
 #[cfg(feature = "embed")]
 pub fn flush_embed_buffer(p: &Arc<Primitives>) {
-    use strata_intelligence::embed::EmbedModelState;
-
     let buf = match p.db.extension::<EmbedBuffer>() {
         Ok(b) => b,
         Err(e) => {
@@ -220,18 +302,7 @@ pub fn flush_embed_buffer(p: &Arc<Primitives>) {
     let batch_len = batch.len() as u64;
 
     // Load model once for the whole batch.
-    let model_dir = p.db.model_dir();
-    let embed_state = match p.db.extension::<EmbedModelState>() {
-        Ok(s) => s,
-        Err(e) => {
-            tracing::warn!(target: "strata::embed", error = %e, "Failed to get embed model state");
-            buf.total_failed
-                .fetch_add(batch_len, std::sync::atomic::Ordering::Relaxed);
-            return;
-        }
-    };
-
-    let model = match embed_state.get_or_load(&model_dir) {
+    let model = match strata_intelligence::embed::load_model(&p.db) {
         Ok(m) => m,
         Err(e) => {
             tracing::warn!(target: "strata::embed", error = %e, "Failed to load embedding model");
@@ -288,6 +359,68 @@ pub fn flush_embed_buffer(p: &Arc<Primitives>) {
 #[cfg(not(feature = "embed"))]
 pub fn flush_embed_buffer(_p: &Arc<Primitives>) {}
 
+/// Embed `texts` on the background scheduler at `priority` and wait for
+/// the vectors.
+///
+/// Takes the same lock as buffer flushes, so it never overlaps another
+/// `embed_batch` call.
+#[cfg(feature = "embed")]
+pub(crate) fn embed_batch_blocking(
+    p: &Arc<Primitives>,
+    model: Arc<strata_intelligence::embed::model::EmbedModel>,
+    texts: Vec<String>,
+    priority: strata_engine::TaskPriority,
+) -> Result<Vec<Vec<f32>>, String> {
+    let p_clone = Arc::clone(p);
+    run_blocking(p, priority, move || {
+        embed_batch_locked(&p_clone, &model, &texts)
+    })?
+}
+
+/// Embed `texts` on the calling thread, holding the buffer flush lock.
+#[cfg(feature = "embed")]
+pub(crate) fn embed_batch_locked(
+    p: &Arc<Primitives>,
+    model: &strata_intelligence::embed::model::EmbedModel,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let buf = p.db.extension::<EmbedBuffer>().map_err(|e| e.to_string())?;
+    let _flush_guard = buf.flush_lock.lock().unwrap_or_else(|e| e.into_inner());
+    let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+    Ok(model.embed_batch(&refs))
+}
+
+/// Run `task` on the background scheduler at `priority` and wait for its
+/// result.
+///
+/// Runs on the calling thread if the scheduler rejects the task (queue full
+/// or shut down). Fails only if the task panicked on a worker.
+#[cfg(any(feature = "embed", test))]
+pub(crate) fn run_blocking<T: Send + 'static>(
+    p: &Arc<Primitives>,
+    priority: strata_engine::TaskPriority,
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, String> {
+    let slot = Arc::new(std::sync::Mutex::new(Some(task)));
+    let queued = Arc::clone(&slot);
+    let (tx, rx) = std::sync::mpsc::channel();
+    let submitted = p.db.scheduler().submit(priority, move || {
+        let task = queued.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(task) = task {
+            let _ = tx.send(task());
+        }
+    });
+    match submitted {
+        Ok(()) => rx
+            .recv()
+            .map_err(|_| "background task ended without a result".to_string()),
+        Err(_) => {
+            let task = slot.lock().unwrap_or_else(|e| e.into_inner()).take();
+            Ok(task.expect("rejected task was never started")())
+        }
+    }
+}
+
 /// Return a snapshot of the embedding pipeline status.
 #[cfg(feature = "embed")]
 pub fn embed_status(p: &Arc<Primitives>) -> EmbedStatusInfo {
@@ -316,6 +449,7 @@ pub fn embed_status(p: &Arc<Primitives>) -> EmbedStatusInfo {
         total_failed,
         scheduler_queue_depth: stats.queue_depth,
         scheduler_active_tasks: stats.active_tasks,
+        scheduler_tasks_rejected: stats.tasks_rejected,
     }
 }
 
@@ -332,6 +466,7 @@ pub fn embed_status(p: &Arc<Primitives>) -> EmbedStatusInfo {
         total_failed: 0,
         scheduler_queue_depth: stats.queue_depth,
         scheduler_active_tasks: stats.active_tasks,
+        scheduler_tasks_rejected: stats.tasks_rejected,
     }
 }
 
@@ -744,6 +879,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_full_buffer_refuses_writes_with_text() {
+        use crate::{Command, Error, Executor, Output};
+
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = strata_engine::StrataConfig {
+            background_threads: Some(1),
+            background_queue_depth: Some(1),
+            ..Default::default()
+        };
+        let db = strata_engine::Database::open_with_config(dir.path().join("db"), cfg).unwrap();
+        db.set_auto_embed(true);
+        let executor = Executor::new(db);
+        executor.execute(Command::Ping).unwrap();
+        let p = executor.primitives().clone();
+
+        // Occupy the only worker and fill the queue so every flush is rejected
+        let (release, blocked) = std::sync::mpsc::channel::<()>();
+        let blocked = Arc::new(std::sync::Mutex::new(blocked));
+        for queued in 0..2 {
+            let blocked = Arc::clone(&blocked);
+            p.db.scheduler()
+                .submit(strata_engine::TaskPriority::High, move || {
+                    let _ = blocked.lock().unwrap().recv();
+                })
+                .unwrap();
+            while p.db.scheduler().stats().queue_depth > queued {
+                std::thread::yield_now();
+            }
+        }
+
+        let limit = max_pending_embeds(&p);
+        push_n(&p, limit);
+        assert_eq!(buffer_len(&p), limit);
+
+        // Texts arriving at a full buffer are dropped, not buffered
+        push_n(&p, 1);
+        assert_eq!(buffer_len(&p), limit);
+        assert_eq!(embed_status(&p).total_failed, 1);
+
+        let put = |value: &str| {
+            executor.execute(Command::KvPut {
+                branch: None,
+                space: None,
+                key: "k".into(),
+                value: strata_core::Value::String(value.into()),
+            })
+        };
+        let err = put("some text").unwrap_err();
+        assert!(
+            matches!(err, Error::Backpressure { pending, limit: l } if pending == limit as u64 && l == limit as u64),
+            "{:?}",
+            err
+        );
+        let get = executor
+            .execute(Command::KvGet {
+                branch: None,
+                space: None,
+                key: "k".into(),
+                as_of: None,
+            })
+            .unwrap();
+        assert_eq!(get, Output::MaybeVersioned(None));
+
+        // Once the workers drain the buffer, writes go through again
+        drop(release);
+        p.db.scheduler().drain();
+        flush_embed_buffer(&p);
+        assert!(put("some text").is_ok());
+    }
+
     #[test]
     fn test_embed_status_counters() {
         let p = setup();
@@ -814,6 +1020,10 @@ mod tests {
 
 /// Ensure a shadow collection exists, swallowing AlreadyExists errors.
 ///
+/// New collections use the configured embedding dimension. A collection left
+/// over from a model with a different dimension is reported and not cached,
+/// so inserts into it fail loudly until it is rebuilt.
+///
 /// Uses a per-Database cache to avoid repeated creation attempts on every write.
 ///
 /// # Race Condition Safety
@@ -831,7 +1041,7 @@ fn ensure_shadow_collection(
     branch_id: strata_core::types::BranchId,
     name: &str,
 ) {
-    use strata_core::primitives::vector::VectorConfig;
+    use strata_core::primitives::vector::{DistanceMetric, VectorConfig};
 
     let cache_key = format!("{:?}{}{}", branch_id.as_bytes(), SHADOW_KEY_SEP, name);
     let state = match p.db.extension::<AutoEmbedState>() {
@@ -847,7 +1057,13 @@ fn ensure_shadow_collection(
         return;
     }
 
-    let config = VectorConfig::for_minilm();
+    let config = match VectorConfig::new(p.db.embed_dimension(), DistanceMetric::Cosine) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!(target: "strata::embed", error = %e, "Invalid embedding dimension");
+            return;
+        }
+    };
 
     match p.vector.create_system_collection(branch_id, name, config) {
         Ok(_) => {
@@ -858,6 +1074,15 @@ fn ensure_shadow_collection(
             // Already exists from a previous process run — mark as created
             state.insert(cache_key);
         }
+        Err(strata_engine::vector::VectorError::DimensionMismatch { expected, got }) => {
+            tracing::warn!(
+                target: "strata::embed",
+                collection = name,
+                collection_dimension = expected,
+                model_dimension = got,
+                "Shadow collection was built with a different embedding model"
+            );
+        }
         Err(e) => {
             tracing::warn!(
                 target: "strata::embed",
@@ -45,14 +45,18 @@ pub fn event_append(
 
     // Extract text before payload is consumed
     let text = super::embed_hook::extract_text(&payload);
+    if text.is_some() {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
-    let version = convert_result(
-        p.event
-            .append(&core_branch_id, &space, &event_type, payload),
-    )?;
+    let event =
+        convert_result(
+            p.event
+                .append_event(&core_branch_id, &space, &event_type, payload),
+        )?;
 
     // Best-effort auto-embed after successful write
-    let sequence = bridge::extract_version(&version);
+    let sequence = event.sequence;
     if let Some(ref text) = text {
         let event_key = sequence.to_string();
         super::embed_hook::maybe_embed_text(
@@ -66,7 +70,7 @@ pub fn event_append(
         );
     }
 
-    Ok(Output::Version(sequence))
+    Ok(Output::event_appended(&event))
 }
 
 /// Handle EventGet command.
@@ -227,6 +231,9 @@ pub fn event_batch_append(
             (*idx, text)
         })
         .collect();
+    if embed_data.iter().any(|(_, text)| text.is_some()) {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
     // Build engine entries
     let engine_entries: Vec<(String, strata_core::Value)> = valid_entries
@@ -277,6 +284,13 @@ pub fn event_len(p: &Arc<Primitives>, branch: BranchId, space: String) -> Result
     Ok(Output::Uint(count))
 }
 
+/// Handle EventVerifyChain command.
+pub fn event_verify_chain(p: &Arc<Primitives>, branch: BranchId, space: String) -> Result<Output> {
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    let verification = convert_result(p.event.verify_chain(&core_branch_id, &space))?;
+    Ok(Output::ChainVerification(verification))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
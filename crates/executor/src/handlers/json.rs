@@ -7,11 +7,11 @@ use std::sync::Arc;
 use strata_core::Value;
 
 use crate::bridge::{
-    extract_version, json_to_value, parse_path, to_core_branch_id, validate_key, validate_value,
-    value_to_json, Primitives,
+    extract_version, json_to_value, parse_path, to_core_branch_id, validate_json_limits,
+    validate_json_value, validate_key, value_to_json, Primitives,
 };
 use crate::convert::convert_result;
-use crate::types::{BranchId, VersionedValue};
+use crate::types::{BranchId, JsonDocInfo, VersionedValue};
 use crate::{Error, Output, Result};
 
 /// Validate that a branch exists before performing a write operation (#951).
@@ -82,10 +82,11 @@ pub fn json_set(
     require_branch_exists(p, &branch)?;
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_key(&key))?;
-    convert_result(validate_value(&value, &p.limits))?;
+    convert_result(validate_json_value(&value, p))?;
 
     let json_path = convert_result(parse_path(&path))?;
     let json_value = convert_result(value_to_json(value))?;
+    super::embed_hook::check_embed_backpressure(p)?;
 
     // Single atomic transaction: checks existence, creates if needed, sets at path.
     // Produces exactly 1 WAL append (fixes #973).
@@ -246,7 +247,7 @@ pub fn json_batch_set(
             results[i].error = Some(e.to_string());
             continue;
         }
-        if let Err(e) = validate_value(&entry.value, &p.limits) {
+        if let Err(e) = validate_json_value(&entry.value, p) {
             results[i].error = Some(e.to_string());
             continue;
         }
@@ -264,6 +265,10 @@ pub fn json_batch_set(
                 continue;
             }
         };
+        if let Err(e) = validate_json_limits(p, &json_path, Some(&json_value)) {
+            results[i].error = Some(e.to_string());
+            continue;
+        }
         valid_entries.push((i, entry.key, json_path, json_value));
     }
 
@@ -285,6 +290,7 @@ pub fn json_batch_set(
         .map(|(_, key, path, value)| (key, path, value))
         .collect();
 
+    super::embed_hook::check_embed_backpressure(p)?;
     let engine_results = convert_result(p.json.batch_set_or_create(
         &branch_id,
         &space,
@@ -321,9 +327,24 @@ pub fn json_list(
     prefix: Option<String>,
     cursor: Option<String>,
     limit: u64,
+    include_metadata: bool,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
 
+    if include_metadata {
+        let (docs, cursor) = convert_result(p.json.list_with_metadata(
+            &branch_id,
+            &space,
+            prefix.as_deref(),
+            cursor.as_deref(),
+            limit as usize,
+        ))?;
+        return Ok(Output::JsonListDetailed {
+            docs: docs.into_iter().map(to_doc_info).collect(),
+            cursor,
+        });
+    }
+
     let result = convert_result(p.json.list(
         &branch_id,
         &space,
@@ -338,6 +359,14 @@ pub fn json_list(
     })
 }
 
+fn to_doc_info(info: strata_engine::JsonDocInfo) -> JsonDocInfo {
+    JsonDocInfo {
+        key: info.doc_id,
+        version: info.version,
+        size_bytes: info.size_bytes,
+    }
+}
+
 /// Best-effort: read back the full JSON document and embed its complete text.
 ///
 /// This ensures that partial-path writes (e.g. `$.name`) produce an embedding
@@ -389,8 +418,21 @@ pub fn json_list_at(
     space: String,
     prefix: Option<String>,
     as_of_ts: u64,
+    include_metadata: bool,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
+    if include_metadata {
+        let docs = convert_result(p.json.list_at_with_metadata(
+            &branch_id,
+            &space,
+            prefix.as_deref(),
+            as_of_ts,
+        ))?;
+        return Ok(Output::JsonListDetailed {
+            docs: docs.into_iter().map(to_doc_info).collect(),
+            cursor: None,
+        });
+    }
     let keys = convert_result(
         p.json
             .list_at(&branch_id, &space, prefix.as_deref(), as_of_ts),
@@ -8,8 +8,8 @@ use std::sync::Arc;
 use strata_core::Value;
 
 use crate::bridge::{
-    extract_version, to_core_branch_id, to_versioned_value, validate_key, validate_value,
-    Primitives,
+    extract_version, is_reserved_key, to_core_branch_id, to_versioned_value, validate_key,
+    validate_key_bytes, validate_value, Primitives,
 };
 use crate::convert::convert_result;
 use crate::types::BranchId;
@@ -20,7 +20,7 @@ use crate::{Error, Output, Result};
 /// The default branch is always allowed (it is implicit and not stored in BranchIndex).
 /// For all other branches, checks `BranchIndex::exists()` and returns
 /// `Error::BranchNotFound` if the branch does not exist.
-fn require_branch_exists(p: &Arc<Primitives>, branch: &BranchId) -> Result<()> {
+pub(crate) fn require_branch_exists(p: &Arc<Primitives>, branch: &BranchId) -> Result<()> {
     if branch.is_default() {
         return Ok(());
     }
@@ -33,6 +33,24 @@ fn require_branch_exists(p: &Arc<Primitives>, branch: &BranchId) -> Result<()> {
     Ok(())
 }
 
+/// Checks a string-key write runs before touching storage: the branch
+/// exists, the key is valid, and the value (if any) is within limits.
+///
+/// Shared with [`crate::validate`] so a dry run fails the same way.
+pub(crate) fn check_write(
+    p: &Arc<Primitives>,
+    branch: &BranchId,
+    key: &str,
+    value: Option<&Value>,
+) -> Result<()> {
+    require_branch_exists(p, branch)?;
+    convert_result(validate_key(key))?;
+    match value {
+        Some(value) => convert_result(validate_value(value, &p.limits)),
+        None => Ok(()),
+    }
+}
+
 /// Handle KvGetv command — get full version history for a key.
 pub fn kv_getv(
     p: &Arc<Primitives>,
@@ -65,13 +83,14 @@ pub fn kv_put(
     key: String,
     value: Value,
 ) -> Result<Output> {
-    require_branch_exists(p, &branch)?;
+    check_write(p, &branch, &key, Some(&value))?;
     let branch_id = to_core_branch_id(&branch)?;
-    convert_result(validate_key(&key))?;
-    convert_result(validate_value(&value, &p.limits))?;
 
     // Extract text before the value is consumed by put()
     let text = super::embed_hook::extract_text(&value);
+    if text.is_some() {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
     let version = convert_result(p.kv.put(&branch_id, &space, &key, value))?;
 
@@ -115,6 +134,38 @@ pub fn kv_get_at(
     Ok(Output::Maybe(result))
 }
 
+/// Handle KvPutBytes command.
+///
+/// Binary keys skip the search index and auto-embedding, both of which
+/// identify KV entries by string key.
+pub fn kv_put_bytes(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: Vec<u8>,
+    value: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key_bytes(&key, &p.limits))?;
+    convert_result(validate_value(&value, &p.limits))?;
+    let version = convert_result(p.kv.put_bytes(&branch_id, &space, &key, value))?;
+    Ok(Output::Version(extract_version(&version)))
+}
+
+/// Handle KvGetBytes command.
+pub fn kv_get_bytes(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: Vec<u8>,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key_bytes(&key, &p.limits))?;
+    let result = convert_result(p.kv.get_bytes(&branch_id, &space, &key))?;
+    Ok(Output::Maybe(result))
+}
+
 /// Handle KvDelete command.
 pub fn kv_delete(
     p: &Arc<Primitives>,
@@ -122,9 +173,8 @@ pub fn kv_delete(
     space: String,
     key: String,
 ) -> Result<Output> {
-    require_branch_exists(p, &branch)?;
+    check_write(p, &branch, &key, None)?;
     let branch_id = to_core_branch_id(&branch)?;
-    convert_result(validate_key(&key))?;
     let existed = convert_result(p.kv.delete(&branch_id, &space, &key))?;
 
     // Best-effort remove shadow embedding
@@ -141,6 +191,72 @@ pub fn kv_delete(
     Ok(Output::Bool(existed))
 }
 
+/// Handle KvDeleteIfEquals command.
+pub fn kv_delete_if_equals(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    expected: Value,
+) -> Result<Output> {
+    check_write(p, &branch, &key, None)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    let deleted = convert_result(p.kv.delete_if_equals(&branch_id, &space, &key, &expected))?;
+
+    // Best-effort remove shadow embedding
+    if deleted {
+        super::embed_hook::maybe_remove_embedding(
+            p,
+            branch_id,
+            &space,
+            super::embed_hook::SHADOW_KV,
+            &key,
+        );
+    }
+
+    Ok(Output::Bool(deleted))
+}
+
+/// Handle KvGetOrPut command.
+///
+/// Returns `Maybe(Some(value))` with the existing value, or with `default`
+/// after inserting it.
+pub fn kv_get_or_put(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    default: Value,
+) -> Result<Output> {
+    check_write(p, &branch, &key, Some(&default))?;
+    let branch_id = to_core_branch_id(&branch)?;
+
+    // Extract text before the default is consumed; it is only embedded if inserted
+    let text = super::embed_hook::extract_text(&default);
+    if text.is_some() {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
+
+    let (value, inserted) = convert_result(p.kv.get_or_put(&branch_id, &space, &key, default))?;
+
+    // Best-effort auto-embed, only when this call wrote the value
+    if inserted {
+        if let Some(ref text) = text {
+            super::embed_hook::maybe_embed_text(
+                p,
+                branch_id,
+                &space,
+                super::embed_hook::SHADOW_KV,
+                &key,
+                text,
+                strata_core::EntityRef::kv(branch_id, &key),
+            );
+        }
+    }
+
+    Ok(Output::Maybe(Some(value)))
+}
+
 /// Handle KvList command.
 pub fn kv_list(
     p: &Arc<Primitives>,
@@ -156,7 +272,8 @@ pub fn kv_list(
             convert_result(validate_key(pfx))?;
         }
     }
-    let keys = convert_result(p.kv.list(&branch_id, &space, prefix.as_deref()))?;
+    let mut keys = convert_result(p.kv.list(&branch_id, &space, prefix.as_deref()))?;
+    keys.retain(|k| !is_reserved_key(k));
 
     // Apply cursor-based pagination if limit is present
     if let Some(lim) = limit {
@@ -225,6 +342,9 @@ pub fn kv_batch_put(
             (*idx, key.clone(), text)
         })
         .collect();
+    if embed_data.iter().any(|(_, _, text)| text.is_some()) {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
     // Build engine entries (key, value) pairs
     let engine_entries: Vec<(String, Value)> = valid_entries
@@ -264,6 +384,113 @@ pub fn kv_batch_put(
     Ok(Output::BatchResults(results))
 }
 
+/// Handle KvPutMany command.
+///
+/// All entries are validated up front; any invalid key or value fails the
+/// whole command before anything is written.
+pub fn kv_put_many(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    entries: Vec<crate::types::BatchKvEntry>,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+
+    for entry in &entries {
+        convert_result(validate_key(&entry.key))?;
+        convert_result(validate_value(&entry.value, &p.limits))?;
+    }
+
+    // Extract text for embed hooks BEFORE values are consumed
+    let embed_data: Vec<(String, Option<String>)> = entries
+        .iter()
+        .map(|e| (e.key.clone(), super::embed_hook::extract_text(&e.value)))
+        .collect();
+    if embed_data.iter().any(|(_, text)| text.is_some()) {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
+
+    let engine_entries: Vec<(String, Value)> =
+        entries.into_iter().map(|e| (e.key, e.value)).collect();
+    let engine_results = convert_result(p.kv.batch_put(&branch_id, &space, engine_entries))?;
+
+    let versions = engine_results
+        .iter()
+        .map(|r| match r {
+            Ok(version) => Ok(extract_version(version)),
+            Err(e) => Err(Error::Internal { reason: e.clone() }),
+        })
+        .collect::<Result<Vec<u64>>>()?;
+
+    // Post-commit: fire embed hooks
+    for (key, text) in &embed_data {
+        if let Some(ref text) = text {
+            super::embed_hook::maybe_embed_text(
+                p,
+                branch_id,
+                &space,
+                super::embed_hook::SHADOW_KV,
+                key,
+                text,
+                strata_core::EntityRef::kv(branch_id, key),
+            );
+        }
+    }
+
+    Ok(Output::Versions(versions))
+}
+
+/// Handle KvGetMany command.
+pub fn kv_get_many(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    keys: Vec<String>,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    for key in &keys {
+        convert_result(validate_key(key))?;
+    }
+    let results = convert_result(p.kv.get_many(&branch_id, &space, &keys))?;
+    Ok(Output::MaybeVersionedValues(
+        results
+            .into_iter()
+            .map(|r| r.map(to_versioned_value))
+            .collect(),
+    ))
+}
+
+/// Handle KvDeleteMany command.
+pub fn kv_delete_many(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    keys: Vec<String>,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    for key in &keys {
+        convert_result(validate_key(key))?;
+    }
+    let deleted = convert_result(p.kv.delete_many(&branch_id, &space, &keys))?;
+
+    // Best-effort remove shadow embeddings
+    for (key, existed) in keys.iter().zip(&deleted) {
+        if *existed {
+            super::embed_hook::maybe_remove_embedding(
+                p,
+                branch_id,
+                &space,
+                super::embed_hook::SHADOW_KV,
+                key,
+            );
+        }
+    }
+
+    Ok(Output::Bools(deleted))
+}
+
 /// Handle KvList with as_of timestamp (time-travel read).
 pub fn kv_list_at(
     p: &Arc<Primitives>,
@@ -278,6 +505,7 @@ pub fn kv_list_at(
             convert_result(validate_key(pfx))?;
         }
     }
-    let keys = convert_result(p.kv.list_at(&branch_id, &space, prefix.as_deref(), as_of_ts))?;
+    let mut keys = convert_result(p.kv.list_at(&branch_id, &space, prefix.as_deref(), as_of_ts))?;
+    keys.retain(|k| !is_reserved_key(k));
     Ok(Output::Keys(keys))
 }
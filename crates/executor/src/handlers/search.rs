@@ -40,7 +40,7 @@ fn parse_iso8601_to_micros(s: &str) -> Result<u64> {
 }
 
 /// Parse a TimeRangeInput into (start_micros, end_micros).
-fn parse_time_range(input: &TimeRangeInput) -> Result<(u64, u64)> {
+pub(crate) fn parse_time_range(input: &TimeRangeInput) -> Result<(u64, u64)> {
     let start = parse_iso8601_to_micros(&input.start)?;
     let end = parse_iso8601_to_micros(&input.end)?;
     if start > end {
@@ -55,11 +55,54 @@ fn parse_time_range(input: &TimeRangeInput) -> Result<(u64, u64)> {
 pub fn search(
     p: &Arc<Primitives>,
     branch: BranchId,
-    _space: String,
+    space: String,
     sq: SearchQuery,
 ) -> Result<Output> {
     let core_branch_id = to_core_branch_id(&branch)?;
+    let hybrid = build_hybrid_search(&p.db);
+    let hits = run_search(p, &hybrid, core_branch_id, &space, sq)?;
+    Ok(Output::SearchResults(hits))
+}
+
+/// Handle SearchBatch command: several cross-primitive searches in one call
+///
+/// The queries share one `HybridSearch`, and the text of every hybrid query
+/// is embedded in a single batched forward pass up front instead of once
+/// per query. Results are returned in input order; the first invalid query
+/// fails the whole batch.
+pub fn search_batch(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    searches: Vec<SearchQuery>,
+) -> Result<Output> {
+    let core_branch_id = to_core_branch_id(&branch)?;
+    let hybrid = build_batch_hybrid_search(&p.db, &searches);
+    let results = searches
+        .into_iter()
+        .map(|sq| run_search(p, &hybrid, core_branch_id, &space, sq))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Output::SearchBatchResults(results))
+}
+
+/// Search mode requested by `sq` (default: hybrid for cross-primitive search)
+fn search_mode(sq: &SearchQuery) -> SearchMode {
+    match sq.mode.as_deref() {
+        Some("keyword") => SearchMode::Keyword,
+        Some("hybrid") | None => SearchMode::Hybrid,
+        Some(_) => SearchMode::Hybrid, // unrecognized mode, use default
+    }
+}
 
+/// Run one search through `hybrid`, with expansion and reranking when a
+/// model is configured.
+fn run_search(
+    p: &Arc<Primitives>,
+    hybrid: &HybridSearch,
+    core_branch_id: strata_core::types::BranchId,
+    space: &str,
+    sq: SearchQuery,
+) -> Result<Vec<SearchResultHit>> {
     // Build primitive filter from string names
     let primitive_filter = sq.primitives.as_ref().map(|names| {
         names
@@ -79,7 +122,7 @@ pub fn search(
     // Parse time_range
     let parsed_time_range = sq.time_range.as_ref().map(parse_time_range).transpose()?;
 
-    let mut req = SearchRequest::new(core_branch_id, &sq.query);
+    let mut req = SearchRequest::new(core_branch_id, &sq.query).with_space(space);
     if let Some(top_k) = sq.k {
         req = req.with_k(top_k as usize);
     }
@@ -94,16 +137,14 @@ pub fn search(
     if let Some((start, end)) = parsed_time_range {
         req = req.with_time_range(start, end);
     }
+    if let Some(as_of) = sq.as_of {
+        req = req.with_as_of(as_of);
+    }
+    if let Some(max_edits) = sq.typo_tolerance {
+        req = req.with_typo_tolerance(max_edits);
+    }
 
-    // Set search mode (default: hybrid for cross-primitive search)
-    let mode = match sq.mode.as_deref() {
-        Some("keyword") => SearchMode::Keyword,
-        Some("hybrid") | None => SearchMode::Hybrid,
-        Some(_) => SearchMode::Hybrid, // unrecognized mode, use default
-    };
-    req = req.with_mode(mode);
-
-    let hybrid = build_hybrid_search(&p.db);
+    req = req.with_mode(search_mode(&sq));
 
     // Check if a model is configured for query expansion
     let has_model = has_model_configured(&p.db);
@@ -121,11 +162,16 @@ pub fn search(
     };
 
     let response = if should_expand {
-        // Strong signal detection: cheap BM25 probe BEFORE calling LLM
-        let probe_req = req.clone().with_mode(SearchMode::Keyword);
-        let probe = hybrid.search(&probe_req).map_err(crate::Error::from)?;
+        // Strong signal detection: cheap BM25 probe BEFORE calling LLM.
+        // Without a keyword index there is no BM25 signal to probe.
+        let probe = if p.db.keyword_index_enabled() {
+            let probe_req = req.clone().with_mode(SearchMode::Keyword);
+            Some(hybrid.search(&probe_req).map_err(crate::Error::from)?)
+        } else {
+            None
+        };
 
-        if has_strong_signal(&probe) {
+        if let Some(probe) = probe.filter(has_strong_signal) {
             debug!(
                 target: "strata::search",
                 query = %sq.query,
@@ -183,7 +229,7 @@ pub fn search(
         })
         .collect();
 
-    Ok(Output::SearchResults(results))
+    Ok(results)
 }
 
 /// Check if a model is configured (cheap — no LLM call).
@@ -314,6 +360,65 @@ impl strata_search::QueryEmbedder for IntelligenceEmbedder {
     }
 }
 
+/// Query embedder that answers from embeddings computed in one batch,
+/// falling back to embedding on demand for any other text (e.g. expansions).
+#[cfg(feature = "embed")]
+struct BatchEmbedder {
+    cached: std::collections::HashMap<String, Vec<f32>>,
+    fallback: IntelligenceEmbedder,
+}
+
+#[cfg(feature = "embed")]
+impl strata_search::QueryEmbedder for BatchEmbedder {
+    fn embed(&self, text: &str) -> Option<Vec<f32>> {
+        match self.cached.get(text) {
+            Some(embedding) => Some(embedding.clone()),
+            None => strata_search::QueryEmbedder::embed(&self.fallback, text),
+        }
+    }
+}
+
+/// Build a HybridSearch for a batch, embedding every hybrid query's text in
+/// one pass when the embed feature is active.
+fn build_batch_hybrid_search(
+    db: &Arc<strata_engine::Database>,
+    searches: &[SearchQuery],
+) -> HybridSearch {
+    #[cfg(feature = "embed")]
+    {
+        let mut texts: Vec<&str> = searches
+            .iter()
+            .filter(|sq| search_mode(sq) == SearchMode::Hybrid)
+            .map(|sq| sq.query.as_str())
+            .collect();
+        texts.sort_unstable();
+        texts.dedup();
+        let cached = if texts.is_empty() {
+            Default::default()
+        } else {
+            strata_intelligence::embed::embed_queries(db, &texts)
+                .map(|embeddings| {
+                    texts
+                        .iter()
+                        .map(|t| t.to_string())
+                        .zip(embeddings)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let embedder = Arc::new(BatchEmbedder {
+            cached,
+            fallback: IntelligenceEmbedder { db: db.clone() },
+        });
+        HybridSearch::with_embedder(db.clone(), embedder)
+    }
+    #[cfg(not(feature = "embed"))]
+    {
+        let _ = searches;
+        HybridSearch::new(db.clone())
+    }
+}
+
 /// Build a HybridSearch, injecting the embedder when the embed feature is active.
 fn build_hybrid_search(db: &Arc<strata_engine::Database>) -> HybridSearch {
     #[cfg(feature = "embed")]
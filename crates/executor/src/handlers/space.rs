@@ -25,13 +25,14 @@ pub fn space_create(p: &Arc<Primitives>, branch: BranchId, space: String) -> Res
     Ok(Output::Unit)
 }
 
-/// Handle SpaceDelete command.
-pub fn space_delete(
+/// Checks a space delete runs before deleting anything: the space is not
+/// the default space and, unless forced, is empty.
+pub(crate) fn check_delete(
     p: &Arc<Primitives>,
-    branch: BranchId,
-    space: String,
+    branch: &BranchId,
+    space: &str,
     force: bool,
-) -> Result<Output> {
+) -> Result<()> {
     // Cannot delete the default space
     if space == "default" {
         return Err(Error::ConstraintViolation {
@@ -39,11 +40,10 @@ pub fn space_delete(
         });
     }
 
-    let core_branch_id = to_core_branch_id(&branch)?;
-
     // If not forcing, check that the space is empty
     if !force {
-        let empty = convert_result(p.space.is_empty(core_branch_id, &space))?;
+        let core_branch_id = to_core_branch_id(branch)?;
+        let empty = convert_result(p.space.is_empty(core_branch_id, space))?;
         if !empty {
             return Err(Error::ConstraintViolation {
                 reason: format!(
@@ -53,6 +53,18 @@ pub fn space_delete(
             });
         }
     }
+    Ok(())
+}
+
+/// Handle SpaceDelete command.
+pub fn space_delete(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    force: bool,
+) -> Result<Output> {
+    check_delete(p, &branch, &space, force)?;
+    let core_branch_id = to_core_branch_id(&branch)?;
 
     // Delete all data in the space by scanning+deleting all TypeTag prefixes
     let ns = Namespace::for_branch_space(core_branch_id, &space);
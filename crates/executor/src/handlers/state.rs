@@ -72,6 +72,9 @@ pub fn state_set(
 
     // Extract text before value is consumed
     let text = super::embed_hook::extract_text(&value);
+    if text.is_some() {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
     let version = convert_result(p.state.set(&branch_id, &space, &cell, value))?;
 
@@ -138,6 +141,9 @@ pub fn state_cas(
 
     // Extract text before value is consumed
     let text = super::embed_hook::extract_text(&value);
+    if text.is_some() {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
     let result =
         match expected_counter {
@@ -214,6 +220,9 @@ pub fn state_init(
 
     // Extract text before value is consumed
     let text = super::embed_hook::extract_text(&value);
+    if text.is_some() {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
     let version = convert_result(p.state.init(&branch_id, &space, &cell, value))?;
 
@@ -328,6 +337,9 @@ pub fn state_batch_set(
             (*idx, cell.clone(), text)
         })
         .collect();
+    if embed_data.iter().any(|(_, _, text)| text.is_some()) {
+        super::embed_hook::check_embed_backpressure(p)?;
+    }
 
     let engine_entries: Vec<(String, Value)> = valid_entries
         .into_iter()
@@ -2,19 +2,22 @@
 //!
 //! MVP: upsert, get, delete, search, create_collection, delete_collection, list_collections
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use strata_core::Value;
+use strata_engine::vector::NovelInsert;
 
 use crate::bridge::{
     extract_version, from_engine_metric, is_internal_collection, serde_json_to_value_public,
-    to_core_branch_id, to_engine_filter, to_engine_metric, validate_key,
-    validate_not_internal_collection, validate_vector, value_to_serde_json_public, Primitives,
+    to_core_branch_id, to_engine_filter, to_engine_metadata_schema, to_engine_metric,
+    to_engine_upsert_mode, validate_key, validate_not_internal_collection, validate_vector,
+    value_to_serde_json_public, Primitives,
 };
 use crate::convert::convert_result;
 use crate::types::{
-    BranchId, CollectionInfo, DistanceMetric, MetadataFilter, VectorData, VectorMatch,
-    VersionedVectorData,
+    BranchId, CollectionInfo, DistanceMetric, MetadataFieldType, MetadataFilter, NovelUpsertResult,
+    UpsertMode, VectorData, VectorMatch, VectorUpsertResult, VersionedVectorData,
 };
 use crate::{Output, Result};
 
@@ -22,7 +25,7 @@ use crate::{Output, Result};
 ///
 /// Accepts the `branch_id` so that error messages reference the actual branch
 /// instead of a placeholder UUID.
-fn convert_vector_result<T>(
+pub(crate) fn convert_vector_result<T>(
     r: std::result::Result<T, strata_engine::VectorError>,
     branch_id: strata_core::BranchId,
 ) -> Result<T> {
@@ -63,14 +66,53 @@ fn to_vector_match(m: strata_engine::VectorMatch) -> Result<VectorMatch> {
         key: m.key,
         score: m.score,
         metadata,
+        embedding: None,
     })
 }
 
+/// Convert search results, keeping only the optional fields the caller asked for.
+///
+/// Metadata and embeddings come from the records the search already
+/// resolved, so neither costs an extra read per match.
+fn to_vector_matches(
+    matches: Vec<strata_engine::VectorMatch>,
+    include_metadata: bool,
+    include_vector: bool,
+) -> Result<Output> {
+    let mut results = Vec::with_capacity(matches.len());
+    for mut m in matches {
+        if !include_metadata {
+            m.metadata = None;
+        }
+        let embedding = if include_vector {
+            m.embedding.take()
+        } else {
+            None
+        };
+        let mut result = to_vector_match(m)?;
+        result.embedding = embedding;
+        results.push(result);
+    }
+    Ok(Output::VectorMatches(results))
+}
+
+/// Validate a caller-supplied over-fetch factor, falling back to the engine default.
+pub(crate) fn resolve_overfetch_factor(overfetch_factor: Option<f32>) -> Result<f32> {
+    match overfetch_factor {
+        None => Ok(strata_engine::vector::DEFAULT_OVERFETCH_FACTOR),
+        Some(f) if f.is_finite() && f >= 1.0 => Ok(f),
+        Some(f) => Err(crate::Error::InvalidInput {
+            reason: format!("overfetch_factor must be a finite number >= 1.0, got {}", f),
+        }),
+    }
+}
+
 // =============================================================================
 // Individual Handlers (7 MVP)
 // =============================================================================
 
 /// Handle VectorUpsert command.
+#[allow(clippy::too_many_arguments)]
 pub fn vector_upsert(
     p: &Arc<Primitives>,
     branch: BranchId,
@@ -79,6 +121,7 @@ pub fn vector_upsert(
     key: String,
     vector: Vec<f32>,
     metadata: Option<Value>,
+    mode: UpsertMode,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_key(&key))?;
@@ -89,12 +132,77 @@ pub fn vector_upsert(
         .map(value_to_serde_json_public)
         .transpose()
         .map_err(crate::Error::from)?;
-    let version = convert_vector_result(
-        p.vector
-            .insert(branch_id, &space, &collection, &key, &vector, json_metadata),
+    let upserted = convert_vector_result(
+        p.vector.upsert(
+            branch_id,
+            &space,
+            &collection,
+            &key,
+            &vector,
+            json_metadata,
+            to_engine_upsert_mode(mode),
+        ),
         branch_id,
     )?;
-    Ok(Output::Version(extract_version(&version)))
+    Ok(Output::VectorUpserted(VectorUpsertResult {
+        version: extract_version(&upserted.version),
+        created: upserted.created,
+    }))
+}
+
+/// Handle VectorUpsertIfNovel command.
+#[allow(clippy::too_many_arguments)]
+pub fn vector_upsert_if_novel(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    collection: String,
+    key: String,
+    vector: Vec<f32>,
+    metadata: Option<Value>,
+    min_distance: f32,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    convert_result(validate_not_internal_collection(&collection))?;
+    convert_result(validate_vector(&vector, &p.limits))?;
+    if !min_distance.is_finite() {
+        return Err(crate::Error::InvalidInput {
+            reason: format!("min_distance must be a finite number, got {}", min_distance),
+        });
+    }
+
+    let json_metadata = metadata
+        .map(value_to_serde_json_public)
+        .transpose()
+        .map_err(crate::Error::from)?;
+    let outcome = convert_vector_result(
+        p.vector.insert_if_novel(
+            branch_id,
+            &space,
+            &collection,
+            &key,
+            &vector,
+            json_metadata,
+            min_distance,
+        ),
+        branch_id,
+    )?;
+    let result = match outcome {
+        NovelInsert::Inserted(version) => NovelUpsertResult {
+            inserted: true,
+            key,
+            version: Some(extract_version(&version)),
+            distance: None,
+        },
+        NovelInsert::Duplicate { key, distance } => NovelUpsertResult {
+            inserted: false,
+            key,
+            version: None,
+            distance: Some(distance),
+        },
+    };
+    Ok(Output::NovelUpsert(result))
 }
 
 /// Handle VectorGet command.
@@ -193,25 +301,30 @@ pub fn vector_search(
     k: u64,
     filter: Option<Vec<MetadataFilter>>,
     _metric: Option<DistanceMetric>,
+    overfetch_factor: Option<f32>,
+    include_metadata: Option<bool>,
+    include_vector: bool,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_not_internal_collection(&collection))?;
 
+    let overfetch_factor = resolve_overfetch_factor(overfetch_factor)?;
+
     let engine_filter = filter.as_ref().and_then(|f| to_engine_filter(f));
     let matches = convert_vector_result(
-        p.vector.search(
+        p.vector.search_with_overfetch(
             branch_id,
             &space,
             &collection,
             &query,
             k as usize,
             engine_filter,
+            overfetch_factor,
         ),
         branch_id,
     )?;
 
-    let results: Result<Vec<VectorMatch>> = matches.into_iter().map(to_vector_match).collect();
-    Ok(Output::VectorMatches(results?))
+    to_vector_matches(matches, include_metadata.unwrap_or(true), include_vector)
 }
 
 /// Handle VectorCreateCollection command.
@@ -222,14 +335,20 @@ pub fn vector_create_collection(
     collection: String,
     dimension: u64,
     metric: DistanceMetric,
+    metadata_schema: Option<BTreeMap<String, MetadataFieldType>>,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_not_internal_collection(&collection))?;
 
-    let config = convert_result(strata_core::primitives::VectorConfig::new(
-        dimension as usize,
-        to_engine_metric(metric),
-    ))?;
+    let config = strata_engine::vector::CollectionConfig {
+        vector: convert_result(strata_core::primitives::VectorConfig::new(
+            dimension as usize,
+            to_engine_metric(metric),
+        ))?,
+        hnsw: None,
+        normalize: false,
+        metadata_schema: metadata_schema.map(to_engine_metadata_schema),
+    };
     let versioned = convert_vector_result(
         p.vector
             .create_collection(branch_id, &space, &collection, config),
@@ -238,6 +357,25 @@ pub fn vector_create_collection(
     Ok(Output::Version(extract_version(&versioned.version)))
 }
 
+/// Handle VectorCreateCollectionAuto command.
+pub fn vector_create_collection_auto(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    collection: String,
+    metric: DistanceMetric,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_not_internal_collection(&collection))?;
+
+    let versioned = convert_vector_result(
+        p.vector
+            .create_collection_auto(branch_id, &space, &collection, to_engine_metric(metric)),
+        branch_id,
+    )?;
+    Ok(Output::Version(extract_version(&versioned.version)))
+}
+
 /// Handle VectorDeleteCollection command.
 pub fn vector_delete_collection(
     p: &Arc<Primitives>,
@@ -369,11 +507,16 @@ pub fn vector_search_at(
     k: u64,
     filter: Option<Vec<MetadataFilter>>,
     _metric: Option<DistanceMetric>,
+    overfetch_factor: Option<f32>,
     as_of_ts: u64,
+    include_metadata: Option<bool>,
+    include_vector: bool,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_not_internal_collection(&collection))?;
 
+    let overfetch_factor = resolve_overfetch_factor(overfetch_factor)?;
+
     let engine_filter = filter.as_ref().and_then(|f| to_engine_filter(f));
     let matches = convert_vector_result(
         p.vector.search_at(
@@ -384,12 +527,12 @@ pub fn vector_search_at(
             k as usize,
             engine_filter,
             as_of_ts,
+            overfetch_factor,
         ),
         branch_id,
     )?;
 
-    let results: Result<Vec<VectorMatch>> = matches.into_iter().map(to_vector_match).collect();
-    Ok(Output::VectorMatches(results?))
+    to_vector_matches(matches, include_metadata.unwrap_or(true), include_vector)
 }
 
 /// Handle TimeRange command — get the available time range for a branch.
@@ -19,6 +19,8 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value as JsonValue;
 use strata_core::Value;
 
+use crate::types::JsonPatchOp;
+
 /// Serialize a Value to canonical JSON.
 ///
 /// Handles special cases:
@@ -121,6 +123,69 @@ pub fn json_to_value(json: &JsonValue) -> Result<Value, String> {
     }
 }
 
+/// Convert loosely typed JSON to a Value, coercing scalar strings.
+///
+/// Like [`json_to_value`], but strings anywhere in the document are
+/// converted when they are exactly:
+/// - a JSON integer that fits in an `i64` (`"42"`, `"-7"`) → `Value::Int`
+/// - a JSON number with a fraction or exponent (`"3.5"`, `"1e3"`) →
+///   `Value::Float`
+/// - `"true"` or `"false"` → `Value::Bool`
+///
+/// Anything else stays a string, including padded or signed numbers
+/// (`" 42"`, `"+42"`), leading zeros (`"007"`), integers beyond `i64`,
+/// `"NaN"`/`"inf"` and other spellings of booleans, so identifiers such as
+/// ZIP codes or account numbers are not mangled. Object keys are never
+/// coerced.
+pub fn coerce_json_to_value(json: &JsonValue) -> Result<Value, String> {
+    match json {
+        JsonValue::String(s) => Ok(coerce_str(s)),
+        JsonValue::Array(arr) => {
+            let items: Result<Vec<Value>, String> = arr.iter().map(coerce_json_to_value).collect();
+            Ok(Value::Array(items?))
+        }
+        JsonValue::Object(obj) if !is_special_encoding(obj) => {
+            let map: Result<std::collections::HashMap<String, Value>, String> = obj
+                .iter()
+                .map(|(k, v)| coerce_json_to_value(v).map(|val| (k.clone(), val)))
+                .collect();
+            Ok(Value::Object(map?))
+        }
+        other => json_to_value(other),
+    }
+}
+
+/// Apply the [`coerce_json_to_value`] rules to one string.
+fn coerce_str(s: &str) -> Value {
+    match s {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    // The JSON number grammar rejects signs, leading zeros and special
+    // floats; serde_json would also skip surrounding whitespace.
+    if s.trim() == s {
+        if let Ok(JsonValue::Number(n)) = serde_json::from_str::<JsonValue>(s) {
+            if let Some(i) = n.as_i64() {
+                return Value::Int(i);
+            }
+            // Integers too large for i64 also parse as floats; keep them
+            // as strings rather than lose digits.
+            if s.contains(['.', 'e', 'E']) {
+                if let Some(f) = n.as_f64() {
+                    return Value::Float(f);
+                }
+            }
+        }
+    }
+    Value::String(s.to_string())
+}
+
+/// Whether `obj` is a `$bytes` or `$f64` encoding rather than a document.
+fn is_special_encoding(obj: &serde_json::Map<String, JsonValue>) -> bool {
+    obj.len() == 1 && (obj.contains_key("$bytes") || obj.contains_key("$f64"))
+}
+
 /// Convert a float to JSON, handling special values.
 fn float_to_json(f: f64) -> JsonValue {
     if f.is_nan() {
@@ -193,10 +258,173 @@ impl From<CanonicalValue> for Value {
     }
 }
 
+/// Compute the RFC 6902 patch that turns `before` into `after`.
+///
+/// Objects are compared key by key and arrays index by index; any other
+/// difference replaces the value whole. Equal inputs give an empty patch.
+pub fn diff(before: &JsonValue, after: &JsonValue) -> Vec<JsonPatchOp> {
+    let mut ops = Vec::new();
+    diff_at(before, after, &mut String::new(), &mut ops);
+    ops
+}
+
+fn diff_at(before: &JsonValue, after: &JsonValue, path: &mut String, ops: &mut Vec<JsonPatchOp>) {
+    if before == after {
+        return;
+    }
+    match (before, after) {
+        (JsonValue::Object(b), JsonValue::Object(a)) => {
+            for (key, bv) in b {
+                let len = push_pointer_token(path, key);
+                match a.get(key) {
+                    Some(av) => diff_at(bv, av, path, ops),
+                    None => ops.push(JsonPatchOp::Remove { path: path.clone() }),
+                }
+                path.truncate(len);
+            }
+            for (key, av) in a {
+                if !b.contains_key(key) {
+                    let len = push_pointer_token(path, key);
+                    ops.push(JsonPatchOp::Add {
+                        path: path.clone(),
+                        value: av.clone(),
+                    });
+                    path.truncate(len);
+                }
+            }
+        }
+        (JsonValue::Array(b), JsonValue::Array(a)) => {
+            let common = b.len().min(a.len());
+            for i in 0..common {
+                let len = push_pointer_token(path, &i.to_string());
+                diff_at(&b[i], &a[i], path, ops);
+                path.truncate(len);
+            }
+            for (i, av) in a.iter().enumerate().skip(common) {
+                let len = push_pointer_token(path, &i.to_string());
+                ops.push(JsonPatchOp::Add {
+                    path: path.clone(),
+                    value: av.clone(),
+                });
+                path.truncate(len);
+            }
+            // Remove from the end so earlier indices stay valid
+            for i in (common..b.len()).rev() {
+                let len = push_pointer_token(path, &i.to_string());
+                ops.push(JsonPatchOp::Remove { path: path.clone() });
+                path.truncate(len);
+            }
+        }
+        _ => ops.push(JsonPatchOp::Replace {
+            path: path.clone(),
+            value: after.clone(),
+        }),
+    }
+}
+
+/// Append an escaped JSON Pointer token, returning the previous length.
+fn push_pointer_token(path: &mut String, token: &str) -> usize {
+    let len = path.len();
+    path.push('/');
+    path.push_str(&token.replace('~', "~0").replace('/', "~1"));
+    len
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_diff_objects_and_arrays() {
+        let before = serde_json::json!({"a": 1, "b": {"c": [1, 2, 3]}, "gone": true});
+        let after = serde_json::json!({"a": 2, "b": {"c": [1, 5]}, "new/key": null});
+        let ops = serde_json::to_value(diff(&before, &after)).unwrap();
+        assert_eq!(
+            ops,
+            serde_json::json!([
+                {"op": "replace", "path": "/a", "value": 2},
+                {"op": "replace", "path": "/b/c/1", "value": 5},
+                {"op": "remove", "path": "/b/c/2"},
+                {"op": "remove", "path": "/gone"},
+                {"op": "add", "path": "/new~1key", "value": null},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_diff_equal_and_root_replace() {
+        let v = serde_json::json!({"x": [1, {"y": 2}]});
+        assert!(diff(&v, &v).is_empty());
+        assert_eq!(
+            diff(&serde_json::json!(1), &serde_json::json!("one")),
+            vec![JsonPatchOp::Replace {
+                path: String::new(),
+                value: serde_json::json!("one"),
+            }]
+        );
+        assert_eq!(
+            diff(&serde_json::json!([1]), &serde_json::json!([1, 2])),
+            vec![JsonPatchOp::Add {
+                path: "/1".to_string(),
+                value: serde_json::json!(2),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_coerce_scalar_strings() {
+        let json = serde_json::json!({
+            "int": "42",
+            "neg": "-7",
+            "float": "3.5",
+            "exp": "1e3",
+            "yes": "true",
+            "no": "false",
+            "zip": "007",
+            "plus": "+42",
+            "padded": " 42",
+            "huge": "99999999999999999999",
+            "nan": "NaN",
+            "caps": "TRUE",
+            "nested": ["1", {"b": "false"}],
+            "native": 5,
+        });
+        let Value::Object(map) = coerce_json_to_value(&json).unwrap() else {
+            panic!("expected an object");
+        };
+        assert_eq!(map["int"], Value::Int(42));
+        assert_eq!(map["neg"], Value::Int(-7));
+        assert_eq!(map["float"], Value::Float(3.5));
+        assert_eq!(map["exp"], Value::Float(1000.0));
+        assert_eq!(map["yes"], Value::Bool(true));
+        assert_eq!(map["no"], Value::Bool(false));
+        for kept in ["zip", "plus", "padded", "huge", "nan", "caps"] {
+            assert!(
+                matches!(map[kept], Value::String(_)),
+                "{} was coerced",
+                kept
+            );
+        }
+        assert_eq!(
+            map["nested"],
+            Value::Array(vec![
+                Value::Int(1),
+                Value::Object(
+                    [("b".to_string(), Value::Bool(false))]
+                        .into_iter()
+                        .collect()
+                ),
+            ])
+        );
+        assert_eq!(map["native"], Value::Int(5));
+    }
+
+    #[test]
+    fn test_coerce_keeps_special_encodings() {
+        let json = serde_json::json!({"$f64": "NaN"});
+        assert!(matches!(coerce_json_to_value(&json).unwrap(), Value::Float(f) if f.is_nan()));
+    }
+
     #[test]
     fn test_bytes_round_trip() {
         let original = Value::Bytes(vec![1, 2, 3, 255, 0]);
@@ -58,6 +58,7 @@ pub(crate) mod json;
 mod output;
 mod session;
 mod types;
+mod validate;
 
 // Handler modules
 mod handlers;
@@ -72,31 +73,79 @@ mod tests;
 
 // Core types
 pub use api::{
-    BranchDiffEntry, BranchDiffResult, Branches, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff, Strata,
+    BlobReader, BranchDiffEntry, BranchDiffResult, BranchSize, Branches, ConflictEntry,
+    DiffSummary, ForkInfo, MergeInfo, MergeStrategy, ReadView, Resolution, SpaceDiff, Strata,
+    Trace, TraceTree, TraceType, BLOB_CHUNK_SIZE, DEFAULT_STATE_UPDATE_RETRIES, TRACE_EVENT_TYPE,
 };
 pub use command::Command;
 pub use error::Error;
 pub use executor::Executor;
-pub use output::{EmbedStatusInfo, Output};
+pub use output::{CompactInfo, EmbedStatusInfo, IndexStats, Output};
 pub use session::Session;
 pub use types::*;
 
 // Re-export Value from strata_core so users don't need to import it
 pub use strata_core::Value;
 
+// Re-export Event (return type of Strata::event_append)
+pub use strata_core::Event;
+
+// Re-export ChainVerification (return type of Strata::verify_event_chain)
+pub use strata_core::ChainVerification;
+
+// Re-export PrimitiveType (argument of Strata::exists)
+pub use strata_core::PrimitiveType;
+
+// Re-export Ed25519 key types for signed branch bundles
+#[cfg(feature = "bundle-signing")]
+pub use strata_engine::bundle::{SigningKey, VerifyingKey};
+
+// Re-export clock types accepted by OpenOptions::clock
+pub use strata_core::{Clock, ManualClock, SystemClock, Timestamp};
+
+// Re-export vector storage modes accepted by OpenOptions::vector_storage
+pub use strata_core::VectorStorage;
+
+// Re-export key normalization modes accepted by OpenOptions::key_normalization
+pub use strata_core::KeyNormalization;
+
 // Re-export security types so users don't need strata-security directly
-pub use strata_security::{AccessMode, OpenOptions};
+pub use strata_security::{AccessMode, MissingModelPolicy, OpenOptions};
 
 // Re-export WAL counters (return type of Strata::durability_counters)
 pub use strata_engine::WalCounters;
 
+// Re-export metrics types (return types of Strata::transaction_metrics and
+// Strata::metrics_snapshot)
+pub use strata_engine::{MetricsSnapshot, TransactionMetrics};
+
+// Re-export snapshot metadata (return type of Strata::list_snapshots)
+pub use strata_engine::SnapshotInfo;
+
 // Re-export configuration types so users don't need strata-engine directly
 pub use strata_engine::{ModelConfig, StrataConfig};
 
+// Re-export admin log types (item type of Strata::admin_log)
+pub use strata_engine::admin_log::{AdminAction, AdminEntry};
+
+// Re-export branch handles (item type of Branches::handles)
+pub use strata_engine::BranchHandle;
+
 // Re-export Database and DurabilityMode so users can open/create databases
 // and create sessions without depending on strata-engine directly
 pub use strata_engine::{Database, DurabilityMode};
 
+// Re-export TaskPriority for SetOptions::priority
+pub use strata_engine::TaskPriority;
+
+// Re-export crash-testing and model-verification types (used by
+// Strata::self_test_crash_consistency and Strata::verify_against_model)
+#[cfg(feature = "testing")]
+pub use api::{encode_model_value, MODEL_EVENT_TYPE};
+#[cfg(feature = "testing")]
+pub use strata_durability::testing::{
+    CrashConfig, Operation, ReferenceModel, StateMismatch, VerificationResult,
+};
+
 /// Result type for executor operations
 pub type Result<T> = std::result::Result<T, Error>;
@@ -5,7 +5,7 @@
 //! may differ based on database state).
 
 use serde::{Deserialize, Serialize};
-use strata_core::Value;
+use strata_core::{ChainVerification, Event, Value};
 
 use crate::types::*;
 
@@ -45,6 +45,28 @@ pub enum Output {
     /// Version number
     Version(u64),
 
+    /// Event appended: its sequence number, plus the fields the log assigned
+    /// to it
+    ///
+    /// Fields other than `sequence` default when absent, so the variant can
+    /// grow without breaking readers.
+    EventAppended {
+        /// Assigned sequence number.
+        sequence: u64,
+        /// Append time in microseconds since the Unix epoch.
+        #[serde(default)]
+        timestamp: u64,
+        /// Hash of the preceding event in the chain.
+        #[serde(default)]
+        prev_hash: [u8; 32],
+        /// Hash of this event.
+        #[serde(default)]
+        hash: [u8; 32],
+    },
+
+    /// Event log hash chain verification result
+    ChainVerification(ChainVerification),
+
     /// Boolean result
     Bool(bool),
 
@@ -62,6 +84,12 @@ pub enum Output {
     /// List of keys
     Keys(Vec<String>),
 
+    /// Per-key optional versioned values (positionally maps to the requested keys)
+    MaybeVersionedValues(Vec<Option<VersionedValue>>),
+
+    /// Per-key boolean results (positionally maps to the requested keys)
+    Bools(Vec<bool>),
+
     // ==================== Scan Results ====================
     /// JSON list result with cursor
     JsonListResult {
@@ -71,6 +99,14 @@ pub enum Output {
         cursor: Option<String>,
     },
 
+    /// JSON list result with per-document metadata and cursor
+    JsonListDetailed {
+        /// Matching documents.
+        docs: Vec<JsonDocInfo>,
+        /// Cursor for fetching the next page, if more results exist.
+        cursor: Option<String>,
+    },
+
     // ==================== Search Results ====================
     /// Vector search matches
     VectorMatches(Vec<VectorMatch>),
@@ -82,6 +118,12 @@ pub enum Output {
     /// List of vector collections
     VectorCollectionList(Vec<CollectionInfo>),
 
+    /// Result of a vector upsert
+    VectorUpserted(VectorUpsertResult),
+
+    /// Result of a dedup-aware vector upsert
+    NovelUpsert(NovelUpsertResult),
+
     /// Multiple version numbers (for batch operations)
     Versions(Vec<u64>),
 
@@ -103,6 +145,9 @@ pub enum Output {
         version: u64,
     },
 
+    /// Branch deletion result (storage reclaimed)
+    BranchDeleted(DeleteBranchInfo),
+
     // ==================== Transaction-specific ====================
     /// Transaction info
     TxnInfo(Option<TransactionInfo>),
@@ -133,6 +178,9 @@ pub enum Output {
     /// Search results across primitives
     SearchResults(Vec<SearchResultHit>),
 
+    /// Results of a batched search, one list per query in request order
+    SearchBatchResults(Vec<Vec<SearchResultHit>>),
+
     // ==================== Space ====================
     /// List of space names
     SpaceList(Vec<String>),
@@ -157,6 +205,37 @@ pub enum Output {
 
     /// Embedding pipeline status
     EmbedStatus(EmbedStatusInfo),
+
+    /// Keyword index rebuilt from stored data
+    KeywordIndexRebuilt(IndexStats),
+
+    /// WAL compaction finished
+    Compacted(CompactInfo),
+
+    // ==================== Result Limits ====================
+    /// A list or search result cut short by `max_result_limit` or
+    /// `default_result_limit`; wraps the output holding the results kept
+    Truncated(Box<Output>),
+}
+
+impl Output {
+    /// [`Output::EventAppended`] for an event the log just stored.
+    pub(crate) fn event_appended(event: &Event) -> Output {
+        Output::EventAppended {
+            sequence: event.sequence,
+            timestamp: event.timestamp,
+            prev_hash: event.prev_hash,
+            hash: event.hash,
+        }
+    }
+
+    /// Unwrap [`Output::Truncated`], returning any other output as-is.
+    pub fn untruncated(self) -> Output {
+        match self {
+            Output::Truncated(output) => *output,
+            output => output,
+        }
+    }
 }
 
 /// Snapshot of the embedding pipeline status.
@@ -184,4 +263,31 @@ pub struct EmbedStatusInfo {
     pub scheduler_queue_depth: usize,
     /// Number of tasks currently being executed by scheduler workers.
     pub scheduler_active_tasks: usize,
+    /// Cumulative count of tasks rejected because the scheduler queue was
+    /// full. A growing value means `background_queue_depth` or
+    /// `background_threads` is too small for the workload.
+    pub scheduler_tasks_rejected: u64,
+}
+
+/// Size of the keyword index after a rebuild.
+///
+/// Returned by [`Command::RebuildKeywordIndex`](crate::Command::RebuildKeywordIndex).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexStats {
+    /// Number of documents in the index.
+    pub documents_indexed: u64,
+    /// Number of tokens across all indexed documents, after stemming and
+    /// stopword removal.
+    pub tokens_indexed: u64,
+}
+
+/// What a WAL compaction removed.
+///
+/// Returned by [`Command::Compact`](crate::Command::Compact).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactInfo {
+    /// Number of WAL segments deleted.
+    pub segments_removed: u64,
+    /// Bytes freed on disk by the deleted segments.
+    pub bytes_reclaimed: u64,
 }
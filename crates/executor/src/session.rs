@@ -25,11 +25,13 @@
 use std::sync::Arc;
 
 use strata_core::types::{Key, Namespace, TypeTag};
+use strata_core::JsonPathValue;
 use strata_engine::{Database, Transaction, TransactionContext, TransactionOps};
 use strata_security::AccessMode;
 
 use crate::bridge::{
-    extract_version, json_to_value, parse_path, to_core_branch_id, to_versioned_value,
+    apply_result_limits, extract_version, is_reserved_key, json_to_value, normalize_command_keys,
+    parse_path, to_core_branch_id, to_versioned_value, validate_json_limits, validate_json_value,
     value_to_json,
 };
 use crate::convert::convert_result;
@@ -79,6 +81,8 @@ impl Session {
 
     /// Execute a command, routing through the active transaction when appropriate.
     pub fn execute(&mut self, mut cmd: Command) -> Result<Output> {
+        self.executor.check_open()?;
+
         if self.executor.access_mode() == AccessMode::ReadOnly && cmd.is_write() {
             return Err(Error::AccessDenied {
                 command: cmd.name().to_string(),
@@ -98,8 +102,10 @@ impl Session {
             // Vector write commands are not supported inside a transaction
             // because the engine's vector store is not transactional.
             Command::VectorUpsert { .. }
+            | Command::VectorUpsertIfNovel { .. }
             | Command::VectorDelete { .. }
             | Command::VectorCreateCollection { .. }
+            | Command::VectorCreateCollectionAuto { .. }
             | Command::VectorDeleteCollection { .. }
                 if self.txn_ctx.is_some() =>
             {
@@ -131,16 +137,19 @@ impl Session {
             // Vector commands: writes delegate to executor outside txn,
             // reads are always safe to delegate.
             | Command::VectorUpsert { .. }
+            | Command::VectorUpsertIfNovel { .. }
             | Command::VectorGet { .. }
             | Command::VectorDelete { .. }
             | Command::VectorSearch { .. }
             | Command::VectorCreateCollection { .. }
+            | Command::VectorCreateCollectionAuto { .. }
             | Command::VectorDeleteCollection { .. }
             | Command::VectorListCollections { .. }
             | Command::Ping
             | Command::Info
             | Command::Flush
             | Command::Compact
+            | Command::RebuildKeywordIndex
             | Command::EmbedStatus
             | Command::RetentionApply { .. }
             | Command::RetentionStats { .. }
@@ -149,6 +158,7 @@ impl Session {
             | Command::BranchImport { .. }
             | Command::BranchBundleValidate { .. }
             | Command::Search { .. }
+            | Command::SearchBatch { .. }
             // Space commands: manage spaces at the branch level,
             // not transactional.
             | Command::SpaceList { .. }
@@ -174,7 +184,9 @@ impl Session {
             // The transaction write-set does not maintain per-type indexes, so
             // this always reads from the committed store even during an active
             // transaction.
-            | Command::EventGetByType { .. } => self.executor.execute(cmd),
+            | Command::EventGetByType { .. }
+            // EventVerifyChain walks the committed log in its own snapshot.
+            | Command::EventVerifyChain { .. } => self.executor.execute(cmd),
 
             // Data commands: route through txn if active, else delegate
             _ => {
@@ -207,7 +219,7 @@ impl Session {
         };
 
         let core_branch_id = to_core_branch_id(&branch)?;
-        let ctx = self.db.begin_transaction(core_branch_id);
+        let ctx = self.db.begin_transaction(core_branch_id)?;
         self.txn_ctx = Some(ctx);
         self.txn_branch_id = Some(core_branch_id);
 
@@ -281,8 +293,15 @@ impl Session {
         // Extract space from the command being executed
         let space = match &cmd {
             Command::KvPut { space, .. }
+            | Command::KvPutBytes { space, .. }
+            | Command::KvGetBytes { space, .. }
+            | Command::KvPutMany { space, .. }
+            | Command::KvGetMany { space, .. }
+            | Command::KvDeleteMany { space, .. }
             | Command::KvGet { space, .. }
             | Command::KvDelete { space, .. }
+            | Command::KvDeleteIfEquals { space, .. }
+            | Command::KvGetOrPut { space, .. }
             | Command::KvList { space, .. }
             | Command::KvGetv { space, .. }
             | Command::StateSet { space, .. }
@@ -315,12 +334,15 @@ impl Session {
         result
     }
 
-    fn dispatch_in_txn(
+    pub(crate) fn dispatch_in_txn(
         executor: &Executor,
         ctx: &mut TransactionContext,
         ns: Namespace,
-        cmd: Command,
+        mut cmd: Command,
     ) -> Result<Output> {
+        normalize_command_keys(&mut cmd, executor.primitives().db.key_normalization());
+        let caps = apply_result_limits(&mut cmd, executor.primitives().db.result_limits());
+
         // Read commands use ctx.get() / ctx.scan_prefix() directly so they
         // fall through to the snapshot when the key isn't in the write-set.
         // Write commands create a Transaction which handles event sequencing
@@ -332,6 +354,20 @@ impl Session {
                 let result = ctx.get(&full_key).map_err(Error::from)?;
                 Ok(Output::Maybe(result))
             }
+            Command::KvGetBytes { key, .. } => {
+                let full_key = Key::new_kv(ns, &key);
+                let result = ctx.get(&full_key).map_err(Error::from)?;
+                Ok(Output::Maybe(result))
+            }
+            Command::KvGetMany { keys, .. } => {
+                let mut values = Vec::with_capacity(keys.len());
+                for key in keys {
+                    let full_key = Key::new_kv(ns.clone(), &key);
+                    let result = ctx.get_versioned(&full_key).map_err(Error::from)?;
+                    values.push(result.map(to_versioned_value));
+                }
+                Ok(Output::MaybeVersionedValues(values))
+            }
             Command::KvList {
                 prefix,
                 cursor,
@@ -346,6 +382,7 @@ impl Session {
                 let keys: Vec<String> = entries
                     .into_iter()
                     .filter_map(|(k, _)| k.user_key_string())
+                    .filter(|k| !is_reserved_key(k))
                     .collect();
                 if let Some(lim) = limit {
                     let start_idx = if let Some(ref cur) = cursor {
@@ -354,7 +391,7 @@ impl Session {
                         0
                     };
                     let end_idx = std::cmp::min(start_idx + lim as usize, keys.len());
-                    Ok(Output::Keys(keys[start_idx..end_idx].to_vec()))
+                    Ok(caps.apply(Output::Keys(keys[start_idx..end_idx].to_vec())))
                 } else {
                     Ok(Output::Keys(keys))
                 }
@@ -409,11 +446,11 @@ impl Session {
                     let json_path = convert_result(parse_path(&path))?;
                     let result = txn.json_get_path(&key, &json_path).map_err(Error::from)?;
                     match result {
-                        Some(jv) => {
+                        JsonPathValue::Present(jv) => {
                             let val = convert_result(json_to_value(jv))?;
                             Ok(Output::Maybe(Some(val)))
                         }
-                        None => Ok(Output::Maybe(None)),
+                        JsonPathValue::Missing => Ok(Output::Maybe(None)),
                     }
                 }
             }
@@ -424,6 +461,11 @@ impl Session {
                 let version = txn.kv_put(&key, value).map_err(Error::from)?;
                 Ok(Output::Version(extract_version(&version)))
             }
+            Command::KvPutBytes { key, value, .. } => {
+                let full_key = Key::new_kv(ns, &key);
+                ctx.put(full_key, value).map_err(Error::from)?;
+                Ok(Output::Version(ctx.txn_id))
+            }
             Command::KvDelete { key, .. } => {
                 let full_key = Key::new_kv(ns, &key);
                 let existed = ctx.exists(&full_key).map_err(Error::from)?;
@@ -431,6 +473,46 @@ impl Session {
                 Ok(Output::Bool(existed))
             }
 
+            Command::KvPutMany { entries, .. } => {
+                let mut txn = Transaction::new(ctx, ns);
+                let mut versions = Vec::with_capacity(entries.len());
+                for entry in entries {
+                    let version = txn.kv_put(&entry.key, entry.value).map_err(Error::from)?;
+                    versions.push(extract_version(&version));
+                }
+                Ok(Output::Versions(versions))
+            }
+            Command::KvDeleteMany { keys, .. } => {
+                let mut deleted = Vec::with_capacity(keys.len());
+                for key in keys {
+                    let full_key = Key::new_kv(ns.clone(), &key);
+                    let existed = ctx.exists(&full_key).map_err(Error::from)?;
+                    if existed {
+                        ctx.delete(full_key).map_err(Error::from)?;
+                    }
+                    deleted.push(existed);
+                }
+                Ok(Output::Bools(deleted))
+            }
+            Command::KvDeleteIfEquals { key, expected, .. } => {
+                let full_key = Key::new_kv(ns, &key);
+                let matches = ctx.get(&full_key).map_err(Error::from)? == Some(expected);
+                if matches {
+                    ctx.delete(full_key).map_err(Error::from)?;
+                }
+                Ok(Output::Bool(matches))
+            }
+            Command::KvGetOrPut { key, default, .. } => {
+                let full_key = Key::new_kv(ns, &key);
+                match ctx.get(&full_key).map_err(Error::from)? {
+                    Some(existing) => Ok(Output::Maybe(Some(existing))),
+                    None => {
+                        ctx.put(full_key, default.clone()).map_err(Error::from)?;
+                        Ok(Output::Maybe(Some(default)))
+                    }
+                }
+            }
+
             // === State delete — via ctx ===
             Command::StateDelete { cell, .. } => {
                 let full_key = Key::new_state(ns, &cell);
@@ -449,7 +531,15 @@ impl Session {
                 let version = txn
                     .event_append(&event_type, payload)
                     .map_err(Error::from)?;
-                Ok(Output::Version(extract_version(&version)))
+                let sequence = extract_version(&version);
+                let event = txn
+                    .event_get(sequence)
+                    .map_err(Error::from)?
+                    .ok_or_else(|| Error::Internal {
+                        reason: "Appended event missing from transaction".into(),
+                    })?
+                    .value;
+                Ok(Output::event_appended(&event))
             }
             Command::EventGet { sequence, .. } => {
                 let txn = Transaction::new(ctx, ns);
@@ -515,9 +605,12 @@ impl Session {
             Command::JsonSet {
                 key, path, value, ..
             } => {
-                let mut txn = Transaction::new(ctx, ns);
+                let p = executor.primitives();
+                convert_result(validate_json_value(&value, p))?;
                 let json_path = convert_result(parse_path(&path))?;
                 let json_value = convert_result(value_to_json(value))?;
+                convert_result(validate_json_limits(p, &json_path, Some(&json_value)))?;
+                let mut txn = Transaction::new(ctx, ns);
                 let version = txn
                     .json_set(&key, &json_path, json_value)
                     .map_err(Error::from)?;
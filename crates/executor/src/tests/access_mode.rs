@@ -128,6 +128,7 @@ fn test_read_only_blocks_all_writes() {
             key: "k".into(),
             vector: vec![1.0],
             metadata: None,
+            mode: None,
         },
         Command::VectorDelete {
             branch: None,
@@ -141,6 +142,7 @@ fn test_read_only_blocks_all_writes() {
             collection: "c".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            metadata_schema: None,
         },
         Command::VectorDeleteCollection {
             branch: None,
@@ -163,6 +165,7 @@ fn test_read_only_blocks_all_writes() {
         Command::RetentionApply { branch: None },
         Command::Flush,
         Command::Compact,
+        Command::RebuildKeywordIndex,
     ];
 
     for cmd in write_commands {
@@ -221,6 +224,7 @@ fn test_read_only_allows_all_reads() {
             cursor: None,
             limit: 10,
             as_of: None,
+            include_metadata: false,
         },
         Command::EventGet {
             branch: None,
@@ -240,6 +244,10 @@ fn test_read_only_allows_all_reads() {
             branch: None,
             space: None,
         },
+        Command::EventVerifyChain {
+            branch: None,
+            space: None,
+        },
         Command::StateGet {
             branch: None,
             space: None,
@@ -285,6 +293,8 @@ fn test_read_only_allows_all_reads() {
                 k: None,
                 primitives: None,
                 time_range: None,
+                as_of: None,
+                typo_tolerance: None,
                 mode: None,
                 expand: None,
                 rerank: None,
@@ -379,6 +389,34 @@ fn test_is_write_classification() {
             space: None,
             key: "".into(),
         },
+        Command::KvDeleteIfEquals {
+            branch: None,
+            space: None,
+            key: "".into(),
+            expected: Value::Null,
+        },
+        Command::KvPutMany {
+            branch: None,
+            space: None,
+            entries: vec![],
+        },
+        Command::KvPutBytes {
+            branch: None,
+            space: None,
+            key: vec![0xFF],
+            value: Value::Null,
+        },
+        Command::KvDeleteMany {
+            branch: None,
+            space: None,
+            keys: vec![],
+        },
+        Command::KvGetOrPut {
+            branch: None,
+            space: None,
+            key: "".into(),
+            default: Value::Null,
+        },
         Command::JsonSet {
             branch: None,
             space: None,
@@ -429,6 +467,7 @@ fn test_is_write_classification() {
             key: "".into(),
             vector: vec![],
             metadata: None,
+            mode: None,
         },
         Command::VectorDelete {
             branch: None,
@@ -442,6 +481,7 @@ fn test_is_write_classification() {
             collection: "".into(),
             dimension: 0,
             metric: DistanceMetric::Cosine,
+            metadata_schema: None,
         },
         Command::VectorDeleteCollection {
             branch: None,
@@ -464,9 +504,11 @@ fn test_is_write_classification() {
         Command::RetentionApply { branch: None },
         Command::Flush,
         Command::Compact,
+        Command::RebuildKeywordIndex,
         Command::BranchExport {
             branch_id: "".into(),
             path: "".into(),
+            digest: false,
         },
         Command::BranchImport { path: "".into() },
     ];
@@ -487,6 +529,11 @@ fn test_is_write_classification() {
             key: "".into(),
             as_of: None,
         },
+        Command::KvGetBytes {
+            branch: None,
+            space: None,
+            key: vec![],
+        },
         Command::KvList {
             branch: None,
             space: None,
@@ -521,6 +568,7 @@ fn test_is_write_classification() {
             cursor: None,
             limit: 10,
             as_of: None,
+            include_metadata: false,
         },
         Command::EventGet {
             branch: None,
@@ -540,6 +588,10 @@ fn test_is_write_classification() {
             branch: None,
             space: None,
         },
+        Command::EventVerifyChain {
+            branch: None,
+            space: None,
+        },
         Command::StateGet {
             branch: None,
             space: None,
@@ -574,6 +626,9 @@ fn test_is_write_classification() {
             filter: None,
             metric: None,
             as_of: None,
+            overfetch_factor: None,
+            include_metadata: None,
+            include_vector: false,
         },
         Command::VectorListCollections {
             branch: None,
@@ -605,6 +660,8 @@ fn test_is_write_classification() {
                 k: None,
                 primitives: None,
                 time_range: None,
+                as_of: None,
+                typo_tolerance: None,
                 mode: None,
                 expand: None,
                 rerank: None,
@@ -294,6 +294,7 @@ fn test_vector_search_determinism() {
             collection: "embeddings".to_string(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            metadata_schema: None,
         })
         .unwrap();
 
@@ -309,6 +310,7 @@ fn test_vector_search_determinism() {
                 key: format!("v{}", i),
                 vector: vec,
                 metadata: None,
+                mode: None,
             })
             .unwrap();
     }
@@ -326,6 +328,9 @@ fn test_vector_search_determinism() {
                 filter: None,
                 metric: None,
                 as_of: None,
+                overfetch_factor: None,
+                include_metadata: None,
+                include_vector: false,
             })
         })
         .collect();
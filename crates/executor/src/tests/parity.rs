@@ -221,7 +221,7 @@ fn test_event_append_get_by_type_parity() {
     let (executor, p) = create_test_environment();
     let branch_id = strata_core::types::BranchId::from_bytes([0u8; 16]);
 
-    // Append via executor - EventAppend returns Version
+    // Append via executor - EventAppend returns the appended event
     let result1 = executor.execute(Command::EventAppend {
         branch: None,
         space: None,
@@ -233,10 +233,10 @@ fn test_event_append_get_by_type_parity() {
         ),
     });
 
-    // Just verify it returns a Version
+    // Just verify it returns an EventAppended
     match result1 {
-        Ok(Output::Version(_seq)) => {}
-        other => panic!("Expected Version output, got {:?}", other),
+        Ok(Output::EventAppended { .. }) => {}
+        other => panic!("Expected EventAppended output, got {:?}", other),
     }
 
     // Append via direct primitive
@@ -342,6 +342,7 @@ fn test_vector_create_collection_parity() {
         collection: "embeddings".to_string(),
         dimension: 4,
         metric: DistanceMetric::Cosine,
+        metadata_schema: None,
     });
 
     assert!(result.is_ok());
@@ -378,6 +379,7 @@ fn test_vector_upsert_search_parity() {
             key: "v1".to_string(),
             vector: vec![1.0, 0.0, 0.0, 0.0],
             metadata: None,
+            mode: None,
         })
         .unwrap();
 
@@ -403,6 +405,9 @@ fn test_vector_upsert_search_parity() {
         filter: None,
         metric: None,
         as_of: None,
+        overfetch_factor: None,
+        include_metadata: None,
+        include_vector: false,
     });
 
     match search_result {
@@ -415,6 +420,92 @@ fn test_vector_upsert_search_parity() {
     }
 }
 
+#[test]
+fn test_vector_search_overfetch_factor_parity() {
+    let (executor, p) = create_test_environment();
+    let branch_id = bridge::to_core_branch_id(&BranchId::from("default")).unwrap();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "vecs".to_string(),
+            dimension: 2,
+            metric: DistanceMetric::Euclidean,
+            metadata_schema: None,
+        })
+        .unwrap();
+    for i in 0..20 {
+        let keep = i >= 18;
+        executor
+            .execute(Command::VectorUpsert {
+                branch: None,
+                space: None,
+                collection: "vecs".to_string(),
+                key: format!("v{:02}", i),
+                vector: vec![i as f32, 0.0],
+                metadata: Some(Value::Object(
+                    [("keep".to_string(), Value::Bool(keep))]
+                        .into_iter()
+                        .collect(),
+                )),
+                mode: None,
+            })
+            .unwrap();
+    }
+
+    let search = |overfetch_factor| {
+        executor.execute(Command::VectorSearch {
+            branch: None,
+            space: None,
+            collection: "vecs".to_string(),
+            query: vec![0.0, 0.0],
+            k: 2,
+            filter: Some(vec![MetadataFilter {
+                field: "keep".to_string(),
+                op: FilterOp::Eq,
+                value: Value::Bool(true),
+            }]),
+            metric: None,
+            as_of: None,
+            overfetch_factor,
+            include_metadata: None,
+            include_vector: false,
+        })
+    };
+
+    let direct = p
+        .vector
+        .search_with_overfetch(
+            branch_id,
+            "default",
+            "vecs",
+            &[0.0, 0.0],
+            2,
+            Some(strata_engine::MetadataFilter::new().eq("keep", true)),
+            10.0,
+        )
+        .unwrap();
+    match search(Some(10.0)) {
+        Ok(Output::VectorMatches(matches)) => {
+            let keys: Vec<&str> = matches.iter().map(|m| m.key.as_str()).collect();
+            let direct_keys: Vec<&str> = direct.iter().map(|m| m.key.as_str()).collect();
+            assert_eq!(keys, direct_keys);
+            assert_eq!(keys, vec!["v18", "v19"]);
+        }
+        other => panic!("Expected VectorMatches output, got {:?}", other),
+    }
+
+    assert!(matches!(
+        search(Some(0.5)),
+        Err(crate::Error::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        search(Some(f32::NAN)),
+        Err(crate::Error::InvalidInput { .. })
+    ));
+}
+
 // =============================================================================
 // Branch Parity Tests
 // =============================================================================
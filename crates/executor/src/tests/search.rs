@@ -27,6 +27,8 @@ fn test_search_empty_database() {
             k: None,
             primitives: None,
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: None,
             expand: None,
             rerank: None,
@@ -73,6 +75,8 @@ fn test_search_returns_empty_for_kv_primitive() {
             k: Some(10),
             primitives: Some(vec!["kv".to_string()]),
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: None,
             expand: None,
             rerank: None,
@@ -88,6 +92,47 @@ fn test_search_returns_empty_for_kv_primitive() {
     }
 }
 
+#[test]
+fn test_search_with_typo_tolerance() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "incident".to_string(),
+            value: Value::String("database connection refused".into()),
+        })
+        .unwrap();
+
+    let search = |typo_tolerance| match executor.execute(Command::Search {
+        branch: None,
+        space: None,
+        search: SearchQuery {
+            query: "databse conection".to_string(),
+            k: Some(10),
+            primitives: Some(vec!["kv".to_string()]),
+            time_range: None,
+            as_of: None,
+            typo_tolerance,
+            mode: Some("keyword".to_string()),
+            expand: None,
+            rerank: None,
+        },
+    }) {
+        Ok(Output::SearchResults(hits)) => hits,
+        other => panic!("Expected SearchResults, got {:?}", other),
+    };
+
+    assert!(search(None).is_empty());
+    let hits = search(Some(1));
+    assert_eq!(
+        hits.len(),
+        1,
+        "Misspelled terms should match within one edit"
+    );
+}
+
 #[test]
 fn test_search_with_primitive_filter() {
     let executor = create_executor();
@@ -111,6 +156,8 @@ fn test_search_with_primitive_filter() {
             k: Some(10),
             primitives: Some(vec!["event".to_string()]),
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: None,
             expand: None,
             rerank: None,
@@ -140,6 +187,8 @@ fn test_search_command_infrastructure_works() {
             k: Some(5),
             primitives: None,
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: None,
             expand: None,
             rerank: None,
@@ -168,6 +217,8 @@ fn test_search_with_mode_override() {
             k: None,
             primitives: None,
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: Some("keyword".to_string()),
             expand: None,
             rerank: None,
@@ -184,6 +235,8 @@ fn test_search_with_mode_override() {
             k: None,
             primitives: None,
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: Some("hybrid".to_string()),
             expand: None,
             rerank: None,
@@ -204,6 +257,8 @@ fn test_search_with_expand_rerank_disabled() {
             k: None,
             primitives: None,
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: None,
             expand: Some(false),
             rerank: Some(false),
@@ -211,3 +266,75 @@ fn test_search_with_expand_rerank_disabled() {
     });
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_search_batch_matches_individual_searches() {
+    let executor = create_executor();
+
+    for (key, text) in [
+        ("greeting", "hello world"),
+        ("farewell", "goodbye world"),
+        ("weather", "sunny skies"),
+    ] {
+        executor
+            .execute(Command::KvPut {
+                branch: None,
+                space: None,
+                key: key.to_string(),
+                value: Value::String(text.into()),
+            })
+            .unwrap();
+    }
+
+    let query = |text: &str| SearchQuery {
+        query: text.to_string(),
+        k: Some(10),
+        primitives: Some(vec!["kv".to_string()]),
+        mode: Some("keyword".to_string()),
+        ..Default::default()
+    };
+    let searches = vec![query("world"), query("sunny"), query("nonexistent")];
+
+    let batch = match executor.execute(Command::SearchBatch {
+        branch: None,
+        space: None,
+        searches: searches.clone(),
+    }) {
+        Ok(Output::SearchBatchResults(results)) => results,
+        other => panic!("Expected SearchBatchResults, got {:?}", other),
+    };
+    assert_eq!(batch.len(), 3, "One hit list per query, in input order");
+
+    for (search, batch_hits) in searches.into_iter().zip(&batch) {
+        let single = match executor.execute(Command::Search {
+            branch: None,
+            space: None,
+            search,
+        }) {
+            Ok(Output::SearchResults(hits)) => hits,
+            other => panic!("Expected SearchResults, got {:?}", other),
+        };
+        let entities = |hits: &[crate::types::SearchResultHit]| {
+            hits.iter().map(|h| h.entity.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(entities(batch_hits), entities(&single));
+    }
+    assert_eq!(batch[0].len(), 2);
+    assert_eq!(batch[1].len(), 1);
+    assert!(batch[2].is_empty());
+}
+
+#[test]
+fn test_search_batch_empty() {
+    let executor = create_executor();
+
+    let result = executor.execute(Command::SearchBatch {
+        branch: None,
+        space: None,
+        searches: vec![],
+    });
+    match result {
+        Ok(Output::SearchBatchResults(results)) => assert!(results.is_empty()),
+        other => panic!("Expected SearchBatchResults, got {:?}", other),
+    }
+}
@@ -61,6 +61,11 @@ fn test_command_compact() {
     test_command_round_trip(Command::Compact);
 }
 
+#[test]
+fn test_command_rebuild_keyword_index() {
+    test_command_round_trip(Command::RebuildKeywordIndex);
+}
+
 // =============================================================================
 // KV Command Tests (4 MVP)
 // =============================================================================
@@ -94,6 +99,63 @@ fn test_command_kv_delete() {
     });
 }
 
+#[test]
+fn test_command_kv_many() {
+    test_command_round_trip(Command::KvPutMany {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        entries: vec![BatchKvEntry {
+            key: "a".to_string(),
+            value: Value::Int(1),
+        }],
+    });
+    test_command_round_trip(Command::KvGetMany {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        keys: vec!["a".to_string(), "b".to_string()],
+    });
+    test_command_round_trip(Command::KvDeleteMany {
+        branch: None,
+        space: Some("s".to_string()),
+        keys: vec!["a".to_string()],
+    });
+}
+
+#[test]
+fn test_command_kv_bytes() {
+    test_command_round_trip(Command::KvPutBytes {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        key: vec![0x00, 0xFF, 0x80],
+        value: Value::Int(1),
+    });
+    test_command_round_trip(Command::KvGetBytes {
+        branch: None,
+        space: Some("s".to_string()),
+        key: vec![0x00, 0xFF, 0x80],
+    });
+}
+
+#[test]
+fn test_command_kv_delete_if_equals() {
+    test_command_round_trip(Command::KvDeleteIfEquals {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        key: "test-key".to_string(),
+        expected: Value::String("v".into()),
+    });
+}
+
+#[test]
+fn test_command_kv_get_or_put() {
+    test_command_round_trip(Command::KvGetOrPut {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        key: "test-key".to_string(),
+        default: Value::Int(0),
+    });
+}
+
 #[test]
 fn test_command_kv_list() {
     test_command_round_trip(Command::KvList {
@@ -132,6 +194,30 @@ fn test_command_json_get() {
     });
 }
 
+#[test]
+fn test_command_json_list() {
+    test_command_round_trip(Command::JsonList {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        prefix: Some("user:".to_string()),
+        cursor: None,
+        limit: 10,
+        as_of: None,
+        include_metadata: true,
+    });
+
+    // Older clients omit the flag and get the keys-only listing
+    let json = r#"{"JsonList":{"prefix":null,"cursor":null,"limit":5}}"#;
+    let cmd: Command = serde_json::from_str(json).unwrap();
+    assert!(matches!(
+        cmd,
+        Command::JsonList {
+            include_metadata: false,
+            ..
+        }
+    ));
+}
+
 // =============================================================================
 // Event Command Tests (4 MVP)
 // =============================================================================
@@ -180,6 +266,14 @@ fn test_command_event_len() {
     });
 }
 
+#[test]
+fn test_command_event_verify_chain() {
+    test_command_round_trip(Command::EventVerifyChain {
+        branch: Some(BranchId::from("default")),
+        space: Some("audit".into()),
+    });
+}
+
 // =============================================================================
 // State Command Tests
 // =============================================================================
@@ -222,6 +316,7 @@ fn test_command_vector_upsert() {
                 .into_iter()
                 .collect(),
         )),
+        mode: None,
     });
 }
 
@@ -236,6 +331,9 @@ fn test_command_vector_search() {
         filter: None,
         metric: Some(DistanceMetric::Cosine),
         as_of: None,
+        overfetch_factor: None,
+        include_metadata: None,
+        include_vector: false,
     });
 }
 
@@ -247,6 +345,17 @@ fn test_command_vector_create_collection() {
         collection: "embeddings".to_string(),
         dimension: 384,
         metric: DistanceMetric::Cosine,
+        metadata_schema: None,
+    });
+}
+
+#[test]
+fn test_command_vector_create_collection_auto() {
+    test_command_round_trip(Command::VectorCreateCollectionAuto {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        collection: "embeddings".to_string(),
+        metric: DistanceMetric::Cosine,
     });
 }
 
@@ -317,11 +426,43 @@ fn test_output_uint() {
     test_output_round_trip(Output::Uint(12345));
 }
 
+#[test]
+fn test_output_chain_verification() {
+    test_output_round_trip(Output::ChainVerification(crate::ChainVerification::valid(
+        3,
+    )));
+    test_output_round_trip(Output::ChainVerification(
+        crate::ChainVerification::hash_mismatch(3, 1, "mismatch", [1u8; 32], [2u8; 32]),
+    ));
+}
+
 #[test]
 fn test_output_version() {
     test_output_round_trip(Output::Version(42));
 }
 
+#[test]
+fn test_output_event_appended() {
+    test_output_round_trip(Output::EventAppended {
+        sequence: 7,
+        timestamp: 1_700_000_000_000_000,
+        prev_hash: [1; 32],
+        hash: [2; 32],
+    });
+
+    // Fields beyond the sequence number are optional on the wire
+    let restored: Output = serde_json::from_str(r#"{"EventAppended":{"sequence":7}}"#).unwrap();
+    assert_eq!(
+        restored,
+        Output::EventAppended {
+            sequence: 7,
+            timestamp: 0,
+            prev_hash: [0; 32],
+            hash: [0; 32],
+        }
+    );
+}
+
 #[test]
 fn test_output_maybe_versioned() {
     test_output_round_trip(Output::MaybeVersioned(Some(VersionedValue {
@@ -357,12 +498,40 @@ fn test_output_versioned_values() {
     ]));
 }
 
+#[test]
+fn test_output_maybe_versioned_values() {
+    test_output_round_trip(Output::MaybeVersionedValues(vec![
+        Some(VersionedValue {
+            value: Value::Int(1),
+            version: 1,
+            timestamp: 1000,
+        }),
+        None,
+    ]));
+}
+
+#[test]
+fn test_output_bools() {
+    test_output_round_trip(Output::Bools(vec![true, false]));
+}
+
 #[test]
 fn test_output_vector_matches() {
     test_output_round_trip(Output::VectorMatches(vec![VectorMatch {
         key: "vec1".to_string(),
         score: 0.95,
         metadata: Some(Value::String("test".to_string())),
+        embedding: None,
+    }]));
+}
+
+#[test]
+fn test_output_vector_matches_with_embedding() {
+    test_output_round_trip(Output::VectorMatches(vec![VectorMatch {
+        key: "vec1".to_string(),
+        score: 0.95,
+        metadata: None,
+        embedding: Some(vec![1.0, 0.0, 0.5]),
     }]));
 }
 
@@ -397,6 +566,34 @@ fn test_output_database_info() {
     }));
 }
 
+#[test]
+fn test_output_keyword_index_rebuilt() {
+    test_output_round_trip(Output::KeywordIndexRebuilt(crate::IndexStats {
+        documents_indexed: 42,
+        tokens_indexed: 1337,
+    }));
+}
+
+#[test]
+fn test_output_json_list_detailed() {
+    test_output_round_trip(Output::JsonListDetailed {
+        docs: vec![JsonDocInfo {
+            key: "doc".to_string(),
+            version: 3,
+            size_bytes: 128,
+        }],
+        cursor: Some("doc".to_string()),
+    });
+}
+
+#[test]
+fn test_output_compacted() {
+    test_output_round_trip(Output::Compacted(crate::CompactInfo {
+        segments_removed: 3,
+        bytes_reclaimed: 4096,
+    }));
+}
+
 // =============================================================================
 // Search Command Tests
 // =============================================================================
@@ -411,6 +608,8 @@ fn test_command_search_minimal() {
             k: None,
             primitives: None,
             time_range: None,
+            as_of: None,
+            typo_tolerance: None,
             mode: None,
             expand: None,
             rerank: None,
@@ -431,6 +630,8 @@ fn test_command_search_full() {
                 start: "2026-02-07T00:00:00Z".to_string(),
                 end: "2026-02-09T23:59:59Z".to_string(),
             }),
+            as_of: Some(1_770_600_000_000_000),
+            typo_tolerance: None,
             mode: Some("hybrid".to_string()),
             expand: Some(true),
             rerank: Some(false),
@@ -438,6 +639,60 @@ fn test_command_search_full() {
     });
 }
 
+#[test]
+fn test_command_search_batch() {
+    test_command_round_trip(Command::SearchBatch {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        searches: vec![
+            SearchQuery {
+                query: "refund policy".to_string(),
+                k: Some(5),
+                primitives: None,
+                time_range: None,
+                as_of: None,
+                typo_tolerance: None,
+                mode: Some("hybrid".to_string()),
+                expand: None,
+                rerank: None,
+            },
+            SearchQuery {
+                query: "shipping times".to_string(),
+                k: None,
+                primitives: Some(vec!["json".to_string()]),
+                time_range: None,
+                as_of: None,
+                typo_tolerance: None,
+                mode: None,
+                expand: None,
+                rerank: None,
+            },
+        ],
+    });
+}
+
+#[test]
+fn test_output_search_batch_results() {
+    test_output_round_trip(Output::SearchBatchResults(vec![
+        vec![SearchResultHit {
+            entity: "refunds".to_string(),
+            primitive: "kv".to_string(),
+            score: 0.75,
+            rank: 1,
+            snippet: Some("refund within 30 days".to_string()),
+        }],
+        vec![],
+    ]));
+}
+
+#[test]
+fn test_output_truncated() {
+    test_output_round_trip(Output::Truncated(Box::new(Output::Keys(vec![
+        "a".to_string(),
+        "b".to_string(),
+    ]))));
+}
+
 // =============================================================================
 // Complex Value Serialization Tests
 // =============================================================================
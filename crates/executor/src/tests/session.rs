@@ -198,6 +198,100 @@ fn test_ryw_kv_put_get_inside_txn() {
     session.execute(Command::TxnCommit).unwrap();
 }
 
+#[test]
+fn test_kv_many_inside_txn() {
+    let mut session = create_test_session();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    let result = session
+        .execute(Command::KvPutMany {
+            branch: None,
+            space: None,
+            entries: vec![crate::BatchKvEntry {
+                key: "a".to_string(),
+                value: Value::Int(1),
+            }],
+        })
+        .unwrap();
+    assert!(matches!(result, Output::Versions(ref vs) if vs.len() == 1));
+
+    let result = session
+        .execute(Command::KvGetMany {
+            branch: None,
+            space: None,
+            keys: vec!["a".to_string(), "b".to_string()],
+        })
+        .unwrap();
+    match result {
+        Output::MaybeVersionedValues(vals) => {
+            assert_eq!(vals.len(), 2);
+            assert_eq!(vals[0].as_ref().unwrap().value, Value::Int(1));
+            assert!(vals[1].is_none());
+        }
+        other => panic!("Expected MaybeVersionedValues, got {:?}", other),
+    }
+
+    let result = session
+        .execute(Command::KvDeleteMany {
+            branch: None,
+            space: None,
+            keys: vec!["a".to_string(), "b".to_string()],
+        })
+        .unwrap();
+    assert_eq!(result, Output::Bools(vec![true, false]));
+
+    session.execute(Command::TxnCommit).unwrap();
+}
+
+#[test]
+fn test_kv_get_or_put_inside_txn() {
+    let mut session = create_test_session();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    let get_or_put = |session: &mut Session, default: i64| {
+        session
+            .execute(Command::KvGetOrPut {
+                branch: None,
+                space: None,
+                key: "gop_key".to_string(),
+                default: Value::Int(default),
+            })
+            .unwrap()
+    };
+    assert_eq!(
+        get_or_put(&mut session, 1),
+        Output::Maybe(Some(Value::Int(1)))
+    );
+    assert_eq!(
+        get_or_put(&mut session, 2),
+        Output::Maybe(Some(Value::Int(1)))
+    );
+
+    session.execute(Command::TxnCommit).unwrap();
+
+    let result = session
+        .execute(Command::KvGet {
+            branch: None,
+            space: None,
+            key: "gop_key".to_string(),
+            as_of: None,
+        })
+        .unwrap();
+    assert!(matches!(result, Output::MaybeVersioned(Some(vv)) if vv.value == Value::Int(1)));
+}
+
 #[test]
 fn test_ryw_kv_get_inside_txn() {
     let mut session = create_test_session();
@@ -413,7 +507,10 @@ fn test_event_append_in_txn() {
             Value::String("event_data".into()),
         )])),
     });
-    assert!(result.is_ok(), "EventAppend should succeed in txn");
+    assert!(
+        matches!(result, Ok(Output::EventAppended { .. })),
+        "EventAppend should succeed in txn"
+    );
 
     session.execute(Command::TxnCommit).unwrap();
 }
@@ -0,0 +1,209 @@
+//! Validate tests: verify `Executor::validate` reports the same errors as
+//! `execute` and never writes.
+
+use strata_security::AccessMode;
+
+use crate::types::{BranchId, DistanceMetric};
+use crate::{Command, Error, Executor, Output, Strata, Value};
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Create a cache database with a 3-dimensional "docs" collection.
+fn setup() -> Strata {
+    let strata = Strata::cache().unwrap();
+    strata
+        .vector_create_collection("docs", 3, DistanceMetric::Cosine)
+        .unwrap();
+    strata
+}
+
+fn kv_put(key: &str, value: Value) -> Command {
+    Command::KvPut {
+        branch: None,
+        space: None,
+        key: key.into(),
+        value,
+    }
+}
+
+fn vector_upsert(collection: &str, vector: Vec<f32>) -> Command {
+    Command::VectorUpsert {
+        branch: None,
+        space: None,
+        collection: collection.into(),
+        key: "v1".into(),
+        vector,
+        metadata: None,
+        mode: None,
+    }
+}
+
+fn vector_search(query: Vec<f32>) -> Command {
+    Command::VectorSearch {
+        branch: None,
+        space: None,
+        collection: "docs".into(),
+        query,
+        k: 5,
+        filter: None,
+        metric: None,
+        as_of: None,
+        overfetch_factor: None,
+        include_metadata: None,
+        include_vector: false,
+    }
+}
+
+// =============================================================================
+// Parity with execute
+// =============================================================================
+
+#[test]
+fn test_validate_matches_execute_errors() {
+    let strata = setup();
+    let executor = strata.executor();
+
+    let invalid = vec![
+        kv_put("", Value::Int(1)),
+        Command::KvPut {
+            branch: Some(BranchId::from("missing")),
+            space: None,
+            key: "k".into(),
+            value: Value::Int(1),
+        },
+        Command::KvPutBytes {
+            branch: None,
+            space: None,
+            key: vec![b'k'; 4096],
+            value: Value::Int(1),
+        },
+        Command::JsonSet {
+            branch: None,
+            space: None,
+            key: "doc".into(),
+            path: "$..[".into(),
+            value: Value::Int(1),
+        },
+        vector_upsert("docs", vec![1.0, 0.0]),
+        vector_upsert("missing", vec![1.0, 0.0, 0.0]),
+        vector_upsert("_internal", vec![1.0, 0.0, 0.0]),
+        vector_search(vec![1.0, 0.0]),
+        Command::VectorSearch {
+            branch: None,
+            space: None,
+            collection: "docs".into(),
+            query: vec![1.0, 0.0, 0.0],
+            k: 5,
+            filter: None,
+            metric: None,
+            as_of: None,
+            overfetch_factor: Some(f32::NAN),
+            include_metadata: None,
+            include_vector: false,
+        },
+        Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "empty".into(),
+            dimension: 0,
+            metric: DistanceMetric::Cosine,
+            metadata_schema: None,
+        },
+        Command::BranchCreate {
+            branch_id: Some("bad\0name".into()),
+            metadata: None,
+        },
+        Command::BranchDelete {
+            branch: BranchId::from("default"),
+        },
+        Command::BranchDelete {
+            branch: BranchId::from("missing"),
+        },
+        Command::SpaceDelete {
+            branch: None,
+            space: "default".into(),
+            force: true,
+        },
+        Command::TxnCommit,
+    ];
+
+    for cmd in invalid {
+        let validated = executor.validate(&cmd);
+        let executed = executor.execute(cmd.clone());
+        assert!(validated.is_err(), "{} should fail validation", cmd.name());
+        assert_eq!(
+            validated.unwrap_err(),
+            executed.unwrap_err(),
+            "{} validation and execution disagree",
+            cmd.name()
+        );
+    }
+}
+
+#[test]
+fn test_validate_accepts_valid_commands_without_writing() {
+    let strata = setup();
+    let executor = strata.executor();
+
+    let valid = vec![
+        kv_put("k", Value::Int(1)),
+        Command::JsonSet {
+            branch: None,
+            space: None,
+            key: "doc".into(),
+            path: "$.a".into(),
+            value: Value::Int(1),
+        },
+        Command::StateSet {
+            branch: None,
+            space: None,
+            cell: "cell".into(),
+            value: Value::Int(1),
+        },
+        vector_upsert("docs", vec![1.0, 0.0, 0.0]),
+        vector_search(vec![1.0, 0.0, 0.0]),
+        Command::BranchCreate {
+            branch_id: Some("feature".into()),
+            metadata: None,
+        },
+        Command::SpaceCreate {
+            branch: None,
+            space: "tenant".into(),
+        },
+    ];
+
+    for cmd in &valid {
+        assert!(
+            executor.validate(cmd).is_ok(),
+            "{} should pass validation",
+            cmd.name()
+        );
+    }
+
+    assert_eq!(strata.kv_get("k").unwrap(), None);
+    assert!(strata.vector_get("docs", "v1").unwrap().is_none());
+    assert!(!strata.branches().exists("feature").unwrap());
+    assert_eq!(
+        executor
+            .execute(Command::SpaceList { branch: None })
+            .unwrap(),
+        Output::SpaceList(vec!["default".to_string()])
+    );
+}
+
+#[test]
+fn test_validate_read_only_rejects_writes() {
+    let strata = setup();
+    let db = strata.executor().primitives().db.clone();
+    let executor = Executor::new_with_mode(db, AccessMode::ReadOnly);
+
+    match executor.validate(&kv_put("k", Value::Int(1))) {
+        Err(Error::AccessDenied { command }) => assert_eq!(command, "KvPut"),
+        other => panic!("expected AccessDenied, got {:?}", other),
+    }
+    assert!(executor
+        .validate(&vector_search(vec![1.0, 0.0, 0.0]))
+        .is_ok());
+}
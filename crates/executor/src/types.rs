@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use strata_core::Value;
+use strata_engine::TaskPriority;
 
 // =============================================================================
 // Branch Types
@@ -89,6 +90,21 @@ pub struct VersionedBranchInfo {
     pub timestamp: u64,
 }
 
+/// Information about a branch deletion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeleteBranchInfo {
+    /// Deleted branch name.
+    pub branch: String,
+    /// Number of keys physically removed from storage.
+    pub keys_reclaimed: u64,
+    /// Approximate bytes freed in memory and on disk.
+    pub bytes_reclaimed: u64,
+    /// Whether the branch's versions stay in memory until snapshots that
+    /// can read them are released; they are not counted above.
+    #[serde(default)]
+    pub reclaim_pending: bool,
+}
+
 // =============================================================================
 // Versioned Types
 // =============================================================================
@@ -104,6 +120,34 @@ pub struct VersionedValue {
     pub timestamp: u64,
 }
 
+/// One RFC 6902 JSON Patch operation.
+///
+/// Serializes in the RFC form, e.g. `{"op":"replace","path":"/a/0","value":1}`.
+/// Paths are JSON Pointers (RFC 6901); values use canonical JSON encoding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum JsonPatchOp {
+    /// Insert a value.
+    Add {
+        /// Target location.
+        path: String,
+        /// Value to insert.
+        value: serde_json::Value,
+    },
+    /// Remove the value at a location.
+    Remove {
+        /// Target location.
+        path: String,
+    },
+    /// Replace the value at a location.
+    Replace {
+        /// Target location.
+        path: String,
+        /// New value.
+        value: serde_json::Value,
+    },
+}
+
 // =============================================================================
 // Vector Types
 // =============================================================================
@@ -119,6 +163,31 @@ pub enum DistanceMetric {
     Euclidean,
     /// Dot product similarity.
     DotProduct,
+    /// Manhattan (L1) distance.
+    Manhattan,
+}
+
+/// How a vector upsert treats a key that already holds a vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpsertMode {
+    /// Overwrite the embedding and metadata, keeping the vector's internal id (default).
+    #[default]
+    Replace,
+    /// Fail with a constraint violation and leave the existing vector untouched.
+    Error,
+}
+
+/// Type of a filterable metadata field declared on a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataFieldType {
+    /// JSON string.
+    String,
+    /// JSON number.
+    Number,
+    /// JSON boolean.
+    Bool,
 }
 
 /// Metadata filter for vector search
@@ -148,10 +217,12 @@ pub enum FilterOp {
     Lt,
     /// Less than or equal.
     Lte,
-    /// Value is in a set.
+    /// Value is in a set (`value` is an array, or a single scalar).
     In,
     /// String/array contains value.
     Contains,
+    /// Value is not in a set (`value` is an array, or a single scalar).
+    NotIn,
 }
 
 /// Vector data (embedding + metadata)
@@ -176,6 +247,17 @@ pub struct VersionedVectorData {
     pub timestamp: u64,
 }
 
+/// JSON document metadata returned by a detailed listing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonDocInfo {
+    /// Document key.
+    pub key: String,
+    /// Document version (increments on any change).
+    pub version: u64,
+    /// Size of the stored document in bytes.
+    pub size_bytes: u64,
+}
+
 /// Vector search match result
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VectorMatch {
@@ -183,8 +265,22 @@ pub struct VectorMatch {
     pub key: String,
     /// Similarity score (higher is more similar).
     pub score: f32,
-    /// Optional metadata of the matched vector.
+    /// Metadata of the matched vector, if requested and present.
     pub metadata: Option<Value>,
+    /// Embedding of the matched vector, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// Match from a search across every collection, tagged with its collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionVectorMatch {
+    /// Collection the matched vector belongs to.
+    pub collection: String,
+    /// Reciprocal Rank Fusion score across collections (higher is better).
+    pub rrf_score: f32,
+    /// The match within its collection, scored by that collection's metric.
+    pub vector_match: VectorMatch,
 }
 
 /// Vector collection information
@@ -217,6 +313,85 @@ pub struct BatchVectorEntry {
     pub metadata: Option<Value>,
 }
 
+/// Result of a vector upsert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorUpsertResult {
+    /// Version of the write.
+    pub version: u64,
+    /// Whether the key was new; `false` if an existing vector was replaced.
+    pub created: bool,
+}
+
+/// Result of a dedup-aware vector upsert.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NovelUpsertResult {
+    /// Whether the vector was written.
+    pub inserted: bool,
+    /// The inserted key, or the key of the near-duplicate that blocked it.
+    pub key: String,
+    /// Version of the write, if one happened.
+    pub version: Option<u64>,
+    /// Distance to the near-duplicate, if the insert was skipped.
+    pub distance: Option<f32>,
+}
+
+/// A vector yielded by [`Strata::vector_scan`](crate::Strata::vector_scan):
+/// `(key, embedding, metadata)`.
+pub type ScannedVector = (String, Vec<f32>, Option<Value>);
+
+/// Result of re-embedding a vector collection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReembedStats {
+    /// Number of vectors given a new embedding.
+    pub reembedded: u64,
+    /// Keys of vectors left unchanged because their metadata had no
+    /// string at the source text field.
+    pub skipped: Vec<String>,
+}
+
+/// Per-call options for writes that embed text
+///
+/// The default runs embedding at [`TaskPriority::Normal`], the priority of
+/// auto-embed batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetOptions {
+    priority: TaskPriority,
+}
+
+impl Default for SetOptions {
+    fn default() -> Self {
+        Self {
+            priority: TaskPriority::Normal,
+        }
+    }
+}
+
+impl SetOptions {
+    /// Options with the default priority
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Priority of the embedding task on the background scheduler
+    ///
+    /// `High` lets an interactive write jump ahead of queued bulk work
+    /// such as [`Strata::vector_reembed`](crate::Strata::vector_reembed),
+    /// which runs at `Low`. Lower priorities still run under sustained
+    /// high-priority load: the scheduler forces its oldest task to run
+    /// after it has been passed over
+    /// [`STARVATION_LIMIT`](strata_engine::STARVATION_LIMIT)
+    /// times.
+    pub fn priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The configured embedding priority
+    pub fn embed_priority(&self) -> TaskPriority {
+        self.priority
+    }
+}
+
 // =============================================================================
 // Batch Types
 // =============================================================================
@@ -321,6 +496,165 @@ pub struct DatabaseInfo {
     pub total_keys: u64,
 }
 
+/// Version of the `Command`/`Output` serialization format.
+///
+/// Bumped on incompatible changes to the serialized shape of commands or
+/// outputs, so SDKs can refuse to talk to a build they do not understand.
+///
+/// - 2: `EventAppend` returns `Output::EventAppended { sequence, .. }`
+///   instead of `Output::Version` with its sequence number. Fields after
+///   `sequence` are optional and may be added to.
+/// - 3: `KvList`, `VectorSearch`, `Search` and `SearchBatch` results cut by
+///   a result limit come wrapped in `Output::Truncated`.
+/// - 4: `VectorUpsert` returns `Output::VectorUpserted { version, created }`
+///   instead of `Output::Version`, and `BranchDelete` returns
+///   `Output::BranchDeleted` with the storage reclaimed instead of
+///   `Output::Unit`.
+pub const WIRE_FORMAT_VERSION: u32 = 4;
+
+/// Distance metrics accepted by vector collections.
+pub const SUPPORTED_DISTANCE_METRICS: &[DistanceMetric] = &[
+    DistanceMetric::Cosine,
+    DistanceMetric::Euclidean,
+    DistanceMetric::DotProduct,
+    DistanceMetric::Manhattan,
+];
+
+/// Features, limits and format versions of this build.
+///
+/// Returned by [`Strata::capabilities`](crate::Strata::capabilities). SDKs
+/// can read it at runtime instead of hardcoding limits. Every field is
+/// `Copy` or `'static`, so building one does not allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    /// Database engine version string.
+    pub version: &'static str,
+    /// Version of the `Command`/`Output` serialization format.
+    pub wire_format_version: u32,
+    /// On-disk WAL segment format version.
+    pub wal_format_version: u32,
+    /// On-disk snapshot format version.
+    pub snapshot_format_version: u32,
+    /// Branch bundle (export/import) format version.
+    pub bundle_format_version: u32,
+    /// Size limits enforced on writes.
+    pub limits: CapabilityLimits,
+    /// Distance metrics accepted by vector collections.
+    pub distance_metrics: &'static [DistanceMetric],
+    /// Whether this build includes the embedding model runtime.
+    ///
+    /// A model may still need to be downloaded before `embed` succeeds.
+    pub embedding: bool,
+}
+
+/// Size limits reported by [`Capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityLimits {
+    /// Maximum key length in bytes.
+    pub max_key_bytes: usize,
+    /// Maximum string value length in bytes.
+    pub max_string_bytes: usize,
+    /// Maximum bytes value length.
+    pub max_bytes_len: usize,
+    /// Maximum encoded value size in bytes.
+    pub max_value_bytes: usize,
+    /// Maximum nesting depth of arrays and objects.
+    pub max_nesting_depth: usize,
+    /// Maximum vector dimension.
+    pub max_vector_dim: usize,
+}
+
+/// Overall result of a [`HealthReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// The probe succeeded and nothing is impaired.
+    Healthy,
+    /// The probe succeeded, but a feature is unavailable or the write path
+    /// could not be probed; see [`HealthReport::issues`].
+    Degraded,
+}
+
+/// Result of [`Strata::health_check`](crate::Strata::health_check).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// `Degraded` whenever `issues` is non-empty.
+    pub status: HealthStatus,
+    /// Time spent on the probe in microseconds.
+    pub latency_us: u64,
+    /// Whether the probe committed a write. False on read-only handles and
+    /// while background recovery is running, when only reads are probed.
+    pub write_checked: bool,
+    /// Whether the WAL was fsynced after the probe write. Always false for
+    /// cache databases.
+    pub wal_synced: bool,
+    /// Durability mode: `"standard"`, `"always"` or `"cache"`.
+    pub durability: String,
+    /// Why the database is degraded, one entry per problem.
+    pub issues: Vec<String>,
+}
+
+/// A held snapshot, from
+/// [`Strata::active_snapshots`](crate::Strata::active_snapshots).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDebugInfo {
+    /// What holds the snapshot.
+    #[serde(flatten)]
+    pub holder: SnapshotHolder,
+    /// Version the snapshot reads at.
+    pub version: u64,
+    /// How long the snapshot has been held, in milliseconds.
+    pub age_ms: u64,
+}
+
+/// What holds a [`SnapshotDebugInfo`] snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SnapshotHolder {
+    /// An open transaction.
+    Transaction {
+        /// Transaction holding the snapshot.
+        txn_id: u64,
+        /// Branch the transaction runs on: its name, or its UUID if no
+        /// branch with that ID exists anymore.
+        branch: String,
+    },
+    /// Snapshots pinned in storage by a bundle export, a key scan or a
+    /// vector scan. Not tied to a branch.
+    Pinned {
+        /// Number of live snapshots pinning the version. The age is that
+        /// of the first one.
+        count: u64,
+    },
+}
+
+/// Where a key exists, from [`Strata::key_info`](crate::Strata::key_info).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyInfo {
+    /// The key that was looked up.
+    pub key: String,
+    /// One entry per primitive holding the key, in KV, state, JSON order.
+    pub locations: Vec<KeyLocation>,
+}
+
+impl KeyInfo {
+    /// The primitives holding the key.
+    pub fn primitives(&self) -> Vec<strata_core::PrimitiveType> {
+        self.locations.iter().map(|l| l.primitive).collect()
+    }
+}
+
+/// A key's latest version under one primitive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyLocation {
+    /// Primitive holding the key.
+    pub primitive: strata_core::PrimitiveType,
+    /// Latest version, as the primitive's own reads report it.
+    pub version: u64,
+    /// Unix timestamp when that version was written.
+    pub timestamp: u64,
+}
+
 // =============================================================================
 // Bundle Types
 // =============================================================================
@@ -360,6 +694,10 @@ pub struct BundleValidateResult {
     pub entry_count: u64,
     /// Whether all checksums passed validation.
     pub checksums_valid: bool,
+    /// Whether the SHA-256 section hashes match the manifest digest; absent
+    /// if the bundle was exported without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_valid: Option<bool>,
 }
 
 // =============================================================================
@@ -387,12 +725,13 @@ pub struct TimeRangeInput {
 ///   "k": 10,
 ///   "primitives": ["kv", "json", "event"],
 ///   "time_range": { "start": "2026-02-07T00:00:00Z", "end": "2026-02-09T00:00:00Z" },
+///   "typo_tolerance": 1,
 ///   "mode": "hybrid",
 ///   "expand": true,
 ///   "rerank": true
 /// }
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SearchQuery {
     /// Natural-language or keyword query string.
     pub query: String,
@@ -409,6 +748,16 @@ pub struct SearchQuery {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub time_range: Option<TimeRangeInput>,
 
+    /// Point-in-time bound (microseconds since epoch). Excludes data written
+    /// after this timestamp; combined with `time_range`, it caps the range end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<u64>,
+
+    /// Maximum edits per query term for keyword matching (default: 0, exact).
+    /// Lets misspelled terms match; values above 2 are treated as 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typo_tolerance: Option<u8>,
+
     /// Search mode: "keyword" or "hybrid" (default: "hybrid").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
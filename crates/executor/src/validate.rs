@@ -0,0 +1,408 @@
+//! Dry-run validation of commands.
+//!
+//! [`validate_command`] runs the checks a command hits during execution
+//! before anything is written, in the same order and through the same
+//! helpers as the handlers, so it reports the error execution would
+//! return. Where a handler's pre-checks are more than one call they live
+//! in a `check_*` function both sides call. Every command is matched
+//! explicitly, so a new command has to say what it checks; the tests in
+//! `tests/validate.rs` compare both paths.
+
+use std::sync::Arc;
+
+use strata_core::validate_space_name;
+
+use crate::bridge::{
+    parse_path, to_core_branch_id, to_engine_filter, validate_json_limits, validate_json_value,
+    validate_key, validate_key_bytes, validate_not_internal_collection, validate_value,
+    validate_vector, value_to_json, Primitives,
+};
+use crate::convert::convert_result;
+use crate::handlers::branch::validate_branch_name;
+use crate::handlers::kv::{check_write, require_branch_exists};
+use crate::handlers::search::parse_time_range;
+use crate::handlers::vector::{convert_vector_result, resolve_overfetch_factor};
+use crate::types::BranchId;
+use crate::{Command, Error, Result};
+
+/// Check `cmd` as execution would, without changing any state.
+///
+/// Expects a command whose defaults are resolved and keys normalized, as
+/// at dispatch. Batch commands that report a result per item
+/// (`KvBatchPut`, `JsonBatchSet`, `StateBatchSet`, `EventBatchAppend`)
+/// only fail as a whole on a missing branch, so their items pass here.
+pub(crate) fn validate_command(p: &Arc<Primitives>, cmd: &Command) -> Result<()> {
+    match cmd {
+        // KV
+        Command::KvPut {
+            branch, key, value, ..
+        }
+        | Command::KvGetOrPut {
+            branch,
+            key,
+            default: value,
+            ..
+        } => check_write(p, resolved(branch)?, key, Some(value)),
+        Command::KvPutBytes {
+            branch, key, value, ..
+        } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            convert_result(validate_key_bytes(key, &p.limits))?;
+            convert_result(validate_value(value, &p.limits))
+        }
+        Command::KvGetBytes { branch, key, .. } => {
+            resolved(branch)?;
+            convert_result(validate_key_bytes(key, &p.limits))
+        }
+        Command::KvGet { branch, key, .. } | Command::KvGetv { branch, key, .. } => {
+            resolved(branch)?;
+            convert_result(validate_key(key))
+        }
+        Command::KvDelete { branch, key, .. } | Command::KvDeleteIfEquals { branch, key, .. } => {
+            check_write(p, resolved(branch)?, key, None)
+        }
+        Command::KvPutMany {
+            branch, entries, ..
+        } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            for entry in entries {
+                convert_result(validate_key(&entry.key))?;
+                convert_result(validate_value(&entry.value, &p.limits))?;
+            }
+            Ok(())
+        }
+        Command::KvGetMany { branch, keys, .. } => {
+            resolved(branch)?;
+            keys.iter()
+                .try_for_each(|key| convert_result(validate_key(key)))
+        }
+        Command::KvDeleteMany { branch, keys, .. } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            keys.iter()
+                .try_for_each(|key| convert_result(validate_key(key)))
+        }
+        Command::KvList { branch, prefix, .. } | Command::StateList { branch, prefix, .. } => {
+            resolved(branch)?;
+            match prefix {
+                Some(prefix) if !prefix.is_empty() => convert_result(validate_key(prefix)),
+                _ => Ok(()),
+            }
+        }
+        Command::KvBatchPut { branch, .. }
+        | Command::JsonBatchSet { branch, .. }
+        | Command::StateBatchSet { branch, .. }
+        | Command::EventBatchAppend { branch, .. } => require_branch_exists(p, resolved(branch)?),
+
+        // JSON
+        Command::JsonSet {
+            branch,
+            key,
+            path,
+            value,
+            ..
+        } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            convert_result(validate_key(key))?;
+            convert_result(validate_json_value(value, p))?;
+            let path = convert_result(parse_path(path))?;
+            let value = convert_result(value_to_json(value.clone()))?;
+            convert_result(validate_json_limits(p, &path, Some(&value)))
+        }
+        Command::JsonGet {
+            branch, key, path, ..
+        } => {
+            resolved(branch)?;
+            convert_result(validate_key(key))?;
+            check_json_path(p, path)
+        }
+        Command::JsonDelete {
+            branch, key, path, ..
+        } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            convert_result(validate_key(key))?;
+            check_json_path(p, path)
+        }
+        Command::JsonGetv { branch, key, .. } => {
+            resolved(branch)?;
+            convert_result(validate_key(key))
+        }
+        Command::JsonList { branch, .. } => resolved(branch).map(drop),
+
+        // Event
+        Command::EventAppend {
+            branch, payload, ..
+        } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            convert_result(validate_value(payload, &p.limits))
+        }
+        Command::EventGet { branch, .. }
+        | Command::EventGetByType { branch, .. }
+        | Command::EventLen { branch, .. }
+        | Command::EventVerifyChain { branch, .. } => resolved(branch).map(drop),
+
+        // State
+        Command::StateSet {
+            branch,
+            cell,
+            value,
+            ..
+        }
+        | Command::StateCas {
+            branch,
+            cell,
+            value,
+            ..
+        }
+        | Command::StateInit {
+            branch,
+            cell,
+            value,
+            ..
+        } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            convert_result(validate_key(cell))?;
+            convert_result(validate_value(value, &p.limits))
+        }
+        Command::StateDelete { branch, cell, .. } => {
+            require_branch_exists(p, resolved(branch)?)?;
+            convert_result(validate_key(cell))
+        }
+        Command::StateGet { branch, cell, .. } | Command::StateGetv { branch, cell, .. } => {
+            resolved(branch)?;
+            convert_result(validate_key(cell))
+        }
+
+        // Vector
+        Command::VectorUpsert {
+            branch,
+            space,
+            collection,
+            key,
+            vector,
+            ..
+        } => {
+            let branch = resolved(branch)?;
+            convert_result(validate_key(key))?;
+            convert_result(validate_not_internal_collection(collection))?;
+            convert_result(validate_vector(vector, &p.limits))?;
+            check_insert(p, branch, space, collection, key, vector)
+        }
+        Command::VectorUpsertIfNovel {
+            branch,
+            space,
+            collection,
+            key,
+            vector,
+            min_distance,
+            ..
+        } => {
+            let branch = resolved(branch)?;
+            convert_result(validate_key(key))?;
+            convert_result(validate_not_internal_collection(collection))?;
+            convert_result(validate_vector(vector, &p.limits))?;
+            if !min_distance.is_finite() {
+                return Err(Error::InvalidInput {
+                    reason: format!("min_distance must be a finite number, got {}", min_distance),
+                });
+            }
+            check_insert(p, branch, space, collection, key, vector)
+        }
+        Command::VectorGet {
+            branch,
+            collection,
+            key,
+            ..
+        }
+        | Command::VectorDelete {
+            branch,
+            collection,
+            key,
+            ..
+        } => {
+            resolved(branch)?;
+            convert_result(validate_key(key))?;
+            convert_result(validate_not_internal_collection(collection))
+        }
+        Command::VectorSearch {
+            branch,
+            space,
+            collection,
+            query,
+            k,
+            filter,
+            as_of,
+            overfetch_factor,
+            ..
+        } => {
+            let branch = resolved(branch)?;
+            convert_result(validate_not_internal_collection(collection))?;
+            resolve_overfetch_factor(*overfetch_factor)?;
+            let branch_id = to_core_branch_id(branch)?;
+            let filter = filter.as_ref().and_then(|f| to_engine_filter(f));
+            convert_vector_result(
+                p.vector.check_search(
+                    branch_id,
+                    space.as_deref().unwrap_or("default"),
+                    collection,
+                    query,
+                    *k as usize,
+                    filter.as_ref(),
+                    *as_of,
+                ),
+                branch_id,
+            )
+        }
+        Command::VectorCreateCollection {
+            branch,
+            collection,
+            dimension,
+            metric,
+            ..
+        } => {
+            resolved(branch)?;
+            convert_result(validate_not_internal_collection(collection))?;
+            convert_result(strata_core::primitives::VectorConfig::new(
+                *dimension as usize,
+                crate::bridge::to_engine_metric(*metric),
+            ))
+            .map(drop)
+        }
+        Command::VectorCreateCollectionAuto {
+            branch, collection, ..
+        }
+        | Command::VectorDeleteCollection {
+            branch, collection, ..
+        }
+        | Command::VectorCollectionStats {
+            branch, collection, ..
+        } => {
+            resolved(branch)?;
+            convert_result(validate_not_internal_collection(collection))
+        }
+        Command::VectorListCollections { branch, .. } => resolved(branch).map(drop),
+        Command::VectorBatchUpsert {
+            branch,
+            space,
+            collection,
+            entries,
+        } => {
+            let branch = resolved(branch)?;
+            convert_result(validate_not_internal_collection(collection))?;
+            for entry in entries {
+                convert_result(validate_key(&entry.key))?;
+                convert_result(validate_vector(&entry.vector, &p.limits))?;
+            }
+            entries.iter().try_for_each(|entry| {
+                check_insert(p, branch, space, collection, &entry.key, &entry.vector)
+            })
+        }
+
+        // Branch
+        Command::BranchCreate {
+            branch_id: Some(name),
+            ..
+        } => validate_branch_name(name),
+        Command::BranchDelete { branch } => crate::handlers::branch::check_delete(p, branch),
+        Command::BranchCreate {
+            branch_id: None, ..
+        }
+        | Command::BranchGet { .. }
+        | Command::BranchList { .. }
+        | Command::BranchExists { .. } => Ok(()),
+
+        // Bundles are read and written in full when run
+        Command::BranchExport { .. }
+        | Command::BranchImport { .. }
+        | Command::BranchBundleValidate { .. } => Ok(()),
+
+        // Transactions and retention reports are not run by the executor
+        Command::TxnBegin { .. }
+        | Command::TxnCommit
+        | Command::TxnRollback
+        | Command::TxnInfo
+        | Command::TxnIsActive => Err(Error::Internal {
+            reason: "Transaction commands not yet implemented".to_string(),
+        }),
+        Command::RetentionStats { .. } | Command::RetentionPreview { .. } => Err(Error::Internal {
+            reason: "Retention commands not yet implemented".to_string(),
+        }),
+        Command::RetentionApply { branch } => resolved(branch).map(drop),
+
+        // Database
+        Command::Ping
+        | Command::Info
+        | Command::Flush
+        | Command::Compact
+        | Command::RebuildKeywordIndex
+        | Command::EmbedStatus
+        | Command::ConfigureModel { .. } => Ok(()),
+        Command::TimeRange { branch } => resolved(branch).map(drop),
+
+        // Search
+        Command::Search { branch, search, .. } => {
+            resolved(branch)?;
+            check_time_range(search)
+        }
+        Command::SearchBatch {
+            branch, searches, ..
+        } => {
+            resolved(branch)?;
+            searches.iter().try_for_each(check_time_range)
+        }
+
+        // Space
+        Command::SpaceCreate { branch, space } => {
+            resolved(branch)?;
+            validate_space_name(space).map_err(|reason| Error::InvalidInput { reason })
+        }
+        Command::SpaceDelete {
+            branch,
+            space,
+            force,
+        } => crate::handlers::space::check_delete(p, resolved(branch)?, space, *force),
+        Command::SpaceList { branch } | Command::SpaceExists { branch, .. } => {
+            resolved(branch).map(drop)
+        }
+    }
+}
+
+/// The branch a data command runs on, failing like dispatch when defaults
+/// were not resolved.
+fn resolved(branch: &Option<BranchId>) -> Result<&BranchId> {
+    branch.as_ref().ok_or(Error::InvalidInput {
+        reason: "Branch must be specified or resolved to default".into(),
+    })
+}
+
+/// Check a vector write as the vector store does before writing.
+fn check_insert(
+    p: &Arc<Primitives>,
+    branch: &BranchId,
+    space: &Option<String>,
+    collection: &str,
+    key: &str,
+    vector: &[f32],
+) -> Result<()> {
+    let branch_id = to_core_branch_id(branch)?;
+    let space = space.as_deref().unwrap_or("default");
+    convert_vector_result(
+        p.vector
+            .check_insert(branch_id, space, collection, key, vector),
+        branch_id,
+    )
+}
+
+/// Check a JSON path's syntax and length, as the JSON store does before
+/// reading or deleting.
+fn check_json_path(p: &Arc<Primitives>, path: &str) -> Result<()> {
+    let path = convert_result(parse_path(path))?;
+    convert_result(validate_json_limits(p, &path, None))
+}
+
+/// Check the time range of a search query, if any.
+fn check_time_range(search: &crate::types::SearchQuery) -> Result<()> {
+    match &search.time_range {
+        Some(range) => parse_time_range(range).map(drop),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,147 @@
+//! Encoder architecture manifest (`config.json`).
+//!
+//! BERT-family checkpoints on HuggingFace ship a `config.json` next to the
+//! weights describing the architecture. [`EmbedModel`](super::model::EmbedModel)
+//! reads the few fields it needs from it, so any SafeTensors encoder with the
+//! BERT layout can be dropped into the model directory. Directories without a
+//! manifest are assumed to hold MiniLM-L6-v2.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Manifest file name inside the model directory.
+pub const MANIFEST_FILE_NAME: &str = "config.json";
+
+fn default_layer_norm_eps() -> f32 {
+    1e-12
+}
+
+/// Architecture parameters of a BERT-family encoder.
+///
+/// Field names follow the HuggingFace `config.json` convention; unknown
+/// fields are ignored.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ModelManifest {
+    /// Width of the hidden state, which is also the embedding dimension.
+    pub hidden_size: usize,
+    /// Attention heads per layer.
+    pub num_attention_heads: usize,
+    /// Number of transformer encoder layers.
+    pub num_hidden_layers: usize,
+    /// Rows in the word embedding table.
+    pub vocab_size: usize,
+    /// Epsilon used by every LayerNorm.
+    #[serde(default = "default_layer_norm_eps")]
+    pub layer_norm_eps: f32,
+}
+
+impl ModelManifest {
+    /// The bundled MiniLM-L6-v2 architecture.
+    pub fn minilm() -> Self {
+        Self {
+            hidden_size: 384,
+            num_attention_heads: 12,
+            num_hidden_layers: 6,
+            vocab_size: 30522,
+            layer_norm_eps: default_layer_norm_eps(),
+        }
+    }
+
+    /// Read `config.json` from `model_dir`, or fall back to MiniLM when the
+    /// directory has no manifest.
+    pub fn from_dir(model_dir: &Path) -> Result<Self, String> {
+        let path = model_dir.join(MANIFEST_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::minilm());
+        }
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read model manifest '{}': {}", path.display(), e))?;
+        Self::from_json(&text)
+            .map_err(|e| format!("Invalid model manifest '{}': {}", path.display(), e))
+    }
+
+    /// Parse and validate a manifest from JSON text.
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let manifest: Self = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Check that the parameters describe a usable encoder.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.hidden_size == 0 || self.num_attention_heads == 0 {
+            return Err("hidden_size and num_attention_heads must be non-zero".to_string());
+        }
+        if self.hidden_size % self.num_attention_heads != 0 {
+            return Err(format!(
+                "hidden_size {} is not divisible by num_attention_heads {}",
+                self.hidden_size, self.num_attention_heads
+            ));
+        }
+        if self.num_hidden_layers == 0 || self.vocab_size == 0 {
+            return Err("num_hidden_layers and vocab_size must be non-zero".to_string());
+        }
+        Ok(())
+    }
+
+    /// Width of each attention head.
+    pub fn head_dim(&self) -> usize {
+        self.hidden_size / self.num_attention_heads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_huggingface_config() {
+        let json = r#"{
+            "architectures": ["BertModel"],
+            "hidden_size": 768,
+            "num_attention_heads": 12,
+            "num_hidden_layers": 12,
+            "vocab_size": 30522,
+            "layer_norm_eps": 1e-12,
+            "model_type": "bert"
+        }"#;
+        let manifest = ModelManifest::from_json(json).unwrap();
+        assert_eq!(manifest.hidden_size, 768);
+        assert_eq!(manifest.num_hidden_layers, 12);
+        assert_eq!(manifest.head_dim(), 64);
+    }
+
+    #[test]
+    fn test_layer_norm_eps_defaults() {
+        let json = r#"{"hidden_size": 384, "num_attention_heads": 12,
+                       "num_hidden_layers": 6, "vocab_size": 30522}"#;
+        let manifest = ModelManifest::from_json(json).unwrap();
+        assert_eq!(manifest, ModelManifest::minilm());
+    }
+
+    #[test]
+    fn test_rejects_indivisible_heads() {
+        let json = r#"{"hidden_size": 100, "num_attention_heads": 12,
+                       "num_hidden_layers": 6, "vocab_size": 30522}"#;
+        let err = ModelManifest::from_json(json).unwrap_err();
+        assert!(err.contains("divisible"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_manifest_is_minilm() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            ModelManifest::from_dir(dir.path()).unwrap(),
+            ModelManifest::minilm()
+        );
+    }
+
+    #[test]
+    fn test_invalid_manifest_names_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(MANIFEST_FILE_NAME), "{}").unwrap();
+        let err = ModelManifest::from_dir(dir.path()).unwrap_err();
+        assert!(err.contains(MANIFEST_FILE_NAME), "{}", err);
+    }
+}
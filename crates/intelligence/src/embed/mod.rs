@@ -1,21 +1,24 @@
-//! Auto-embedding module: MiniLM-L6-v2 text embeddings.
+//! Auto-embedding module: local BERT-family text embeddings.
 //!
 //! Provides a lazy-loading model lifecycle via [`EmbedModelState`] and
-//! text extraction from Strata [`Value`] types.
+//! text extraction from Strata [`Value`] types. MiniLM-L6-v2 is the default;
+//! other encoders are described by a [`manifest::ModelManifest`].
 
 pub mod download;
 pub mod extract;
+pub mod manifest;
 pub mod model;
 pub mod tokenizer;
 
 use std::path::Path;
 use std::sync::Arc;
 
+use manifest::ModelManifest;
 use model::EmbedModel;
 
 /// Lazy-loading model state stored as a Database extension.
 ///
-/// On first use, loads the MiniLM-L6-v2 model from the model directory.
+/// On first use, loads the embedding model from the model directory.
 /// If model files are missing, stores the error and never retries.
 pub struct EmbedModelState {
     model: once_cell::sync::OnceCell<Result<Arc<EmbedModel>, String>>,
@@ -32,7 +35,8 @@ impl Default for EmbedModelState {
 impl EmbedModelState {
     /// Get or load the embedding model.
     ///
-    /// Loads from `model_dir/model.safetensors` and `model_dir/vocab.txt`.
+    /// Loads from `model_dir/model.safetensors` and `model_dir/vocab.txt`,
+    /// with the architecture from `model_dir/config.json` (MiniLM if absent).
     /// Caches the result (success or failure) so filesystem is probed at most once.
     pub fn get_or_load(&self, model_dir: &Path) -> Result<Arc<EmbedModel>, String> {
         self.model
@@ -56,35 +60,70 @@ impl EmbedModelState {
                     )
                 })?;
 
-                let model = EmbedModel::load(&safetensors_bytes, &vocab_text)?;
+                let manifest = ModelManifest::from_dir(model_dir)?;
+                let model =
+                    EmbedModel::load_with_manifest(&safetensors_bytes, &vocab_text, manifest)?;
                 Ok(Arc::new(model))
             })
             .clone()
     }
 }
 
-/// Embed a query string using the cached MiniLM model from the database.
+/// Load (or fetch the cached) embedding model configured for `db`.
 ///
-/// Loads or retrieves the cached model via [`EmbedModelState`], then embeds the
+/// Fails if the model's output dimension differs from the database's
+/// configured `embed_model.dimension`, since its vectors would not fit the
+/// shadow collections.
+pub fn load_model(db: &strata_engine::Database) -> Result<Arc<EmbedModel>, String> {
+    let state = db
+        .extension::<EmbedModelState>()
+        .map_err(|e| format!("Failed to get embed model state: {}", e))?;
+    let model = state.get_or_load(&db.model_dir())?;
+    check_dimension(&model, db.embed_dimension())?;
+    Ok(model)
+}
+
+fn check_dimension(model: &EmbedModel, expected: usize) -> Result<(), String> {
+    if model.dimension() != expected {
+        return Err(format!(
+            "Embedding model produces {}-dimensional vectors but embed_model.dimension is {}",
+            model.dimension(),
+            expected
+        ));
+    }
+    Ok(())
+}
+
+/// Embed a query string using the cached embedding model from the database.
+///
+/// Loads or retrieves the cached model via [`load_model`], then embeds the
 /// given text. Returns `None` (with a warning log) if the model cannot be loaded
 /// or embedding fails. This is a best-effort helper for hybrid search.
 pub fn embed_query(db: &strata_engine::Database, text: &str) -> Option<Vec<f32>> {
-    let model_dir = db.model_dir();
-    let state = match db.extension::<EmbedModelState>() {
-        Ok(s) => s,
+    let model = match load_model(db) {
+        Ok(m) => m,
         Err(e) => {
-            tracing::warn!(target: "strata::hybrid", error = %e, "Failed to get embed model state");
+            tracing::warn!(target: "strata::hybrid", error = %e, "Failed to load embed model for hybrid search");
             return None;
         }
     };
-    let model = match state.get_or_load(&model_dir) {
+    Some(model.embed(text))
+}
+
+/// Embed several query strings in one batched forward pass.
+///
+/// Like [`embed_query`], but all texts share one
+/// [`EmbedModel::embed_batch`] call. Returns one vector per text, in
+/// order, or `None` (with a warning log) if the model cannot be loaded.
+pub fn embed_queries(db: &strata_engine::Database, texts: &[&str]) -> Option<Vec<Vec<f32>>> {
+    let model = match load_model(db) {
         Ok(m) => m,
         Err(e) => {
             tracing::warn!(target: "strata::hybrid", error = %e, "Failed to load embed model for hybrid search");
             return None;
         }
     };
-    Some(model.embed(text))
+    Some(model.embed_batch(texts))
 }
 
 #[cfg(test)]
@@ -142,4 +181,25 @@ mod tests {
             err
         );
     }
+
+    #[test]
+    fn test_invalid_manifest_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let header = b"{}";
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(header.len() as u64).to_le_bytes());
+        buf.extend_from_slice(header);
+        std::fs::write(dir.path().join("model.safetensors"), &buf).unwrap();
+        std::fs::write(dir.path().join("vocab.txt"), "[PAD]\n").unwrap();
+        std::fs::write(
+            dir.path().join(manifest::MANIFEST_FILE_NAME),
+            r#"{"hidden_size": 10, "num_attention_heads": 3,
+                "num_hidden_layers": 1, "vocab_size": 1}"#,
+        )
+        .unwrap();
+
+        let state = EmbedModelState::default();
+        let err = state.get_or_load(dir.path()).err().unwrap();
+        assert!(err.contains("config.json"), "{}", err);
+    }
 }
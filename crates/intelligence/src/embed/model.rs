@@ -1,4 +1,7 @@
-//! MiniLM-L6-v2 encoder architecture and forward pass.
+//! BERT-family encoder architecture and forward pass.
+//!
+//! Layer count, head count and hidden size come from a
+//! [`ModelManifest`]; the default is MiniLM-L6-v2.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -8,6 +11,7 @@ use crate::runtime::backend::{select_backend, ComputeBackend, DeviceTensor};
 use crate::runtime::safetensors::SafeTensors;
 use crate::runtime::tensor::Tensor;
 
+use super::manifest::ModelManifest;
 use super::tokenizer::{BatchTokenizedInput, TokenizedInput, WordPieceTokenizer};
 
 /// Check once whether STRATA_EMBED_PROFILE=1 is set.
@@ -22,13 +26,6 @@ fn embed_profile_enabled() -> bool {
     ENABLED.load(Ordering::Relaxed)
 }
 
-const HIDDEN_SIZE: usize = 384;
-const NUM_HEADS: usize = 12;
-const HEAD_DIM: usize = HIDDEN_SIZE / NUM_HEADS; // 32
-const NUM_LAYERS: usize = 6;
-const VOCAB_SIZE: usize = 30522;
-const LAYER_NORM_EPS: f32 = 1e-12;
-
 /// A single transformer encoder layer with weights on the compute device.
 struct TransformerLayer {
     q_weight: DeviceTensor,
@@ -49,8 +46,9 @@ struct TransformerLayer {
     output_ln_bias: DeviceTensor,
 }
 
-/// The MiniLM-L6-v2 embedding model.
+/// A BERT-family sentence embedding model (MiniLM-L6-v2 by default).
 pub struct EmbedModel {
+    manifest: ModelManifest,
     tokenizer: WordPieceTokenizer,
     // Embedding tables stay on CPU for gather (avoids uploading 45MB vocab table).
     word_embeddings: Tensor,
@@ -70,7 +68,16 @@ impl EmbedModel {
     /// - HuggingFace BERT: `bert.embeddings.word_embeddings.weight`
     /// - Sentence Transformers: `embeddings.word_embeddings.weight`
     pub fn load(safetensors_bytes: &[u8], vocab_text: &str) -> Result<Self, String> {
-        Self::load_with_backend(safetensors_bytes, vocab_text, select_backend())
+        Self::load_with_manifest(safetensors_bytes, vocab_text, ModelManifest::minilm())
+    }
+
+    /// Load a model whose architecture is described by `manifest`.
+    pub fn load_with_manifest(
+        safetensors_bytes: &[u8],
+        vocab_text: &str,
+        manifest: ModelManifest,
+    ) -> Result<Self, String> {
+        Self::load_with_backend(safetensors_bytes, vocab_text, manifest, select_backend())
     }
 
     /// Load model with a specific compute backend (used for testing).
@@ -80,14 +87,22 @@ impl EmbedModel {
         vocab_text: &str,
         backend: Arc<dyn ComputeBackend>,
     ) -> Result<Self, String> {
-        Self::load_with_backend(safetensors_bytes, vocab_text, backend)
+        Self::load_with_backend(
+            safetensors_bytes,
+            vocab_text,
+            ModelManifest::minilm(),
+            backend,
+        )
     }
 
     fn load_with_backend(
         safetensors_bytes: &[u8],
         vocab_text: &str,
+        manifest: ModelManifest,
         backend: Arc<dyn ComputeBackend>,
     ) -> Result<Self, String> {
+        manifest.validate()?;
+        let hidden_size = manifest.hidden_size;
         let st = SafeTensors::from_bytes(safetensors_bytes)?;
         let tokenizer = WordPieceTokenizer::from_vocab(vocab_text);
 
@@ -105,10 +120,10 @@ impl EmbedModel {
             .tensor(&format!("{}embeddings.word_embeddings.weight", prefix))
             .ok_or("Missing word_embeddings")?;
 
-        if word_embeddings.rows != VOCAB_SIZE || word_embeddings.cols != HIDDEN_SIZE {
+        if word_embeddings.rows != manifest.vocab_size || word_embeddings.cols != hidden_size {
             return Err(format!(
                 "word_embeddings shape mismatch: expected {}x{}, got {}x{}",
-                VOCAB_SIZE, HIDDEN_SIZE, word_embeddings.rows, word_embeddings.cols
+                manifest.vocab_size, hidden_size, word_embeddings.rows, word_embeddings.cols
             ));
         }
 
@@ -116,6 +131,18 @@ impl EmbedModel {
             .tensor(&format!("{}embeddings.position_embeddings.weight", prefix))
             .ok_or("Missing position_embeddings")?;
 
+        if position_embeddings.rows < tokenizer.max_seq_len()
+            || position_embeddings.cols != hidden_size
+        {
+            return Err(format!(
+                "position_embeddings shape mismatch: expected at least {}x{}, got {}x{}",
+                tokenizer.max_seq_len(),
+                hidden_size,
+                position_embeddings.rows,
+                position_embeddings.cols
+            ));
+        }
+
         let token_type_embeddings = st
             .tensor(&format!(
                 "{}embeddings.token_type_embeddings.weight",
@@ -133,8 +160,8 @@ impl EmbedModel {
                 .ok_or("Missing embeddings LayerNorm bias")?,
         );
 
-        let mut layers = Vec::with_capacity(NUM_LAYERS);
-        for i in 0..NUM_LAYERS {
+        let mut layers = Vec::with_capacity(manifest.num_hidden_layers);
+        for i in 0..manifest.num_hidden_layers {
             let lp = format!("{}encoder.layer.{}", prefix, i);
             let layer = TransformerLayer {
                 q_weight: backend.upload(
@@ -208,6 +235,7 @@ impl EmbedModel {
         }
 
         Ok(Self {
+            manifest,
             tokenizer,
             word_embeddings,
             position_embeddings,
@@ -219,11 +247,22 @@ impl EmbedModel {
         })
     }
 
-    /// Embed multiple texts, returning one 384-dimensional vector per input.
+    /// Embedding dimension produced by this model.
+    pub fn dimension(&self) -> usize {
+        self.manifest.hidden_size
+    }
+
+    /// Architecture this model was loaded with.
+    pub fn manifest(&self) -> &ModelManifest {
+        &self.manifest
+    }
+
+    /// Embed multiple texts, returning one [`dimension`](Self::dimension)-sized
+    /// vector per input.
     ///
     /// Uses tensor-batched forward passes: all texts are tokenized together,
-    /// padded to a common length, and processed as a single (B*S, 384) tensor
-    /// through all transformer layers.
+    /// padded to a common length, and processed as a single (B*S, hidden)
+    /// tensor through all transformer layers.
     ///
     /// Callers control batch size via `embed_batch_size` in `OpenOptions` /
     /// `StrataConfig` — smaller batches reduce padding waste on CPU, larger
@@ -370,7 +409,7 @@ impl EmbedModel {
                 &hidden,
                 &self.embed_ln_weight,
                 &self.embed_ln_bias,
-                LAYER_NORM_EPS,
+                self.manifest.layer_norm_eps,
             );
             for layer in &self.layers {
                 hidden = self.transformer_layer_batched(layer, &hidden, &mask, bs, sl);
@@ -430,7 +469,7 @@ impl EmbedModel {
         (input, hidden_cpu)
     }
 
-    /// Embed a text string into a 384-dimensional vector.
+    /// Embed a text string into a [`dimension`](Self::dimension)-sized vector.
     pub fn embed(&self, text: &str) -> Vec<f32> {
         let input = self.tokenizer.tokenize(text);
         let seq_len = input.input_ids.len();
@@ -447,7 +486,7 @@ impl EmbedModel {
             &hidden,
             &self.embed_ln_weight,
             &self.embed_ln_bias,
-            LAYER_NORM_EPS,
+            self.manifest.layer_norm_eps,
         );
 
         // 4. Transformer layers
@@ -458,12 +497,13 @@ impl EmbedModel {
         // 5. Mean pooling (returns host Vec)
         let pooled = self.backend.mean_pool(&hidden, &mask);
 
-        // 6. L2 normalize (CPU, trivial for 384 elements)
+        // 6. L2 normalize (CPU, trivial for a single vector)
         l2_normalize(&pooled)
     }
 
     fn gather_embeddings(&self, input: &TokenizedInput, seq_len: usize) -> Tensor {
-        let mut data = vec![0.0f32; seq_len * HIDDEN_SIZE];
+        let hidden_size = self.manifest.hidden_size;
+        let mut data = vec![0.0f32; seq_len * hidden_size];
 
         for (pos, (&token_id, &type_id)) in input
             .input_ids
@@ -475,20 +515,21 @@ impl EmbedModel {
             let pos_row = self.position_embeddings.row(pos);
             let type_row = self.token_type_embeddings.row(type_id as usize);
 
-            let offset = pos * HIDDEN_SIZE;
-            for i in 0..HIDDEN_SIZE {
+            let offset = pos * hidden_size;
+            for i in 0..hidden_size {
                 data[offset + i] = word_row[i] + pos_row[i] + type_row[i];
             }
         }
 
-        Tensor::from_slice(&data, seq_len, HIDDEN_SIZE)
+        Tensor::from_slice(&data, seq_len, hidden_size)
     }
 
     fn gather_embeddings_batch(&self, input: &BatchTokenizedInput) -> Tensor {
         let bs = input.batch_size;
         let sl = input.max_seq_len;
         let total_rows = bs * sl;
-        let mut data = vec![0.0f32; total_rows * HIDDEN_SIZE];
+        let hidden_size = self.manifest.hidden_size;
+        let mut data = vec![0.0f32; total_rows * hidden_size];
 
         for seq in 0..bs {
             for pos in 0..sl {
@@ -506,14 +547,14 @@ impl EmbedModel {
                 let pos_row = self.position_embeddings.row(pos);
                 let type_row = self.token_type_embeddings.row(type_id);
 
-                let offset = flat_idx * HIDDEN_SIZE;
-                for i in 0..HIDDEN_SIZE {
+                let offset = flat_idx * hidden_size;
+                for i in 0..hidden_size {
                     data[offset + i] = word_row[i] + pos_row[i] + type_row[i];
                 }
             }
         }
 
-        Tensor::from_slice(&data, total_rows, HIDDEN_SIZE)
+        Tensor::from_slice(&data, total_rows, hidden_size)
     }
 
     fn transformer_layer(
@@ -524,6 +565,8 @@ impl EmbedModel {
     ) -> DeviceTensor {
         let seq_len = hidden.rows;
         let b = &self.backend;
+        let num_heads = self.manifest.num_attention_heads;
+        let head_dim = self.manifest.head_dim();
 
         // Self-attention: Q, K, V projections
         // BERT stores weights transposed: shape is (out, in), so we use matmul_transpose
@@ -535,22 +578,22 @@ impl EmbedModel {
         b.add_bias(&mut v, &layer.v_bias);
 
         // Multi-head attention: transpose heads into batch dimension
-        let scale = 1.0 / (HEAD_DIM as f32).sqrt();
+        let scale = 1.0 / (head_dim as f32).sqrt();
 
         // (S, H*D) -> (H*S, D): each head becomes a separate "batch"
-        let q_t = b.transpose_heads(&q, 1, seq_len, NUM_HEADS, HEAD_DIM);
-        let k_t = b.transpose_heads(&k, 1, seq_len, NUM_HEADS, HEAD_DIM);
-        let v_t = b.transpose_heads(&v, 1, seq_len, NUM_HEADS, HEAD_DIM);
+        let q_t = b.transpose_heads(&q, 1, seq_len, num_heads, head_dim);
+        let k_t = b.transpose_heads(&k, 1, seq_len, num_heads, head_dim);
+        let v_t = b.transpose_heads(&v, 1, seq_len, num_heads, head_dim);
 
         // Batched attention: all heads computed in parallel
-        let mut scores = b.batched_matmul_transpose(&q_t, &k_t, NUM_HEADS, seq_len);
+        let mut scores = b.batched_matmul_transpose(&q_t, &k_t, num_heads, seq_len);
         b.scale(&mut scores, scale);
-        b.multi_head_batched_attention_mask(&mut scores, attention_mask, 1, seq_len, NUM_HEADS);
+        b.multi_head_batched_attention_mask(&mut scores, attention_mask, 1, seq_len, num_heads);
         b.softmax_rows(&mut scores);
-        let context = b.batched_matmul(&scores, &v_t, NUM_HEADS, seq_len);
+        let context = b.batched_matmul(&scores, &v_t, num_heads, seq_len);
 
         // (H*S, D) -> (S, H*D): reassemble heads
-        let attn_output = b.untranspose_heads(&context, 1, seq_len, NUM_HEADS, HEAD_DIM);
+        let attn_output = b.untranspose_heads(&context, 1, seq_len, num_heads, head_dim);
 
         // Output projection
         let mut projected = b.matmul_transpose(&attn_output, &layer.attn_output_weight);
@@ -562,7 +605,7 @@ impl EmbedModel {
             &post_attn,
             &layer.attn_ln_weight,
             &layer.attn_ln_bias,
-            LAYER_NORM_EPS,
+            self.manifest.layer_norm_eps,
         );
 
         // FFN: intermediate
@@ -580,7 +623,7 @@ impl EmbedModel {
             &post_ffn,
             &layer.output_ln_weight,
             &layer.output_ln_bias,
-            LAYER_NORM_EPS,
+            self.manifest.layer_norm_eps,
         )
     }
 
@@ -593,9 +636,11 @@ impl EmbedModel {
         seq_len: usize,
     ) -> DeviceTensor {
         let b = &self.backend;
+        let num_heads = self.manifest.num_attention_heads;
+        let head_dim = self.manifest.head_dim();
 
         // Self-attention: Q, K, V projections
-        // These work on the full (B*S, 384) tensor — single large matmul
+        // These work on the full (B*S, hidden) tensor — single large matmul
         let mut q = b.matmul_transpose(hidden, &layer.q_weight);
         b.add_bias(&mut q, &layer.q_bias);
         let mut k = b.matmul_transpose(hidden, &layer.k_weight);
@@ -604,13 +649,13 @@ impl EmbedModel {
         b.add_bias(&mut v, &layer.v_bias);
 
         // Multi-head attention: transpose heads into batch dimension
-        let scale = 1.0 / (HEAD_DIM as f32).sqrt();
-        let total_batches = batch_size * NUM_HEADS;
+        let scale = 1.0 / (head_dim as f32).sqrt();
+        let total_batches = batch_size * num_heads;
 
         // (B*S, H*D) -> (B*H*S, D): each head becomes a separate "batch"
-        let q_t = b.transpose_heads(&q, batch_size, seq_len, NUM_HEADS, HEAD_DIM);
-        let k_t = b.transpose_heads(&k, batch_size, seq_len, NUM_HEADS, HEAD_DIM);
-        let v_t = b.transpose_heads(&v, batch_size, seq_len, NUM_HEADS, HEAD_DIM);
+        let q_t = b.transpose_heads(&q, batch_size, seq_len, num_heads, head_dim);
+        let k_t = b.transpose_heads(&k, batch_size, seq_len, num_heads, head_dim);
+        let v_t = b.transpose_heads(&v, batch_size, seq_len, num_heads, head_dim);
 
         // Batched attention: all heads computed in parallel
         let mut scores = b.batched_matmul_transpose(&q_t, &k_t, total_batches, seq_len);
@@ -620,15 +665,15 @@ impl EmbedModel {
             attention_mask,
             batch_size,
             seq_len,
-            NUM_HEADS,
+            num_heads,
         );
         b.softmax_rows(&mut scores);
         let context = b.batched_matmul(&scores, &v_t, total_batches, seq_len);
 
         // (B*H*S, D) -> (B*S, H*D): reassemble heads
-        let attn_output = b.untranspose_heads(&context, batch_size, seq_len, NUM_HEADS, HEAD_DIM);
+        let attn_output = b.untranspose_heads(&context, batch_size, seq_len, num_heads, head_dim);
 
-        // Output projection — single large matmul on (B*S, 384)
+        // Output projection — single large matmul on (B*S, hidden)
         let mut projected = b.matmul_transpose(&attn_output, &layer.attn_output_weight);
         b.add_bias(&mut projected, &layer.attn_output_bias);
 
@@ -638,7 +683,7 @@ impl EmbedModel {
             &post_attn,
             &layer.attn_ln_weight,
             &layer.attn_ln_bias,
-            LAYER_NORM_EPS,
+            self.manifest.layer_norm_eps,
         );
 
         // FFN: intermediate — single large matmul
@@ -656,7 +701,7 @@ impl EmbedModel {
             &post_ffn,
             &layer.output_ln_weight,
             &layer.output_ln_bias,
-            LAYER_NORM_EPS,
+            self.manifest.layer_norm_eps,
         )
     }
 }
@@ -674,6 +719,9 @@ fn l2_normalize(v: &[f32]) -> Vec<f32> {
 mod tests {
     use super::*;
 
+    const HIDDEN_SIZE: usize = 384;
+    const VOCAB_SIZE: usize = 30522;
+
     #[test]
     fn test_l2_normalize() {
         let v = vec![3.0, 4.0];
@@ -854,6 +902,128 @@ mod tests {
         );
     }
 
+    /// Build a randomly initialised BERT checkpoint shaped by `manifest`,
+    /// returning the SafeTensors bytes and a matching vocabulary.
+    fn tiny_bert(manifest: &ModelManifest) -> (Vec<u8>, String) {
+        let h = manifest.hidden_size;
+        let ffn = 2 * h;
+        let fill = |n: usize, seed: usize| -> Vec<u8> {
+            let vals: Vec<f32> = (0..n).map(|i| ((i + seed) as f32).sin() * 0.1).collect();
+            f32_bytes(&vals)
+        };
+
+        let mut tensors: Vec<(String, Vec<usize>, Vec<u8>)> = vec![
+            (
+                "embeddings.word_embeddings.weight".into(),
+                vec![manifest.vocab_size, h],
+                fill(manifest.vocab_size * h, 1),
+            ),
+            (
+                "embeddings.position_embeddings.weight".into(),
+                vec![512, h],
+                fill(512 * h, 2),
+            ),
+            (
+                "embeddings.token_type_embeddings.weight".into(),
+                vec![2, h],
+                fill(2 * h, 3),
+            ),
+            (
+                "embeddings.LayerNorm.weight".into(),
+                vec![h],
+                f32_bytes(&vec![1.0; h]),
+            ),
+            (
+                "embeddings.LayerNorm.bias".into(),
+                vec![h],
+                f32_bytes(&vec![0.0; h]),
+            ),
+        ];
+        for i in 0..manifest.num_hidden_layers {
+            let lp = format!("encoder.layer.{}", i);
+            for (name, shape) in [
+                ("attention.self.query", [h, h]),
+                ("attention.self.key", [h, h]),
+                ("attention.self.value", [h, h]),
+                ("attention.output.dense", [h, h]),
+                ("intermediate.dense", [ffn, h]),
+                ("output.dense", [h, ffn]),
+            ] {
+                tensors.push((
+                    format!("{}.{}.weight", lp, name),
+                    shape.to_vec(),
+                    fill(shape[0] * shape[1], i + 4),
+                ));
+                tensors.push((
+                    format!("{}.{}.bias", lp, name),
+                    vec![shape[0]],
+                    f32_bytes(&vec![0.0; shape[0]]),
+                ));
+            }
+            for ln in ["attention.output.LayerNorm", "output.LayerNorm"] {
+                tensors.push((
+                    format!("{}.{}.weight", lp, ln),
+                    vec![h],
+                    f32_bytes(&vec![1.0; h]),
+                ));
+                tensors.push((
+                    format!("{}.{}.bias", lp, ln),
+                    vec![h],
+                    f32_bytes(&vec![0.0; h]),
+                ));
+            }
+        }
+
+        let entries: Vec<(&str, &str, &[usize], &[u8])> = tensors
+            .iter()
+            .map(|(name, shape, data)| (name.as_str(), "F32", shape.as_slice(), data.as_slice()))
+            .collect();
+
+        let mut vocab: Vec<String> = (0..manifest.vocab_size)
+            .map(|i| format!("tok{}", i))
+            .collect();
+        vocab[0] = "[PAD]".into();
+        vocab[100] = "[UNK]".into();
+        vocab[101] = "[CLS]".into();
+        vocab[102] = "[SEP]".into();
+        vocab[103] = "hello".into();
+        (build_safetensors(&entries), vocab.join("\n"))
+    }
+
+    fn tiny_manifest() -> ModelManifest {
+        ModelManifest {
+            hidden_size: 8,
+            num_attention_heads: 2,
+            num_hidden_layers: 1,
+            vocab_size: 110,
+            layer_norm_eps: 1e-12,
+        }
+    }
+
+    #[test]
+    fn test_load_custom_manifest() {
+        let manifest = tiny_manifest();
+        let (bytes, vocab) = tiny_bert(&manifest);
+        let model = EmbedModel::load_with_manifest(&bytes, &vocab, manifest).unwrap();
+        assert_eq!(model.dimension(), 8);
+
+        let single = model.embed("hello world");
+        assert_eq!(single.len(), 8);
+        let norm: f32 = single.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-3, "L2 norm = {}", norm);
+
+        let batch = model.embed_batch(&["hello world", "hello"]);
+        assert_eq!(batch.len(), 2);
+        assert!(batch.iter().all(|v| v.len() == 8));
+    }
+
+    #[test]
+    fn test_load_with_wrong_manifest() {
+        let (bytes, vocab) = tiny_bert(&tiny_manifest());
+        let err = EmbedModel::load(&bytes, &vocab).err().unwrap();
+        assert!(err.contains("shape mismatch"), "{}", err);
+    }
+
     #[test]
     #[ignore] // Requires real model files
     fn test_embed_produces_384_dim_unit_vector() {
@@ -51,6 +51,11 @@ impl WordPieceTokenizer {
         }
     }
 
+    /// Longest token sequence produced, including `[CLS]` and `[SEP]`.
+    pub fn max_seq_len(&self) -> usize {
+        self.max_seq_len
+    }
+
     /// Tokenize a text string.
     pub fn tokenize(&self, text: &str) -> TokenizedInput {
         let lower = text.to_lowercase();
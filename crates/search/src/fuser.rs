@@ -7,11 +7,9 @@
 //!
 //! See `docs/architecture/M6_ARCHITECTURE.md` for authoritative specification.
 
-use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
 use strata_core::PrimitiveType;
-use strata_engine::search::{EntityRef, SearchHit, SearchResponse};
+use strata_engine::search::{cmp_ranked, DedupMode, EntityRef, SearchHit, SearchResponse};
 
 // ============================================================================
 // FusedResult
@@ -46,6 +44,14 @@ impl FusedResult {
 ///
 /// Fusers must be Send + Sync for concurrent search operations.
 ///
+/// # Ordering
+///
+/// Hits are ordered by fused score descending. Hits with equal fused
+/// scores must be ordered by
+/// [`tie_break_cmp`](strata_engine::search::tie_break_cmp) (primitive kind,
+/// then identifier, then branch), so paginated results are reproducible
+/// across runs and nodes.
+///
 /// # Implementation Notes
 ///
 /// RRFFuser (Reciprocal Rank Fusion) is the default and only built-in fuser.
@@ -56,6 +62,20 @@ pub trait Fuser: Send + Sync {
     /// ranked list truncated to k items.
     fn fuse(&self, results: Vec<(PrimitiveType, SearchResponse)>, k: usize) -> FusedResult;
 
+    /// Fuse results, handling documents found by several lists per `dedup`
+    ///
+    /// See [`DedupMode`] for what counts as the same document. The default
+    /// implementation ignores `dedup` and calls [`fuse`](Self::fuse).
+    fn fuse_with_dedup(
+        &self,
+        results: Vec<(PrimitiveType, SearchResponse)>,
+        k: usize,
+        dedup: DedupMode,
+    ) -> FusedResult {
+        let _ = dedup;
+        self.fuse(results, k)
+    }
+
     /// Name for debugging and logging
     fn name(&self) -> &str;
 }
@@ -66,34 +86,12 @@ pub trait Fuser: Send + Sync {
 
 /// Sort scored entries by RRF score with deterministic tie-breaking.
 ///
-/// Tie-breaking order:
-/// 1. RRF score descending
-/// 2. Original hit score descending (from first occurrence)
-/// 3. EntityRef hash for stable ordering
-fn sort_rrf_scored(scored: &mut [(EntityRef, f32)], hit_data: &HashMap<EntityRef, SearchHit>) {
-    scored.sort_by(|a, b| match b.1.partial_cmp(&a.1) {
-        Some(std::cmp::Ordering::Equal) | None => {
-            let orig_a = hit_data.get(&a.0).map(|h| h.score).unwrap_or(0.0);
-            let orig_b = hit_data.get(&b.0).map(|h| h.score).unwrap_or(0.0);
-            match orig_b.partial_cmp(&orig_a) {
-                Some(std::cmp::Ordering::Equal) | None => {
-                    let hash_a = {
-                        let mut hasher = DefaultHasher::new();
-                        a.0.hash(&mut hasher);
-                        hasher.finish()
-                    };
-                    let hash_b = {
-                        let mut hasher = DefaultHasher::new();
-                        b.0.hash(&mut hasher);
-                        hasher.finish()
-                    };
-                    hash_a.cmp(&hash_b)
-                }
-                Some(ord) => ord,
-            }
-        }
-        Some(ord) => ord,
-    });
+/// Entries with equal RRF scores are ordered by
+/// [`tie_break_cmp`](strata_engine::search::tie_break_cmp): primitive kind,
+/// then identifier, then branch. The order never depends on hashing or on
+/// the order the input lists were supplied in.
+fn sort_rrf_scored(scored: &mut [(EntityRef, f32)]) {
+    scored.sort_by(|a, b| cmp_ranked(a.1, &a.0, b.1, &b.0));
 }
 
 /// Build a ranked FusedResult from sorted RRF scores.
@@ -138,6 +136,10 @@ fn build_ranked_result(
 /// - Sum contributions across all lists
 /// - Higher RRF score = higher final rank
 ///
+/// Summing is the default [`DedupMode::Merge`] policy;
+/// [`fuse_with_dedup`](Fuser::fuse_with_dedup) also supports keeping the
+/// best contribution or returning every appearance separately.
+///
 /// # Example
 ///
 /// ```text
@@ -176,23 +178,61 @@ impl RRFFuser {
     pub fn k_rrf(&self) -> u32 {
         self.k_rrf
     }
+
+    /// RRF contribution of a hit at `rank`
+    fn contribution(&self, rank: u32) -> f32 {
+        1.0 / (self.k_rrf as f32 + rank as f32)
+    }
 }
 
 impl Fuser for RRFFuser {
     fn fuse(&self, results: Vec<(PrimitiveType, SearchResponse)>, k: usize) -> FusedResult {
+        self.fuse_with_dedup(results, k, DedupMode::Merge)
+    }
+
+    fn fuse_with_dedup(
+        &self,
+        results: Vec<(PrimitiveType, SearchResponse)>,
+        k: usize,
+        dedup: DedupMode,
+    ) -> FusedResult {
+        if dedup == DedupMode::KeepBoth {
+            // Stable sort: repeated documents keep the input list order
+            let mut hits: Vec<SearchHit> = results
+                .into_iter()
+                .flat_map(|(_primitive, response)| response.hits)
+                .map(|mut hit| {
+                    hit.score = self.contribution(hit.rank);
+                    hit
+                })
+                .collect();
+            hits.sort_by(|a, b| a.cmp_rank(b));
+            let truncated = hits.len() > k;
+            hits.truncate(k);
+            for (i, hit) in hits.iter_mut().enumerate() {
+                hit.rank = (i + 1) as u32;
+            }
+            return FusedResult::new(hits, truncated);
+        }
+
         let mut rrf_scores: HashMap<EntityRef, f32> = HashMap::new();
         let mut hit_data: HashMap<EntityRef, SearchHit> = HashMap::new();
 
         for (_primitive, response) in results {
             for hit in response.hits {
-                let rrf_contribution = 1.0 / (self.k_rrf as f32 + hit.rank as f32);
-                *rrf_scores.entry(hit.doc_ref.clone()).or_insert(0.0) += rrf_contribution;
+                let rrf_contribution = self.contribution(hit.rank);
+                let score = rrf_scores.entry(hit.doc_ref.clone()).or_insert(0.0);
+                if dedup == DedupMode::KeepHigher {
+                    *score = score.max(rrf_contribution);
+                } else {
+                    *score += rrf_contribution;
+                }
                 hit_data.entry(hit.doc_ref.clone()).or_insert(hit);
             }
         }
 
         let mut scored: Vec<_> = rrf_scores.into_iter().collect();
-        sort_rrf_scored(&mut scored, &hit_data);
+        sort_rrf_scored(&mut scored);
         build_ranked_result(scored, hit_data, k)
     }
 
@@ -209,7 +249,8 @@ impl Fuser for RRFFuser {
 ///
 /// Used for keyword-only search where all primitives use the same BM25 scorer
 /// and scores are directly comparable. Simply concatenates, deduplicates,
-/// sorts by score descending, and truncates to top_k.
+/// sorts by score descending (ties broken as in [`RRFFuser`]), and
+/// truncates to top_k.
 pub fn merge_by_score(results: Vec<(PrimitiveType, SearchResponse)>, top_k: usize) -> FusedResult {
     let mut hit_map: HashMap<EntityRef, SearchHit> = HashMap::new();
 
@@ -228,11 +269,7 @@ pub fn merge_by_score(results: Vec<(PrimitiveType, SearchResponse)>, top_k: usiz
     }
 
     let mut hits: Vec<SearchHit> = hit_map.into_values().collect();
-    hits.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    hits.sort_by(|a, b| a.cmp_rank(b));
 
     let truncated = hits.len() > top_k;
     hits.truncate(top_k);
@@ -298,7 +335,7 @@ pub fn weighted_rrf_fuse(
     }
 
     let mut scored: Vec<_> = rrf_scores.into_iter().collect();
-    sort_rrf_scored(&mut scored, &hit_data);
+    sort_rrf_scored(&mut scored);
     build_ranked_result(scored, hit_data, top_k)
 }
 
@@ -394,6 +431,59 @@ mod tests {
         assert!((result.hits[0].score - expected_rrf).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_rrf_fuser_dedup_modes() {
+        let fuser = RRFFuser::default();
+
+        let branch_id = BranchId::new();
+        let shared = make_kv_doc_ref(&branch_id, "shared");
+        let other = make_kv_doc_ref(&branch_id, "other");
+        let results = || {
+            vec![
+                (
+                    PrimitiveType::Kv,
+                    make_response(vec![
+                        make_hit(other.clone(), 0.9, 1),
+                        make_hit(shared.clone(), 0.8, 2),
+                    ]),
+                ),
+                (
+                    PrimitiveType::Vector,
+                    make_response(vec![make_hit(shared.clone(), 0.7, 1)]),
+                ),
+            ]
+        };
+
+        // Merge sums contributions, lifting the shared document to the top
+        let merged = fuser.fuse_with_dedup(results(), 10, DedupMode::Merge);
+        assert_eq!(merged.hits.len(), 2);
+        assert_eq!(merged.hits[0].doc_ref, shared);
+        assert!((merged.hits[0].score - (1.0 / 61.0 + 1.0 / 62.0)).abs() < 0.0001);
+
+        // KeepHigher keeps only the best contribution, so it ties with `other`
+        let higher = fuser.fuse_with_dedup(results(), 10, DedupMode::KeepHigher);
+        assert_eq!(higher.hits.len(), 2);
+        assert!(higher
+            .hits
+            .iter()
+            .all(|hit| (hit.score - 1.0 / 61.0).abs() < 0.0001));
+
+        // KeepBoth returns each appearance
+        let both = fuser.fuse_with_dedup(results(), 10, DedupMode::KeepBoth);
+        assert_eq!(both.hits.len(), 3);
+        assert_eq!(
+            both.hits.iter().filter(|hit| hit.doc_ref == shared).count(),
+            2
+        );
+        assert_eq!(both.hits[2].doc_ref, shared);
+        assert!((both.hits[2].score - 1.0 / 62.0).abs() < 0.0001);
+        let ranks: Vec<u32> = both.hits.iter().map(|hit| hit.rank).collect();
+        assert_eq!(ranks, vec![1, 2, 3]);
+
+        let truncated = fuser.fuse_with_dedup(results(), 2, DedupMode::KeepBoth);
+        assert!(truncated.truncated);
+    }
+
     #[test]
     fn test_rrf_fuser_documents_in_both_lists_rank_higher() {
         let fuser = RRFFuser::default();
@@ -446,6 +536,45 @@ mod tests {
         assert!(result.truncated);
     }
 
+    #[test]
+    fn test_rrf_fuser_ties_ordered_by_entity() {
+        let fuser = RRFFuser::default();
+        let branch_id = BranchId::new();
+        let json_a = EntityRef::json(branch_id, "a");
+        let kv_b = make_kv_doc_ref(&branch_id, "b");
+        let kv_a = make_kv_doc_ref(&branch_id, "a");
+
+        // Each doc is rank 1 in its own list, so all RRF scores are equal.
+        // The output order must not depend on the input list order.
+        let lists = [json_a.clone(), kv_b.clone(), kv_a.clone()];
+        for rotation in 0..lists.len() {
+            let mut rotated = lists.to_vec();
+            rotated.rotate_left(rotation);
+            let results = rotated
+                .into_iter()
+                .map(|r| (r.primitive_type(), make_response(vec![make_hit(r, 1.0, 1)])))
+                .collect();
+            let fused = fuser.fuse(results, 2);
+            let order: Vec<_> = fused.hits.iter().map(|h| h.doc_ref.clone()).collect();
+            assert_eq!(order, vec![kv_a.clone(), kv_b.clone()]);
+            assert!(fused.truncated);
+        }
+
+        let merged = merge_by_score(
+            vec![(
+                PrimitiveType::Kv,
+                make_response(vec![
+                    make_hit(json_a.clone(), 0.5, 1),
+                    make_hit(kv_b.clone(), 0.5, 2),
+                    make_hit(kv_a.clone(), 0.5, 3),
+                ]),
+            )],
+            10,
+        );
+        let order: Vec<_> = merged.hits.iter().map(|h| h.doc_ref.clone()).collect();
+        assert_eq!(order, vec![kv_a, kv_b, json_a]);
+    }
+
     #[test]
     fn test_rrf_fuser_determinism() {
         let fuser = RRFFuser::default();
@@ -19,7 +19,7 @@ use crate::fuser::{Fuser, RRFFuser};
 use std::sync::Arc;
 use std::time::Instant;
 use strata_core::PrimitiveType;
-use strata_core::StrataResult;
+use strata_core::{StrataError, StrataResult};
 use strata_engine::database::{SHADOW_EVENT, SHADOW_JSON, SHADOW_KV, SHADOW_STATE};
 use strata_engine::search::{
     SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse, SearchStats,
@@ -71,8 +71,8 @@ use strata_engine::{BranchIndex, EventLog, JsonStore, KVStore, StateCell, Vector
 /// All search state is ephemeral per-request.
 #[derive(Clone)]
 pub struct HybridSearch {
-    /// Database reference — kept alive for primitive stores; read indirectly via embedder
-    #[allow(dead_code)]
+    /// Database reference — kept alive for primitive stores and checked for
+    /// whether keyword search is available
     db: Arc<Database>,
     /// Optional query embedder for hybrid (BM25+vector) search
     embedder: Option<Arc<dyn crate::QueryEmbedder>>,
@@ -140,8 +140,28 @@ impl HybridSearch {
     /// Per Rule 4: Each primitive's search() uses its own snapshot.
     /// For true cross-primitive consistency, primitives would need
     /// search_with_snapshot() methods. This is acceptable for M6.
+    ///
+    /// # Time Window
+    ///
+    /// `time_range` and `as_of` combine into [`SearchRequest::time_window`].
+    /// Keyword candidates are filtered by the write timestamp of their
+    /// current version; vector candidates by the HNSW node's creation time.
+    /// Results are still drawn from the current index, so entries deleted
+    /// or overwritten after `as_of` are not returned in their older form.
+    ///
+    /// # Keyword Index Disabled
+    ///
+    /// When the database does not maintain its keyword index, a Keyword
+    /// request fails with `InvalidInput` and a Hybrid request skips the
+    /// BM25 pass, returning vector results only.
     pub fn search(&self, req: &SearchRequest) -> StrataResult<SearchResponse> {
         let start = Instant::now();
+        let keyword_enabled = self.db.keyword_index_enabled();
+        if req.mode == SearchMode::Keyword && !keyword_enabled {
+            return Err(StrataError::invalid_input(
+                "Keyword search is unavailable: the database was opened with keyword_index = false",
+            ));
+        }
 
         // 1. Select primitives
         let primitives = self.select_primitives(req);
@@ -168,6 +188,9 @@ impl HybridSearch {
             if req.mode == SearchMode::Hybrid && *primitive == PrimitiveType::Vector {
                 continue;
             }
+            if !keyword_enabled {
+                continue;
+            }
 
             // Check overall time budget
             if start.elapsed().as_micros() as u64 >= req.budget.max_wall_time_micros {
@@ -197,7 +220,7 @@ impl HybridSearch {
                 let mut vector_hits: Vec<SearchHit> = Vec::new();
 
                 for collection in &shadow_collections {
-                    let matches = if let Some((start, end)) = req.time_range {
+                    let matches = if let Some((start, end)) = req.time_window() {
                         self.vector.system_search_with_sources_in_range(
                             req.branch_id,
                             collection,
@@ -231,11 +254,7 @@ impl HybridSearch {
                 if !vector_hits.is_empty() {
                     // Sort by score descending so RRF ranks reflect global relevance,
                     // not the arbitrary shadow-collection iteration order.
-                    vector_hits.sort_by(|a, b| {
-                        b.score
-                            .partial_cmp(&a.score)
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    });
+                    vector_hits.sort_by(|a, b| a.cmp_rank(b));
                     for (i, hit) in vector_hits.iter_mut().enumerate() {
                         hit.rank = (i + 1) as u32;
                     }
@@ -253,7 +272,8 @@ impl HybridSearch {
         let fused = if req.mode == SearchMode::Keyword {
             crate::fuser::merge_by_score(primitive_results, req.k)
         } else {
-            self.fuser.fuse(primitive_results, req.k)
+            self.fuser
+                .fuse_with_dedup(primitive_results, req.k, req.dedup)
         };
 
         // 6. Build stats
@@ -360,16 +380,21 @@ impl HybridSearch {
         result_lists.push((original_response, original_weight));
 
         // Expansion passes
+        let keyword_enabled = self.db.keyword_index_enabled();
         for expansion in expansions {
             let mode = match expansion.query_type {
+                // Lexical rewrites have nothing to match without the index
+                QueryType::Lex if !keyword_enabled => continue,
                 QueryType::Lex => SearchMode::Keyword,
                 QueryType::Vec | QueryType::Hyde => SearchMode::Hybrid,
             };
 
             let mut exp_req = SearchRequest::new(req.branch_id, &expansion.text)
+                .with_space(req.space.clone())
                 .with_k(req.k)
                 .with_mode(mode)
-                .with_budget(req.budget);
+                .with_budget(req.budget)
+                .with_typo_tolerance(req.typo_tolerance);
 
             if let Some(ref filter) = req.primitive_filter {
                 exp_req = exp_req.with_primitive_filter(filter.clone());
@@ -377,6 +402,9 @@ impl HybridSearch {
             if let Some((start, end)) = req.time_range {
                 exp_req = exp_req.with_time_range(start, end);
             }
+            if let Some(as_of) = req.as_of {
+                exp_req = exp_req.with_as_of(as_of);
+            }
 
             match self.search(&exp_req) {
                 Ok(response) => result_lists.push((response, 1.0)),
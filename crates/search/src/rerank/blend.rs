@@ -11,7 +11,8 @@ use strata_engine::search::SearchHit;
 /// Blend RRF scores with reranker scores using position-aware weights.
 ///
 /// Hits without a matching reranker score keep their normalized RRF score.
-/// Results are re-sorted by blended score (descending) and ranks reassigned.
+/// Results are re-sorted by blended score (descending, ties broken by
+/// [`SearchHit::cmp_rank`]) and ranks reassigned.
 pub fn blend_scores(mut hits: Vec<SearchHit>, scores: &[RerankScore]) -> Vec<SearchHit> {
     if hits.is_empty() || scores.is_empty() {
         return hits;
@@ -41,12 +42,8 @@ pub fn blend_scores(mut hits: Vec<SearchHit>, scores: &[RerankScore]) -> Vec<Sea
         }
     }
 
-    // Re-sort by blended score (descending)
-    hits.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    // Re-sort by blended score (descending), deterministic on ties
+    hits.sort_by(|a, b| a.cmp_rank(b));
 
     // Reassign ranks (1-indexed)
     for (i, hit) in hits.iter_mut().enumerate() {
@@ -5,7 +5,11 @@
 
 #![warn(missing_docs)]
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use strata_core::{Clock, KeyNormalization, VectorStorage};
 
 /// Controls whether the database allows writes or is read-only.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -17,6 +21,18 @@ pub enum AccessMode {
     ReadOnly,
 }
 
+/// What to do at open when auto-embedding is enabled but the embedding
+/// model files cannot be found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MissingModelPolicy {
+    /// Open anyway with auto-embedding switched off, logging a warning (default).
+    /// `strata.toml` keeps `auto_embed = true`, so the next open retries.
+    #[default]
+    Disable,
+    /// Refuse to open the database.
+    Fail,
+}
+
 /// Options for opening a database.
 ///
 /// Use the builder pattern to configure options. Any field set to `Some`
@@ -50,6 +66,80 @@ pub struct OpenOptions {
     /// Override embedding batch size for auto-embed.
     /// `None` means "use the config file value, or 512 if unset".
     pub embed_batch_size: Option<usize>,
+    /// Override the number of commit lock shards.
+    /// `None` means "use the config file value, or one lock per branch if unset".
+    pub commit_lock_shards: Option<usize>,
+    /// Override the number of background scheduler threads.
+    /// `None` means "use the config file value, or 2 if unset".
+    pub background_threads: Option<usize>,
+    /// Override the background scheduler queue depth.
+    /// `None` means "use the config file value, or 4096 if unset".
+    pub background_queue_depth: Option<usize>,
+    /// Write a snapshot checkpoint during a clean shutdown.
+    /// `None` means "use the config file value, or off if unset".
+    pub checkpoint_on_shutdown: Option<bool>,
+    /// Fail to open when the WAL holds records this version cannot read.
+    /// `None` means "use the config file value, or skip them if unset".
+    pub strict_recovery: Option<bool>,
+    /// Number of checkpoint snapshots kept after compaction.
+    /// `None` means "use the config file value, or keep all if unset".
+    pub keep_snapshots: Option<usize>,
+    /// Largest transaction accepted into a single WAL record, in bytes.
+    /// `None` means "use the config file value, or 64MB if unset".
+    pub max_record_bytes: Option<u64>,
+    /// Maintain the inverted index for keyword search.
+    /// `None` means "use the config file value, or on if unset".
+    pub keyword_index: Option<bool>,
+    /// Where vector embeddings are held while the database is open.
+    /// `None` means "use the config file value, or mmap if unset".
+    pub vector_storage: Option<VectorStorage>,
+    /// Size in bytes from which values are stored once per distinct content.
+    /// `None` means "use the config file value, or off if unset".
+    pub dedup_threshold: Option<usize>,
+    /// How long a deleted key is kept before GC may remove its tombstone.
+    /// `None` means "use the config file value, or keep forever if unset".
+    pub tombstone_ttl: Option<Duration>,
+    /// Maximum number of transactions open at once.
+    /// `None` means "use the config file value, or no limit if unset".
+    pub max_active_transactions: Option<u64>,
+    /// Result count for list and search requests that do not give one.
+    /// `None` means "use the config file value, or unlimited if unset".
+    pub default_result_limit: Option<u64>,
+    /// Largest result count a list or search request may ask for.
+    /// `None` means "use the config file value, or no cap if unset".
+    pub max_result_limit: Option<u64>,
+    /// Maximum JSON document size in bytes.
+    /// `None` means "use the config file value, or 16MB if unset".
+    pub json_max_document_size: Option<usize>,
+    /// Maximum JSON nesting depth.
+    /// `None` means "use the config file value, or 100 if unset".
+    pub json_max_nesting_depth: Option<usize>,
+    /// Maximum JSON path length in segments.
+    /// `None` means "use the config file value, or 256 if unset".
+    pub json_max_path_length: Option<usize>,
+    /// Maximum JSON array size in elements.
+    /// `None` means "use the config file value, or 1M if unset".
+    pub json_max_array_size: Option<usize>,
+    /// How user keys are canonicalized before reaching storage.
+    /// `None` means "use the config file value, or identity if unset".
+    pub key_normalization: Option<KeyNormalization>,
+    /// Branches whose commits skip the WAL, added to those in the config
+    /// file.
+    pub ephemeral_branches: Vec<String>,
+    /// Behavior when auto-embedding is enabled but the model is missing.
+    pub missing_model: MissingModelPolicy,
+    /// Approximate memory budget in bytes for cache databases.
+    /// `None` means unbounded. Ignored for disk-backed databases.
+    pub cache_max_bytes: Option<usize>,
+    /// KV entry budget for cache databases.
+    /// `None` means unbounded. Ignored for disk-backed databases.
+    pub cache_max_entries: Option<usize>,
+    /// Time source for version, WAL, event and snapshot timestamps.
+    /// `None` means system time.
+    pub clock: Option<Arc<dyn Clock>>,
+    /// Name recorded as the actor of administrative actions in the admin log.
+    /// `None` records no actor.
+    pub actor: Option<String>,
 }
 
 impl OpenOptions {
@@ -100,6 +190,274 @@ impl OpenOptions {
         self.embed_batch_size = Some(size);
         self
     }
+
+    /// Set the number of commit lock shards.
+    ///
+    /// Branches are hashed onto a fixed pool of `n` commit locks instead of
+    /// keeping one lock per branch.
+    pub fn commit_lock_shards(mut self, n: usize) -> Self {
+        self.commit_lock_shards = Some(n);
+        self
+    }
+
+    /// Set the number of background scheduler worker threads.
+    ///
+    /// Workers run deferred work such as auto-embedding batches. Raise this
+    /// for embedding-heavy workloads; lower it to save resources.
+    pub fn background_threads(mut self, n: usize) -> Self {
+        self.background_threads = Some(n);
+        self
+    }
+
+    /// Set the maximum number of queued background tasks.
+    ///
+    /// Submissions beyond this depth are rejected with a backpressure error
+    /// instead of blocking. A deeper queue absorbs larger bursts at the cost
+    /// of memory.
+    pub fn background_queue_depth(mut self, n: usize) -> Self {
+        self.background_queue_depth = Some(n);
+        self
+    }
+
+    /// Write a snapshot checkpoint during a clean shutdown.
+    ///
+    /// The next open then loads the snapshot instead of replaying the whole
+    /// WAL, at the cost of a slower shutdown. Off by default.
+    pub fn checkpoint_on_shutdown(mut self, enabled: bool) -> Self {
+        self.checkpoint_on_shutdown = Some(enabled);
+        self
+    }
+
+    /// Fail to open if the WAL holds records this version cannot read.
+    ///
+    /// A database also written by a newer version may contain WAL records
+    /// in a newer format, or writes to a primitive this version lacks. By
+    /// default those records are skipped with a warning and the rest of the
+    /// data is recovered; with strict recovery the open returns an error
+    /// instead. Off by default.
+    pub fn strict_recovery(mut self, enabled: bool) -> Self {
+        self.strict_recovery = Some(enabled);
+        self
+    }
+
+    /// Keep the newest `n` checkpoint snapshots for rollback.
+    ///
+    /// Older snapshots are deleted by `compact()`, once the latest snapshot
+    /// is durable and the WAL it covers has been removed. The latest
+    /// snapshot is always kept, so 0 behaves like 1. By default every
+    /// snapshot is kept.
+    pub fn keep_snapshots(mut self, n: usize) -> Self {
+        self.keep_snapshots = Some(n);
+        self
+    }
+
+    /// Set the maximum WAL record size in bytes.
+    ///
+    /// A transaction whose serialized writes exceed this is rejected with a
+    /// value-too-large error before anything is written. Large data should
+    /// go through the chunked blob API instead. Defaults to 64MB.
+    pub fn max_record_bytes(mut self, bytes: u64) -> Self {
+        self.max_record_bytes = Some(bytes);
+        self
+    }
+
+    /// Enable or disable the inverted index for keyword search.
+    ///
+    /// With the index off, writes skip tokenizing and indexing their text,
+    /// saving CPU on every write and the memory held by posting lists.
+    /// Keyword search then fails and hybrid search returns vector results
+    /// only. Opening again with the index on rebuilds it from all stored
+    /// data, which takes time proportional to the database size.
+    pub fn keyword_index(mut self, enabled: bool) -> Self {
+        self.keyword_index = Some(enabled);
+        self
+    }
+
+    /// Choose between memory-mapped and fully resident vector embeddings.
+    ///
+    /// [`VectorStorage::Mmap`] (the default) lets the OS page embeddings in
+    /// from each collection's `.vec` file, keeping resident memory bounded
+    /// for memory-constrained deployments; the first searches after open, or
+    /// after the OS evicts pages, wait on disk reads. [`VectorStorage::InMemory`]
+    /// reads every embedding and saved HNSW graph into memory on open, so
+    /// searches never touch disk, at the cost of memory proportional to the
+    /// number of vectors. Ignored for cache databases, which are always in
+    /// memory.
+    pub fn vector_storage(mut self, storage: VectorStorage) -> Self {
+        self.vector_storage = Some(storage);
+        self
+    }
+
+    /// Share large values between versions with identical content.
+    ///
+    /// Values of at least `bytes` are stored once and referenced by every
+    /// version, key and branch that writes the same content, which saves
+    /// memory when large documents or blobs are re-stored unchanged. The
+    /// shared copy is freed with the last version that uses it. Applies to
+    /// disk and cache databases.
+    pub fn dedup_threshold(mut self, bytes: usize) -> Self {
+        self.dedup_threshold = Some(bytes);
+        self
+    }
+
+    /// Keep deleted keys observable for `ttl` before GC may remove them.
+    ///
+    /// A delete leaves a tombstone, so the key's history shows when it was
+    /// deleted. By default tombstones are never removed. With a TTL,
+    /// retention GC drops keys deleted longer ago than `ttl`, reclaiming
+    /// their memory. Shorter TTLs reclaim space faster, but a replica or
+    /// late reader that has not seen the delete by then can no longer tell
+    /// the key was deleted and may write the old value back. Applies to
+    /// disk and cache databases.
+    pub fn tombstone_ttl(mut self, ttl: Duration) -> Self {
+        self.tombstone_ttl = Some(ttl);
+        self
+    }
+
+    /// Reject new transactions while `n` are already open.
+    ///
+    /// Beginning a transaction beyond the limit fails with
+    /// `TooManyTransactions` instead of growing the set of active
+    /// transactions and the snapshots they hold. An open transaction keeps
+    /// its slot until it commits or rolls back, so long-running read
+    /// transactions count too; snapshot reads that do not begin a
+    /// transaction do not. Applies to disk and cache databases.
+    pub fn max_active_transactions(mut self, n: u64) -> Self {
+        self.max_active_transactions = Some(n);
+        self
+    }
+
+    /// Use `n` as the result count of list and search requests that omit
+    /// one.
+    ///
+    /// Applies to `kv_list` without a limit and to searches without `k`.
+    /// Without it, a list returns every key and a search its usual 10 hits.
+    /// Still capped by [`max_result_limit`](Self::max_result_limit).
+    /// Applies to disk and cache databases.
+    pub fn default_result_limit(mut self, n: u64) -> Self {
+        self.default_result_limit = Some(n);
+        self
+    }
+
+    /// Cap the results a single list or search request can return at `n`.
+    ///
+    /// Protects a server from requests that would materialize a whole
+    /// table: `kv_list`, vector search and search requests asking for more
+    /// than `n` results (or a list asking for no limit) are clamped to `n`
+    /// rather than rejected, and the response is marked truncated when
+    /// results were dropped. Applies to disk and cache databases.
+    pub fn max_result_limit(mut self, n: u64) -> Self {
+        self.max_result_limit = Some(n);
+        self
+    }
+
+    /// Accept JSON documents of up to `bytes` bytes (default: 16MB).
+    ///
+    /// Values above the 256MB hard ceiling are lowered to it. Applies to
+    /// disk and cache databases.
+    pub fn json_max_document_size(mut self, bytes: usize) -> Self {
+        self.json_max_document_size = Some(bytes);
+        self
+    }
+
+    /// Accept JSON documents nested up to `depth` levels (default: 100).
+    ///
+    /// Raise it for legitimately deep documents. Values above the 512-level
+    /// hard ceiling are lowered to it, so a document can never be deep
+    /// enough to overflow the stack while it is converted or serialized.
+    /// Applies to disk and cache databases.
+    pub fn json_max_nesting_depth(mut self, depth: usize) -> Self {
+        self.json_max_nesting_depth = Some(depth);
+        self
+    }
+
+    /// Accept JSON paths of up to `segments` segments (default: 256).
+    ///
+    /// Values above the 1024-segment hard ceiling are lowered to it.
+    /// Applies to disk and cache databases.
+    pub fn json_max_path_length(mut self, segments: usize) -> Self {
+        self.json_max_path_length = Some(segments);
+        self
+    }
+
+    /// Accept JSON arrays of up to `elements` elements (default: 1M).
+    ///
+    /// Values above the 100M-element hard ceiling are lowered to it.
+    /// Applies to disk and cache databases.
+    pub fn json_max_array_size(mut self, elements: usize) -> Self {
+        self.json_max_array_size = Some(elements);
+        self
+    }
+
+    /// Canonicalize keys so that differently written keys are the same key.
+    ///
+    /// [`KeyNormalization::Lowercase`] lowercases KV keys, JSON document
+    /// keys, state cell names, vector keys and blob keys on every write and
+    /// read, for case-insensitive domains such as user names or emails.
+    /// Keys already stored are not rewritten, so switching modes on a
+    /// database that holds keys written under another mode requires
+    /// migrating them. Applies to disk and cache databases.
+    pub fn key_normalization(mut self, mode: KeyNormalization) -> Self {
+        self.key_normalization = Some(mode);
+        self
+    }
+
+    /// Mark the branch `name` ephemeral: its commits skip the WAL.
+    ///
+    /// Writes to an ephemeral branch cost no disk I/O but are lost on
+    /// restart or crash unless a checkpoint captured them; merge into a
+    /// durable branch to keep results. Can be called once per branch, and
+    /// is recorded in the config file. The `"default"` branch cannot be
+    /// ephemeral. Ignored for cache databases, which have no WAL.
+    pub fn ephemeral_branch(mut self, name: impl Into<String>) -> Self {
+        self.ephemeral_branches.push(name.into());
+        self
+    }
+
+    /// Choose what happens when auto-embedding is enabled but the embedding
+    /// model files are missing: degrade gracefully or fail the open.
+    pub fn missing_model(mut self, policy: MissingModelPolicy) -> Self {
+        self.missing_model = policy;
+        self
+    }
+
+    /// Set the approximate memory budget for a cache database.
+    ///
+    /// Once exceeded, least-recently-used KV keys are evicted.
+    pub fn cache_max_bytes(mut self, bytes: usize) -> Self {
+        self.cache_max_bytes = Some(bytes);
+        self
+    }
+
+    /// Set the maximum number of KV entries for a cache database.
+    ///
+    /// Once exceeded, least-recently-used KV keys are evicted.
+    pub fn cache_max_entries(mut self, entries: usize) -> Self {
+        self.cache_max_entries = Some(entries);
+        self
+    }
+
+    /// Read timestamps from `clock` instead of the system time.
+    ///
+    /// Version, WAL record, event and snapshot timestamps of writes made
+    /// after open all come from this clock, which lets tests pin them to
+    /// exact values with a manual clock. Applies to disk and cache
+    /// databases.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Attribute administrative actions made through this handle to `name`.
+    ///
+    /// Branch creation and deletion, checkpoints, compaction and runtime
+    /// configuration changes are recorded in the admin log with this name,
+    /// for example a user, service or tool. It is not checked or
+    /// authenticated.
+    pub fn actor(mut self, name: impl Into<String>) -> Self {
+        self.actor = Some(name.into());
+        self
+    }
 }
 
 impl Default for OpenOptions {
@@ -113,6 +471,31 @@ impl Default for OpenOptions {
             model_api_key: None,
             model_timeout_ms: None,
             embed_batch_size: None,
+            commit_lock_shards: None,
+            background_threads: None,
+            background_queue_depth: None,
+            checkpoint_on_shutdown: None,
+            strict_recovery: None,
+            keep_snapshots: None,
+            max_record_bytes: None,
+            keyword_index: None,
+            vector_storage: None,
+            dedup_threshold: None,
+            tombstone_ttl: None,
+            max_active_transactions: None,
+            default_result_limit: None,
+            max_result_limit: None,
+            json_max_document_size: None,
+            json_max_nesting_depth: None,
+            json_max_path_length: None,
+            json_max_array_size: None,
+            key_normalization: None,
+            ephemeral_branches: Vec::new(),
+            missing_model: MissingModelPolicy::Disable,
+            cache_max_bytes: None,
+            cache_max_entries: None,
+            clock: None,
+            actor: None,
         }
     }
 }
@@ -0,0 +1,238 @@
+//! Size-bounded eviction for cache-mode stores
+//!
+//! A [`ShardedStore`](crate::ShardedStore) built with
+//! [`ShardedStore::with_limits`](crate::ShardedStore::with_limits) tracks
+//! approximate memory and the number of resident KV entries. When either
+//! exceeds its budget, [`evict_to_limits`](crate::ShardedStore::evict_to_limits)
+//! drops least-recently-used KV keys until both are back under a low-water
+//! mark.
+//!
+//! Only KV keys are evicted: event logs, state cells, JSON documents and
+//! branch metadata carry invariants that a silently missing key would break.
+//!
+//! # Snapshots
+//!
+//! A bounded store pins the version of every live snapshot. A key is only
+//! evicted when no pinned snapshot can see any version of it, so eviction
+//! never changes what an open snapshot reads. Long-lived snapshots can
+//! therefore hold the store above budget until they are dropped.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Percentage of each budget that an eviction pass drains down to
+///
+/// Draining below the limit means a stream of writes triggers one pass per
+/// batch of evictions instead of one pass per write.
+pub const LOW_WATER_PERCENT: usize = 90;
+
+/// Memory and entry budgets for a bounded store
+///
+/// Both limits are optional; a store with neither set never evicts.
+///
+/// ```
+/// use strata_storage::CacheLimits;
+///
+/// let limits = CacheLimits::new().max_bytes(64 << 20).max_entries(100_000);
+/// assert!(limits.is_bounded());
+/// assert!(!CacheLimits::new().is_bounded());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheLimits {
+    /// Approximate upper bound on bytes held by the store (all data, not
+    /// only KV). `None` means unbounded.
+    pub max_bytes: Option<usize>,
+    /// Upper bound on resident KV entries. Deleted keys count until they are
+    /// evicted or garbage-collected. `None` means unbounded.
+    pub max_entries: Option<usize>,
+}
+
+impl CacheLimits {
+    /// Create unbounded limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: set the approximate memory budget in bytes
+    pub fn max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Builder: set the KV entry budget
+    pub fn max_entries(mut self, entries: usize) -> Self {
+        self.max_entries = Some(entries);
+        self
+    }
+
+    /// Whether any budget is set
+    pub fn is_bounded(&self) -> bool {
+        self.max_bytes.is_some() || self.max_entries.is_some()
+    }
+
+    /// Whether current usage exceeds either budget
+    pub fn exceeded(&self, bytes: usize, entries: usize) -> bool {
+        self.max_bytes.is_some_and(|max| bytes > max)
+            || self.max_entries.is_some_and(|max| entries > max)
+    }
+
+    /// Whether current usage is above either low-water mark
+    pub(crate) fn above_low_water(&self, bytes: usize, entries: usize) -> bool {
+        self.max_bytes.is_some_and(|max| bytes > low_water(max))
+            || self.max_entries.is_some_and(|max| entries > low_water(max))
+    }
+}
+
+/// `LOW_WATER_PERCENT` of `limit`, without overflowing near `usize::MAX`
+fn low_water(limit: usize) -> usize {
+    limit / 100 * LOW_WATER_PERCENT + limit % 100 * LOW_WATER_PERCENT / 100
+}
+
+/// Versions held by live snapshots of a bounded store
+///
+/// Snapshot creation and eviction passes both take the lock, so a snapshot
+/// is either created before a pass (and pins what it can see) or after it
+/// (and never observes the evicted keys).
+#[derive(Debug, Default)]
+pub(crate) struct SnapshotPins {
+    pins: Mutex<BTreeMap<u64, PinSlot>>,
+}
+
+/// Live pins at one version
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PinSlot {
+    /// Number of live pins
+    count: usize,
+    /// When the version was first pinned
+    since: Instant,
+}
+
+/// Snapshots pinning one version
+///
+/// Returned by [`ShardedStore::pinned_snapshots`](crate::ShardedStore::pinned_snapshots).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedSnapshotInfo {
+    /// Version the snapshots read at
+    pub version: u64,
+    /// Number of live snapshots pinning it
+    pub count: usize,
+    /// How long ago the version was first pinned
+    pub age: Duration,
+}
+
+impl SnapshotPins {
+    /// Lock the pin table
+    pub(crate) fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, PinSlot>> {
+        self.pins.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Add a pin at `version` while holding the lock
+    pub(crate) fn pin_locked(pins: &mut BTreeMap<u64, PinSlot>, version: u64) {
+        pins.entry(version)
+            .or_insert_with(|| PinSlot {
+                count: 0,
+                since: Instant::now(),
+            })
+            .count += 1;
+    }
+
+    /// Add a pin at `version`
+    pub(crate) fn pin(&self, version: u64) {
+        Self::pin_locked(&mut self.lock(), version);
+    }
+
+    /// Release one pin at `version`
+    pub(crate) fn unpin(&self, version: u64) {
+        let mut pins = self.lock();
+        if let Some(slot) = pins.get_mut(&version) {
+            slot.count -= 1;
+            if slot.count == 0 {
+                pins.remove(&version);
+            }
+        }
+    }
+
+    /// Number of live pins
+    pub(crate) fn count(&self) -> usize {
+        self.lock().values().map(|slot| slot.count).sum()
+    }
+
+    /// Pinned versions, oldest first
+    pub(crate) fn list(&self) -> Vec<PinnedSnapshotInfo> {
+        let mut pinned: Vec<PinnedSnapshotInfo> = self
+            .lock()
+            .iter()
+            .map(|(&version, slot)| PinnedSnapshotInfo {
+                version,
+                count: slot.count,
+                age: slot.since.elapsed(),
+            })
+            .collect();
+        pinned.sort_by_key(|p| std::cmp::Reverse(p.age));
+        pinned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unbounded_never_exceeded() {
+        let limits = CacheLimits::new();
+        assert!(!limits.is_bounded());
+        assert!(!limits.exceeded(usize::MAX, usize::MAX));
+    }
+
+    #[test]
+    fn test_exceeded_either_budget() {
+        let limits = CacheLimits::new().max_bytes(1000).max_entries(10);
+        assert!(!limits.exceeded(1000, 10));
+        assert!(limits.exceeded(1001, 0));
+        assert!(limits.exceeded(0, 11));
+    }
+
+    #[test]
+    fn test_low_water_mark() {
+        let limits = CacheLimits::new().max_entries(100);
+        assert!(limits.above_low_water(0, 91));
+        assert!(!limits.above_low_water(0, 90));
+        assert_eq!(low_water(1005), 904);
+    }
+
+    #[test]
+    fn test_pins_are_counted() {
+        let pins = SnapshotPins::default();
+        pins.pin(5);
+        pins.pin(5);
+        pins.pin(7);
+        assert_eq!(pins.count(), 3);
+        pins.unpin(5);
+        assert_eq!(pins.lock().keys().copied().collect::<Vec<_>>(), vec![5, 7]);
+        pins.unpin(5);
+        pins.unpin(7);
+        assert_eq!(pins.count(), 0);
+    }
+
+    #[test]
+    fn test_pin_list_oldest_first() {
+        let pins = SnapshotPins::default();
+        pins.pin(5);
+        std::thread::sleep(Duration::from_millis(2));
+        pins.pin(7);
+        pins.pin(7);
+        let listed = pins.list();
+        assert_eq!(
+            listed
+                .iter()
+                .map(|p| (p.version, p.count))
+                .collect::<Vec<_>>(),
+            vec![(5, 1), (7, 2)]
+        );
+        assert!(listed[0].age > listed[1].age);
+        pins.unpin(5);
+        pins.unpin(7);
+        assert_eq!(pins.list().len(), 1);
+    }
+}
@@ -6,24 +6,29 @@
 //! - Lock-free reads via DashMap
 //! - Per-BranchId sharding (no cross-branch contention)
 //! - FxHashMap for O(1) lookups
+//! - Optional content-addressed sharing of large values (`ValuePool`)
 //!
 //! Persistence and durability are handled by the `strata-durability` crate.
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod eviction;
 pub mod index;
 pub mod primitive_ext;
 pub mod registry;
 pub mod sharded;
 pub mod stored_value;
 pub mod ttl;
+pub mod value_pool;
 
+pub use eviction::{CacheLimits, PinnedSnapshotInfo};
 pub use index::{BranchIndex, TypeIndex};
 pub use primitive_ext::{
     is_future_wal_type, is_vector_wal_type, primitive_for_wal_type, primitive_type_ids, wal_ranges,
     PrimitiveExtError, PrimitiveStorageExt,
 };
 pub use registry::PrimitiveRegistry;
-pub use sharded::{Shard, ShardedSnapshot, ShardedStore};
+pub use sharded::{ForkBase, Shard, ShardedSnapshot, ShardedStore};
 pub use ttl::TTLIndex;
+pub use value_pool::ValuePool;
@@ -31,15 +31,18 @@
 //! 4. Performance: Avoiding enum matching on every comparison
 
 use dashmap::DashMap;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 use std::collections::VecDeque;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use strata_core::types::{BranchId, Key};
-use strata_core::{Timestamp, Version, VersionedValue};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use strata_core::types::{BranchId, Key, TypeTag};
+use strata_core::{Clock, SystemClock, Timestamp, Version, VersionedValue};
 
+use crate::eviction::{CacheLimits, PinnedSnapshotInfo, SnapshotPins};
 use crate::stored_value::StoredValue;
+use crate::value_pool::ValuePool;
 
 /// Per-branch shard containing branch's data
 ///
@@ -52,12 +55,23 @@ use crate::stored_value::StoredValue;
 ///
 /// Uses VecDeque for O(1) push_front instead of SmallVec's O(n) insert(0, ...).
 /// This is critical for workloads that repeatedly update the same key (like CAS).
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct VersionChain {
     /// Versions stored newest-first for efficient MVCC reads
     /// VecDeque provides O(1) push_front for new versions
     /// Uses StoredValue to include TTL information
     versions: VecDeque<StoredValue>,
+    /// Access clock tick of the last read or write (bounded stores only)
+    last_access: AtomicU64,
+}
+
+impl Clone for VersionChain {
+    fn clone(&self) -> Self {
+        Self {
+            versions: self.versions.clone(),
+            last_access: AtomicU64::new(self.last_access()),
+        }
+    }
 }
 
 impl VersionChain {
@@ -65,7 +79,27 @@ impl VersionChain {
     pub fn new(value: StoredValue) -> Self {
         let mut versions = VecDeque::with_capacity(4);
         versions.push_front(value);
-        Self { versions }
+        Self {
+            versions,
+            last_access: AtomicU64::new(0),
+        }
+    }
+
+    /// Record an access at the given clock tick
+    #[inline]
+    pub fn touch(&self, tick: u64) {
+        self.last_access.store(tick, Ordering::Relaxed);
+    }
+
+    /// Clock tick of the last recorded access
+    #[inline]
+    pub fn last_access(&self) -> u64 {
+        self.last_access.load(Ordering::Relaxed)
+    }
+
+    /// Version number of the oldest retained version
+    pub fn oldest_version(&self) -> Option<u64> {
+        self.versions.back().map(|sv| sv.version().as_u64())
     }
 
     /// Add a new version (must be newer than existing versions)
@@ -103,6 +137,14 @@ impl VersionChain {
             .find(|sv| u64::from(sv.timestamp()) <= max_timestamp)
     }
 
+    /// Get the newest version at or before both `max_version` and
+    /// `max_timestamp`
+    pub fn get_at(&self, max_version: u64, max_timestamp: u64) -> Option<&StoredValue> {
+        self.versions.iter().find(|sv| {
+            sv.version().as_u64() <= max_version && u64::from(sv.timestamp()) <= max_timestamp
+        })
+    }
+
     /// Get the latest version
     #[inline]
     pub fn latest(&self) -> Option<&StoredValue> {
@@ -134,6 +176,16 @@ impl VersionChain {
         pruned
     }
 
+    /// Version number of the `n`-th newest version (0 = latest)
+    pub fn nth_newest_version(&self, n: usize) -> Option<u64> {
+        self.versions.get(n).map(|sv| sv.version().as_u64())
+    }
+
+    /// Approximate in-memory footprint of all retained versions, in bytes
+    pub fn size_estimate(&self) -> usize {
+        self.versions.iter().map(StoredValue::size_estimate).sum()
+    }
+
     /// Number of versions stored
     pub fn version_count(&self) -> usize {
         self.versions.len()
@@ -262,15 +314,86 @@ pub struct ShardedStore {
     shards: DashMap<BranchId, Shard>,
     /// Global version for snapshots
     version: AtomicU64,
+    /// Copy-on-write parent links for lazily forked branches
+    fork_bases: DashMap<BranchId, ForkBase>,
+    /// Approximate bytes held across all shards
+    approx_bytes: AtomicUsize,
+    /// Number of resident KV keys (live or tombstoned)
+    kv_entries: AtomicUsize,
+    /// Eviction budgets; `None` for an unbounded store
+    limits: Option<CacheLimits>,
+    /// Logical clock for LRU recency (bounded stores only)
+    access_clock: AtomicU64,
+    /// Versions of live snapshots (bounded stores only)
+    pins: SnapshotPins,
+    /// Time source for version and tombstone timestamps
+    clock: RwLock<Arc<dyn Clock>>,
+    /// Shared copies of large values (see [`set_dedup_threshold`](Self::set_dedup_threshold))
+    values: ValuePool,
+    /// How long GC keeps a deleted key (see [`set_tombstone_ttl`](Self::set_tombstone_ttl))
+    tombstone_ttl: RwLock<Option<Duration>>,
+}
+
+/// Copy-on-write parent link for a lazily forked branch
+///
+/// A branch with a fork base reads through to `parent` (as of `version`)
+/// for every key it has not written itself. Writes and deletes in the child
+/// land in the child's own shard and shadow the inherited value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForkBase {
+    /// Branch the child reads through to
+    pub parent: BranchId,
+    /// Parent version the child was forked at
+    pub version: u64,
+}
+
+/// Which keys of a branch a list/scan operation covers
+#[derive(Debug, Clone)]
+enum KeyScope<'a> {
+    /// Every key in the branch
+    Branch,
+    /// Keys with the given type tag
+    Type(strata_core::types::TypeTag),
+    /// Keys starting with the given prefix key
+    Prefix(Cow<'a, Key>),
+}
+
+impl KeyScope<'_> {
+    /// Re-target the scope at another branch's namespace
+    fn rebased(&self, branch_id: BranchId) -> KeyScope<'static> {
+        match self {
+            KeyScope::Branch => KeyScope::Branch,
+            KeyScope::Type(type_tag) => KeyScope::Type(*type_tag),
+            KeyScope::Prefix(prefix) => KeyScope::Prefix(Cow::Owned(rebase_key(prefix, branch_id))),
+        }
+    }
+}
+
+/// Copy a key into another branch's namespace, keeping space, type and user key
+fn rebase_key(key: &Key, branch_id: BranchId) -> Key {
+    let mut rebased = key.clone();
+    rebased.namespace.branch_id = branch_id;
+    rebased
+}
+
+/// Approximate per-key overhead of a chain entry, excluding its versions
+fn key_size_estimate(key: &Key) -> usize {
+    std::mem::size_of::<Key>() + key.user_key.len() + key.namespace.space.len()
+}
+
+/// Approximate footprint of a shard: every key plus its retained versions
+fn shard_size_estimate(shard: &Shard) -> usize {
+    shard
+        .data
+        .iter()
+        .map(|(key, chain)| key_size_estimate(key) + chain.size_estimate())
+        .sum()
 }
 
 impl ShardedStore {
     /// Create new sharded store
     pub fn new() -> Self {
-        Self {
-            shards: DashMap::new(),
-            version: AtomicU64::new(0),
-        }
+        Self::with_capacity(0)
     }
 
     /// Create with expected number of branches
@@ -278,9 +401,129 @@ impl ShardedStore {
         Self {
             shards: DashMap::with_capacity(num_branches),
             version: AtomicU64::new(0),
+            fork_bases: DashMap::new(),
+            approx_bytes: AtomicUsize::new(0),
+            kv_entries: AtomicUsize::new(0),
+            limits: None,
+            access_clock: AtomicU64::new(0),
+            pins: SnapshotPins::default(),
+            clock: RwLock::new(Arc::new(SystemClock)),
+            values: ValuePool::new(),
+            tombstone_ttl: RwLock::new(None),
         }
     }
 
+    /// Create a store that evicts least-recently-used KV entries when over
+    /// budget
+    ///
+    /// Unbounded `limits` behave like [`new`](Self::new). See
+    /// [`evict_to_limits`](Self::evict_to_limits).
+    pub fn with_limits(limits: CacheLimits) -> Self {
+        Self {
+            limits: limits.is_bounded().then_some(limits),
+            ..Self::new()
+        }
+    }
+
+    /// Eviction budgets, if this store is bounded
+    pub fn limits(&self) -> Option<CacheLimits> {
+        self.limits
+    }
+
+    /// Replace the clock that stamps new versions
+    ///
+    /// Affects writes made after the call; existing versions keep their
+    /// timestamps.
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        *self.clock.write().unwrap_or_else(|e| e.into_inner()) = clock;
+    }
+
+    /// The clock that stamps new versions
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.clock.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Current time according to this store's clock
+    pub fn now(&self) -> Timestamp {
+        self.clock.read().unwrap_or_else(|e| e.into_inner()).now()
+    }
+
+    /// Store values of at least `bytes` heap bytes once per distinct content
+    ///
+    /// Versions whose value matches an already stored large value, in any
+    /// key or branch, share that copy instead of holding their own. The
+    /// shared copy is freed when GC, eviction or branch deletion drops the
+    /// last version using it. `None` (the default) stores every value
+    /// inline. Applies to writes made after the call.
+    pub fn set_dedup_threshold(&self, bytes: Option<usize>) {
+        self.values.set_threshold(bytes);
+    }
+
+    /// Heap size from which values are shared, `None` if dedup is off
+    pub fn dedup_threshold(&self) -> Option<usize> {
+        self.values.threshold()
+    }
+
+    /// Let GC drop deleted keys once their delete is older than `ttl`
+    ///
+    /// [`gc_branch`](Self::gc_branch) always keeps the newest version of a
+    /// key, so by default a delete leaves its tombstone in place forever and
+    /// the key's deletion stays observable through its history. With a TTL,
+    /// a key whose only remaining version is a tombstone at least `ttl` old
+    /// is removed entirely. Branches involved in copy-on-write forks are
+    /// skipped, since removing a child's tombstone would expose the parent's
+    /// value. `None` (the default) keeps every tombstone.
+    pub fn set_tombstone_ttl(&self, ttl: Option<Duration>) {
+        *self
+            .tombstone_ttl
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = ttl;
+    }
+
+    /// How long GC keeps a deleted key, `None` if tombstones are kept forever
+    pub fn tombstone_ttl(&self) -> Option<Duration> {
+        *self.tombstone_ttl.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Number of distinct large values currently shared between versions
+    pub fn shared_value_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Approximate in-memory footprint of all shards, in bytes
+    ///
+    /// Uses the same accounting as
+    /// [`branch_size_estimate`](Self::branch_size_estimate).
+    pub fn approx_bytes(&self) -> usize {
+        self.approx_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Number of resident KV keys, deleted keys included
+    pub fn kv_entry_count(&self) -> usize {
+        self.kv_entries.load(Ordering::Relaxed)
+    }
+
+    /// Number of snapshots currently pinning versions
+    ///
+    /// Counts snapshots from [`pinned_snapshot`](Self::pinned_snapshot) and,
+    /// in a bounded store, from [`snapshot`](Self::snapshot).
+    pub fn pinned_snapshot_count(&self) -> usize {
+        self.pins.count()
+    }
+
+    /// Version of the oldest snapshot currently pinning versions, if any
+    pub fn oldest_pinned_version(&self) -> Option<u64> {
+        self.pins.lock().keys().next().copied()
+    }
+
+    /// Versions pinned by live snapshots, oldest first
+    ///
+    /// One entry per pinned version, with the number of snapshots pinning
+    /// it and how long ago it was first pinned.
+    pub fn pinned_snapshots(&self) -> Vec<PinnedSnapshotInfo> {
+        self.pins.list()
+    }
+
     /// Get current version
     #[inline]
     pub fn version(&self) -> u64 {
@@ -312,6 +555,17 @@ impl ShardedStore {
         self.shards.len()
     }
 
+    /// Approximate in-memory footprint of a branch's own data, in bytes
+    ///
+    /// Counts every key and retained version, tombstones included. Data a
+    /// copy-on-write fork reads through to its parent is not counted.
+    pub fn branch_size_estimate(&self, branch_id: &BranchId) -> usize {
+        self.shards
+            .get(branch_id)
+            .map(|shard| shard_size_estimate(&shard))
+            .unwrap_or(0)
+    }
+
     /// Check if a branch exists
     pub fn has_branch(&self, branch_id: &BranchId) -> bool {
         self.shards.contains_key(branch_id)
@@ -349,14 +603,51 @@ impl ShardedStore {
     pub fn put(&self, key: Key, value: StoredValue) {
         let branch_id = key.namespace.branch_id;
         let mut shard = self.shards.entry(branch_id).or_default();
+        self.push_version(&mut shard, key, value);
+    }
 
+    /// Append a version to a key's chain, creating the chain if needed
+    ///
+    /// Keeps the byte and entry counters and LRU recency up to date.
+    #[inline]
+    fn push_version(&self, shard: &mut Shard, key: Key, value: StoredValue) {
+        let value = value.share_in(&self.values);
+        let mut added = value.size_estimate();
+        let tick = self.tick();
         if let Some(chain) = shard.data.get_mut(&key) {
             // Add new version to existing chain
             chain.push(value);
+            if let Some(tick) = tick {
+                chain.touch(tick);
+            }
         } else {
             // Create new chain — also add to BTreeSet index
+            added += key_size_estimate(&key);
+            if key.type_tag == TypeTag::KV {
+                self.kv_entries.fetch_add(1, Ordering::Relaxed);
+            }
+            let chain = VersionChain::new(value);
+            if let Some(tick) = tick {
+                chain.touch(tick);
+            }
             shard.ordered_keys.insert(key.clone());
-            shard.data.insert(key, VersionChain::new(value));
+            shard.data.insert(key, chain);
+        }
+        self.approx_bytes.fetch_add(added, Ordering::Relaxed);
+    }
+
+    /// Next LRU clock tick, or `None` for an unbounded store
+    #[inline]
+    fn tick(&self) -> Option<u64> {
+        self.limits
+            .map(|_| self.access_clock.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    /// Record a read of `chain` for LRU ordering
+    #[inline]
+    fn touch(&self, chain: &VersionChain) {
+        if let Some(tick) = self.tick() {
+            chain.touch(tick);
         }
     }
 
@@ -401,14 +692,14 @@ impl ShardedStore {
                     if sv.is_tombstone() {
                         None
                     } else {
-                        Some(sv.versioned().clone())
+                        Some(sv.to_versioned())
                     }
                 })
             })
         });
 
         // Add tombstone to version chain
-        let tombstone = StoredValue::tombstone(Version::txn(version));
+        let tombstone = StoredValue::tombstone_at(Version::txn(version), self.now());
         self.put(key.clone(), tombstone);
 
         Ok(previous)
@@ -421,15 +712,19 @@ impl ShardedStore {
     #[inline]
     pub fn contains(&self, key: &Key) -> bool {
         let branch_id = key.namespace.branch_id;
-        self.shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .data
-                    .get(key)
-                    .is_some_and(|chain| chain.latest().is_some_and(|sv| !sv.is_tombstone()))
-            })
-            .unwrap_or(false)
+        let local = self.shards.get(&branch_id).and_then(|shard| {
+            shard
+                .data
+                .get(key)
+                .and_then(|chain| chain.latest().map(|sv| !sv.is_tombstone()))
+        });
+        match local {
+            Some(live) => live,
+            None => self.fork_base(&branch_id).is_some_and(|base| {
+                self.resolve_at_version(&rebase_key(key, base.parent), base.version)
+                    .is_some()
+            }),
+        }
     }
 
     /// Apply a batch of writes and deletes atomically
@@ -460,7 +755,7 @@ impl ShardedStore {
         use std::sync::atomic::Ordering;
 
         // Capture timestamp once for entire batch
-        let timestamp = Timestamp::now();
+        let timestamp = self.now();
 
         // Group writes and deletes by branch_id to apply atomically per branch.
         // This ensures concurrent readers never see partial transaction state
@@ -491,22 +786,12 @@ impl ShardedStore {
             let mut shard = self.shards.entry(branch_id).or_default();
 
             for (key, stored) in branch_writes {
-                if let Some(chain) = shard.data.get_mut(&key) {
-                    chain.push(stored);
-                } else {
-                    shard.ordered_keys.insert(key.clone());
-                    shard.data.insert(key, VersionChain::new(stored));
-                }
+                self.push_version(&mut shard, key, stored);
             }
 
             for key in branch_deletes {
-                let tombstone = StoredValue::tombstone(Version::txn(version));
-                if let Some(chain) = shard.data.get_mut(&key) {
-                    chain.push(tombstone);
-                } else {
-                    shard.ordered_keys.insert(key.clone());
-                    shard.data.insert(key, VersionChain::new(tombstone));
-                }
+                let tombstone = StoredValue::tombstone_at(Version::txn(version), timestamp);
+                self.push_version(&mut shard, key, tombstone);
             }
         }
 
@@ -527,52 +812,30 @@ impl ShardedStore {
 
     /// Get value at or before the given timestamp.
     /// Returns None if key doesn't exist, has no version at that time, is expired, or is a tombstone.
+    ///
+    /// Copy-on-write forks fall through to their parent, as for [`Storage::get`].
     pub fn get_at_timestamp(
         &self,
         key: &Key,
         max_timestamp: u64,
     ) -> strata_core::StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.get_at_timestamp(max_timestamp).and_then(|sv| {
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
-            })
-        }))
+        Ok(self.resolve_at(key, u64::MAX, max_timestamp))
     }
 
     /// Scan keys matching a prefix, returning values at or before the given timestamp.
+    ///
+    /// Includes entries a copy-on-write fork inherits from its parent.
     pub fn scan_prefix_at_timestamp(
         &self,
         prefix: &Key,
         max_timestamp: u64,
     ) -> strata_core::StrataResult<Vec<(Key, VersionedValue)>> {
-        let branch_id = prefix.namespace.branch_id;
-        Ok(self
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_timestamp(max_timestamp).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default())
+        Ok(self.collect_at(
+            prefix.namespace.branch_id,
+            u64::MAX,
+            max_timestamp,
+            &KeyScope::Prefix(Cow::Borrowed(prefix)),
+        ))
     }
 
     /// Get the available time range for a branch.
@@ -610,16 +873,441 @@ impl ShardedStore {
     ///
     /// Calls `VersionChain::gc(min_version)` on each entry in the branch's shard.
     /// Returns the total number of pruned versions.
+    ///
+    /// If the branch is the parent of copy-on-write forks, the version each
+    /// child reads at its fork base is retained regardless of `min_version`.
+    /// Likewise, the versions visible to every pinned snapshot are retained
+    /// until the snapshot is dropped.
+    ///
+    /// With a [tombstone TTL](Self::set_tombstone_ttl), keys left holding
+    /// only an expired tombstone are removed too, each counted as one pruned
+    /// version.
     pub fn gc_branch(&self, branch_id: BranchId, min_version: u64) -> usize {
+        self.gc_branch_chains(branch_id, |_| min_version, None, Some(min_version))
+    }
+
+    /// Garbage-collect all but the newest `keep_last` versions of each entry
+    /// for a given branch.
+    ///
+    /// The latest version is always kept, so `keep_last` of 0 acts as 1.
+    /// Versions needed by copy-on-write forks and pinned snapshots are
+    /// retained as in [`gc_branch`](Self::gc_branch), and so is the version
+    /// of each entry visible at `snapshot_floor`, for snapshots the store
+    /// does not pin itself. Expired tombstones are not removed. Returns the
+    /// total number of pruned versions.
+    pub fn gc_branch_keep_last(
+        &self,
+        branch_id: BranchId,
+        keep_last: usize,
+        snapshot_floor: Option<u64>,
+    ) -> usize {
+        let depth = keep_last.max(1) - 1;
+        self.gc_branch_chains(
+            branch_id,
+            |chain| chain.nth_newest_version(depth).unwrap_or(0),
+            snapshot_floor,
+            None,
+        )
+    }
+
+    /// Prune each chain in a branch below the version `min_version_of`
+    /// picks for it, keeping what fork bases, pinned snapshots and
+    /// `snapshot_floor` can see. When `expire_before` is set, keys holding
+    /// only a tombstone older than it that has outlived the tombstone TTL
+    /// are removed too.
+    fn gc_branch_chains(
+        &self,
+        branch_id: BranchId,
+        min_version_of: impl Fn(&VersionChain) -> u64,
+        snapshot_floor: Option<u64>,
+        expire_before: Option<u64>,
+    ) -> usize {
+        let fork_floor = self.fork_floor(&branch_id);
+        let expire_tombstones = expire_before.and_then(|min_version| {
+            self.tombstone_ttl()
+                .filter(|_| fork_floor.is_none() && !self.fork_bases.contains_key(&branch_id))
+                .map(|ttl| (self.now(), ttl, min_version))
+        });
+        // Held for the whole pass so no snapshot older than the pass is
+        // pinned while its versions are being pruned.
+        let pins = self.pins.lock();
+        let oldest_pin = pins.keys().next().copied();
         let mut pruned = 0;
+        let mut freed = 0;
         if let Some(mut shard) = self.shards.get_mut(&branch_id) {
             for chain in shard.data.values_mut() {
-                pruned += chain.gc(min_version);
+                let keep_from = [fork_floor, oldest_pin, snapshot_floor]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|floor| chain.get_at_version(floor))
+                    .map(|sv| sv.version().as_u64())
+                    .fold(min_version_of(chain), u64::min);
+                let before = chain.size_estimate();
+                let count = chain.gc(keep_from);
+                if count > 0 {
+                    pruned += count;
+                    freed += before - chain.size_estimate();
+                }
             }
+            if let Some((now, ttl, min_version)) = expire_tombstones {
+                let expired: Vec<Key> = shard
+                    .data
+                    .iter()
+                    .filter(|(_, chain)| {
+                        chain.version_count() == 1
+                            && chain.latest().is_some_and(|sv| {
+                                sv.is_tombstone()
+                                    && sv.version().as_u64() < min_version
+                                    && now
+                                        .duration_since(sv.timestamp())
+                                        .is_some_and(|age| age >= ttl)
+                            })
+                    })
+                    .map(|(key, _)| key.clone())
+                    .collect();
+                for key in expired {
+                    if let Some(chain) = shard.data.remove(&key) {
+                        shard.ordered_keys.remove(&key);
+                        freed += key_size_estimate(&key) + chain.size_estimate();
+                        if key.type_tag == TypeTag::KV {
+                            self.kv_entries.fetch_sub(1, Ordering::Relaxed);
+                        }
+                        pruned += 1;
+                    }
+                }
+            }
+        }
+        self.approx_bytes.fetch_sub(freed, Ordering::Relaxed);
+        if pruned > 0 {
+            self.values.prune();
         }
         pruned
     }
 
+    // ========================================================================
+    // Eviction
+    // ========================================================================
+
+    /// Evict least-recently-used KV keys while the store is over budget
+    ///
+    /// No-op for an unbounded store or one within its limits. Otherwise
+    /// removes whole KV chains, deleted keys first and then by last access,
+    /// until usage is back under the low-water mark
+    /// ([`LOW_WATER_PERCENT`](crate::eviction::LOW_WATER_PERCENT) of each
+    /// budget) or no candidates remain.
+    ///
+    /// A key is a candidate only if no live snapshot can see it, i.e. its
+    /// oldest retained version is newer than every pinned snapshot. Branches
+    /// involved in copy-on-write forks are skipped, since removing a child's
+    /// key would expose the parent's value and a parent's key may be read
+    /// through by children.
+    ///
+    /// Returns the evicted keys so callers can drop derived state such as
+    /// search index entries.
+    pub fn evict_to_limits(&self) -> Vec<Key> {
+        let Some(limits) = self.limits else {
+            return Vec::new();
+        };
+        if !limits.exceeded(self.approx_bytes(), self.kv_entry_count()) {
+            return Vec::new();
+        }
+
+        // Hold the pin table for the whole pass so no snapshot is created
+        // between choosing victims and removing them.
+        let pins = self.pins.lock();
+        let newest_pin = pins.keys().next_back().copied();
+
+        let forked: FxHashSet<BranchId> = self
+            .fork_bases
+            .iter()
+            .flat_map(|entry| [*entry.key(), entry.value().parent])
+            .collect();
+
+        // (live, last_access, key): deleted keys sort first, then oldest access
+        let mut candidates: Vec<(bool, u64, Key)> = Vec::new();
+        for shard in self.shards.iter() {
+            if forked.contains(shard.key()) {
+                continue;
+            }
+            for (key, chain) in shard.data.iter() {
+                if key.type_tag != TypeTag::KV {
+                    continue;
+                }
+                let visible_to_snapshot = match (newest_pin, chain.oldest_version()) {
+                    (Some(pin), Some(oldest)) => oldest <= pin,
+                    _ => false,
+                };
+                if visible_to_snapshot {
+                    continue;
+                }
+                let live = chain.latest().is_some_and(|sv| !sv.is_tombstone());
+                candidates.push((live, chain.last_access(), key.clone()));
+            }
+        }
+        candidates.sort_unstable_by_key(|(live, tick, _)| (*live, *tick));
+
+        let mut evicted = Vec::new();
+        for (_, _, key) in candidates {
+            if !limits.above_low_water(self.approx_bytes(), self.kv_entry_count()) {
+                break;
+            }
+            let Some(mut shard) = self.shards.get_mut(&key.namespace.branch_id) else {
+                continue;
+            };
+            if let Some(chain) = shard.data.remove(&key) {
+                shard.ordered_keys.remove(&key);
+                self.approx_bytes.fetch_sub(
+                    key_size_estimate(&key) + chain.size_estimate(),
+                    Ordering::Relaxed,
+                );
+                self.kv_entries.fetch_sub(1, Ordering::Relaxed);
+                evicted.push(key);
+            }
+        }
+        drop(pins);
+        if !evicted.is_empty() {
+            self.values.prune();
+        }
+        evicted
+    }
+
+    // ========================================================================
+    // Copy-on-write Forks
+    // ========================================================================
+
+    /// Point `child` at a parent branch version for copy-on-write reads
+    ///
+    /// Until the child writes a key, reads of that key resolve against the
+    /// parent as of `base.version`. No data is copied.
+    pub fn set_fork_base(&self, child: BranchId, base: ForkBase) {
+        self.fork_bases.insert(child, base);
+    }
+
+    /// Get the copy-on-write parent link for a branch, if any
+    pub fn fork_base(&self, branch_id: &BranchId) -> Option<ForkBase> {
+        self.fork_bases.get(branch_id).map(|base| *base)
+    }
+
+    /// Remove the copy-on-write parent link for a branch
+    ///
+    /// Inherited keys stop being visible in the child afterwards; callers
+    /// must materialize them first if they should survive.
+    pub fn clear_fork_base(&self, branch_id: &BranchId) -> Option<ForkBase> {
+        self.fork_bases.remove(branch_id).map(|(_, base)| base)
+    }
+
+    /// List branches that read through to `parent`
+    pub fn fork_children(&self, parent: &BranchId) -> Vec<BranchId> {
+        self.fork_bases
+            .iter()
+            .filter(|entry| entry.value().parent == *parent)
+            .map(|entry| *entry.key())
+            .collect()
+    }
+
+    /// Oldest fork base version any child of `parent` still reads at
+    fn fork_floor(&self, parent: &BranchId) -> Option<u64> {
+        self.fork_bases
+            .iter()
+            .filter(|entry| entry.value().parent == *parent)
+            .map(|entry| entry.value().version)
+            .min()
+    }
+
+    /// Resolve a point read at `max_version`, falling through to the fork
+    /// parent when the branch holds no version of the key at that point.
+    ///
+    /// A tombstone in the child hides the parent value.
+    fn resolve_at_version(&self, key: &Key, max_version: u64) -> Option<VersionedValue> {
+        self.resolve_at(key, max_version, u64::MAX)
+    }
+
+    /// Resolve a point read at or before both `max_version` and
+    /// `max_timestamp`, falling through to the fork parent like
+    /// [`resolve_at_version`](Self::resolve_at_version).
+    fn resolve_at(
+        &self,
+        key: &Key,
+        max_version: u64,
+        max_timestamp: u64,
+    ) -> Option<VersionedValue> {
+        let branch_id = key.namespace.branch_id;
+        if let Some(shard) = self.shards.get(&branch_id) {
+            if let Some(sv) = shard.data.get(key).and_then(|chain| {
+                self.touch(chain);
+                chain.get_at(max_version, max_timestamp)
+            }) {
+                return if !sv.is_expired() && !sv.is_tombstone() {
+                    Some(sv.to_versioned())
+                } else {
+                    None
+                };
+            }
+        }
+        // Shard guard is released before walking up to the parent
+        let base = self.fork_base(&branch_id)?;
+        self.resolve_at(
+            &rebase_key(key, base.parent),
+            max_version.min(base.version),
+            max_timestamp,
+        )
+    }
+
+    /// Version history for a key, continuing into the fork parent's history
+    /// up to the fork base.
+    fn history_through(
+        &self,
+        key: &Key,
+        limit: Option<usize>,
+        before_version: Option<u64>,
+    ) -> Vec<VersionedValue> {
+        let branch_id = key.namespace.branch_id;
+        let mut result: Vec<VersionedValue> = match self.shards.get(&branch_id) {
+            Some(shard) => match shard.data.get(key) {
+                Some(chain) => chain
+                    .history(limit, before_version)
+                    .into_iter()
+                    .filter(|sv| !sv.is_expired())
+                    .map(|sv| sv.to_versioned())
+                    .collect(),
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+
+        if let Some(base) = self.fork_base(&branch_id) {
+            let remaining = limit.map(|l| l.saturating_sub(result.len()));
+            if remaining != Some(0) {
+                let parent_before = before_version
+                    .unwrap_or(u64::MAX)
+                    .min(base.version.saturating_add(1));
+                result.extend(self.history_through(
+                    &rebase_key(key, base.parent),
+                    remaining,
+                    Some(parent_before),
+                ));
+            }
+        }
+        result
+    }
+
+    /// Live entries of a branch at `max_version` within `scope`, including
+    /// entries inherited from its fork parent. Sorted by key.
+    fn collect_at_version(
+        &self,
+        branch_id: BranchId,
+        max_version: u64,
+        scope: &KeyScope<'_>,
+    ) -> Vec<(Key, VersionedValue)> {
+        self.collect_at(branch_id, max_version, u64::MAX, scope)
+    }
+
+    /// Like [`collect_at_version`](Self::collect_at_version), but also bounded
+    /// by write timestamp for time-travel reads.
+    fn collect_at(
+        &self,
+        branch_id: BranchId,
+        max_version: u64,
+        max_timestamp: u64,
+        scope: &KeyScope<'_>,
+    ) -> Vec<(Key, VersionedValue)> {
+        let mut results: Vec<(Key, VersionedValue)> = self
+            .shards
+            .get(&branch_id)
+            .map(|shard| {
+                let visible = |k: &Key| {
+                    shard.data.get(k).and_then(|chain| {
+                        chain.get_at(max_version, max_timestamp).and_then(|sv| {
+                            if !sv.is_expired() && !sv.is_tombstone() {
+                                Some((k.clone(), sv.to_versioned()))
+                            } else {
+                                None
+                            }
+                        })
+                    })
+                };
+                match scope {
+                    KeyScope::Branch => shard.ordered_keys.iter().filter_map(visible).collect(),
+                    KeyScope::Type(type_tag) => shard
+                        .ordered_keys
+                        .iter()
+                        .filter(|k| k.type_tag == *type_tag)
+                        .filter_map(visible)
+                        .collect(),
+                    KeyScope::Prefix(prefix) => {
+                        shard.keys_with_prefix(prefix).filter_map(visible).collect()
+                    }
+                }
+            })
+            .unwrap_or_default();
+        self.merge_inherited_at(branch_id, max_version, max_timestamp, scope, &mut results);
+        results
+    }
+
+    /// Merge entries a copy-on-write child inherits from its fork parent
+    /// into `results` (the child's own entries), keeping key order.
+    ///
+    /// Parent entries are rebased into the child's namespace. Keys the child
+    /// holds a version of at `max_version`, tombstones included, shadow the
+    /// parent. Branch metadata is never inherited. No-op for branches
+    /// without a fork base.
+    fn merge_inherited(
+        &self,
+        branch_id: BranchId,
+        max_version: u64,
+        scope: &KeyScope<'_>,
+        results: &mut Vec<(Key, VersionedValue)>,
+    ) {
+        self.merge_inherited_at(branch_id, max_version, u64::MAX, scope, results)
+    }
+
+    /// [`merge_inherited`](Self::merge_inherited) bounded by write timestamp
+    /// as well as version.
+    fn merge_inherited_at(
+        &self,
+        branch_id: BranchId,
+        max_version: u64,
+        max_timestamp: u64,
+        scope: &KeyScope<'_>,
+        results: &mut Vec<(Key, VersionedValue)>,
+    ) {
+        let Some(base) = self.fork_base(&branch_id) else {
+            return;
+        };
+        let parent_entries = self.collect_at(
+            base.parent,
+            max_version.min(base.version),
+            max_timestamp,
+            &scope.rebased(base.parent),
+        );
+        if parent_entries.is_empty() {
+            return;
+        }
+
+        let inherited: Vec<(Key, VersionedValue)> = {
+            let shard = self.shards.get(&branch_id);
+            parent_entries
+                .into_iter()
+                .filter(|(k, _)| k.type_tag != strata_core::types::TypeTag::Branch)
+                .filter_map(|(k, vv)| {
+                    let child_key = rebase_key(&k, branch_id);
+                    let shadowed = shard.as_ref().is_some_and(|shard| {
+                        shard
+                            .data
+                            .get(&child_key)
+                            .is_some_and(|chain| chain.get_at(max_version, max_timestamp).is_some())
+                    });
+                    (!shadowed).then_some((child_key, vv))
+                })
+                .collect()
+        };
+
+        if !inherited.is_empty() {
+            results.extend(inherited);
+            results.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+    }
+
     // ========================================================================
     // List Operations
     // ========================================================================
@@ -637,7 +1325,8 @@ impl ShardedStore {
     ///
     /// Vector of (Key, VersionedValue) pairs, sorted by key
     pub fn list_branch(&self, branch_id: &BranchId) -> Vec<(Key, VersionedValue)> {
-        self.shards
+        let mut results: Vec<(Key, VersionedValue)> = self
+            .shards
             .get(branch_id)
             .map(|shard| {
                 shard
@@ -647,7 +1336,7 @@ impl ShardedStore {
                         shard.data.get(k).and_then(|chain| {
                             chain.latest().and_then(|sv| {
                                 if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -656,7 +1345,9 @@ impl ShardedStore {
                     })
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        self.merge_inherited(*branch_id, u64::MAX, &KeyScope::Branch, &mut results);
+        results
     }
 
     /// List entries matching a key prefix
@@ -677,7 +1368,8 @@ impl ShardedStore {
     pub fn list_by_prefix(&self, prefix: &Key) -> Vec<(Key, VersionedValue)> {
         let branch_id = prefix.namespace.branch_id;
 
-        self.shards
+        let mut results: Vec<(Key, VersionedValue)> = self
+            .shards
             .get(&branch_id)
             .map(|shard| {
                 shard
@@ -686,7 +1378,7 @@ impl ShardedStore {
                         shard.data.get(k).and_then(|chain| {
                             chain.latest().and_then(|sv| {
                                 if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -695,7 +1387,14 @@ impl ShardedStore {
                     })
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        self.merge_inherited(
+            branch_id,
+            u64::MAX,
+            &KeyScope::Prefix(Cow::Borrowed(prefix)),
+            &mut results,
+        );
+        results
     }
 
     /// List entries of a specific type for a branch
@@ -715,7 +1414,8 @@ impl ShardedStore {
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
     ) -> Vec<(Key, VersionedValue)> {
-        self.shards
+        let mut results: Vec<(Key, VersionedValue)> = self
+            .shards
             .get(branch_id)
             .map(|shard| {
                 shard
@@ -726,7 +1426,7 @@ impl ShardedStore {
                         shard.data.get(k).and_then(|chain| {
                             chain.latest().and_then(|sv| {
                                 if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -735,7 +1435,14 @@ impl ShardedStore {
                     })
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        self.merge_inherited(
+            *branch_id,
+            u64::MAX,
+            &KeyScope::Type(type_tag),
+            &mut results,
+        );
+        results
     }
 
     /// Count entries of a specific type for a branch (excludes tombstones)
@@ -744,6 +1451,9 @@ impl ShardedStore {
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
     ) -> usize {
+        if self.fork_bases.contains_key(branch_id) {
+            return self.list_by_type(branch_id, type_tag).len();
+        }
         self.shards
             .get(branch_id)
             .map(|shard| {
@@ -775,7 +1485,61 @@ impl ShardedStore {
     /// Removes the entire shard for the given branch.
     /// Returns true if the branch existed and was removed.
     pub fn clear_branch(&self, branch_id: &BranchId) -> bool {
-        self.shards.remove(branch_id).is_some()
+        self.fork_bases.remove(branch_id);
+        match self.shards.remove(branch_id) {
+            Some((_, shard)) => {
+                self.release_shard(shard);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Clear a deleted branch unless it was written after `deleted_at`
+    ///
+    /// Like [`clear_branch`](Self::clear_branch), but leaves the branch
+    /// alone if any key has a version newer than `deleted_at`, which means
+    /// it was created again since; a fork link made since is kept too. The
+    /// check and the removal happen under the shard lock. Returns the number
+    /// of keys and the estimated bytes removed, or `None` if the branch was
+    /// kept.
+    pub fn clear_deleted_branch(
+        &self,
+        branch_id: &BranchId,
+        deleted_at: u64,
+    ) -> Option<(usize, usize)> {
+        let removed = self.shards.remove_if(branch_id, |_, shard| {
+            shard.data.values().all(|chain| {
+                chain
+                    .latest()
+                    .map_or(true, |sv| sv.version().as_u64() <= deleted_at)
+            })
+        });
+        if removed.is_none() && self.shards.contains_key(branch_id) {
+            return None;
+        }
+        self.fork_bases
+            .remove_if(branch_id, |_, base| base.version < deleted_at);
+        Some(removed.map_or((0, 0), |(_, shard)| {
+            (shard.len(), self.release_shard(shard))
+        }))
+    }
+
+    /// Drop a shard removed from the map, updating the store's counters
+    ///
+    /// Returns the shard's estimated size.
+    fn release_shard(&self, shard: Shard) -> usize {
+        let kv = shard
+            .data
+            .keys()
+            .filter(|key| key.type_tag == TypeTag::KV)
+            .count();
+        let size = shard_size_estimate(&shard);
+        self.approx_bytes.fetch_sub(size, Ordering::Relaxed);
+        self.kv_entries.fetch_sub(kv, Ordering::Relaxed);
+        drop(shard);
+        self.values.prune();
+        size
     }
 
     // ========================================================================
@@ -811,11 +1575,44 @@ impl ShardedStore {
     /// // Reads through snapshot see store state at snapshot time
     /// let value = snapshot.get(&key);
     /// ```
+    ///
+    /// A bounded store also pins the snapshot's version (see
+    /// [`evict_to_limits`](Self::evict_to_limits)), which takes a short lock.
     #[inline]
     pub fn snapshot(self: &Arc<Self>) -> ShardedSnapshot {
+        if self.limits.is_none() {
+            return ShardedSnapshot {
+                version: self.version.load(Ordering::Acquire),
+                store: Arc::clone(self),
+                pinned: false,
+            };
+        }
+        let mut pins = self.pins.lock();
+        let version = self.version.load(Ordering::Acquire);
+        SnapshotPins::pin_locked(&mut pins, version);
+        ShardedSnapshot {
+            version,
+            store: Arc::clone(self),
+            pinned: true,
+        }
+    }
+
+    /// Create a snapshot that holds back garbage collection
+    ///
+    /// Like [`snapshot`](Self::snapshot), but always pins its version, even
+    /// in an unbounded store. While the snapshot (or any clone of it) is
+    /// alive, [`gc_branch`](Self::gc_branch) keeps every version it can see
+    /// and [`evict_to_limits`](Self::evict_to_limits) skips keys it can see.
+    /// Use it for long-running reads such as exports; drop it promptly, as
+    /// superseded versions accumulate until it is released.
+    pub fn pinned_snapshot(self: &Arc<Self>) -> ShardedSnapshot {
+        let mut pins = self.pins.lock();
+        let version = self.version.load(Ordering::Acquire);
+        SnapshotPins::pin_locked(&mut pins, version);
         ShardedSnapshot {
-            version: self.version.load(Ordering::Acquire),
+            version,
             store: Arc::clone(self),
+            pinned: true,
         }
     }
 
@@ -888,12 +1685,34 @@ impl std::fmt::Debug for ShardedStore {
 ///
 /// ShardedSnapshot is Send + Sync since it only holds Arc<ShardedStore>.
 /// Multiple snapshots can exist concurrently without blocking.
-#[derive(Clone)]
 pub struct ShardedSnapshot {
     /// Version captured at snapshot time
     version: u64,
     /// Reference to the underlying store
     store: Arc<ShardedStore>,
+    /// Whether this snapshot holds a pin in a bounded store
+    pinned: bool,
+}
+
+impl Clone for ShardedSnapshot {
+    fn clone(&self) -> Self {
+        if self.pinned {
+            self.store.pins.pin(self.version);
+        }
+        Self {
+            version: self.version,
+            store: Arc::clone(&self.store),
+            pinned: self.pinned,
+        }
+    }
+}
+
+impl Drop for ShardedSnapshot {
+    fn drop(&mut self) {
+        if self.pinned {
+            self.store.pins.unpin(self.version);
+        }
+    }
 }
 
 impl ShardedSnapshot {
@@ -923,7 +1742,8 @@ impl ShardedSnapshot {
     /// filtering out expired values and tombstones.
     /// Results are sorted by key (BTreeSet iteration order).
     pub fn list_branch(&self, branch_id: &BranchId) -> Vec<(Key, VersionedValue)> {
-        self.store
+        let mut results: Vec<(Key, VersionedValue)> = self
+            .store
             .shards
             .get(branch_id)
             .map(|shard| {
@@ -934,7 +1754,7 @@ impl ShardedSnapshot {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(self.version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -943,7 +1763,10 @@ impl ShardedSnapshot {
                     })
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        self.store
+            .merge_inherited(*branch_id, self.version, &KeyScope::Branch, &mut results);
+        results
     }
 
     /// List entries matching a prefix at snapshot version
@@ -953,7 +1776,8 @@ impl ShardedSnapshot {
     /// Uses BTreeSet range scan for O(log n + k) performance.
     pub fn list_by_prefix(&self, prefix: &Key) -> Vec<(Key, VersionedValue)> {
         let branch_id = prefix.namespace.branch_id;
-        self.store
+        let mut results: Vec<(Key, VersionedValue)> = self
+            .store
             .shards
             .get(&branch_id)
             .map(|shard| {
@@ -963,7 +1787,7 @@ impl ShardedSnapshot {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(self.version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -972,7 +1796,14 @@ impl ShardedSnapshot {
                     })
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        self.store.merge_inherited(
+            branch_id,
+            self.version,
+            &KeyScope::Prefix(Cow::Borrowed(prefix)),
+            &mut results,
+        );
+        results
     }
 
     /// List entries of a specific type at snapshot version
@@ -985,7 +1816,8 @@ impl ShardedSnapshot {
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
     ) -> Vec<(Key, VersionedValue)> {
-        self.store
+        let mut results: Vec<(Key, VersionedValue)> = self
+            .store
             .shards
             .get(branch_id)
             .map(|shard| {
@@ -997,7 +1829,7 @@ impl ShardedSnapshot {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(self.version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -1006,7 +1838,14 @@ impl ShardedSnapshot {
                     })
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        self.store.merge_inherited(
+            *branch_id,
+            self.version,
+            &KeyScope::Type(type_tag),
+            &mut results,
+        );
+        results
     }
 
     /// Get count of entries for a branch at snapshot version
@@ -1084,38 +1923,14 @@ impl Storage for ShardedStore {
     ///
     /// Returns None if key doesn't exist, is expired, or is a tombstone.
     fn get(&self, key: &Key) -> StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.latest().and_then(|sv| {
-                    // Filter out expired values and tombstones
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
-            })
-        }))
+        Ok(self.resolve_at_version(key, u64::MAX))
     }
 
     /// Get value at or before specified version (for snapshot isolation)
     ///
     /// Returns the value if version <= max_version, not expired, and not a tombstone.
     fn get_versioned(&self, key: &Key, max_version: u64) -> StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.get_at_version(max_version).and_then(|sv| {
-                    // Filter out expired values and tombstones
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
-            })
-        }))
+        Ok(self.resolve_at_version(key, max_version))
     }
 
     /// Get version history for a key
@@ -1125,25 +1940,9 @@ impl Storage for ShardedStore {
         &self,
         key: &Key,
         limit: Option<usize>,
-        before_version: Option<u64>,
-    ) -> StrataResult<Vec<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-
-        // Get the shard and extract history within the same scope to avoid lifetime issues
-        let result = match self.shards.get(&branch_id) {
-            Some(shard) => match shard.data.get(key) {
-                Some(chain) => chain
-                    .history(limit, before_version)
-                    .into_iter()
-                    .filter(|sv| !sv.is_expired())
-                    .map(|sv| sv.versioned().clone())
-                    .collect(),
-                None => Vec::new(),
-            },
-            None => Vec::new(),
-        };
-
-        Ok(result)
+        before_version: Option<u64>,
+    ) -> StrataResult<Vec<VersionedValue>> {
+        Ok(self.history_through(key, limit, before_version))
     }
 
     /// Put key-value pair with optional TTL
@@ -1151,7 +1950,7 @@ impl Storage for ShardedStore {
     /// Allocates a new version and returns it.
     fn put(&self, key: Key, value: Value, ttl: Option<Duration>) -> StrataResult<u64> {
         let version = self.next_version();
-        let stored = StoredValue::new(value, Version::txn(version), ttl);
+        let stored = StoredValue::with_timestamp(value, Version::txn(version), self.now(), ttl);
 
         // Use the inherent put method which handles version chain
         ShardedStore::put(self, key, stored);
@@ -1175,27 +1974,11 @@ impl Storage for ShardedStore {
         prefix: &Key,
         max_version: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
-        let branch_id = prefix.namespace.branch_id;
-        Ok(self
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_version(max_version).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default())
+        Ok(self.collect_at_version(
+            prefix.namespace.branch_id,
+            max_version,
+            &KeyScope::Prefix(Cow::Borrowed(prefix)),
+        ))
     }
 
     /// Scan all keys for a given branch_id at or before max_version
@@ -1206,29 +1989,7 @@ impl Storage for ShardedStore {
         branch_id: BranchId,
         max_version: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
-        Ok(self
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                let mut results: Vec<_> = shard
-                    .data
-                    .iter()
-                    .filter_map(|(k, chain)| {
-                        chain.get_at_version(max_version).and_then(|sv| {
-                            // Filter out expired values and tombstones
-                            if !sv.is_expired() && !sv.is_tombstone() {
-                                Some((k.clone(), sv.versioned().clone()))
-                            } else {
-                                None
-                            }
-                        })
-                    })
-                    .collect();
-
-                results.sort_by(|(a, _), (b, _)| a.cmp(b));
-                results
-            })
-            .unwrap_or_default())
+        Ok(self.collect_at_version(branch_id, max_version, &KeyScope::Branch))
     }
 
     /// Get current global version
@@ -1246,7 +2007,7 @@ impl Storage for ShardedStore {
         version: u64,
         ttl: Option<Duration>,
     ) -> StrataResult<()> {
-        let stored = StoredValue::new(value, Version::txn(version), ttl);
+        let stored = StoredValue::with_timestamp(value, Version::txn(version), self.now(), ttl);
 
         // Use the inherent put method which handles version chain
         ShardedStore::put(self, key, stored);
@@ -1291,34 +2052,22 @@ impl SnapshotView for ShardedSnapshot {
     /// Uses BTreeSet range scan for O(log n + k) performance.
     /// Returns all matching keys at or before snapshot version.
     fn scan_prefix(&self, prefix: &Key) -> StrataResult<Vec<(Key, VersionedValue)>> {
-        let branch_id = prefix.namespace.branch_id;
-        Ok(self
-            .store
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_version(self.version).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default())
+        Ok(self.store.collect_at_version(
+            prefix.namespace.branch_id,
+            self.version,
+            &KeyScope::Prefix(Cow::Borrowed(prefix)),
+        ))
     }
 
     /// Get snapshot version
     fn version(&self) -> u64 {
         self.version
     }
+
+    /// Current time according to the store's clock
+    fn now(&self) -> Timestamp {
+        self.store.now()
+    }
 }
 
 #[cfg(test)]
@@ -1548,6 +2297,31 @@ mod tests {
         assert!(store.get(&key3).unwrap().is_none());
     }
 
+    #[test]
+    fn test_apply_batch_uses_store_clock() {
+        use strata_core::value::Value;
+        use strata_core::ManualClock;
+
+        let store = ShardedStore::new();
+        let clock = Arc::new(ManualClock::new(Timestamp::from_micros(1_000)));
+        store.set_clock(clock.clone());
+        let branch_id = BranchId::new();
+        let key = create_test_key(branch_id, "clocked");
+
+        store
+            .apply_batch(&[(key.clone(), Value::Int(1))], &[], 1)
+            .unwrap();
+        clock.set(Timestamp::from_micros(2_000));
+        store
+            .apply_batch(&[], std::slice::from_ref(&key), 2)
+            .unwrap();
+
+        let written = store.get_at_timestamp(&key, 1_500).unwrap().unwrap();
+        assert_eq!(written.timestamp, Timestamp::from_micros(1_000));
+        assert!(store.get_at_timestamp(&key, 2_000).unwrap().is_none());
+        assert!(store.get_at_timestamp(&key, 999).unwrap().is_none());
+    }
+
     #[test]
     fn test_branch_entry_count() {
         use strata_core::value::Value;
@@ -1835,6 +2609,370 @@ mod tests {
         assert!(!store.has_branch(&branch_id));
     }
 
+    #[test]
+    fn test_branch_size_estimate() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        assert_eq!(store.branch_size_estimate(&branch_id), 0);
+
+        let key = create_test_key(branch_id, "key");
+        store.put(key.clone(), create_stored_value(Value::Int(1), 1));
+        let one_version = store.branch_size_estimate(&branch_id);
+        assert!(one_version > 0);
+
+        // Every retained version counts
+        store.put(key, create_stored_value(Value::String("x".repeat(256)), 2));
+        assert!(store.branch_size_estimate(&branch_id) >= one_version + 256);
+    }
+
+    #[test]
+    fn test_approx_bytes_tracks_writes_gc_and_clear() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let a = BranchId::new();
+        let b = BranchId::new();
+        let key = create_test_key(a, "key");
+        store.put(key.clone(), create_stored_value(Value::Int(1), 1));
+        store.put(
+            key.clone(),
+            create_stored_value(Value::String("x".repeat(64)), 2),
+        );
+        store.delete_with_version(&key, 3).unwrap();
+        store.put(
+            create_test_key(b, "other"),
+            create_stored_value(Value::Int(7), 4),
+        );
+
+        let total = |s: &ShardedStore| s.branch_size_estimate(&a) + s.branch_size_estimate(&b);
+        assert_eq!(store.approx_bytes(), total(&store));
+        assert_eq!(store.kv_entry_count(), 2);
+
+        assert_eq!(store.gc_branch(a, 3), 2);
+        assert_eq!(store.approx_bytes(), total(&store));
+
+        store.clear_branch(&a);
+        assert_eq!(store.approx_bytes(), store.branch_size_estimate(&b));
+        assert_eq!(store.kv_entry_count(), 1);
+    }
+
+    #[test]
+    fn test_evict_least_recently_used_kv() {
+        use crate::eviction::CacheLimits;
+        use strata_core::value::Value;
+
+        let store = ShardedStore::with_limits(CacheLimits::new().max_entries(4));
+        let branch_id = BranchId::new();
+        for (i, name) in ["a", "b", "c", "d"].iter().enumerate() {
+            store.put(
+                create_test_key(branch_id, name),
+                create_stored_value(Value::Int(i as i64), i as u64 + 1),
+            );
+        }
+        // Within budget: nothing to do
+        assert!(store.evict_to_limits().is_empty());
+
+        // Read "a" so "b" becomes the least recently used
+        Storage::get(&store, &create_test_key(branch_id, "a")).unwrap();
+        let state_key = Key::new_state(create_test_key(branch_id, "s").namespace, "s");
+        store.put(state_key.clone(), create_stored_value(Value::Int(0), 5));
+        store.put(
+            create_test_key(branch_id, "e"),
+            create_stored_value(Value::Int(4), 6),
+        );
+
+        // Over budget (5 > 4): drain to the low-water mark of 3 entries
+        let evicted: Vec<String> = store
+            .evict_to_limits()
+            .iter()
+            .map(|k| k.user_key_string().unwrap())
+            .collect();
+        assert_eq!(evicted, vec!["b", "c"]);
+        assert_eq!(store.kv_entry_count(), 3);
+        assert!(store.contains(&create_test_key(branch_id, "a")));
+        // Non-KV data is never evicted
+        assert!(store.contains(&state_key));
+    }
+
+    #[test]
+    fn test_evict_prefers_deleted_keys() {
+        use crate::eviction::CacheLimits;
+        use strata_core::value::Value;
+
+        let store = ShardedStore::with_limits(CacheLimits::new().max_entries(2));
+        let branch_id = BranchId::new();
+        let old = create_test_key(branch_id, "old");
+        let gone = create_test_key(branch_id, "gone");
+        store.put(old.clone(), create_stored_value(Value::Int(1), 1));
+        store.put(gone.clone(), create_stored_value(Value::Int(2), 2));
+        store.delete_with_version(&gone, 3).unwrap();
+        store.put(
+            create_test_key(branch_id, "new"),
+            create_stored_value(Value::Int(3), 4),
+        );
+
+        // Low-water mark for a budget of 2 is 1 entry: the deleted key goes
+        // first even though it was written after "old"
+        assert_eq!(store.evict_to_limits(), vec![gone, old]);
+        assert!(store.contains(&create_test_key(branch_id, "new")));
+    }
+
+    #[test]
+    fn test_evict_respects_snapshots() {
+        use crate::eviction::CacheLimits;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::with_limits(CacheLimits::new().max_entries(1)));
+        let branch_id = BranchId::new();
+        let seen = create_test_key(branch_id, "seen");
+        Storage::put_with_version(&*store, seen.clone(), Value::Int(1), 1, None).unwrap();
+
+        let snapshot = store.snapshot();
+        let clone = snapshot.clone();
+        assert_eq!(store.pinned_snapshot_count(), 2);
+
+        // Written after the snapshot, so invisible to it and evictable
+        let unseen = create_test_key(branch_id, "unseen");
+        Storage::put_with_version(&*store, unseen.clone(), Value::Int(2), 2, None).unwrap();
+        Storage::get(&*store, &unseen).unwrap();
+        let late = create_test_key(branch_id, "late");
+        Storage::put_with_version(&*store, late.clone(), Value::Int(3), 3, None).unwrap();
+
+        // "seen" is the least recently used but pinned by the snapshots
+        assert_eq!(store.evict_to_limits(), vec![unseen, late]);
+        assert!(clone.contains(&seen));
+        assert_eq!(store.kv_entry_count(), 1);
+
+        drop(snapshot);
+        drop(clone);
+        assert_eq!(store.pinned_snapshot_count(), 0);
+        let more = create_test_key(branch_id, "more");
+        Storage::put_with_version(&*store, more.clone(), Value::Int(4), 4, None).unwrap();
+        assert_eq!(store.evict_to_limits(), vec![seen, more]);
+    }
+
+    #[test]
+    fn test_unbounded_store_does_not_pin() {
+        let store = Arc::new(ShardedStore::new());
+        let _snapshot = store.snapshot();
+        assert_eq!(store.pinned_snapshot_count(), 0);
+        assert!(store.limits().is_none());
+        assert!(store.evict_to_limits().is_empty());
+    }
+
+    #[test]
+    fn test_pinned_snapshot_holds_back_gc() {
+        let store = Arc::new(ShardedStore::new());
+        let branch_id = BranchId::new();
+        let key = create_test_key(branch_id, "key");
+        Storage::put_with_version(&*store, key.clone(), Value::Int(1), 1, None).unwrap();
+
+        let snapshot = store.pinned_snapshot();
+        assert_eq!(store.pinned_snapshot_count(), 1);
+        let pinned = store.pinned_snapshots();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].version, snapshot.version());
+        Storage::put_with_version(&*store, key.clone(), Value::Int(2), 2, None).unwrap();
+        Storage::put_with_version(&*store, key.clone(), Value::Int(3), 3, None).unwrap();
+
+        assert_eq!(store.gc_branch(branch_id, 3), 0);
+        assert_eq!(
+            SnapshotView::get(&snapshot, &key).unwrap().unwrap().value,
+            Value::Int(1)
+        );
+
+        drop(snapshot);
+        assert_eq!(store.pinned_snapshot_count(), 0);
+        assert_eq!(store.gc_branch(branch_id, 3), 2);
+    }
+
+    #[test]
+    fn test_gc_branch_keep_last() {
+        let store = Arc::new(ShardedStore::new());
+        let branch_id = BranchId::new();
+        let busy = create_test_key(branch_id, "busy");
+        let quiet = create_test_key(branch_id, "quiet");
+        Storage::put_with_version(&*store, quiet.clone(), Value::Int(0), 1, None).unwrap();
+        for v in 2..=6 {
+            Storage::put_with_version(&*store, busy.clone(), Value::Int(v as i64), v, None)
+                .unwrap();
+        }
+
+        // A snapshot at version 3 keeps the value it reads
+        assert_eq!(store.gc_branch_keep_last(branch_id, 2, Some(3)), 1);
+        assert_eq!(
+            Storage::get_versioned(&*store, &busy, 3)
+                .unwrap()
+                .unwrap()
+                .value,
+            Value::Int(3)
+        );
+
+        assert_eq!(store.gc_branch_keep_last(branch_id, 2, None), 2);
+        let history = Storage::get_history(&*store, &busy, None, None).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].value, Value::Int(6));
+
+        // The latest version always survives
+        assert_eq!(store.gc_branch_keep_last(branch_id, 0, None), 1);
+        assert_eq!(
+            Storage::get_history(&*store, &quiet, None, None)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_tombstone_ttl_expires_deleted_keys() {
+        use strata_core::value::Value;
+        use strata_core::ManualClock;
+
+        let store = ShardedStore::new();
+        let clock = Arc::new(ManualClock::new(Timestamp::from_micros(1_000_000)));
+        store.set_clock(clock.clone());
+        let branch_id = BranchId::new();
+        let deleted = create_test_key(branch_id, "deleted");
+        let live = create_test_key(branch_id, "live");
+        store
+            .apply_batch(
+                &[
+                    (deleted.clone(), Value::Int(1)),
+                    (live.clone(), Value::Int(1)),
+                ],
+                &[],
+                1,
+            )
+            .unwrap();
+        store
+            .apply_batch(&[], std::slice::from_ref(&deleted), 2)
+            .unwrap();
+
+        // Default: the tombstone is kept forever
+        clock.advance(Duration::from_secs(3600));
+        assert_eq!(store.gc_branch(branch_id, 3), 1);
+        assert_eq!(store.branch_entry_count(&branch_id), 2);
+        assert_eq!(store.kv_entry_count(), 2);
+
+        store.set_tombstone_ttl(Some(Duration::from_secs(7200)));
+        assert_eq!(store.tombstone_ttl(), Some(Duration::from_secs(7200)));
+        assert_eq!(store.gc_branch(branch_id, 3), 0);
+        assert_eq!(store.kv_entry_count(), 2);
+
+        clock.advance(Duration::from_secs(3600));
+        let bytes = store.approx_bytes();
+        assert_eq!(store.gc_branch(branch_id, 3), 1);
+        assert_eq!(store.branch_entry_count(&branch_id), 1);
+        assert_eq!(store.kv_entry_count(), 1);
+        assert!(store.approx_bytes() < bytes);
+        assert!(store.get(&deleted).unwrap().is_none());
+        assert_eq!(store.get(&live).unwrap().unwrap().value, Value::Int(1));
+
+        // Forked branches keep their tombstones
+        let child = BranchId::new();
+        store.set_fork_base(
+            child,
+            ForkBase {
+                parent: branch_id,
+                version: 3,
+            },
+        );
+        let shadowed = create_test_key(child, "live");
+        store
+            .apply_batch(&[], std::slice::from_ref(&shadowed), 4)
+            .unwrap();
+        clock.advance(Duration::from_secs(7200));
+        assert_eq!(store.gc_branch(child, 5), 0);
+        assert!(store.get(&shadowed).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dedup_shares_large_values() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        store.set_dedup_threshold(Some(1024));
+        let a = BranchId::new();
+        let b = BranchId::new();
+        let doc = Value::String("x".repeat(4096));
+
+        // Same content under two keys, two branches and two versions
+        store.put(
+            create_test_key(a, "k1"),
+            create_stored_value(doc.clone(), 1),
+        );
+        store.put(
+            create_test_key(a, "k1"),
+            create_stored_value(doc.clone(), 2),
+        );
+        store.put(
+            create_test_key(a, "k2"),
+            create_stored_value(doc.clone(), 3),
+        );
+        store.put(
+            create_test_key(b, "k1"),
+            create_stored_value(doc.clone(), 4),
+        );
+        // Below the threshold: stored inline
+        store.put(
+            create_test_key(a, "small"),
+            create_stored_value(Value::Int(1), 5),
+        );
+        assert_eq!(store.shared_value_count(), 1);
+
+        for key in [create_test_key(a, "k2"), create_test_key(b, "k1")] {
+            assert_eq!(store.get(&key).unwrap().unwrap().value, doc);
+        }
+        let chain = store.shards.get(&a).unwrap();
+        let chain = chain.data.get(&create_test_key(a, "k1")).unwrap();
+        assert!(chain.latest().unwrap().is_shared());
+        assert_eq!(chain.get_at_version(1).unwrap().to_versioned().value, doc);
+    }
+
+    #[test]
+    fn test_dedup_releases_values_on_gc_and_clear() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        store.set_dedup_threshold(Some(1024));
+        let a = BranchId::new();
+        let b = BranchId::new();
+        let old = Value::Bytes(vec![1; 2048]);
+        let new = Value::Bytes(vec![2; 2048]);
+        let key = create_test_key(a, "k");
+
+        store.put(key.clone(), create_stored_value(old.clone(), 1));
+        store.put(key.clone(), create_stored_value(new.clone(), 2));
+        store.put(create_test_key(b, "k"), create_stored_value(new.clone(), 3));
+        assert_eq!(store.shared_value_count(), 2);
+
+        // GC drops the only version using `old`
+        assert_eq!(store.gc_branch(a, 2), 1);
+        assert_eq!(store.shared_value_count(), 1);
+
+        // `new` is still used by branch b after branch a is gone
+        assert!(store.clear_branch(&a));
+        assert_eq!(store.shared_value_count(), 1);
+        assert!(store.clear_branch(&b));
+        assert_eq!(store.shared_value_count(), 0);
+    }
+
+    #[test]
+    fn test_dedup_off_by_default() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        assert_eq!(store.dedup_threshold(), None);
+        let doc = Value::String("x".repeat(4096));
+        store.put(
+            create_test_key(BranchId::new(), "k"),
+            create_stored_value(doc, 1),
+        );
+        assert_eq!(store.shared_value_count(), 0);
+    }
+
     #[test]
     fn test_clear_branch_nonexistent() {
         let store = ShardedStore::new();
@@ -2492,19 +3630,19 @@ mod tests {
         let result = chain.get_at_version(3);
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 3);
-        assert_eq!(result.unwrap().versioned().value, Value::Int(300));
+        assert_eq!(result.unwrap().to_versioned().value, Value::Int(300));
 
         // Query at version 2 should return version 2
         let result = chain.get_at_version(2);
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 2);
-        assert_eq!(result.unwrap().versioned().value, Value::Int(200));
+        assert_eq!(result.unwrap().to_versioned().value, Value::Int(200));
 
         // Query at version 1 should return version 1
         let result = chain.get_at_version(1);
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 1);
-        assert_eq!(result.unwrap().versioned().value, Value::Int(100));
+        assert_eq!(result.unwrap().to_versioned().value, Value::Int(100));
 
         // Query at version 0 should return None
         let result = chain.get_at_version(0);
@@ -2559,7 +3697,7 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 1);
         assert_eq!(
-            result.unwrap().versioned().value,
+            result.unwrap().to_versioned().value,
             Value::String("v1".into())
         );
 
@@ -3371,4 +4509,160 @@ mod tests {
         let results_none = Storage::scan_prefix(&store, &prefix_none, u64::MAX).unwrap();
         assert_eq!(results_none.len(), 0, "gamma: prefix should match 0 keys");
     }
+
+    // ========================================================================
+    // Copy-on-write Fork Tests
+    // ========================================================================
+
+    fn cow_fork(store: &ShardedStore, parent: BranchId) -> BranchId {
+        let child = BranchId::new();
+        store.set_fork_base(
+            child,
+            ForkBase {
+                parent,
+                version: store.version(),
+            },
+        );
+        child
+    }
+
+    #[test]
+    fn test_cow_fork_reads_through_to_parent() {
+        use strata_core::traits::Storage;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let parent = BranchId::new();
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(1), None).unwrap();
+        Storage::put(&*store, create_test_key(parent, "b"), Value::Int(2), None).unwrap();
+
+        let child = cow_fork(&store, parent);
+
+        // Nothing copied, but reads resolve against the parent
+        assert!(!store.has_branch(&child));
+        let got = Storage::get(&*store, &create_test_key(child, "a")).unwrap();
+        assert_eq!(got.unwrap().value, Value::Int(1));
+        assert!(store.contains(&create_test_key(child, "b")));
+
+        let listed = store.list_branch(&child);
+        assert_eq!(listed.len(), 2);
+        assert!(listed.iter().all(|(k, _)| k.namespace.branch_id == child));
+    }
+
+    #[test]
+    fn test_cow_fork_writes_shadow_parent() {
+        use strata_core::traits::Storage;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let parent = BranchId::new();
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(1), None).unwrap();
+        Storage::put(&*store, create_test_key(parent, "b"), Value::Int(2), None).unwrap();
+        let child = cow_fork(&store, parent);
+
+        // Only the changed key is materialized in the child
+        Storage::put(&*store, create_test_key(child, "a"), Value::Int(10), None).unwrap();
+        ShardedStore::delete(&store, &create_test_key(child, "b"));
+        assert_eq!(store.branch_entry_count(&child), 2);
+
+        let a = Storage::get(&*store, &create_test_key(child, "a")).unwrap();
+        assert_eq!(a.unwrap().value, Value::Int(10));
+        assert!(Storage::get(&*store, &create_test_key(child, "b"))
+            .unwrap()
+            .is_none());
+        assert_eq!(store.list_branch(&child).len(), 1);
+
+        // Parent is untouched
+        let pa = Storage::get(&*store, &create_test_key(parent, "a")).unwrap();
+        assert_eq!(pa.unwrap().value, Value::Int(1));
+        assert_eq!(store.list_branch(&parent).len(), 2);
+    }
+
+    #[test]
+    fn test_cow_fork_isolated_from_later_parent_writes() {
+        use strata_core::traits::Storage;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let parent = BranchId::new();
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(1), None).unwrap();
+        let child = cow_fork(&store, parent);
+
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(2), None).unwrap();
+        Storage::put(&*store, create_test_key(parent, "new"), Value::Int(3), None).unwrap();
+
+        let a = Storage::get(&*store, &create_test_key(child, "a")).unwrap();
+        assert_eq!(a.unwrap().value, Value::Int(1));
+        assert!(!store.contains(&create_test_key(child, "new")));
+
+        let prefix = create_test_key(child, "");
+        let scanned = Storage::scan_prefix(&*store, &prefix, u64::MAX).unwrap();
+        assert_eq!(scanned.len(), 1);
+        let snap_scanned = store.snapshot().list_by_prefix(&prefix);
+        assert_eq!(snap_scanned.len(), 1);
+
+        // History continues into the parent up to the fork point
+        let history =
+            Storage::get_history(&*store, &create_test_key(child, "a"), None, None).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].value, Value::Int(1));
+    }
+
+    #[test]
+    fn test_cow_fork_time_travel_reads_inherit_parent() {
+        use strata_core::traits::Storage;
+        use strata_core::value::Value;
+        use strata_core::ManualClock;
+
+        let store = Arc::new(ShardedStore::new());
+        let clock = Arc::new(ManualClock::new(Timestamp::from_micros(1_000)));
+        store.set_clock(clock.clone());
+        let parent = BranchId::new();
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(1), None).unwrap();
+        clock.set(Timestamp::from_micros(2_000));
+        Storage::put(&*store, create_test_key(parent, "b"), Value::Int(2), None).unwrap();
+        let child = cow_fork(&store, parent);
+
+        clock.set(Timestamp::from_micros(3_000));
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(10), None).unwrap();
+        Storage::put(&*store, create_test_key(child, "b"), Value::Int(20), None).unwrap();
+
+        let a = store.get_at_timestamp(&create_test_key(child, "a"), 1_500);
+        assert_eq!(a.unwrap().unwrap().value, Value::Int(1));
+        // Parent writes after the fork stay invisible, even as of later times
+        let a = store.get_at_timestamp(&create_test_key(child, "a"), 3_500);
+        assert_eq!(a.unwrap().unwrap().value, Value::Int(1));
+        assert!(store
+            .get_at_timestamp(&create_test_key(child, "a"), 999)
+            .unwrap()
+            .is_none());
+
+        // The child's own write shadows the parent only from its timestamp on
+        let prefix = create_test_key(child, "");
+        let before = store.scan_prefix_at_timestamp(&prefix, 2_500).unwrap();
+        let values: Vec<_> = before.iter().map(|(_, vv)| vv.value.clone()).collect();
+        assert_eq!(values, vec![Value::Int(1), Value::Int(2)]);
+        assert!(before.iter().all(|(k, _)| k.namespace.branch_id == child));
+        let after = store.scan_prefix_at_timestamp(&prefix, 3_500).unwrap();
+        let values: Vec<_> = after.iter().map(|(_, vv)| vv.value.clone()).collect();
+        assert_eq!(values, vec![Value::Int(1), Value::Int(20)]);
+    }
+
+    #[test]
+    fn test_cow_fork_gc_keeps_base_version() {
+        use strata_core::traits::Storage;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let parent = BranchId::new();
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(1), None).unwrap();
+        let child = cow_fork(&store, parent);
+        Storage::put(&*store, create_test_key(parent, "a"), Value::Int(2), None).unwrap();
+
+        store.gc_branch(parent, store.version() + 1);
+
+        let a = Storage::get(&*store, &create_test_key(child, "a")).unwrap();
+        assert_eq!(a.unwrap().value, Value::Int(1));
+        assert_eq!(store.fork_children(&parent), vec![child]);
+    }
 }
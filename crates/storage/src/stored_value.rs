@@ -5,33 +5,55 @@
 //! `StoredValue` which combines a `VersionedValue` with optional TTL
 //! for the storage layer.
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use strata_core::{Timestamp, Value, Version, VersionedValue};
 
+use crate::value_pool::ValuePool;
+
 /// A stored value with optional TTL
 ///
 /// Wraps `VersionedValue` with TTL metadata for the storage layer.
 /// This separation keeps TTL as a storage concern, not part of the
 /// contract types.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct StoredValue {
-    /// The versioned value (value + version + timestamp)
-    inner: VersionedValue,
+    /// The value, held inline or shared through a [`ValuePool`]
+    payload: Payload,
+    /// Version of this entry
+    version: Version,
+    /// Write timestamp
+    timestamp: Timestamp,
     /// Optional time-to-live
     ttl: Option<Duration>,
     /// Whether this entry is a tombstone (explicit deletion marker)
     is_tombstone: bool,
 }
 
+/// Where a stored value's data lives
+#[derive(Debug, Clone)]
+enum Payload {
+    /// Owned by this version
+    Inline(Value),
+    /// Shared with other versions of identical content
+    Shared(Arc<Value>),
+}
+
+impl PartialEq for StoredValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+            && self.version == other.version
+            && self.timestamp == other.timestamp
+            && self.ttl == other.ttl
+            && self.is_tombstone == other.is_tombstone
+    }
+}
+
 impl StoredValue {
     /// Create a new stored value with TTL
     pub fn new(value: Value, version: Version, ttl: Option<Duration>) -> Self {
-        StoredValue {
-            inner: VersionedValue::new(value, version),
-            ttl,
-            is_tombstone: false,
-        }
+        Self::from_versioned_with_ttl(VersionedValue::new(value, version), ttl)
     }
 
     /// Create a stored value with explicit timestamp
@@ -41,26 +63,23 @@ impl StoredValue {
         timestamp: Timestamp,
         ttl: Option<Duration>,
     ) -> Self {
-        StoredValue {
-            inner: VersionedValue::with_timestamp(value, version, timestamp),
+        Self::from_versioned_with_ttl(
+            VersionedValue::with_timestamp(value, version, timestamp),
             ttl,
-            is_tombstone: false,
-        }
+        )
     }
 
     /// Create from a VersionedValue without TTL
     pub fn from_versioned(vv: VersionedValue) -> Self {
-        StoredValue {
-            inner: vv,
-            ttl: None,
-            is_tombstone: false,
-        }
+        Self::from_versioned_with_ttl(vv, None)
     }
 
     /// Create from a VersionedValue with TTL
     pub fn from_versioned_with_ttl(vv: VersionedValue, ttl: Option<Duration>) -> Self {
         StoredValue {
-            inner: vv,
+            payload: Payload::Inline(vv.value),
+            version: vv.version,
+            timestamp: vv.timestamp,
             ttl,
             is_tombstone: false,
         }
@@ -72,46 +91,84 @@ impl StoredValue {
     /// conflating `Value::Null` with deletion.
     pub fn tombstone(version: Version) -> Self {
         StoredValue {
-            inner: VersionedValue::new(Value::Null, version),
-            ttl: None,
             is_tombstone: true,
+            ..Self::new(Value::Null, version, None)
         }
     }
 
+    /// Create a tombstone entry with explicit timestamp
+    pub fn tombstone_at(version: Version, timestamp: Timestamp) -> Self {
+        StoredValue {
+            is_tombstone: true,
+            ..Self::with_timestamp(Value::Null, version, timestamp, None)
+        }
+    }
+
+    /// Move a large value into `pool`, sharing it with identical values
+    ///
+    /// Values below the pool's threshold, tombstones and values that are
+    /// already shared are returned unchanged.
+    pub fn share_in(self, pool: &ValuePool) -> Self {
+        match self.payload {
+            Payload::Inline(value)
+                if !self.is_tombstone && pool.accepts(value_heap_size(&value)) =>
+            {
+                StoredValue {
+                    payload: Payload::Shared(pool.intern(value)),
+                    ..self
+                }
+            }
+            payload => StoredValue { payload, ..self },
+        }
+    }
+
+    /// Whether the value is held in a [`ValuePool`] rather than inline
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        matches!(self.payload, Payload::Shared(_))
+    }
+
     /// Check whether this entry is a tombstone (explicit deletion marker)
     #[inline]
     pub fn is_tombstone(&self) -> bool {
         self.is_tombstone
     }
 
-    /// Get the inner VersionedValue
+    /// Copy out the contract view (value + version + timestamp)
     #[inline]
-    pub fn versioned(&self) -> &VersionedValue {
-        &self.inner
+    pub fn to_versioned(&self) -> VersionedValue {
+        VersionedValue::with_timestamp(self.value().clone(), self.version, self.timestamp)
     }
 
     /// Consume and return the inner VersionedValue
     #[inline]
     pub fn into_versioned(self) -> VersionedValue {
-        self.inner
+        let value = match self.payload {
+            Payload::Inline(value) => value,
+            Payload::Shared(shared) => Arc::try_unwrap(shared).unwrap_or_else(|s| (*s).clone()),
+        };
+        VersionedValue::with_timestamp(value, self.version, self.timestamp)
     }
 
     /// Get the value
     #[inline]
     pub fn value(&self) -> &Value {
-        &self.inner.value
+        match &self.payload {
+            Payload::Inline(value) => value,
+            Payload::Shared(shared) => shared,
+        }
     }
 
     /// Get the version
     #[inline]
     pub fn version(&self) -> Version {
-        self.inner.version
+        self.version
     }
 
     /// Get the timestamp
     #[inline]
     pub fn timestamp(&self) -> Timestamp {
-        self.inner.timestamp
+        self.timestamp
     }
 
     /// Get the TTL
@@ -124,7 +181,7 @@ impl StoredValue {
     pub fn is_expired(&self) -> bool {
         if let Some(ttl) = self.ttl {
             let now = Timestamp::now();
-            if let Some(age) = now.duration_since(self.inner.timestamp) {
+            if let Some(age) = now.duration_since(self.timestamp) {
                 return age >= ttl;
             }
         }
@@ -135,13 +192,39 @@ impl StoredValue {
     ///
     /// Returns `Some(timestamp)` when the value will expire, or `None` if no TTL.
     pub fn expiry_timestamp(&self) -> Option<Timestamp> {
-        self.ttl.map(|ttl| self.inner.timestamp.saturating_add(ttl))
+        self.ttl.map(|ttl| self.timestamp.saturating_add(ttl))
+    }
+
+    /// Approximate in-memory footprint in bytes (struct plus value heap data)
+    ///
+    /// A shared value is counted in full for every version that uses it, so
+    /// the estimate stays an upper bound and does not change when other
+    /// versions are dropped.
+    pub fn size_estimate(&self) -> usize {
+        std::mem::size_of::<StoredValue>() + value_heap_size(self.value())
+    }
+}
+
+/// Approximate heap bytes owned by a value (excluding the `Value` itself)
+pub(crate) fn value_heap_size(value: &Value) -> usize {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Int(_) | Value::Float(_) => 0,
+        Value::String(s) => s.capacity(),
+        Value::Bytes(b) => b.capacity(),
+        Value::Array(items) => items
+            .iter()
+            .map(|v| std::mem::size_of::<Value>() + value_heap_size(v))
+            .sum(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| k.capacity() + std::mem::size_of::<Value>() + value_heap_size(v))
+            .sum(),
     }
 }
 
 impl From<StoredValue> for VersionedValue {
     fn from(sv: StoredValue) -> Self {
-        sv.inner
+        sv.into_versioned()
     }
 }
 
@@ -225,4 +308,12 @@ mod tests {
         assert_eq!(sv.version(), Version::Sequence(10));
         assert!(sv.ttl().is_none());
     }
+
+    #[test]
+    fn test_size_estimate_grows_with_value() {
+        let small = StoredValue::new(Value::Int(1), Version::txn(1), None);
+        let large = StoredValue::new(Value::String("x".repeat(1024)), Version::txn(1), None);
+        assert_eq!(small.size_estimate(), std::mem::size_of::<StoredValue>());
+        assert!(large.size_estimate() >= small.size_estimate() + 1024);
+    }
 }
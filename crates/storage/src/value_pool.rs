@@ -0,0 +1,246 @@
+//! Content-addressed sharing of large values
+//!
+//! Every update appends a full copy of the value to the key's version
+//! chain, so a workload that keeps re-storing the same large document or
+//! blob holds one copy per version. With a dedup threshold set, values at
+//! least that many bytes are interned here: versions with identical content
+//! point at one shared allocation instead of each owning a copy.
+//!
+//! # Reference Counting
+//!
+//! Versions hold a strong `Arc` to their shared value and the pool only a
+//! weak one, so the `Arc` count is the reference count. When GC, eviction
+//! or branch deletion drops the last version that uses a value, the value is
+//! freed; [`ValuePool::prune`] then removes the dead pool entries.
+//!
+//! # Content Identity
+//!
+//! Values are matched by a 64-bit content hash and then compared in full,
+//! so a hash collision never shares different values. The comparison is
+//! stricter than `Value`'s `PartialEq`: floats match by bit pattern, so
+//! `0.0` and `-0.0` are never merged and a `NaN` can share with itself.
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use rustc_hash::FxHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use strata_core::Value;
+
+/// Pool of shared large values, keyed by content hash
+#[derive(Debug, Default)]
+pub struct ValuePool {
+    /// Minimum heap size in bytes for a value to be shared; 0 disables sharing
+    threshold: AtomicUsize,
+    /// Weak references to shared values by content hash
+    entries: DashMap<u64, Weak<Value>>,
+}
+
+impl ValuePool {
+    /// Create a pool with sharing disabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Share values whose heap size is at least `bytes`, or stop sharing
+    /// with `None`
+    ///
+    /// Only affects values written afterwards; existing versions keep their
+    /// current representation.
+    pub fn set_threshold(&self, bytes: Option<usize>) {
+        // 0 is reserved for "off", so a zero threshold is stored as 1
+        let stored = match bytes {
+            Some(bytes) => bytes.max(1),
+            None => 0,
+        };
+        self.threshold.store(stored, Ordering::Relaxed);
+    }
+
+    /// Current sharing threshold in bytes, `None` if sharing is off
+    pub fn threshold(&self) -> Option<usize> {
+        match self.threshold.load(Ordering::Relaxed) {
+            0 => None,
+            bytes => Some(bytes),
+        }
+    }
+
+    /// Whether a value with `heap_bytes` of heap data should be shared
+    #[inline]
+    pub fn accepts(&self, heap_bytes: usize) -> bool {
+        self.threshold()
+            .is_some_and(|threshold| heap_bytes >= threshold)
+    }
+
+    /// Return the shared copy of `value`, adding it to the pool if new
+    ///
+    /// If a different value already holds the same content hash, `value`
+    /// is returned in its own allocation and not pooled.
+    pub fn intern(&self, value: Value) -> Arc<Value> {
+        match self.entries.entry(content_hash(&value)) {
+            Entry::Occupied(mut entry) => match entry.get().upgrade() {
+                Some(shared) if same_content(&shared, &value) => shared,
+                Some(_) => Arc::new(value),
+                None => {
+                    let shared = Arc::new(value);
+                    entry.insert(Arc::downgrade(&shared));
+                    shared
+                }
+            },
+            Entry::Vacant(entry) => {
+                let shared = Arc::new(value);
+                entry.insert(Arc::downgrade(&shared));
+                shared
+            }
+        }
+    }
+
+    /// Drop entries whose value is no longer used by any version
+    ///
+    /// Returns the number of entries removed.
+    pub fn prune(&self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, value| value.strong_count() > 0);
+        before.saturating_sub(self.entries.len())
+    }
+
+    /// Number of distinct values currently shared
+    pub fn len(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.value().strong_count() > 0)
+            .count()
+    }
+
+    /// Whether no values are currently shared
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 64-bit hash of a value's content
+///
+/// Object fields are combined order-independently, so equal objects hash
+/// alike whatever their map iteration order.
+fn content_hash(value: &Value) -> u64 {
+    let mut hasher = FxHasher::default();
+    hash_value(value, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_value(value: &Value, hasher: &mut FxHasher) {
+    match value {
+        Value::Null => 0u8.hash(hasher),
+        Value::Bool(b) => (1u8, b).hash(hasher),
+        Value::Int(i) => (2u8, i).hash(hasher),
+        Value::Float(f) => (3u8, f.to_bits()).hash(hasher),
+        Value::String(s) => (4u8, s).hash(hasher),
+        Value::Bytes(b) => (5u8, b).hash(hasher),
+        Value::Array(items) => {
+            (6u8, items.len()).hash(hasher);
+            for item in items {
+                hash_value(item, hasher);
+            }
+        }
+        Value::Object(map) => {
+            let fields = map.iter().fold(0u64, |acc, (k, v)| {
+                let mut field = FxHasher::default();
+                k.hash(&mut field);
+                hash_value(v, &mut field);
+                acc.wrapping_add(field.finish())
+            });
+            (7u8, map.len(), fields).hash(hasher);
+        }
+    }
+}
+
+/// Exact content equality; floats compare by bit pattern
+fn same_content(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_content(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, v)| b.get(k).is_some_and(|other| same_content(v, other)))
+        }
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> Value {
+        Value::String(s.repeat(1024))
+    }
+
+    #[test]
+    fn test_threshold() {
+        let pool = ValuePool::new();
+        assert_eq!(pool.threshold(), None);
+        assert!(!pool.accepts(usize::MAX));
+
+        pool.set_threshold(Some(100));
+        assert!(pool.accepts(100));
+        assert!(!pool.accepts(99));
+
+        // Zero still means "share everything", not "off"
+        pool.set_threshold(Some(0));
+        assert_eq!(pool.threshold(), Some(1));
+        pool.set_threshold(None);
+        assert_eq!(pool.threshold(), None);
+    }
+
+    #[test]
+    fn test_intern_shares_equal_content() {
+        let pool = ValuePool::new();
+        let a = pool.intern(big("a"));
+        let b = pool.intern(big("a"));
+        let c = pool.intern(big("c"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn test_object_hash_ignores_field_order() {
+        let pool = ValuePool::new();
+        let fields: Vec<(String, Value)> =
+            (0..32).map(|i| (format!("f{i}"), Value::Int(i))).collect();
+        let a = pool.intern(Value::Object(fields.iter().cloned().collect()));
+        let b = pool.intern(Value::Object(fields.into_iter().rev().collect()));
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_floats_match_by_bits() {
+        let pool = ValuePool::new();
+        let zero = pool.intern(Value::Array(vec![Value::Float(0.0)]));
+        let neg_zero = pool.intern(Value::Array(vec![Value::Float(-0.0)]));
+        assert!(!Arc::ptr_eq(&zero, &neg_zero));
+        assert_eq!(*neg_zero, Value::Array(vec![Value::Float(-0.0)]));
+        assert!(same_content(
+            &Value::Float(f64::NAN),
+            &Value::Float(f64::NAN)
+        ));
+    }
+
+    #[test]
+    fn test_prune_drops_unused_values() {
+        let pool = ValuePool::new();
+        let kept = pool.intern(big("kept"));
+        drop(pool.intern(big("dropped")));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.prune(), 1);
+        assert_eq!(pool.prune(), 0);
+
+        // A value re-interned after its last user went away is stored afresh
+        let again = pool.intern(big("dropped"));
+        assert_eq!(pool.len(), 2);
+        assert!(!Arc::ptr_eq(&kept, &again));
+    }
+}
@@ -1095,6 +1095,9 @@ fn error_recovery() {
         filter: None,
         metric: None,
         as_of: None,
+        overfetch_factor: None,
+        include_metadata: None,
+        include_vector: false,
     });
     assert!(result.is_err());
 
@@ -5,7 +5,10 @@
 
 use crate::common::*;
 use strata_core::Value;
-use strata_executor::{BranchId, Command, DistanceMetric, Output};
+use strata_executor::{
+    BranchId, Command, DistanceMetric, Error, FilterOp, MetadataFieldType, MetadataFilter, Output,
+    UpsertMode,
+};
 
 // ============================================================================
 // Database Commands
@@ -171,19 +174,37 @@ fn kv_delete_returns_bool() {
 // ============================================================================
 
 #[test]
-fn event_append_returns_version() {
+fn event_append_returns_appended_event() {
     let executor = create_executor();
 
-    let output = executor
-        .execute(Command::EventAppend {
-            branch: None,
-            space: None,
-            event_type: "test_stream".into(),
-            payload: event_payload("data", Value::String("event1".into())),
-        })
-        .unwrap();
+    let append = |data: &str| {
+        executor
+            .execute(Command::EventAppend {
+                branch: None,
+                space: None,
+                event_type: "test_stream".into(),
+                payload: event_payload("data", Value::String(data.into())),
+            })
+            .unwrap()
+    };
 
-    assert!(matches!(output, Output::Version(_)));
+    let (first_seq, first_hash) = match append("event1") {
+        Output::EventAppended { sequence, hash, .. } => (sequence, hash),
+        other => panic!("Expected EventAppended, got {:?}", other),
+    };
+    match append("event2") {
+        Output::EventAppended {
+            sequence,
+            prev_hash,
+            hash,
+            ..
+        } => {
+            assert_eq!(sequence, first_seq + 1);
+            assert_eq!(prev_hash, first_hash);
+            assert_ne!(hash, first_hash);
+        }
+        other => panic!("Expected EventAppended, got {:?}", other),
+    }
 }
 
 #[test]
@@ -267,6 +288,7 @@ fn vector_create_collection_and_upsert() {
             collection: "embeddings".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            metadata_schema: None,
         })
         .unwrap();
 
@@ -281,10 +303,46 @@ fn vector_create_collection_and_upsert() {
             key: "v1".into(),
             vector: vec![1.0, 0.0, 0.0, 0.0],
             metadata: None,
+            mode: None,
         })
         .unwrap();
 
-    assert!(matches!(output, Output::Version(_)));
+    assert!(matches!(output, Output::VectorUpserted(r) if r.created));
+}
+
+#[test]
+fn vector_upsert_mode_controls_duplicate_keys() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "embeddings".into(),
+            dimension: 4,
+            metric: DistanceMetric::Cosine,
+            metadata_schema: None,
+        })
+        .unwrap();
+    let upsert = |vector: Vec<f32>, mode| {
+        executor.execute(Command::VectorUpsert {
+            branch: None,
+            space: None,
+            collection: "embeddings".into(),
+            key: "v1".into(),
+            vector,
+            metadata: None,
+            mode,
+        })
+    };
+
+    upsert(vec![1.0, 0.0, 0.0, 0.0], Some(UpsertMode::Error)).unwrap();
+
+    let result = upsert(vec![0.0, 1.0, 0.0, 0.0], Some(UpsertMode::Error));
+    assert!(matches!(result, Err(Error::ConstraintViolation { .. })));
+
+    let output = upsert(vec![0.0, 1.0, 0.0, 0.0], None).unwrap();
+    assert!(matches!(output, Output::VectorUpserted(r) if !r.created));
 }
 
 #[test]
@@ -298,6 +356,7 @@ fn vector_search_returns_matches() {
             collection: "search_test".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            metadata_schema: None,
         })
         .unwrap();
 
@@ -309,6 +368,7 @@ fn vector_search_returns_matches() {
             key: "v1".into(),
             vector: vec![1.0, 0.0, 0.0, 0.0],
             metadata: None,
+            mode: None,
         })
         .unwrap();
 
@@ -320,6 +380,7 @@ fn vector_search_returns_matches() {
             key: "v2".into(),
             vector: vec![0.0, 1.0, 0.0, 0.0],
             metadata: None,
+            mode: None,
         })
         .unwrap();
 
@@ -333,6 +394,9 @@ fn vector_search_returns_matches() {
             filter: None,
             metric: None,
             as_of: None,
+            overfetch_factor: None,
+            include_metadata: None,
+            include_vector: false,
         })
         .unwrap();
 
@@ -345,6 +409,142 @@ fn vector_search_returns_matches() {
     }
 }
 
+#[test]
+fn vector_search_checks_filter_against_metadata_schema() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "typed".into(),
+            dimension: 2,
+            metric: DistanceMetric::Cosine,
+            metadata_schema: Some(
+                [("category".to_string(), MetadataFieldType::String)]
+                    .into_iter()
+                    .collect(),
+            ),
+        })
+        .unwrap();
+    let search = |field: &str| {
+        executor.execute(Command::VectorSearch {
+            branch: None,
+            space: None,
+            collection: "typed".into(),
+            query: vec![1.0, 0.0],
+            k: 10,
+            filter: Some(vec![MetadataFilter {
+                field: field.into(),
+                op: FilterOp::Eq,
+                value: Value::String("doc".into()),
+            }]),
+            metric: None,
+            as_of: None,
+            overfetch_factor: None,
+            include_metadata: None,
+            include_vector: false,
+        })
+    };
+
+    assert!(matches!(search("category"), Ok(Output::VectorMatches(_))));
+    match search("categroy") {
+        Err(Error::UnknownFilterField { collection, field }) => {
+            assert_eq!(collection, "typed");
+            assert_eq!(field, "categroy");
+        }
+        other => panic!("Expected UnknownFilterField, got {:?}", other),
+    }
+}
+
+#[test]
+fn vector_search_filters_by_range_and_set_membership() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "scored".into(),
+            dimension: 2,
+            metric: DistanceMetric::Euclidean,
+            metadata_schema: None,
+        })
+        .unwrap();
+    for (i, (key, score, tag)) in [("a", 0.9, "x"), ("b", 0.7, "y"), ("c", 0.95, "z")]
+        .into_iter()
+        .enumerate()
+    {
+        executor
+            .execute(Command::VectorUpsert {
+                branch: None,
+                space: None,
+                collection: "scored".into(),
+                key: key.into(),
+                vector: vec![i as f32, 0.0],
+                metadata: Some(Value::Object(
+                    [
+                        ("score".to_string(), Value::Float(score)),
+                        ("tag".to_string(), Value::String(tag.into())),
+                    ]
+                    .into_iter()
+                    .collect(),
+                )),
+                mode: None,
+            })
+            .unwrap();
+    }
+    let search = |filter: Vec<MetadataFilter>| -> Vec<String> {
+        match executor
+            .execute(Command::VectorSearch {
+                branch: None,
+                space: None,
+                collection: "scored".into(),
+                query: vec![0.0, 0.0],
+                k: 10,
+                filter: Some(filter),
+                metric: None,
+                as_of: None,
+                overfetch_factor: None,
+                include_metadata: None,
+                include_vector: false,
+            })
+            .unwrap()
+        {
+            Output::VectorMatches(matches) => matches.into_iter().map(|m| m.key).collect(),
+            other => panic!("Expected VectorMatches, got {:?}", other),
+        }
+    };
+    let tags =
+        |tags: &[&str]| Value::Array(tags.iter().map(|t| Value::String(t.to_string())).collect());
+
+    let high = MetadataFilter {
+        field: "score".into(),
+        op: FilterOp::Gt,
+        value: Value::Float(0.8),
+    };
+    assert_eq!(search(vec![high.clone()]), ["a", "c"]);
+    assert_eq!(
+        search(vec![MetadataFilter {
+            field: "tag".into(),
+            op: FilterOp::In,
+            value: tags(&["x", "y"]),
+        }]),
+        ["a", "b"]
+    );
+    assert_eq!(
+        search(vec![
+            high,
+            MetadataFilter {
+                field: "tag".into(),
+                op: FilterOp::NotIn,
+                value: tags(&["x", "y"]),
+            },
+        ]),
+        ["c"]
+    );
+}
+
 #[test]
 fn vector_list_collections() {
     let executor = create_executor();
@@ -356,6 +556,7 @@ fn vector_list_collections() {
             collection: "coll_a".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            metadata_schema: None,
         })
         .unwrap();
 
@@ -366,6 +567,7 @@ fn vector_list_collections() {
             collection: "coll_b".into(),
             dimension: 8,
             metric: DistanceMetric::Euclidean,
+            metadata_schema: None,
         })
         .unwrap();
 
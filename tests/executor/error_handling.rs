@@ -4,7 +4,7 @@
 
 use crate::common::*;
 use strata_core::Value;
-use strata_executor::{BranchId, Command, DistanceMetric, Error, Output};
+use strata_executor::{BranchId, Command, DistanceMetric, Error, MergeStrategy, Output};
 
 // ============================================================================
 // Vector Errors
@@ -21,6 +21,7 @@ fn vector_upsert_to_nonexistent_collection_behavior() {
         key: "v1".into(),
         vector: vec![1.0, 0.0, 0.0, 0.0],
         metadata: None,
+        mode: None,
     });
 
     // Vector auto-create was removed (#923) - upsert to nonexistent collection
@@ -51,6 +52,9 @@ fn vector_search_in_nonexistent_collection_fails() {
         filter: None,
         metric: None,
         as_of: None,
+        overfetch_factor: None,
+        include_metadata: None,
+        include_vector: false,
     });
 
     match result {
@@ -65,6 +69,72 @@ fn vector_search_in_nonexistent_collection_fails() {
     }
 }
 
+#[test]
+fn vector_search_in_nonexistent_collection_fails_for_k_zero_and_as_of() {
+    let executor = create_executor();
+
+    for (k, as_of) in [(0, None), (10, Some(u64::MAX))] {
+        let result = executor.execute(Command::VectorSearch {
+            branch: None,
+            space: None,
+            collection: "nonexistent".into(),
+            query: vec![1.0, 0.0, 0.0, 0.0],
+            k,
+            filter: None,
+            metric: None,
+            as_of,
+            overfetch_factor: None,
+            include_metadata: None,
+            include_vector: false,
+        });
+        assert!(
+            matches!(result, Err(Error::CollectionNotFound { .. })),
+            "Expected CollectionNotFound for k={} as_of={:?}, got {:?}",
+            k,
+            as_of,
+            result
+        );
+    }
+}
+
+#[test]
+fn vector_search_in_empty_collection_returns_no_matches() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "empty".into(),
+            dimension: 4,
+            metric: DistanceMetric::Cosine,
+            metadata_schema: None,
+        })
+        .unwrap();
+
+    for as_of in [None, Some(u64::MAX)] {
+        let result = executor
+            .execute(Command::VectorSearch {
+                branch: None,
+                space: None,
+                collection: "empty".into(),
+                query: vec![1.0, 0.0, 0.0, 0.0],
+                k: 10,
+                filter: None,
+                metric: None,
+                as_of,
+                overfetch_factor: None,
+                include_metadata: Some(true),
+                include_vector: true,
+            })
+            .unwrap();
+        match result {
+            Output::VectorMatches(matches) => assert!(matches.is_empty()),
+            other => panic!("Expected VectorMatches, got {:?}", other),
+        }
+    }
+}
+
 #[test]
 fn vector_wrong_dimension_fails() {
     let executor = create_executor();
@@ -76,6 +146,7 @@ fn vector_wrong_dimension_fails() {
             collection: "dim4".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            metadata_schema: None,
         })
         .unwrap();
 
@@ -87,6 +158,7 @@ fn vector_wrong_dimension_fails() {
         key: "v1".into(),
         vector: vec![1.0, 0.0], // Only 2 dimensions
         metadata: None,
+        mode: None,
     });
 
     match result {
@@ -174,6 +246,39 @@ fn branch_duplicate_id_fails() {
     }
 }
 
+#[test]
+fn branch_ops_on_missing_branch_return_branch_not_found() {
+    let mut db = create_strata();
+    db.create_branch("real").unwrap();
+
+    let not_found = |result: Result<(), Error>| match result {
+        Err(Error::BranchNotFound { branch }) => assert_eq!(branch, "ghost"),
+        other => panic!("Expected BranchNotFound for 'ghost', got {:?}", other),
+    };
+
+    not_found(db.set_branch("ghost"));
+    not_found(db.branches().fork("ghost", "copy").map(drop));
+    not_found(db.branches().fork_cow("ghost", "copy").map(drop));
+    not_found(db.diff_branches("real", "ghost").map(drop));
+    not_found(db.diff_branches("ghost", "real").map(drop));
+    not_found(
+        db.merge_branches("ghost", "real", MergeStrategy::LastWriterWins)
+            .map(drop),
+    );
+    not_found(
+        db.merge_branches("real", "ghost", MergeStrategy::LastWriterWins)
+            .map(drop),
+    );
+    not_found(db.delete_branch("ghost").map(drop));
+    assert!(!db.branches().exists("copy").unwrap());
+}
+
+#[test]
+fn kv_get_missing_key_is_none_not_error() {
+    let db = create_strata();
+    assert_eq!(db.kv_get("missing").unwrap(), None);
+}
+
 // ============================================================================
 // Transaction Errors
 // ============================================================================
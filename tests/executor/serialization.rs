@@ -85,6 +85,9 @@ fn vector_search_roundtrip() {
         filter: None,
         metric: Some(DistanceMetric::Cosine),
         as_of: None,
+        overfetch_factor: None,
+        include_metadata: None,
+        include_vector: false,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
@@ -446,6 +449,7 @@ fn distance_metric_roundtrip() {
         DistanceMetric::Cosine,
         DistanceMetric::Euclidean,
         DistanceMetric::DotProduct,
+        DistanceMetric::Manhattan,
     ];
 
     for metric in metrics {
@@ -455,6 +459,7 @@ fn distance_metric_roundtrip() {
             collection: "test".into(),
             dimension: 4,
             metric,
+            metadata_schema: None,
         };
 
         let json = serde_json::to_string(&cmd).unwrap();
@@ -118,6 +118,42 @@ fn test_r2_dotproduct_higher_is_more_similar() {
     assert_eq!(results[2].key, "negative", "R2 VIOLATED: Negative vector not last");
 }
 
+/// Test Manhattan: higher score = closer L1 distance
+#[test]
+fn test_r2_manhattan_higher_is_closer() {
+    let test_db = TestDb::new();
+    let vector = test_db.vector();
+
+    vector
+        .create_collection(test_db.branch_id, "embeddings", config_custom(3, DistanceMetric::Manhattan))
+        .unwrap();
+
+    let query = vec![0.0, 0.0, 0.0];
+    let axis = vec![3.0, 0.0, 0.0]; // L1 3, L2 3
+    let diagonal = vec![2.0, 2.0, 0.0]; // L1 4, L2 ~2.83
+    let far = vec![5.0, 5.0, 5.0]; // L1 15
+
+    vector
+        .insert(test_db.branch_id, "embeddings", "axis", &axis, None)
+        .unwrap();
+    vector
+        .insert(test_db.branch_id, "embeddings", "diagonal", &diagonal, None)
+        .unwrap();
+    vector
+        .insert(test_db.branch_id, "embeddings", "far", &far, None)
+        .unwrap();
+
+    let results = vector
+        .search(test_db.branch_id, "embeddings", &query, 3, None)
+        .unwrap();
+
+    // Ranked by L1, not L2: axis (1 / (1 + 3) = 0.25) beats diagonal (0.2)
+    assert_eq!(results[0].key, "axis", "R2 VIOLATED: L1-closest vector not first");
+    assert!((results[0].score - 0.25).abs() < 1e-6);
+    assert_eq!(results[1].key, "diagonal");
+    assert_eq!(results[2].key, "far", "R2 VIOLATED: Farthest vector not last");
+}
+
 /// Test score normalization consistency
 #[test]
 fn test_r2_score_normalization_consistency() {